@@ -0,0 +1,382 @@
+use chrono::NaiveDate;
+use serde::Serialize;
+
+use crate::calendar::{ics, DisplayTimezone, Event, EventStatus};
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum AgendaFormat {
+    #[default]
+    Text,
+    Plain,
+    Markdown,
+    Json,
+    Ics,
+    /// One tab-separated line per event with fixed columns, for grep/awk.
+    Tsv,
+}
+
+impl AgendaFormat {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.trim().to_lowercase().as_str() {
+            "text" => Some(Self::Text),
+            "plain" => Some(Self::Plain),
+            "markdown" | "md" => Some(Self::Markdown),
+            "json" => Some(Self::Json),
+            "ics" | "ical" => Some(Self::Ics),
+            "tsv" => Some(Self::Tsv),
+            _ => None,
+        }
+    }
+
+    /// Only the human-oriented text agenda is sent through the pager.
+    pub fn uses_pager(&self) -> bool {
+        *self == Self::Text
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct JsonAgenda<'a> {
+    start_date: String,
+    end_date: String,
+    timezone: Option<String>,
+    events: Vec<JsonAgendaEvent<'a>>,
+}
+
+#[derive(Debug, Serialize)]
+struct JsonAgendaEvent<'a> {
+    id: &'a str,
+    calendar_id: &'a str,
+    title: &'a str,
+    start: String,
+    end: String,
+    local_start: String,
+    local_end: String,
+    all_day: bool,
+    status: &'static str,
+    location: Option<&'a str>,
+    description: Option<&'a str>,
+    meeting_url: Option<&'a str>,
+    html_link: Option<&'a str>,
+}
+
+pub fn format_agenda(
+    format: AgendaFormat,
+    start: NaiveDate,
+    end: NaiveDate,
+    events: &[Event],
+    tz: &DisplayTimezone,
+) -> String {
+    match format {
+        AgendaFormat::Text => format_agenda_text(start, end, events, tz),
+        AgendaFormat::Plain => format_agenda_plain(start, end, events, tz),
+        AgendaFormat::Markdown => format_agenda_markdown(start, end, events, tz),
+        AgendaFormat::Json => format_agenda_json(start, end, events, tz),
+        AgendaFormat::Ics => ics::to_ics(events),
+        AgendaFormat::Tsv => format_agenda_tsv(events, tz),
+    }
+}
+
+fn agenda_title(start: NaiveDate, end: NaiveDate) -> String {
+    if start == end {
+        format!("Agenda – {}", start.format("%A, %B %d, %Y"))
+    } else {
+        format!("Agenda – {} to {}", start.format("%a, %b %d"), end.format("%a, %b %d, %Y"))
+    }
+}
+
+fn events_by_day<'a>(
+    start: NaiveDate,
+    end: NaiveDate,
+    events: &'a [Event],
+    tz: &DisplayTimezone,
+) -> Vec<(NaiveDate, Vec<&'a Event>)> {
+    start.iter_days()
+        .take_while(|day| *day <= end)
+        .map(|day| (day, events.iter().filter(|e| e.local_start(tz).date() == day).collect::<Vec<_>>()))
+        .filter(|(_, day_events)| !day_events.is_empty())
+        .collect()
+}
+
+fn format_agenda_text(start: NaiveDate, end: NaiveDate, events: &[Event], tz: &DisplayTimezone) -> String {
+    let mut lines = Vec::new();
+    lines.push(agenda_title(start, end));
+    lines.push(String::new());
+
+    if events.is_empty() {
+        lines.push("No events scheduled.".to_string());
+    } else if start == end {
+        for event in events {
+            lines.push(format!("- {}", build_agenda_line(event, tz, usize::MAX)));
+        }
+    } else {
+        for (day, day_events) in events_by_day(start, end, events, tz) {
+            lines.push(day.format("%A, %B %d").to_string());
+            for event in day_events {
+                lines.push(format!("- {}", build_agenda_line(event, tz, usize::MAX)));
+            }
+            lines.push(String::new());
+        }
+    }
+
+    lines.join("\n").trim_end().to_string() + "\n"
+}
+
+fn format_agenda_plain(start: NaiveDate, end: NaiveDate, events: &[Event], tz: &DisplayTimezone) -> String {
+    events.iter()
+        .map(|event| {
+            let line = build_agenda_line(event, tz, usize::MAX);
+            if start == end {
+                format!("{}\n", line)
+            } else {
+                format!("{} {}\n", event.local_start(tz).format("%Y-%m-%d"), line)
+            }
+        })
+        .collect()
+}
+
+/// `date  start  end  calendar  title  location`, whatever the range.
+/// All-day events have `all-day` as start and end.
+fn format_agenda_tsv(events: &[Event], tz: &DisplayTimezone) -> String {
+    events.iter()
+        .map(|event| {
+            let (start, end) = if event.all_day {
+                ("all-day".to_string(), "all-day".to_string())
+            } else {
+                (event.local_start(tz).format("%H:%M").to_string(), event.local_end(tz).format("%H:%M").to_string())
+            };
+            format!(
+                "{}\t{}\t{}\t{}\t{}\t{}\n",
+                event.local_start(tz).format("%Y-%m-%d"),
+                start,
+                end,
+                tsv_field(&event.calendar_id),
+                tsv_field(&event.title),
+                tsv_field(event.location.as_deref().unwrap_or_default()),
+            )
+        })
+        .collect()
+}
+
+/// Tabs and line breaks would shift the columns.
+fn tsv_field(value: &str) -> String {
+    value.replace(['\t', '\r', '\n'], " ")
+}
+
+fn format_agenda_markdown(start: NaiveDate, end: NaiveDate, events: &[Event], tz: &DisplayTimezone) -> String {
+    let mut lines = vec![format!("# {}", agenda_title(start, end)), String::new()];
+
+    if events.is_empty() {
+        lines.push("_No events scheduled._".to_string());
+    }
+
+    for (day, day_events) in events_by_day(start, end, events, tz) {
+        if start != end {
+            lines.push(format!("## {}", day.format("%A, %B %d")));
+            lines.push(String::new());
+        }
+        for event in day_events {
+            lines.push(markdown_line(event, tz));
+        }
+        if start != end {
+            lines.push(String::new());
+        }
+    }
+
+    lines.join("\n").trim_end().to_string() + "\n"
+}
+
+fn markdown_line(event: &Event, tz: &DisplayTimezone) -> String {
+    let mut line = format!("- **{}** {}", time_label(event, tz), event.title);
+    if let Some(location) = event.location.as_deref().filter(|l| !l.is_empty()) {
+        line.push_str(&format!(" _@ {}_", location));
+    }
+    if let Some(url) = event.meeting_url() {
+        line.push_str(&format!(" ([join]({}))", url));
+    }
+    line
+}
+
+fn format_agenda_json(start: NaiveDate, end: NaiveDate, events: &[Event], tz: &DisplayTimezone) -> String {
+    let agenda = JsonAgenda {
+        start_date: start.format("%Y-%m-%d").to_string(),
+        end_date: end.format("%Y-%m-%d").to_string(),
+        timezone: tz.name(),
+        events: events.iter().map(|event| JsonAgendaEvent {
+            id: &event.id,
+            calendar_id: &event.calendar_id,
+            title: &event.title,
+            start: event.start.to_rfc3339(),
+            end: event.end.to_rfc3339(),
+            local_start: event.local_start(tz).format("%Y-%m-%dT%H:%M:%S").to_string(),
+            local_end: event.local_end(tz).format("%Y-%m-%dT%H:%M:%S").to_string(),
+            all_day: event.all_day,
+            status: match event.status {
+                EventStatus::Confirmed => "confirmed",
+                EventStatus::Tentative => "tentative",
+                EventStatus::Cancelled => "cancelled",
+            },
+            location: event.location.as_deref(),
+            description: event.description.as_deref(),
+            meeting_url: event.meeting_url(),
+            html_link: event.html_link.as_deref(),
+        }).collect(),
+    };
+
+    serde_json::to_string_pretty(&agenda).unwrap_or_else(|_| "{}".to_string()) + "\n"
+}
+
+fn time_label(event: &Event, tz: &DisplayTimezone) -> String {
+    if event.all_day {
+        "All Day".to_string()
+    } else {
+        format!(
+            "{}-{}",
+            event.local_start(tz).format("%H:%M"),
+            event.local_end(tz).format("%H:%M")
+        )
+    }
+}
+
+pub fn build_agenda_line(event: &Event, tz: &DisplayTimezone, width: usize) -> String {
+    let mut line = format!("{:<13} {}", time_label(event, tz), event.title);
+    if let Some(location) = &event.location
+        && !location.is_empty()
+    {
+        line.push_str(&format!(" @ {}", location));
+    }
+    truncate_to_width(&line, width)
+}
+
+/// The event a launcher such as rofi or wofi handed back from `--dmenu`.
+pub fn find_by_agenda_line<'a>(events: &'a [Event], line: &str, tz: &DisplayTimezone) -> Option<&'a Event> {
+    let line = line.trim_end_matches(['\r', '\n']);
+    events.iter().find(|event| build_agenda_line(event, tz, usize::MAX) == line)
+}
+
+fn truncate_to_width(line: &str, width: usize) -> String {
+    if width > 0 && line.len() > width {
+        let mut truncated = line.chars().take(width.saturating_sub(1)).collect::<String>();
+        truncated.push('…');
+        truncated
+    } else {
+        line.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::EventBuilder;
+
+    use chrono::{TimeZone, Utc};
+
+    fn event(id: &str, hour: u32) -> Event {
+        let start = Utc.with_ymd_and_hms(2025, 1, 15, hour, 0, 0).unwrap();
+        EventBuilder::new(id).location("Room 1").at(start, chrono::Duration::minutes(30)).build()
+    }
+
+    fn tz() -> DisplayTimezone {
+        DisplayTimezone::Named(chrono_tz::UTC)
+    }
+
+    fn date() -> NaiveDate {
+        NaiveDate::from_ymd_opt(2025, 1, 15).unwrap()
+    }
+
+    #[test]
+    fn parses_format_names() {
+        assert_eq!(AgendaFormat::from_name("JSON"), Some(AgendaFormat::Json));
+        assert_eq!(AgendaFormat::from_name("md"), Some(AgendaFormat::Markdown));
+        assert_eq!(AgendaFormat::from_name("yaml"), None);
+    }
+
+    #[test]
+    fn plain_format_has_one_line_per_event() {
+        let text = format_agenda(AgendaFormat::Plain, date(), date(), &[event("a", 9), event("b", 14)], &tz());
+
+        assert_eq!(text, "09:00-09:30   Event a @ Room 1\n14:00-14:30   Event b @ Room 1\n");
+    }
+
+    #[test]
+    fn markdown_format_lists_events() {
+        let text = format_agenda(AgendaFormat::Markdown, date(), date(), &[event("a", 9)], &tz());
+
+        assert!(text.starts_with("# Agenda – Wednesday, January 15, 2025\n"));
+        assert!(text.contains("- **09:00-09:30** Event a _@ Room 1_\n"));
+    }
+
+    #[test]
+    fn json_format_uses_stable_field_names() {
+        let text = format_agenda(AgendaFormat::Json, date(), date(), &[event("a", 9)], &tz());
+        let value: serde_json::Value = serde_json::from_str(&text).unwrap();
+
+        assert_eq!(value["start_date"], "2025-01-15");
+        assert_eq!(value["end_date"], "2025-01-15");
+        assert_eq!(value["timezone"], "UTC");
+        assert_eq!(value["events"][0]["id"], "a");
+        assert_eq!(value["events"][0]["local_start"], "2025-01-15T09:00:00");
+        assert_eq!(value["events"][0]["status"], "confirmed");
+        assert_eq!(value["events"][0]["all_day"], false);
+    }
+
+    #[test]
+    fn ics_format_writes_calendar() {
+        let text = format_agenda(AgendaFormat::Ics, date(), date(), &[event("a", 9)], &tz());
+
+        assert!(text.contains("BEGIN:VEVENT"));
+    }
+
+    #[test]
+    fn multi_day_text_groups_events_by_day() {
+        let mut later = event("b", 10);
+        later.start += chrono::Duration::days(2);
+        later.end += chrono::Duration::days(2);
+        let end = NaiveDate::from_ymd_opt(2025, 1, 21).unwrap();
+
+        let text = format_agenda(AgendaFormat::Text, date(), end, &[event("a", 9), later], &tz());
+
+        assert_eq!(text, "Agenda – Wed, Jan 15 to Tue, Jan 21, 2025\n\n\
+            Wednesday, January 15\n- 09:00-09:30   Event a @ Room 1\n\n\
+            Friday, January 17\n- 10:00-10:30   Event b @ Room 1\n");
+    }
+
+    #[test]
+    fn text_format_ends_with_one_newline() {
+        let text = format_agenda(AgendaFormat::Text, date(), date(), &[event("a", 9)], &tz());
+
+        assert_eq!(text, "Agenda – Wednesday, January 15, 2025\n\n- 09:00-09:30   Event a @ Room 1\n");
+    }
+
+    #[test]
+    fn tsv_format_has_fixed_columns() {
+        let mut all_day = event("b", 0);
+        all_day.all_day = true;
+        all_day.title = "Trip\tto\nBerlin".to_string();
+        all_day.location = None;
+
+        let text = format_agenda(AgendaFormat::Tsv, date(), date(), &[event("a", 9), all_day], &tz());
+
+        assert_eq!(text, "2025-01-15\t09:00\t09:30\tprimary\tEvent a\tRoom 1\n\
+            2025-01-15\tall-day\tall-day\tprimary\tTrip to Berlin\t\n");
+        assert_eq!(AgendaFormat::from_name("tsv"), Some(AgendaFormat::Tsv));
+    }
+
+    #[test]
+    fn finds_the_event_for_a_selected_line() {
+        let events = [event("a", 9), event("b", 14)];
+
+        let found = find_by_agenda_line(&events, "14:00-14:30   Event b @ Room 1\n", &tz());
+
+        assert_eq!(found.map(|e| e.id.as_str()), Some("b"));
+        assert!(find_by_agenda_line(&events, "Event b", &tz()).is_none());
+    }
+
+    #[test]
+    fn multi_day_plain_lines_carry_dates() {
+        let end = NaiveDate::from_ymd_opt(2025, 1, 21).unwrap();
+
+        let text = format_agenda(AgendaFormat::Plain, date(), end, &[event("a", 9)], &tz());
+
+        assert_eq!(text, "2025-01-15 09:00-09:30   Event a @ Room 1\n");
+    }
+}
@@ -1,8 +1,35 @@
-use chrono::{Local, NaiveDate, Timelike};
-use std::collections::HashMap;
+use chrono::{DateTime, NaiveDate, NaiveDateTime, Timelike, Utc};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::sync::Arc;
 
-use crate::calendar::Event;
+use crate::calendar::{date_expr, overlay, AccessRole, Calendar, event::{join_link, split_link}, event_diff::FieldChange, free_slots::FreeSlot, map_link::MapProvider, quick_add::QuickAdd, subscription, travel::{travel_warnings, TravelReport, TravelWarning}, working_hours::WorkingHours, DisplayTimezone, Event, EventStatus, Transparency, Visibility, DEFAULT_CALENDAR_ID};
+use crate::error::ErrorReport;
+use crate::input::command_history::CommandHistory;
+use crate::input::macros::MacroRecorder;
+use crate::input::registers::Registers;
+use crate::report::{build_report, Report, TitlePattern};
+use crate::storage::config::UiConfig;
+use crate::storage::contacts::Contacts;
+use crate::sync::activity_log::ActivityLog;
+use crate::sync::conflict::Conflict;
+use crate::sync::google_tasks::Task;
+use crate::sync::sync_state::SyncState;
 use crate::ui::theme::Theme;
+use crate::ui::time_format::TimeFormat;
+use crate::ui::month_view::MonthCellStyle;
+use crate::ui::day_view::DayGrid;
+use crate::ui::week_view;
+use crate::ui::event_list_view;
+use crate::ui::layout_cache::LayoutCache;
+use crate::ui::url_hints::UrlHints;
+use crate::util::clock::{Clock, SystemClock};
+
+/// `[ui]` settings that `:set` accepts.
+pub const UI_OPTIONS: &[&str] = &[
+    "date_format", "day_grid", "declined_events", "default_view", "first_day_of_week", "map_provider",
+    "month_cell_style", "show_week_numbers", "statusline", "theme", "time_format", "timezone",
+    "travel_buffer_minutes", "working_hours", "year_heatmap",
+];
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum Mode {
@@ -18,14 +45,124 @@ pub enum ViewType {
     Week,
     Day,
     Year,
+    /// The month grid with the selected day's timeline beside it.
+    Split,
+}
+
+impl ViewType {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "month" => Some(Self::Month),
+            "week" => Some(Self::Week),
+            "day" => Some(Self::Day),
+            "year" => Some(Self::Year),
+            "split" => Some(Self::Split),
+            _ => None,
+        }
+    }
+
+    /// Reads `ui.default_view` or a saved view, falling back to month.
+    pub fn from_config(name: &str) -> Self {
+        Self::from_name(name).unwrap_or_else(|| {
+            tracing::warn!("Unknown view '{}', using month", name);
+            Self::Month
+        })
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Month => "Month",
+            Self::Week => "Week",
+            Self::Day => "Day",
+            Self::Year => "Year",
+            Self::Split => "Split",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MessageKind {
+    Info,
+    Success,
+    Error,
+}
+
+/// How events the user declined are shown (`ui.declined_events`).
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum DeclinedEvents {
+    #[default]
+    Dim,
+    Hide,
+}
+
+impl DeclinedEvents {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.trim().to_lowercase().as_str() {
+            "dim" | "show" => Some(Self::Dim),
+            "hide" => Some(Self::Hide),
+            _ => None,
+        }
+    }
+
+    pub fn from_config(name: Option<&str>) -> Self {
+        match name {
+            Some(name) => Self::from_name(name).unwrap_or_else(|| {
+                tracing::warn!("Unknown declined_events setting '{}', using dim", name);
+                Self::Dim
+            }),
+            None => Self::Dim,
+        }
+    }
+
+    pub fn toggled(self) -> Self {
+        match self {
+            Self::Dim => Self::Hide,
+            Self::Hide => Self::Dim,
+        }
+    }
+}
+
+/// The last structural edit, which `.` applies again to the selection.
+#[derive(Debug, Clone, PartialEq)]
+pub enum RepeatableEdit {
+    /// Shift the selected event by this many days.
+    Move(i64),
+    /// Copy the selected event this many days from its own date.
+    Duplicate(i64),
+    /// Create this event again on the selected date, at the same time.
+    Create(Box<Event>),
+}
+
+impl RepeatableEdit {
+    /// The event `.` would update or create, or None when there is nothing
+    /// to apply it to.
+    pub fn target(&self, selected: Option<&Event>, date: NaiveDate, tz: &DisplayTimezone, now: DateTime<Utc>) -> Option<Event> {
+        match self {
+            Self::Move(days) => selected?.shifted_by_days(*days, tz),
+            Self::Duplicate(days) => {
+                let event = selected?;
+                let date = event.local_start(tz).date() + chrono::Duration::days(*days);
+                event.duplicate_to(uuid::Uuid::new_v4().to_string(), date, tz, now)
+            }
+            Self::Create(template) => template.duplicate_to(uuid::Uuid::new_v4().to_string(), date, tz, now),
+        }
+    }
+
+    /// "move +1d", "duplicate +7d" or "create \"Standup\"", for the status bar.
+    pub fn describe(&self) -> String {
+        match self {
+            Self::Move(days) => format!("move {:+}d", days),
+            Self::Duplicate(days) => format!("duplicate {:+}d", days),
+            Self::Create(template) => format!("create \"{}\"", template.title),
+        }
+    }
 }
 
+/// Feedback shown in the status bar until the next keypress.
 #[derive(Debug, Clone, PartialEq)]
-pub enum SyncStatus {
-    Synced,
-    Syncing,
-    Offline,
-    Error(String),
+pub struct StatusMessage {
+    pub text: String,
+    pub kind: MessageKind,
 }
 
 #[derive(Debug, Clone)]
@@ -38,9 +175,18 @@ pub struct AppState {
     pub mode: Mode,
     pub view: ViewType,
     pub selected_date: NaiveDate,
+    /// Change through `add_event`/`remove_event`/`clear_events` so the date
+    /// index stays in step.
     pub events: HashMap<String, Event>,
+    /// Event ids under every local date each event covers.
+    date_index: BTreeMap<NaiveDate, Vec<String>>,
+    /// Bumped on every change to `events`, so cached layouts know to rebuild.
+    events_version: u64,
+    pub layout_cache: LayoutCache,
+    /// Set when something on screen changed and the next loop should draw.
+    pub needs_redraw: bool,
     pub cursor_position: Position,
-    pub sync_status: SyncStatus,
+    pub sync: SyncState,
     pub command_buffer: String,
     pub search_query: Option<String>,
     pub show_help: bool,
@@ -48,14 +194,95 @@ pub struct AppState {
     pub theme: Theme,
     pub event_form: Option<EventForm>,
     pub selected_event_index: usize,
+    /// First event drawn in the event list pane.
+    pub event_list_scroll: usize,
+    pub event_list_visible_lines: usize,
     pub delete_confirmation_event_id: Option<String>,
+    pub bulk_delete_event_ids: Vec<String>,
+    pub conflict_event_ids: Vec<String>,
+    /// What an edit would change, shown for confirmation before it is pushed.
+    pub pending_changes: Vec<FieldChange>,
+    /// Local edits that changed on the server too, resolved one at a time.
+    pub sync_conflicts: Vec<Conflict>,
+    pub free_slots: Vec<FreeSlot>,
+    pub free_slot_index: usize,
+    pub free_slot_minutes: u32,
+    /// The open `:report` table, if any.
+    pub report: Option<Report>,
+    pub report_patterns: Vec<TitlePattern>,
+    pub last_edit: Option<RepeatableEdit>,
+    pub macros: MacroRecorder,
+    pub registers: Registers,
+    /// `"` (or Ctrl-R in insert mode) was pressed and a register name is next.
+    pub awaiting_register: bool,
+    /// The register named with `"a` for the next yank.
+    pub pending_register: Option<char>,
+    pub show_registers: bool,
     pub visual_selection_start: Option<NaiveDate>,
     pub detail_view_event_id: Option<String>,
+    /// The description cut with `d` in the detail view; the event keeps its
+    /// own until the draft is saved through the form with `E`.
+    pub detail_view_draft: Option<String>,
     pub detail_view_scroll: usize,
     pub detail_view_cursor_line: usize,
     pub detail_view_cursor_col: usize,
     pub detail_view_line_text: Vec<String>,
     pub detail_view_visual_start: Option<(usize, usize)>,
+    pub detail_view_wrap_width: usize,
+    pub detail_view_visible_lines: usize,
+    pub detail_view_pending_g: bool,
+    /// `f` labelled the URLs in the detail view and a label is being typed.
+    pub detail_view_hints: Option<UrlHints>,
+    pub show_week_numbers: bool,
+    pub year_heatmap: bool,
+    /// A 1 was typed in year view, so 0-2 next make it October-December.
+    pub year_month_prefix: bool,
+    pub month_cell_style: MonthCellStyle,
+    pub day_grid: DayGrid,
+    pub map_provider: MapProvider,
+    /// `first_day_of_week`: the first column of month, week and year grids.
+    pub week_start: chrono::Weekday,
+    pub time_format: TimeFormat,
+    /// `travel_buffer_minutes`; travel warnings are off without it.
+    pub travel_buffer_minutes: Option<i64>,
+    /// The open `:conflicts` list, if any.
+    pub travel_report: Option<TravelReport>,
+    pub timezone: DisplayTimezone,
+    /// The `[ui]` settings in effect, including `:set` overrides.
+    pub ui_config: UiConfig,
+    /// Read the time through `now`/`today` so tests can pin it.
+    pub clock: Arc<dyn Clock>,
+    pub working_hours: WorkingHours,
+    pub week_scroll_hour: u32,
+    pub status_message: Option<StatusMessage>,
+    pub command_history: CommandHistory,
+    pub command_completions: Vec<String>,
+    pub command_completion_index: usize,
+    /// Attendee addresses seen before, most recent first, for completion.
+    pub contacts: Contacts,
+    pub calendar_names: Vec<String>,
+    /// Roles from the calendar list, once it has been fetched.
+    pub calendar_access: HashMap<String, AccessRole>,
+    pub hidden_calendars: HashSet<String>,
+    pub show_calendar_list: bool,
+    pub show_sync_status: bool,
+    /// Every status message and sync result, for the `:messages` panel.
+    pub activity: ActivityLog,
+    pub show_activity: bool,
+    pub activity_scroll: usize,
+    /// A failure shown in a dialog until dismissed.
+    pub error_report: Option<ErrorReport>,
+    /// Mutations are skipped with a note instead of being sent.
+    pub read_only: bool,
+    /// A `--script` replay, which leaves the command history and config
+    /// files alone.
+    pub replaying: bool,
+    pub calendar_list_index: usize,
+    pub default_calendar: String,
+    pub declined_events: DeclinedEvents,
+    pub tasks: Vec<Task>,
+    pub show_tasks: bool,
+    pub task_index: usize,
 }
 
 #[derive(Debug, Clone)]
@@ -66,19 +293,37 @@ pub struct EventForm {
     pub start_minute: u32,
     pub duration_minutes: u32,
     pub location: String,
+    /// A meeting or reference URL, saved as a `Link: ` line in the description.
+    pub link: String,
+    /// Comma-separated attendee addresses.
+    pub attendees: String,
     pub description: String,
+    /// Char offset of the cursor in the multi-line description.
+    pub description_cursor: usize,
+    /// Cursor in the active single-line field; reset to the end on focus.
+    pub field_cursor: usize,
     pub active_field: FormField,
     pub event_id: Option<String>,
     pub time_input_buffer: String,
     pub duration_input_buffer: String,
+    pub date_input_buffer: String,
     pub time_buffer_touched: bool,
     pub duration_buffer_touched: bool,
+    pub date_buffer_touched: bool,
     pub all_day: bool,
+    /// `None` posts to the configured default calendar.
+    pub calendar_id: Option<String>,
+    pub status: EventStatus,
+    pub transparency: Transparency,
+    pub visibility: Visibility,
+    /// Field-level problems from the last save attempt.
+    pub errors: Vec<(FormField, String)>,
 }
 
 impl EventForm {
-    pub fn new(date: NaiveDate, title: String) -> Self {
-        let now = chrono::Local::now();
+    /// A blank form on `date`, starting at the hour of `now`.
+    pub fn new(date: NaiveDate, title: String, now: NaiveDateTime) -> Self {
+        let field_cursor = title.chars().count();
         Self {
             title,
             date,
@@ -86,40 +331,91 @@ impl EventForm {
             start_minute: 0,
             duration_minutes: 60,
             location: String::new(),
+            link: String::new(),
+            attendees: String::new(),
             description: String::new(),
+            description_cursor: 0,
+            field_cursor,
             active_field: FormField::Title,
             event_id: None,
             time_input_buffer: format!("{:02}:{:02}", now.hour(), 0),
             duration_input_buffer: "60".to_string(),
+            date_input_buffer: date.format("%Y-%m-%d").to_string(),
             time_buffer_touched: false,
             duration_buffer_touched: false,
+            date_buffer_touched: false,
             all_day: false,
+            calendar_id: None,
+            status: EventStatus::Confirmed,
+            transparency: Transparency::Opaque,
+            visibility: Visibility::Default,
+            errors: Vec::new(),
         }
     }
 
-    pub fn for_event(event: &Event) -> Self {
-        let start_hour = event.start.time().hour();
-        let start_minute = event.start.time().minute();
+    pub fn for_event(event: &Event, tz: &DisplayTimezone) -> Self {
+        let local_start = event.local_start(tz);
+        let start_hour = local_start.hour();
+        let start_minute = local_start.minute();
         let duration_minutes = event.duration_minutes() as u32;
+        let (description, link) = split_link(event.description.as_deref().unwrap_or_default());
         Self {
             title: event.title.clone(),
-            date: event.start.date_naive(),
+            date: local_start.date(),
             start_hour,
             start_minute,
             duration_minutes,
             location: event.location.clone().unwrap_or_default(),
-            description: event.description.clone().unwrap_or_default(),
+            link: link.unwrap_or_default(),
+            attendees: event.attendees.join(", "),
+            description_cursor: description.chars().count(),
+            description,
+            field_cursor: event.title.chars().count(),
             active_field: FormField::Title,
             event_id: Some(event.id.clone()),
             time_input_buffer: format!("{:02}:{:02}", start_hour, start_minute),
             duration_input_buffer: duration_minutes.to_string(),
+            date_input_buffer: local_start.date().format("%Y-%m-%d").to_string(),
             time_buffer_touched: false,
             duration_buffer_touched: false,
+            date_buffer_touched: false,
             all_day: event.all_day,
+            calendar_id: Some(event.calendar_id.clone()),
+            status: event.status.clone(),
+            transparency: event.transparency,
+            visibility: event.visibility,
+            errors: Vec::new(),
+        }
+    }
+
+    /// Pre-fills a new-event form from `:new` text; anything the text does
+    /// not mention keeps the `new` defaults on `default_date`.
+    pub fn from_quick_add(quick_add: &QuickAdd, default_date: NaiveDate, now: NaiveDateTime) -> Self {
+        let mut form = Self::new(quick_add.date.unwrap_or(default_date), quick_add.title.clone(), now);
+
+        if let Some(time) = quick_add.start_time {
+            form.start_hour = time.hour();
+            form.start_minute = time.minute();
+            form.time_input_buffer = format!("{:02}:{:02}", form.start_hour, form.start_minute);
         }
+        form.duration_minutes = quick_add.duration_minutes;
+        form.duration_input_buffer = quick_add.duration_minutes.to_string();
+        form.location = quick_add.location.clone().unwrap_or_default();
+        form
+    }
+
+    pub fn for_slot(start: NaiveDateTime, duration_minutes: u32) -> Self {
+        let mut form = Self::new(start.date(), String::new(), start);
+        form.start_hour = start.hour();
+        form.start_minute = start.minute();
+        form.time_input_buffer = format!("{:02}:{:02}", form.start_hour, form.start_minute);
+        form.duration_minutes = duration_minutes;
+        form.duration_input_buffer = duration_minutes.to_string();
+        form
     }
 
     pub fn new_all_day(date: NaiveDate, title: String, duration_days: u32) -> Self {
+        let field_cursor = title.chars().count();
         Self {
             title,
             date,
@@ -127,14 +423,25 @@ impl EventForm {
             start_minute: 0,
             duration_minutes: duration_days * 24 * 60,
             location: String::new(),
+            link: String::new(),
+            attendees: String::new(),
             description: String::new(),
+            description_cursor: 0,
+            field_cursor,
             active_field: FormField::Title,
             event_id: None,
             time_input_buffer: String::new(),
             duration_input_buffer: duration_days.to_string(),
+            date_input_buffer: date.format("%Y-%m-%d").to_string(),
             time_buffer_touched: false,
             duration_buffer_touched: false,
+            date_buffer_touched: false,
             all_day: true,
+            calendar_id: None,
+            status: EventStatus::Confirmed,
+            transparency: Transparency::Opaque,
+            visibility: Visibility::Default,
+            errors: Vec::new(),
         }
     }
 
@@ -145,83 +452,359 @@ impl EventForm {
     pub fn next_field(&mut self) {
         if self.all_day {
             self.active_field = match self.active_field {
-                FormField::Title => FormField::Duration,
+                FormField::Title => FormField::Date,
+                FormField::Date => FormField::AllDay,
+                FormField::AllDay => FormField::Duration,
                 FormField::Duration => FormField::Location,
-                FormField::Location => FormField::Description,
-                FormField::Description => FormField::Title,
+                FormField::Location => FormField::Link,
+                FormField::Link => FormField::Attendees,
+                FormField::Attendees => FormField::Description,
+                FormField::Description => FormField::Calendar,
+                FormField::Calendar => FormField::Status,
+                FormField::Status => FormField::ShowAs,
+                FormField::ShowAs => FormField::Visibility,
+                FormField::Visibility => FormField::Title,
                 FormField::StartTime => FormField::Duration,
             };
         } else {
             self.active_field = match self.active_field {
-                FormField::Title => FormField::StartTime,
+                FormField::Title => FormField::Date,
+                FormField::Date => FormField::AllDay,
+                FormField::AllDay => FormField::StartTime,
                 FormField::StartTime => FormField::Duration,
                 FormField::Duration => FormField::Location,
-                FormField::Location => FormField::Description,
-                FormField::Description => FormField::Title,
+                FormField::Location => FormField::Link,
+                FormField::Link => FormField::Attendees,
+                FormField::Attendees => FormField::Description,
+                FormField::Description => FormField::Calendar,
+                FormField::Calendar => FormField::Status,
+                FormField::Status => FormField::ShowAs,
+                FormField::ShowAs => FormField::Visibility,
+                FormField::Visibility => FormField::Title,
             };
         }
+        self.focus_changed();
     }
 
     pub fn prev_field(&mut self) {
         if self.all_day {
             self.active_field = match self.active_field {
-                FormField::Title => FormField::Description,
-                FormField::Duration => FormField::Title,
+                FormField::Title => FormField::Visibility,
+                FormField::Visibility => FormField::ShowAs,
+                FormField::ShowAs => FormField::Status,
+                FormField::Status => FormField::Calendar,
+                FormField::Date => FormField::Title,
+                FormField::AllDay => FormField::Date,
+                FormField::Duration => FormField::AllDay,
                 FormField::Location => FormField::Duration,
-                FormField::Description => FormField::Location,
-                FormField::StartTime => FormField::Title,
+                FormField::Description => FormField::Attendees,
+                FormField::Attendees => FormField::Link,
+                FormField::Link => FormField::Location,
+                FormField::Calendar => FormField::Description,
+                FormField::StartTime => FormField::AllDay,
             };
         } else {
             self.active_field = match self.active_field {
-                FormField::Title => FormField::Description,
-                FormField::StartTime => FormField::Title,
+                FormField::Title => FormField::Visibility,
+                FormField::Visibility => FormField::ShowAs,
+                FormField::ShowAs => FormField::Status,
+                FormField::Status => FormField::Calendar,
+                FormField::Date => FormField::Title,
+                FormField::AllDay => FormField::Date,
+                FormField::StartTime => FormField::AllDay,
                 FormField::Duration => FormField::StartTime,
                 FormField::Location => FormField::Duration,
-                FormField::Description => FormField::Location,
+                FormField::Description => FormField::Attendees,
+                FormField::Attendees => FormField::Link,
+                FormField::Link => FormField::Location,
+                FormField::Calendar => FormField::Description,
             };
         }
+        self.focus_changed();
     }
 
-    pub fn parse_time_input(&mut self) {
-        let input = self.time_input_buffer.replace(':', "");
-        if let Ok(num) = input.parse::<u32>() {
-            if input.len() == 3 || input.len() == 4 {
-                self.start_hour = num / 100;
-                self.start_minute = num % 100;
-                if self.start_hour >= 24 {
-                    self.start_hour = 23;
-                }
-                if self.start_minute >= 60 {
-                    self.start_minute = 59;
-                }
-                self.time_input_buffer = format!("{:02}:{:02}", self.start_hour, self.start_minute);
-            } else if input.len() <= 2 {
-                self.start_hour = num.min(23);
+    fn focus_changed(&mut self) {
+        self.field_cursor = self.active_text().map_or(0, |text| text.chars().count());
+    }
+
+    /// The typed-in text of the active field, if it has any.
+    pub fn active_text(&self) -> Option<&str> {
+        match self.active_field {
+            FormField::Title => Some(&self.title),
+            FormField::Date => Some(&self.date_input_buffer),
+            FormField::StartTime => Some(&self.time_input_buffer),
+            FormField::Duration => Some(&self.duration_input_buffer),
+            FormField::Location => Some(&self.location),
+            FormField::Link => Some(&self.link),
+            FormField::Attendees => Some(&self.attendees),
+            FormField::Description => Some(&self.description),
+            _ => None,
+        }
+    }
+
+    /// The active field's text with its cursor, clamped to the text.
+    pub fn active_text_mut(&mut self) -> Option<(&mut String, &mut usize)> {
+        let (text, cursor) = match self.active_field {
+            FormField::Title => (&mut self.title, &mut self.field_cursor),
+            FormField::Date => (&mut self.date_input_buffer, &mut self.field_cursor),
+            FormField::StartTime => (&mut self.time_input_buffer, &mut self.field_cursor),
+            FormField::Duration => (&mut self.duration_input_buffer, &mut self.field_cursor),
+            FormField::Location => (&mut self.location, &mut self.field_cursor),
+            FormField::Link => (&mut self.link, &mut self.field_cursor),
+            FormField::Attendees => (&mut self.attendees, &mut self.field_cursor),
+            FormField::Description => (&mut self.description, &mut self.description_cursor),
+            _ => return None,
+        };
+        *cursor = (*cursor).min(text.chars().count());
+        Some((text, cursor))
+    }
+
+    fn buffer_touched_mut(&mut self) -> Option<&mut bool> {
+        match self.active_field {
+            FormField::Date => Some(&mut self.date_buffer_touched),
+            FormField::StartTime => Some(&mut self.time_buffer_touched),
+            FormField::Duration => Some(&mut self.duration_buffer_touched),
+            _ => None,
+        }
+    }
+
+    pub fn mark_touched(&mut self) {
+        if let Some(touched) = self.buffer_touched_mut() {
+            *touched = true;
+        }
+    }
+
+    /// Typing into a prefilled date, time or duration replaces it until the
+    /// field has been edited or the cursor moved.
+    pub fn start_typing(&mut self) {
+        if self.buffer_touched_mut().is_some_and(|touched| !*touched)
+            && let Some((text, cursor)) = self.active_text_mut()
+        {
+            text.clear();
+            *cursor = 0;
+        }
+        self.mark_touched();
+    }
+
+    /// Switches between a timed and an all-day event. The duration is
+    /// rounded up to whole days, or reset to an hour from 09:00 when the
+    /// form never had a start time.
+    pub fn toggle_all_day(&mut self) {
+        self.all_day = !self.all_day;
+        if self.all_day {
+            let days = self.duration_minutes.div_ceil(24 * 60).max(1);
+            self.duration_minutes = days * 24 * 60;
+            self.duration_input_buffer = days.to_string();
+        } else {
+            if self.time_input_buffer.is_empty() {
+                self.start_hour = 9;
                 self.start_minute = 0;
-                self.time_input_buffer = format!("{:02}:{:02}", self.start_hour, self.start_minute);
+                self.time_input_buffer = "09:00".to_string();
             }
+            self.duration_minutes = 60;
+            self.duration_input_buffer = "60".to_string();
+        }
+        self.duration_buffer_touched = false;
+    }
+
+    pub fn calendar_or<'a>(&'a self, default: &'a str) -> &'a str {
+        self.calendar_id.as_deref().unwrap_or(default)
+    }
+
+    /// Steps to the next (or previous) of `calendars`, wrapping around.
+    /// Existing events stay on their calendar.
+    pub fn cycle_calendar(&mut self, calendars: &[String], default: &str, forward: bool) {
+        if self.is_editing() || calendars.is_empty() {
+            return;
+        }
+        let current = self.calendar_or(default);
+        let index = calendars.iter().position(|name| name == current);
+        let next = match (index, forward) {
+            (None, _) => 0,
+            (Some(i), true) => (i + 1) % calendars.len(),
+            (Some(i), false) => (i + calendars.len() - 1) % calendars.len(),
+        };
+        self.calendar_id = Some(calendars[next].clone());
+    }
+
+    pub fn time_range(&self, tz: &DisplayTimezone) -> Option<(DateTime<Utc>, DateTime<Utc>)> {
+        if self.all_day {
+            let days = self.duration_minutes / (24 * 60);
+            let start = self.date.and_hms_opt(0, 0, 0)?.and_utc();
+            Some((start, start + chrono::Duration::days(days as i64)))
+        } else {
+            let naive = self.date.and_hms_opt(self.start_hour, self.start_minute, 0)?;
+            let start = tz.from_local(&naive)?;
+            Some((start, start + chrono::Duration::minutes(self.duration_minutes as i64)))
+        }
+    }
+
+    /// Accepts anything the shared date parser does (`2025-01-15`, `fri`,
+    /// `+2d`, ...); unparseable input is kept for validation to report.
+    pub fn parse_date_input(&mut self, today: NaiveDate) {
+        if let Ok(date) = date_expr::parse_date(&self.date_input_buffer, today) {
+            self.date = date;
+            self.date_input_buffer = self.date.format("%Y-%m-%d").to_string();
         }
     }
 
+    pub fn shift_date(&mut self, days: i64) {
+        if let Some(date) = self.date.checked_add_signed(chrono::Duration::days(days)) {
+            self.date = date;
+        }
+        self.date_input_buffer = self.date.format("%Y-%m-%d").to_string();
+        self.date_buffer_touched = false;
+        self.focus_changed();
+    }
+
+    pub fn parse_time_input(&mut self) {
+        if let Some((hour, minute)) = parse_clock(&self.time_input_buffer) {
+            self.start_hour = hour;
+            self.start_minute = minute;
+            self.time_input_buffer = format!("{:02}:{:02}", self.start_hour, self.start_minute);
+        }
+    }
+
+    /// Minutes, or for timed events an end time (`-17:30`, `until 1730`);
+    /// an end at or before the start is taken to be on the next day.
     pub fn parse_duration_input(&mut self) {
-        if let Ok(value) = self.duration_input_buffer.parse::<u32>() {
-            if self.all_day {
-                let days = value.clamp(1, 365);
-                self.duration_minutes = days * 24 * 60;
-            } else {
-                self.duration_minutes = value.clamp(1, 10080);
+        if let Some(minutes) = self.parsed_duration() {
+            self.duration_minutes = minutes;
+            let shown = if self.all_day { minutes / (24 * 60) } else { minutes };
+            self.duration_input_buffer = shown.to_string();
+        }
+    }
+
+    fn parsed_duration(&self) -> Option<u32> {
+        let input = self.duration_input_buffer.trim();
+        let end_time = input.strip_prefix('-').or_else(|| input.strip_prefix("until"));
+        if let Some(end_time) = end_time.filter(|_| !self.all_day) {
+            let (hour, minute) = parse_clock(end_time.trim())?;
+            let start = self.start_hour * 60 + self.start_minute;
+            let end = hour * 60 + minute;
+            return Some(if end > start { end - start } else { end + 24 * 60 - start });
+        }
+
+        let value = input.parse::<u32>().ok()?;
+        if self.all_day {
+            (1..=365).contains(&value).then_some(value * 24 * 60)
+        } else {
+            (1..=10080).contains(&value).then_some(value)
+        }
+    }
+
+    /// Problems that should block saving, checked against the typed buffers
+    /// rather than the last values they parsed to.
+    pub fn validate(&self, tz: &DisplayTimezone, today: NaiveDate) -> Vec<(FormField, String)> {
+        let mut errors = Vec::new();
+        if self.title.trim().is_empty() {
+            errors.push((FormField::Title, "Title is required".to_string()));
+        }
+        if let Err(e) = date_expr::parse_date(&self.date_input_buffer, today) {
+            errors.push((FormField::Date, e.to_string()));
+        }
+        if !self.all_day && parse_clock(&self.time_input_buffer).is_none() {
+            errors.push((FormField::StartTime, "Use HH:MM between 00:00 and 23:59".to_string()));
+        }
+
+        if !self.link.is_empty() && !self.link.starts_with("https://") && !self.link.starts_with("http://") {
+            errors.push((FormField::Link, "Links start with https:// or http://".to_string()));
+        }
+        if let Some(bad) = self.attendee_list().into_iter().find(|email| !email.contains('@')) {
+            errors.push((FormField::Attendees, format!("'{}' is not an email address", bad)));
+        }
+
+        let mut parsed = self.clone();
+        parsed.parse_date_input(today);
+        parsed.parse_time_input();
+        if parsed.parsed_duration().is_none() {
+            let message = if self.all_day { "Use 1 to 365 days" } else { "Use 1 to 10080 minutes or an end time like -17:30" };
+            errors.push((FormField::Duration, message.to_string()));
+        } else if errors.is_empty() {
+            parsed.parse_duration_input();
+            if parsed.time_range(tz).is_none() {
+                errors.push((FormField::StartTime, "That time does not exist on this date".to_string()));
             }
         }
+        errors
+    }
+
+    /// The description to save, with the link appended on its own line.
+    pub fn description_with_link(&self) -> Option<String> {
+        let description = join_link(&self.description, self.link.trim());
+        (!description.is_empty()).then_some(description)
+    }
+
+    pub fn attendee_list(&self) -> Vec<String> {
+        self.attendees.split(',')
+            .map(str::trim)
+            .filter(|email| !email.is_empty())
+            .map(String::from)
+            .collect()
+    }
+
+    /// A known address completing the attendee being typed at the end of
+    /// the field, skipping ones already listed.
+    pub fn attendee_suggestion<'a>(&self, contacts: &'a [String]) -> Option<&'a str> {
+        let partial = self.attendees.rsplit(',').next()?.trim_start().to_lowercase();
+        if partial.is_empty() {
+            return None;
+        }
+        let listed = self.attendee_list();
+        contacts.iter()
+            .map(String::as_str)
+            .find(|c| c.len() > partial.len() && c.starts_with(&partial) && !listed.iter().any(|l| l.eq_ignore_ascii_case(c)))
+    }
+
+    pub fn accept_attendee_suggestion(&mut self, contacts: &[String]) {
+        let Some(suggestion) = self.attendee_suggestion(contacts) else {
+            return;
+        };
+        let kept = self.attendees.rfind(',').map_or("", |i| &self.attendees[..=i]);
+        let separator = if kept.is_empty() { "" } else { " " };
+        self.attendees = format!("{}{}{}, ", kept, separator, suggestion);
+        self.field_cursor = self.attendees.chars().count();
+    }
+
+    pub fn error_for(&self, field: &FormField) -> Option<&str> {
+        self.errors.iter().find(|(f, _)| f == field).map(|(_, message)| message.as_str())
     }
+
+    /// Local wall-clock end of a timed event, for display next to the duration.
+    pub fn end_time(&self) -> (u32, u32) {
+        let end = (self.start_hour * 60 + self.start_minute + self.duration_minutes) % (24 * 60);
+        (end / 60, end % 60)
+    }
+}
+
+/// `HHMM`, `HH:MM` or a bare hour.
+fn parse_clock(input: &str) -> Option<(u32, u32)> {
+    let input = input.replace(':', "");
+    let num = input.parse::<u32>().ok()?;
+    let (hour, minute) = match input.len() {
+        3 | 4 => (num / 100, num % 100),
+        0..=2 => (num, 0),
+        _ => return None,
+    };
+    (hour < 24 && minute < 60).then_some((hour, minute))
 }
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum FormField {
     Title,
+    Date,
+    AllDay,
     StartTime,
     Duration,
     Location,
+    Link,
+    Attendees,
     Description,
+    Calendar,
+    Status,
+    /// Busy/free (Google's transparency).
+    ShowAs,
+    Visibility,
 }
 
 impl AppState {
@@ -229,10 +812,14 @@ impl AppState {
         Self {
             mode: Mode::Normal,
             view: ViewType::Month,
-            selected_date: Local::now().date_naive(),
+            selected_date: DisplayTimezone::default().date_of(&SystemClock.now()),
             events: HashMap::new(),
+            date_index: BTreeMap::new(),
+            events_version: 0,
+            layout_cache: LayoutCache::default(),
+            needs_redraw: true,
             cursor_position: Position { x: 0, y: 0 },
-            sync_status: SyncStatus::Synced,
+            sync: SyncState::default(),
             command_buffer: String::new(),
             search_query: None,
             show_help: false,
@@ -240,14 +827,73 @@ impl AppState {
             theme: Theme::default(),
             event_form: None,
             selected_event_index: 0,
+            event_list_scroll: 0,
+            event_list_visible_lines: 0,
             delete_confirmation_event_id: None,
+            bulk_delete_event_ids: Vec::new(),
+            conflict_event_ids: Vec::new(),
+            pending_changes: Vec::new(),
+            sync_conflicts: Vec::new(),
+            free_slots: Vec::new(),
+            free_slot_index: 0,
+            free_slot_minutes: 0,
+            report: None,
+            report_patterns: Vec::new(),
+            last_edit: None,
+            macros: MacroRecorder::default(),
+            registers: Registers::default(),
+            awaiting_register: false,
+            pending_register: None,
+            show_registers: false,
             visual_selection_start: None,
             detail_view_event_id: None,
+            detail_view_draft: None,
             detail_view_scroll: 0,
             detail_view_cursor_line: 0,
             detail_view_cursor_col: 0,
             detail_view_line_text: Vec::new(),
             detail_view_visual_start: None,
+            detail_view_wrap_width: 0,
+            detail_view_visible_lines: 0,
+            detail_view_pending_g: false,
+            detail_view_hints: None,
+            show_week_numbers: false,
+            year_heatmap: false,
+            year_month_prefix: false,
+            month_cell_style: MonthCellStyle::default(),
+            day_grid: DayGrid::default(),
+            map_provider: MapProvider::default(),
+            week_start: chrono::Weekday::Mon,
+            time_format: TimeFormat::default(),
+            travel_buffer_minutes: None,
+            travel_report: None,
+            timezone: DisplayTimezone::default(),
+            ui_config: UiConfig::default(),
+            clock: Arc::new(SystemClock),
+            working_hours: WorkingHours::default(),
+            week_scroll_hour: week_view::DEFAULT_SCROLL_HOUR,
+            status_message: None,
+            command_history: CommandHistory::default(),
+            command_completions: Vec::new(),
+            command_completion_index: 0,
+            contacts: Contacts::default(),
+            calendar_names: Vec::new(),
+            calendar_access: HashMap::new(),
+            hidden_calendars: HashSet::new(),
+            show_calendar_list: false,
+            show_sync_status: false,
+            activity: ActivityLog::default(),
+            show_activity: false,
+            activity_scroll: 0,
+            error_report: None,
+            read_only: false,
+            replaying: false,
+            calendar_list_index: 0,
+            default_calendar: DEFAULT_CALENDAR_ID.to_string(),
+            declined_events: DeclinedEvents::default(),
+            tasks: Vec::new(),
+            show_tasks: false,
+            task_index: 0,
         }
     }
 
@@ -256,24 +902,471 @@ impl AppState {
         self
     }
 
+    pub fn with_timezone(mut self, timezone: DisplayTimezone) -> Self {
+        self.set_timezone(timezone);
+        self.selected_date = self.today();
+        self
+    }
+
+    pub fn with_clock(mut self, clock: impl Clock + 'static) -> Self {
+        self.clock = Arc::new(clock);
+        self.selected_date = self.today();
+        self
+    }
+
+    pub fn now(&self) -> DateTime<Utc> {
+        self.clock.now()
+    }
+
+    /// The current time in the display timezone.
+    pub fn local_now(&self) -> NaiveDateTime {
+        self.timezone.to_local(&self.clock.now())
+    }
+
+    pub fn today(&self) -> NaiveDate {
+        self.local_now().date()
+    }
+
+    /// Local dates depend on the timezone, so the date index is rebuilt.
+    pub fn set_timezone(&mut self, timezone: DisplayTimezone) {
+        self.timezone = timezone;
+        self.date_index.clear();
+        let ids: Vec<String> = self.events.keys().cloned().collect();
+        for id in ids {
+            self.index_event(&id);
+        }
+    }
+
+    /// Applies every `[ui]` setting that can change while running.
+    pub fn with_ui_config(mut self, ui: &UiConfig) -> Self {
+        self.apply_ui_config(ui);
+        self.selected_date = self.today();
+        self
+    }
+
+    pub fn apply_ui_config(&mut self, ui: &UiConfig) {
+        self.theme = Theme::get_by_name(&ui.theme);
+        self.set_timezone(DisplayTimezone::from_config(ui.timezone.as_deref()));
+        self.show_week_numbers = ui.show_week_numbers;
+        self.year_heatmap = ui.year_heatmap;
+        self.working_hours = WorkingHours::from_config(ui.working_hours.as_deref());
+        self.month_cell_style = MonthCellStyle::from_config(ui.month_cell_style.as_deref());
+        self.declined_events = DeclinedEvents::from_config(ui.declined_events.as_deref());
+        self.day_grid = DayGrid::from_config(ui.day_grid.as_deref());
+        self.map_provider = MapProvider::from_config(ui.map_provider.as_deref());
+        self.week_start = week_view::first_day_from_config(&ui.first_day_of_week);
+        self.time_format = TimeFormat::from_config(&ui.time_format);
+        self.travel_buffer_minutes = ui.travel_buffer_minutes.map(i64::from);
+        self.ui_config = ui.clone();
+        self.layout_cache.clear();
+        self.reset_event_selection();
+    }
+
+    /// Handles `:set key=value`, rejecting values the config loader would
+    /// only warn about. `default_view` also switches to that view.
+    pub fn set_ui_option(&mut self, key: &str, value: &str) -> Result<(), String> {
+        let mut ui = self.ui_config.clone();
+        let invalid = || format!("Invalid value for {}: {}", key, value);
+        let optional = |value: &str| (!value.is_empty()).then(|| value.to_string());
+        match key {
+            "theme" => {
+                Theme::find_by_name(value).ok_or_else(invalid)?;
+                ui.theme = value.to_string();
+            }
+            "timezone" => {
+                if !value.is_empty() {
+                    DisplayTimezone::from_name(value).ok_or_else(invalid)?;
+                }
+                ui.timezone = optional(value);
+            }
+            "show_week_numbers" => ui.show_week_numbers = value.parse().map_err(|_| invalid())?,
+            "year_heatmap" => ui.year_heatmap = value.parse().map_err(|_| invalid())?,
+            "working_hours" => {
+                if !value.is_empty() {
+                    WorkingHours::parse(value).ok_or_else(invalid)?;
+                }
+                ui.working_hours = optional(value);
+            }
+            "month_cell_style" => {
+                MonthCellStyle::from_name(value).ok_or_else(invalid)?;
+                ui.month_cell_style = optional(value);
+            }
+            "declined_events" => {
+                DeclinedEvents::from_name(value).ok_or_else(invalid)?;
+                ui.declined_events = optional(value);
+            }
+            "day_grid" => {
+                DayGrid::from_name(value).ok_or_else(invalid)?;
+                ui.day_grid = optional(value);
+            }
+            "travel_buffer_minutes" => {
+                ui.travel_buffer_minutes = if value.is_empty() { None } else { Some(value.parse().map_err(|_| invalid())?) };
+            }
+            "map_provider" => {
+                MapProvider::from_name(value).ok_or_else(invalid)?;
+                ui.map_provider = optional(value);
+            }
+            "default_view" => {
+                self.view = ViewType::from_name(value).ok_or_else(invalid)?;
+                ui.default_view = value.to_string();
+            }
+            "time_format" => {
+                TimeFormat::from_name(value).ok_or_else(invalid)?;
+                ui.time_format = value.to_string();
+            }
+            "date_format" => {
+                let bad = chrono::format::StrftimeItems::new(value).any(|item| item == chrono::format::Item::Error);
+                if value.is_empty() || bad {
+                    return Err(invalid());
+                }
+                ui.date_format = value.to_string();
+            }
+            "statusline" => ui.statusline = optional(value),
+            "first_day_of_week" => {
+                week_view::parse_first_day(value).ok_or_else(invalid)?;
+                ui.first_day_of_week = value.to_string();
+            }
+            _ => return Err(format!("Unknown option: {} (available: {})", key, UI_OPTIONS.join(", "))),
+        }
+        self.apply_ui_config(&ui);
+        Ok(())
+    }
+
+    pub fn with_month_cell_style(mut self, style: MonthCellStyle) -> Self {
+        self.month_cell_style = style;
+        self
+    }
+
+    pub fn with_working_hours(mut self, working_hours: WorkingHours) -> Self {
+        self.working_hours = working_hours;
+        self
+    }
+
+    pub fn with_replaying(mut self, replaying: bool) -> Self {
+        self.replaying = replaying;
+        self
+    }
+
+    pub fn with_stale_after(mut self, minutes: u32) -> Self {
+        self.sync.stale_after = chrono::Duration::minutes(minutes as i64);
+        self
+    }
+
+    pub fn with_week_numbers(mut self, show_week_numbers: bool) -> Self {
+        self.show_week_numbers = show_week_numbers;
+        self
+    }
+
+    pub fn with_command_history(mut self, history: CommandHistory) -> Self {
+        self.command_history = history;
+        self
+    }
+
+    pub fn with_contacts(mut self, contacts: Contacts) -> Self {
+        self.contacts = contacts;
+        self
+    }
+
+    pub fn with_calendar_names(mut self, names: Vec<String>) -> Self {
+        self.calendar_names = names;
+        self
+    }
+
+    pub fn with_report_patterns(mut self, patterns: Vec<TitlePattern>) -> Self {
+        self.report_patterns = patterns;
+        self
+    }
+
+    pub fn with_day_grid(mut self, day_grid: DayGrid) -> Self {
+        self.day_grid = day_grid;
+        self
+    }
+
+    pub fn with_declined_events(mut self, declined_events: DeclinedEvents) -> Self {
+        self.declined_events = declined_events;
+        self
+    }
+
+    pub fn with_default_calendar(mut self, calendar_id: String) -> Self {
+        self.default_calendar = calendar_id;
+        self
+    }
+
+    /// Hides every calendar in `calendar_names` that is not in `visible`.
+    pub fn with_visible_calendars(mut self, visible: &[String]) -> Self {
+        self.hidden_calendars = self.calendar_names.iter()
+            .filter(|name| !visible.contains(name))
+            .cloned()
+            .collect();
+        self
+    }
+
+    pub fn is_calendar_visible(&self, calendar_id: &str) -> bool {
+        !self.hidden_calendars.contains(calendar_id)
+    }
+
+    pub fn toggle_calendar_visibility(&mut self, calendar_id: &str) {
+        if !self.hidden_calendars.remove(calendar_id) {
+            self.hidden_calendars.insert(calendar_id.to_string());
+        }
+        self.reset_event_selection();
+    }
+
+    pub fn visible_calendars(&self) -> Vec<String> {
+        self.known_calendars()
+            .into_iter()
+            .filter(|name| self.is_calendar_visible(name))
+            .collect()
+    }
+
+    /// Configured calendars plus any seen on loaded events, sorted.
+    pub fn known_calendars(&self) -> Vec<String> {
+        let mut names: Vec<String> = self.calendar_names.iter()
+            .cloned()
+            .chain(self.events.values().map(|event| event.calendar_id.clone()))
+            .collect();
+        names.sort();
+        names.dedup();
+        names
+    }
+
+    /// Records the fetched calendar list: names for completion and roles
+    /// for `calendar_access_role`.
+    pub fn add_calendars(&mut self, calendars: Vec<Calendar>) {
+        for calendar in calendars {
+            self.calendar_access.insert(calendar.id.clone(), calendar.access_role);
+            self.calendar_names.push(calendar.id);
+        }
+    }
+
+    /// The role from the calendar list. Subscribed overlays are public
+    /// calendars and `.ics` feeds are never written back, so both are
+    /// read-only even before the list arrives.
+    pub fn calendar_access_role(&self, calendar_id: &str) -> Option<AccessRole> {
+        self.calendar_access.get(calendar_id).cloned()
+            .or_else(|| {
+                (overlay::is_overlay(calendar_id) || subscription::is_subscription(calendar_id)).then_some(AccessRole::Reader)
+            })
+    }
+
+    /// Unknown calendars are assumed editable and left to the server to refuse.
+    pub fn can_edit_calendar(&self, calendar_id: &str) -> bool {
+        self.calendar_access_role(calendar_id).is_none_or(|role| role.can_edit())
+    }
+
+    /// Shows why `calendar_id` cannot be edited and returns true, or returns
+    /// false when it can.
+    pub fn refuse_read_only_calendar(&mut self, calendar_id: &str) -> bool {
+        match self.calendar_access_role(calendar_id) {
+            Some(role) if !role.can_edit() => {
+                self.set_message(MessageKind::Error, format!("{} is read-only ({})", calendar_id, role.label()));
+                true
+            }
+            _ => false,
+        }
+    }
+
+    pub fn set_message(&mut self, kind: MessageKind, text: impl Into<String>) {
+        let text = text.into();
+        self.log_activity(kind, text.clone());
+        self.status_message = Some(StatusMessage { text, kind });
+    }
+
+    /// Records `text` in the activity panel without showing it in the status bar.
+    pub fn log_activity(&mut self, kind: MessageKind, text: impl Into<String>) {
+        let now = self.now();
+        self.activity.push(now, kind, text);
+    }
+
+    pub fn clear_message(&mut self) {
+        self.status_message = None;
+    }
+
     pub fn add_event(&mut self, event: Event) {
-        self.events.insert(event.id.clone(), event);
+        for email in &event.attendees {
+            self.contacts.record(email, event.start);
+        }
+        let id = event.id.clone();
+        self.unindex_event(&id);
+        self.events.insert(id.clone(), event);
+        self.index_event(&id);
+        self.events_version += 1;
+    }
+
+    pub fn clear_events(&mut self) {
+        self.events.clear();
+        self.date_index.clear();
+        self.events_version += 1;
+    }
+
+    /// Swaps in a fresh fetch of one calendar for `start..=end`, dropping
+    /// its events that are gone from the server.
+    pub fn replace_calendar_events(&mut self, calendar_id: &str, start: NaiveDate, end: NaiveDate, events: Vec<Event>) {
+        let stale: Vec<String> = self.events.values()
+            .filter(|event| event.calendar_id == calendar_id)
+            .filter(|event| {
+                let (first, last) = event.local_dates(&self.timezone);
+                first <= end && last >= start
+            })
+            .map(|event| event.id.clone())
+            .collect();
+        for id in stale {
+            self.remove_event(&id);
+        }
+        for event in events {
+            self.add_event(event);
+        }
+    }
+
+    pub fn events_version(&self) -> u64 {
+        self.events_version
     }
 
+    fn index_event(&mut self, event_id: &str) {
+        let Some(event) = self.events.get(event_id) else { return };
+        let (first, last) = event.local_dates(&self.timezone);
+        for date in first.iter_days().take_while(|date| *date <= last) {
+            self.date_index.entry(date).or_default().push(event_id.to_string());
+        }
+    }
+
+    fn unindex_event(&mut self, event_id: &str) {
+        let Some(event) = self.events.get(event_id) else { return };
+        let (first, last) = event.local_dates(&self.timezone);
+        for date in first.iter_days().take_while(|date| *date <= last) {
+            if let Some(ids) = self.date_index.get_mut(&date) {
+                ids.retain(|id| id != event_id);
+                if ids.is_empty() {
+                    self.date_index.remove(&date);
+                }
+            }
+        }
+    }
+
+    fn is_shown(&self, event: &Event) -> bool {
+        self.is_calendar_visible(&event.calendar_id)
+            && !(self.declined_events == DeclinedEvents::Hide && event.is_declined())
+    }
+
+    fn visible_events(&self) -> impl Iterator<Item = &Event> {
+        self.events.values().filter(|event| self.is_shown(event))
+    }
+
+    /// Events whose `[start, end)` range intersects `date` in the display
+    /// timezone, in start order.
     pub fn get_events_for_date(&self, date: NaiveDate) -> Vec<&Event> {
-        let mut events: Vec<&Event> = self.events
-            .values()
-            .filter(|event| event.start.date_naive() == date)
+        let mut events: Vec<&Event> = self.date_index.get(&date)
+            .into_iter()
+            .flatten()
+            .filter_map(|id| self.events.get(id))
+            .filter(|event| self.is_shown(event))
+            .collect();
+        events.sort_by_key(|e| e.start);
+        events
+    }
+
+    /// Events starting on any day from `start` through `end`, in start order.
+    pub fn events_in_range(&self, start: NaiveDate, end: NaiveDate) -> Vec<&Event> {
+        if start > end {
+            return Vec::new();
+        }
+        // Multi-day events sit under several dates; keep them only under
+        // the one they start on.
+        let mut events: Vec<&Event> = self.date_index.range(start..=end)
+            .flat_map(|(date, ids)| ids.iter().map(move |id| (*date, id)))
+            .filter_map(|(date, id)| self.events.get(id).filter(|e| e.local_start(&self.timezone).date() == date))
+            .filter(|event| self.is_shown(event))
             .collect();
         events.sort_by_key(|e| e.start);
         events
     }
 
+    /// Opens the `:report` table for shown events from `start` through `end`.
+    /// Events that only overlap the range count for the part inside it.
+    pub fn open_report(&mut self, start: NaiveDate, end: NaiveDate) {
+        let range_start = start.and_time(chrono::NaiveTime::MIN);
+        let range_end = end.succ_opt().unwrap_or(end).and_time(chrono::NaiveTime::MIN);
+        let events: Vec<&Event> = self.events.values()
+            .filter(|event| self.is_shown(event))
+            .filter(|event| event.local_start(&self.timezone) < range_end && event.local_end(&self.timezone) > range_start)
+            .collect();
+        self.report = Some(build_report(&events, start, end, &self.report_patterns, &self.timezone));
+    }
+
+    /// Tight hops between places among shown events starting from `start`
+    /// through `end`; empty while `travel_buffer_minutes` is unset.
+    pub fn travel_warnings_in_range(&self, start: NaiveDate, end: NaiveDate) -> Vec<TravelWarning> {
+        let Some(buffer) = self.travel_buffer_minutes else {
+            return Vec::new();
+        };
+        travel_warnings(&self.events_in_range(start, end), buffer, &self.timezone)
+    }
+
+    /// Minutes to get to each flagged event, by event id, for the views.
+    pub fn travel_gaps(&self, start: NaiveDate, end: NaiveDate) -> HashMap<String, i64> {
+        self.travel_warnings_in_range(start, end)
+            .into_iter()
+            .map(|warning| (warning.to_id, warning.gap_minutes))
+            .collect()
+    }
+
+    /// Opens the `:conflicts` list, or says why it cannot.
+    pub fn open_travel_report(&mut self, start: NaiveDate, end: NaiveDate) -> Result<(), String> {
+        let buffer_minutes = self.travel_buffer_minutes
+            .ok_or("Travel warnings are off; :set travel_buffer_minutes=15 to turn them on")?;
+        let warnings = self.travel_warnings_in_range(start, end);
+        self.travel_report = Some(TravelReport { start, end, buffer_minutes, warnings });
+        Ok(())
+    }
+
+    /// Ids of shown timed events on `date` that are double-booked with
+    /// another one. Free, cancelled and declined events do not count.
+    pub fn overlapping_on(&self, date: NaiveDate) -> HashSet<String> {
+        let events: Vec<&Event> = self.get_events_for_date(date)
+            .into_iter()
+            .filter(|e| !e.all_day && e.is_busy() && !e.is_declined())
+            .collect();
+        events.iter()
+            .filter(|event| events.iter().any(|other| other.id != event.id && event.overlaps(other)))
+            .map(|event| event.id.clone())
+            .collect()
+    }
+
+    /// Timed events that overlap `event`, ignoring itself, all-day events and
+    /// those that are not busy (cancelled or shown as free).
+    pub fn conflicts_with(&self, event: &Event) -> Vec<&Event> {
+        if event.all_day || !event.is_busy() {
+            return Vec::new();
+        }
+
+        let mut conflicts: Vec<&Event> = self.events
+            .values()
+            .filter(|other| other.id != event.id && !other.all_day && other.is_busy())
+            .filter(|other| event.overlaps(other))
+            .collect();
+        conflicts.sort_by_key(|e| e.start);
+        conflicts
+    }
+
     pub fn get_selected_event(&self) -> Option<&Event> {
         let events = self.get_events_for_date(self.selected_date);
         events.get(self.selected_event_index).copied()
     }
 
+    /// The selected event when it has a meeting link, otherwise the next
+    /// event with one that has not ended yet.
+    pub fn meeting_event(&self, now: DateTime<Utc>) -> Option<&Event> {
+        if let Some(event) = self.get_selected_event()
+            && event.meeting_url().is_some()
+        {
+            return Some(event);
+        }
+
+        self.visible_events()
+            .filter(|e| e.end > now && e.status != EventStatus::Cancelled && e.meeting_url().is_some())
+            .min_by_key(|e| e.start)
+    }
+
     pub fn move_event_selection_down(&mut self) {
         let event_count = self.get_events_for_date(self.selected_date).len();
         if event_count > 0 && self.selected_event_index < event_count - 1 {
@@ -287,12 +1380,91 @@ impl AppState {
         }
     }
 
+    /// Steps through the selected day's events, wrapping at either end
+    /// (Tab/Shift-Tab).
+    pub fn cycle_event_selection(&mut self, direction: i32) {
+        let event_count = self.get_events_for_date(self.selected_date).len();
+        if event_count == 0 {
+            return;
+        }
+        self.selected_event_index = (self.selected_event_index as i64 + direction as i64)
+            .rem_euclid(event_count as i64) as usize;
+        self.sync_event_list_scroll();
+    }
+
+    /// Moves the selection by a pane's worth of events (PageDown/PageUp).
+    pub fn page_event_selection(&mut self, direction: i32) {
+        let heights = self.event_list_heights();
+        if heights.is_empty() {
+            return;
+        }
+        let page = event_list_view::events_per_page(&heights, self.event_list_scroll, self.event_list_visible_lines);
+        self.selected_event_index = if direction > 0 {
+            (self.selected_event_index + page).min(heights.len() - 1)
+        } else {
+            self.selected_event_index.saturating_sub(page)
+        };
+        self.sync_event_list_scroll();
+    }
+
+    /// Scrolls the event list so the selected event stays in view.
+    pub fn sync_event_list_scroll(&mut self) {
+        let heights = self.event_list_heights();
+        self.event_list_scroll = event_list_view::scroll_for_selection(
+            &heights,
+            self.selected_event_index,
+            self.event_list_scroll,
+            self.event_list_visible_lines,
+        );
+    }
+
+    fn event_list_heights(&self) -> Vec<usize> {
+        self.get_events_for_date(self.selected_date)
+            .into_iter()
+            .map(event_list_view::event_height)
+            .collect()
+    }
+
+    pub fn scroll_week_grid(&mut self, hours: i32) {
+        let target = self.week_scroll_hour as i32 + hours;
+        self.week_scroll_hour = target.clamp(0, week_view::MAX_SCROLL_HOUR as i32) as u32;
+    }
+
+    /// Adds `event`, selects it on its date and opens its detail view.
+    pub fn show_event(&mut self, event: Event) {
+        let id = event.id.clone();
+        self.selected_date = event.local_start(&self.timezone).date();
+        self.add_event(event);
+        self.selected_event_index = self.get_events_for_date(self.selected_date).iter()
+            .position(|e| e.id == id)
+            .unwrap_or(0);
+        self.sync_event_list_scroll();
+        self.detail_view_event_id = Some(id);
+        self.detail_view_draft = None;
+        self.detail_view_hints = None;
+        self.detail_view_scroll = 0;
+        self.detail_view_cursor_line = 0;
+        self.detail_view_cursor_col = 0;
+    }
+
     pub fn reset_event_selection(&mut self) {
         self.selected_event_index = 0;
+        self.event_list_scroll = 0;
     }
 
     pub fn remove_event(&mut self, event_id: &str) {
+        self.unindex_event(event_id);
         self.events.remove(event_id);
+        self.events_version += 1;
+    }
+
+    pub fn selected_task(&self) -> Option<&Task> {
+        self.tasks.get(self.task_index)
+    }
+
+    pub fn remove_task(&mut self, task_id: &str) {
+        self.tasks.retain(|task| task.id != task_id);
+        self.task_index = self.task_index.min(self.tasks.len().saturating_sub(1));
     }
 
     pub fn get_visual_selection_range(&self) -> Option<(NaiveDate, NaiveDate)> {
@@ -321,7 +1493,7 @@ impl Default for AppState {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::calendar::DEFAULT_CALENDAR_ID;
+    use crate::test_support::EventBuilder;
     use chrono::{TimeZone, Utc};
 
     fn create_event_at(id: &str, date: NaiveDate, hour: u32) -> Event {
@@ -340,6 +1512,17 @@ mod tests {
             status: crate::calendar::EventStatus::Confirmed,
             last_modified: Utc::now(),
             html_link: None,
+            time_zone: None,
+            conference_url: None,
+            color_id: None,
+            color: None,
+            self_response: None,
+            attendee_responses: Default::default(),
+            visibility: Visibility::Default,
+            transparency: Transparency::Opaque,
+            etag: None,
+            organizer: None,
+            creator: None,
         }
     }
 
@@ -358,7 +1541,7 @@ mod tests {
     #[test]
     fn new_app_selects_today() {
         let app = AppState::new();
-        assert_eq!(app.selected_date, Local::now().date_naive());
+        assert_eq!(app.selected_date, chrono::Local::now().date_naive());
     }
 
     #[test]
@@ -388,6 +1571,17 @@ mod tests {
             status: crate::calendar::EventStatus::Confirmed,
             last_modified: Utc::now(),
             html_link: None,
+            time_zone: None,
+            conference_url: None,
+            color_id: None,
+            color: None,
+            self_response: None,
+            attendee_responses: Default::default(),
+            visibility: Visibility::Default,
+            transparency: Transparency::Opaque,
+            etag: None,
+            organizer: None,
+            creator: None,
         };
 
         app.add_event(event.clone());
@@ -410,4 +1604,548 @@ mod tests {
 
         assert_eq!(events.len(), 2);
     }
+
+    #[test]
+    fn get_events_for_date_uses_display_timezone() {
+        let mut app = AppState::new()
+            .with_timezone(DisplayTimezone::Named(chrono_tz::Asia::Tokyo));
+        let date = NaiveDate::from_ymd_opt(2025, 1, 15).unwrap();
+        app.add_event(create_event_at("late", date, 20));
+
+        assert!(app.get_events_for_date(date).is_empty());
+        assert_eq!(app.get_events_for_date(date.succ_opt().unwrap()).len(), 1);
+    }
+
+    #[test]
+    fn form_for_event_shows_local_start_time() {
+        let date = NaiveDate::from_ymd_opt(2025, 1, 15).unwrap();
+        let event = create_event_at("e1", date, 17);
+        let tz = DisplayTimezone::Named(chrono_tz::America::Los_Angeles);
+
+        let form = EventForm::for_event(&event, &tz);
+
+        assert_eq!(form.start_hour, 9);
+        assert_eq!(form.time_range(&tz).map(|(start, _)| start), Some(event.start));
+    }
+
+    #[test]
+    fn form_edits_the_link_apart_from_the_description() {
+        let date = NaiveDate::from_ymd_opt(2025, 1, 15).unwrap();
+        let mut event = create_event_at("e1", date, 17);
+        event.description = Some("Agenda\n\nLink: https://zoom.us/j/1".to_string());
+
+        let mut form = EventForm::for_event(&event, &DisplayTimezone::Named(chrono_tz::UTC));
+        assert_eq!(form.description, "Agenda");
+        assert_eq!(form.link, "https://zoom.us/j/1");
+
+        form.link = "https://zoom.us/j/2".to_string();
+        assert_eq!(form.description_with_link().as_deref(), Some("Agenda\n\nLink: https://zoom.us/j/2"));
+    }
+
+    fn blank_form(date: NaiveDate, title: &str) -> EventForm {
+        EventForm::new(date, title.to_string(), date.and_hms_opt(9, 40, 0).unwrap())
+    }
+
+    #[test]
+    fn new_form_starts_at_the_current_hour() {
+        let form = blank_form(NaiveDate::from_ymd_opt(2025, 1, 15).unwrap(), "Sync");
+
+        assert_eq!((form.start_hour, form.start_minute), (9, 0));
+        assert_eq!(form.time_input_buffer, "09:00");
+    }
+
+    #[test]
+    fn attendee_suggestion_completes_the_last_address() {
+        let contacts = vec!["sam@example.com".to_string(), "alex@example.com".to_string()];
+        let mut form = blank_form(NaiveDate::from_ymd_opt(2025, 1, 15).unwrap(), "Sync");
+
+        form.attendees = "sam@example.com, A".to_string();
+        assert_eq!(form.attendee_suggestion(&contacts), Some("alex@example.com"));
+
+        form.accept_attendee_suggestion(&contacts);
+        assert_eq!(form.attendee_list(), vec!["sam@example.com", "alex@example.com"]);
+
+        form.attendees = "alex@example.com, s".to_string();
+        assert_eq!(form.attendee_suggestion(&contacts), Some("sam@example.com"));
+        form.attendees = "sam@example.com, s".to_string();
+        assert_eq!(form.attendee_suggestion(&contacts), None);
+    }
+
+    #[test]
+    fn added_events_feed_the_contact_list() {
+        let date = NaiveDate::from_ymd_opt(2025, 1, 15).unwrap();
+        let mut app = AppState::new().with_contacts(Contacts::from_iter(["sam@example.com"]));
+        let mut event = create_event_at("e1", date, 9);
+        event.attendees = vec!["Sam@Example.com".to_string(), "kim@example.com".to_string()];
+
+        app.add_event(event);
+
+        assert_eq!(app.contacts.emails(), ["sam@example.com", "kim@example.com"]);
+    }
+
+    #[test]
+    fn meeting_event_prefers_selected_event() {
+        let date = NaiveDate::from_ymd_opt(2025, 1, 15).unwrap();
+        let mut app = AppState::new();
+        app.selected_date = date;
+        let mut selected = create_event_at("selected", date, 9);
+        selected.conference_url = Some("https://meet.google.com/aaa".to_string());
+        let mut later = create_event_at("later", date, 11);
+        later.conference_url = Some("https://meet.google.com/bbb".to_string());
+        app.add_event(selected);
+        app.add_event(later);
+
+        let now = date.and_hms_opt(10, 0, 0).unwrap().and_utc();
+
+        assert_eq!(app.meeting_event(now).map(|e| e.id.as_str()), Some("selected"));
+    }
+
+    #[test]
+    fn meeting_event_falls_back_to_next_upcoming() {
+        let date = NaiveDate::from_ymd_opt(2025, 1, 15).unwrap();
+        let mut app = AppState::new();
+        app.selected_date = date;
+        app.add_event(create_event_at("no-link", date, 8));
+        let mut past = create_event_at("past", date, 9);
+        past.conference_url = Some("https://meet.google.com/aaa".to_string());
+        let mut next = create_event_at("next", date, 14);
+        next.description = Some("https://zoom.us/j/1".to_string());
+        app.add_event(past);
+        app.add_event(next);
+
+        let now = date.and_hms_opt(12, 0, 0).unwrap().and_utc();
+
+        assert_eq!(app.meeting_event(now).map(|e| e.id.as_str()), Some("next"));
+    }
+
+    #[test]
+    fn form_from_quick_add_prefills_fields() {
+        let today = NaiveDate::from_ymd_opt(2025, 1, 15).unwrap();
+        let quick_add = crate::calendar::quick_add::parse_quick_add("Standup tomorrow 9am 15m", today).unwrap();
+
+        let form = EventForm::from_quick_add(&quick_add, today, today.and_hms_opt(8, 30, 0).unwrap());
+
+        assert_eq!(form.title, "Standup");
+        assert_eq!(form.date, NaiveDate::from_ymd_opt(2025, 1, 16).unwrap());
+        assert_eq!((form.start_hour, form.start_minute), (9, 0));
+        assert_eq!(form.time_input_buffer, "09:00");
+        assert_eq!(form.duration_minutes, 15);
+        assert!(!form.all_day);
+    }
+
+    #[test]
+    fn form_from_quick_add_without_date_uses_default() {
+        let today = NaiveDate::from_ymd_opt(2025, 1, 15).unwrap();
+        let selected = NaiveDate::from_ymd_opt(2025, 2, 3).unwrap();
+        let quick_add = crate::calendar::quick_add::parse_quick_add("Team meeting", today).unwrap();
+
+        let form = EventForm::from_quick_add(&quick_add, selected, today.and_hms_opt(8, 30, 0).unwrap());
+
+        assert_eq!(form.title, "Team meeting");
+        assert_eq!(form.date, selected);
+        assert_eq!(form.start_hour, 8);
+        assert_eq!(form.duration_minutes, 60);
+    }
+
+    #[test]
+    fn duration_accepts_an_end_time() {
+        let start = NaiveDate::from_ymd_opt(2025, 1, 15).unwrap().and_hms_opt(16, 0, 0).unwrap();
+        let mut form = EventForm::for_slot(start, 60);
+
+        form.duration_input_buffer = "-17:30".to_string();
+        form.parse_duration_input();
+        assert_eq!(form.duration_minutes, 90);
+        assert_eq!(form.duration_input_buffer, "90");
+        assert_eq!(form.end_time(), (17, 30));
+
+        form.duration_input_buffer = "until 0100".to_string();
+        form.parse_duration_input();
+        assert_eq!(form.duration_minutes, 540);
+    }
+
+    #[test]
+    fn validate_reports_each_bad_field() {
+        let tz = DisplayTimezone::Named(chrono_tz::UTC);
+        let mut form = blank_form(NaiveDate::from_ymd_opt(2025, 1, 15).unwrap(), "  ");
+        form.date_input_buffer = "someday".to_string();
+        form.time_input_buffer = "2561".to_string();
+        form.duration_input_buffer = "0".to_string();
+
+        let fields: Vec<FormField> = form.validate(&tz, form.date).into_iter().map(|(field, _)| field).collect();
+
+        assert_eq!(fields, vec![FormField::Title, FormField::Date, FormField::StartTime, FormField::Duration]);
+    }
+
+    #[test]
+    fn validate_accepts_a_complete_form() {
+        let tz = DisplayTimezone::Named(chrono_tz::UTC);
+        let mut form = blank_form(NaiveDate::from_ymd_opt(2025, 1, 15).unwrap(), "Review");
+        form.time_input_buffer = "930".to_string();
+        form.duration_input_buffer = "until 10:15".to_string();
+
+        assert!(form.validate(&tz, form.date).is_empty());
+    }
+
+    #[test]
+    fn validate_rejects_a_time_skipped_by_dst() {
+        let tz = DisplayTimezone::Named(chrono_tz::America::New_York);
+        let mut form = blank_form(NaiveDate::from_ymd_opt(2025, 3, 9).unwrap(), "Early");
+        form.time_input_buffer = "02:30".to_string();
+
+        assert_eq!(form.validate(&tz, form.date).first().map(|(field, _)| field), Some(&FormField::StartTime));
+    }
+
+    #[test]
+    fn set_message_replaces_previous_message() {
+        let mut app = AppState::new();
+
+        app.set_message(MessageKind::Error, "Unknown command: thme");
+        app.set_message(MessageKind::Success, "Event created");
+
+        assert_eq!(app.status_message, Some(StatusMessage { text: "Event created".to_string(), kind: MessageKind::Success }));
+
+        app.clear_message();
+        assert_eq!(app.status_message, None);
+    }
+
+    #[test]
+    fn set_ui_option_validates_before_applying() {
+        let mut app = AppState::new();
+
+        app.set_ui_option("working_hours", "8-16").unwrap();
+        app.set_ui_option("default_view", "week").unwrap();
+        assert_eq!(app.working_hours, WorkingHours::parse("8-16").unwrap());
+        assert_eq!(app.view, ViewType::Week);
+        assert_eq!(app.ui_config.working_hours.as_deref(), Some("8-16"));
+        app.set_ui_option("map_provider", "google").unwrap();
+        assert_eq!(app.map_provider, MapProvider::Google);
+
+        assert!(app.set_ui_option("theme", "no-such-theme").is_err());
+        assert!(app.set_ui_option("show_week_numbers", "maybe").is_err());
+        assert!(app.set_ui_option("map_provider", "bing").is_err());
+        assert!(app.set_ui_option("colour", "red").unwrap_err().starts_with("Unknown option"));
+        assert_eq!(app.ui_config.theme, "default");
+    }
+
+    #[test]
+    fn time_and_week_options_are_validated_and_applied() {
+        let mut app = AppState::new();
+
+        app.set_ui_option("time_format", "12h").unwrap();
+        app.set_ui_option("first_day_of_week", "sunday").unwrap();
+        app.set_ui_option("date_format", "%d/%m/%Y").unwrap();
+        assert_eq!(app.time_format, TimeFormat::TwelveHour);
+        assert_eq!(app.week_start, chrono::Weekday::Sun);
+        assert_eq!(app.ui_config.date_format, "%d/%m/%Y");
+
+        assert!(app.set_ui_option("time_format", "25h").is_err());
+        assert!(app.set_ui_option("first_day_of_week", "someday").is_err());
+        assert!(app.set_ui_option("date_format", "%Q").is_err());
+        assert!(app.set_ui_option("date_format", "").is_err());
+        assert_eq!(app.ui_config.date_format, "%d/%m/%Y");
+    }
+
+    #[test]
+    fn view_names_round_trip() {
+        for view in [ViewType::Month, ViewType::Week, ViewType::Day, ViewType::Year, ViewType::Split] {
+            assert_eq!(ViewType::from_config(view.name()), view);
+        }
+        assert_eq!(ViewType::from_config("week"), ViewType::Week);
+        assert_eq!(ViewType::from_config("agenda"), ViewType::Month);
+    }
+
+    #[test]
+    fn cleared_messages_stay_in_the_activity_log() {
+        let mut app = AppState::new();
+
+        app.set_message(MessageKind::Error, "Failed to create event");
+        app.clear_message();
+        app.log_activity(MessageKind::Info, "Synced 3 events");
+
+        let texts: Vec<&str> = app.activity.entries().map(|entry| entry.text.as_str()).collect();
+        assert_eq!(texts, vec!["Synced 3 events", "Failed to create event"]);
+    }
+
+    #[test]
+    fn events_in_range_includes_both_ends() {
+        let mut app = AppState::new();
+        let day = |d| NaiveDate::from_ymd_opt(2025, 1, d).unwrap();
+
+        app.add_event(create_event_at("before", day(9), 9));
+        app.add_event(create_event_at("first", day(10), 9));
+        app.add_event(create_event_at("last", day(12), 9));
+        app.add_event(create_event_at("after", day(13), 9));
+
+        let ids: Vec<&str> = app.events_in_range(day(10), day(12)).iter().map(|e| e.id.as_str()).collect();
+
+        assert_eq!(ids, vec!["first", "last"]);
+    }
+
+    #[test]
+    fn conflicts_with_skips_itself_all_day_and_cancelled() {
+        let mut app = AppState::new();
+        let date = NaiveDate::from_ymd_opt(2025, 1, 15).unwrap();
+
+        app.add_event(create_event_at("same", date, 14));
+        app.add_event(create_event_at("overlap", date, 14));
+        app.add_event(create_event_at("adjacent", date, 15));
+        let mut all_day = create_event_at("all-day", date, 0);
+        all_day.all_day = true;
+        all_day.end = all_day.start + chrono::Duration::days(1);
+        app.add_event(all_day);
+        let mut cancelled = create_event_at("cancelled", date, 14);
+        cancelled.status = EventStatus::Cancelled;
+        app.add_event(cancelled);
+
+        let event = app.events["same"].clone();
+        let ids: Vec<&str> = app.conflicts_with(&event).iter().map(|e| e.id.as_str()).collect();
+
+        assert_eq!(ids, vec!["overlap"]);
+    }
+
+    #[test]
+    fn hidden_calendars_are_filtered_from_views() {
+        let date = NaiveDate::from_ymd_opt(2025, 1, 15).unwrap();
+        let mut app = AppState::new()
+            .with_calendar_names(vec!["primary".to_string(), "work".to_string()])
+            .with_visible_calendars(&["primary".to_string()]);
+        app.add_event(create_event_at("mine", date, 9));
+        let mut work = create_event_at("work", date, 10);
+        work.calendar_id = "work".to_string();
+        app.add_event(work);
+
+        let ids: Vec<&str> = app.get_events_for_date(date).iter().map(|e| e.id.as_str()).collect();
+        assert_eq!(ids, vec!["mine"]);
+
+        app.toggle_calendar_visibility("work");
+        assert_eq!(app.get_events_for_date(date).len(), 2);
+        assert_eq!(app.visible_calendars(), vec!["primary", "work"]);
+    }
+
+    #[test]
+    fn declined_events_are_hidden_only_in_hide_mode() {
+        let date = NaiveDate::from_ymd_opt(2025, 1, 15).unwrap();
+        let mut app = AppState::new();
+        let mut declined = create_event_at("declined", date, 9);
+        declined.self_response = Some(crate::calendar::ResponseStatus::Declined);
+        app.add_event(declined);
+        app.add_event(create_event_at("accepted", date, 10));
+
+        assert_eq!(app.get_events_for_date(date).len(), 2);
+
+        app.declined_events = app.declined_events.toggled();
+        let ids: Vec<&str> = app.get_events_for_date(date).iter().map(|e| e.id.as_str()).collect();
+        assert_eq!(ids, vec!["accepted"]);
+    }
+
+    fn timed_event(id: &str, start: DateTime<Utc>, end: DateTime<Utc>) -> Event {
+        Event { start, end, ..create_event_at(id, start.date_naive(), 0) }
+    }
+
+    fn ids_on(app: &AppState, date: NaiveDate) -> Vec<&str> {
+        app.get_events_for_date(date).iter().map(|e| e.id.as_str()).collect()
+    }
+
+    #[test]
+    fn multi_day_all_day_event_shows_on_every_day() {
+        let day = |d| NaiveDate::from_ymd_opt(2025, 1, d).unwrap();
+        let mut app = AppState::new()
+            .with_timezone(DisplayTimezone::Named(chrono_tz::America::Los_Angeles));
+        let mut conference = create_event_at("conf", day(15), 0);
+        conference.all_day = true;
+        conference.end = conference.start + chrono::Duration::days(3);
+        app.add_event(conference);
+
+        assert_eq!(ids_on(&app, day(14)), Vec::<&str>::new());
+        assert_eq!(ids_on(&app, day(15)), vec!["conf"]);
+        assert_eq!(ids_on(&app, day(17)), vec!["conf"]);
+        assert_eq!(ids_on(&app, day(18)), Vec::<&str>::new());
+    }
+
+    #[test]
+    fn event_crossing_local_midnight_shows_on_both_days() {
+        let day = |d| NaiveDate::from_ymd_opt(2025, 1, d).unwrap();
+        let start = Utc.with_ymd_and_hms(2025, 1, 15, 14, 0, 0).unwrap();
+        let mut app = AppState::new()
+            .with_timezone(DisplayTimezone::Named(chrono_tz::Asia::Tokyo));
+        app.add_event(timed_event("late", start, start + chrono::Duration::hours(2)));
+
+        assert_eq!(ids_on(&app, day(15)), vec!["late"]);
+        assert_eq!(ids_on(&app, day(16)), vec!["late"]);
+
+        app.set_timezone(DisplayTimezone::Named(chrono_tz::UTC));
+        assert_eq!(ids_on(&app, day(15)), vec!["late"]);
+        assert_eq!(ids_on(&app, day(16)), Vec::<&str>::new());
+    }
+
+    #[test]
+    fn event_ending_at_local_midnight_stays_on_its_day() {
+        let day = |d| NaiveDate::from_ymd_opt(2025, 1, d).unwrap();
+        // 22:00-00:00 in New York.
+        let start = Utc.with_ymd_and_hms(2025, 1, 16, 3, 0, 0).unwrap();
+        let mut app = AppState::new()
+            .with_timezone(DisplayTimezone::Named(chrono_tz::America::New_York));
+        app.add_event(timed_event("party", start, start + chrono::Duration::hours(2)));
+
+        assert_eq!(ids_on(&app, day(15)), vec!["party"]);
+        assert_eq!(ids_on(&app, day(16)), Vec::<&str>::new());
+    }
+
+    #[test]
+    fn event_ending_where_a_dst_gap_starts_the_day_stays_on_its_day() {
+        // Sao Paulo skipped from 00:00 to 01:00 on 2018-11-04, so that day
+        // began at 03:00 UTC.
+        let start = Utc.with_ymd_and_hms(2018, 11, 4, 1, 0, 0).unwrap();
+        let mut app = AppState::new()
+            .with_timezone(DisplayTimezone::Named(chrono_tz::America::Sao_Paulo));
+        app.add_event(timed_event("late", start, start + chrono::Duration::hours(2)));
+
+        assert_eq!(ids_on(&app, NaiveDate::from_ymd_opt(2018, 11, 3).unwrap()), vec!["late"]);
+        assert_eq!(ids_on(&app, NaiveDate::from_ymd_opt(2018, 11, 4).unwrap()), Vec::<&str>::new());
+    }
+
+    #[test]
+    fn zero_length_event_shows_on_its_start_day() {
+        let day = |d| NaiveDate::from_ymd_opt(2025, 1, d).unwrap();
+        let start = Utc.with_ymd_and_hms(2025, 1, 15, 0, 0, 0).unwrap();
+        let mut app = AppState::new()
+            .with_timezone(DisplayTimezone::Named(chrono_tz::UTC));
+        app.add_event(timed_event("marker", start, start));
+
+        assert_eq!(ids_on(&app, day(14)), Vec::<&str>::new());
+        assert_eq!(ids_on(&app, day(15)), vec!["marker"]);
+    }
+
+    #[test]
+    fn date_index_follows_updates_and_removal() {
+        let day = |d| NaiveDate::from_ymd_opt(2025, 1, d).unwrap();
+        let mut app = AppState::new()
+            .with_timezone(DisplayTimezone::Named(chrono_tz::UTC));
+        app.add_event(create_event_at("e1", day(15), 9));
+        app.add_event(create_event_at("e1", day(16), 9));
+
+        assert_eq!(ids_on(&app, day(15)), Vec::<&str>::new());
+        assert_eq!(ids_on(&app, day(16)), vec!["e1"]);
+
+        app.remove_event("e1");
+        assert_eq!(ids_on(&app, day(16)), Vec::<&str>::new());
+        assert!(app.date_index.is_empty());
+    }
+
+    #[test]
+    fn date_index_is_rebuilt_for_a_new_timezone() {
+        let day = |d| NaiveDate::from_ymd_opt(2025, 1, d).unwrap();
+        let mut app = AppState::new()
+            .with_timezone(DisplayTimezone::Named(chrono_tz::UTC));
+        app.add_event(create_event_at("late", day(15), 20));
+
+        app.set_timezone(DisplayTimezone::Named(chrono_tz::Asia::Tokyo));
+
+        assert_eq!(ids_on(&app, day(15)), Vec::<&str>::new());
+        assert_eq!(ids_on(&app, day(16)), vec!["late"]);
+    }
+
+    #[test]
+    fn events_in_range_lists_multi_day_events_once() {
+        let day = |d| NaiveDate::from_ymd_opt(2025, 1, d).unwrap();
+        let mut app = AppState::new()
+            .with_timezone(DisplayTimezone::Named(chrono_tz::UTC));
+        let mut trip = create_event_at("trip", day(10), 9);
+        trip.end = trip.start + chrono::Duration::days(3);
+        app.add_event(trip);
+        let mut earlier = create_event_at("earlier", day(8), 9);
+        earlier.end = earlier.start + chrono::Duration::days(3);
+        app.add_event(earlier);
+
+        let ids: Vec<&str> = app.events_in_range(day(9), day(14)).iter().map(|e| e.id.as_str()).collect();
+        assert_eq!(ids, vec!["trip"]);
+        assert!(app.events_in_range(day(14), day(9)).is_empty());
+    }
+
+    #[test]
+    fn reports_count_the_part_of_overlapping_events_inside_the_range() {
+        let day = |d| NaiveDate::from_ymd_opt(2025, 1, d).unwrap();
+        let mut app = AppState::new()
+            .with_timezone(DisplayTimezone::Named(chrono_tz::UTC));
+        let overnight = EventBuilder::new("overnight")
+            .at(day(9).and_hms_opt(22, 0, 0).unwrap().and_utc(), chrono::Duration::hours(4))
+            .build();
+        app.add_event(overnight);
+        app.add_event(create_event_at("inside", day(10), 9));
+
+        app.open_report(day(10), day(10));
+
+        let report = app.report.unwrap();
+        assert_eq!((report.events, report.total_minutes), (2, 180));
+    }
+
+    #[test]
+    fn show_event_selects_it_on_its_date_and_opens_details() {
+        let day = |d| NaiveDate::from_ymd_opt(2025, 1, d).unwrap();
+        let mut app = AppState::new()
+            .with_timezone(DisplayTimezone::Named(chrono_tz::UTC));
+        app.add_event(create_event_at("early", day(20), 8));
+
+        app.show_event(create_event_at("opened", day(20), 14));
+
+        assert_eq!(app.selected_date, day(20));
+        assert_eq!(app.get_selected_event().map(|e| e.id.as_str()), Some("opened"));
+        assert_eq!(app.detail_view_event_id.as_deref(), Some("opened"));
+    }
+
+    #[test]
+    fn calendars_are_editable_unless_listed_read_only() {
+        let mut app = AppState::new();
+        app.add_calendars(vec![
+            Calendar { id: "me".to_string(), name: "Me".to_string(), color: String::new(), is_primary: true, access_role: AccessRole::Owner },
+            Calendar { id: "team".to_string(), name: "Team".to_string(), color: String::new(), is_primary: false, access_role: AccessRole::FreeBusyReader },
+        ]);
+
+        assert!(app.can_edit_calendar("me"));
+        assert!(!app.can_edit_calendar("team"));
+        assert!(app.can_edit_calendar("not-listed"));
+        assert!(!app.can_edit_calendar(&overlay::resolve("holidays german")));
+        assert!(!app.can_edit_calendar(&subscription::calendar_id("league")));
+        assert!(app.known_calendars().contains(&"team".to_string()));
+    }
+
+    #[test]
+    fn repeatable_edits_apply_to_the_selection() {
+        let day = |d| NaiveDate::from_ymd_opt(2025, 1, d).unwrap();
+        let tz = DisplayTimezone::Named(chrono_tz::UTC);
+        let selected = create_event_at("standup", day(13), 9);
+        let now = Utc.with_ymd_and_hms(2025, 1, 13, 12, 0, 0).unwrap();
+
+        let moved = RepeatableEdit::Move(1).target(Some(&selected), day(20), &tz, now).unwrap();
+        assert_eq!((moved.id.as_str(), moved.local_start(&tz).date()), ("standup", day(14)));
+
+        let copy = RepeatableEdit::Duplicate(7).target(Some(&selected), day(1), &tz, now).unwrap();
+        assert_ne!(copy.id, "standup");
+        assert_eq!(copy.local_start(&tz), day(20).and_hms_opt(9, 0, 0).unwrap());
+        assert_eq!(copy.last_modified, now);
+
+        let created = RepeatableEdit::Create(Box::new(selected.clone())).target(None, day(22), &tz, now).unwrap();
+        assert_eq!(created.local_start(&tz), day(22).and_hms_opt(9, 0, 0).unwrap());
+        assert_eq!(created.duration_minutes(), 60);
+
+        assert_eq!(RepeatableEdit::Move(-2).target(None, day(20), &tz, now), None);
+        assert_eq!(RepeatableEdit::Move(1).describe(), "move +1d");
+        assert_eq!(RepeatableEdit::Create(Box::new(selected)).describe(), "create \"Event standup\"");
+    }
+
+    #[test]
+    fn replacing_a_calendar_drops_only_its_events_in_the_range() {
+        let day = |d| NaiveDate::from_ymd_opt(2025, 1, d).unwrap();
+        let mut app = AppState::new();
+        let later = Utc.with_ymd_and_hms(2025, 2, 10, 9, 0, 0).unwrap();
+        app.add_event(EventBuilder::new("deleted").build());
+        app.add_event(EventBuilder::new("later").at(later, chrono::Duration::hours(1)).build());
+        app.add_event(EventBuilder::new("team").calendar("team").build());
+
+        app.replace_calendar_events(DEFAULT_CALENDAR_ID, day(1), day(31), vec![EventBuilder::new("added").build()]);
+
+        let mut ids: Vec<&str> = app.events.keys().map(String::as_str).collect();
+        ids.sort();
+        assert_eq!(ids, ["added", "later", "team"]);
+    }
 }
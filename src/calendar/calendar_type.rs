@@ -14,5 +14,46 @@ pub enum AccessRole {
     Owner,
     Writer,
     Reader,
+    /// Sees only busy/free blocks, not event details.
+    FreeBusyReader,
 }
 
+impl AccessRole {
+    /// Parses the calendarList `accessRole` value.
+    pub fn from_google(role: &str) -> Option<Self> {
+        match role {
+            "owner" => Some(Self::Owner),
+            "writer" => Some(Self::Writer),
+            "reader" => Some(Self::Reader),
+            "freeBusyReader" => Some(Self::FreeBusyReader),
+            _ => None,
+        }
+    }
+
+    pub fn can_edit(&self) -> bool {
+        matches!(self, Self::Owner | Self::Writer)
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Owner => "owner",
+            Self::Writer => "writer",
+            Self::Reader => "reader",
+            Self::FreeBusyReader => "free/busy only",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_owners_and_writers_can_edit() {
+        assert!(AccessRole::from_google("owner").unwrap().can_edit());
+        assert!(AccessRole::from_google("writer").unwrap().can_edit());
+        assert!(!AccessRole::from_google("reader").unwrap().can_edit());
+        assert!(!AccessRole::from_google("freeBusyReader").unwrap().can_edit());
+        assert_eq!(AccessRole::from_google("admin"), None);
+    }
+}
@@ -0,0 +1,318 @@
+use chrono::{Datelike, Days, Months, NaiveDate, Weekday};
+use thiserror::Error;
+
+#[derive(Debug, Error, PartialEq)]
+pub enum DateExprError {
+    #[error("Empty date expression")]
+    Empty,
+    #[error("Unrecognised date '{0}'")]
+    Unrecognised(String),
+    #[error("Date out of range: {0}")]
+    OutOfRange(String),
+    #[error("Range end {end} is before start {start}")]
+    InvertedRange { start: NaiveDate, end: NaiveDate },
+}
+
+/// Parses a single date such as `today`, `+2w`, `-3d`, `2025-01-15`,
+/// `friday`, `next friday`, `dec 25` or `next-week` (the Monday it starts on).
+/// Weekdays and month-days without a year resolve to the next occurrence.
+pub fn parse_date(expr: &str, today: NaiveDate) -> Result<NaiveDate, DateExprError> {
+    parse_span(expr, today).map(|(start, _)| start)
+}
+
+/// Parses `start..end` or a named span such as `next-week` or `this-month`.
+/// A single date yields a one-day range.
+pub fn parse_range(expr: &str, today: NaiveDate) -> Result<(NaiveDate, NaiveDate), DateExprError> {
+    let (start, end) = match expr.split_once("..") {
+        Some((from, to)) => (parse_date(from, today)?, parse_date(to, today)?),
+        None => parse_span(expr, today)?,
+    };
+
+    if end < start {
+        return Err(DateExprError::InvertedRange { start, end });
+    }
+    Ok((start, end))
+}
+
+fn parse_span(expr: &str, today: NaiveDate) -> Result<(NaiveDate, NaiveDate), DateExprError> {
+    let normalized = normalize(expr);
+    if normalized.is_empty() {
+        return Err(DateExprError::Empty);
+    }
+    let unrecognised = || DateExprError::Unrecognised(expr.trim().to_string());
+
+    if let Some(date) = parse_absolute(&normalized) {
+        return Ok((date, date));
+    }
+
+    let words: Vec<&str> = normalized.split_whitespace().collect();
+    if let Some(date) = parse_month_day(&words, today) {
+        return single(date);
+    }
+
+    match words.as_slice() {
+        ["today"] => Ok((today, today)),
+        ["tomorrow"] => single(add_days(today, 1)?),
+        ["yesterday"] => single(add_days(today, -1)?),
+        [word] if word.starts_with(['+', '-']) => single(parse_offset(word, today).ok_or_else(unrecognised)?),
+        [word] => {
+            if let Some(span) = named_span(word, 0, today) {
+                return span;
+            }
+            let weekday = parse_weekday(word).ok_or_else(unrecognised)?;
+            single(weekday_on_or_after(today, weekday))
+        }
+        [modifier @ ("next" | "last" | "this"), word] => {
+            let offset = match *modifier {
+                "next" => 1,
+                "last" => -1,
+                _ => 0,
+            };
+            if let Some(span) = named_span(word, offset, today) {
+                return span;
+            }
+
+            let weekday = parse_weekday(word).ok_or_else(unrecognised)?;
+            let date = match offset {
+                1 => weekday_on_or_after(add_days(today, 1)?, weekday),
+                -1 => weekday_on_or_before(add_days(today, -1)?, weekday),
+                _ => weekday_on_or_after(today, weekday),
+            };
+            single(date)
+        }
+        _ => Err(unrecognised()),
+    }
+}
+
+fn normalize(expr: &str) -> String {
+    let lower = expr.trim().to_lowercase();
+    match lower.split_once('-') {
+        Some((modifier @ ("next" | "last" | "this"), rest)) => format!("{} {}", modifier, rest),
+        _ => lower,
+    }
+}
+
+fn single(date: NaiveDate) -> Result<(NaiveDate, NaiveDate), DateExprError> {
+    Ok((date, date))
+}
+
+fn add_days(date: NaiveDate, days: i64) -> Result<NaiveDate, DateExprError> {
+    let result = if days >= 0 {
+        date.checked_add_days(Days::new(days as u64))
+    } else {
+        date.checked_sub_days(Days::new(days.unsigned_abs()))
+    };
+    result.ok_or_else(|| DateExprError::OutOfRange(format!("{} {:+} days", date, days)))
+}
+
+fn add_months(date: NaiveDate, months: i64) -> Option<NaiveDate> {
+    if months >= 0 {
+        date.checked_add_months(Months::new(months as u32))
+    } else {
+        date.checked_sub_months(Months::new(months.unsigned_abs() as u32))
+    }
+}
+
+fn parse_absolute(text: &str) -> Option<NaiveDate> {
+    ["%Y-%m-%d", "%Y/%m/%d"]
+        .iter()
+        .find_map(|fmt| NaiveDate::parse_from_str(text, fmt).ok())
+}
+
+fn parse_offset(text: &str, today: NaiveDate) -> Option<NaiveDate> {
+    let (sign, rest) = text.split_at(1);
+    let sign = if sign == "-" { -1 } else { 1 };
+    let (digits, unit) = match rest.find(|c: char| !c.is_ascii_digit()) {
+        Some(idx) => rest.split_at(idx),
+        None => (rest, "d"),
+    };
+    let amount: i64 = digits.parse().ok()?;
+
+    match unit {
+        "d" => add_days(today, sign * amount).ok(),
+        "w" => add_days(today, sign * amount * 7).ok(),
+        "m" => add_months(today, sign * amount),
+        "y" => add_months(today, sign * amount * 12),
+        _ => None,
+    }
+}
+
+fn parse_month(word: &str) -> Option<u32> {
+    const MONTHS: [&str; 12] = [
+        "january", "february", "march", "april", "may", "june",
+        "july", "august", "september", "october", "november", "december",
+    ];
+    let word = word.trim_end_matches('.');
+    if word.len() < 3 {
+        return None;
+    }
+    MONTHS.iter()
+        .position(|name| name.starts_with(word))
+        .map(|idx| idx as u32 + 1)
+}
+
+fn parse_day_number(word: &str) -> Option<u32> {
+    word.trim_end_matches(|c: char| c.is_ascii_alphabetic() || c == ',')
+        .parse()
+        .ok()
+        .filter(|day| (1..=31).contains(day))
+}
+
+/// `dec 25`, `25 dec`, `december 25th` and the same with a trailing year.
+fn parse_month_day(words: &[&str], today: NaiveDate) -> Option<NaiveDate> {
+    let (month, day, year) = match words {
+        [a, b] | [a, b, _] => {
+            let (month, day) = match (parse_month(a), parse_month(b)) {
+                (Some(month), None) => (month, parse_day_number(b)?),
+                (None, Some(month)) => (month, parse_day_number(a)?),
+                _ => return None,
+            };
+            let year = match words.get(2) {
+                Some(year) => Some(year.parse::<i32>().ok()?),
+                None => None,
+            };
+            (month, day, year)
+        }
+        _ => return None,
+    };
+
+    match year {
+        Some(year) => NaiveDate::from_ymd_opt(year, month, day),
+        None => {
+            let this_year = NaiveDate::from_ymd_opt(today.year(), month, day);
+            match this_year {
+                Some(date) if date >= today => Some(date),
+                _ => NaiveDate::from_ymd_opt(today.year() + 1, month, day),
+            }
+        }
+    }
+}
+
+fn parse_weekday(word: &str) -> Option<Weekday> {
+    match word {
+        "mon" | "monday" => Some(Weekday::Mon),
+        "tue" | "tues" | "tuesday" => Some(Weekday::Tue),
+        "wed" | "wednesday" => Some(Weekday::Wed),
+        "thu" | "thur" | "thurs" | "thursday" => Some(Weekday::Thu),
+        "fri" | "friday" => Some(Weekday::Fri),
+        "sat" | "saturday" => Some(Weekday::Sat),
+        "sun" | "sunday" => Some(Weekday::Sun),
+        _ => None,
+    }
+}
+
+fn weekday_on_or_after(date: NaiveDate, weekday: Weekday) -> NaiveDate {
+    let ahead = (7 + weekday.num_days_from_monday() - date.weekday().num_days_from_monday()) % 7;
+    date + Days::new(ahead as u64)
+}
+
+fn weekday_on_or_before(date: NaiveDate, weekday: Weekday) -> NaiveDate {
+    let behind = (7 + date.weekday().num_days_from_monday() - weekday.num_days_from_monday()) % 7;
+    date - Days::new(behind as u64)
+}
+
+fn named_span(word: &str, offset: i64, today: NaiveDate) -> Option<Result<(NaiveDate, NaiveDate), DateExprError>> {
+    let out_of_range = || DateExprError::OutOfRange(word.to_string());
+
+    match word {
+        "week" => {
+            let monday = today - Days::new(today.weekday().num_days_from_monday() as u64);
+            Some(add_days(monday, offset * 7).and_then(|start| Ok((start, add_days(start, 6)?))))
+        }
+        "month" => {
+            let first = today.with_day(1)?;
+            Some(
+                add_months(first, offset)
+                    .and_then(|start| Some((start, add_months(start, 1)?.pred_opt()?)))
+                    .ok_or_else(out_of_range),
+            )
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    // Wednesday
+    fn today() -> NaiveDate {
+        date(2025, 1, 15)
+    }
+
+    #[test]
+    fn parses_keywords() {
+        assert_eq!(parse_date("today", today()), Ok(today()));
+        assert_eq!(parse_date("Tomorrow", today()), Ok(date(2025, 1, 16)));
+        assert_eq!(parse_date("yesterday", today()), Ok(date(2025, 1, 14)));
+    }
+
+    #[test]
+    fn parses_absolute_dates() {
+        assert_eq!(parse_date("2025-03-01", today()), Ok(date(2025, 3, 1)));
+        assert_eq!(parse_date("2025/03/01", today()), Ok(date(2025, 3, 1)));
+    }
+
+    #[test]
+    fn parses_relative_offsets() {
+        assert_eq!(parse_date("+7d", today()), Ok(date(2025, 1, 22)));
+        assert_eq!(parse_date("+2w", today()), Ok(date(2025, 1, 29)));
+        assert_eq!(parse_date("-3", today()), Ok(date(2025, 1, 12)));
+        assert_eq!(parse_date("+1m", today()), Ok(date(2025, 2, 15)));
+        assert_eq!(parse_date("-1y", today()), Ok(date(2024, 1, 15)));
+    }
+
+    #[test]
+    fn parses_weekdays() {
+        assert_eq!(parse_date("friday", today()), Ok(date(2025, 1, 17)));
+        assert_eq!(parse_date("wed", today()), Ok(today()));
+        assert_eq!(parse_date("next wednesday", today()), Ok(date(2025, 1, 22)));
+        assert_eq!(parse_date("next friday", today()), Ok(date(2025, 1, 17)));
+        assert_eq!(parse_date("last monday", today()), Ok(date(2025, 1, 13)));
+    }
+
+    #[test]
+    fn parses_named_ranges() {
+        assert_eq!(parse_range("this-week", today()), Ok((date(2025, 1, 13), date(2025, 1, 19))));
+        assert_eq!(parse_range("next-week", today()), Ok((date(2025, 1, 20), date(2025, 1, 26))));
+        assert_eq!(parse_range("next month", today()), Ok((date(2025, 2, 1), date(2025, 2, 28))));
+        assert_eq!(parse_date("next-week", today()), Ok(date(2025, 1, 20)));
+    }
+
+    #[test]
+    fn parses_explicit_ranges() {
+        assert_eq!(parse_range("today..+7d", today()), Ok((today(), date(2025, 1, 22))));
+        assert_eq!(parse_range("2025-02-01..2025-02-03", today()), Ok((date(2025, 2, 1), date(2025, 2, 3))));
+    }
+
+    #[test]
+    fn single_date_is_one_day_range() {
+        assert_eq!(parse_range("tomorrow", today()), Ok((date(2025, 1, 16), date(2025, 1, 16))));
+    }
+
+    #[test]
+    fn rejects_inverted_ranges() {
+        assert!(matches!(parse_range("+3d..today", today()), Err(DateExprError::InvertedRange { .. })));
+    }
+
+    #[test]
+    fn rejects_garbage() {
+        assert_eq!(parse_date("", today()), Err(DateExprError::Empty));
+        assert_eq!(parse_date("someday", today()), Err(DateExprError::Unrecognised("someday".to_string())));
+        assert!(parse_date("+3x", today()).is_err());
+    }
+
+    #[test]
+    fn parses_month_days() {
+        assert_eq!(parse_date("dec 25", today()), Ok(date(2025, 12, 25)));
+        assert_eq!(parse_date("25 Dec", today()), Ok(date(2025, 12, 25)));
+        assert_eq!(parse_date("january 20th", today()), Ok(date(2025, 1, 20)));
+        assert_eq!(parse_date("jan 5", today()), Ok(date(2026, 1, 5)));
+        assert_eq!(parse_date("mar 1 2024", today()), Ok(date(2024, 3, 1)));
+        assert!(parse_date("feb 30", today()).is_err());
+    }
+}
@@ -1,6 +1,11 @@
-use chrono::{DateTime, Utc};
+use std::collections::BTreeMap;
+use std::sync::OnceLock;
+use chrono::{DateTime, Duration, NaiveDate, NaiveDateTime, Utc};
+use regex::Regex;
 use serde::{Deserialize, Serialize};
 
+use crate::calendar::DisplayTimezone;
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Event {
     pub id: String,
@@ -16,6 +21,49 @@ pub struct Event {
     pub status: EventStatus,
     pub last_modified: DateTime<Utc>,
     pub html_link: Option<String>,
+    #[serde(default)]
+    pub time_zone: Option<String>,
+    #[serde(default)]
+    pub conference_url: Option<String>,
+    /// Google event colorId, kept so updates do not reset it.
+    #[serde(default)]
+    pub color_id: Option<String>,
+    /// Resolved `#rrggbb` display color from the event or its calendar.
+    #[serde(default)]
+    pub color: Option<String>,
+    /// The signed-in user's RSVP, when they are an attendee.
+    #[serde(default)]
+    pub self_response: Option<ResponseStatus>,
+    /// Each attendee's RSVP by lowercased address, sent back on updates so
+    /// saving an edit does not reset them.
+    #[serde(default)]
+    pub attendee_responses: BTreeMap<String, ResponseStatus>,
+    #[serde(default)]
+    pub visibility: Visibility,
+    /// Transparent events (e.g. out-of-office blocks) do not count as busy.
+    #[serde(default)]
+    pub transparency: Transparency,
+    /// Server version tag, sent as If-Match so stale writes are refused.
+    #[serde(default)]
+    pub etag: Option<String>,
+    /// Who owns the event, as "Name <email>" when Google gives a name.
+    #[serde(default)]
+    pub organizer: Option<String>,
+    #[serde(default)]
+    pub creator: Option<String>,
+}
+
+/// Prefix of the description line that holds a link attached in the event
+/// form; Google only creates conferenceData through its own add-ons.
+pub const LINK_PREFIX: &str = "Link: ";
+
+static MEETING_URL_RE: OnceLock<Regex> = OnceLock::new();
+
+fn meeting_url_pattern() -> &'static Regex {
+    MEETING_URL_RE.get_or_init(|| {
+        Regex::new(r#"https://(?:[\w-]+\.)*(?:meet\.google\.com|zoom\.us|teams\.microsoft\.com|teams\.live\.com|whereby\.com|webex\.com)/[^\s<>"'\)\]]*"#)
+            .expect("invalid meeting url regex")
+    })
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -25,6 +73,129 @@ pub enum EventStatus {
     Cancelled,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub enum Visibility {
+    #[default]
+    Default,
+    Public,
+    Private,
+    Confidential,
+}
+
+impl Visibility {
+    pub fn from_google(value: &str) -> Self {
+        match value {
+            "public" => Self::Public,
+            "private" => Self::Private,
+            "confidential" => Self::Confidential,
+            _ => Self::Default,
+        }
+    }
+
+    pub fn as_google(&self) -> &'static str {
+        match self {
+            Self::Default => "default",
+            Self::Public => "public",
+            Self::Private => "private",
+            Self::Confidential => "confidential",
+        }
+    }
+
+    /// Default <-> private; the rarer public/confidential go back to default.
+    pub fn toggled(self) -> Self {
+        match self {
+            Self::Default => Self::Private,
+            _ => Self::Default,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub enum Transparency {
+    #[default]
+    Opaque,
+    Transparent,
+}
+
+impl Transparency {
+    pub fn from_google(value: &str) -> Self {
+        match value {
+            "transparent" => Self::Transparent,
+            _ => Self::Opaque,
+        }
+    }
+
+    pub fn as_google(&self) -> &'static str {
+        match self {
+            Self::Opaque => "opaque",
+            Self::Transparent => "transparent",
+        }
+    }
+
+    /// "busy"/"free", as Google shows it.
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Opaque => "busy",
+            Self::Transparent => "free",
+        }
+    }
+
+    pub fn toggled(self) -> Self {
+        match self {
+            Self::Opaque => Self::Transparent,
+            Self::Transparent => Self::Opaque,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum ResponseStatus {
+    NeedsAction,
+    Declined,
+    Tentative,
+    Accepted,
+}
+
+impl ResponseStatus {
+    pub fn from_google(value: &str) -> Option<Self> {
+        match value {
+            "needsAction" => Some(Self::NeedsAction),
+            "declined" => Some(Self::Declined),
+            "tentative" => Some(Self::Tentative),
+            "accepted" => Some(Self::Accepted),
+            _ => None,
+        }
+    }
+
+    pub fn as_google(&self) -> &'static str {
+        match self {
+            Self::NeedsAction => "needsAction",
+            Self::Declined => "declined",
+            Self::Tentative => "tentative",
+            Self::Accepted => "accepted",
+        }
+    }
+}
+
+impl EventStatus {
+    pub fn label(&self) -> &'static str {
+        match self {
+            EventStatus::Confirmed => "Confirmed",
+            EventStatus::Tentative => "Tentative",
+            EventStatus::Cancelled => "Cancelled",
+        }
+    }
+
+    /// Confirmed -> Tentative -> Cancelled -> Confirmed, for form toggling.
+    pub fn cycled(&self) -> Self {
+        match self {
+            EventStatus::Confirmed => EventStatus::Tentative,
+            EventStatus::Tentative => EventStatus::Cancelled,
+            EventStatus::Cancelled => EventStatus::Confirmed,
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Reminder {
     pub method: ReminderMethod,
@@ -42,9 +213,131 @@ impl Event {
         (self.end - self.start).num_minutes()
     }
 
+    /// Whether the event blocks time for free-slot search and conflicts.
+    pub fn is_busy(&self) -> bool {
+        self.status != EventStatus::Cancelled && self.transparency == Transparency::Opaque
+    }
+
+    pub fn is_declined(&self) -> bool {
+        self.self_response == Some(ResponseStatus::Declined)
+    }
+
     pub fn overlaps(&self, other: &Event) -> bool {
         self.start < other.end && other.start < self.end
     }
+
+    /// All-day events are date-based and stored at UTC midnight, so they are
+    /// never shifted into the display timezone.
+    pub fn local_start(&self, tz: &DisplayTimezone) -> NaiveDateTime {
+        if self.all_day {
+            self.start.naive_utc()
+        } else {
+            tz.to_local(&self.start)
+        }
+    }
+
+    pub fn local_end(&self, tz: &DisplayTimezone) -> NaiveDateTime {
+        if self.all_day {
+            self.end.naive_utc()
+        } else {
+            tz.to_local(&self.end)
+        }
+    }
+
+    /// First and last local day the event's `[start, end)` range touches.
+    /// The last day is taken from the instant just before `end`, so an event
+    /// ending at the start of a day (midnight, or whenever a DST gap makes
+    /// the day begin) does not reach into it. Zero-length events cover
+    /// their start day.
+    pub fn local_dates(&self, tz: &DisplayTimezone) -> (NaiveDate, NaiveDate) {
+        let first = self.local_start(tz).date();
+        let last_instant = if self.end > self.start {
+            self.end - Duration::nanoseconds(1)
+        } else {
+            self.start
+        };
+        let last = if self.all_day {
+            last_instant.date_naive()
+        } else {
+            tz.date_of(&last_instant)
+        };
+        (first, last.max(first))
+    }
+
+    pub fn occurs_on(&self, date: NaiveDate, tz: &DisplayTimezone) -> bool {
+        let (first, last) = self.local_dates(tz);
+        first <= date && date <= last
+    }
+
+    pub fn spans_multiple_days(&self, tz: &DisplayTimezone) -> bool {
+        let (first, last) = self.local_dates(tz);
+        first != last
+    }
+
+    /// Moves the event by whole days on the local wall clock, so a 9:00
+    /// meeting stays at 9:00 across a DST change.
+    pub fn shifted_by_days(&self, days: i64, tz: &DisplayTimezone) -> Option<Event> {
+        let start = if self.all_day {
+            self.start + Duration::days(days)
+        } else {
+            tz.from_local(&(self.local_start(tz) + Duration::days(days)))?
+        };
+
+        Some(Event {
+            start,
+            end: start + (self.end - self.start),
+            ..self.clone()
+        })
+    }
+
+    /// A copy on `date` at the same local time, ready to be created under `id`
+    /// and stamped as modified at `now`.
+    pub fn duplicate_to(&self, id: String, date: NaiveDate, tz: &DisplayTimezone, now: DateTime<Utc>) -> Option<Event> {
+        let days = (date - self.local_start(tz).date()).num_days();
+        let copy = self.shifted_by_days(days, tz)?;
+
+        Some(Event {
+            id,
+            html_link: None,
+            conference_url: None,
+            last_modified: now,
+            ..copy
+        })
+    }
+
+    /// Conference entry point from the API, falling back to a Meet/Zoom/Teams
+    /// link pasted into the location or description.
+    pub fn meeting_url(&self) -> Option<&str> {
+        if let Some(url) = &self.conference_url {
+            return Some(url);
+        }
+
+        [&self.location, &self.description]
+            .into_iter()
+            .flatten()
+            .find_map(|text| meeting_url_pattern().find(text))
+            .map(|m| m.as_str())
+    }
+}
+
+/// Splits an attached `Link: ` line off the end of a description. A `Link:`
+/// line elsewhere is the user's text and stays where it is.
+pub fn split_link(description: &str) -> (String, Option<String>) {
+    let trimmed = description.trim_end();
+    let (body, last_line) = trimmed.rsplit_once('\n').unwrap_or(("", trimmed));
+    match last_line.strip_prefix(LINK_PREFIX) {
+        Some(url) => (body.trim_end().to_string(), Some(url.trim().to_string())),
+        None => (description.to_string(), None),
+    }
+}
+
+/// The inverse of `split_link`: the link goes on its own line after the body.
+pub fn join_link(body: &str, link: &str) -> String {
+    match (body.is_empty(), link.is_empty()) {
+        (_, true) => body.to_string(),
+        (true, false) => format!("{}{}", LINK_PREFIX, link),
+        (false, false) => format!("{}\n\n{}{}", body, LINK_PREFIX, link),
+    }
 }
 
 #[cfg(test)]
@@ -66,6 +359,17 @@ mod tests {
             status: EventStatus::Confirmed,
             last_modified: Utc::now(),
             html_link: None,
+            time_zone: None,
+            conference_url: None,
+            color_id: None,
+            color: None,
+            self_response: None,
+            attendee_responses: BTreeMap::new(),
+            visibility: Visibility::Default,
+            transparency: Transparency::Opaque,
+            etag: None,
+            organizer: None,
+            creator: None,
         }
     }
 
@@ -91,6 +395,85 @@ mod tests {
         assert!(event1.overlaps(&event2));
     }
 
+    #[test]
+    fn local_start_converts_timed_events() {
+        use chrono::{TimeZone, Timelike};
+        let start = Utc.with_ymd_and_hms(2025, 1, 15, 17, 0, 0).unwrap();
+        let event = create_test_event("e1", "Standup", start, start + chrono::Duration::minutes(15));
+        let tz = DisplayTimezone::Named(chrono_tz::America::Los_Angeles);
+
+        assert_eq!(event.local_start(&tz).hour(), 9);
+    }
+
+    #[test]
+    fn local_start_keeps_all_day_dates() {
+        use chrono::TimeZone;
+        let start = Utc.with_ymd_and_hms(2025, 1, 15, 0, 0, 0).unwrap();
+        let mut event = create_test_event("e1", "Holiday", start, start + chrono::Duration::days(1));
+        event.all_day = true;
+        let tz = DisplayTimezone::Named(chrono_tz::America::Los_Angeles);
+
+        assert_eq!(event.local_start(&tz).date(), start.date_naive());
+    }
+
+    #[test]
+    fn all_day_event_covers_every_day_until_its_exclusive_end() {
+        use chrono::TimeZone;
+        let start = Utc.with_ymd_and_hms(2025, 1, 15, 0, 0, 0).unwrap();
+        let mut event = create_test_event("e1", "Conference", start, start + chrono::Duration::days(3));
+        event.all_day = true;
+        let tz = DisplayTimezone::Named(chrono_tz::UTC);
+        let day = |d| NaiveDate::from_ymd_opt(2025, 1, d).unwrap();
+
+        assert_eq!(event.local_dates(&tz), (day(15), day(17)));
+        assert!(event.occurs_on(day(17), &tz));
+        assert!(!event.occurs_on(day(18), &tz));
+        assert!(event.spans_multiple_days(&tz));
+    }
+
+    #[test]
+    fn timed_event_crossing_midnight_spans_two_days() {
+        use chrono::TimeZone;
+        let start = Utc.with_ymd_and_hms(2025, 1, 15, 22, 0, 0).unwrap();
+        let late = create_test_event("e1", "Deploy", start, start + chrono::Duration::hours(3));
+        let until_midnight = create_test_event("e2", "Party", start, start + chrono::Duration::hours(2));
+        let tz = DisplayTimezone::Named(chrono_tz::UTC);
+
+        assert!(late.spans_multiple_days(&tz));
+        assert!(!until_midnight.spans_multiple_days(&tz));
+    }
+
+    #[test]
+    fn shifted_by_days_keeps_local_time_across_dst() {
+        use chrono::TimeZone;
+        let start = Utc.with_ymd_and_hms(2025, 3, 7, 14, 0, 0).unwrap();
+        let event = create_test_event("e1", "Standup", start, start + chrono::Duration::minutes(30));
+        let tz = DisplayTimezone::Named(chrono_tz::America::New_York);
+
+        let moved = event.shifted_by_days(7, &tz).unwrap();
+
+        assert_eq!(moved.start, Utc.with_ymd_and_hms(2025, 3, 14, 13, 0, 0).unwrap());
+        assert_eq!(moved.duration_minutes(), 30);
+    }
+
+    #[test]
+    fn duplicate_to_gets_new_id_and_date() {
+        use chrono::TimeZone;
+        let start = Utc.with_ymd_and_hms(2025, 1, 15, 17, 0, 0).unwrap();
+        let mut event = create_test_event("e1", "Standup", start, start + chrono::Duration::minutes(15));
+        event.html_link = Some("https://calendar.google.com/event?eid=e1".to_string());
+        let tz = DisplayTimezone::Named(chrono_tz::America::Los_Angeles);
+
+        let copy = event.duplicate_to("e2".to_string(), NaiveDate::from_ymd_opt(2025, 1, 20).unwrap(), &tz, start).unwrap();
+
+        assert_eq!(copy.id, "e2");
+        assert_eq!(copy.title, "Standup");
+        assert_eq!(copy.start, Utc.with_ymd_and_hms(2025, 1, 20, 17, 0, 0).unwrap());
+        assert_eq!(copy.duration_minutes(), 15);
+        assert_eq!(copy.html_link, None);
+        assert_eq!(copy.last_modified, start);
+    }
+
     #[test]
     fn event_does_not_overlap_when_adjacent() {
         let start1 = Utc::now();
@@ -101,4 +484,53 @@ mod tests {
 
         assert!(!event1.overlaps(&event2));
     }
+
+    #[test]
+    fn meeting_url_prefers_conference_data() {
+        let start = Utc::now();
+        let mut event = create_test_event("e1", "Standup", start, start + chrono::Duration::minutes(15));
+        event.description = Some("Backup: https://zoom.us/j/123".to_string());
+        event.conference_url = Some("https://meet.google.com/abc-defg-hij".to_string());
+
+        assert_eq!(event.meeting_url(), Some("https://meet.google.com/abc-defg-hij"));
+    }
+
+    #[test]
+    fn meeting_url_found_in_description() {
+        let start = Utc::now();
+        let mut event = create_test_event("e1", "Sync", start, start + chrono::Duration::minutes(30));
+        event.description = Some("Agenda at https://example.com\nJoin: https://acme.zoom.us/j/98765?pwd=x).".to_string());
+
+        assert_eq!(event.meeting_url(), Some("https://acme.zoom.us/j/98765?pwd=x"));
+    }
+
+    #[test]
+    fn meeting_url_is_none_without_links() {
+        let start = Utc::now();
+        let event = create_test_event("e1", "Lunch", start, start + chrono::Duration::hours(1));
+
+        assert_eq!(event.meeting_url(), None);
+    }
+
+    #[test]
+    fn link_line_splits_out_of_the_description_and_back() {
+        let description = join_link("Agenda\n- intro", "https://example.com/room");
+        assert_eq!(description, "Agenda\n- intro\n\nLink: https://example.com/room");
+
+        let (body, link) = split_link(&description);
+        assert_eq!(body, "Agenda\n- intro");
+        assert_eq!(link.as_deref(), Some("https://example.com/room"));
+        assert_eq!(join_link("", "https://example.com"), "Link: https://example.com");
+        assert_eq!(split_link("No link here"), ("No link here".to_string(), None));
+    }
+
+    #[test]
+    fn only_a_trailing_link_line_is_the_link() {
+        let description = "Agenda\nLink: https://example.com/doc\n- intro";
+        assert_eq!(split_link(description), (description.to_string(), None));
+
+        let (body, link) = split_link("Link: https://example.com/doc\nnotes\n\nLink: https://example.com/room\n");
+        assert_eq!(body, "Link: https://example.com/doc\nnotes");
+        assert_eq!(link.as_deref(), Some("https://example.com/room"));
+    }
 }
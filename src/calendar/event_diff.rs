@@ -0,0 +1,80 @@
+use crate::calendar::{DisplayTimezone, Event};
+
+/// One user-visible field that an update would change.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldChange {
+    pub field: &'static str,
+    pub old: String,
+    pub new: String,
+}
+
+type Show = fn(&Event, &DisplayTimezone) -> String;
+
+const FIELDS: [(&str, Show); 8] = [
+    ("Title", |e, _| e.title.clone()),
+    ("When", when),
+    ("Location", |e, _| e.location.clone().unwrap_or_default()),
+    ("Description", |e, _| e.description.clone().unwrap_or_default().replace('\n', " / ")),
+    ("Attendees", |e, _| e.attendees.join(", ")),
+    ("Status", |e, _| e.status.label().to_string()),
+    ("Show as", |e, _| e.transparency.label().to_string()),
+    ("Visibility", |e, _| e.visibility.as_google().to_string()),
+];
+
+/// The fields that differ between `old` and `new`, formatted for display.
+pub fn diff(old: &Event, new: &Event, tz: &DisplayTimezone) -> Vec<FieldChange> {
+    FIELDS.iter()
+        .filter_map(|&(field, show)| {
+            let (old, new) = (show(old, tz), show(new, tz));
+            (old != new).then_some(FieldChange { field, old, new })
+        })
+        .collect()
+}
+
+fn when(event: &Event, tz: &DisplayTimezone) -> String {
+    let (first, last) = event.local_dates(tz);
+    if event.all_day {
+        return if first == last {
+            format!("{} (all day)", first.format("%a %b %d"))
+        } else {
+            format!("{} \u{2013} {} (all day)", first.format("%a %b %d"), last.format("%a %b %d"))
+        };
+    }
+
+    let (start, end) = (event.local_start(tz), event.local_end(tz));
+    let end_format = if first == last { "%H:%M" } else { "%a %b %d %H:%M" };
+    format!("{} {}\u{2013}{}", first.format("%a %b %d"), start.format("%H:%M"), end.format(end_format))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::EventBuilder;
+    use chrono::{TimeZone, Utc};
+
+    fn event(start_hour: u32) -> Event {
+        let start = Utc.with_ymd_and_hms(2025, 1, 15, start_hour, 0, 0).unwrap();
+        EventBuilder::new("e1").title("Planning").location("Room 1").at(start, chrono::Duration::hours(1)).build()
+    }
+
+    #[test]
+    fn lists_only_changed_fields() {
+        let tz = DisplayTimezone::Named(chrono_tz::UTC);
+        let old = event(9);
+        let mut new = event(14);
+        new.location = None;
+
+        let changes = diff(&old, &new, &tz);
+
+        assert_eq!(changes, vec![
+            FieldChange { field: "When", old: "Wed Jan 15 09:00\u{2013}10:00".to_string(), new: "Wed Jan 15 14:00\u{2013}15:00".to_string() },
+            FieldChange { field: "Location", old: "Room 1".to_string(), new: String::new() },
+        ]);
+    }
+
+    #[test]
+    fn identical_events_have_no_changes() {
+        let tz = DisplayTimezone::Named(chrono_tz::UTC);
+        assert!(diff(&event(9), &event(9), &tz).is_empty());
+    }
+}
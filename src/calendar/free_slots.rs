@@ -0,0 +1,220 @@
+use chrono::{Days, Duration, NaiveDate, NaiveDateTime, Timelike};
+use thiserror::Error;
+
+use crate::calendar::{date_expr, quick_add, working_hours::WorkingHours, DisplayTimezone, Event};
+
+pub const DEFAULT_SEARCH_DAYS: u64 = 7;
+pub const MAX_SLOTS: usize = 20;
+
+#[derive(Debug, Error, PartialEq)]
+pub enum FreeQueryError {
+    #[error("free requires a duration like 30m or 1h")]
+    MissingDuration,
+    #[error("Invalid hours '{0}', expected e.g. 9-17")]
+    InvalidHours(String),
+    #[error(transparent)]
+    Range(#[from] date_expr::DateExprError),
+}
+
+/// A `:free` request such as `60m this week 9-17`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FreeQuery {
+    pub duration_minutes: u32,
+    pub start_date: NaiveDate,
+    pub end_date: NaiveDate,
+    /// Overrides the working hours for this search.
+    pub hours: Option<WorkingHours>,
+}
+
+/// A gap in local time at least as long as the requested duration.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FreeSlot {
+    pub start: NaiveDateTime,
+    pub end: NaiveDateTime,
+}
+
+/// Parses `<duration> [range] [hours]`; the range defaults to the next
+/// seven days and the hours to the configured working hours.
+pub fn parse_free_query(args: &str, today: NaiveDate) -> Result<FreeQuery, FreeQueryError> {
+    let mut tokens = args.split_whitespace();
+    let duration_minutes = tokens
+        .next()
+        .and_then(|token| quick_add::parse_duration(&token.to_lowercase()))
+        .ok_or(FreeQueryError::MissingDuration)?;
+
+    let mut hours = None;
+    let mut range_words = Vec::new();
+
+    for token in tokens {
+        if WorkingHours::is_range_syntax(token) {
+            hours = Some(WorkingHours::parse(token).ok_or_else(|| FreeQueryError::InvalidHours(token.to_string()))?);
+        } else {
+            range_words.push(token);
+        }
+    }
+
+    let (start_date, end_date) = if range_words.is_empty() {
+        let end = today.checked_add_days(Days::new(DEFAULT_SEARCH_DAYS - 1)).unwrap_or(today);
+        (today, end)
+    } else {
+        date_expr::parse_range(&range_words.join(" "), today)?
+    };
+
+    Ok(FreeQuery { duration_minutes, start_date, end_date, hours })
+}
+
+/// Gaps between timed events inside each day's window, skipping anything
+/// before `now` (rounded up to the next quarter hour).
+pub fn find_free_slots<'a>(
+    query: &FreeQuery,
+    events: impl IntoIterator<Item = &'a Event>,
+    working_hours: &WorkingHours,
+    tz: &DisplayTimezone,
+    now: NaiveDateTime,
+) -> Vec<FreeSlot> {
+    let hours = query.hours.unwrap_or(*working_hours);
+    let mut busy: Vec<(NaiveDateTime, NaiveDateTime)> = events
+        .into_iter()
+        .filter(|event| !event.all_day && event.is_busy())
+        .map(|event| (event.local_start(tz), event.local_end(tz)))
+        .collect();
+    busy.sort();
+
+    let earliest = round_up_to_quarter(now);
+    let needed = Duration::minutes(query.duration_minutes as i64);
+    let mut slots = Vec::new();
+
+    for date in query.start_date.iter_days().take_while(|date| *date <= query.end_date) {
+        let window_end = date.and_time(hours.end);
+        let mut cursor = date.and_time(hours.start).max(earliest);
+
+        for &(start, end) in &busy {
+            if end <= cursor || start >= window_end {
+                continue;
+            }
+            if start - cursor >= needed {
+                slots.push(FreeSlot { start: cursor, end: start });
+            }
+            cursor = cursor.max(end);
+        }
+        if window_end - cursor >= needed {
+            slots.push(FreeSlot { start: cursor, end: window_end });
+        }
+
+        if slots.len() >= MAX_SLOTS {
+            slots.truncate(MAX_SLOTS);
+            break;
+        }
+    }
+
+    slots
+}
+
+fn round_up_to_quarter(time: NaiveDateTime) -> NaiveDateTime {
+    let extra = (15 - time.minute() % 15) % 15;
+    let rounded = time + Duration::minutes(extra as i64);
+    rounded.with_second(0).and_then(|t| t.with_nanosecond(0)).unwrap_or(rounded)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::EventBuilder;
+    use crate::calendar::Transparency;
+
+    // Wednesday
+    fn today() -> NaiveDate {
+        NaiveDate::from_ymd_opt(2025, 1, 15).unwrap()
+    }
+
+    fn at(day: u32, hour: u32, minute: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2025, 1, day).unwrap().and_hms_opt(hour, minute, 0).unwrap()
+    }
+
+    fn event(id: &str, start: NaiveDateTime, minutes: i64) -> Event {
+        EventBuilder::new(id).title(id).at(start.and_utc(), Duration::minutes(minutes)).build()
+    }
+
+    fn utc() -> DisplayTimezone {
+        DisplayTimezone::Named(chrono_tz::UTC)
+    }
+
+    fn query(minutes: u32) -> FreeQuery {
+        FreeQuery {
+            duration_minutes: minutes,
+            start_date: today(),
+            end_date: today(),
+            hours: None,
+        }
+    }
+
+    #[test]
+    fn parses_duration_range_and_hours() {
+        let query = parse_free_query("60m this week 8:30-18", today()).unwrap();
+
+        assert_eq!(query.duration_minutes, 60);
+        assert_eq!(query.start_date, NaiveDate::from_ymd_opt(2025, 1, 13).unwrap());
+        assert_eq!(query.end_date, NaiveDate::from_ymd_opt(2025, 1, 19).unwrap());
+        assert_eq!(query.hours, WorkingHours::parse("08:30-18:00"));
+    }
+
+    #[test]
+    fn defaults_to_next_seven_days_of_working_hours() {
+        let query = parse_free_query("1h", today()).unwrap();
+
+        assert_eq!(query.start_date, today());
+        assert_eq!(query.end_date, NaiveDate::from_ymd_opt(2025, 1, 21).unwrap());
+        assert_eq!(query.hours, None);
+    }
+
+    #[test]
+    fn rejects_bad_queries() {
+        assert_eq!(parse_free_query("", today()), Err(FreeQueryError::MissingDuration));
+        assert_eq!(parse_free_query("tomorrow", today()), Err(FreeQueryError::MissingDuration));
+        assert_eq!(parse_free_query("30m 17-9", today()), Err(FreeQueryError::InvalidHours("17-9".to_string())));
+    }
+
+    #[test]
+    fn finds_gaps_between_events() {
+        let events = [
+            event("standup", at(15, 9, 0), 30),
+            event("lunch", at(15, 12, 0), 60),
+            event("review", at(15, 13, 0), 210),
+        ];
+
+        let slots = find_free_slots(&query(60), &events, &WorkingHours::default(), &utc(), at(15, 0, 0));
+
+        assert_eq!(slots, vec![
+            FreeSlot { start: at(15, 9, 30), end: at(15, 12, 0) },
+        ]);
+    }
+
+    #[test]
+    fn overlapping_events_merge_into_one_busy_block() {
+        let events = [
+            event("a", at(15, 9, 0), 120),
+            event("b", at(15, 10, 0), 30),
+        ];
+
+        let slots = find_free_slots(&query(30), &events, &WorkingHours::default(), &utc(), at(15, 0, 0));
+
+        assert_eq!(slots, vec![FreeSlot { start: at(15, 11, 0), end: at(15, 17, 0) }]);
+    }
+
+    #[test]
+    fn events_shown_as_free_do_not_block_slots() {
+        let mut ooo = event("ooo", at(15, 9, 0), 480);
+        ooo.transparency = Transparency::Transparent;
+
+        let slots = find_free_slots(&query(60), &[ooo], &WorkingHours::default(), &utc(), at(15, 0, 0));
+
+        assert_eq!(slots, vec![FreeSlot { start: at(15, 9, 0), end: at(15, 17, 0) }]);
+    }
+
+    #[test]
+    fn skips_time_already_past() {
+        let slots = find_free_slots(&query(60), &[], &WorkingHours::default(), &utc(), at(15, 10, 7));
+
+        assert_eq!(slots, vec![FreeSlot { start: at(15, 10, 15), end: at(15, 17, 0) }]);
+    }
+}
@@ -0,0 +1,545 @@
+use chrono::{DateTime, Duration, NaiveDate, NaiveDateTime, NaiveTime, Utc};
+
+use crate::calendar::{recurrence::Rule, DisplayTimezone, Event, EventStatus, Transparency, Visibility};
+
+const PRODUCT_ID: &str = "-//gcal-imp//gcal-imp//EN";
+const MAX_LINE_OCTETS: usize = 75;
+
+pub fn to_ics(events: &[Event]) -> String {
+    let mut lines = vec![
+        "BEGIN:VCALENDAR".to_string(),
+        "VERSION:2.0".to_string(),
+        format!("PRODID:{}", PRODUCT_ID),
+        "CALSCALE:GREGORIAN".to_string(),
+    ];
+
+    for event in events {
+        lines.extend(event_lines(event));
+    }
+
+    lines.push("END:VCALENDAR".to_string());
+    lines.iter().map(|line| fold_line(line)).collect::<Vec<_>>().join("\r\n") + "\r\n"
+}
+
+fn event_lines(event: &Event) -> Vec<String> {
+    let mut lines = vec![
+        "BEGIN:VEVENT".to_string(),
+        format!("UID:{}", escape_text(&event.id)),
+        format!("DTSTAMP:{}", format_utc(&event.last_modified)),
+    ];
+
+    if event.all_day {
+        lines.push(format!("DTSTART;VALUE=DATE:{}", event.start.format("%Y%m%d")));
+        lines.push(format!("DTEND;VALUE=DATE:{}", event.end.format("%Y%m%d")));
+    } else {
+        lines.push(format!("DTSTART:{}", format_utc(&event.start)));
+        lines.push(format!("DTEND:{}", format_utc(&event.end)));
+    }
+
+    lines.push(format!("SUMMARY:{}", escape_text(&event.title)));
+    if let Some(location) = &event.location {
+        lines.push(format!("LOCATION:{}", escape_text(location)));
+    }
+    if let Some(description) = &event.description {
+        lines.push(format!("DESCRIPTION:{}", escape_text(description)));
+    }
+    if let Some(url) = &event.html_link {
+        lines.push(format!("URL:{}", url));
+    }
+
+    let status = match event.status {
+        EventStatus::Confirmed => "CONFIRMED",
+        EventStatus::Tentative => "TENTATIVE",
+        EventStatus::Cancelled => "CANCELLED",
+    };
+    lines.push(format!("STATUS:{}", status));
+    lines.push("END:VEVENT".to_string());
+    lines
+}
+
+fn format_utc(dt: &DateTime<Utc>) -> String {
+    dt.format("%Y%m%dT%H%M%SZ").to_string()
+}
+
+fn escape_text(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace("\r\n", "\\n")
+        .replace('\n', "\\n")
+}
+
+/// Folds content lines longer than 75 octets (RFC 5545 §3.1) without
+/// splitting UTF-8 sequences.
+fn fold_line(line: &str) -> String {
+    let mut folded = String::with_capacity(line.len());
+    let mut octets = 0;
+
+    for ch in line.chars() {
+        let len = ch.len_utf8();
+        if octets + len > MAX_LINE_OCTETS {
+            folded.push_str("\r\n ");
+            octets = 1;
+        }
+        folded.push(ch);
+        octets += len;
+    }
+    folded
+}
+
+/// Reads the VEVENTs of a calendar feed that overlap `window_start..window_end`
+/// as events on `calendar_id`. Ids are the UID prefixed with the calendar id,
+/// so feeds cannot clash with Google ids. Repeating events become one event
+/// per occurrence, with the occurrence's start added to the id; moved
+/// occurrences (RECURRENCE-ID) replace the ones they move. Rules beyond
+/// `recurrence::Rule` keep only their first occurrence. Events without a UID
+/// or start are skipped.
+pub fn from_ics(text: &str, calendar_id: &str, window_start: DateTime<Utc>, window_end: DateTime<Utc>) -> Vec<Event> {
+    let components = components(text);
+    let moved: Vec<(String, DateTime<Utc>)> = components.iter()
+        .filter_map(|props| {
+            let uid = find(props, "UID")?.value.trim().to_string();
+            let (at, _) = parse_date_time(find(props, "RECURRENCE-ID")?)?;
+            Some((uid, at))
+        })
+        .collect();
+
+    let overlaps = |event: &Event| event.end > window_start && event.start < window_end;
+    let mut events = Vec::new();
+    for props in &components {
+        let Some(event) = event_from(props, calendar_id) else {
+            continue;
+        };
+        let rule = find(props, "RRULE")
+            .filter(|_| find(props, "RECURRENCE-ID").is_none())
+            .and_then(|property| Rule::parse(&property.value));
+        let (Some(rule), Some(dtstart)) = (rule, find(props, "DTSTART")) else {
+            if overlaps(&event) {
+                events.push(event);
+            }
+            continue;
+        };
+        let Some((local_start, zone, _)) = parse_local(dtstart) else {
+            continue;
+        };
+
+        let uid = find(props, "UID").map(|property| property.value.trim().to_string()).unwrap_or_default();
+        let excluded = excluded_starts(props);
+        let length = event.end - event.start;
+        let to_utc = |local: &NaiveDateTime| if event.all_day { Some(local.and_utc()) } else { zone.from_local(local) };
+        for start in rule.occurrences(local_start, window_end, to_utc) {
+            if excluded.contains(&start) || moved.iter().any(|(moved_uid, at)| moved_uid == &uid && at == &start) {
+                continue;
+            }
+            let occurrence = Event {
+                id: format!("{}:{}:{}", calendar_id, uid, occurrence_key(&start, event.all_day)),
+                start,
+                end: start + length,
+                ..event.clone()
+            };
+            if overlaps(&occurrence) {
+                events.push(occurrence);
+            }
+        }
+    }
+    events
+}
+
+/// The property lists of each VEVENT.
+fn components(text: &str) -> Vec<Vec<Property>> {
+    let mut components = Vec::new();
+    let mut properties: Option<Vec<Property>> = None;
+    // VALARMs and other components nested in a VEVENT are skipped.
+    let mut nested = 0;
+
+    for line in unfold(text) {
+        let Some(property) = Property::parse(&line) else {
+            continue;
+        };
+        match (property.name.as_str(), property.value.to_uppercase().as_str()) {
+            ("BEGIN", "VEVENT") if properties.is_none() => properties = Some(Vec::new()),
+            ("BEGIN", _) if properties.is_some() => nested += 1,
+            ("END", "VEVENT") if nested == 0 => components.extend(properties.take()),
+            ("END", _) if nested > 0 => nested -= 1,
+            _ if nested == 0 => {
+                if let Some(props) = &mut properties {
+                    props.push(property);
+                }
+            }
+            _ => {}
+        }
+    }
+    components
+}
+
+fn find<'a>(properties: &'a [Property], name: &str) -> Option<&'a Property> {
+    properties.iter().find(|property| property.name == name)
+}
+
+/// EXDATE values, which may be repeated and comma-separated.
+fn excluded_starts(properties: &[Property]) -> Vec<DateTime<Utc>> {
+    properties.iter()
+        .filter(|property| property.name == "EXDATE")
+        .flat_map(|property| {
+            property.value.split(',').filter_map(|value| {
+                let single = Property { name: property.name.clone(), params: property.params.clone(), value: value.to_string() };
+                parse_date_time(&single).map(|(at, _)| at)
+            })
+        })
+        .collect()
+}
+
+/// Identifies an occurrence the way RECURRENCE-ID does, in UTC.
+fn occurrence_key(start: &DateTime<Utc>, all_day: bool) -> String {
+    if all_day { start.format("%Y%m%d").to_string() } else { format_utc(start) }
+}
+
+/// A content line split into its name, parameters and value.
+#[derive(Debug)]
+struct Property {
+    name: String,
+    params: Vec<(String, String)>,
+    value: String,
+}
+
+impl Property {
+    /// Parameter values may be quoted and hold `:` or `;`.
+    fn parse(line: &str) -> Option<Self> {
+        let mut in_quotes = false;
+        let mut value_at = None;
+        for (i, ch) in line.char_indices() {
+            match ch {
+                '"' => in_quotes = !in_quotes,
+                ':' if !in_quotes => {
+                    value_at = Some(i);
+                    break;
+                }
+                _ => {}
+            }
+        }
+        let value_at = value_at?;
+        let mut parts = line[..value_at].split(';');
+        let name = parts.next()?.trim().to_uppercase();
+        let params = parts
+            .filter_map(|param| param.split_once('='))
+            .map(|(key, value)| (key.trim().to_uppercase(), value.trim_matches('"').to_string()))
+            .collect();
+        Some(Self { name, params, value: line[value_at + 1..].to_string() })
+    }
+
+    fn param(&self, key: &str) -> Option<&str> {
+        self.params.iter().find(|(name, _)| name == key).map(|(_, value)| value.as_str())
+    }
+}
+
+/// Joins folded lines back together (RFC 5545 §3.1).
+fn unfold(text: &str) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+    for line in text.lines() {
+        match (line.strip_prefix([' ', '\t']), lines.last_mut()) {
+            (Some(rest), Some(last)) => last.push_str(rest),
+            _ => lines.push(line.to_string()),
+        }
+    }
+    lines
+}
+
+fn event_from(properties: &[Property], calendar_id: &str) -> Option<Event> {
+    let get = |name: &str| find(properties, name);
+    let text = |name: &str| get(name).map(|property| unescape_text(&property.value)).filter(|value| !value.is_empty());
+
+    let uid = text("UID")?;
+    let (start, all_day) = parse_date_time(get("DTSTART")?)?;
+    let end = get("DTEND")
+        .and_then(parse_date_time)
+        .map(|(end, _)| end)
+        .or_else(|| get("DURATION").and_then(|property| parse_duration(&property.value)).map(|duration| start + duration))
+        .unwrap_or(if all_day { start + Duration::days(1) } else { start });
+
+    // Moved occurrences of a repeating event share its UID.
+    let id = match get("RECURRENCE-ID").and_then(parse_date_time) {
+        Some((at, all_day)) => format!("{}:{}:{}", calendar_id, uid, occurrence_key(&at, all_day)),
+        None => format!("{}:{}", calendar_id, uid),
+    };
+    let status = match get("STATUS").map(|property| property.value.trim().to_uppercase()).as_deref() {
+        Some("TENTATIVE") => EventStatus::Tentative,
+        Some("CANCELLED") => EventStatus::Cancelled,
+        _ => EventStatus::Confirmed,
+    };
+    let transparency = match get("TRANSP").map(|property| property.value.trim().to_uppercase()).as_deref() {
+        Some("TRANSPARENT") => Transparency::Transparent,
+        _ => Transparency::Opaque,
+    };
+    let last_modified = get("LAST-MODIFIED").or_else(|| get("DTSTAMP"))
+        .and_then(parse_date_time)
+        .map_or(start, |(modified, _)| modified);
+
+    Some(Event {
+        id,
+        calendar_id: calendar_id.to_string(),
+        title: text("SUMMARY").unwrap_or_default(),
+        description: text("DESCRIPTION"),
+        location: text("LOCATION"),
+        start,
+        end: end.max(start),
+        all_day,
+        attendees: vec![],
+        reminders: vec![],
+        status,
+        last_modified,
+        html_link: get("URL").map(|property| property.value.trim().to_string()),
+        time_zone: get("DTSTART").and_then(|property| property.param("TZID")).map(String::from),
+        conference_url: None,
+        color_id: None,
+        color: None,
+        self_response: None,
+        attendee_responses: Default::default(),
+        visibility: Visibility::Default,
+        transparency,
+        etag: None,
+        organizer: None,
+        creator: None,
+    })
+}
+
+/// A DATE value is all-day and kept at UTC midnight, as Google sends them.
+/// DATE-TIMEs are UTC with a `Z`, in their TZID, or else in local time.
+fn parse_date_time(property: &Property) -> Option<(DateTime<Utc>, bool)> {
+    let (local, zone, all_day) = parse_local(property)?;
+    Some((zone.from_local(&local)?, all_day))
+}
+
+/// The wall-clock time of a DATE or DATE-TIME and the zone it is in, which
+/// repeating events keep across DST changes.
+fn parse_local(property: &Property) -> Option<(NaiveDateTime, DisplayTimezone, bool)> {
+    let value = property.value.trim();
+    if property.param("VALUE") == Some("DATE") || value.len() == 8 {
+        let date = NaiveDate::parse_from_str(value, "%Y%m%d").ok()?;
+        return Some((date.and_time(NaiveTime::MIN), DisplayTimezone::Named(chrono_tz::UTC), true));
+    }
+    if let Some(utc) = value.strip_suffix(['Z', 'z']) {
+        let local = NaiveDateTime::parse_from_str(utc, "%Y%m%dT%H%M%S").ok()?;
+        return Some((local, DisplayTimezone::Named(chrono_tz::UTC), false));
+    }
+    let local = NaiveDateTime::parse_from_str(value, "%Y%m%dT%H%M%S").ok()?;
+    let zone = property.param("TZID")
+        .and_then(|tzid| tzid.trim_start_matches('/').parse::<chrono_tz::Tz>().ok())
+        .map_or(DisplayTimezone::Local, DisplayTimezone::Named);
+    Some((local, zone, false))
+}
+
+/// `P1W`, `P1D`, `PT1H30M`, `P1DT12H` and so on; negative durations are
+/// not end times.
+fn parse_duration(value: &str) -> Option<Duration> {
+    let rest = value.trim().strip_prefix('+').unwrap_or(value.trim()).strip_prefix('P')?;
+    let mut total = Duration::zero();
+    let mut number = String::new();
+    for ch in rest.chars() {
+        match ch {
+            '0'..='9' => number.push(ch),
+            'T' => {}
+            unit => {
+                let amount: i64 = number.parse().ok()?;
+                number.clear();
+                total += match unit {
+                    'W' => Duration::weeks(amount),
+                    'D' => Duration::days(amount),
+                    'H' => Duration::hours(amount),
+                    'M' => Duration::minutes(amount),
+                    'S' => Duration::seconds(amount),
+                    _ => return None,
+                };
+            }
+        }
+    }
+    number.is_empty().then_some(total)
+}
+
+fn unescape_text(text: &str) -> String {
+    let mut unescaped = String::with_capacity(text.len());
+    let mut chars = text.chars();
+    while let Some(ch) = chars.next() {
+        if ch != '\\' {
+            unescaped.push(ch);
+            continue;
+        }
+        match chars.next() {
+            Some('n' | 'N') => unescaped.push('\n'),
+            Some(other) => unescaped.push(other),
+            None => unescaped.push('\\'),
+        }
+    }
+    unescaped.trim().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::EventBuilder;
+    use chrono::TimeZone;
+
+    fn event(id: &str) -> Event {
+        let start = Utc.with_ymd_and_hms(2025, 1, 15, 9, 0, 0).unwrap();
+        EventBuilder::new(id).title("Standup").at(start, chrono::Duration::minutes(15)).build()
+    }
+
+    #[test]
+    fn writes_calendar_with_timed_event() {
+        let ics = to_ics(&[event("e1")]);
+
+        assert!(ics.starts_with("BEGIN:VCALENDAR\r\nVERSION:2.0\r\n"));
+        assert!(ics.contains("UID:e1\r\n"));
+        assert!(ics.contains("DTSTART:20250115T090000Z\r\n"));
+        assert!(ics.contains("DTEND:20250115T091500Z\r\n"));
+        assert!(ics.ends_with("END:VEVENT\r\nEND:VCALENDAR\r\n"));
+    }
+
+    #[test]
+    fn all_day_events_use_date_values() {
+        let mut e = event("e1");
+        e.all_day = true;
+        e.start = Utc.with_ymd_and_hms(2025, 1, 15, 0, 0, 0).unwrap();
+        e.end = Utc.with_ymd_and_hms(2025, 1, 16, 0, 0, 0).unwrap();
+
+        let ics = to_ics(&[e]);
+
+        assert!(ics.contains("DTSTART;VALUE=DATE:20250115\r\n"));
+        assert!(ics.contains("DTEND;VALUE=DATE:20250116\r\n"));
+    }
+
+    #[test]
+    fn escapes_text_values() {
+        let mut e = event("e1");
+        e.description = Some("Line one\nBring snacks, drinks; cups".to_string());
+
+        assert!(to_ics(&[e]).contains("DESCRIPTION:Line one\\nBring snacks\\, drinks\\; cups\r\n"));
+    }
+
+    #[test]
+    fn folds_long_lines() {
+        let folded = fold_line(&"x".repeat(100));
+
+        assert_eq!(folded, format!("{}\r\n {}", "x".repeat(75), "x".repeat(25)));
+    }
+
+    const FEED: &str = concat!(
+        "BEGIN:VCALENDAR\r\n",
+        "VERSION:2.0\r\n",
+        "BEGIN:VEVENT\r\n",
+        "UID:match-1@league.example\r\n",
+        "DTSTART;TZID=Europe/London:20250118T150000\r\n",
+        "DURATION:PT1H45M\r\n",
+        "SUMMARY:Rovers v United\r\n",
+        "LOCATION:Park Ground\\, Field 2\r\n",
+        "DESCRIPTION:Kick-off 3pm.\\nBring the away ki\r\n",
+        " t.\r\n",
+        "URL:https://league.example/match/1\r\n",
+        "BEGIN:VALARM\r\n",
+        "DESCRIPTION:Reminder\r\n",
+        "END:VALARM\r\n",
+        "END:VEVENT\r\n",
+        "BEGIN:VEVENT\r\n",
+        "UID:rota-7\r\n",
+        "DTSTART;VALUE=DATE:20250120\r\n",
+        "SUMMARY:On call: Sam\r\n",
+        "STATUS:TENTATIVE\r\n",
+        "TRANSP:TRANSPARENT\r\n",
+        "END:VEVENT\r\n",
+        "BEGIN:VEVENT\r\n",
+        "SUMMARY:No uid\r\n",
+        "DTSTART:20250121T090000Z\r\n",
+        "END:VEVENT\r\n",
+        "END:VCALENDAR\r\n",
+    );
+
+    fn january() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap()
+    }
+
+    fn february() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2025, 2, 1, 0, 0, 0).unwrap()
+    }
+
+    const TRAINING: &str = concat!(
+        "BEGIN:VCALENDAR\r\n",
+        "BEGIN:VEVENT\r\n",
+        "UID:training\r\n",
+        "DTSTART;TZID=Europe/London:20241105T180000\r\n",
+        "DTEND;TZID=Europe/London:20241105T193000\r\n",
+        "RRULE:FREQ=WEEKLY;UNTIL=20250128T235959Z\r\n",
+        "EXDATE;TZID=Europe/London:20241224T180000,20241231T180000\r\n",
+        "EXDATE;TZID=Europe/London:20250114T180000\r\n",
+        "SUMMARY:Training\r\n",
+        "END:VEVENT\r\n",
+        "BEGIN:VEVENT\r\n",
+        "UID:training\r\n",
+        "RECURRENCE-ID;TZID=Europe/London:20250121T180000\r\n",
+        "DTSTART;TZID=Europe/London:20250122T180000\r\n",
+        "DTEND;TZID=Europe/London:20250122T193000\r\n",
+        "SUMMARY:Training (moved)\r\n",
+        "END:VEVENT\r\n",
+        "END:VCALENDAR\r\n",
+    );
+
+    #[test]
+    fn repeating_events_are_expanded_within_the_window() {
+        let events = from_ics(TRAINING, "ics:club", Utc.with_ymd_and_hms(2024, 12, 16, 0, 0, 0).unwrap(), february());
+
+        let starts: Vec<String> = events.iter().map(|e| e.start.format("%m-%d %H:%M").to_string()).collect();
+        assert_eq!(starts, vec!["12-17 18:00", "01-07 18:00", "01-28 18:00", "01-22 18:00"]);
+        assert_eq!(events[0].id, "ics:club:training:20241217T180000Z");
+        assert_eq!(events[0].duration_minutes(), 90);
+        assert_eq!(events[3].id, "ics:club:training:20250121T180000Z");
+        assert_eq!(events[3].title, "Training (moved)");
+    }
+
+    #[test]
+    fn repeating_events_show_up_after_their_first_occurrence() {
+        let late = Utc.with_ymd_and_hms(2025, 1, 27, 0, 0, 0).unwrap();
+
+        let events = from_ics(TRAINING, "ics:club", late, february());
+
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].start, Utc.with_ymd_and_hms(2025, 1, 28, 18, 0, 0).unwrap());
+    }
+
+    #[test]
+    fn reads_feed_events() {
+        let events = from_ics(FEED, "ics:league", january(), february());
+
+        assert_eq!(events.len(), 2);
+        let game = &events[0];
+        assert_eq!(game.id, "ics:league:match-1@league.example");
+        assert_eq!(game.calendar_id, "ics:league");
+        assert_eq!(game.start, Utc.with_ymd_and_hms(2025, 1, 18, 15, 0, 0).unwrap());
+        assert_eq!(game.end, Utc.with_ymd_and_hms(2025, 1, 18, 16, 45, 0).unwrap());
+        assert_eq!(game.location.as_deref(), Some("Park Ground, Field 2"));
+        assert_eq!(game.description.as_deref(), Some("Kick-off 3pm.\nBring the away kit."));
+        assert_eq!(game.html_link.as_deref(), Some("https://league.example/match/1"));
+        assert_eq!(game.time_zone.as_deref(), Some("Europe/London"));
+    }
+
+    #[test]
+    fn dates_are_all_day_and_default_to_one_day() {
+        let shift = &from_ics(FEED, "ics:rota", january(), february())[1];
+
+        assert!(shift.all_day);
+        assert_eq!(shift.start, Utc.with_ymd_and_hms(2025, 1, 20, 0, 0, 0).unwrap());
+        assert_eq!(shift.end, Utc.with_ymd_and_hms(2025, 1, 21, 0, 0, 0).unwrap());
+        assert_eq!(shift.status, EventStatus::Tentative);
+        assert_eq!(shift.transparency, Transparency::Transparent);
+    }
+
+    #[test]
+    fn reads_back_what_it_writes() {
+        let mut e = event("e1");
+        e.description = Some("Line one\nBring snacks, drinks; cups".to_string());
+
+        let read = from_ics(&to_ics(&[e.clone()]), "primary", january(), february()).remove(0);
+
+        assert_eq!(read.id, "primary:e1");
+        assert_eq!((read.start, read.end), (e.start, e.end));
+        assert_eq!(read.description, e.description);
+        assert_eq!(parse_duration("P1DT2H"), Some(Duration::hours(26)));
+    }
+}
@@ -0,0 +1,86 @@
+use super::Event;
+
+/// Where `M` and the detail view's map link look up an event's location.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum MapProvider {
+    #[default]
+    OpenStreetMap,
+    Google,
+}
+
+impl MapProvider {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.trim().to_lowercase().as_str() {
+            "openstreetmap" | "osm" => Some(Self::OpenStreetMap),
+            "google" | "gmaps" => Some(Self::Google),
+            _ => None,
+        }
+    }
+
+    pub fn from_config(name: Option<&str>) -> Self {
+        match name {
+            Some(name) => Self::from_name(name).unwrap_or_else(|| {
+                tracing::warn!("Unknown map provider '{}', using OpenStreetMap", name);
+                Self::OpenStreetMap
+            }),
+            None => Self::OpenStreetMap,
+        }
+    }
+
+    /// A search for `query` on the provider's map.
+    pub fn search_url(self, query: &str) -> String {
+        let query = urlencoding::encode(query.trim());
+        match self {
+            Self::OpenStreetMap => format!("https://www.openstreetmap.org/search?query={}", query),
+            Self::Google => format!("https://www.google.com/maps/search/?api=1&query={}", query),
+        }
+    }
+}
+
+impl Event {
+    /// The location unless there is none or it is a link (a video call, say).
+    pub fn physical_location(&self) -> Option<&str> {
+        let location = self.location.as_deref()?.trim();
+        let is_link = location.starts_with("http://") || location.starts_with("https://");
+        (!location.is_empty() && !is_link).then_some(location)
+    }
+
+    pub fn map_url(&self, provider: MapProvider) -> Option<String> {
+        self.physical_location().map(|location| provider.search_url(location))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::EventBuilder;
+    use chrono::{Duration, TimeZone, Utc};
+
+    fn event(location: Option<&str>) -> Event {
+        let start = Utc.with_ymd_and_hms(2025, 1, 13, 9, 0, 0).unwrap();
+        EventBuilder::new("offsite").title("Offsite").location(location).at(start, Duration::hours(8)).build()
+    }
+
+    #[test]
+    fn locations_become_encoded_map_searches() {
+        let offsite = event(Some(" Café Nero, 1 High St & Co "));
+
+        assert_eq!(
+            offsite.map_url(MapProvider::OpenStreetMap).as_deref(),
+            Some("https://www.openstreetmap.org/search?query=Caf%C3%A9%20Nero%2C%201%20High%20St%20%26%20Co"),
+        );
+        assert_eq!(
+            offsite.map_url(MapProvider::Google).as_deref(),
+            Some("https://www.google.com/maps/search/?api=1&query=Caf%C3%A9%20Nero%2C%201%20High%20St%20%26%20Co"),
+        );
+        assert_eq!(MapProvider::from_name("OSM"), Some(MapProvider::OpenStreetMap));
+        assert_eq!(MapProvider::from_config(Some("bing")), MapProvider::OpenStreetMap);
+    }
+
+    #[test]
+    fn links_and_missing_locations_have_no_map() {
+        assert_eq!(event(Some("https://zoom.us/j/123")).map_url(MapProvider::Google), None);
+        assert_eq!(event(Some("  ")).map_url(MapProvider::Google), None);
+        assert_eq!(event(None).map_url(MapProvider::Google), None);
+    }
+}
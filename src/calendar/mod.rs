@@ -1,7 +1,21 @@
 pub mod event;
 pub mod calendar_type;
+pub mod timezone;
+pub mod reminders;
+pub mod ics;
+pub mod date_expr;
+pub mod quick_add;
+pub mod free_slots;
+pub mod working_hours;
+pub mod overlay;
+pub mod event_diff;
+pub mod map_link;
+pub mod travel;
+pub mod subscription;
+pub mod recurrence;
 
-pub use event::{Event, EventStatus, Reminder, ReminderMethod};
+pub use event::{Event, EventStatus, Reminder, ReminderMethod, ResponseStatus, Transparency, Visibility};
 pub use calendar_type::{Calendar, AccessRole};
+pub use timezone::DisplayTimezone;
 
 pub const DEFAULT_CALENDAR_ID: &str = "primary";
@@ -0,0 +1,63 @@
+use super::subscription;
+
+pub const BIRTHDAYS_CALENDAR_ID: &str = "addressbook#contacts@group.v.calendar.google.com";
+const HOLIDAY_SUFFIX: &str = "#holiday@group.v.calendar.google.com";
+const DEFAULT_HOLIDAY_REGION: &str = "usa";
+
+/// Maps `birthdays`, `holidays` or `holidays <region>` (e.g. `uk`, `german`)
+/// to a Google calendar id; anything else is taken as an id already.
+pub fn resolve(name: &str) -> String {
+    let mut words = name.split_whitespace();
+    match (words.next().map(str::to_lowercase).as_deref(), words.next()) {
+        (Some("birthdays"), None) => BIRTHDAYS_CALENDAR_ID.to_string(),
+        (Some("holidays"), region) => {
+            format!("en.{}{}", region.unwrap_or(DEFAULT_HOLIDAY_REGION).to_lowercase(), HOLIDAY_SUFFIX)
+        }
+        _ => name.trim().to_string(),
+    }
+}
+
+pub fn is_overlay(calendar_id: &str) -> bool {
+    calendar_id == BIRTHDAYS_CALENDAR_ID || calendar_id.ends_with(HOLIDAY_SUFFIX)
+}
+
+/// A readable name for overlay and subscription ids; other ids are
+/// returned unchanged.
+pub fn label(calendar_id: &str) -> String {
+    if calendar_id == BIRTHDAYS_CALENDAR_ID {
+        return "Birthdays".to_string();
+    }
+    if let Some(name) = subscription::name(calendar_id) {
+        return format!("{} (feed)", name);
+    }
+    match calendar_id.strip_suffix(HOLIDAY_SUFFIX) {
+        Some(region) => {
+            let region = region.split_once('.').map_or(region, |(_, region)| region);
+            format!("Holidays ({})", region)
+        }
+        None => calendar_id.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resolves_short_names() {
+        assert_eq!(resolve("birthdays"), BIRTHDAYS_CALENDAR_ID);
+        assert_eq!(resolve("holidays"), "en.usa#holiday@group.v.calendar.google.com");
+        assert_eq!(resolve("holidays UK"), "en.uk#holiday@group.v.calendar.google.com");
+        assert_eq!(resolve("team@example.com"), "team@example.com");
+    }
+
+    #[test]
+    fn labels_overlay_ids() {
+        assert!(is_overlay(&resolve("holidays german")));
+        assert_eq!(label(&resolve("holidays german")), "Holidays (german)");
+        assert_eq!(label(BIRTHDAYS_CALENDAR_ID), "Birthdays");
+        assert_eq!(label("primary"), "primary");
+        assert_eq!(label("ics:league"), "league (feed)");
+        assert!(!is_overlay("primary"));
+    }
+}
@@ -0,0 +1,368 @@
+use std::sync::OnceLock;
+use chrono::{DateTime, Duration, NaiveDate, NaiveTime, Utc};
+use regex::Regex;
+use thiserror::Error;
+
+use crate::calendar::{date_expr, DisplayTimezone, Event, EventStatus, Transparency, Visibility};
+
+pub const DEFAULT_DURATION_MINUTES: u32 = 60;
+
+#[derive(Debug, Error, PartialEq)]
+pub enum QuickAddError {
+    #[error("Event needs a title")]
+    MissingTitle,
+    #[error("Invalid duration '{0}'")]
+    InvalidDuration(String),
+    #[error("{0} does not exist in the display timezone")]
+    NonexistentTime(String),
+}
+
+/// An event described in free text, e.g.
+/// `Lunch with Sam tomorrow 12:30 for 45m at Cafe`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct QuickAdd {
+    pub title: String,
+    pub date: Option<NaiveDate>,
+    pub start_time: Option<NaiveTime>,
+    pub duration_minutes: u32,
+    pub location: Option<String>,
+}
+
+static TIME_RE: OnceLock<Regex> = OnceLock::new();
+static DURATION_RE: OnceLock<Regex> = OnceLock::new();
+
+fn time_pattern() -> &'static Regex {
+    TIME_RE.get_or_init(|| {
+        Regex::new(r"^(\d{1,2})(?::(\d{2}))?(am|pm)?$").expect("invalid time regex")
+    })
+}
+
+fn duration_pattern() -> &'static Regex {
+    DURATION_RE.get_or_init(|| {
+        Regex::new(r"^(?:(\d+)(?:h|hr|hrs|hour|hours))?(?:(\d+)(?:m|min|mins|minute|minutes))?$")
+            .expect("invalid duration regex")
+    })
+}
+
+impl QuickAdd {
+    pub fn is_all_day(&self) -> bool {
+        self.start_time.is_none()
+    }
+
+    /// The event to create, on today's date at `now` when no date was given.
+    pub fn to_event(&self, id: String, calendar_id: &str, tz: &DisplayTimezone, now: DateTime<Utc>) -> Result<Event, QuickAddError> {
+        if self.title.is_empty() {
+            return Err(QuickAddError::MissingTitle);
+        }
+
+        let date = self.date.unwrap_or_else(|| tz.date_of(&now));
+        let (start, end) = match self.start_time {
+            Some(time) => {
+                let naive = date.and_time(time);
+                let start = tz.from_local(&naive)
+                    .ok_or_else(|| QuickAddError::NonexistentTime(naive.to_string()))?;
+                (start, start + Duration::minutes(self.duration_minutes as i64))
+            }
+            None => {
+                let start = date.and_time(NaiveTime::MIN).and_utc();
+                (start, start + Duration::days(1))
+            }
+        };
+
+        Ok(Event {
+            id,
+            calendar_id: calendar_id.to_string(),
+            title: self.title.clone(),
+            description: None,
+            location: self.location.clone(),
+            start,
+            end,
+            all_day: self.is_all_day(),
+            attendees: vec![],
+            reminders: vec![],
+            status: EventStatus::Confirmed,
+            last_modified: now,
+            html_link: None,
+            time_zone: if self.is_all_day() { None } else { tz.name() },
+            conference_url: None,
+            color_id: None,
+            color: None,
+            self_response: None,
+            attendee_responses: Default::default(),
+            visibility: Visibility::Default,
+            transparency: Transparency::Opaque,
+            etag: None,
+            organizer: None,
+            creator: None,
+        })
+    }
+}
+
+pub fn parse_quick_add(input: &str, today: NaiveDate) -> Result<QuickAdd, QuickAddError> {
+    let tokens: Vec<&str> = input.split_whitespace().collect();
+    let mut title_words = Vec::new();
+    let mut date = None;
+    let mut start_time = None;
+    let mut duration = None;
+    let mut location = None;
+    let mut i = 0;
+
+    while i < tokens.len() {
+        let token = tokens[i];
+        let lower = token.to_lowercase();
+        let next = tokens.get(i + 1).map(|t| t.to_lowercase());
+
+        if lower == "for"
+            && let Some(next) = &next
+        {
+            let unit = tokens.get(i + 2).map(|t| t.to_lowercase()).unwrap_or_default();
+            if let Some(minutes) = parse_duration(&format!("{}{}", next, unit)) {
+                duration = Some(minutes);
+                i += 3;
+                continue;
+            }
+            if let Some(minutes) = parse_duration(next) {
+                duration = Some(minutes);
+                i += 2;
+                continue;
+            }
+            if next.chars().next().is_some_and(|c| c.is_ascii_digit()) {
+                return Err(QuickAddError::InvalidDuration(next.clone()));
+            }
+        }
+
+        if lower == "at" || lower == "@" {
+            if let Some(time) = next.as_deref().and_then(parse_time) {
+                start_time = Some(time);
+                i += 2;
+                continue;
+            }
+
+            let end = (i + 1..tokens.len())
+                .find(|&j| is_keyword(&tokens[j].to_lowercase(), today))
+                .unwrap_or(tokens.len());
+            if end > i + 1 {
+                location = Some(tokens[i + 1..end].join(" "));
+                i = end;
+                continue;
+            }
+        }
+
+        if date.is_none()
+            && matches!(lower.as_str(), "next" | "last" | "this" | "on")
+            && let Some(next) = &next
+        {
+            let expr = if lower == "on" { next.clone() } else { format!("{} {}", lower, next) };
+            if let Ok(parsed) = date_expr::parse_date(&expr, today) {
+                date = Some(parsed);
+                i += 2;
+                continue;
+            }
+        }
+
+        if date.is_none() && let Some(parsed) = parse_date_token(&lower, today) {
+            date = Some(parsed);
+        } else if start_time.is_none() && let Some(time) = parse_time(&lower) {
+            start_time = Some(time);
+        } else if duration.is_none() && let Some(minutes) = parse_duration(&lower) {
+            duration = Some(minutes);
+        } else {
+            title_words.push(token);
+        }
+        i += 1;
+    }
+
+    Ok(QuickAdd {
+        title: title_words.join(" "),
+        date,
+        start_time,
+        duration_minutes: duration.unwrap_or(DEFAULT_DURATION_MINUTES),
+        location,
+    })
+}
+
+/// Single-word dates; bare `week`/`month` are left for the title.
+fn parse_date_token(text: &str, today: NaiveDate) -> Option<NaiveDate> {
+    if matches!(text, "week" | "month") {
+        return None;
+    }
+    date_expr::parse_date(text, today).ok()
+}
+
+fn is_keyword(text: &str, today: NaiveDate) -> bool {
+    matches!(text, "for" | "on" | "next" | "this" | "last")
+        || parse_date_token(text, today).is_some()
+        || parse_time(text).is_some()
+        || parse_duration(text).is_some()
+}
+
+fn parse_time(text: &str) -> Option<NaiveTime> {
+    match text {
+        "noon" => return NaiveTime::from_hms_opt(12, 0, 0),
+        "midnight" => return Some(NaiveTime::MIN),
+        _ => {}
+    }
+
+    let caps = time_pattern().captures(text)?;
+    let mut hour: u32 = caps[1].parse().ok()?;
+    let minute: u32 = caps.get(2).map_or(Some(0), |m| m.as_str().parse().ok())?;
+
+    match caps.get(3).map(|m| m.as_str()) {
+        Some(suffix) => {
+            if hour == 0 || hour > 12 {
+                return None;
+            }
+            hour = match (suffix, hour) {
+                ("am", 12) => 0,
+                ("pm", 12) => 12,
+                ("pm", h) => h + 12,
+                (_, h) => h,
+            };
+        }
+        // A bare number is a day count or part of the title, not a time.
+        None if caps.get(2).is_none() => return None,
+        None => {}
+    }
+
+    NaiveTime::from_hms_opt(hour, minute, 0)
+}
+
+pub(crate) fn parse_duration(text: &str) -> Option<u32> {
+    let caps = duration_pattern().captures(text)?;
+    if caps.get(1).is_none() && caps.get(2).is_none() {
+        return None;
+    }
+
+    let hours: u32 = caps.get(1).map_or(Some(0), |m| m.as_str().parse().ok())?;
+    let minutes: u32 = caps.get(2).map_or(Some(0), |m| m.as_str().parse().ok())?;
+    let total = hours * 60 + minutes;
+    (total > 0).then_some(total)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Wednesday
+    fn today() -> NaiveDate {
+        NaiveDate::from_ymd_opt(2025, 1, 15).unwrap()
+    }
+
+    fn now() -> DateTime<Utc> {
+        today().and_hms_opt(12, 0, 0).unwrap().and_utc()
+    }
+
+    fn time(hour: u32, minute: u32) -> Option<NaiveTime> {
+        NaiveTime::from_hms_opt(hour, minute, 0)
+    }
+
+    #[test]
+    fn parses_full_sentence() {
+        let qa = parse_quick_add("Lunch with Sam tomorrow 12:30 for 45m at Cafe Nero", today()).unwrap();
+
+        assert_eq!(qa.title, "Lunch with Sam");
+        assert_eq!(qa.date, NaiveDate::from_ymd_opt(2025, 1, 16));
+        assert_eq!(qa.start_time, time(12, 30));
+        assert_eq!(qa.duration_minutes, 45);
+        assert_eq!(qa.location.as_deref(), Some("Cafe Nero"));
+    }
+
+    #[test]
+    fn parses_am_pm_times_and_bare_durations() {
+        let qa = parse_quick_add("Standup friday 9am 15m", today()).unwrap();
+
+        assert_eq!(qa.title, "Standup");
+        assert_eq!(qa.date, NaiveDate::from_ymd_opt(2025, 1, 17));
+        assert_eq!(qa.start_time, time(9, 0));
+        assert_eq!(qa.duration_minutes, 15);
+    }
+
+    #[test]
+    fn at_followed_by_time_sets_start() {
+        let qa = parse_quick_add("Dentist next monday at 3:15pm for 1h30m", today()).unwrap();
+
+        assert_eq!(qa.title, "Dentist");
+        assert_eq!(qa.date, NaiveDate::from_ymd_opt(2025, 1, 20));
+        assert_eq!(qa.start_time, time(15, 15));
+        assert_eq!(qa.duration_minutes, 90);
+        assert_eq!(qa.location, None);
+    }
+
+    #[test]
+    fn for_with_separate_unit() {
+        let qa = parse_quick_add("Review 14:00 for 2 hours", today()).unwrap();
+
+        assert_eq!(qa.duration_minutes, 120);
+    }
+
+    #[test]
+    fn without_date_or_time_is_undated_all_day() {
+        let qa = parse_quick_add("Team offsite", today()).unwrap();
+
+        assert!(qa.is_all_day());
+        assert_eq!(qa.date, None);
+        assert_eq!(qa.title, "Team offsite");
+    }
+
+    #[test]
+    fn location_stops_at_next_keyword() {
+        let qa = parse_quick_add("Lunch at Cafe Nero tomorrow noon", today()).unwrap();
+
+        assert_eq!(qa.title, "Lunch");
+        assert_eq!(qa.location.as_deref(), Some("Cafe Nero"));
+        assert_eq!(qa.start_time, time(12, 0));
+    }
+
+    #[test]
+    fn plain_week_word_stays_in_title() {
+        let qa = parse_quick_add("Plan the week monday 9am", today()).unwrap();
+
+        assert_eq!(qa.title, "Plan the week");
+        assert_eq!(qa.date, NaiveDate::from_ymd_opt(2025, 1, 20));
+    }
+
+    #[test]
+    fn bare_numbers_stay_in_title() {
+        let qa = parse_quick_add("Call 3 vendors 10:00", today()).unwrap();
+
+        assert_eq!(qa.title, "Call 3 vendors");
+    }
+
+    #[test]
+    fn missing_title_is_an_error_when_creating() {
+        let qa = parse_quick_add("tomorrow 9am", today()).unwrap();
+
+        assert_eq!(qa.title, "");
+        assert_eq!(qa.to_event("id".to_string(), "primary", &DisplayTimezone::Local, now()), Err(QuickAddError::MissingTitle));
+    }
+
+    #[test]
+    fn invalid_duration_is_an_error() {
+        assert!(matches!(
+            parse_quick_add("Gym 7am for 3 fortnights", today()),
+            Err(QuickAddError::InvalidDuration(_))
+        ));
+    }
+
+    #[test]
+    fn to_event_uses_display_timezone() {
+        let qa = parse_quick_add("Standup tomorrow 9am 15m", today()).unwrap();
+        let tz = DisplayTimezone::Named(chrono_tz::America::New_York);
+
+        let event = qa.to_event("id".to_string(), "primary", &tz, now()).unwrap();
+
+        assert_eq!(event.start.to_rfc3339(), "2025-01-16T14:00:00+00:00");
+        assert_eq!(event.duration_minutes(), 15);
+        assert_eq!(event.time_zone.as_deref(), Some("America/New_York"));
+    }
+
+    #[test]
+    fn all_day_event_spans_one_day() {
+        let qa = parse_quick_add("Holiday friday", today()).unwrap();
+
+        let event = qa.to_event("id".to_string(), "primary", &DisplayTimezone::Local, now()).unwrap();
+
+        assert!(event.all_day);
+        assert_eq!(event.duration_minutes(), 24 * 60);
+    }
+}
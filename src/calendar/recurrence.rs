@@ -0,0 +1,210 @@
+use chrono::{DateTime, Datelike, Duration, NaiveDate, NaiveDateTime, Utc, Weekday};
+
+/// Stops rules that would run for ever, e.g. a daily rule started decades ago.
+const MAX_PERIODS: i64 = 50_000;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Frequency {
+    Daily,
+    Weekly,
+    Monthly,
+    Yearly,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Until {
+    Date(NaiveDate),
+    /// A local time, for rules on floating DTSTARTs.
+    Local(NaiveDateTime),
+    Utc(DateTime<Utc>),
+}
+
+/// The parts of an RRULE (RFC 5545 §3.3.10) that feeds use for rosters and
+/// fixtures: FREQ, INTERVAL, COUNT, UNTIL and, for weekly rules, BYDAY.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Rule {
+    frequency: Frequency,
+    interval: i64,
+    count: Option<usize>,
+    until: Option<Until>,
+    weekdays: Vec<Weekday>,
+}
+
+impl Rule {
+    /// None for rules this does not understand, such as `BYSETPOS` or a
+    /// monthly `BYDAY=2TU`.
+    pub fn parse(value: &str) -> Option<Self> {
+        let mut frequency = None;
+        let mut interval = 1;
+        let mut count = None;
+        let mut until = None;
+        let mut weekdays = Vec::new();
+
+        for part in value.trim().split(';').filter(|part| !part.is_empty()) {
+            let (key, value) = part.split_once('=')?;
+            let value = value.trim();
+            match key.trim().to_uppercase().as_str() {
+                "FREQ" => {
+                    frequency = Some(match value.to_uppercase().as_str() {
+                        "DAILY" => Frequency::Daily,
+                        "WEEKLY" => Frequency::Weekly,
+                        "MONTHLY" => Frequency::Monthly,
+                        "YEARLY" => Frequency::Yearly,
+                        _ => return None,
+                    })
+                }
+                "INTERVAL" => interval = value.parse().ok().filter(|interval| *interval > 0)?,
+                "COUNT" => count = Some(value.parse().ok()?),
+                "UNTIL" => until = Some(parse_until(value)?),
+                "BYDAY" => weekdays = value.split(',').map(parse_weekday).collect::<Option<_>>()?,
+                "WKST" => {}
+                _ => return None,
+            }
+        }
+
+        let frequency = frequency?;
+        if !weekdays.is_empty() && frequency != Frequency::Weekly {
+            return None;
+        }
+        weekdays.sort_by_key(|day: &Weekday| day.num_days_from_monday());
+        Some(Self { frequency, interval, count, until, weekdays })
+    }
+
+    /// Occurrence starts from `start`, which is the first, up to
+    /// `window_end`. Times are local; `to_utc` places them on the timeline
+    /// and drops those a DST change skips.
+    pub fn occurrences(
+        &self,
+        start: NaiveDateTime,
+        window_end: DateTime<Utc>,
+        to_utc: impl Fn(&NaiveDateTime) -> Option<DateTime<Utc>>,
+    ) -> Vec<DateTime<Utc>> {
+        let mut occurrences = Vec::new();
+        let mut generated = 0;
+        for period in 0..MAX_PERIODS {
+            for local in self.period(start, period) {
+                if local < start {
+                    continue;
+                }
+                let past_until = match self.until {
+                    Some(Until::Date(date)) => local.date() > date,
+                    Some(Until::Local(until)) => local > until,
+                    Some(Until::Utc(until)) => to_utc(&local).is_some_and(|at| at > until),
+                    None => false,
+                };
+                if past_until || self.count.is_some_and(|count| generated >= count) {
+                    return occurrences;
+                }
+                generated += 1;
+                match to_utc(&local) {
+                    Some(at) if at > window_end => return occurrences,
+                    Some(at) => occurrences.push(at),
+                    None => {}
+                }
+            }
+        }
+        occurrences
+    }
+
+    /// The candidates in the `n`th period after `start`. Monthly and yearly
+    /// rules skip dates that do not exist, such as the 31st in April.
+    fn period(&self, start: NaiveDateTime, n: i64) -> Vec<NaiveDateTime> {
+        let step = n * self.interval;
+        let date = start.date();
+        let dates = match self.frequency {
+            Frequency::Daily => vec![date + Duration::days(step)],
+            Frequency::Weekly if self.weekdays.is_empty() => vec![date + Duration::weeks(step)],
+            Frequency::Weekly => {
+                let monday = date - Duration::days(date.weekday().num_days_from_monday() as i64) + Duration::weeks(step);
+                self.weekdays.iter()
+                    .map(|day| monday + Duration::days(day.num_days_from_monday() as i64))
+                    .collect()
+            }
+            Frequency::Monthly => {
+                let month = date.year() as i64 * 12 + date.month0() as i64 + step;
+                i32::try_from(month.div_euclid(12)).ok()
+                    .and_then(|year| NaiveDate::from_ymd_opt(year, month.rem_euclid(12) as u32 + 1, date.day()))
+                    .into_iter()
+                    .collect()
+            }
+            Frequency::Yearly => i32::try_from(date.year() as i64 + step).ok()
+                .and_then(|year| NaiveDate::from_ymd_opt(year, date.month(), date.day()))
+                .into_iter()
+                .collect(),
+        };
+        dates.into_iter().map(|date| date.and_time(start.time())).collect()
+    }
+}
+
+fn parse_until(value: &str) -> Option<Until> {
+    if value.len() == 8 {
+        return NaiveDate::parse_from_str(value, "%Y%m%d").ok().map(Until::Date);
+    }
+    match value.strip_suffix(['Z', 'z']) {
+        Some(utc) => NaiveDateTime::parse_from_str(utc, "%Y%m%dT%H%M%S").ok().map(|at| Until::Utc(at.and_utc())),
+        None => NaiveDateTime::parse_from_str(value, "%Y%m%dT%H%M%S").ok().map(Until::Local),
+    }
+}
+
+fn parse_weekday(value: &str) -> Option<Weekday> {
+    match value.trim().to_uppercase().as_str() {
+        "MO" => Some(Weekday::Mon),
+        "TU" => Some(Weekday::Tue),
+        "WE" => Some(Weekday::Wed),
+        "TH" => Some(Weekday::Thu),
+        "FR" => Some(Weekday::Fri),
+        "SA" => Some(Weekday::Sat),
+        "SU" => Some(Weekday::Sun),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{TimeZone, Timelike};
+
+    fn local(day: u32, hour: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2025, 1, day).unwrap().and_hms_opt(hour, 0, 0).unwrap()
+    }
+
+    fn expand(rule: &str, start: NaiveDateTime, window_end: DateTime<Utc>) -> Vec<DateTime<Utc>> {
+        Rule::parse(rule).unwrap().occurrences(start, window_end, |local| Some(local.and_utc()))
+    }
+
+    #[test]
+    fn weekly_rules_repeat_on_their_days_until_the_count_runs_out() {
+        let end = Utc.with_ymd_and_hms(2025, 12, 31, 0, 0, 0).unwrap();
+
+        let practices = expand("FREQ=WEEKLY;BYDAY=TU,TH;COUNT=4", local(14, 18), end);
+
+        let days: Vec<u32> = practices.iter().map(|at| at.day()).collect();
+        assert_eq!(days, vec![14, 16, 21, 23]);
+        assert!(practices.iter().all(|at| at.hour() == 18));
+    }
+
+    #[test]
+    fn rules_stop_at_until_and_the_window_end() {
+        let end = Utc.with_ymd_and_hms(2025, 1, 10, 0, 0, 0).unwrap();
+
+        assert_eq!(expand("FREQ=DAILY;INTERVAL=2;UNTIL=20250105", local(1, 9), end).len(), 3);
+        assert_eq!(expand("FREQ=DAILY", local(1, 9), end).len(), 9);
+        assert_eq!(expand("FREQ=WEEKLY;UNTIL=20250108T090000Z", local(1, 9), end).len(), 2);
+    }
+
+    #[test]
+    fn monthly_rules_skip_months_without_the_day() {
+        let end = Utc.with_ymd_and_hms(2025, 6, 1, 0, 0, 0).unwrap();
+
+        let months: Vec<u32> = expand("FREQ=MONTHLY", local(31, 9), end).iter().map(|at| at.month()).collect();
+
+        assert_eq!(months, vec![1, 3, 5]);
+    }
+
+    #[test]
+    fn unsupported_rules_are_not_parsed() {
+        assert_eq!(Rule::parse("FREQ=MONTHLY;BYDAY=2TU"), None);
+        assert_eq!(Rule::parse("FREQ=WEEKLY;BYSETPOS=1"), None);
+        assert_eq!(Rule::parse("COUNT=3"), None);
+    }
+}
@@ -0,0 +1,103 @@
+use chrono::{DateTime, Duration, Utc};
+
+use crate::calendar::{Event, EventStatus, ReminderMethod};
+
+pub const DEFAULT_REMINDER_MINUTES: u32 = 10;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DueReminder {
+    pub event_id: String,
+    pub title: String,
+    pub start: DateTime<Utc>,
+    pub minutes_before: u32,
+}
+
+/// Popup reminders whose fire time falls in `(after, until]`. Events without
+/// explicit reminders get a single default one.
+pub fn due_reminders(events: &[Event], after: DateTime<Utc>, until: DateTime<Utc>) -> Vec<DueReminder> {
+    let mut due: Vec<DueReminder> = events
+        .iter()
+        .filter(|event| !event.all_day && event.status != EventStatus::Cancelled)
+        .flat_map(|event| {
+            let minutes: Vec<u32> = if event.reminders.is_empty() {
+                vec![DEFAULT_REMINDER_MINUTES]
+            } else {
+                event.reminders.iter()
+                    .filter(|r| r.method == ReminderMethod::Popup)
+                    .map(|r| r.minutes_before)
+                    .collect()
+            };
+
+            minutes.into_iter().filter_map(move |minutes_before| {
+                let fire_at = event.start - Duration::minutes(minutes_before as i64);
+                (fire_at > after && fire_at <= until).then(|| DueReminder {
+                    event_id: event.id.clone(),
+                    title: event.title.clone(),
+                    start: event.start,
+                    minutes_before,
+                })
+            })
+        })
+        .collect();
+
+    due.sort_by_key(|r| r.start);
+    due
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::EventBuilder;
+    use crate::calendar::Reminder;
+    use chrono::TimeZone;
+
+    fn event_at(id: &str, start: DateTime<Utc>) -> Event {
+        EventBuilder::new(id).at(start, Duration::hours(1)).build()
+    }
+
+    fn at(hour: u32, minute: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2025, 1, 15, hour, minute, 0).unwrap()
+    }
+
+    #[test]
+    fn default_reminder_fires_ten_minutes_before() {
+        let events = vec![event_at("e1", at(10, 0))];
+
+        let due = due_reminders(&events, at(9, 49), at(9, 50));
+
+        assert_eq!(due.len(), 1);
+        assert_eq!(due[0].minutes_before, DEFAULT_REMINDER_MINUTES);
+    }
+
+    #[test]
+    fn reminder_outside_window_is_not_due() {
+        let events = vec![event_at("e1", at(10, 0))];
+
+        assert!(due_reminders(&events, at(9, 50), at(9, 55)).is_empty());
+        assert!(due_reminders(&events, at(9, 40), at(9, 45)).is_empty());
+    }
+
+    #[test]
+    fn only_popup_reminders_fire() {
+        let mut event = event_at("e1", at(10, 0));
+        event.reminders = vec![
+            Reminder { method: ReminderMethod::Email, minutes_before: 30 },
+            Reminder { method: ReminderMethod::Popup, minutes_before: 5 },
+        ];
+
+        let due = due_reminders(&[event], at(9, 0), at(10, 0));
+
+        assert_eq!(due.len(), 1);
+        assert_eq!(due[0].minutes_before, 5);
+    }
+
+    #[test]
+    fn all_day_and_cancelled_events_are_skipped() {
+        let mut all_day = event_at("e1", at(10, 0));
+        all_day.all_day = true;
+        let mut cancelled = event_at("e2", at(10, 0));
+        cancelled.status = EventStatus::Cancelled;
+
+        assert!(due_reminders(&[all_day, cancelled], at(9, 0), at(10, 0)).is_empty());
+    }
+}
@@ -0,0 +1,52 @@
+const ID_PREFIX: &str = "ics:";
+
+/// The calendar id events from the `[subscriptions]` feed `name` are filed
+/// under, e.g. `ics:league`.
+pub fn calendar_id(name: &str) -> String {
+    format!("{}{}", ID_PREFIX, name)
+}
+
+pub fn is_subscription(calendar_id: &str) -> bool {
+    calendar_id.starts_with(ID_PREFIX)
+}
+
+/// The feed name of a subscription's calendar id.
+pub fn name(calendar_id: &str) -> Option<&str> {
+    calendar_id.strip_prefix(ID_PREFIX)
+}
+
+/// `webcal://` links are plain HTTPS feeds under another scheme.
+pub fn feed_url(url: &str) -> String {
+    let url = url.trim();
+    for scheme in ["webcal://", "webcals://"] {
+        if let Some(rest) = url.get(..scheme.len())
+            .filter(|prefix| prefix.eq_ignore_ascii_case(scheme))
+            .map(|_| &url[scheme.len()..])
+        {
+            return format!("https://{}", rest);
+        }
+    }
+    url.to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn names_round_trip_through_calendar_ids() {
+        let id = calendar_id("league");
+
+        assert_eq!(id, "ics:league");
+        assert!(is_subscription(&id));
+        assert_eq!(name(&id), Some("league"));
+        assert!(!is_subscription("primary"));
+    }
+
+    #[test]
+    fn webcal_links_are_fetched_over_https() {
+        assert_eq!(feed_url("webcal://league.example/team.ics"), "https://league.example/team.ics");
+        assert_eq!(feed_url(" WEBCALS://league.example/team.ics"), "https://league.example/team.ics");
+        assert_eq!(feed_url("http://intranet/rota.ics"), "http://intranet/rota.ics");
+    }
+}
@@ -0,0 +1,131 @@
+use chrono::{DateTime, Local, LocalResult, NaiveDate, NaiveDateTime, TimeZone, Utc};
+use chrono_tz::Tz;
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum DisplayTimezone {
+    #[default]
+    Local,
+    Named(Tz),
+}
+
+impl DisplayTimezone {
+    pub fn from_name(name: &str) -> Option<Self> {
+        let trimmed = name.trim();
+        if trimmed.is_empty() || trimmed.eq_ignore_ascii_case("local") {
+            return Some(Self::Local);
+        }
+        trimmed.parse::<Tz>().ok().map(Self::Named)
+    }
+
+    pub fn from_config(name: Option<&str>) -> Self {
+        match name {
+            Some(name) => Self::from_name(name).unwrap_or_else(|| {
+                tracing::warn!("Unknown timezone '{}', falling back to local time", name);
+                Self::Local
+            }),
+            None => Self::Local,
+        }
+    }
+
+    pub fn name(&self) -> Option<String> {
+        match self {
+            Self::Local => None,
+            Self::Named(tz) => Some(tz.name().to_string()),
+        }
+    }
+
+    pub fn to_local(&self, dt: &DateTime<Utc>) -> NaiveDateTime {
+        match self {
+            Self::Local => dt.with_timezone(&Local).naive_local(),
+            Self::Named(tz) => dt.with_timezone(tz).naive_local(),
+        }
+    }
+
+    pub fn date_of(&self, dt: &DateTime<Utc>) -> NaiveDate {
+        self.to_local(dt).date()
+    }
+
+    pub fn from_local(&self, naive: &NaiveDateTime) -> Option<DateTime<Utc>> {
+        let result = match self {
+            Self::Local => map_result(Local.from_local_datetime(naive)),
+            Self::Named(tz) => map_result(tz.from_local_datetime(naive)),
+        };
+
+        if result.is_none() {
+            tracing::warn!("{} does not exist in the display timezone", naive);
+        }
+        result
+    }
+}
+
+fn map_result<T: TimeZone>(result: LocalResult<DateTime<T>>) -> Option<DateTime<Utc>> {
+    match result {
+        LocalResult::Single(dt) => Some(dt.with_timezone(&Utc)),
+        LocalResult::Ambiguous(earliest, _) => Some(earliest.with_timezone(&Utc)),
+        LocalResult::None => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Timelike;
+
+    fn utc(year: i32, month: u32, day: u32, hour: u32, minute: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(year, month, day, hour, minute, 0).unwrap()
+    }
+
+    #[test]
+    fn parses_iana_names() {
+        let tz = DisplayTimezone::from_name("America/Los_Angeles");
+        assert_eq!(tz, Some(DisplayTimezone::Named(chrono_tz::America::Los_Angeles)));
+    }
+
+    #[test]
+    fn empty_and_local_names_mean_local() {
+        assert_eq!(DisplayTimezone::from_name(""), Some(DisplayTimezone::Local));
+        assert_eq!(DisplayTimezone::from_name("local"), Some(DisplayTimezone::Local));
+    }
+
+    #[test]
+    fn unknown_name_falls_back_to_local_from_config() {
+        assert_eq!(DisplayTimezone::from_config(Some("Mars/Olympus")), DisplayTimezone::Local);
+    }
+
+    #[test]
+    fn converts_utc_to_named_zone() {
+        let tz = DisplayTimezone::Named(chrono_tz::America::Los_Angeles);
+        let local = tz.to_local(&utc(2025, 1, 15, 17, 0));
+
+        assert_eq!(local.hour(), 9);
+    }
+
+    #[test]
+    fn date_of_crosses_midnight() {
+        let tz = DisplayTimezone::Named(chrono_tz::Asia::Tokyo);
+
+        assert_eq!(
+            tz.date_of(&utc(2025, 1, 15, 20, 0)),
+            NaiveDate::from_ymd_opt(2025, 1, 16).unwrap()
+        );
+    }
+
+    #[test]
+    fn from_local_round_trips() {
+        let tz = DisplayTimezone::Named(chrono_tz::Europe::Berlin);
+        let naive = NaiveDate::from_ymd_opt(2025, 7, 1).unwrap().and_hms_opt(9, 0, 0).unwrap();
+
+        let dt = tz.from_local(&naive).unwrap();
+
+        assert_eq!(dt, utc(2025, 7, 1, 7, 0));
+        assert_eq!(tz.to_local(&dt), naive);
+    }
+
+    #[test]
+    fn from_local_rejects_nonexistent_time() {
+        let tz = DisplayTimezone::Named(chrono_tz::Europe::Berlin);
+        let naive = NaiveDate::from_ymd_opt(2025, 3, 30).unwrap().and_hms_opt(2, 30, 0).unwrap();
+
+        assert!(tz.from_local(&naive).is_none());
+    }
+}
@@ -0,0 +1,142 @@
+use chrono::{NaiveDate, NaiveDateTime};
+
+use super::{DisplayTimezone, Event};
+
+/// An event that starts too soon after the previous one somewhere else.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TravelWarning {
+    pub from_id: String,
+    pub to_id: String,
+    pub to_title: String,
+    pub from_location: String,
+    pub to_location: String,
+    /// When the first event ends, in the display timezone.
+    pub from_end: NaiveDateTime,
+    pub to_start: NaiveDateTime,
+    pub gap_minutes: i64,
+}
+
+impl TravelWarning {
+    /// "Mon Jan 13 10:00 Office → 10:10 Café Nero: Lunch (10m to get there)"
+    pub fn text_line(&self) -> String {
+        format!(
+            "{} {} → {} {}: {} ({}m to get there)",
+            self.from_end.format("%a %b %d %H:%M"),
+            self.from_location,
+            self.to_start.format("%H:%M"),
+            self.to_location,
+            self.to_title,
+            self.gap_minutes,
+        )
+    }
+}
+
+/// The `:conflicts` list for a date range.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TravelReport {
+    pub start: NaiveDate,
+    pub end: NaiveDate,
+    pub buffer_minutes: i64,
+    pub warnings: Vec<TravelWarning>,
+}
+
+impl TravelReport {
+    /// A summary line, then one line per warning.
+    pub fn text_lines(&self) -> Vec<String> {
+        let count = self.warnings.len();
+        let mut lines = vec![format!(
+            "{} to {}: {} tight hop{} (buffer {}m)",
+            self.start, self.end, count, if count == 1 { "" } else { "s" }, self.buffer_minutes,
+        )];
+        lines.extend(self.warnings.iter().map(|warning| format!("  {}", warning.text_line())));
+        lines
+    }
+}
+
+/// Pairs each timed event with the next one that starts after it ends and
+/// flags those at another location with less than `buffer_minutes` in
+/// between. Events without a physical location (calls, say) are ignored, as
+/// are cancelled, free and declined ones.
+pub fn travel_warnings(events: &[&Event], buffer_minutes: i64, tz: &DisplayTimezone) -> Vec<TravelWarning> {
+    let mut placed: Vec<(&Event, &str)> = events.iter()
+        .filter(|e| !e.all_day && e.is_busy() && !e.is_declined())
+        .filter_map(|e| Some((*e, e.physical_location()?)))
+        .collect();
+    placed.sort_by_key(|(e, _)| e.start);
+
+    let mut warnings = Vec::new();
+    for (from, from_location) in &placed {
+        let Some((to, to_location)) = placed.iter().find(|(to, _)| to.id != from.id && to.start >= from.end) else {
+            continue;
+        };
+        let gap_minutes = (to.start - from.end).num_minutes();
+        if gap_minutes >= buffer_minutes || same_place(from_location, to_location) {
+            continue;
+        }
+        warnings.push(TravelWarning {
+            from_id: from.id.clone(),
+            to_id: to.id.clone(),
+            to_title: to.title.clone(),
+            from_location: from_location.to_string(),
+            to_location: to_location.to_string(),
+            from_end: from.local_end(tz),
+            to_start: to.local_start(tz),
+            gap_minutes,
+        });
+    }
+    warnings.sort_by_key(|w| w.to_start);
+    warnings
+}
+
+fn same_place(a: &str, b: &str) -> bool {
+    a.trim().eq_ignore_ascii_case(b.trim())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::EventBuilder;
+    use chrono::{Duration, TimeZone, Utc};
+    use crate::calendar::ResponseStatus;
+
+    fn event(id: &str, location: Option<&str>, hour: u32, minute: u32, minutes: i64) -> Event {
+        let start = Utc.with_ymd_and_hms(2025, 1, 13, hour, minute, 0).unwrap();
+        EventBuilder::new(id).title(id).location(location).at(start, Duration::minutes(minutes)).build()
+    }
+
+    fn warnings(events: &[Event], buffer_minutes: i64) -> Vec<TravelWarning> {
+        let events: Vec<&Event> = events.iter().collect();
+        travel_warnings(&events, buffer_minutes, &DisplayTimezone::Named(chrono_tz::UTC))
+    }
+
+    #[test]
+    fn flags_tight_hops_between_places() {
+        let events = [
+            event("standup", Some("Office"), 9, 0, 60),
+            event("call", Some("https://zoom.us/j/1"), 10, 0, 15),
+            event("lunch", Some("Café Nero"), 10, 10, 50),
+            event("review", Some("café nero "), 11, 0, 30),
+            event("dentist", Some("Main St Clinic"), 12, 0, 30),
+        ];
+
+        let found = warnings(&events, 30);
+
+        assert_eq!(found.len(), 1);
+        assert_eq!((found[0].from_id.as_str(), found[0].to_id.as_str(), found[0].gap_minutes), ("standup", "lunch", 10));
+        assert_eq!(found[0].text_line(), "Mon Jan 13 10:00 Office → 10:10 Café Nero: lunch (10m to get there)");
+        assert_eq!(warnings(&events, 31).len(), 2);
+    }
+
+    #[test]
+    fn declined_and_overlapping_events_are_not_hops() {
+        let mut declined = event("declined", Some("Airport"), 10, 0, 60);
+        declined.self_response = Some(ResponseStatus::Declined);
+        let events = [
+            event("workshop", Some("Office"), 9, 0, 120),
+            event("overlap", Some("Lab"), 10, 0, 30),
+            declined,
+        ];
+
+        assert!(warnings(&events, 30).is_empty());
+    }
+}
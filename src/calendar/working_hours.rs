@@ -0,0 +1,114 @@
+use std::sync::OnceLock;
+use chrono::NaiveTime;
+use regex::Regex;
+
+/// Daily working window such as `9-17` or `08:30-17:30`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct WorkingHours {
+    pub start: NaiveTime,
+    pub end: NaiveTime,
+}
+
+static HOURS_RE: OnceLock<Regex> = OnceLock::new();
+
+fn hours_pattern() -> &'static Regex {
+    HOURS_RE.get_or_init(|| {
+        Regex::new(r"^(\d{1,2})(?::(\d{2}))?-(\d{1,2})(?::(\d{2}))?$").expect("invalid hours regex")
+    })
+}
+
+impl WorkingHours {
+    pub fn parse(text: &str) -> Option<Self> {
+        let caps = hours_pattern().captures(text.trim())?;
+        let time = |hour: usize, minute: usize| -> Option<NaiveTime> {
+            let h = caps[hour].parse().ok()?;
+            let m = caps.get(minute).map_or(Some(0), |m| m.as_str().parse().ok())?;
+            if h == 24 && m == 0 {
+                return NaiveTime::from_hms_opt(23, 59, 59);
+            }
+            NaiveTime::from_hms_opt(h, m, 0)
+        };
+
+        let (start, end) = (time(1, 2)?, time(3, 4)?);
+        (start < end).then_some(Self { start, end })
+    }
+
+    /// Whether `text` is shaped like an hours range, valid or not.
+    pub fn is_range_syntax(text: &str) -> bool {
+        hours_pattern().is_match(text.trim())
+    }
+
+    pub fn from_config(text: Option<&str>) -> Self {
+        match text {
+            Some(text) => Self::parse(text).unwrap_or_else(|| {
+                tracing::warn!("Invalid working hours '{}', falling back to 9-17", text);
+                Self::default()
+            }),
+            None => Self::default(),
+        }
+    }
+
+    pub fn contains(&self, time: NaiveTime) -> bool {
+        time >= self.start && time < self.end
+    }
+
+    /// Whether any part of the hour starting at `hour` is working time.
+    pub fn overlaps_hour(&self, hour: u32) -> bool {
+        let Some(start) = NaiveTime::from_hms_opt(hour, 0, 0) else {
+            return false;
+        };
+        let before_end = hour == 23 || NaiveTime::from_hms_opt(hour + 1, 0, 0).is_some_and(|end| self.start < end);
+        before_end && start < self.end
+    }
+}
+
+impl Default for WorkingHours {
+    fn default() -> Self {
+        Self {
+            start: NaiveTime::from_hms_opt(9, 0, 0).unwrap_or(NaiveTime::MIN),
+            end: NaiveTime::from_hms_opt(17, 0, 0).unwrap_or(NaiveTime::MIN),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn time(hour: u32, minute: u32) -> NaiveTime {
+        NaiveTime::from_hms_opt(hour, minute, 0).unwrap()
+    }
+
+    #[test]
+    fn parses_hours_and_minutes() {
+        assert_eq!(WorkingHours::parse("9-17"), Some(WorkingHours { start: time(9, 0), end: time(17, 0) }));
+        assert_eq!(WorkingHours::parse("08:30-17:45"), Some(WorkingHours { start: time(8, 30), end: time(17, 45) }));
+        assert_eq!(WorkingHours::parse("17-9"), None);
+        assert_eq!(WorkingHours::parse("nine-five"), None);
+    }
+
+    #[test]
+    fn invalid_config_falls_back_to_default() {
+        assert_eq!(WorkingHours::from_config(Some("25-30")), WorkingHours::default());
+        assert_eq!(WorkingHours::from_config(None), WorkingHours::default());
+    }
+
+    #[test]
+    fn contains_excludes_end() {
+        let hours = WorkingHours::default();
+
+        assert!(hours.contains(time(9, 0)));
+        assert!(hours.contains(time(16, 59)));
+        assert!(!hours.contains(time(17, 0)));
+    }
+
+    #[test]
+    fn overlaps_partial_hours() {
+        let hours = WorkingHours::parse("8:30-17:30").unwrap();
+
+        assert!(!hours.overlaps_hour(7));
+        assert!(hours.overlaps_hour(8));
+        assert!(hours.overlaps_hour(17));
+        assert!(!hours.overlaps_hour(18));
+    }
+}
@@ -1,68 +1,300 @@
 use std::{
     env,
-    io::{self, Write},
+    io::{self, IsTerminal, Write},
+    path::PathBuf,
     process::{Command, Stdio},
+    time::Duration,
 };
 
-use chrono::{Local, NaiveDate};
+use chrono::{DateTime, Datelike, NaiveDate, Utc};
+use clap::{error::ErrorKind, Args, CommandFactory, Parser, Subcommand};
+use clap_complete::Shell;
 
 use gcal_imp::{
-    calendar::Event as CalendarEvent,
-    storage::config::Config,
+    agenda::{build_agenda_line, find_by_agenda_line, format_agenda, AgendaFormat},
+    report::{build_report, ReportFormat, TitlePattern},
+    app::{AppState, ViewType},
+    error::AppError,
+    calendar::{date_expr, ics, quick_add::parse_quick_add, reminders::due_reminders, DisplayTimezone},
+    input::key_script::{parse_key_script, KeyScript},
+    storage::{cache::Cache, config::Config, crypto::KeySource},
+    ui::view_export::ViewExportFormat,
     sync::sync_engine::SyncEngine,
+    util::{notify::send_notification, opener::open_url},
 };
 
-#[derive(Clone, Copy)]
+use crate::tui::view_export::{export_view, write_view};
+
+const NOTES: &str = "Dates: today, tomorrow, +3d, -1w, friday, next-friday, 'dec 25', 2025-01-15
+Ranges: today..+7d, this-week, next-week, this-month, next-month
+Launchers: gcal-imp dmenu | rofi -dmenu | gcal-imp dmenu opens the chosen event
+Scripts: keys in vim notation, e.g. jj:goto friday<CR>; '@date 2025-01-15' sets the start";
+/// Flags from before the subcommands, rewritten to the subcommand they meant.
+const LEGACY_FLAGS: &[(&str, &str)] = &[
+    ("--agenda", "agenda"),
+    ("--report", "report"),
+    ("--export-view", "export-view"),
+    ("--dmenu", "dmenu"),
+    ("--notify-daemon", "notify-daemon"),
+];
+/// `gcal-imp sync` finished, but an overlay failed or edits need resolving.
+pub const EXIT_SYNC_INCOMPLETE: i32 = 3;
+const DAEMON_TICK: Duration = Duration::from_secs(30);
+
+#[derive(Debug, Parser)]
+#[command(name = "gcal-imp", version, about = "A vim-style terminal client for Google Calendar", after_help = NOTES)]
+#[command(args_conflicts_with_subcommands = true)]
+struct Cli {
+    #[command(subcommand)]
+    command: Option<CliCommand>,
+    #[command(flatten)]
+    tui: TuiArgs,
+}
+
+#[derive(Debug, Subcommand)]
+enum CliCommand {
+    /// Open the calendar (the default)
+    Tui(TuiArgs),
+    /// Print events for a date or range
+    Agenda(AgendaArgs),
+    /// Create an event from a sentence, e.g. "Lunch with Sam tomorrow 12:30 for 45m at Cafe"
+    Add {
+        #[arg(required = true, num_args = 1.., value_name = "TEXT")]
+        text: Vec<String>,
+    },
+    /// Write events in a range as an iCalendar file
+    Export {
+        /// Date or range; defaults to this month
+        #[arg(allow_hyphen_values = true)]
+        range: Option<String>,
+        /// File to write instead of stdout
+        #[arg(short, long, value_name = "FILE")]
+        output: Option<PathBuf>,
+    },
+    /// Draw the month, week or day view into an HTML or text file for printing
+    ExportView {
+        #[arg(long, default_value = "week", value_parser = |name: &str| ViewType::from_name(name).filter(|view| *view != ViewType::Split).ok_or("use month, week, day or year"))]
+        view: ViewType,
+        /// Date to show; defaults to today
+        #[arg(long, value_name = "DATE", allow_hyphen_values = true)]
+        date: Option<String>,
+        #[arg(long, default_value = "html", value_parser = |name: &str| ViewExportFormat::from_name(name).ok_or("use html or text"))]
+        format: ViewExportFormat,
+        /// File to write instead of stdout
+        #[arg(short, long, value_name = "FILE")]
+        output: Option<PathBuf>,
+    },
+    /// Fetch the sync window into the local cache and push kept edits, without the TUI
+    ///
+    /// Exits 0 on success, 75 to retry later, 77 when a sign-in is needed and 3 when
+    /// an overlay calendar failed or edits conflict
+    Sync {
+        /// Print nothing on success
+        #[arg(short, long)]
+        quiet: bool,
+    },
+    /// Hours per calendar and per [report.patterns] title pattern
+    Report {
+        /// Date or range; defaults to this week
+        #[arg(allow_hyphen_values = true)]
+        range: Option<String>,
+        #[arg(long, default_value = "text", value_parser = |name: &str| ReportFormat::from_name(name).ok_or("use text or csv"))]
+        format: ReportFormat,
+    },
+    /// List today's events for rofi/wofi, or open the one whose line arrives on stdin
+    Dmenu,
+    /// Send desktop notifications for reminders
+    NotifyDaemon,
+    /// Print a completion script for bash, zsh, fish, elvish or powershell
+    Completions {
+        shell: Shell,
+    },
+}
+
+#[derive(Debug, Clone, Default, Args)]
+struct TuiArgs {
+    /// Show sample events instead of signing in
+    #[arg(long)]
+    sample: bool,
+    /// Refuse every change to the calendar
+    #[arg(long)]
+    read_only: bool,
+    /// Use a fake in-memory calendar
+    #[arg(long)]
+    mock: bool,
+    /// Replay keys from a script file, against the mock calendar
+    #[arg(long, value_name = "FILE")]
+    script: Option<PathBuf>,
+    /// Replay the script against the configured calendar instead
+    #[arg(long, requires = "script")]
+    live: bool,
+}
+
+#[derive(Debug, Args)]
+struct AgendaArgs {
+    /// Date or range; defaults to today
+    #[arg(allow_hyphen_values = true)]
+    range: Option<String>,
+    #[arg(long, value_name = "DATE", allow_hyphen_values = true)]
+    from: Option<String>,
+    #[arg(long, value_name = "DATE", conflicts_with = "days", allow_hyphen_values = true)]
+    to: Option<String>,
+    /// Number of days from the start
+    #[arg(long, value_parser = clap::value_parser!(u32).range(1..))]
+    days: Option<u32>,
+    /// text, plain, markdown, json, ics or tsv (fixed tab-separated columns)
+    #[arg(long, default_value = "text", value_parser = |name: &str| AgendaFormat::from_name(name).ok_or("use text, plain, markdown, json, ics or tsv"))]
+    format: AgendaFormat,
+    /// Print even to a terminal instead of opening $PAGER; pipes never get the pager
+    #[arg(long)]
+    no_pager: bool,
+}
+
+impl AgendaArgs {
+    fn resolve(&self, today: NaiveDate) -> Result<(NaiveDate, NaiveDate), String> {
+        let range = self.range.as_deref().map(|expr| parse_range(expr, today)).transpose()?;
+        let (mut start, mut end) = range.unwrap_or((today, today));
+        if let Some(from) = &self.from {
+            start = parse_date(from, today)?;
+            if range.is_none() && self.to.is_none() {
+                end = start;
+            }
+        }
+        if let Some(to) = &self.to {
+            end = parse_date(to, today)?;
+        }
+        if let Some(days) = self.days {
+            end = start.checked_add_days(chrono::Days::new(days as u64 - 1))
+                .ok_or_else(|| format!("--days {} is out of range", days))?;
+        }
+        if end < start {
+            return Err(format!("Agenda end {} is before start {}", end, start));
+        }
+        Ok((start, end))
+    }
+}
+
+#[derive(Clone)]
 pub enum CliMode {
-    Default { sample: bool },
-    AgendaDate(NaiveDate),
+    Default { sample: bool, read_only: bool, mock: bool, script: Option<KeyScript> },
+    Agenda { start: NaiveDate, end: NaiveDate, format: AgendaFormat, pager: bool },
+    Export { start: NaiveDate, end: NaiveDate, output: Option<PathBuf> },
+    Report { start: NaiveDate, end: NaiveDate, format: ReportFormat },
+    ExportView { view: ViewType, date: NaiveDate, format: ViewExportFormat, output: Option<PathBuf> },
+    Sync { quiet: bool },
+    /// List today's events, or open the one whose line arrives on stdin.
+    Dmenu,
+    NotifyDaemon,
+    QuickAdd(String),
+    Completions(Shell),
+}
+
+/// Reads the timezone without creating config.toml, so `--help` and
+/// `completions` leave the disk alone.
+fn today() -> NaiveDate {
+    let timezone = std::fs::read_to_string(Config::config_path()).ok()
+        .and_then(|text| Config::from_toml(&text).ok())
+        .map(|config| DisplayTimezone::from_config(config.ui.timezone.as_deref()))
+        .unwrap_or_default();
+    timezone.date_of(&Utc::now())
+}
+
+fn parse_date(expr: &str, today: NaiveDate) -> Result<NaiveDate, String> {
+    date_expr::parse_date(expr, today).map_err(|e| e.to_string())
+}
+
+fn parse_range(expr: &str, today: NaiveDate) -> Result<(NaiveDate, NaiveDate), String> {
+    date_expr::parse_range(expr, today).map_err(|e| e.to_string())
 }
 
-pub fn parse_cli_mode() -> Result<CliMode, String> {
-    let mut sample = false;
-    let mut agenda_date = None;
-    let mut args = env::args().skip(1).peekable();
+/// Moves `--agenda [RANGE]` and friends to the front as subcommands, and
+/// treats a bare `--from`/`--to` as an agenda, so old scripts keep working.
+fn rewrite_legacy_args(mut args: Vec<String>) -> Vec<String> {
+    let flag_index = args.iter().skip(1)
+        .position(|arg| LEGACY_FLAGS.iter().any(|(flag, _)| arg == flag))
+        .map(|i| i + 1);
+    if let Some(index) = flag_index {
+        let flag = args.remove(index);
+        let command = LEGACY_FLAGS.iter().find(|(f, _)| *f == flag).map_or("agenda", |(_, command)| *command);
+        // Relative dates such as -1w are values; only another flag is not.
+        let value = (index < args.len() && !args[index].starts_with("--")).then(|| args.remove(index));
+        args.splice(1..1, std::iter::once(command.to_string()).chain(value));
+    } else if args.get(1).is_some_and(|arg| arg.starts_with('-'))
+        && args.iter().skip(1).any(|arg| arg == "--from" || arg == "--to")
+    {
+        args.insert(1, "agenda".to_string());
+    }
+    args
+}
+
+/// Parses the command line, exiting with clap's usage message when it is wrong.
+pub fn parse_cli_mode() -> CliMode {
+    let cli = Cli::parse_from(rewrite_legacy_args(env::args().collect()));
+    cli.into_mode(today()).unwrap_or_else(|message| {
+        Cli::command().error(ErrorKind::ValueValidation, message).exit()
+    })
+}
 
-    while let Some(arg) = args.next() {
-        match arg.as_str() {
-            "--sample" => {
-                sample = true;
+impl Cli {
+    fn into_mode(self, today: NaiveDate) -> Result<CliMode, String> {
+        let mode = match self.command {
+            None => tui_mode(self.tui)?,
+            Some(CliCommand::Tui(args)) => tui_mode(args)?,
+            Some(CliCommand::Agenda(args)) => {
+                let (start, end) = args.resolve(today)?;
+                CliMode::Agenda { start, end, format: args.format, pager: !args.no_pager }
             }
-            "--agenda" => {
-                let target_date = if let Some(next) = args.peek() {
-                    if !next.starts_with("--") {
-                        let date_str = args.next().expect("peeked value must exist");
-                        NaiveDate::parse_from_str(&date_str, "%Y/%m/%d")
-                            .map_err(|_| format!("Invalid date '{}'. Use YYYY/MM/DD.", date_str))?
-                    } else {
-                        Local::now().date_naive()
-                    }
-                } else {
-                    Local::now().date_naive()
-                };
-                agenda_date = Some(target_date);
+            Some(CliCommand::Add { text }) => CliMode::QuickAdd(text.join(" ")),
+            Some(CliCommand::Export { range, output }) => {
+                let (start, end) = parse_range(range.as_deref().unwrap_or("month"), today)?;
+                CliMode::Export { start, end, output }
             }
-            "--help" => {
-                println!("Usage: gcal-imp [--agenda [YYYY/MM/DD]] [--sample]");
-                std::process::exit(0);
+            Some(CliCommand::ExportView { view, date, format, output }) => {
+                let date = date.as_deref().map(|expr| parse_date(expr, today)).transpose()?.unwrap_or(today);
+                CliMode::ExportView { view, date, format, output }
             }
-            _ => return Err(format!("Unknown argument: {}", arg)),
-        }
+            Some(CliCommand::Report { range, format }) => {
+                let (start, end) = parse_range(range.as_deref().unwrap_or("week"), today)?;
+                CliMode::Report { start, end, format }
+            }
+            Some(CliCommand::Sync { quiet }) => CliMode::Sync { quiet },
+            Some(CliCommand::Dmenu) => CliMode::Dmenu,
+            Some(CliCommand::NotifyDaemon) => CliMode::NotifyDaemon,
+            Some(CliCommand::Completions { shell }) => CliMode::Completions(shell),
+        };
+        Ok(mode)
     }
+}
 
-    if let Some(date) = agenda_date {
-        Ok(CliMode::AgendaDate(date))
-    } else {
-        Ok(CliMode::Default { sample })
-    }
+fn tui_mode(args: TuiArgs) -> Result<CliMode, String> {
+    let script = match &args.script {
+        Some(path) => {
+            let text = std::fs::read_to_string(path)
+                .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+            Some(parse_key_script(&text).map_err(|e| format!("{}: {}", path.display(), e))?)
+        }
+        None => None,
+    };
+    // Scripts drive real edits, so they only reach the calendar when asked.
+    let mock = args.mock || (script.is_some() && !args.live);
+    Ok(CliMode::Default { sample: args.sample, read_only: args.read_only, mock, script })
 }
 
-pub async fn run_agenda_mode(date: NaiveDate) -> Result<(), io::Error> {
-    let config = Config::load_or_create()
-        .map_err(|e| io::Error::other(e.to_string()))?;
+/// Generated into memory first: clap_complete panics when a write fails,
+/// e.g. on a closed pipe.
+pub fn print_completions(shell: Shell) -> Result<(), AppError> {
+    let mut script = Vec::new();
+    clap_complete::generate(shell, &mut Cli::command(), "gcal-imp", &mut script);
+    io::stdout().write_all(&script)?;
+    Ok(())
+}
+
+pub async fn run_agenda_mode(start: NaiveDate, end: NaiveDate, format: AgendaFormat, pager: bool) -> Result<(), AppError> {
+    let config = Config::load_or_create()?;
+    let timezone = DisplayTimezone::from_config(config.ui.timezone.as_deref());
     let mut sync_engine = SyncEngine::new(config);
 
-    let mut events = match sync_engine.fetch_events(date, date).await {
+    let mut events = match sync_engine.fetch_events(start, end).await {
         Ok(list) => list,
         Err(e) => {
             eprintln!("Failed to fetch events: {}", e);
@@ -71,55 +303,233 @@ pub async fn run_agenda_mode(date: NaiveDate) -> Result<(), io::Error> {
     };
 
     events.sort_by_key(|event| event.start);
-    let agenda = format_agenda_text(date, &events);
-    display_with_pager(&agenda)
+    let agenda = format_agenda(format, start, end, &events, &timezone);
+    // A pipe gets the bare text so it composes with grep and awk.
+    if format.uses_pager() && pager && io::stdout().is_terminal() {
+        display_with_pager(&agenda)?;
+    } else {
+        io::stdout().write_all(agenda.as_bytes())?;
+    }
+    Ok(())
 }
 
-fn format_agenda_text(date: NaiveDate, events: &[CalendarEvent]) -> String {
-    let mut lines = Vec::new();
-    lines.push(format!("Agenda – {}", date.format("%A, %B %d, %Y")));
-    lines.push(String::new());
+pub async fn run_dmenu_mode() -> Result<(), AppError> {
+    let config = Config::load_or_create()?;
+    let timezone = DisplayTimezone::from_config(config.ui.timezone.as_deref());
+    let today = timezone.date_of(&Utc::now());
+    let mut sync_engine = SyncEngine::new(config);
 
-    if events.is_empty() {
-        lines.push("No events scheduled.".to_string());
-    } else {
-        for event in events {
-            lines.push(format!("- {}", build_agenda_line(event, usize::MAX)));
+    // An empty menu would look like a free day, so a failed fetch is an error.
+    let mut events = sync_engine.fetch_events(today, today).await?;
+    events.sort_by_key(|event| event.start);
+
+    // Launched from a keybinding stdin may be empty rather than a terminal.
+    let mut selection = String::new();
+    if !io::stdin().is_terminal() {
+        io::stdin().read_line(&mut selection)?;
+    }
+    if selection.trim().is_empty() {
+        let mut stdout = io::stdout();
+        for event in &events {
+            writeln!(stdout, "{}", build_agenda_line(event, &timezone, usize::MAX))?;
+        }
+        return Ok(());
+    }
+
+    match find_by_agenda_line(&events, &selection, &timezone) {
+        Some(event) => match &event.html_link {
+            Some(link) => open_url(link)?,
+            None => eprintln!("\"{}\" has no link to open", event.title),
+        },
+        None => eprintln!("No event today matches \"{}\"", selection.trim()),
+    }
+    Ok(())
+}
+
+pub async fn run_export_mode(start: NaiveDate, end: NaiveDate, output: Option<PathBuf>) -> Result<(), AppError> {
+    let config = Config::load_or_create()?;
+    let mut sync_engine = SyncEngine::new(config);
+
+    let mut events = sync_engine.fetch_events(start, end).await?;
+    events.sort_by_key(|event| event.start);
+    let calendar = ics::to_ics(&events);
+    match output {
+        Some(path) => {
+            std::fs::write(&path, calendar)?;
+            eprintln!("Exported {} events to {}", events.len(), path.display());
+        }
+        None => io::stdout().write_all(calendar.as_bytes())?,
+    }
+    Ok(())
+}
+
+pub async fn run_export_view_mode(
+    view: ViewType,
+    date: NaiveDate,
+    format: ViewExportFormat,
+    output: Option<PathBuf>,
+) -> Result<(), AppError> {
+    let config = Config::load_or_create()?;
+    let mut app = AppState::new()
+        .with_ui_config(&config.ui)
+        .with_default_calendar(config.calendars.default.clone())
+        .with_visible_calendars(&config.calendars.visible);
+    let (start, end) = export_range(&view, date);
+    let mut sync_engine = SyncEngine::new(config);
+
+    for event in sync_engine.fetch_events(start, end).await? {
+        app.add_event(event);
+    }
+    app.view = view;
+    app.selected_date = date;
+
+    match output {
+        Some(path) => {
+            let path = write_view(&app, format, Some(path))?;
+            eprintln!("Wrote the {} view to {}", app.view.name().to_lowercase(), path.display());
+        }
+        None => io::stdout().write_all(export_view(&app, format)?.as_bytes())?,
+    }
+    Ok(())
+}
+
+/// Dates an exported view needs events for.
+fn export_range(view: &ViewType, date: NaiveDate) -> (NaiveDate, NaiveDate) {
+    if *view == ViewType::Year {
+        let start = date.with_ordinal(1).unwrap_or(date);
+        let end = NaiveDate::from_ymd_opt(date.year(), 12, 31).unwrap_or(date);
+        return (start, end);
+    }
+    // Six weeks either side covers the month grid's leading and trailing days.
+    let start = date.checked_sub_days(chrono::Days::new(42)).unwrap_or(date);
+    let end = date.checked_add_days(chrono::Days::new(42)).unwrap_or(date);
+    (start, end)
+}
+
+/// Returns the exit code: a sync that left work for the user is not a failure
+/// but should not look like a clean run either.
+pub async fn run_sync_mode(quiet: bool) -> Result<i32, AppError> {
+    let config = Config::load_or_create()?;
+    let timezone = DisplayTimezone::from_config(config.ui.timezone.as_deref());
+    let cache = Cache::open(&config.cache_path())?.with_keys(KeySource::from_config(&config.security));
+    cache.seal_plain_rows()?;
+    let mut sync_engine = SyncEngine::new(config).with_cache(cache);
+
+    let summary = sync_engine.sync_to_cache(timezone.date_of(&Utc::now()), Utc::now()).await?;
+    if !quiet {
+        println!(
+            "Synced {} events from {} calendars ({} to {})",
+            summary.events, summary.calendars, summary.start_date, summary.end_date
+        );
+        if summary.pushed > 0 {
+            println!("Pushed {} local edits", summary.pushed);
         }
     }
+    for calendar_id in &summary.failed_calendars {
+        eprintln!("Failed to fetch overlay calendar {}", calendar_id);
+    }
+    if summary.conflicts > 0 {
+        eprintln!("{} local edits changed on the server too; open gcal-imp to resolve them", summary.conflicts);
+    }
+
+    let incomplete = !summary.failed_calendars.is_empty() || summary.conflicts > 0;
+    Ok(if incomplete { EXIT_SYNC_INCOMPLETE } else { 0 })
+}
+
+pub async fn run_report_mode(start: NaiveDate, end: NaiveDate, format: ReportFormat) -> Result<(), AppError> {
+    let config = Config::load_or_create()?;
+    let timezone = DisplayTimezone::from_config(config.ui.timezone.as_deref());
+    let patterns = TitlePattern::from_config(&config.report.patterns);
+    let mut sync_engine = SyncEngine::new(config);
 
-    lines.join("\n")
+    let events = sync_engine.fetch_events(start, end).await?;
+    let events: Vec<_> = events.iter().collect();
+    let report = build_report(&events, start, end, &patterns, &timezone);
+    io::stdout().write_all(report.format(format).as_bytes())?;
+    Ok(())
 }
 
-fn build_agenda_line(event: &CalendarEvent, width: usize) -> String {
-    let start_local = event.start.with_timezone(&Local);
-    let end_local = event.end.with_timezone(&Local);
-    let time_label = if event.all_day {
-        "All Day".to_string()
+pub async fn run_quick_add(text: &str) -> Result<(), AppError> {
+    let config = Config::load_or_create()?;
+    let timezone = DisplayTimezone::from_config(config.ui.timezone.as_deref());
+    let calendar_id = config.calendars.default.clone();
+
+    let now = Utc::now();
+    let event = parse_quick_add(text, timezone.date_of(&now))
+        .and_then(|qa| qa.to_event(uuid::Uuid::new_v4().to_string(), &calendar_id, &timezone, now))?;
+
+    let mut sync_engine = SyncEngine::new(config);
+    sync_engine.create_event(&calendar_id, &event).await?;
+
+    let when = if event.all_day {
+        event.local_start(&timezone).format("%a %b %d (all day)").to_string()
     } else {
         format!(
             "{}-{}",
-            start_local.format("%H:%M"),
-            end_local.format("%H:%M")
+            event.local_start(&timezone).format("%a %b %d %H:%M"),
+            event.local_end(&timezone).format("%H:%M")
         )
     };
-
-    let mut line = format!("{:<13} {}", time_label, event.title);
-    if let Some(location) = &event.location
-        && !location.is_empty()
-    {
-        line.push_str(&format!(" @ {}", location));
+    println!("Created \"{}\" {}", event.title, when);
+    if let Some(location) = &event.location {
+        println!("  @ {}", location);
     }
-    truncate_to_width(&line, width)
+    Ok(())
 }
 
-fn truncate_to_width(line: &str, width: usize) -> String {
-    if width > 0 && line.len() > width {
-        let mut truncated = line.chars().take(width.saturating_sub(1)).collect::<String>();
-        truncated.push('…');
-        truncated
-    } else {
-        line.to_string()
+pub async fn run_notify_daemon() -> Result<(), AppError> {
+    let config = Config::load_or_create()?;
+    let timezone = DisplayTimezone::from_config(config.ui.timezone.as_deref());
+    let sync_interval = chrono::Duration::minutes(config.sync.auto_sync_interval_minutes.max(1) as i64);
+    let mut sync_engine = SyncEngine::new(config);
+
+    let mut events = Vec::new();
+    let mut last_sync: Option<DateTime<Utc>> = None;
+    let mut last_check = Utc::now();
+    let mut ticker = tokio::time::interval(DAEMON_TICK);
+
+    tracing::info!("Notify daemon started");
+    println!("gcal-imp notify daemon running (Ctrl-C to stop)");
+
+    loop {
+        tokio::select! {
+            _ = ticker.tick() => {}
+            _ = tokio::signal::ctrl_c() => {
+                tracing::info!("Notify daemon stopped");
+                return Ok(());
+            }
+        }
+
+        let now = Utc::now();
+        if last_sync.is_none_or(|synced| now - synced >= sync_interval) {
+            let today = timezone.date_of(&now);
+            let tomorrow = today.succ_opt().unwrap_or(today);
+            match sync_engine.fetch_events(today, tomorrow).await {
+                Ok(list) => {
+                    tracing::info!("Notify daemon synced {} events", list.len());
+                    events = list;
+                }
+                Err(e) => {
+                    tracing::error!("Notify daemon sync failed: {}", e);
+                    eprintln!("Sync failed: {}", e);
+                }
+            }
+            last_sync = Some(now);
+        }
+
+        for reminder in due_reminders(&events, last_check, now) {
+            let body = format!(
+                "Starts at {} (in {} min)",
+                timezone.to_local(&reminder.start).format("%H:%M"),
+                reminder.minutes_before
+            );
+            tracing::info!("Reminder for {}: {}", reminder.title, body);
+            if let Err(e) = send_notification(&reminder.title, &body) {
+                tracing::error!("Failed to send notification: {}", e);
+                eprintln!("{}: {}", reminder.title, body);
+            }
+        }
+        last_check = now;
     }
 }
 
@@ -153,3 +563,98 @@ fn display_with_pager(text: &str) -> Result<(), io::Error> {
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(line: &str) -> Vec<String> {
+        line.split_whitespace().map(String::from).collect()
+    }
+
+    fn mode(line: &str) -> Result<CliMode, String> {
+        let today = NaiveDate::from_ymd_opt(2025, 1, 15).unwrap();
+        Cli::try_parse_from(rewrite_legacy_args(args(line)))
+            .map_err(|e| e.to_string())?
+            .into_mode(today)
+    }
+
+    fn date(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2025, 1, day).unwrap()
+    }
+
+    #[test]
+    fn export_view_takes_a_view_date_and_format() {
+        let Ok(CliMode::ExportView { view, date, format, output }) = mode("gcal-imp export-view --view month --date friday --format text") else {
+            panic!("expected a view export");
+        };
+        assert_eq!((view, format, output), (ViewType::Month, ViewExportFormat::Text, None));
+        assert_eq!(date, NaiveDate::from_ymd_opt(2025, 1, 17).unwrap());
+        assert!(matches!(mode("gcal-imp --export-view -o week.html"), Ok(CliMode::ExportView { view: ViewType::Week, .. })));
+        assert!(mode("gcal-imp export-view --format pdf").is_err());
+        assert!(mode("gcal-imp export-view --view split").is_err());
+    }
+
+    #[test]
+    fn year_exports_fetch_the_whole_year() {
+        let year = NaiveDate::from_ymd_opt(2025, 12, 31).unwrap();
+        assert_eq!(export_range(&ViewType::Year, date(17)), (date(1), year));
+        assert_eq!(export_range(&ViewType::Week, date(17)), (NaiveDate::from_ymd_opt(2024, 12, 6).unwrap(), NaiveDate::from_ymd_opt(2025, 2, 28).unwrap()));
+    }
+
+    #[test]
+    fn command_definition_is_valid() {
+        Cli::command().debug_assert();
+    }
+
+    #[test]
+    fn agenda_takes_a_range_and_days() {
+        let Ok(CliMode::Agenda { start, end, format, pager }) = mode("gcal-imp agenda friday --days 3 --format tsv --no-pager") else {
+            panic!("expected an agenda");
+        };
+
+        assert_eq!((start, end), (date(17), date(19)));
+        assert_eq!(format, AgendaFormat::Tsv);
+        assert!(!pager);
+        assert!(mode("gcal-imp agenda --days 3 --to friday").is_err());
+        assert!(mode("gcal-imp agenda --format yaml").is_err());
+    }
+
+    #[test]
+    fn legacy_flags_become_subcommands() {
+        assert_eq!(rewrite_legacy_args(args("gcal-imp --format json --agenda this-week")), args("gcal-imp agenda this-week --format json"));
+        assert_eq!(rewrite_legacy_args(args("gcal-imp --from monday")), args("gcal-imp agenda --from monday"));
+        assert_eq!(rewrite_legacy_args(args("gcal-imp --dmenu")), args("gcal-imp dmenu"));
+        assert!(matches!(mode("gcal-imp --report --format csv"), Ok(CliMode::Report { format: ReportFormat::Csv, .. })));
+    }
+
+    #[test]
+    fn relative_dates_may_start_with_a_hyphen() {
+        let last_week = (date(8), date(8));
+        assert!(matches!(mode("gcal-imp agenda -1w"), Ok(CliMode::Agenda { start, end, .. }) if (start, end) == last_week));
+        assert!(matches!(mode("gcal-imp --agenda -1w"), Ok(CliMode::Agenda { start, end, .. }) if (start, end) == last_week));
+        assert!(matches!(mode("gcal-imp agenda --from -1w"), Ok(CliMode::Agenda { start, .. }) if start == date(8)));
+        assert!(matches!(mode("gcal-imp report -1w"), Ok(CliMode::Report { start, .. }) if start == date(8)));
+        assert!(matches!(mode("gcal-imp agenda -1w --format tsv"), Ok(CliMode::Agenda { format: AgendaFormat::Tsv, .. })));
+    }
+
+    #[test]
+    fn scripts_replay_against_the_mock_calendar_unless_live() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("keys.txt");
+        std::fs::write(&path, "jj").unwrap();
+        let line = format!("gcal-imp --script {}", path.display());
+
+        assert!(matches!(mode(&line), Ok(CliMode::Default { mock: true, .. })));
+        assert!(matches!(mode(&format!("{} --live", line)), Ok(CliMode::Default { mock: false, .. })));
+    }
+
+    #[test]
+    fn tui_flags_do_not_mix_with_subcommands() {
+        assert!(matches!(mode("gcal-imp --read-only"), Ok(CliMode::Default { read_only: true, .. })));
+        assert!(mode("gcal-imp --read-only agenda").is_err());
+        assert!(mode("gcal-imp --live").is_err());
+        assert!(matches!(mode("gcal-imp add Lunch tomorrow 12:30"), Ok(CliMode::QuickAdd(text)) if text == "Lunch tomorrow 12:30"));
+        assert!(matches!(mode("gcal-imp completions zsh"), Ok(CliMode::Completions(Shell::Zsh))));
+    }
+}
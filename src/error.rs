@@ -0,0 +1,186 @@
+use std::path::PathBuf;
+
+use thiserror::Error;
+
+use crate::calendar::quick_add::QuickAddError;
+use crate::storage::cache::CacheError;
+use crate::storage::config::{Config, ConfigError};
+use crate::storage::crypto::KEY_ENV_VAR;
+use crate::sync::google_api::ApiError;
+use crate::sync::google_auth::AuthError;
+use crate::sync::sync_engine::SyncError;
+
+/// Any error that reaches the user, with a message and a hint written for
+/// them rather than for the log.
+#[derive(Debug, Error)]
+pub enum AppError {
+    #[error(transparent)]
+    Config(#[from] ConfigError),
+    #[error(transparent)]
+    Auth(#[from] AuthError),
+    #[error(transparent)]
+    Api(#[from] ApiError),
+    #[error(transparent)]
+    Cache(#[from] CacheError),
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    #[error(transparent)]
+    QuickAdd(#[from] QuickAddError),
+    #[error("Read-only mode is on")]
+    ReadOnly,
+    #[error("Calendar {0} is read-only")]
+    CalendarReadOnly(String),
+}
+
+impl From<SyncError> for AppError {
+    fn from(error: SyncError) -> Self {
+        match error {
+            SyncError::AuthError(e) => Self::Auth(e),
+            SyncError::ApiError(e) => Self::Api(e),
+            SyncError::ReadOnly => Self::ReadOnly,
+            SyncError::CalendarReadOnly(calendar_id) => Self::CalendarReadOnly(calendar_id),
+            SyncError::CacheError(e) => Self::Cache(e),
+        }
+    }
+}
+
+impl AppError {
+    pub fn message(&self) -> String {
+        match self {
+            Self::Config(ConfigError::ReadError(_)) => "Could not read the config file".to_string(),
+            Self::Config(ConfigError::ParseError(e)) => format!("The config file is not valid TOML: {}", e.message()),
+            Self::Auth(AuthError::ReadError(_) | AuthError::ParseError(_)) => "No saved sign-in was found".to_string(),
+            Self::Auth(AuthError::TokenExpired | AuthError::NoRefreshToken) => "Your Google sign-in has expired".to_string(),
+            Self::Auth(AuthError::HttpError(_)) | Self::Api(ApiError::HttpError(_)) => "Could not reach Google".to_string(),
+            Self::Api(ApiError::AuthenticationFailed) => "Google rejected the sign-in".to_string(),
+            Self::Api(ApiError::RateLimited) => "Google is limiting requests".to_string(),
+            Self::Api(ApiError::NotFound(what)) => format!("Google could not find {}", what),
+            Self::Api(ApiError::PreconditionFailed(what)) => format!("{} changed on the server", what),
+            Self::Auth(AuthError::CryptoError(_)) | Self::Cache(CacheError::CryptoError(_)) => {
+                "Encrypted data could not be read or written".to_string()
+            }
+            Self::Cache(_) => "The local cache could not be used".to_string(),
+            _ => self.to_string(),
+        }
+    }
+
+    /// The process exit code, following sysexits.h so cron jobs and scripts
+    /// can tell a retry-later failure from one that needs the user.
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            Self::Config(_) => 78,
+            Self::Auth(AuthError::HttpError(_)) | Self::Api(ApiError::HttpError(_) | ApiError::RateLimited) => 75,
+            Self::Auth(_) | Self::Api(ApiError::AuthenticationFailed) => 77,
+            Self::Cache(_) | Self::Io(_) => 74,
+            _ => 1,
+        }
+    }
+
+    /// What the user can do about it, when there is something.
+    pub fn hint(&self) -> Option<String> {
+        let hint = match self {
+            Self::Config(_) => format!("Check {}, or move it aside to start from the defaults.", Config::config_path().display()),
+            Self::Auth(AuthError::HttpError(_)) | Self::Api(ApiError::HttpError(_)) => {
+                "Check your network connection, then sync again with :w.".to_string()
+            }
+            Self::Auth(AuthError::CryptoError(_)) | Self::Cache(CacheError::CryptoError(_)) => {
+                format!("Unlock the OS keyring or set {}, or turn off security.encrypt.", KEY_ENV_VAR)
+            }
+            Self::Auth(_) | Self::Api(ApiError::AuthenticationFailed) => {
+                "Restart gcal-imp to sign in again.".to_string()
+            }
+            Self::Api(ApiError::RateLimited) => {
+                "Wait a minute, or lower sync.max_requests_per_second in the config.".to_string()
+            }
+            Self::Api(ApiError::NotFound(_)) => "Check the calendar ids in the config.".to_string(),
+            Self::Api(ApiError::PreconditionFailed(_)) => "Sync with :w to load the server's version, then edit again.".to_string(),
+            Self::Cache(_) => format!("Delete {} to rebuild it on the next sync.", configured_cache_path().display()),
+            Self::QuickAdd(_) => "Try e.g. gcal-imp add \"Lunch with Sam tomorrow 12:30 for 45m\".".to_string(),
+            Self::CalendarReadOnly(_) => "Ask the calendar's owner for edit access, or pick another calendar with :cal.".to_string(),
+            Self::ReadOnly => "Start without --read-only and set sync.read_only = false to make changes.".to_string(),
+            _ => return None,
+        };
+        Some(hint)
+    }
+}
+
+/// The cache the config points at. A config that no longer parses only costs
+/// the custom path.
+fn configured_cache_path() -> PathBuf {
+    std::fs::read_to_string(Config::config_path())
+        .ok()
+        .and_then(|content| Config::from_toml(&content).ok())
+        .unwrap_or_default()
+        .cache_path()
+}
+
+/// An error as the error dialog shows it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ErrorReport {
+    /// What was being done, e.g. "Failed to save".
+    pub action: String,
+    pub message: String,
+    pub hint: Option<String>,
+    /// The underlying error, for bug reports.
+    pub detail: String,
+}
+
+impl ErrorReport {
+    pub fn new(action: impl Into<String>, error: &AppError) -> Self {
+        Self {
+            action: action.into(),
+            message: error.message(),
+            hint: error.hint(),
+            detail: error.to_string(),
+        }
+    }
+
+    /// One line for the status bar.
+    pub fn summary(&self) -> String {
+        format!("{}: {}", self.action, self.message)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sync_errors_keep_their_cause() {
+        let error = AppError::from(SyncError::ApiError(ApiError::RateLimited));
+
+        assert_eq!(error.message(), "Google is limiting requests");
+        assert!(error.hint().unwrap().contains("max_requests_per_second"));
+        assert!(matches!(AppError::from(SyncError::ReadOnly), AppError::ReadOnly));
+        let error = AppError::from(SyncError::CalendarReadOnly("holidays".to_string()));
+        assert_eq!(error.message(), "Calendar holidays is read-only");
+        assert!(error.hint().unwrap().contains(":cal"));
+    }
+
+    #[test]
+    fn exit_codes_separate_temporary_failures_from_sign_in_problems() {
+        assert_eq!(AppError::from(ApiError::RateLimited).exit_code(), 75);
+        assert_eq!(AppError::from(ApiError::AuthenticationFailed).exit_code(), 77);
+        assert_eq!(AppError::from(AuthError::NoRefreshToken).exit_code(), 77);
+        assert_eq!(AppError::ReadOnly.exit_code(), 1);
+    }
+
+    #[test]
+    fn report_keeps_the_raw_error_as_detail() {
+        let error = AppError::from(ApiError::NotFound("work@example.com".to_string()));
+
+        let report = ErrorReport::new("Sync failed", &error);
+
+        assert_eq!(report.summary(), "Sync failed: Google could not find work@example.com");
+        assert_eq!(report.detail, "Calendar not found: work@example.com");
+        assert_eq!(report.hint.as_deref(), Some("Check the calendar ids in the config."));
+    }
+
+    #[test]
+    fn config_parse_errors_point_at_the_file() {
+        let error = AppError::from(Config::from_toml("not toml").unwrap_err());
+
+        assert!(error.message().starts_with("The config file is not valid TOML"));
+        assert!(error.hint().unwrap().contains("config.toml"));
+    }
+}
@@ -0,0 +1,71 @@
+use crossterm::event::KeyCode;
+
+use crate::app::AppState;
+
+/// Keys while the calendar list is open. Returns true when visibility
+/// changed, so the caller can persist it.
+pub fn handle_key(key: KeyCode, state: &mut AppState) -> bool {
+    let calendars = state.known_calendars();
+    match key {
+        KeyCode::Char('j') | KeyCode::Down if state.calendar_list_index + 1 < calendars.len() => {
+            state.calendar_list_index += 1;
+        }
+        KeyCode::Char('k') | KeyCode::Up => {
+            state.calendar_list_index = state.calendar_list_index.saturating_sub(1);
+        }
+        KeyCode::Char(' ') | KeyCode::Enter => {
+            if let Some(calendar) = calendars.get(state.calendar_list_index) {
+                state.toggle_calendar_visibility(calendar);
+                return true;
+            }
+        }
+        KeyCode::Char('q') | KeyCode::Char('c') | KeyCode::Esc => {
+            state.show_calendar_list = false;
+            state.calendar_list_index = 0;
+        }
+        _ => {}
+    }
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn state_with_calendars() -> AppState {
+        let mut state = AppState::new()
+            .with_calendar_names(vec!["primary".to_string(), "work".to_string()]);
+        state.show_calendar_list = true;
+        state
+    }
+
+    #[test]
+    fn space_toggles_the_highlighted_calendar() {
+        let mut state = state_with_calendars();
+
+        handle_key(KeyCode::Char('j'), &mut state);
+        assert!(handle_key(KeyCode::Char(' '), &mut state));
+
+        assert!(state.is_calendar_visible("primary"));
+        assert!(!state.is_calendar_visible("work"));
+    }
+
+    #[test]
+    fn j_stops_at_the_last_calendar() {
+        let mut state = state_with_calendars();
+
+        handle_key(KeyCode::Char('j'), &mut state);
+        handle_key(KeyCode::Char('j'), &mut state);
+
+        assert_eq!(state.calendar_list_index, 1);
+    }
+
+    #[test]
+    fn esc_closes_without_changes() {
+        let mut state = state_with_calendars();
+
+        assert!(!handle_key(KeyCode::Esc, &mut state));
+        assert!(!state.show_calendar_list);
+        assert!(state.hidden_calendars.is_empty());
+    }
+}
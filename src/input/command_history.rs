@@ -0,0 +1,164 @@
+use std::path::{Path, PathBuf};
+
+use crate::storage::paths;
+
+pub const MAX_HISTORY: usize = 500;
+
+/// Previously entered `:` commands, oldest first, browsed with Up/Down.
+#[derive(Debug, Clone, Default)]
+pub struct CommandHistory {
+    entries: Vec<String>,
+    position: Option<usize>,
+    draft: String,
+}
+
+impl CommandHistory {
+    pub fn new(entries: Vec<String>) -> Self {
+        let mut history = Self::default();
+        for entry in entries {
+            history.push(&entry);
+        }
+        history
+    }
+
+    pub fn default_path() -> PathBuf {
+        paths::config_dir().join("history")
+    }
+
+    /// Reads one command per line; a missing file is an empty history.
+    pub fn load(path: &Path) -> std::io::Result<Self> {
+        match std::fs::read_to_string(path) {
+            Ok(content) => Ok(Self::new(content.lines().map(str::to_string).collect())),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> std::io::Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let mut content = self.entries.join("\n");
+        content.push('\n');
+        std::fs::write(path, content)
+    }
+
+    pub fn entries(&self) -> &[String] {
+        &self.entries
+    }
+
+    /// Records a command, skipping blanks and repeats of the last entry.
+    pub fn push(&mut self, command: &str) {
+        self.reset();
+        let command = command.trim();
+        if command.is_empty() || command == ":" || self.entries.last().is_some_and(|last| last == command) {
+            return;
+        }
+        self.entries.push(command.to_string());
+        if self.entries.len() > MAX_HISTORY {
+            self.entries.remove(0);
+        }
+    }
+
+    /// Steps back in time; `current` is kept so Down can return to it.
+    pub fn older(&mut self, current: &str) -> Option<&str> {
+        let position = match self.position {
+            None if self.entries.is_empty() => return None,
+            None => {
+                self.draft = current.to_string();
+                self.entries.len() - 1
+            }
+            Some(0) => 0,
+            Some(position) => position - 1,
+        };
+        self.position = Some(position);
+        self.entries.get(position).map(String::as_str)
+    }
+
+    pub fn newer(&mut self) -> Option<&str> {
+        let position = self.position?;
+        if position + 1 < self.entries.len() {
+            self.position = Some(position + 1);
+            self.entries.get(position + 1).map(String::as_str)
+        } else {
+            self.position = None;
+            Some(&self.draft)
+        }
+    }
+
+    pub fn reset(&mut self) {
+        self.position = None;
+        self.draft.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn history() -> CommandHistory {
+        CommandHistory::new(vec![":w".to_string(), ":goto +2w".to_string(), ":theme nord".to_string()])
+    }
+
+    #[test]
+    fn older_walks_back_and_stops_at_oldest() {
+        let mut history = history();
+
+        assert_eq!(history.older(":"), Some(":theme nord"));
+        assert_eq!(history.older(":"), Some(":goto +2w"));
+        assert_eq!(history.older(":"), Some(":w"));
+        assert_eq!(history.older(":"), Some(":w"));
+    }
+
+    #[test]
+    fn newer_returns_to_the_draft() {
+        let mut history = history();
+
+        history.older(":go");
+        history.older(":go");
+        assert_eq!(history.newer(), Some(":theme nord"));
+        assert_eq!(history.newer(), Some(":go"));
+        assert_eq!(history.newer(), None);
+    }
+
+    #[test]
+    fn push_skips_blank_and_repeated_commands() {
+        let mut history = history();
+
+        history.push(":theme nord");
+        history.push(":");
+        history.push("  ");
+        history.push(":q");
+
+        assert_eq!(history.entries(), [":w", ":goto +2w", ":theme nord", ":q"]);
+    }
+
+    #[test]
+    fn push_drops_oldest_beyond_limit() {
+        let mut history = CommandHistory::default();
+        for i in 0..=MAX_HISTORY {
+            history.push(&format!(":goto +{}d", i));
+        }
+
+        assert_eq!(history.entries().len(), MAX_HISTORY);
+        assert_eq!(history.entries()[0], ":goto +1d");
+    }
+
+    #[test]
+    fn save_and_load_round_trip() {
+        let path = std::env::temp_dir().join(format!("gcal-imp-history-{}", std::process::id()));
+        history().save(&path).unwrap();
+
+        let loaded = CommandHistory::load(&path).unwrap();
+        std::fs::remove_file(&path).ok();
+
+        assert_eq!(loaded.entries(), history().entries());
+    }
+
+    #[test]
+    fn load_missing_file_is_empty() {
+        let loaded = CommandHistory::load(Path::new("/nonexistent/gcal-imp/history")).unwrap();
+
+        assert!(loaded.entries().is_empty());
+    }
+}
@@ -1,18 +1,79 @@
+use std::path::PathBuf;
+
 use chrono::NaiveDate;
+use crossterm::event::KeyCode;
+
+use crate::app::{AppState, UI_OPTIONS};
+use crate::calendar::{date_expr, free_slots::{self, FreeQuery}, overlay};
+use crate::sync::google_api::EventReference;
+use crate::ui::{theme::Theme, view_export::ViewExportFormat};
+
+const COMMAND_NAMES: &[&str] = &["calendar", "conflicts", "delete", "duplicate", "export-view", "free", "goto", "help", "log", "messages", "move", "new", "open", "quit", "registers", "reload-config", "report", "set", "subscribe", "sync-status", "theme", "toggle", "write"];
+const COMMANDS_WITH_ARGS: &[&str] = &["calendar", "cal", "conflicts", "duplicate", "export-view", "free", "goto", "move", "new", "open", "report", "set", "subscribe", "theme", "toggle"];
+
+/// Display settings that `:toggle` can flip.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ToggleOption {
+    Declined,
+}
+
+impl ToggleOption {
+    pub const NAMES: &[&str] = &["declined"];
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "declined" => Some(Self::Declined),
+            _ => None,
+        }
+    }
+}
 
 #[derive(Debug, PartialEq)]
 pub enum Command {
     Quit,
     Sync,
+    /// Show progress and totals of the current or last sync.
+    SyncStatus,
     Goto(NaiveDate),
     NewEvent(Option<String>),
     SwitchCalendar(String),
     Theme(String),
     Help,
+    /// Open the current log file in the pager.
+    Log,
+    /// Override one `[ui]` option until the next reload, e.g. `:set theme=nord`.
+    Set(String, String),
+    /// Re-read config.toml and apply its `[ui]` section.
+    ReloadConfig,
+    /// Toggle the panel of recent syncs, errors and finished operations.
+    Messages,
+    /// Delete every event in the visual selection.
+    Delete,
+    /// Shift every event in the visual selection by this many days.
+    Move(i64),
+    /// Copy the selected event, to its own date when none is given.
+    Duplicate(Option<NaiveDate>),
+    /// List gaps in loaded events, e.g. `:free 60m this week 9-17`.
+    Free(FreeQuery),
+    /// Hours per calendar and title pattern, e.g. `:report last week`.
+    Report(NaiveDate, NaiveDate),
+    /// Tight hops between locations, e.g. `:conflicts next week`.
+    Conflicts(NaiveDate, NaiveDate),
+    /// Show what each yank register holds.
+    Registers,
+    /// Write the current view to a file, e.g. `:export-view text week.txt`.
+    ExportView(ViewExportFormat, Option<PathBuf>),
+    Toggle(ToggleOption),
+    /// Fetch one event by id or Google Calendar link and show its details.
+    Open(EventReference),
+    /// Fetch a holiday/birthday calendar as an overlay; holds its resolved id.
+    Subscribe(String),
     Error(String),
 }
 
-pub fn parse_command(input: &str) -> Command {
+/// Parses a `:` command, resolving relative dates such as `:goto +2w`
+/// against `today`.
+pub fn parse_command_at(input: &str, today: NaiveDate) -> Command {
     let trimmed = input.trim();
 
     if !trimmed.starts_with(':') {
@@ -30,13 +91,66 @@ pub fn parse_command(input: &str) -> Command {
         "q" | "quit" => Command::Quit,
         "w" | "write" => Command::Sync,
         "help" => Command::Help,
+        "log" => Command::Log,
+        "reload-config" => Command::ReloadConfig,
+        // The raw text, so a value such as a statusline keeps its spacing.
+        "set" => match command_text.trim_start()["set".len()..].split_once('=') {
+            Some((key, value)) => Command::Set(key.trim().to_string(), value.to_string()),
+            None => Command::Error("set requires key=value, e.g. :set theme=nord".to_string()),
+        },
+        "messages" | "mes" => Command::Messages,
+        "sync-status" => Command::SyncStatus,
+        "registers" | "reg" => Command::Registers,
+        "export-view" => {
+            let format = match parts.get(1) {
+                Some(name) => ViewExportFormat::from_name(name),
+                None => Some(ViewExportFormat::default()),
+            };
+            match format {
+                Some(format) => Command::ExportView(format, parts.get(2).map(PathBuf::from)),
+                None => Command::Error("export-view: use html or text".to_string()),
+            }
+        }
         "goto" => {
             if parts.len() < 2 {
                 Command::Error("goto requires a date argument".to_string())
-            } else if let Ok(date) = NaiveDate::parse_from_str(parts[1], "%Y-%m-%d") {
-                Command::Goto(date)
             } else {
-                Command::Error(format!("Invalid date format: {}", parts[1]))
+                match date_expr::parse_date(&parts[1..].join(" "), today) {
+                    Ok(date) => Command::Goto(date),
+                    Err(e) => Command::Error(format!("goto: {}", e)),
+                }
+            }
+        }
+        "d" | "delete" => Command::Delete,
+        "m" | "move" => {
+            match parts.get(1) {
+                Some(offset) if offset.starts_with(['+', '-']) => match date_expr::parse_date(offset, today) {
+                    Ok(date) => Command::Move((date - today).num_days()),
+                    Err(e) => Command::Error(format!("move: {}", e)),
+                },
+                _ => Command::Error("move requires an offset like +7d or -1w".to_string()),
+            }
+        }
+        "dup" | "duplicate" => {
+            if parts.len() < 2 {
+                Command::Duplicate(None)
+            } else {
+                match date_expr::parse_date(&parts[1..].join(" "), today) {
+                    Ok(date) => Command::Duplicate(Some(date)),
+                    Err(e) => Command::Error(format!("duplicate: {}", e)),
+                }
+            }
+        }
+        "free" => match free_slots::parse_free_query(&parts[1..].join(" "), today) {
+            Ok(query) => Command::Free(query),
+            Err(e) => Command::Error(format!("free: {}", e)),
+        },
+        "report" | "conflicts" => {
+            let range = if parts.len() < 2 { "week".to_string() } else { parts[1..].join(" ") };
+            match date_expr::parse_range(&range, today) {
+                Ok((start, end)) if parts[0] == "report" => Command::Report(start, end),
+                Ok((start, end)) => Command::Conflicts(start, end),
+                Err(e) => Command::Error(format!("{}: {}", parts[0], e)),
             }
         }
         "new" => {
@@ -54,6 +168,24 @@ pub fn parse_command(input: &str) -> Command {
                 Command::SwitchCalendar(parts[1].to_string())
             }
         }
+        "subscribe" => {
+            if parts.len() < 2 {
+                Command::Error("subscribe requires holidays [region], birthdays or a calendar id".to_string())
+            } else {
+                Command::Subscribe(overlay::resolve(&parts[1..].join(" ")))
+            }
+        }
+        "open" => match parts.get(1).and_then(|reference| EventReference::parse(reference)) {
+            Some(reference) => Command::Open(reference),
+            None => Command::Error("open requires an event id or link".to_string()),
+        },
+        "toggle" => match parts.get(1) {
+            Some(name) => match ToggleOption::from_name(name) {
+                Some(option) => Command::Toggle(option),
+                None => Command::Error(format!("toggle: unknown option '{}' (available: {})", name, ToggleOption::NAMES.join(", "))),
+            },
+            None => Command::Error(format!("toggle requires an option ({})", ToggleOption::NAMES.join(", "))),
+        },
         "theme" => {
             if parts.len() < 2 {
                 Command::Error("theme requires a theme name".to_string())
@@ -65,9 +197,165 @@ pub fn parse_command(input: &str) -> Command {
     }
 }
 
+/// Line-editing keys; Enter and Esc are handled by the caller.
+pub fn handle_key(key: KeyCode, state: &mut AppState) {
+    if key != KeyCode::Tab {
+        state.command_completions.clear();
+    }
+
+    match key {
+        KeyCode::Tab => complete(state),
+        KeyCode::Up => {
+            if let Some(entry) = state.command_history.older(&state.command_buffer) {
+                state.command_buffer = entry.to_string();
+            }
+        }
+        KeyCode::Down => {
+            if let Some(entry) = state.command_history.newer() {
+                state.command_buffer = entry.to_string();
+            }
+        }
+        KeyCode::Backspace => {
+            state.command_buffer.pop();
+        }
+        KeyCode::Char(c) => {
+            state.command_buffer.push(c);
+        }
+        _ => {}
+    }
+}
+
+/// Appends a terminal paste, e.g. a link for `:open`. Line breaks are
+/// dropped so a paste cannot run the command.
+pub fn paste(state: &mut AppState, text: &str) {
+    state.command_completions.clear();
+    state.command_buffer.extend(text.chars().filter(|c| !c.is_control()));
+}
+
+/// Replaces the buffer with the next completion, cycling on repeated Tab.
+fn complete(state: &mut AppState) {
+    if state.command_completions.is_empty() {
+        state.command_completions = completions(&state.command_buffer, &state.known_calendars());
+        state.command_completion_index = 0;
+    } else {
+        state.command_completion_index = (state.command_completion_index + 1) % state.command_completions.len();
+    }
+
+    if let Some(completion) = state.command_completions.get(state.command_completion_index) {
+        state.command_buffer = completion.clone();
+    }
+}
+
+/// Full command lines that `input` could complete to.
+pub fn completions(input: &str, calendars: &[String]) -> Vec<String> {
+    let Some(rest) = input.strip_prefix(':') else {
+        return Vec::new();
+    };
+
+    let mut matches: Vec<String> = match rest.split_once(' ') {
+        None => COMMAND_NAMES
+            .iter()
+            .filter(|name| name.starts_with(rest))
+            .map(|name| {
+                let suffix = if COMMANDS_WITH_ARGS.contains(name) { " " } else { "" };
+                format!(":{}{}", name, suffix)
+            })
+            .collect(),
+        Some((command, arg)) => {
+            let arg = arg.trim_start().to_lowercase();
+            let candidates: Vec<String> = match command {
+                "theme" => Theme::available_themes().into_iter().map(str::to_string).collect(),
+                "cal" | "calendar" => calendars.to_vec(),
+                "toggle" => ToggleOption::NAMES.iter().map(|name| name.to_string()).collect(),
+                "subscribe" => vec!["birthdays".to_string(), "holidays".to_string()],
+                "set" => UI_OPTIONS.iter().map(|option| format!("{}=", option)).collect(),
+                _ => Vec::new(),
+            };
+            candidates
+                .into_iter()
+                .filter(|candidate| candidate.to_lowercase().starts_with(&arg))
+                .map(|candidate| format!(":{} {}", command, candidate))
+                .collect()
+        }
+    };
+
+    matches.sort();
+    matches.dedup();
+    matches
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::input::command_history::CommandHistory;
+
+    fn today() -> NaiveDate {
+        NaiveDate::from_ymd_opt(2025, 1, 15).unwrap()
+    }
+
+    fn parse_command(input: &str) -> Command {
+        parse_command_at(input, today())
+    }
+
+    fn type_keys(state: &mut AppState, keys: &[KeyCode]) {
+        for key in keys {
+            handle_key(*key, state);
+        }
+    }
+
+    #[test]
+    fn completes_command_names() {
+        assert_eq!(completions(":th", &[]), vec![":theme "]);
+        assert_eq!(completions(":h", &[]), vec![":help"]);
+        assert_eq!(completions(":", &[]).len(), COMMAND_NAMES.len());
+    }
+
+    #[test]
+    fn completes_theme_and_calendar_arguments() {
+        let calendars = vec!["primary".to_string(), "work@example.com".to_string()];
+
+        assert_eq!(completions(":theme gr", &calendars), vec![":theme gruvbox"]);
+        assert_eq!(completions(":cal w", &calendars), vec![":cal work@example.com"]);
+        assert!(completions(":goto to", &calendars).is_empty());
+    }
+
+    #[test]
+    fn tab_cycles_through_completions() {
+        let mut state = AppState::new();
+        state.command_buffer = ":theme ".to_string();
+
+        type_keys(&mut state, &[KeyCode::Tab]);
+        assert_eq!(state.command_buffer, ":theme default");
+
+        type_keys(&mut state, &[KeyCode::Tab]);
+        assert_eq!(state.command_buffer, ":theme dracula");
+
+        type_keys(&mut state, &[KeyCode::Char('x')]);
+        assert!(state.command_completions.is_empty());
+    }
+
+    #[test]
+    fn pastes_append_to_the_command_without_line_breaks() {
+        let mut state = AppState::new();
+        state.command_buffer = ":open ".to_string();
+
+        paste(&mut state, "https://example.com/a\r\n");
+
+        assert_eq!(state.command_buffer, ":open https://example.com/a");
+    }
+
+    #[test]
+    fn up_and_down_browse_history() {
+        let mut state = AppState::new();
+        state.command_history = CommandHistory::new(vec![":w".to_string(), ":theme nord".to_string()]);
+        state.command_buffer = ":".to_string();
+
+        type_keys(&mut state, &[KeyCode::Up, KeyCode::Up]);
+        assert_eq!(state.command_buffer, ":w");
+
+        type_keys(&mut state, &[KeyCode::Down, KeyCode::Down]);
+        assert_eq!(state.command_buffer, ":");
+    }
 
     #[test]
     fn parse_quit_command() {
@@ -106,6 +394,21 @@ mod tests {
         assert!(matches!(cmd, Command::Error(_)));
     }
 
+    #[test]
+    fn parse_goto_relative_dates() {
+        let today = NaiveDate::from_ymd_opt(2025, 1, 15).unwrap();
+
+        assert_eq!(parse_command_at(":goto +2w", today), Command::Goto(NaiveDate::from_ymd_opt(2025, 1, 29).unwrap()));
+        assert_eq!(parse_command_at(":goto next friday", today), Command::Goto(NaiveDate::from_ymd_opt(2025, 1, 17).unwrap()));
+        assert_eq!(parse_command_at(":goto dec 25", today), Command::Goto(NaiveDate::from_ymd_opt(2025, 12, 25).unwrap()));
+    }
+
+    #[test]
+    fn parse_goto_error_names_the_input() {
+        let cmd = parse_command(":goto someday");
+        assert_eq!(cmd, Command::Error("goto: Unrecognised date 'someday'".to_string()));
+    }
+
     #[test]
     fn parse_goto_without_date_returns_error() {
         let cmd = parse_command(":goto");
@@ -142,6 +445,131 @@ mod tests {
         assert_eq!(cmd, Command::SwitchCalendar("personal".to_string()));
     }
 
+    #[test]
+    fn parse_delete_and_move_commands() {
+        let today = NaiveDate::from_ymd_opt(2025, 1, 15).unwrap();
+
+        assert_eq!(parse_command_at(":delete", today), Command::Delete);
+        assert_eq!(parse_command_at(":move +7d", today), Command::Move(7));
+        assert_eq!(parse_command_at(":move -2w", today), Command::Move(-14));
+        assert!(matches!(parse_command_at(":move friday", today), Command::Error(_)));
+        assert!(matches!(parse_command_at(":move", today), Command::Error(_)));
+    }
+
+    #[test]
+    fn parse_duplicate_command() {
+        let today = NaiveDate::from_ymd_opt(2025, 1, 15).unwrap();
+
+        assert_eq!(parse_command_at(":duplicate", today), Command::Duplicate(None));
+        assert_eq!(parse_command_at(":duplicate next monday", today), Command::Duplicate(NaiveDate::from_ymd_opt(2025, 1, 20)));
+        assert!(matches!(parse_command_at(":dup someday", today), Command::Error(_)));
+    }
+
+    #[test]
+    fn parse_free_command() {
+        let today = NaiveDate::from_ymd_opt(2025, 1, 15).unwrap();
+
+        let Command::Free(query) = parse_command_at(":free 60m this week 9-17", today) else {
+            panic!("expected :free to parse");
+        };
+        assert_eq!(query.duration_minutes, 60);
+        assert_eq!(query.start_date, NaiveDate::from_ymd_opt(2025, 1, 13).unwrap());
+        assert!(matches!(parse_command_at(":free", today), Command::Error(_)));
+    }
+
+    #[test]
+    fn parse_report_command() {
+        let today = NaiveDate::from_ymd_opt(2025, 1, 15).unwrap();
+        let date = |day| NaiveDate::from_ymd_opt(2025, 1, day).unwrap();
+
+        assert_eq!(parse_command_at(":report", today), Command::Report(date(13), date(19)));
+        assert_eq!(parse_command_at(":report last week", today), Command::Report(date(6), date(12)));
+        assert_eq!(parse_command_at(":report month", today), Command::Report(date(1), date(31)));
+        assert!(matches!(parse_command_at(":report someday", today), Command::Error(_)));
+        assert_eq!(parse_command_at(":conflicts next week", today), Command::Conflicts(date(20), date(26)));
+    }
+
+    #[test]
+    fn parse_open_command() {
+        let reference = EventReference { event_id: "abc123".to_string(), calendar_id: None };
+
+        assert_eq!(parse_command(":open abc123"), Command::Open(reference));
+        assert!(matches!(parse_command(":open"), Command::Error(_)));
+    }
+
+    #[test]
+    fn parse_toggle_command() {
+        assert_eq!(parse_command(":toggle declined"), Command::Toggle(ToggleOption::Declined));
+        assert!(matches!(parse_command(":toggle weekends"), Command::Error(_)));
+        assert!(matches!(parse_command(":toggle"), Command::Error(_)));
+        assert_eq!(completions(":toggle d", &[]), vec![":toggle declined"]);
+    }
+
+    #[test]
+    fn parse_set_command() {
+        assert_eq!(parse_command(":set theme=nord"), Command::Set("theme".to_string(), "nord".to_string()));
+        assert_eq!(parse_command(":set timezone="), Command::Set("timezone".to_string(), String::new()));
+        assert!(matches!(parse_command(":set theme"), Command::Error(_)));
+        assert_eq!(
+            parse_command(":set statusline=%date %view"),
+            Command::Set("statusline".to_string(), "%date %view".to_string()),
+        );
+        assert_eq!(completions(":set the", &[]), vec![":set theme="]);
+    }
+
+    #[test]
+    fn set_keeps_the_spacing_of_the_value() {
+        assert_eq!(
+            parse_command(":set statusline=%date   %view"),
+            Command::Set("statusline".to_string(), "%date   %view".to_string()),
+        );
+    }
+
+    #[test]
+    fn parse_subscribe_command() {
+        assert_eq!(
+            parse_command(":subscribe holidays uk"),
+            Command::Subscribe("en.uk#holiday@group.v.calendar.google.com".to_string())
+        );
+        assert_eq!(parse_command(":subscribe birthdays"), Command::Subscribe(overlay::BIRTHDAYS_CALENDAR_ID.to_string()));
+        assert!(matches!(parse_command(":subscribe"), Command::Error(_)));
+    }
+
+    #[test]
+    fn parse_export_view_command() {
+        assert_eq!(parse_command(":export-view"), Command::ExportView(ViewExportFormat::Html, None));
+        assert_eq!(
+            parse_command(":export-view text week.txt"),
+            Command::ExportView(ViewExportFormat::Text, Some(PathBuf::from("week.txt"))),
+        );
+        assert!(matches!(parse_command(":export-view pdf"), Command::Error(_)));
+    }
+
+    #[test]
+    fn parse_sync_status_command() {
+        assert_eq!(parse_command(":sync-status"), Command::SyncStatus);
+    }
+
+    #[test]
+    fn parse_log_command() {
+        assert_eq!(parse_command(":log"), Command::Log);
+    }
+
+    #[test]
+    fn parse_messages_command() {
+        assert_eq!(parse_command(":messages"), Command::Messages);
+    }
+
+    #[test]
+    fn parse_registers_command() {
+        assert_eq!(parse_command(":reg"), Command::Registers);
+    }
+
+    #[test]
+    fn parse_reload_config_command() {
+        assert_eq!(parse_command(":reload-config"), Command::ReloadConfig);
+    }
+
     #[test]
     fn parse_help_command() {
         let cmd = parse_command(":help");
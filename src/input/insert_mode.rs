@@ -1,99 +1,209 @@
+use chrono::NaiveDate;
 use crossterm::event::KeyCode;
-use crate::app::{AppState, EventForm, FormField};
+use crate::app::{AppState, EventForm, FormField, MessageKind};
+use crate::calendar::DisplayTimezone;
+use crate::input::text_area;
 
-fn parse_current_field(form: &mut EventForm) {
+fn parse_current_field(form: &mut EventForm, today: NaiveDate) {
     match form.active_field {
+        FormField::Date => form.parse_date_input(today),
         FormField::StartTime => form.parse_time_input(),
         FormField::Duration => form.parse_duration_input(),
         _ => {}
     }
 }
 
+fn type_char(form: &mut EventForm, c: char, max_len: usize) {
+    form.start_typing();
+    if let Some((text, cursor)) = form.active_text_mut()
+        && text.chars().count() < max_len
+    {
+        text_area::insert_char(text, cursor, c);
+    }
+}
+
+/// Word-wise editing: Ctrl-Left/Right move by word, Ctrl-W and
+/// Ctrl-Backspace delete the word before the cursor. Ctrl-R waits for a
+/// register to paste.
+pub fn handle_ctrl_key(key: KeyCode, state: &mut AppState) {
+    if key == KeyCode::Char('r') {
+        state.awaiting_register = true;
+        return;
+    }
+    let timezone = state.timezone;
+    let today = state.today();
+    let Some(form) = state.event_form.as_mut() else {
+        return;
+    };
+    let Some((text, cursor)) = form.active_text_mut() else {
+        return;
+    };
+
+    match key {
+        KeyCode::Left => text_area::word_left(text, cursor),
+        KeyCode::Right => text_area::word_right(text, cursor),
+        KeyCode::Char('w') | KeyCode::Backspace => text_area::delete_word_back(text, cursor),
+        _ => return,
+    }
+    form.mark_touched();
+    refresh_errors(form, &timezone, today);
+}
+
 pub fn handle_key(key: KeyCode, state: &mut AppState) {
+    let calendars = state.known_calendars();
+    let default_calendar = state.default_calendar.clone();
+    let contacts = state.contacts.emails();
+    let timezone = state.timezone;
+    let today = state.today();
     let Some(form) = state.event_form.as_mut() else {
         return;
     };
 
     match key {
         KeyCode::Tab => {
-            parse_current_field(form);
+            parse_current_field(form, today);
             form.next_field();
         }
         KeyCode::BackTab => {
-            parse_current_field(form);
+            parse_current_field(form, today);
             form.prev_field();
         }
-        KeyCode::Backspace => {
-            match form.active_field {
-                FormField::Title => {
-                    form.title.pop();
-                }
-                FormField::StartTime => {
-                    form.time_input_buffer.pop();
-                    form.time_buffer_touched = true;
-                }
-                FormField::Duration => {
-                    form.duration_input_buffer.pop();
-                    form.duration_buffer_touched = true;
-                }
-                FormField::Location => {
-                    form.location.pop();
-                }
-                FormField::Description => {
-                    form.description.pop();
+        KeyCode::Left | KeyCode::Right if form.active_field == FormField::Calendar => {
+            form.cycle_calendar(&calendars, &default_calendar, key == KeyCode::Right);
+        }
+        // The date steps with the arrows; Home/End and Ctrl-Left/Right still
+        // move the cursor while typing one.
+        KeyCode::Left | KeyCode::Right if form.active_field == FormField::Date => {
+            form.shift_date(if key == KeyCode::Right { 1 } else { -1 });
+        }
+        KeyCode::Up | KeyCode::Down if form.active_field == FormField::Date => {
+            form.shift_date(if key == KeyCode::Down { 7 } else { -7 });
+        }
+        KeyCode::Enter if form.active_field == FormField::Description => {
+            text_area::insert_char(&mut form.description, &mut form.description_cursor, '\n');
+        }
+        KeyCode::Up if form.active_field == FormField::Description => {
+            text_area::move_up(&form.description, &mut form.description_cursor);
+        }
+        KeyCode::Down if form.active_field == FormField::Description => {
+            text_area::move_down(&form.description, &mut form.description_cursor);
+        }
+        KeyCode::Right | KeyCode::End
+            if form.active_field == FormField::Attendees
+                && form.field_cursor >= form.attendees.chars().count()
+                && form.attendee_suggestion(contacts).is_some() =>
+        {
+            form.accept_attendee_suggestion(contacts);
+        }
+        KeyCode::Left | KeyCode::Right | KeyCode::Home | KeyCode::End => {
+            form.mark_touched();
+            if let Some((text, cursor)) = form.active_text_mut() {
+                match key {
+                    KeyCode::Left => text_area::move_left(cursor),
+                    KeyCode::Right => text_area::move_right(text, cursor),
+                    KeyCode::Home => text_area::move_home(text, cursor),
+                    _ => text_area::move_end(text, cursor),
                 }
             }
         }
+        KeyCode::Backspace => {
+            form.mark_touched();
+            if let Some((text, cursor)) = form.active_text_mut() {
+                text_area::backspace(text, cursor);
+            }
+        }
         KeyCode::Char(c) => {
             match form.active_field {
-                FormField::Title => {
-                    form.title.push(c);
+                FormField::AllDay => {
+                    if c == ' ' {
+                        form.toggle_all_day();
+                    }
                 }
-                FormField::StartTime => {
-                    if c.is_ascii_digit() || c == ':' {
-                        if !form.time_buffer_touched {
-                            form.time_input_buffer.clear();
-                            form.time_buffer_touched = true;
-                        }
-                        if form.time_input_buffer.len() < 5 {
-                            form.time_input_buffer.push(c);
-                        }
+                FormField::Calendar => {
+                    if c == ' ' {
+                        form.cycle_calendar(&calendars, &default_calendar, true);
                     }
                 }
-                FormField::Duration => {
-                    if c.is_ascii_digit() {
-                        if !form.duration_buffer_touched {
-                            form.duration_input_buffer.clear();
-                            form.duration_buffer_touched = true;
-                        }
-                        if form.duration_input_buffer.len() < 5 {
-                            form.duration_input_buffer.push(c);
-                        }
+                FormField::Status => {
+                    if c == ' ' {
+                        form.status = form.status.cycled();
                     }
                 }
-                FormField::Location => {
-                    form.location.push(c);
+                FormField::ShowAs => {
+                    if c == ' ' {
+                        form.transparency = form.transparency.toggled();
+                    }
                 }
-                FormField::Description => {
-                    form.description.push(c);
+                FormField::Visibility => {
+                    if c == ' ' {
+                        form.visibility = form.visibility.toggled();
+                    }
                 }
+                FormField::StartTime if c.is_ascii_digit() || c == ':' => type_char(form, c, 5),
+                FormField::Duration if c.is_ascii_alphanumeric() || c == ':' || c == '-' || c == ' ' => type_char(form, c, 11),
+                FormField::StartTime | FormField::Duration => {}
+                FormField::Title | FormField::Date | FormField::Location | FormField::Link | FormField::Attendees | FormField::Description => type_char(form, c, usize::MAX),
             }
         }
         _ => {}
     }
+
+    refresh_errors(form, &timezone, today);
+}
+
+/// The key after Ctrl-R: pastes that register into the active field.
+pub fn paste_register(key: KeyCode, state: &mut AppState) {
+    let (KeyCode::Char(name), Some(form)) = (key, state.event_form.as_ref()) else {
+        return;
+    };
+    match state.registers.get(name).and_then(|register| register.text_for(&form.active_field, &state.timezone)) {
+        Some(text) => paste(state, &text),
+        None => state.set_message(MessageKind::Info, format!("Nothing in register \"{} for this field", name)),
+    }
+}
+
+/// Pastes into the active field, e.g. from the clipboard or a terminal paste.
+pub fn paste(state: &mut AppState, text: &str) {
+    let timezone = state.timezone;
+    let today = state.today();
+    if let Some(form) = state.event_form.as_mut() {
+        paste_text(form, text);
+        refresh_errors(form, &timezone, today);
+    }
+}
+
+/// Inserts `text` at the cursor; single-line fields get line breaks as spaces.
+fn paste_text(form: &mut EventForm, text: &str) {
+    let multi_line = form.active_field == FormField::Description;
+    form.start_typing();
+    if let Some((field, cursor)) = form.active_text_mut() {
+        for c in text.trim_end_matches(['\r', '\n']).chars().filter(|&c| c != '\r') {
+            let c = if c.is_control() && !(multi_line && c == '\n') { ' ' } else { c };
+            text_area::insert_char(field, cursor, c);
+        }
+    }
+}
+
+/// Once a save has been refused, keep the messages in step with the edits.
+fn refresh_errors(form: &mut EventForm, timezone: &DisplayTimezone, today: NaiveDate) {
+    if !form.errors.is_empty() {
+        form.errors = form.validate(timezone, today);
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use chrono::Local;
     use crate::app::EventForm;
+    use crate::calendar::EventStatus;
+    use crate::storage::contacts::Contacts;
 
     fn setup_state_with_form() -> AppState {
         let mut state = AppState::new();
         state.event_form = Some(EventForm::new(
-            Local::now().date_naive(),
+            state.today(),
             "Test Event".to_string(),
+            state.local_now(),
         ));
         state
     }
@@ -106,7 +216,7 @@ mod tests {
 
         handle_key(KeyCode::Tab, &mut state);
         let form = state.event_form.as_ref().unwrap();
-        assert_eq!(form.active_field, FormField::StartTime);
+        assert_eq!(form.active_field, FormField::Date);
     }
 
     #[test]
@@ -116,7 +226,7 @@ mod tests {
 
         handle_key(KeyCode::BackTab, &mut state);
         let form = state.event_form.as_ref().unwrap();
-        assert_eq!(form.active_field, FormField::Title);
+        assert_eq!(form.active_field, FormField::AllDay);
     }
 
     #[test]
@@ -181,4 +291,200 @@ mod tests {
         let form = state.event_form.as_ref().unwrap();
         assert_eq!(form.duration_input_buffer, "90");
     }
+
+    #[test]
+    fn space_cycles_calendar_field() {
+        let mut state = setup_state_with_form()
+            .with_calendar_names(vec!["primary".to_string(), "work".to_string()]);
+        state.event_form.as_mut().unwrap().active_field = FormField::Calendar;
+
+        handle_key(KeyCode::Char(' '), &mut state);
+        assert_eq!(state.event_form.as_ref().unwrap().calendar_id.as_deref(), Some("work"));
+
+        handle_key(KeyCode::Right, &mut state);
+        assert_eq!(state.event_form.as_ref().unwrap().calendar_id.as_deref(), Some("primary"));
+
+        handle_key(KeyCode::Left, &mut state);
+        assert_eq!(state.event_form.as_ref().unwrap().calendar_id.as_deref(), Some("work"));
+    }
+
+    #[test]
+    fn calendar_is_fixed_when_editing() {
+        let mut state = setup_state_with_form()
+            .with_calendar_names(vec!["primary".to_string(), "work".to_string()]);
+        let form = state.event_form.as_mut().unwrap();
+        form.active_field = FormField::Calendar;
+        form.event_id = Some("e1".to_string());
+
+        handle_key(KeyCode::Char(' '), &mut state);
+
+        assert_eq!(state.event_form.as_ref().unwrap().calendar_id, None);
+    }
+
+    #[test]
+    fn space_cycles_status_field() {
+        let mut state = setup_state_with_form();
+        state.event_form.as_mut().unwrap().active_field = FormField::Status;
+
+        handle_key(KeyCode::Char(' '), &mut state);
+        assert_eq!(state.event_form.as_ref().unwrap().status, EventStatus::Tentative);
+
+        handle_key(KeyCode::Char(' '), &mut state);
+        handle_key(KeyCode::Char(' '), &mut state);
+        assert_eq!(state.event_form.as_ref().unwrap().status, EventStatus::Confirmed);
+    }
+
+    #[test]
+    fn description_is_edited_as_multiple_lines() {
+        let mut state = setup_state_with_form();
+        state.event_form.as_mut().unwrap().active_field = FormField::Description;
+
+        handle_key(KeyCode::Char('a'), &mut state);
+        handle_key(KeyCode::Enter, &mut state);
+        handle_key(KeyCode::Char('c'), &mut state);
+        handle_key(KeyCode::Up, &mut state);
+        handle_key(KeyCode::Char('b'), &mut state);
+
+        let form = state.event_form.as_ref().unwrap();
+        assert_eq!(form.description, "ab\nc");
+        assert_eq!(form.description_cursor, 2);
+    }
+
+    #[test]
+    fn date_is_typed_and_parsed_on_tab() {
+        let mut state = setup_state_with_form();
+        state.event_form.as_mut().unwrap().active_field = FormField::Date;
+
+        for c in "2025-03-14".chars() {
+            handle_key(KeyCode::Char(c), &mut state);
+        }
+        handle_key(KeyCode::Tab, &mut state);
+
+        let form = state.event_form.as_ref().unwrap();
+        assert_eq!(form.date, NaiveDate::from_ymd_opt(2025, 3, 14).unwrap());
+        assert_eq!(form.date_input_buffer, "2025-03-14");
+    }
+
+    #[test]
+    fn arrows_shift_the_date() {
+        let mut state = setup_state_with_form();
+        let form = state.event_form.as_mut().unwrap();
+        form.active_field = FormField::Date;
+        form.date = NaiveDate::from_ymd_opt(2025, 2, 28).unwrap();
+
+        handle_key(KeyCode::Right, &mut state);
+        handle_key(KeyCode::Down, &mut state);
+        handle_key(KeyCode::Left, &mut state);
+
+        let form = state.event_form.as_ref().unwrap();
+        assert_eq!(form.date, NaiveDate::from_ymd_opt(2025, 3, 7).unwrap());
+        assert_eq!(form.date_input_buffer, "2025-03-07");
+    }
+
+    #[test]
+    fn home_and_end_move_the_cursor_in_a_typed_date() {
+        let mut state = setup_state_with_form();
+        state.event_form.as_mut().unwrap().active_field = FormField::Date;
+
+        for c in "03-14".chars() {
+            handle_key(KeyCode::Char(c), &mut state);
+        }
+        handle_key(KeyCode::Home, &mut state);
+        for c in "2025-".chars() {
+            handle_key(KeyCode::Char(c), &mut state);
+        }
+        handle_key(KeyCode::End, &mut state);
+        handle_key(KeyCode::Backspace, &mut state);
+        handle_key(KeyCode::Char('5'), &mut state);
+
+        assert_eq!(state.event_form.as_ref().unwrap().date_input_buffer, "2025-03-15");
+    }
+
+    #[test]
+    fn space_toggles_all_day_and_reflows_fields() {
+        let mut state = setup_state_with_form();
+        state.event_form.as_mut().unwrap().active_field = FormField::AllDay;
+
+        handle_key(KeyCode::Char(' '), &mut state);
+        handle_key(KeyCode::Tab, &mut state);
+
+        let form = state.event_form.as_ref().unwrap();
+        assert!(form.all_day);
+        assert_eq!(form.duration_input_buffer, "1");
+        assert_eq!(form.active_field, FormField::Duration);
+    }
+
+    #[test]
+    fn errors_clear_as_fields_are_fixed() {
+        let mut state = setup_state_with_form();
+        let today = state.today();
+        let form = state.event_form.as_mut().unwrap();
+        form.title.clear();
+        form.errors = form.validate(&state.timezone, today);
+        assert_eq!(state.event_form.as_ref().unwrap().error_for(&FormField::Title), Some("Title is required"));
+
+        handle_key(KeyCode::Char('x'), &mut state);
+
+        assert!(state.event_form.as_ref().unwrap().errors.is_empty());
+    }
+
+    #[test]
+    fn typing_and_deleting_follow_the_cursor() {
+        let mut state = setup_state_with_form();
+        state.event_form.as_mut().unwrap().title = "Team sync".to_string();
+
+        handle_key(KeyCode::Home, &mut state);
+        handle_key(KeyCode::Char('A'), &mut state);
+        handle_key(KeyCode::Char(' '), &mut state);
+        handle_key(KeyCode::End, &mut state);
+        handle_key(KeyCode::Left, &mut state);
+        handle_key(KeyCode::Backspace, &mut state);
+        assert_eq!(state.event_form.as_ref().unwrap().title, "A Team syc");
+
+        handle_ctrl_key(KeyCode::Char('w'), &mut state);
+        assert_eq!(state.event_form.as_ref().unwrap().title, "A Team c");
+    }
+
+    #[test]
+    fn moving_the_cursor_keeps_a_prefilled_time() {
+        let mut state = setup_state_with_form();
+        let form = state.event_form.as_mut().unwrap();
+        form.active_field = FormField::StartTime;
+        form.time_input_buffer = "09:00".to_string();
+        form.field_cursor = 5;
+
+        handle_key(KeyCode::Left, &mut state);
+        handle_key(KeyCode::Backspace, &mut state);
+        handle_key(KeyCode::Char('3'), &mut state);
+
+        assert_eq!(state.event_form.as_ref().unwrap().time_input_buffer, "09:30");
+    }
+
+    #[test]
+    fn right_at_the_end_accepts_an_attendee_suggestion() {
+        let mut state = setup_state_with_form()
+            .with_contacts(Contacts::from_iter(["sam@example.com", "sasha@example.com"]));
+        state.event_form.as_mut().unwrap().active_field = FormField::Attendees;
+
+        handle_key(KeyCode::Char('s'), &mut state);
+        handle_key(KeyCode::Char('a'), &mut state);
+        handle_key(KeyCode::Char('s'), &mut state);
+        handle_key(KeyCode::Right, &mut state);
+
+        assert_eq!(state.event_form.as_ref().unwrap().attendees, "sasha@example.com, ");
+    }
+
+    #[test]
+    fn paste_inserts_at_the_cursor_and_keeps_single_line_fields_on_one_line() {
+        let mut state = setup_state_with_form();
+        state.event_form.as_mut().unwrap().field_cursor = 4;
+
+        paste(&mut state, " sync\r\nnotes\n");
+        assert_eq!(state.event_form.as_ref().unwrap().title, "Test sync notes Event");
+
+        let form = state.event_form.as_mut().unwrap();
+        form.active_field = FormField::Description;
+        paste(&mut state, "Agenda:\n- intros");
+        assert_eq!(state.event_form.as_ref().unwrap().description, "Agenda:\n- intros");
+    }
 }
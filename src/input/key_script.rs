@@ -0,0 +1,155 @@
+use chrono::NaiveDate;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use thiserror::Error;
+
+#[derive(Debug, Error, PartialEq)]
+pub enum KeyScriptError {
+    #[error("Line {line}: unknown key <{name}>")]
+    UnknownKey { line: usize, name: String },
+    #[error("Line {line}: unclosed <")]
+    Unclosed { line: usize },
+    #[error("Line {line}: invalid date '{value}'")]
+    InvalidDate { line: usize, value: String },
+    #[error("Line {line}: unknown directive @{name}")]
+    UnknownDirective { line: usize, name: String },
+}
+
+/// Keys to feed into the TUI instead of the terminal, and the date to
+/// start on so a replay looks the same whenever it runs.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct KeyScript {
+    pub date: Option<NaiveDate>,
+    pub keys: Vec<KeyEvent>,
+}
+
+/// Parses vim-style key notation: plain characters type themselves, and
+/// `<CR>`, `<Esc>`, `<C-d>`, `<lt>` and friends name the rest. Line breaks
+/// are not keys; `#` starts a comment line and `@date 2025-01-15` sets
+/// the starting date.
+pub fn parse_key_script(text: &str) -> Result<KeyScript, KeyScriptError> {
+    let mut script = KeyScript::default();
+    for (index, line) in text.lines().enumerate() {
+        let line_number = index + 1;
+        if line.trim_start().starts_with('#') {
+            continue;
+        }
+        if let Some(directive) = line.strip_prefix('@') {
+            let (name, value) = directive.split_once(' ').unwrap_or((directive, ""));
+            match name {
+                "date" => {
+                    let value = value.trim();
+                    script.date = Some(NaiveDate::parse_from_str(value, "%Y-%m-%d").map_err(|_| {
+                        KeyScriptError::InvalidDate { line: line_number, value: value.to_string() }
+                    })?);
+                }
+                _ => return Err(KeyScriptError::UnknownDirective { line: line_number, name: name.to_string() }),
+            }
+            continue;
+        }
+
+        let mut rest = line;
+        while let Some(c) = rest.chars().next() {
+            if c == '<' {
+                let end = rest.find('>').ok_or(KeyScriptError::Unclosed { line: line_number })?;
+                let name = &rest[1..end];
+                let key = named_key(name)
+                    .ok_or_else(|| KeyScriptError::UnknownKey { line: line_number, name: name.to_string() })?;
+                script.keys.push(key);
+                rest = &rest[end + 1..];
+            } else {
+                script.keys.push(KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE));
+                rest = &rest[c.len_utf8()..];
+            }
+        }
+    }
+    Ok(script)
+}
+
+fn named_key(name: &str) -> Option<KeyEvent> {
+    if let Some((prefix, key)) = name.split_once('-')
+        && !key.is_empty()
+    {
+        let modifier = match prefix.to_ascii_uppercase().as_str() {
+            "C" => KeyModifiers::CONTROL,
+            "A" | "M" => KeyModifiers::ALT,
+            "S" => KeyModifiers::SHIFT,
+            _ => return None,
+        };
+        let mut event = match key.chars().count() {
+            1 => KeyEvent::new(KeyCode::Char(key.chars().next()?), KeyModifiers::NONE),
+            _ => named_key(key)?,
+        };
+        if modifier == KeyModifiers::SHIFT && event.code == KeyCode::Tab {
+            return Some(KeyEvent::new(KeyCode::BackTab, KeyModifiers::SHIFT));
+        }
+        event.modifiers |= modifier;
+        return Some(event);
+    }
+
+    let code = match name.to_ascii_lowercase().as_str() {
+        "cr" | "enter" | "return" => KeyCode::Enter,
+        "esc" => KeyCode::Esc,
+        "tab" => KeyCode::Tab,
+        "bs" | "backspace" => KeyCode::Backspace,
+        "del" | "delete" => KeyCode::Delete,
+        "space" => KeyCode::Char(' '),
+        "lt" => KeyCode::Char('<'),
+        "up" => KeyCode::Up,
+        "down" => KeyCode::Down,
+        "left" => KeyCode::Left,
+        "right" => KeyCode::Right,
+        "home" => KeyCode::Home,
+        "end" => KeyCode::End,
+        "pageup" => KeyCode::PageUp,
+        "pagedown" => KeyCode::PageDown,
+        _ => return None,
+    };
+    Some(KeyEvent::new(code, KeyModifiers::NONE))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::AppState;
+    use crate::input::normal_mode;
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    #[test]
+    fn parses_characters_and_named_keys() {
+        let script = parse_key_script("# open a new event\n:new Lunch<CR>\n<Esc><lt><C-d><S-Tab>").unwrap();
+
+        assert_eq!(script.keys[0], key(KeyCode::Char(':')));
+        assert_eq!(script.keys[10], key(KeyCode::Enter));
+        assert_eq!(&script.keys[11..13], &[key(KeyCode::Esc), key(KeyCode::Char('<'))]);
+        assert_eq!(script.keys[13], KeyEvent::new(KeyCode::Char('d'), KeyModifiers::CONTROL));
+        assert_eq!(script.keys[14].code, KeyCode::BackTab);
+        assert_eq!(script.keys.len(), 15);
+    }
+
+    #[test]
+    fn reports_the_line_of_bad_input() {
+        assert_eq!(
+            parse_key_script("jj\nk<Bogus>"),
+            Err(KeyScriptError::UnknownKey { line: 2, name: "Bogus".to_string() })
+        );
+        assert_eq!(parse_key_script("<CR"), Err(KeyScriptError::Unclosed { line: 1 }));
+        assert!(matches!(parse_key_script("@date tomorrow"), Err(KeyScriptError::InvalidDate { line: 1, .. })));
+        assert!(matches!(parse_key_script("@speed 2"), Err(KeyScriptError::UnknownDirective { .. })));
+    }
+
+    #[test]
+    fn replays_through_normal_mode_from_the_script_date() {
+        let script = parse_key_script("@date 2025-01-15\nlll").unwrap();
+        let mut app = AppState::new();
+        app.selected_date = script.date.unwrap();
+
+        for key in &script.keys {
+            normal_mode::handle_key(key.code, &mut app);
+        }
+
+        assert_eq!(app.selected_date, NaiveDate::from_ymd_opt(2025, 1, 18).unwrap());
+    }
+}
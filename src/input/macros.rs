@@ -0,0 +1,161 @@
+use std::collections::HashMap;
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+/// Registers a macro can be recorded into.
+fn is_register(c: char) -> bool {
+    c.is_ascii_lowercase()
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum MacroAction {
+    /// `Q` or `@` is waiting for a register, or recording started.
+    Consumed,
+    /// Feed these keys back through the input dispatch.
+    Replay(Vec<KeyEvent>),
+    /// Something for the status bar, e.g. an empty register.
+    Message(String),
+}
+
+/// Vim-style macros: `Q<reg>` records normal-mode keys until the next `q`,
+/// `@<reg>` replays them and `@@` replays the last one again. Recording
+/// starts with `Q` rather than vim's `q` because a plain `q` quits.
+#[derive(Debug, Clone, Default)]
+pub struct MacroRecorder {
+    registers: HashMap<char, Vec<KeyEvent>>,
+    recording: Option<(char, Vec<KeyEvent>)>,
+    pending: Option<char>,
+    last_played: Option<char>,
+}
+
+impl MacroRecorder {
+    pub fn recording(&self) -> Option<char> {
+        self.recording.as_ref().map(|(register, _)| *register)
+    }
+
+    /// Adds a typed key to the macro being recorded. Replayed keys should not
+    /// come through here or they would be recorded twice.
+    pub fn record(&mut self, key: KeyEvent) {
+        if let Some((_, keys)) = self.recording.as_mut() {
+            keys.push(key);
+        }
+    }
+
+    /// True when `key` belongs to `handle_key` rather than the normal-mode
+    /// map. `q` only does while recording; otherwise it still quits.
+    pub fn wants(&self, key: &KeyEvent) -> bool {
+        if self.pending.is_some() {
+            return true;
+        }
+        match key.code {
+            KeyCode::Char('q') => self.recording.is_some() && key.modifiers == KeyModifiers::NONE,
+            KeyCode::Char('@') => key.modifiers == KeyModifiers::NONE,
+            // Shifted letters may come with or without the SHIFT modifier.
+            KeyCode::Char('Q') => matches!(key.modifiers, KeyModifiers::NONE | KeyModifiers::SHIFT),
+            _ => false,
+        }
+    }
+
+    pub fn handle_key(&mut self, key: KeyEvent) -> MacroAction {
+        if let Some(prefix) = self.pending.take() {
+            let KeyCode::Char(c) = key.code else {
+                return MacroAction::Consumed;
+            };
+            return match (prefix, c) {
+                ('Q', register) if is_register(register) => {
+                    self.recording = Some((register, Vec::new()));
+                    MacroAction::Consumed
+                }
+                ('@', '@') => match self.last_played {
+                    Some(register) => self.play(register),
+                    None => MacroAction::Message("No macro replayed yet".to_string()),
+                },
+                ('@', register) if is_register(register) => self.play(register),
+                _ => MacroAction::Consumed,
+            };
+        }
+
+        match key.code {
+            KeyCode::Char('q') => match self.recording.take() {
+                Some((register, mut keys)) => {
+                    // The `q` that stopped the recording was recorded too.
+                    keys.pop();
+                    self.registers.insert(register, keys);
+                    MacroAction::Message(format!("Recorded @{}", register))
+                }
+                None => MacroAction::Consumed,
+            },
+            KeyCode::Char(prefix @ ('Q' | '@')) => {
+                self.pending = Some(prefix);
+                MacroAction::Consumed
+            }
+            _ => MacroAction::Consumed,
+        }
+    }
+
+    fn play(&mut self, register: char) -> MacroAction {
+        match self.registers.get(&register) {
+            Some(keys) if !keys.is_empty() => {
+                self.last_played = Some(register);
+                MacroAction::Replay(keys.clone())
+            }
+            _ => MacroAction::Message(format!("Register @{} is empty", register)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(c: char) -> KeyEvent {
+        KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE)
+    }
+
+    /// Feeds keys the way the session does: recorded first, then handled by
+    /// the recorder if it wants them.
+    fn type_keys(recorder: &mut MacroRecorder, keys: &str) -> Vec<MacroAction> {
+        keys.chars()
+            .map(key)
+            .filter_map(|k| {
+                recorder.record(k);
+                recorder.wants(&k).then(|| recorder.handle_key(k))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn records_until_q_and_replays_the_register() {
+        let mut recorder = MacroRecorder::default();
+
+        type_keys(&mut recorder, "Qa");
+        assert_eq!(recorder.recording(), Some('a'));
+        let actions = type_keys(&mut recorder, "lj.q");
+        assert_eq!(actions.last(), Some(&MacroAction::Message("Recorded @a".to_string())));
+        assert_eq!(recorder.recording(), None);
+
+        assert_eq!(type_keys(&mut recorder, "@a").pop(), Some(MacroAction::Replay(vec![key('l'), key('j'), key('.')])));
+        assert_eq!(type_keys(&mut recorder, "@@").pop(), Some(MacroAction::Replay(vec![key('l'), key('j'), key('.')])));
+    }
+
+    #[test]
+    fn q_is_left_for_quitting_unless_recording() {
+        let mut recorder = MacroRecorder::default();
+        assert!(!recorder.wants(&key('q')));
+
+        type_keys(&mut recorder, "Qq");
+        assert_eq!(recorder.recording(), Some('q'));
+        assert!(recorder.wants(&key('q')));
+        assert_eq!(type_keys(&mut recorder, "q").pop(), Some(MacroAction::Message("Recorded @q".to_string())));
+        assert!(!recorder.wants(&key('q')));
+    }
+
+    #[test]
+    fn empty_registers_say_so() {
+        let mut recorder = MacroRecorder::default();
+
+        assert_eq!(type_keys(&mut recorder, "@b").pop(), Some(MacroAction::Message("Register @b is empty".to_string())));
+        assert_eq!(type_keys(&mut recorder, "@@").pop(), Some(MacroAction::Message("No macro replayed yet".to_string())));
+        assert!(!recorder.wants(&key('j')));
+    }
+}
@@ -2,3 +2,12 @@ pub mod normal_mode;
 pub mod visual_mode;
 pub mod command_mode;
 pub mod insert_mode;
+pub mod command_history;
+pub mod slot_picker;
+pub mod calendar_list;
+pub mod task_pane;
+pub mod text_area;
+pub mod key_script;
+pub mod setup_wizard;
+pub mod macros;
+pub mod registers;
@@ -1,20 +1,32 @@
 use chrono::{Datelike, Days, NaiveDate};
 use crossterm::event::KeyCode;
 
-use crate::app::{AppState, Mode, ViewType};
+use crate::app::{AppState, MessageKind, Mode, ViewType};
+use crate::input::registers::{Register, Registers, UNNAMED};
 
 pub fn handle_key(key: KeyCode, state: &mut AppState) {
+    let month_prefix = std::mem::take(&mut state.year_month_prefix);
+    if take_register_name(key, state) {
+        return;
+    }
+    let register = state.pending_register.take();
     match key {
+        KeyCode::Char('"') => state.awaiting_register = true,
+        KeyCode::Char('y') if register.is_some() => yank_selected_event(state, register),
         KeyCode::Char('h') => move_previous_day(state),
         KeyCode::Char('j') => {
-            if state.view == ViewType::Day || has_events_on_selected_date(state) {
+            if state.view == ViewType::Week {
+                state.scroll_week_grid(1);
+            } else if state.view == ViewType::Day || has_events_on_selected_date(state) {
                 state.move_event_selection_down();
             } else {
                 move_down_week(state);
             }
         }
         KeyCode::Char('k') => {
-            if state.view == ViewType::Day || has_events_on_selected_date(state) {
+            if state.view == ViewType::Week {
+                state.scroll_week_grid(-1);
+            } else if state.view == ViewType::Day || has_events_on_selected_date(state) {
                 state.move_event_selection_up();
             } else {
                 move_up_week(state);
@@ -26,18 +38,29 @@ pub fn handle_key(key: KeyCode, state: &mut AppState) {
         KeyCode::Char('w') => switch_to_week_view(state),
         KeyCode::Char('d') => switch_to_day_view(state),
         KeyCode::Char('y') => switch_to_year_view(state),
+        KeyCode::Char('s') => toggle_split_view(state),
         KeyCode::Char('a') => enter_insert_mode(state),
         KeyCode::Char('E') => enter_edit_mode(state),
         KeyCode::Char('x') => delete_selected_event(state),
+        KeyCode::Char('D') => prompt_duplicate_event(state),
         KeyCode::Char('v') => enter_visual_mode(state),
         KeyCode::Char('i') => open_event_detail_view(state),
         KeyCode::Enter => handle_enter_key(state),
         KeyCode::Char(':') => enter_command_mode(state),
         KeyCode::Char('?') => show_help(state),
+        KeyCode::Char('c') => open_calendar_list(state),
         KeyCode::Char('g') => handle_gg_motion(state),
         KeyCode::Char('G') => move_to_end_of_month(state),
         KeyCode::Char('{') => change_month(state, -1),
         KeyCode::Char('}') => change_month(state, 1),
+        KeyCode::Char('(') => change_month(state, -3),
+        KeyCode::Char(')') => change_month(state, 3),
+        KeyCode::Char(c @ '0'..='9') if state.view == ViewType::Year => jump_to_month_key(state, c, month_prefix),
+        KeyCode::Char('z') if matches!(state.view, ViewType::Day | ViewType::Split) => state.day_grid = state.day_grid.toggled(),
+        KeyCode::Tab => state.cycle_event_selection(1),
+        KeyCode::BackTab => state.cycle_event_selection(-1),
+        KeyCode::PageDown => state.page_event_selection(1),
+        KeyCode::PageUp => state.page_event_selection(-1),
         _ => {}
     }
 }
@@ -46,59 +69,99 @@ fn has_events_on_selected_date(state: &AppState) -> bool {
     !state.get_events_for_date(state.selected_date).is_empty()
 }
 
-fn move_previous_day(state: &mut AppState) {
+pub fn move_previous_day(state: &mut AppState) {
     if let Some(new_date) = state.selected_date.checked_sub_days(Days::new(1)) {
         state.selected_date = new_date;
         state.reset_event_selection();
     }
 }
 
-fn move_next_day(state: &mut AppState) {
+pub fn move_next_day(state: &mut AppState) {
     if let Some(new_date) = state.selected_date.checked_add_days(Days::new(1)) {
         state.selected_date = new_date;
         state.reset_event_selection();
     }
 }
 
-fn move_down_week(state: &mut AppState) {
+pub fn move_down_week(state: &mut AppState) {
     if let Some(new_date) = state.selected_date.checked_add_days(Days::new(7)) {
         state.selected_date = new_date;
     }
 }
 
-fn move_up_week(state: &mut AppState) {
+pub fn move_up_week(state: &mut AppState) {
     if let Some(new_date) = state.selected_date.checked_sub_days(Days::new(7)) {
         state.selected_date = new_date;
     }
 }
 
 fn jump_to_today(state: &mut AppState) {
-    state.selected_date = chrono::Local::now().date_naive();
+    state.selected_date = state.today();
     state.reset_event_selection();
 }
 
 fn enter_edit_mode(state: &mut AppState) {
-    if let Some(event) = state.get_selected_event() {
-        state.event_form = Some(crate::app::EventForm::for_event(event));
+    if let Some(event) = state.get_selected_event().cloned() {
+        if state.refuse_read_only_calendar(&event.calendar_id) {
+            return;
+        }
+        state.event_form = Some(crate::app::EventForm::for_event(&event, &state.timezone));
         state.mode = Mode::Insert;
     }
 }
 
 fn delete_selected_event(state: &mut AppState) {
-    if let Some(event) = state.get_selected_event() {
+    if let Some(event) = state.get_selected_event().cloned() {
+        if state.refuse_read_only_calendar(&event.calendar_id) {
+            return;
+        }
         state.delete_confirmation_event_id = Some(event.id.clone());
         state.mode = Mode::Visual;
     }
 }
 
+/// After `"`, keeps the register name for the next yank. True when `key`
+/// was taken as the name, or cancelled it.
+pub fn take_register_name(key: KeyCode, state: &mut AppState) -> bool {
+    if !std::mem::take(&mut state.awaiting_register) {
+        return false;
+    }
+    if let KeyCode::Char(name) = key
+        && Registers::is_name(name)
+    {
+        state.pending_register = Some(name);
+    }
+    true
+}
+
+fn yank_selected_event(state: &mut AppState, register: Option<char>) {
+    if let Some(event) = state.get_selected_event().cloned() {
+        state.set_message(
+            MessageKind::Info,
+            format!("Yanked \"{}\" into \"{}", event.title, register.unwrap_or(UNNAMED)),
+        );
+        state.registers.yank(register, Register::Event(Box::new(event)));
+    }
+}
+
+/// Opens `:duplicate ` so the copy's date can be typed, or Enter for the same day.
+fn prompt_duplicate_event(state: &mut AppState) {
+    if state.get_selected_event().is_some() {
+        state.mode = Mode::Command;
+        state.command_buffer = ":duplicate ".to_string();
+    }
+}
+
 fn enter_visual_mode(state: &mut AppState) {
     state.visual_selection_start = Some(state.selected_date);
     state.mode = Mode::Visual;
 }
 
-fn open_event_detail_view(state: &mut AppState) {
+pub fn open_event_detail_view(state: &mut AppState) {
     if let Some(event) = state.get_selected_event() {
         state.detail_view_event_id = Some(event.id.clone());
+        state.detail_view_draft = None;
+        state.detail_view_hints = None;
         state.detail_view_scroll = 0;
         state.detail_view_cursor_line = 0;
         state.detail_view_cursor_col = 0;
@@ -107,13 +170,17 @@ fn open_event_detail_view(state: &mut AppState) {
 
 fn handle_enter_key(state: &mut AppState) {
     match state.view {
-        ViewType::Month | ViewType::Week => {
+        ViewType::Week | ViewType::Split if state.get_selected_event().is_some() => {
+            open_event_detail_view(state);
+        }
+        ViewType::Month | ViewType::Week | ViewType::Split => {
             state.view = ViewType::Day;
         }
-        ViewType::Day => {
-            if state.get_selected_event().is_some() {
-                enter_edit_mode(state);
-            }
+        ViewType::Day if state.get_selected_event().is_some() => {
+            enter_edit_mode(state);
+        }
+        ViewType::Year => {
+            state.view = ViewType::Day;
         }
         _ => {}
     }
@@ -135,8 +202,13 @@ fn switch_to_year_view(state: &mut AppState) {
     state.view = ViewType::Year;
 }
 
+/// Back to the month view from the split view.
+fn toggle_split_view(state: &mut AppState) {
+    state.view = if state.view == ViewType::Split { ViewType::Month } else { ViewType::Split };
+}
+
 fn enter_insert_mode(state: &mut AppState) {
-    state.event_form = Some(crate::app::EventForm::new(state.selected_date, String::new()));
+    state.event_form = Some(crate::app::EventForm::new(state.selected_date, String::new(), state.local_now()));
     state.mode = Mode::Insert;
 }
 
@@ -150,6 +222,11 @@ fn show_help(state: &mut AppState) {
     state.command_buffer = ":help".to_string();
 }
 
+fn open_calendar_list(state: &mut AppState) {
+    state.show_calendar_list = true;
+    state.calendar_list_index = 0;
+}
+
 fn handle_gg_motion(state: &mut AppState) {
     let year = state.selected_date.year();
     let month = state.selected_date.month();
@@ -175,7 +252,20 @@ fn move_to_end_of_month(state: &mut AppState) {
     }
 }
 
-fn change_month(state: &mut AppState, offset: i32) {
+/// 1-9 jump to January-September. After a 1, the next 0, 1 or 2 moves on
+/// to October, November or December.
+fn jump_to_month_key(state: &mut AppState, key: char, month_prefix: bool) {
+    let Some(digit) = key.to_digit(10) else { return };
+    let month = match digit {
+        0..=2 if month_prefix => 10 + digit,
+        0 => return,
+        _ => digit,
+    };
+    change_month(state, month as i32 - state.selected_date.month() as i32);
+    state.year_month_prefix = month == 1;
+}
+
+pub fn change_month(state: &mut AppState, offset: i32) {
     let year = state.selected_date.year();
     let month = state.selected_date.month() as i32;
     let day = state.selected_date.day();
@@ -199,11 +289,19 @@ fn change_month(state: &mut AppState, offset: i32) {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_support::EventBuilder;
+    use chrono::{TimeZone, Utc};
+    use crate::util::clock::FixedClock;
 
     fn date(year: i32, month: u32, day: u32) -> NaiveDate {
         NaiveDate::from_ymd_opt(year, month, day).unwrap()
     }
 
+    fn event_on(date: NaiveDate, id: &str) -> crate::calendar::Event {
+        let start = date.and_hms_opt(10, 0, 0).unwrap().and_utc();
+        EventBuilder::new(id).at(start, chrono::Duration::hours(1)).build()
+    }
+
     #[test]
     fn h_key_moves_to_previous_day() {
         let mut state = AppState::new();
@@ -245,13 +343,78 @@ mod tests {
     }
 
     #[test]
-    fn t_key_jumps_to_today() {
+    fn page_keys_move_selection_by_a_pane_and_scroll() {
         let mut state = AppState::new();
+        state.selected_date = date(2025, 1, 15);
+        for i in 0..10 {
+            state.add_event(event_on(date(2025, 1, 15), &format!("e{}", i)));
+        }
+        state.event_list_visible_lines = 6;
+
+        handle_key(KeyCode::PageDown, &mut state);
+        assert_eq!(state.selected_event_index, 3);
+        assert_eq!(state.event_list_scroll, 1);
+
+        handle_key(KeyCode::PageDown, &mut state);
+        handle_key(KeyCode::PageDown, &mut state);
+        handle_key(KeyCode::PageDown, &mut state);
+        assert_eq!(state.selected_event_index, 9);
+        assert_eq!(state.event_list_scroll, 7);
+
+        handle_key(KeyCode::PageUp, &mut state);
+        assert_eq!(state.selected_event_index, 6);
+        assert_eq!(state.event_list_scroll, 6);
+    }
+
+    #[test]
+    fn z_toggles_the_day_grid_in_day_view_only() {
+        let mut state = AppState::new();
+
+        handle_key(KeyCode::Char('z'), &mut state);
+        assert_eq!(state.day_grid, crate::ui::day_view::DayGrid::Compact);
+
+        state.view = ViewType::Day;
+        handle_key(KeyCode::Char('z'), &mut state);
+        assert_eq!(state.day_grid, crate::ui::day_view::DayGrid::Full);
+    }
+
+    #[test]
+    fn j_and_k_scroll_week_grid() {
+        let mut state = AppState::new();
+        state.view = ViewType::Week;
+        state.week_scroll_hour = 8;
+
+        handle_key(KeyCode::Char('j'), &mut state);
+        assert_eq!(state.week_scroll_hour, 9);
+
+        handle_key(KeyCode::Char('k'), &mut state);
+        handle_key(KeyCode::Char('k'), &mut state);
+        assert_eq!(state.week_scroll_hour, 7);
+    }
+
+    #[test]
+    fn week_grid_scroll_is_clamped() {
+        let mut state = AppState::new();
+        state.view = ViewType::Week;
+        state.week_scroll_hour = 0;
+
+        handle_key(KeyCode::Char('k'), &mut state);
+        assert_eq!(state.week_scroll_hour, 0);
+
+        state.week_scroll_hour = crate::ui::week_view::MAX_SCROLL_HOUR;
+        handle_key(KeyCode::Char('j'), &mut state);
+        assert_eq!(state.week_scroll_hour, crate::ui::week_view::MAX_SCROLL_HOUR);
+    }
+
+    #[test]
+    fn t_key_jumps_to_today() {
+        let now = Utc.with_ymd_and_hms(2025, 3, 10, 12, 0, 0).unwrap();
+        let mut state = AppState::new().with_clock(FixedClock(now));
         state.selected_date = date(2025, 1, 1);
 
         handle_key(KeyCode::Char('t'), &mut state);
 
-        assert_eq!(state.selected_date, chrono::Local::now().date_naive());
+        assert_eq!(state.selected_date, date(2025, 3, 10));
     }
 
     #[test]
@@ -347,6 +510,152 @@ mod tests {
         assert_eq!(state.event_form.as_ref().unwrap().date, date(2025, 1, 15));
     }
 
+    #[test]
+    fn enter_in_week_view_opens_selected_event_details() {
+        let mut state = AppState::new();
+        state.view = ViewType::Week;
+        state.selected_date = date(2025, 1, 15);
+        state.add_event(event_on(date(2025, 1, 15), "e1"));
+
+        handle_key(KeyCode::Enter, &mut state);
+
+        assert_eq!(state.detail_view_event_id.as_deref(), Some("e1"));
+        assert_eq!(state.view, ViewType::Week);
+    }
+
+    #[test]
+    fn enter_in_week_view_without_events_opens_day_view() {
+        let mut state = AppState::new();
+        state.view = ViewType::Week;
+        state.selected_date = date(2025, 1, 15);
+
+        handle_key(KeyCode::Enter, &mut state);
+
+        assert!(state.detail_view_event_id.is_none());
+        assert_eq!(state.view, ViewType::Day);
+    }
+
+    #[test]
+    fn shift_d_prompts_for_duplicate_date() {
+        let mut state = AppState::new();
+        state.selected_date = date(2025, 1, 15);
+        state.add_event(event_on(date(2025, 1, 15), "e1"));
+
+        handle_key(KeyCode::Char('D'), &mut state);
+
+        assert_eq!(state.mode, Mode::Command);
+        assert_eq!(state.command_buffer, ":duplicate ");
+    }
+
+    #[test]
+    fn shift_d_without_selected_event_does_nothing() {
+        let mut state = AppState::new();
+
+        handle_key(KeyCode::Char('D'), &mut state);
+
+        assert_eq!(state.mode, Mode::Normal);
+    }
+
+    #[test]
+    fn edit_and_delete_are_refused_on_read_only_calendars() {
+        let mut state = AppState::new();
+        state.selected_date = date(2025, 1, 15);
+        let mut event = event_on(date(2025, 1, 15), "e1");
+        event.calendar_id = "holidays".to_string();
+        state.add_event(event);
+        state.add_calendars(vec![crate::calendar::Calendar {
+            id: "holidays".to_string(),
+            name: "Holidays".to_string(),
+            color: String::new(),
+            is_primary: false,
+            access_role: crate::calendar::AccessRole::Reader,
+        }]);
+
+        handle_key(KeyCode::Char('E'), &mut state);
+        assert_eq!(state.mode, Mode::Normal);
+        assert!(state.event_form.is_none());
+
+        handle_key(KeyCode::Char('x'), &mut state);
+        assert!(state.delete_confirmation_event_id.is_none());
+        assert_eq!(state.status_message.unwrap().text, "holidays is read-only (reader)");
+    }
+
+    #[test]
+    fn tab_cycles_through_the_focused_days_events_in_week_view() {
+        let mut state = AppState::new();
+        state.view = ViewType::Week;
+        state.selected_date = date(2025, 1, 15);
+        state.add_event(event_on(date(2025, 1, 15), "e1"));
+        state.add_event(event_on(date(2025, 1, 15), "e2"));
+
+        handle_key(KeyCode::Tab, &mut state);
+        assert_eq!(state.selected_event_index, 1);
+        handle_key(KeyCode::Tab, &mut state);
+        assert_eq!(state.selected_event_index, 0);
+        handle_key(KeyCode::BackTab, &mut state);
+        assert_eq!(state.selected_event_index, 1);
+
+        let selected = state.get_selected_event().unwrap().id.clone();
+        handle_key(KeyCode::Char('i'), &mut state);
+        assert_eq!(state.detail_view_event_id, Some(selected));
+    }
+
+    #[test]
+    fn number_keys_jump_to_a_month_in_year_view() {
+        let mut state = AppState::new();
+        state.view = ViewType::Year;
+        state.selected_date = date(2025, 5, 31);
+
+        handle_key(KeyCode::Char('6'), &mut state);
+        assert_eq!(state.selected_date, date(2025, 6, 30));
+
+        handle_key(KeyCode::Char('1'), &mut state);
+        assert_eq!(state.selected_date, date(2025, 1, 30));
+        handle_key(KeyCode::Char('2'), &mut state);
+        assert_eq!(state.selected_date, date(2025, 12, 30));
+
+        handle_key(KeyCode::Char('2'), &mut state);
+        assert_eq!(state.selected_date, date(2025, 2, 28));
+    }
+
+    #[test]
+    fn parentheses_move_by_quarter() {
+        let mut state = AppState::new();
+        state.selected_date = date(2025, 11, 15);
+
+        handle_key(KeyCode::Char(')'), &mut state);
+        assert_eq!(state.selected_date, date(2026, 2, 15));
+
+        handle_key(KeyCode::Char('('), &mut state);
+        handle_key(KeyCode::Char('('), &mut state);
+        assert_eq!(state.selected_date, date(2025, 8, 15));
+    }
+
+    #[test]
+    fn enter_in_year_view_opens_the_day() {
+        let mut state = AppState::new();
+        state.view = ViewType::Year;
+
+        handle_key(KeyCode::Enter, &mut state);
+
+        assert_eq!(state.view, ViewType::Day);
+    }
+
+    #[test]
+    fn s_toggles_split_view_which_navigates_like_month_view() {
+        let mut state = AppState::new();
+        state.selected_date = date(2025, 1, 15);
+
+        handle_key(KeyCode::Char('s'), &mut state);
+        assert_eq!(state.view, ViewType::Split);
+
+        handle_key(KeyCode::Char('j'), &mut state);
+        assert_eq!(state.selected_date, date(2025, 1, 22));
+
+        handle_key(KeyCode::Char('s'), &mut state);
+        assert_eq!(state.view, ViewType::Month);
+    }
+
     #[test]
     fn colon_enters_command_mode() {
         let mut state = AppState::new();
@@ -357,4 +666,22 @@ mod tests {
         assert_eq!(state.mode, Mode::Command);
         assert_eq!(state.command_buffer, ":");
     }
+
+    #[test]
+    fn quote_register_y_yanks_the_selected_event() {
+        let mut state = AppState::new();
+        state.selected_date = date(2025, 1, 15);
+        state.add_event(event_on(date(2025, 1, 15), "standup"));
+
+        for key in ['"', 'a', 'y'] {
+            handle_key(KeyCode::Char(key), &mut state);
+        }
+
+        assert_eq!(state.view, ViewType::Month);
+        assert!(matches!(state.registers.get('a'), Some(Register::Event(event)) if event.id == "standup"));
+        assert!(state.registers.get(UNNAMED).is_some());
+
+        handle_key(KeyCode::Char('y'), &mut state);
+        assert_eq!(state.view, ViewType::Year);
+    }
 }
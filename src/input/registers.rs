@@ -0,0 +1,122 @@
+use std::collections::BTreeMap;
+
+use crate::app::{EventForm, FormField};
+use crate::calendar::{DisplayTimezone, Event};
+
+/// Holds the last yank, whichever register it was also stored in.
+pub const UNNAMED: char = '"';
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Register {
+    /// Text yanked from the detail view.
+    Text(String),
+    /// A whole event yanked from the calendar views.
+    Event(Box<Event>),
+}
+
+impl Register {
+    /// What pasting into `field` inserts: text as it is, or the event's own
+    /// value for that field (its title into the title, and so on).
+    pub fn text_for(&self, field: &FormField, tz: &DisplayTimezone) -> Option<String> {
+        match self {
+            Self::Text(text) => Some(text.clone()),
+            Self::Event(event) => {
+                let mut form = EventForm::for_event(event, tz);
+                form.active_field = field.clone();
+                form.active_text_mut().map(|(text, _)| text.clone())
+            }
+        }
+    }
+
+    /// One line for `:registers`.
+    pub fn preview(&self, tz: &DisplayTimezone) -> String {
+        match self {
+            Self::Text(text) => {
+                let first = text.lines().next().unwrap_or_default();
+                let more = text.lines().count().saturating_sub(1);
+                if more > 0 { format!("{} (+{} lines)", first, more) } else { first.to_string() }
+            }
+            Self::Event(event) => {
+                let start = event.local_start(tz);
+                let when = if event.all_day { start.format("%a %b %d") } else { start.format("%a %b %d %H:%M") };
+                format!("event: {} · {}", event.title, when)
+            }
+        }
+    }
+}
+
+/// Vim-style registers: `"` plus the named `a`-`z`.
+#[derive(Debug, Clone, Default)]
+pub struct Registers {
+    slots: BTreeMap<char, Register>,
+}
+
+impl Registers {
+    pub fn is_name(c: char) -> bool {
+        c == UNNAMED || c.is_ascii_lowercase()
+    }
+
+    /// Stores a yank in `name` (if any) and always in `"`.
+    pub fn yank(&mut self, name: Option<char>, content: Register) {
+        if let Some(name) = name.filter(|&name| name != UNNAMED) {
+            self.slots.insert(name, content.clone());
+        }
+        self.slots.insert(UNNAMED, content);
+    }
+
+    pub fn get(&self, name: char) -> Option<&Register> {
+        self.slots.get(&name)
+    }
+
+    /// Filled registers, `"` first and the rest alphabetically.
+    pub fn iter(&self) -> impl Iterator<Item = (char, &Register)> {
+        self.slots.iter().map(|(name, content)| (*name, content))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.slots.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::EventBuilder;
+    use chrono::{Duration, TimeZone, Utc};
+
+    fn event() -> Event {
+        let start = Utc.with_ymd_and_hms(2025, 1, 13, 9, 0, 0).unwrap();
+        EventBuilder::new("standup")
+            .title("Standup")
+            .description("Notes")
+            .location("Room 4")
+            .at(start, Duration::minutes(15))
+            .build()
+    }
+
+    #[test]
+    fn yanks_land_in_the_named_and_unnamed_registers() {
+        let mut registers = Registers::default();
+
+        registers.yank(Some('a'), Register::Text("zoom.us/j/1".to_string()));
+        registers.yank(None, Register::Text("agenda".to_string()));
+
+        assert_eq!(registers.get('a'), Some(&Register::Text("zoom.us/j/1".to_string())));
+        assert_eq!(registers.get(UNNAMED), Some(&Register::Text("agenda".to_string())));
+        assert_eq!(registers.iter().map(|(name, _)| name).collect::<Vec<_>>(), vec!['"', 'a']);
+        assert!(!Registers::is_name('A'));
+    }
+
+    #[test]
+    fn events_paste_the_value_of_the_matching_field() {
+        let tz = DisplayTimezone::Named(chrono_tz::UTC);
+        let register = Register::Event(Box::new(event()));
+
+        assert_eq!(register.text_for(&FormField::Title, &tz).as_deref(), Some("Standup"));
+        assert_eq!(register.text_for(&FormField::Location, &tz).as_deref(), Some("Room 4"));
+        assert_eq!(register.text_for(&FormField::StartTime, &tz).as_deref(), Some("09:00"));
+        assert_eq!(register.text_for(&FormField::AllDay, &tz), None);
+        assert_eq!(register.preview(&tz), "event: Standup · Mon Jan 13 09:00");
+        assert_eq!(Register::Text("a\nb\nc".to_string()).preview(&tz), "a (+2 lines)");
+    }
+}
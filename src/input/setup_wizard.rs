@@ -0,0 +1,254 @@
+use crossterm::event::KeyCode;
+
+use crate::storage::config::Config;
+use crate::sync::mock_api::ApiBackend;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum WizardStep {
+    Provider,
+    ClientId,
+    ClientSecret,
+    /// Waiting for the code from the Google consent page.
+    AuthCode,
+    Calendars,
+}
+
+/// What the caller has to do after a key.
+#[derive(Debug, Clone, PartialEq)]
+pub enum WizardAction {
+    None,
+    Cancel,
+    /// Exchange `auth_code` for a token, then call `set_calendars`.
+    ExchangeCode,
+    /// Write the result with `apply` and start.
+    Finish,
+}
+
+/// First-run setup: pick a provider, enter OAuth credentials, sign in and
+/// choose calendars. The caller runs the network steps and writes the config.
+#[derive(Debug, Clone)]
+pub struct SetupWizard {
+    pub step: WizardStep,
+    pub provider: ApiBackend,
+    pub client_id: String,
+    pub client_secret: String,
+    pub auth_code: String,
+    pub calendars: Vec<String>,
+    /// Parallel to `calendars`.
+    pub visible: Vec<bool>,
+    pub calendar_index: usize,
+    /// The last failure, shown until the next key.
+    pub error: Option<String>,
+}
+
+impl Default for SetupWizard {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SetupWizard {
+    pub const PROVIDERS: &[ApiBackend] = &[ApiBackend::Google, ApiBackend::Local];
+
+    pub fn new() -> Self {
+        Self {
+            step: WizardStep::Provider,
+            provider: ApiBackend::Google,
+            client_id: String::new(),
+            client_secret: String::new(),
+            auth_code: String::new(),
+            calendars: Vec::new(),
+            visible: Vec::new(),
+            calendar_index: 0,
+            error: None,
+        }
+    }
+
+    /// Starts from credentials already in the config, if any.
+    pub fn from_config(config: &Config) -> Self {
+        Self {
+            client_id: config.google.client_id.clone(),
+            client_secret: config.google.client_secret.clone(),
+            ..Self::new()
+        }
+    }
+
+    pub fn handle_key(&mut self, key: KeyCode) -> WizardAction {
+        self.error = None;
+        match self.step {
+            WizardStep::Provider => match key {
+                KeyCode::Char('j') | KeyCode::Down | KeyCode::Char('k') | KeyCode::Up => {
+                    self.provider = match self.provider {
+                        ApiBackend::Google => ApiBackend::Local,
+                        _ => ApiBackend::Google,
+                    };
+                }
+                KeyCode::Enter => match self.provider {
+                    ApiBackend::Google => self.step = WizardStep::ClientId,
+                    _ => return WizardAction::Finish,
+                },
+                KeyCode::Esc | KeyCode::Char('q') => return WizardAction::Cancel,
+                _ => {}
+            },
+            WizardStep::ClientId => {
+                if let Some(done) = edit_line(&mut self.client_id, key) {
+                    self.step = if done { WizardStep::ClientSecret } else { WizardStep::Provider };
+                }
+            }
+            WizardStep::ClientSecret => {
+                if let Some(done) = edit_line(&mut self.client_secret, key) {
+                    self.step = if done { WizardStep::AuthCode } else { WizardStep::ClientId };
+                }
+            }
+            WizardStep::AuthCode => match edit_line(&mut self.auth_code, key) {
+                Some(true) => return WizardAction::ExchangeCode,
+                Some(false) => self.step = WizardStep::ClientSecret,
+                None => {}
+            },
+            WizardStep::Calendars => match key {
+                KeyCode::Char('j') | KeyCode::Down if self.calendar_index + 1 < self.calendars.len() => {
+                    self.calendar_index += 1;
+                }
+                KeyCode::Char('k') | KeyCode::Up => {
+                    self.calendar_index = self.calendar_index.saturating_sub(1);
+                }
+                KeyCode::Char(' ') => {
+                    if let Some(visible) = self.visible.get_mut(self.calendar_index) {
+                        *visible = !*visible;
+                    }
+                }
+                KeyCode::Enter => return WizardAction::Finish,
+                KeyCode::Esc => self.step = WizardStep::AuthCode,
+                _ => {}
+            },
+        }
+        WizardAction::None
+    }
+
+    /// Moves on to choosing calendars, with all of them shown and the
+    /// first one, Google's primary, highlighted.
+    pub fn set_calendars(&mut self, calendars: Vec<String>) {
+        self.visible = vec![true; calendars.len()];
+        self.calendars = calendars;
+        self.calendar_index = 0;
+        self.step = WizardStep::Calendars;
+    }
+
+    /// The code as typed, or taken from a pasted `localhost:8080/?code=...` URL.
+    pub fn code(&self) -> String {
+        let input = self.auth_code.trim();
+        match input.split_once("code=") {
+            Some((_, rest)) => {
+                let code = rest.split('&').next().unwrap_or(rest);
+                urlencoding::decode(code).map(|code| code.into_owned()).unwrap_or_else(|_| code.to_string())
+            }
+            None => input.to_string(),
+        }
+    }
+
+    /// Writes the choices into `config`. The highlighted calendar becomes
+    /// the default and is always visible.
+    pub fn apply(&self, config: &mut Config) {
+        if self.provider == ApiBackend::Local {
+            config.sync.backend = Some("local".to_string());
+            return;
+        }
+        config.sync.backend = None;
+        config.google.client_id = self.client_id.trim().to_string();
+        config.google.client_secret = self.client_secret.trim().to_string();
+        if let Some(default) = self.calendars.get(self.calendar_index) {
+            config.calendars.default = default.clone();
+            config.calendars.visible = self.calendars.iter()
+                .zip(&self.visible)
+                .filter(|(calendar, visible)| **visible || *calendar == default)
+                .map(|(calendar, _)| calendar.clone())
+                .collect();
+        }
+    }
+}
+
+/// Some(true) on Enter with text, Some(false) on Esc, None while typing.
+fn edit_line(line: &mut String, key: KeyCode) -> Option<bool> {
+    match key {
+        KeyCode::Enter if !line.trim().is_empty() => return Some(true),
+        KeyCode::Esc => return Some(false),
+        KeyCode::Backspace => {
+            line.pop();
+        }
+        KeyCode::Char(c) => line.push(c),
+        _ => {}
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn type_text(wizard: &mut SetupWizard, text: &str) {
+        for c in text.chars() {
+            wizard.handle_key(KeyCode::Char(c));
+        }
+    }
+
+    #[test]
+    fn google_setup_collects_credentials_then_calendars() {
+        let mut wizard = SetupWizard::new();
+
+        wizard.handle_key(KeyCode::Enter);
+        type_text(&mut wizard, "id.apps");
+        wizard.handle_key(KeyCode::Enter);
+        type_text(&mut wizard, "secret");
+        wizard.handle_key(KeyCode::Enter);
+        type_text(&mut wizard, "4/code");
+        assert_eq!(wizard.handle_key(KeyCode::Enter), WizardAction::ExchangeCode);
+
+        wizard.set_calendars(vec!["me@example.com".to_string(), "work".to_string(), "team".to_string()]);
+        wizard.handle_key(KeyCode::Char('j'));
+        wizard.handle_key(KeyCode::Char('j'));
+        wizard.handle_key(KeyCode::Char(' '));
+        assert_eq!(wizard.handle_key(KeyCode::Enter), WizardAction::Finish);
+
+        let mut config = Config::default();
+        wizard.apply(&mut config);
+        assert_eq!(config.google.client_id, "id.apps");
+        assert_eq!(config.google.client_secret, "secret");
+        assert_eq!(config.calendars.default, "team");
+        assert_eq!(config.calendars.visible, vec!["me@example.com", "work", "team"]);
+    }
+
+    #[test]
+    fn code_can_be_pasted_as_the_redirect_url() {
+        let mut wizard = SetupWizard::new();
+        wizard.auth_code = "http://localhost:8080/?code=4%2F0Abc&scope=calendar".to_string();
+        assert_eq!(wizard.code(), "4/0Abc");
+
+        wizard.auth_code = " 4/0Abc ".to_string();
+        assert_eq!(wizard.code(), "4/0Abc");
+    }
+
+    #[test]
+    fn local_provider_finishes_straight_away() {
+        let mut wizard = SetupWizard::new();
+
+        wizard.handle_key(KeyCode::Char('j'));
+        assert_eq!(wizard.handle_key(KeyCode::Enter), WizardAction::Finish);
+
+        let mut config = Config::default();
+        wizard.apply(&mut config);
+        assert_eq!(config.sync.backend.as_deref(), Some("local"));
+    }
+
+    #[test]
+    fn esc_steps_back_and_empty_fields_do_not_advance() {
+        let mut wizard = SetupWizard::new();
+        wizard.handle_key(KeyCode::Enter);
+
+        wizard.handle_key(KeyCode::Enter);
+        assert_eq!(wizard.step, WizardStep::ClientId);
+
+        wizard.handle_key(KeyCode::Esc);
+        assert_eq!(wizard.step, WizardStep::Provider);
+        assert_eq!(wizard.handle_key(KeyCode::Esc), WizardAction::Cancel);
+    }
+}
@@ -0,0 +1,85 @@
+use crossterm::event::KeyCode;
+
+use crate::app::{AppState, EventForm, Mode};
+
+/// Keys while the `:free` results list is open; Enter opens a form for the
+/// highlighted slot.
+pub fn handle_key(key: KeyCode, state: &mut AppState) {
+    match key {
+        KeyCode::Char('j') | KeyCode::Down if state.free_slot_index + 1 < state.free_slots.len() => {
+            state.free_slot_index += 1;
+        }
+        KeyCode::Char('k') | KeyCode::Up => {
+            state.free_slot_index = state.free_slot_index.saturating_sub(1);
+        }
+        KeyCode::Enter => {
+            if let Some(slot) = state.free_slots.get(state.free_slot_index) {
+                state.event_form = Some(EventForm::for_slot(slot.start, state.free_slot_minutes));
+                state.mode = Mode::Insert;
+            }
+            close(state);
+        }
+        KeyCode::Char('q') | KeyCode::Esc => close(state),
+        _ => {}
+    }
+}
+
+fn close(state: &mut AppState) {
+    state.free_slots.clear();
+    state.free_slot_index = 0;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::calendar::free_slots::FreeSlot;
+    use chrono::NaiveDate;
+
+    fn state_with_slots() -> AppState {
+        let at = |hour| NaiveDate::from_ymd_opt(2025, 1, 15).unwrap().and_hms_opt(hour, 0, 0).unwrap();
+        let mut state = AppState::new();
+        state.free_slots = vec![
+            FreeSlot { start: at(9), end: at(11) },
+            FreeSlot { start: at(14), end: at(17) },
+        ];
+        state.free_slot_minutes = 45;
+        state
+    }
+
+    #[test]
+    fn j_and_k_stay_within_the_list() {
+        let mut state = state_with_slots();
+
+        handle_key(KeyCode::Char('j'), &mut state);
+        handle_key(KeyCode::Char('j'), &mut state);
+        assert_eq!(state.free_slot_index, 1);
+
+        handle_key(KeyCode::Char('k'), &mut state);
+        handle_key(KeyCode::Char('k'), &mut state);
+        assert_eq!(state.free_slot_index, 0);
+    }
+
+    #[test]
+    fn enter_opens_prefilled_form() {
+        let mut state = state_with_slots();
+
+        handle_key(KeyCode::Char('j'), &mut state);
+        handle_key(KeyCode::Enter, &mut state);
+
+        let form = state.event_form.as_ref().unwrap();
+        assert_eq!(state.mode, Mode::Insert);
+        assert_eq!((form.start_hour, form.start_minute), (14, 0));
+        assert_eq!(form.duration_minutes, 45);
+        assert!(state.free_slots.is_empty());
+    }
+
+    #[test]
+    fn esc_closes_without_form() {
+        let mut state = state_with_slots();
+
+        handle_key(KeyCode::Esc, &mut state);
+
+        assert!(state.free_slots.is_empty());
+        assert!(state.event_form.is_none());
+    }
+}
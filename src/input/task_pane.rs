@@ -0,0 +1,63 @@
+use crossterm::event::KeyCode;
+
+use crate::app::AppState;
+
+/// Keys while the tasks pane has focus. Returns true when the highlighted
+/// task should be marked done.
+pub fn handle_key(key: KeyCode, state: &mut AppState) -> bool {
+    match key {
+        KeyCode::Char('j') | KeyCode::Down if state.task_index + 1 < state.tasks.len() => {
+            state.task_index += 1;
+        }
+        KeyCode::Char('k') | KeyCode::Up => {
+            state.task_index = state.task_index.saturating_sub(1);
+        }
+        KeyCode::Char('x') | KeyCode::Char(' ') => return state.selected_task().is_some(),
+        KeyCode::Char('T') | KeyCode::Char('q') | KeyCode::Esc => {
+            state.show_tasks = false;
+            state.task_index = 0;
+        }
+        _ => {}
+    }
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sync::google_tasks::Task;
+
+    fn state_with_tasks() -> AppState {
+        let task = |id: &str| Task {
+            id: id.to_string(),
+            list_id: "list".to_string(),
+            title: id.to_string(),
+            due: None,
+        };
+        let mut state = AppState::new();
+        state.tasks = vec![task("a"), task("b")];
+        state.show_tasks = true;
+        state
+    }
+
+    #[test]
+    fn x_requests_completion_of_the_highlighted_task() {
+        let mut state = state_with_tasks();
+
+        handle_key(KeyCode::Char('j'), &mut state);
+        assert!(handle_key(KeyCode::Char('x'), &mut state));
+        assert_eq!(state.selected_task().map(|t| t.id.as_str()), Some("b"));
+
+        state.remove_task("b");
+        assert_eq!(state.task_index, 0);
+    }
+
+    #[test]
+    fn t_closes_the_pane() {
+        let mut state = state_with_tasks();
+
+        handle_key(KeyCode::Char('T'), &mut state);
+
+        assert!(!state.show_tasks);
+    }
+}
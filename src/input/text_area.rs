@@ -0,0 +1,186 @@
+/// Editing helpers for a multi-line field; `cursor` is a char offset into
+/// `text` and always lands on a char boundary.
+pub fn insert_char(text: &mut String, cursor: &mut usize, c: char) {
+    let at = byte_offset(text, *cursor);
+    text.insert(at, c);
+    *cursor += 1;
+}
+
+pub fn backspace(text: &mut String, cursor: &mut usize) {
+    if *cursor == 0 {
+        return;
+    }
+    *cursor -= 1;
+    let at = byte_offset(text, *cursor);
+    text.remove(at);
+}
+
+pub fn move_left(cursor: &mut usize) {
+    *cursor = cursor.saturating_sub(1);
+}
+
+pub fn move_right(text: &str, cursor: &mut usize) {
+    *cursor = (*cursor + 1).min(text.chars().count());
+}
+
+/// Moves to the same column on the previous line, or the line's end when
+/// it is shorter.
+pub fn move_up(text: &str, cursor: &mut usize) {
+    let (line, col) = line_and_column(text, *cursor);
+    if line > 0 {
+        *cursor = offset_of(text, line - 1, col);
+    }
+}
+
+pub fn move_down(text: &str, cursor: &mut usize) {
+    let (line, col) = line_and_column(text, *cursor);
+    if line + 1 < text.split('\n').count() {
+        *cursor = offset_of(text, line + 1, col);
+    }
+}
+
+pub fn move_home(text: &str, cursor: &mut usize) {
+    let (_, col) = line_and_column(text, *cursor);
+    *cursor -= col;
+}
+
+pub fn move_end(text: &str, cursor: &mut usize) {
+    let (line, _) = line_and_column(text, *cursor);
+    *cursor = offset_of(text, line, usize::MAX);
+}
+
+/// Back to the start of the previous word, skipping any spaces before the cursor.
+pub fn word_left(text: &str, cursor: &mut usize) {
+    let chars: Vec<char> = text.chars().collect();
+    let mut at = (*cursor).min(chars.len());
+    while at > 0 && chars[at - 1].is_whitespace() {
+        at -= 1;
+    }
+    while at > 0 && !chars[at - 1].is_whitespace() {
+        at -= 1;
+    }
+    *cursor = at;
+}
+
+/// On to the start of the next word.
+pub fn word_right(text: &str, cursor: &mut usize) {
+    let chars: Vec<char> = text.chars().collect();
+    let mut at = *cursor;
+    while at < chars.len() && !chars[at].is_whitespace() {
+        at += 1;
+    }
+    while at < chars.len() && chars[at].is_whitespace() {
+        at += 1;
+    }
+    *cursor = at;
+}
+
+pub fn delete_word_back(text: &mut String, cursor: &mut usize) {
+    let end = *cursor;
+    word_left(text, cursor);
+    let (from, to) = (byte_offset(text, *cursor), byte_offset(text, end));
+    text.replace_range(from..to, "");
+}
+
+/// Zero-based line and column of the cursor.
+pub fn line_and_column(text: &str, cursor: usize) -> (usize, usize) {
+    let before: String = text.chars().take(cursor).collect();
+    let line = before.matches('\n').count();
+    let col = before.rsplit('\n').next().map_or(0, |l| l.chars().count());
+    (line, col)
+}
+
+fn offset_of(text: &str, line: usize, col: usize) -> usize {
+    let lines: Vec<&str> = text.split('\n').collect();
+    let preceding: usize = lines.iter().take(line).map(|l| l.chars().count() + 1).sum();
+    preceding + col.min(lines.get(line).map_or(0, |l| l.chars().count()))
+}
+
+fn byte_offset(text: &str, cursor: usize) -> usize {
+    text.char_indices().nth(cursor).map_or(text.len(), |(i, _)| i)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn inserts_and_deletes_at_the_cursor() {
+        let mut text = "ac".to_string();
+        let mut cursor = 1;
+
+        insert_char(&mut text, &mut cursor, 'b');
+        insert_char(&mut text, &mut cursor, '\n');
+        assert_eq!(text, "ab\nc");
+        assert_eq!(cursor, 3);
+
+        backspace(&mut text, &mut cursor);
+        backspace(&mut text, &mut cursor);
+        assert_eq!(text, "ac");
+        assert_eq!(cursor, 1);
+    }
+
+    #[test]
+    fn handles_multibyte_chars() {
+        let mut text = "café".to_string();
+        let mut cursor = 4;
+
+        backspace(&mut text, &mut cursor);
+        insert_char(&mut text, &mut cursor, 'é');
+        assert_eq!(text, "café");
+    }
+
+    #[test]
+    fn up_and_down_keep_the_column_where_they_can() {
+        let text = "agenda\nab\nnotes";
+        let mut cursor = 5;
+
+        move_down(text, &mut cursor);
+        assert_eq!(line_and_column(text, cursor), (1, 2));
+        move_down(text, &mut cursor);
+        assert_eq!(line_and_column(text, cursor), (2, 2));
+        move_down(text, &mut cursor);
+        assert_eq!(line_and_column(text, cursor), (2, 2));
+        move_up(text, &mut cursor);
+        move_up(text, &mut cursor);
+        assert_eq!(line_and_column(text, cursor), (0, 2));
+    }
+
+    #[test]
+    fn left_and_right_stay_in_bounds() {
+        let mut cursor = 0;
+        move_left(&mut cursor);
+        assert_eq!(cursor, 0);
+
+        cursor = 2;
+        move_right("ab", &mut cursor);
+        assert_eq!(cursor, 2);
+    }
+
+    #[test]
+    fn home_and_end_stay_on_the_line() {
+        let text = "ab\ncdef";
+        let mut cursor = 5;
+
+        move_home(text, &mut cursor);
+        assert_eq!(cursor, 3);
+        move_end(text, &mut cursor);
+        assert_eq!(cursor, 7);
+    }
+
+    #[test]
+    fn word_motions_and_deletion() {
+        let mut text = "team  sync notes".to_string();
+        let mut cursor = 11;
+
+        word_left(&text, &mut cursor);
+        assert_eq!(cursor, 6);
+        word_right(&text, &mut cursor);
+        assert_eq!(cursor, 11);
+
+        cursor = 10;
+        delete_word_back(&mut text, &mut cursor);
+        assert_eq!(text, "team   notes");
+        assert_eq!(cursor, 6);
+    }
+}
@@ -35,7 +35,7 @@ pub fn handle_key(key: KeyCode, state: &mut AppState) {
                 };
 
                 let form = if duration_days == 1 {
-                    EventForm::new(start, title)
+                    EventForm::new(start, title, state.local_now())
                 } else {
                     EventForm::new_all_day(start, title, duration_days)
                 };
@@ -45,6 +45,10 @@ pub fn handle_key(key: KeyCode, state: &mut AppState) {
                 state.mode = Mode::Insert;
             }
         }
+        KeyCode::Char(':') => {
+            state.mode = Mode::Command;
+            state.command_buffer = ":".to_string();
+        }
         KeyCode::Esc => {
             state.visual_selection_start = None;
             state.mode = Mode::Normal;
@@ -107,6 +111,17 @@ mod tests {
         assert!(state.event_form.is_some());
         let form = state.event_form.as_ref().unwrap();
         assert_eq!(form.duration_minutes, 3 * 24 * 60);
-        assert_eq!(form.all_day, true);
+        assert!(form.all_day);
+    }
+
+    #[test]
+    fn colon_enters_command_mode_keeping_selection() {
+        let mut state = setup_visual_state();
+
+        handle_key(KeyCode::Char(':'), &mut state);
+
+        assert_eq!(state.mode, Mode::Command);
+        assert_eq!(state.command_buffer, ":");
+        assert!(state.visual_selection_start.is_some());
     }
 }
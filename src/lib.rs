@@ -4,8 +4,15 @@ pub mod ui;
 pub mod sync;
 pub mod storage;
 pub mod app;
+pub mod agenda;
+pub mod report;
+pub mod util;
+pub mod error;
+#[cfg(test)]
+pub(crate) mod test_support;
 
 pub use calendar::{Event, EventStatus};
-pub use app::{AppState, Mode, ViewType, SyncStatus};
+pub use app::{AppState, Mode, ViewType};
+pub use sync::sync_state::{SyncPhase, SyncState};
 
 pub use input::{normal_mode, command_mode};
@@ -1,60 +1,175 @@
 use std::io;
 
+use gcal_imp::{storage::{config::Config, paths}, util::logging::{self, LogDestination}};
+use tracing_appender::{non_blocking::WorkerGuard, rolling::{RollingFileAppender, Rotation}};
+use tracing_subscriber::EnvFilter;
+
 mod cli;
-use cli::{CliMode, parse_cli_mode, run_agenda_mode};
+use cli::{CliMode, parse_cli_mode, print_completions, run_agenda_mode, run_dmenu_mode, run_export_mode, run_export_view_mode, run_notify_daemon, run_quick_add, run_report_mode, run_sync_mode};
 mod tui;
 use tui::{run_tui, check_or_setup_auth};
 
 #[tokio::main]
 async fn main() -> Result<(), io::Error> {
-    setup_logging();
+    // Parsed first so `--help` and `completions` do not create config.toml.
+    let cli_mode = parse_cli_mode();
+    if let CliMode::Completions(shell) = cli_mode {
+        if let Err(e) = print_completions(shell) {
+            eprintln!("Error: {}", e.message());
+            std::process::exit(e.exit_code());
+        }
+        return Ok(());
+    }
 
-    let cli_mode = match parse_cli_mode() {
-        Ok(mode) => mode,
-        Err(err) => {
-            eprintln!("Error: {}", err);
-            println!("Usage: gcal-imp [--agenda [YYYY/MM/DD]]");
-            return Ok(());
+    let config = Config::load_or_create().unwrap_or_default();
+    let migrated = paths::migrate_legacy_files(&paths::config_dir(), &config.cache_path(), &config.log_dir());
+    let log_guard = setup_logging(&config, matches!(cli_mode, CliMode::Default { .. }));
+    match migrated {
+        Ok(moved) => {
+            for path in moved {
+                tracing::info!("Moved {} to its new location", path.display());
+            }
         }
-    };
+        Err(e) => tracing::warn!("Failed to move files out of {}: {}", paths::config_dir().display(), e),
+    }
 
-    match cli_mode {
-        CliMode::AgendaDate(date) => {
+    let result = match cli_mode {
+        CliMode::Agenda { start, end, format, pager } => {
+            if let Err(e) = check_or_setup_auth().await {
+                eprintln!("Authentication error: {}", e);
+                tracing::error!("Authentication failed: {}", e);
+                return Ok(());
+            }
+            run_agenda_mode(start, end, format, pager).await
+        }
+        CliMode::Export { start, end, output } => {
+            if let Err(e) = check_or_setup_auth().await {
+                eprintln!("Authentication error: {}", e);
+                tracing::error!("Authentication failed: {}", e);
+                return Ok(());
+            }
+            run_export_mode(start, end, output).await
+        }
+        // No interactive sign-in: this runs from cron.
+        CliMode::Sync { quiet } => match run_sync_mode(quiet).await {
+            Ok(0) => Ok(()),
+            Ok(code) => {
+                drop(log_guard);
+                std::process::exit(code);
+            }
+            Err(e) => Err(e),
+        },
+        CliMode::Report { start, end, format } => {
+            if let Err(e) = check_or_setup_auth().await {
+                eprintln!("Authentication error: {}", e);
+                tracing::error!("Authentication failed: {}", e);
+                return Ok(());
+            }
+            run_report_mode(start, end, format).await
+        }
+        CliMode::ExportView { view, date, format, output } => {
             if let Err(e) = check_or_setup_auth().await {
                 eprintln!("Authentication error: {}", e);
                 tracing::error!("Authentication failed: {}", e);
                 return Ok(());
             }
-            run_agenda_mode(date).await
+            run_export_view_mode(view, date, format, output).await
         }
-        CliMode::Default { sample } => {
+        CliMode::Dmenu => {
             if let Err(e) = check_or_setup_auth().await {
                 eprintln!("Authentication error: {}", e);
                 tracing::error!("Authentication failed: {}", e);
                 return Ok(());
             }
-            run_tui(sample).await
+            run_dmenu_mode().await
         }
+        CliMode::QuickAdd(text) => {
+            if let Err(e) = check_or_setup_auth().await {
+                eprintln!("Authentication error: {}", e);
+                tracing::error!("Authentication failed: {}", e);
+                return Ok(());
+            }
+            run_quick_add(&text).await
+        }
+        CliMode::NotifyDaemon => {
+            if let Err(e) = check_or_setup_auth().await {
+                eprintln!("Authentication error: {}", e);
+                tracing::error!("Authentication failed: {}", e);
+                return Ok(());
+            }
+            run_notify_daemon().await
+        }
+        CliMode::Completions(_) => Ok(()),
+        CliMode::Default { sample, read_only, mock, script } => {
+            if !mock && let Err(e) = check_or_setup_auth().await {
+                eprintln!("Authentication error: {}", e);
+                tracing::error!("Authentication failed: {}", e);
+                return Ok(());
+            }
+            run_tui(sample, read_only, mock, script).await
+        }
+    };
+
+    if let Err(e) = result {
+        tracing::error!("{}", e);
+        eprintln!("Error: {}", e.message());
+        if let Some(hint) = e.hint() {
+            eprintln!("{}", hint);
+        }
+        drop(log_guard);
+        std::process::exit(e.exit_code());
     }
+    Ok(())
 }
 
-fn setup_logging() {
-    let log_dir = dirs::config_dir()
-        .map(|d| d.join("gcal-imp"))
-        .unwrap_or_else(|| std::path::PathBuf::from("."));
-
-    std::fs::create_dir_all(&log_dir).ok();
+/// Holding the returned guard keeps the file writer flushing until exit.
+fn setup_logging(config: &Config, runs_tui: bool) -> Option<WorkerGuard> {
+    let logging = &config.logging;
+    let filter = EnvFilter::try_from_default_env()
+        .or_else(|_| EnvFilter::try_new(&logging.level))
+        .unwrap_or_else(|e| {
+            eprintln!("Invalid logging.level '{}': {}", logging.level, e);
+            EnvFilter::new("info")
+        });
 
-    let file_appender = tracing_appender::rolling::daily(log_dir, "gcal-imp.log");
-    let (non_blocking, _guard) = tracing_appender::non_blocking(file_appender);
+    let guard = match LogDestination::from_config(logging.destination.as_deref()).for_mode(runs_tui) {
+        LogDestination::Stderr => {
+            tracing_subscriber::fmt()
+                .with_env_filter(filter)
+                .with_writer(io::stderr)
+                .with_target(false)
+                .init();
+            None
+        }
+        LogDestination::File => {
+            let log_dir = config.log_dir();
+            std::fs::create_dir_all(&log_dir).ok();
 
-    tracing_subscriber::fmt()
-        .with_writer(non_blocking)
-        .with_ansi(false)
-        .with_target(false)
-        .init();
+            let mut builder = RollingFileAppender::builder()
+                .rotation(Rotation::DAILY)
+                .filename_prefix(logging::LOG_FILE_PREFIX);
+            if logging.retention_days > 0 {
+                builder = builder.max_log_files(logging.retention_days as usize);
+            }
+            let file_appender = match builder.build(&log_dir) {
+                Ok(appender) => appender,
+                Err(e) => {
+                    eprintln!("Failed to open log file in {}: {}", log_dir.display(), e);
+                    return None;
+                }
+            };
+            let (non_blocking, guard) = tracing_appender::non_blocking(file_appender);
 
-    std::mem::forget(_guard);
+            tracing_subscriber::fmt()
+                .with_env_filter(filter)
+                .with_writer(non_blocking)
+                .with_ansi(false)
+                .with_target(false)
+                .init();
+            Some(guard)
+        }
+    };
 
     tracing::info!("gcal-imp started");
+    guard
 }
@@ -0,0 +1,255 @@
+use std::collections::{BTreeMap, HashMap};
+
+use chrono::{NaiveDate, NaiveDateTime};
+use regex::Regex;
+
+use crate::calendar::{DisplayTimezone, Event, EventStatus};
+use crate::ui::stats::format_minutes;
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum ReportFormat {
+    #[default]
+    Text,
+    Csv,
+}
+
+impl ReportFormat {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.trim().to_lowercase().as_str() {
+            "text" => Some(Self::Text),
+            "csv" => Some(Self::Csv),
+            _ => None,
+        }
+    }
+}
+
+/// A `[report.patterns]` entry: events whose title matches are added up
+/// under `name`.
+#[derive(Debug, Clone)]
+pub struct TitlePattern {
+    pub name: String,
+    pub regex: Regex,
+}
+
+impl TitlePattern {
+    /// Compiles the configured patterns case-insensitively, skipping invalid
+    /// ones with a warning.
+    pub fn from_config(patterns: &BTreeMap<String, String>) -> Vec<Self> {
+        patterns.iter()
+            .filter_map(|(name, pattern)| match Regex::new(&format!("(?i){}", pattern)) {
+                Ok(regex) => Some(Self { name: name.clone(), regex }),
+                Err(e) => {
+                    tracing::warn!("Invalid report pattern '{}' for {}: {}", pattern, name, e);
+                    None
+                }
+            })
+            .collect()
+    }
+}
+
+/// Hours spent per calendar and per title pattern over a date range.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Report {
+    pub start: NaiveDate,
+    pub end: NaiveDate,
+    pub events: usize,
+    pub total_minutes: i64,
+    /// Most time first.
+    pub by_calendar: Vec<(String, i64)>,
+    /// In config order; an event can match several patterns.
+    pub by_pattern: Vec<(String, i64)>,
+}
+
+/// Adds up timed events from `start` through `end`, clipped to the range.
+/// Cancelled and declined events are left out, and overlapping events are
+/// each counted in full.
+pub fn build_report(
+    events: &[&Event],
+    start: NaiveDate,
+    end: NaiveDate,
+    patterns: &[TitlePattern],
+    tz: &DisplayTimezone,
+) -> Report {
+    let range_start = start.and_time(chrono::NaiveTime::MIN);
+    let range_end = end.succ_opt().unwrap_or(end).and_time(chrono::NaiveTime::MIN);
+
+    let mut by_calendar: HashMap<&str, i64> = HashMap::new();
+    let mut by_pattern = vec![0; patterns.len()];
+    let mut counted = 0;
+    let mut total_minutes = 0;
+
+    for event in events {
+        if event.all_day || event.status == EventStatus::Cancelled || event.is_declined() {
+            continue;
+        }
+        let minutes = clipped_minutes(event.local_start(tz), event.local_end(tz), range_start, range_end);
+        if minutes <= 0 {
+            continue;
+        }
+        counted += 1;
+        total_minutes += minutes;
+        *by_calendar.entry(event.calendar_id.as_str()).or_default() += minutes;
+        for (pattern, sum) in patterns.iter().zip(&mut by_pattern) {
+            if pattern.regex.is_match(&event.title) {
+                *sum += minutes;
+            }
+        }
+    }
+
+    let mut by_calendar: Vec<(String, i64)> = by_calendar.into_iter()
+        .map(|(calendar, minutes)| (calendar.to_string(), minutes))
+        .collect();
+    by_calendar.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+
+    Report {
+        start,
+        end,
+        events: counted,
+        total_minutes,
+        by_calendar,
+        by_pattern: patterns.iter().map(|p| p.name.clone()).zip(by_pattern).collect(),
+    }
+}
+
+fn clipped_minutes(start: NaiveDateTime, end: NaiveDateTime, range_start: NaiveDateTime, range_end: NaiveDateTime) -> i64 {
+    (end.min(range_end) - start.max(range_start)).num_minutes()
+}
+
+impl Report {
+    /// Table lines, shared by the `:report` dialog and `--report`.
+    pub fn text_lines(&self) -> Vec<String> {
+        let mut lines = vec![
+            format!("{} to {}: {} events, {}", self.start, self.end, self.events, format_minutes(self.total_minutes)),
+        ];
+        let width = self.by_calendar.iter().chain(&self.by_pattern)
+            .map(|(name, _)| name.chars().count())
+            .max()
+            .unwrap_or(0);
+
+        let mut section = |title: &str, rows: &[(String, i64)]| {
+            if rows.is_empty() {
+                return;
+            }
+            lines.extend([String::new(), title.to_string()]);
+            for (name, minutes) in rows {
+                lines.push(format!("  {:<width$}  {:>7}  {:>3}%", name, format_minutes(*minutes), self.percent(*minutes)));
+            }
+        };
+        section("By calendar", &self.by_calendar);
+        section("By title", &self.by_pattern);
+        lines
+    }
+
+    pub fn to_text(&self) -> String {
+        let mut text = self.text_lines().join("\n");
+        text.push('\n');
+        text
+    }
+
+    /// One row per calendar and pattern, hours with two decimals.
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from("group,name,hours\n");
+        let rows = self.by_calendar.iter().map(|row| ("calendar", row))
+            .chain(self.by_pattern.iter().map(|row| ("title", row)));
+        for (group, (name, minutes)) in rows {
+            csv.push_str(&format!("{},{},{:.2}\n", group, csv_field(name), *minutes as f64 / 60.0));
+        }
+        csv
+    }
+
+    pub fn format(&self, format: ReportFormat) -> String {
+        match format {
+            ReportFormat::Text => self.to_text(),
+            ReportFormat::Csv => self.to_csv(),
+        }
+    }
+
+    fn percent(&self, minutes: i64) -> i64 {
+        if self.total_minutes == 0 { 0 } else { (minutes * 100 + self.total_minutes / 2) / self.total_minutes }
+    }
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::EventBuilder;
+    use chrono::{Duration, TimeZone, Utc};
+    use crate::calendar::{ResponseStatus, DEFAULT_CALENDAR_ID};
+
+    fn event(title: &str, calendar_id: &str, day: u32, hour: u32, minutes: i64) -> Event {
+        let start = Utc.with_ymd_and_hms(2025, 1, day, hour, 0, 0).unwrap();
+        EventBuilder::new(&format!("{}-{}", title, day))
+            .title(title)
+            .calendar(calendar_id)
+            .at(start, Duration::minutes(minutes))
+            .build()
+    }
+
+    fn patterns() -> Vec<TitlePattern> {
+        let config = BTreeMap::from([
+            ("Meetings".to_string(), "meeting|standup|sync".to_string()),
+            ("Broken".to_string(), "(".to_string()),
+        ]);
+        TitlePattern::from_config(&config)
+    }
+
+    fn date(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2025, 1, day).unwrap()
+    }
+
+    #[test]
+    fn adds_up_hours_per_calendar_and_pattern() {
+        let mut declined = event("Team sync", DEFAULT_CALENDAR_ID, 14, 15, 60);
+        declined.self_response = Some(ResponseStatus::Declined);
+        let events = [
+            event("Standup", DEFAULT_CALENDAR_ID, 13, 9, 15),
+            event("Planning meeting", "work", 14, 10, 90),
+            event("Focus time", DEFAULT_CALENDAR_ID, 15, 13, 120),
+            event("Standup", DEFAULT_CALENDAR_ID, 20, 9, 15),
+            declined,
+        ];
+        let events: Vec<&Event> = events.iter().collect();
+
+        let report = build_report(&events, date(13), date(19), &patterns(), &DisplayTimezone::Named(chrono_tz::UTC));
+
+        assert_eq!(report.events, 3);
+        assert_eq!(report.total_minutes, 225);
+        assert_eq!(report.by_calendar, vec![("primary".to_string(), 135), ("work".to_string(), 90)]);
+        assert_eq!(report.by_pattern, vec![("Meetings".to_string(), 105)]);
+        assert_eq!(report.text_lines()[0], "2025-01-13 to 2025-01-19: 3 events, 3h45m");
+        assert!(report.text_lines().contains(&"  Meetings    1h45m   47%".to_string()));
+    }
+
+    #[test]
+    fn events_are_clipped_to_the_range() {
+        let events = [event("Offsite", "work", 19, 20, 8 * 60)];
+        let events: Vec<&Event> = events.iter().collect();
+
+        let report = build_report(&events, date(13), date(19), &[], &DisplayTimezone::Named(chrono_tz::UTC));
+
+        assert_eq!(report.total_minutes, 4 * 60);
+    }
+
+    #[test]
+    fn csv_has_a_row_per_group() {
+        let report = Report {
+            start: date(13),
+            end: date(19),
+            events: 2,
+            total_minutes: 90,
+            by_calendar: vec![("work, team".to_string(), 90)],
+            by_pattern: vec![("Meetings".to_string(), 45)],
+        };
+
+        assert_eq!(report.to_csv(), "group,name,hours\ncalendar,\"work, team\",1.50\ntitle,Meetings,0.75\n");
+        assert_eq!(ReportFormat::from_name("CSV"), Some(ReportFormat::Csv));
+    }
+}
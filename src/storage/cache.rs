@@ -1,7 +1,11 @@
+use std::path::{Path, PathBuf};
+use chrono::{DateTime, Utc};
 use rusqlite::{Connection, Result as SqliteResult};
 use thiserror::Error;
 
 use crate::calendar::Event;
+use crate::storage::crypto::{CryptoError, KeySource};
+use crate::storage::paths;
 
 #[derive(Debug, Error)]
 pub enum CacheError {
@@ -9,15 +13,71 @@ pub enum CacheError {
     DatabaseError(#[from] rusqlite::Error),
     #[error("Serialization error: {0}")]
     SerializationError(#[from] serde_json::Error),
+    #[error("IO error: {0}")]
+    IoError(#[from] std::io::Error),
+    #[error("Encryption error: {0}")]
+    CryptoError(#[from] CryptoError),
+}
+
+/// An edit made here that has not reached the server yet.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LocalEdit {
+    /// The edited event; its `last_modified` is the server version it was based on.
+    pub event: Event,
+    pub local_modified: DateTime<Utc>,
 }
 
 pub struct Cache {
     conn: Connection,
+    keys: KeySource,
 }
 
 impl Cache {
     pub fn new(conn: Connection) -> Self {
-        Self { conn }
+        Self { conn, keys: KeySource::Plain }
+    }
+
+    /// Seals each event's data column from now on; plain rows stay readable.
+    pub fn with_keys(mut self, keys: KeySource) -> Self {
+        self.keys = keys;
+        self
+    }
+
+    /// Seals rows written before encryption was turned on. Returns how many
+    /// were sealed; none are once every row is.
+    pub fn seal_plain_rows(&self) -> Result<usize, CacheError> {
+        if !self.keys.encrypts() {
+            return Ok(0);
+        }
+        let tx = self.conn.unchecked_transaction()?;
+        let rows: Vec<(String, String)> = tx
+            .prepare("SELECT id, data FROM events WHERE data NOT LIKE 'enc1:%'")?
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+            .collect::<Result<_, _>>()?;
+        for (id, data) in &rows {
+            tx.execute("UPDATE events SET data = ?1 WHERE id = ?2", rusqlite::params![self.keys.seal(data.clone())?, id])?;
+        }
+        tx.commit()?;
+        Ok(rows.len())
+    }
+
+    fn decode_event(&self, data: String) -> Result<Event, CacheError> {
+        Ok(serde_json::from_str(&self.keys.open(data)?)?)
+    }
+
+    /// Where the cache lives unless `paths.cache` overrides it.
+    pub fn default_path() -> PathBuf {
+        paths::default_cache_path()
+    }
+
+    /// Opens (creating if needed) the database at `path` with its schema.
+    pub fn open(path: &Path) -> Result<Self, CacheError> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let cache = Self::new(Connection::open(path)?);
+        cache.initialize()?;
+        Ok(cache)
     }
 
     pub fn initialize(&self) -> Result<(), CacheError> {
@@ -28,11 +88,18 @@ impl Cache {
                 data TEXT NOT NULL,
                 start_date TEXT NOT NULL,
                 end_date TEXT NOT NULL,
-                last_modified TEXT NOT NULL
+                last_modified TEXT NOT NULL,
+                dirty INTEGER NOT NULL DEFAULT 0,
+                local_modified TEXT
             )",
             [],
         )?;
 
+        if !self.column_exists("events", "dirty") {
+            self.conn.execute("ALTER TABLE events ADD COLUMN dirty INTEGER NOT NULL DEFAULT 0", [])?;
+            self.conn.execute("ALTER TABLE events ADD COLUMN local_modified TEXT", [])?;
+        }
+
         self.conn.execute(
             "CREATE TABLE IF NOT EXISTS calendars (
                 id TEXT PRIMARY KEY,
@@ -52,14 +119,53 @@ impl Cache {
             [],
         )?;
 
+        self.conn.execute(
+            "CREATE TABLE IF NOT EXISTS sync_meta (
+                key TEXT PRIMARY KEY,
+                value TEXT NOT NULL
+            )",
+            [],
+        )?;
+
+        // Contacts moved to their own file; older caches kept them here unsealed.
+        self.conn.execute("DROP TABLE IF EXISTS contacts", [])?;
+
         Ok(())
     }
 
+    pub fn set_last_sync(&self, at: DateTime<Utc>) -> Result<(), CacheError> {
+        self.conn.execute(
+            "INSERT OR REPLACE INTO sync_meta (key, value) VALUES ('last_sync', ?1)",
+            [at.to_rfc3339()],
+        )?;
+        Ok(())
+    }
+
+    /// When events were last fetched successfully, if ever.
+    pub fn last_sync(&self) -> Result<Option<DateTime<Utc>>, CacheError> {
+        let mut stmt = self.conn.prepare("SELECT value FROM sync_meta WHERE key = 'last_sync'")?;
+        let mut rows = stmt.query([])?;
+        let Some(row) = rows.next()? else {
+            return Ok(None);
+        };
+        let value: String = row.get(0)?;
+        Ok(DateTime::parse_from_rfc3339(&value).ok().map(|at| at.with_timezone(&Utc)))
+    }
+
     pub fn store_event(&self, event: &Event) -> Result<(), CacheError> {
-        let data = serde_json::to_string(event)?;
+        self.write_event(event, None)
+    }
+
+    /// Stores an edit that still has to be pushed, flagged dirty.
+    pub fn store_local_edit(&self, event: &Event, local_modified: DateTime<Utc>) -> Result<(), CacheError> {
+        self.write_event(event, Some(local_modified))
+    }
+
+    fn write_event(&self, event: &Event, local_modified: Option<DateTime<Utc>>) -> Result<(), CacheError> {
+        let data = self.keys.seal(serde_json::to_string(event)?)?;
         self.conn.execute(
-            "INSERT OR REPLACE INTO events (id, calendar_id, data, start_date, end_date, last_modified)
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            "INSERT OR REPLACE INTO events (id, calendar_id, data, start_date, end_date, last_modified, dirty, local_modified)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
             rusqlite::params![
                 &event.id,
                 &event.calendar_id,
@@ -67,29 +173,121 @@ impl Cache {
                 event.start.to_rfc3339(),
                 event.end.to_rfc3339(),
                 event.last_modified.to_rfc3339(),
+                local_modified.is_some(),
+                local_modified.map(|at| at.to_rfc3339()),
             ],
         )?;
         Ok(())
     }
 
+    /// Replaces the events of `calendar_ids` starting in `start..end` with
+    /// a fresh fetch of that window. Other calendars keep their rows, so one
+    /// that failed to fetch is not emptied. Local edits are left alone, so
+    /// fetched copies of them do not overwrite changes that still have to be
+    /// pushed.
+    pub fn replace_window(
+        &self,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+        calendar_ids: &[String],
+        events: &[Event],
+    ) -> Result<(), CacheError> {
+        let tx = self.conn.unchecked_transaction()?;
+        let placeholders = (0..calendar_ids.len()).map(|i| format!("?{}", i + 3)).collect::<Vec<_>>().join(", ");
+        tx.execute(
+            &format!(
+                "DELETE FROM events WHERE dirty = 0 AND start_date >= ?1 AND start_date < ?2 AND calendar_id IN ({})",
+                placeholders,
+            ),
+            rusqlite::params_from_iter([start.to_rfc3339(), end.to_rfc3339()].iter().chain(calendar_ids)),
+        )?;
+        for event in events {
+            let data = self.keys.seal(serde_json::to_string(event)?)?;
+            tx.execute(
+                "INSERT INTO events (id, calendar_id, data, start_date, end_date, last_modified, dirty, local_modified)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, 0, NULL)
+                 ON CONFLICT(id) DO UPDATE SET calendar_id = excluded.calendar_id, data = excluded.data,
+                     start_date = excluded.start_date, end_date = excluded.end_date, last_modified = excluded.last_modified
+                 WHERE dirty = 0",
+                rusqlite::params![
+                    &event.id,
+                    &event.calendar_id,
+                    &data,
+                    event.start.to_rfc3339(),
+                    event.end.to_rfc3339(),
+                    event.last_modified.to_rfc3339(),
+                ],
+            )?;
+        }
+        tx.commit()?;
+        Ok(())
+    }
+
+    /// Dirty events, oldest edit first.
+    pub fn local_edits(&self) -> Result<Vec<LocalEdit>, CacheError> {
+        let mut stmt = self.conn.prepare(
+            "SELECT data, local_modified FROM events WHERE dirty = 1 ORDER BY local_modified",
+        )?;
+        let rows = stmt.query_map([], |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?)))?
+            .collect::<SqliteResult<Vec<(String, String)>>>()?;
+        rows.into_iter()
+            .map(|(data, local_modified)| {
+                Ok(LocalEdit {
+                    event: self.decode_event(data)?,
+                    local_modified: DateTime::parse_from_rfc3339(&local_modified)
+                        .map(|at| at.with_timezone(&Utc))
+                        .unwrap_or_else(|_| Utc::now()),
+                })
+            })
+            .collect()
+    }
+
     pub fn load_event(&self, id: &str) -> Result<Option<Event>, CacheError> {
         let mut stmt = self.conn.prepare("SELECT data FROM events WHERE id = ?1")?;
         let mut rows = stmt.query([id])?;
 
         if let Some(row) = rows.next()? {
             let data: String = row.get(0)?;
-            let event: Event = serde_json::from_str(&data)?;
-            Ok(Some(event))
+            Ok(Some(self.decode_event(data)?))
         } else {
             Ok(None)
         }
     }
 
+    pub fn load_events(&self) -> Result<Vec<Event>, CacheError> {
+        let mut stmt = self.conn.prepare("SELECT data FROM events")?;
+        let rows = stmt.query_map([], |row| row.get::<_, String>(0))?
+            .collect::<SqliteResult<Vec<String>>>()?;
+        rows.into_iter()
+            .map(|data| self.decode_event(data))
+            .collect()
+    }
+
+    /// Replaces every stored event, e.g. with the whole local calendar.
+    pub fn replace_all(&self, events: &[Event]) -> Result<(), CacheError> {
+        let tx = self.conn.unchecked_transaction()?;
+        tx.execute("DELETE FROM events", [])?;
+        for event in events {
+            self.write_event(event, None)?;
+        }
+        tx.commit()?;
+        Ok(())
+    }
+
     pub fn delete_event(&self, id: &str) -> Result<(), CacheError> {
         self.conn.execute("DELETE FROM events WHERE id = ?1", [id])?;
         Ok(())
     }
 
+    fn column_exists(&self, table_name: &str, column: &str) -> bool {
+        let result: SqliteResult<i32> = self.conn.query_row(
+            "SELECT COUNT(*) FROM pragma_table_info(?1) WHERE name = ?2",
+            [table_name, column],
+            |row| row.get(0),
+        );
+        result.unwrap_or(0) > 0
+    }
+
     pub fn table_exists(&self, table_name: &str) -> bool {
         let result: SqliteResult<i32> = self.conn.query_row(
             "SELECT COUNT(*) FROM sqlite_master WHERE type='table' AND name=?1",
@@ -104,7 +302,7 @@ impl Cache {
 mod tests {
     use super::*;
     use chrono::Utc;
-    use crate::calendar::{EventStatus, DEFAULT_CALENDAR_ID};
+    use crate::calendar::{EventStatus, Transparency, Visibility, DEFAULT_CALENDAR_ID};
 
     fn create_test_cache() -> Cache {
         let conn = Connection::open_in_memory().unwrap();
@@ -129,6 +327,17 @@ mod tests {
             status: EventStatus::Confirmed,
             last_modified: Utc::now(),
             html_link: None,
+            time_zone: None,
+            conference_url: None,
+            color_id: None,
+            color: None,
+            self_response: None,
+            attendee_responses: Default::default(),
+            visibility: Visibility::Default,
+            transparency: Transparency::Opaque,
+            etag: None,
+            organizer: None,
+            creator: None,
         }
     }
 
@@ -142,6 +351,20 @@ mod tests {
         assert!(cache.table_exists("events"));
         assert!(cache.table_exists("calendars"));
         assert!(cache.table_exists("sync_queue"));
+        assert!(cache.table_exists("sync_meta"));
+    }
+
+    #[test]
+    fn remembers_the_last_sync_time() {
+        use chrono::TimeZone;
+        let cache = create_test_cache();
+        assert_eq!(cache.last_sync().unwrap(), None);
+
+        let at = Utc.with_ymd_and_hms(2025, 1, 15, 9, 30, 0).unwrap();
+        cache.set_last_sync(at - chrono::Duration::hours(1)).unwrap();
+        cache.set_last_sync(at).unwrap();
+
+        assert_eq!(cache.last_sync().unwrap(), Some(at));
     }
 
     #[test]
@@ -155,6 +378,44 @@ mod tests {
         assert_eq!(loaded, Some(event));
     }
 
+    #[test]
+    fn encrypted_events_keep_titles_off_disk() {
+        let keys = KeySource::Key(crate::storage::crypto::DataKey::generate().unwrap());
+        let cache = create_test_cache();
+        let plain = create_test_event("plain", "Written before encryption");
+        cache.store_event(&plain).unwrap();
+        let cache = cache.with_keys(keys);
+        let event = create_test_event("event1", "Salary review");
+
+        cache.store_event(&event).unwrap();
+
+        let data: String = cache.conn
+            .query_row("SELECT data FROM events WHERE id = 'event1'", [], |row| row.get(0))
+            .unwrap();
+        assert!(!data.contains("Salary"));
+        assert_eq!(cache.load_event("event1").unwrap(), Some(event));
+        assert_eq!(cache.load_event("plain").unwrap(), Some(plain));
+    }
+
+    #[test]
+    fn turning_encryption_on_seals_the_plain_rows() {
+        let keys = KeySource::Key(crate::storage::crypto::DataKey::generate().unwrap());
+        let cache = create_test_cache();
+        let plain = create_test_event("plain", "Salary review");
+        cache.store_event(&plain).unwrap();
+        assert_eq!(cache.seal_plain_rows().unwrap(), 0);
+        let cache = cache.with_keys(keys);
+
+        assert_eq!(cache.seal_plain_rows().unwrap(), 1);
+        assert_eq!(cache.seal_plain_rows().unwrap(), 0);
+
+        let data: String = cache.conn
+            .query_row("SELECT data FROM events WHERE id = 'plain'", [], |row| row.get(0))
+            .unwrap();
+        assert!(!data.contains("Salary"));
+        assert_eq!(cache.load_event("plain").unwrap(), Some(plain));
+    }
+
     #[test]
     fn loads_nonexistent_event_returns_none() {
         let cache = create_test_cache();
@@ -177,6 +438,18 @@ mod tests {
         assert_eq!(loaded.title, "Updated");
     }
 
+    #[test]
+    fn loads_all_cached_events() {
+        let cache = create_test_cache();
+        cache.store_event(&create_test_event("event1", "One")).unwrap();
+        cache.store_event(&create_test_event("event2", "Two")).unwrap();
+
+        let mut titles: Vec<String> = cache.load_events().unwrap().into_iter().map(|e| e.title).collect();
+        titles.sort();
+
+        assert_eq!(titles, vec!["One", "Two"]);
+    }
+
     #[test]
     fn deletes_event_from_cache() {
         let cache = create_test_cache();
@@ -188,4 +461,61 @@ mod tests {
         let loaded = cache.load_event(&event.id).unwrap();
         assert!(loaded.is_none());
     }
+
+    #[test]
+    fn local_edits_are_flagged_dirty() {
+        use chrono::TimeZone;
+        let cache = create_test_cache();
+        let edited_at = Utc.with_ymd_and_hms(2025, 1, 10, 9, 0, 0).unwrap();
+        let edit = create_test_event("event1", "Edited");
+        cache.store_event(&create_test_event("event2", "Fetched")).unwrap();
+        cache.store_local_edit(&edit, edited_at).unwrap();
+
+        assert_eq!(cache.local_edits().unwrap(), vec![LocalEdit { event: edit.clone(), local_modified: edited_at }]);
+        assert_eq!(cache.load_event("event1").unwrap(), Some(edit.clone()));
+
+        cache.store_event(&edit).unwrap();
+        assert!(cache.local_edits().unwrap().is_empty());
+    }
+
+    #[test]
+    fn replacing_a_window_drops_stale_events_but_keeps_local_edits() {
+        let cache = create_test_cache();
+        let stale = create_test_event("stale", "Cancelled since");
+        let edit = create_test_event("edited", "Edited here");
+        let mut outside = create_test_event("outside", "Next year");
+        outside.start += chrono::Duration::days(365);
+        cache.store_event(&stale).unwrap();
+        cache.store_event(&outside).unwrap();
+        cache.store_local_edit(&edit, Utc::now()).unwrap();
+
+        let mut fetched_edit = edit.clone();
+        fetched_edit.title = "Server version".to_string();
+        let new = create_test_event("new", "Fetched");
+        let start = stale.start - chrono::Duration::days(1);
+        let calendars = [DEFAULT_CALENDAR_ID.to_string()];
+        cache.replace_window(start, start + chrono::Duration::days(30), &calendars, &[fetched_edit, new.clone()]).unwrap();
+
+        assert_eq!(cache.load_event("stale").unwrap(), None);
+        assert_eq!(cache.load_event("new").unwrap(), Some(new));
+        assert_eq!(cache.load_event("edited").unwrap(), Some(edit));
+        assert_eq!(cache.load_event("outside").unwrap(), Some(outside));
+    }
+
+    #[test]
+    fn adds_dirty_columns_to_an_old_events_table() {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute(
+            "CREATE TABLE events (id TEXT PRIMARY KEY, calendar_id TEXT NOT NULL, data TEXT NOT NULL,
+             start_date TEXT NOT NULL, end_date TEXT NOT NULL, last_modified TEXT NOT NULL)",
+            [],
+        ).unwrap();
+        let cache = Cache::new(conn);
+
+        cache.initialize().unwrap();
+        cache.initialize().unwrap();
+
+        assert!(cache.column_exists("events", "dirty"));
+        assert!(cache.column_exists("events", "local_modified"));
+    }
 }
@@ -1,7 +1,11 @@
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::path::PathBuf;
 use thiserror::Error;
 
+use crate::storage::paths;
+use crate::sync::sync_state::DEFAULT_STALE_AFTER_MINUTES;
+
 #[derive(Debug, Error)]
 pub enum ConfigError {
     #[error("Failed to read config file: {0}")]
@@ -16,6 +20,18 @@ pub struct Config {
     pub sync: SyncConfig,
     pub ui: UiConfig,
     pub calendars: CalendarsConfig,
+    #[serde(default)]
+    pub logging: LoggingConfig,
+    #[serde(default)]
+    pub paths: PathsConfig,
+    #[serde(default)]
+    pub security: SecurityConfig,
+    #[serde(default)]
+    pub report: ReportConfig,
+    /// Read-only `.ics` feeds fetched on sync, by name, e.g.
+    /// `league = "webcal://league.example/team.ics"`.
+    #[serde(default)]
+    pub subscriptions: BTreeMap<String, String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -31,6 +47,43 @@ pub struct SyncConfig {
     pub offline_mode: bool,
     pub sync_past_days: u32,
     pub sync_future_days: u32,
+    /// Also request Google Tasks access and show the tasks pane.
+    #[serde(default)]
+    pub tasks: bool,
+    /// Public HTTPS URL Google posts change notifications to; it must forward
+    /// to `push_port` on this machine. Push sync is off without it.
+    #[serde(default)]
+    pub push_address: Option<String>,
+    #[serde(default = "default_push_port")]
+    pub push_port: u16,
+    /// Skip every create, update and delete, e.g. on a shared calendar.
+    #[serde(default)]
+    pub read_only: bool,
+    /// Requests sent to Google per second at most; 0 turns the limit off.
+    #[serde(default = "default_max_requests_per_second")]
+    pub max_requests_per_second: u32,
+    /// Minutes after the last sync before the status bar flags events as stale.
+    #[serde(default = "default_stale_after_minutes")]
+    pub stale_after_minutes: u32,
+    /// "google" (the default), "local" for a calendar kept only in the
+    /// cache, or "mock" for an in-memory one. Neither needs credentials.
+    #[serde(default)]
+    pub backend: Option<String>,
+    /// Makes every nth mock request fail; 0 never fails.
+    #[serde(default)]
+    pub mock_failure_every: u32,
+}
+
+fn default_max_requests_per_second() -> u32 {
+    5
+}
+
+fn default_stale_after_minutes() -> u32 {
+    DEFAULT_STALE_AFTER_MINUTES
+}
+
+fn default_push_port() -> u16 {
+    8765
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -41,15 +94,142 @@ pub struct UiConfig {
     pub show_week_numbers: bool,
     pub default_view: String,
     pub theme: String,
+    #[serde(default)]
+    pub timezone: Option<String>,
+    #[serde(default)]
+    pub clipboard: Option<String>,
+    #[serde(default)]
+    pub working_hours: Option<String>,
+    #[serde(default)]
+    pub month_cell_style: Option<String>,
+    #[serde(default)]
+    pub declined_events: Option<String>,
+    #[serde(default)]
+    pub day_grid: Option<String>,
+    /// `openstreetmap` (default) or `google`, for location map links.
+    #[serde(default)]
+    pub map_provider: Option<String>,
+    /// Minutes needed between events at different locations; tighter hops
+    /// are flagged in the day and week views and `:conflicts`. Off if unset.
+    #[serde(default)]
+    pub travel_buffer_minutes: Option<u32>,
+    /// Shade year view days by how many events they have.
+    #[serde(default)]
+    pub year_heatmap: bool,
+    /// Status bar format with `%date`, `%today`, `%time`, `%view`, `%mode`,
+    /// `%sync`, `%events`, `%day_events`, `%overlaps`, `%calendar`,
+    /// `%readonly` and `%recording`.
+    #[serde(default)]
+    pub statusline: Option<String>,
+}
+
+impl Default for UiConfig {
+    fn default() -> Self {
+        Self {
+            first_day_of_week: "Monday".to_string(),
+            time_format: "24h".to_string(),
+            date_format: "%Y-%m-%d".to_string(),
+            show_week_numbers: true,
+            default_view: "Month".to_string(),
+            theme: "default".to_string(),
+            timezone: None,
+            clipboard: None,
+            working_hours: None,
+            month_cell_style: None,
+            declined_events: None,
+            day_grid: None,
+            map_provider: None,
+            travel_buffer_minutes: None,
+            year_heatmap: false,
+            statusline: None,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct CalendarsConfig {
     pub default: String,
     pub visible: Vec<String>,
+    /// Read-only holiday/birthday calendars fetched alongside the default.
+    #[serde(default)]
+    pub overlays: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct LoggingConfig {
+    /// A tracing filter such as "info" or "gcal_imp=debug"; RUST_LOG wins.
+    #[serde(default = "default_log_level")]
+    pub level: String,
+    /// "file" (daily files next to the config) or "stderr"; the TUI always
+    /// logs to the file.
+    #[serde(default)]
+    pub destination: Option<String>,
+    /// Daily log files to keep; 0 keeps them all.
+    #[serde(default = "default_log_retention_days")]
+    pub retention_days: u32,
+}
+
+fn default_log_level() -> String {
+    "info".to_string()
+}
+
+fn default_log_retention_days() -> u32 {
+    14
+}
+
+impl Default for LoggingConfig {
+    fn default() -> Self {
+        Self {
+            level: default_log_level(),
+            destination: None,
+            retention_days: default_log_retention_days(),
+        }
+    }
+}
+
+/// Overrides for where data lives; unset paths follow the platform's
+/// data and cache directories.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct PathsConfig {
+    #[serde(default)]
+    pub cache: Option<PathBuf>,
+    #[serde(default)]
+    pub logs: Option<PathBuf>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct SecurityConfig {
+    /// Encrypt token.json, cached events and subscription copies with a key
+    /// kept in the OS keyring. Event ids, times and the attendee addresses
+    /// offered for completion stay readable for lookups, and a description
+    /// opened in `$EDITOR` is plain text until the editor exits. Plain events
+    /// and tokens written before this was turned on are sealed on the next start.
+    #[serde(default)]
+    pub encrypt: bool,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct ReportConfig {
+    /// Report groups by title, e.g. `Meetings = "meeting|standup|1:1"`.
+    /// Patterns are case-insensitive regular expressions.
+    #[serde(default)]
+    pub patterns: BTreeMap<String, String>,
 }
 
 impl Config {
+    pub fn cache_path(&self) -> PathBuf {
+        self.paths.cache.clone().unwrap_or_else(paths::default_cache_path)
+    }
+
+    /// The last copy of each subscription feed, kept next to the cache.
+    pub fn subscriptions_dir(&self) -> PathBuf {
+        self.cache_path().with_file_name("subscriptions")
+    }
+
+    pub fn log_dir(&self) -> PathBuf {
+        self.paths.logs.clone().unwrap_or_else(paths::cache_dir)
+    }
+
     pub fn from_toml(content: &str) -> Result<Self, ConfigError> {
         toml::from_str(content).map_err(ConfigError::from)
     }
@@ -68,10 +248,7 @@ impl Config {
     }
 
     pub fn config_path() -> PathBuf {
-        dirs::config_dir()
-            .unwrap_or_else(|| PathBuf::from("."))
-            .join("gcal-imp")
-            .join("config.toml")
+        paths::config_dir().join("config.toml")
     }
 
     pub fn save(&self) -> Result<(), ConfigError> {
@@ -91,9 +268,7 @@ impl Config {
 
 impl Default for Config {
     fn default() -> Self {
-        let config_dir = dirs::config_dir()
-            .unwrap_or_else(|| PathBuf::from("."))
-            .join("gcal-imp");
+        let config_dir = paths::config_dir();
 
         Self {
             google: GoogleConfig {
@@ -106,19 +281,26 @@ impl Default for Config {
                 offline_mode: false,
                 sync_past_days: 90,
                 sync_future_days: 365,
+                tasks: false,
+                push_address: None,
+                push_port: default_push_port(),
+                read_only: false,
+                max_requests_per_second: default_max_requests_per_second(),
+                stale_after_minutes: default_stale_after_minutes(),
+                backend: None,
+                mock_failure_every: 0,
             },
-            ui: UiConfig {
-                first_day_of_week: "Monday".to_string(),
-                time_format: "24h".to_string(),
-                date_format: "%Y-%m-%d".to_string(),
-                show_week_numbers: true,
-                default_view: "Month".to_string(),
-                theme: "default".to_string(),
-            },
+            ui: UiConfig::default(),
             calendars: CalendarsConfig {
                 default: "primary".to_string(),
                 visible: vec!["primary".to_string()],
+                overlays: Vec::new(),
             },
+            logging: LoggingConfig::default(),
+            paths: PathsConfig::default(),
+            security: SecurityConfig::default(),
+            report: ReportConfig::default(),
+            subscriptions: BTreeMap::new(),
         }
     }
 }
@@ -170,6 +352,59 @@ mod tests {
         assert_eq!(config.calendars.visible, vec!["primary", "work"]);
     }
 
+    #[test]
+    fn push_sync_is_off_unless_an_address_is_set() {
+        let toml_content = r#"
+            [google]
+            client_id = "id"
+            client_secret = "secret"
+            token_cache = "/tmp/token.json"
+
+            [sync]
+            auto_sync_interval_minutes = 15
+            offline_mode = false
+            sync_past_days = 90
+            sync_future_days = 365
+
+            [ui]
+            first_day_of_week = "Monday"
+            time_format = "24h"
+            date_format = "%Y-%m-%d"
+            show_week_numbers = true
+            default_view = "Month"
+            theme = "default"
+
+            [calendars]
+            default = "primary"
+            visible = ["primary"]
+        "#;
+
+        let config = Config::from_toml(toml_content).unwrap();
+
+        assert_eq!(config.sync.push_address, None);
+        assert_eq!(config.sync.push_port, 8765);
+        assert_eq!(config.sync.stale_after_minutes, 60);
+        assert_eq!(config.sync.max_requests_per_second, 5);
+        assert!(!config.sync.read_only);
+        assert_eq!(config.sync.backend, None);
+        assert_eq!(config.sync.mock_failure_every, 0);
+        assert_eq!(config.logging, LoggingConfig::default());
+        assert_eq!(config.paths, PathsConfig::default());
+        assert!(!config.security.encrypt);
+        assert!(config.subscriptions.is_empty());
+    }
+
+    #[test]
+    fn subscriptions_survive_a_save() {
+        let mut config = Config::default();
+        config.subscriptions.insert("league".to_string(), "webcal://league.example/team.ics".to_string());
+
+        let saved = toml::to_string_pretty(&config).unwrap();
+
+        assert!(saved.contains("[subscriptions]\nleague = \"webcal://league.example/team.ics\""));
+        assert_eq!(Config::from_toml(&saved).unwrap().subscriptions, config.subscriptions);
+    }
+
     #[test]
     fn parse_invalid_toml_returns_error() {
         let invalid_toml = "this is not valid toml";
@@ -0,0 +1,114 @@
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Utc};
+use thiserror::Error;
+
+use crate::storage::crypto::{CryptoError, KeySource};
+use crate::storage::paths;
+
+#[derive(Debug, Error)]
+pub enum ContactsError {
+    #[error("IO error: {0}")]
+    IoError(#[from] std::io::Error),
+    #[error("Encryption error: {0}")]
+    CryptoError(#[from] CryptoError),
+}
+
+/// Attendee addresses seen in events, for completion in the Attendees field.
+/// They are rebuilt from events, so the file lives in the cache directory.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Contacts {
+    /// Most recently seen first as loaded; new addresses go at the end.
+    emails: Vec<String>,
+    known: HashSet<String>,
+    last_seen: HashMap<String, DateTime<Utc>>,
+}
+
+impl Contacts {
+    pub fn default_path() -> PathBuf {
+        paths::cache_dir().join("contacts")
+    }
+
+    /// Reads `email<TAB>last seen` lines; a missing file has no contacts.
+    pub fn load(path: &Path, keys: &KeySource) -> Result<Self, ContactsError> {
+        let content = match std::fs::read_to_string(path) {
+            Ok(content) => keys.open(content)?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Self::default()),
+            Err(e) => return Err(e.into()),
+        };
+        let mut contacts = Self::default();
+        for line in content.lines() {
+            let (email, seen) = line.split_once('\t').unwrap_or((line, ""));
+            let seen = DateTime::parse_from_rfc3339(seen).map_or(DateTime::UNIX_EPOCH, |at| at.with_timezone(&Utc));
+            contacts.record(email, seen);
+        }
+        Ok(contacts)
+    }
+
+    /// Writes the addresses most recently seen first, sealed when
+    /// `security.encrypt` is on.
+    pub fn save(&self, path: &Path, keys: &KeySource) -> Result<(), ContactsError> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let mut emails: Vec<&String> = self.emails.iter().collect();
+        emails.sort_by_key(|email| std::cmp::Reverse(self.last_seen[*email]));
+        let content: String = emails.iter()
+            .map(|email| format!("{}\t{}\n", email, self.last_seen[*email].to_rfc3339()))
+            .collect();
+        std::fs::write(path, keys.seal(content)?)?;
+        Ok(())
+    }
+
+    /// Remembers an address, keeping the latest time it was seen.
+    pub fn record(&mut self, email: &str, seen: DateTime<Utc>) {
+        let email = email.trim().to_lowercase();
+        if email.is_empty() {
+            return;
+        }
+        if self.known.insert(email.clone()) {
+            self.emails.push(email.clone());
+        }
+        let last_seen = self.last_seen.entry(email).or_insert(seen);
+        *last_seen = (*last_seen).max(seen);
+    }
+
+    pub fn emails(&self) -> &[String] {
+        &self.emails
+    }
+}
+
+/// Addresses in order of preference, e.g. for tests.
+impl<'a> FromIterator<&'a str> for Contacts {
+    fn from_iter<I: IntoIterator<Item = &'a str>>(emails: I) -> Self {
+        let mut contacts = Self::default();
+        for email in emails {
+            contacts.record(email, DateTime::UNIX_EPOCH);
+        }
+        contacts
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn saves_most_recently_seen_first() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("contacts");
+        let mut contacts = Contacts::default();
+        contacts.record("Alex@Example.com", Utc.with_ymd_and_hms(2025, 1, 10, 9, 0, 0).unwrap());
+        contacts.record("sam@example.com", Utc.with_ymd_and_hms(2025, 1, 12, 9, 0, 0).unwrap());
+        contacts.record("alex@example.com", Utc.with_ymd_and_hms(2025, 1, 14, 9, 0, 0).unwrap());
+        assert_eq!(contacts.emails(), ["alex@example.com", "sam@example.com"]);
+
+        contacts.save(&path, &KeySource::Plain).unwrap();
+        let loaded = Contacts::load(&path, &KeySource::Plain).unwrap();
+
+        assert_eq!(loaded, contacts);
+        assert!(Contacts::load(&dir.path().join("missing"), &KeySource::Plain).unwrap().emails().is_empty());
+    }
+}
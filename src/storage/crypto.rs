@@ -0,0 +1,208 @@
+use std::sync::OnceLock;
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+use ring::aead::{Aad, LessSafeKey, Nonce, UnboundKey, CHACHA20_POLY1305, NONCE_LEN};
+use ring::rand::{SecureRandom, SystemRandom};
+use thiserror::Error;
+
+use crate::storage::config::SecurityConfig;
+use crate::util::keyring;
+
+/// Marks a value as sealed, so plain values written before encryption was
+/// turned on can still be read.
+const SEALED_PREFIX: &str = "enc1:";
+/// Holds a base64 key for machines without a keyring, e.g. servers.
+pub const KEY_ENV_VAR: &str = "GCAL_IMP_DATA_KEY";
+const KEYRING_ACCOUNT: &str = "data-key";
+
+#[derive(Debug, Error)]
+pub enum CryptoError {
+    #[error("Keyring unavailable: {0}")]
+    Keyring(#[from] std::io::Error),
+    #[error("Invalid data key: {0}")]
+    InvalidKey(String),
+    #[error("Data is encrypted but encryption is turned off")]
+    Locked,
+    #[error("Failed to decrypt data; the key may have changed")]
+    Decrypt,
+    #[error("Failed to generate random bytes")]
+    Random,
+}
+
+/// A ChaCha20-Poly1305 key for data at rest.
+#[derive(Clone, PartialEq)]
+pub struct DataKey([u8; 32]);
+
+impl std::fmt::Debug for DataKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("DataKey(..)")
+    }
+}
+
+impl DataKey {
+    pub fn generate() -> Result<Self, CryptoError> {
+        let mut bytes = [0u8; 32];
+        SystemRandom::new().fill(&mut bytes).map_err(|_| CryptoError::Random)?;
+        Ok(Self(bytes))
+    }
+
+    pub fn from_base64(text: &str) -> Result<Self, CryptoError> {
+        let bytes = STANDARD.decode(text.trim()).map_err(|e| CryptoError::InvalidKey(e.to_string()))?;
+        let bytes: [u8; 32] = bytes.try_into().map_err(|_| CryptoError::InvalidKey("expected 32 bytes".to_string()))?;
+        Ok(Self(bytes))
+    }
+
+    pub fn to_base64(&self) -> String {
+        STANDARD.encode(self.0)
+    }
+
+    fn aead_key(&self) -> LessSafeKey {
+        LessSafeKey::new(UnboundKey::new(&CHACHA20_POLY1305, &self.0).expect("32-byte ChaCha20 key"))
+    }
+
+    /// Encrypts `plaintext` under a fresh nonce into `enc1:<base64>`.
+    pub fn seal(&self, plaintext: &str) -> Result<String, CryptoError> {
+        let mut nonce = [0u8; NONCE_LEN];
+        SystemRandom::new().fill(&mut nonce).map_err(|_| CryptoError::Random)?;
+        let mut data = plaintext.as_bytes().to_vec();
+        self.aead_key()
+            .seal_in_place_append_tag(Nonce::assume_unique_for_key(nonce), Aad::empty(), &mut data)
+            .map_err(|_| CryptoError::Random)?;
+
+        let mut sealed = nonce.to_vec();
+        sealed.extend(data);
+        Ok(format!("{}{}", SEALED_PREFIX, STANDARD.encode(sealed)))
+    }
+
+    pub fn open(&self, sealed: &str) -> Result<String, CryptoError> {
+        let encoded = sealed.strip_prefix(SEALED_PREFIX).ok_or(CryptoError::Decrypt)?;
+        let mut data = STANDARD.decode(encoded).map_err(|_| CryptoError::Decrypt)?;
+        if data.len() < NONCE_LEN {
+            return Err(CryptoError::Decrypt);
+        }
+        let nonce = Nonce::try_assume_unique_for_key(&data[..NONCE_LEN]).map_err(|_| CryptoError::Decrypt)?;
+        let plaintext = self.aead_key()
+            .open_in_place(nonce, Aad::empty(), &mut data[NONCE_LEN..])
+            .map_err(|_| CryptoError::Decrypt)?;
+        String::from_utf8(plaintext.to_vec()).map_err(|_| CryptoError::Decrypt)
+    }
+}
+
+pub fn is_sealed(text: &str) -> bool {
+    text.starts_with(SEALED_PREFIX)
+}
+
+/// Where the token file and cache get their key from.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub enum KeySource {
+    /// Encryption is off; values are written as they are.
+    #[default]
+    Plain,
+    /// `GCAL_IMP_DATA_KEY`, or the OS keyring, creating a key there on first use.
+    Keyring,
+    Key(DataKey),
+}
+
+impl KeySource {
+    pub fn from_config(security: &SecurityConfig) -> Self {
+        if security.encrypt { Self::Keyring } else { Self::Plain }
+    }
+
+    pub fn encrypts(&self) -> bool {
+        *self != Self::Plain
+    }
+
+    fn key(&self) -> Result<Option<DataKey>, CryptoError> {
+        match self {
+            Self::Plain => Ok(None),
+            Self::Keyring => keyring_key().map(Some),
+            Self::Key(key) => Ok(Some(key.clone())),
+        }
+    }
+
+    pub fn seal(&self, plaintext: String) -> Result<String, CryptoError> {
+        match self.key()? {
+            Some(key) => key.seal(&plaintext),
+            None => Ok(plaintext),
+        }
+    }
+
+    /// Plain values pass through, so turning encryption on needs no migration.
+    pub fn open(&self, text: String) -> Result<String, CryptoError> {
+        if !is_sealed(&text) {
+            return Ok(text);
+        }
+        self.key()?.ok_or(CryptoError::Locked)?.open(&text)
+    }
+}
+
+/// Loads the key once per process; the keyring may prompt to unlock. A new
+/// key is only created when the keyring reports that none is stored, since
+/// replacing the key would leave everything sealed so far unreadable.
+fn keyring_key() -> Result<DataKey, CryptoError> {
+    static KEY: OnceLock<DataKey> = OnceLock::new();
+    if let Some(key) = KEY.get() {
+        return Ok(key.clone());
+    }
+
+    let key = match std::env::var(KEY_ENV_VAR) {
+        Ok(text) => DataKey::from_base64(&text)?,
+        Err(_) => match keyring::lookup(KEYRING_ACCOUNT)? {
+            Some(text) => DataKey::from_base64(&text)?,
+            None => {
+                let key = DataKey::generate()?;
+                keyring::store(KEYRING_ACCOUNT, &key.to_base64())?;
+                tracing::info!("Stored a new data key in the OS keyring");
+                key
+            }
+        },
+    };
+    Ok(KEY.get_or_init(|| key).clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn seal_and_open_round_trip_with_fresh_nonces() {
+        let key = DataKey::generate().unwrap();
+
+        let first = key.seal("refresh-token").unwrap();
+        let second = key.seal("refresh-token").unwrap();
+
+        assert!(is_sealed(&first));
+        assert_ne!(first, second);
+        assert_eq!(key.open(&first).unwrap(), "refresh-token");
+    }
+
+    #[test]
+    fn wrong_key_or_tampering_fails_to_open() {
+        let key = DataKey::generate().unwrap();
+        let sealed = key.seal("secret").unwrap();
+
+        assert!(matches!(DataKey::generate().unwrap().open(&sealed), Err(CryptoError::Decrypt)));
+        let mut tampered = sealed.clone();
+        tampered.pop();
+        tampered.push(if sealed.ends_with('A') { 'B' } else { 'A' });
+        assert!(matches!(key.open(&tampered), Err(CryptoError::Decrypt)));
+    }
+
+    #[test]
+    fn plain_values_pass_through_and_sealed_ones_need_a_key() {
+        let key = KeySource::Key(DataKey::generate().unwrap());
+        let sealed = key.seal("event".to_string()).unwrap();
+
+        assert_eq!(key.open("{\"plain\":true}".to_string()).unwrap(), "{\"plain\":true}");
+        assert_eq!(key.open(sealed.clone()).unwrap(), "event");
+        assert!(matches!(KeySource::Plain.open(sealed), Err(CryptoError::Locked)));
+    }
+
+    #[test]
+    fn keys_round_trip_through_base64() {
+        let key = DataKey::generate().unwrap();
+
+        assert_eq!(DataKey::from_base64(&key.to_base64()).unwrap(), key);
+        assert!(DataKey::from_base64("c2hvcnQ=").is_err());
+    }
+}
@@ -1,2 +1,6 @@
 pub mod cache;
+pub mod contacts;
 pub mod config;
+pub mod session_state;
+pub mod paths;
+pub mod crypto;
@@ -0,0 +1,156 @@
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::util::logging::LOG_FILE_PREFIX;
+
+const APP_DIR: &str = "gcal-imp";
+const CACHE_FILE: &str = "cache.db";
+/// SQLite keeps uncommitted pages next to the database in these.
+const CACHE_SIDE_FILES: [&str; 2] = ["-wal", "-shm"];
+
+/// Settings, credentials, command history and session state.
+pub fn config_dir() -> PathBuf {
+    dirs::config_dir().unwrap_or_else(|| PathBuf::from(".")).join(APP_DIR)
+}
+
+/// The event cache, which only a resync can rebuild.
+pub fn data_dir() -> PathBuf {
+    dirs::data_dir().unwrap_or_else(|| PathBuf::from(".")).join(APP_DIR)
+}
+
+/// Logs, which are safe to delete at any time.
+pub fn cache_dir() -> PathBuf {
+    dirs::cache_dir().unwrap_or_else(|| PathBuf::from(".")).join(APP_DIR)
+}
+
+pub fn default_cache_path() -> PathBuf {
+    data_dir().join(CACHE_FILE)
+}
+
+/// Moves the cache and logs that older versions kept in `config_dir` to
+/// their own directories, skipping anything already present there. Returns
+/// the new paths of what was moved.
+pub fn migrate_legacy_files(config_dir: &Path, cache_path: &Path, log_dir: &Path) -> io::Result<Vec<PathBuf>> {
+    let mut moves = Vec::new();
+    let legacy_cache = config_dir.join(CACHE_FILE);
+    if legacy_cache != cache_path {
+        // The side files belong to the old database, so they only follow it.
+        if legacy_cache.is_file() && !cache_path.exists() {
+            for suffix in CACHE_SIDE_FILES {
+                moves.push((with_suffix(&legacy_cache, suffix), with_suffix(cache_path, suffix)));
+            }
+        }
+        moves.push((legacy_cache, cache_path.to_path_buf()));
+    }
+    if log_dir != config_dir {
+        let entries = match std::fs::read_dir(config_dir) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e),
+        };
+        for entry in entries {
+            let entry = entry?;
+            if entry.file_name().to_str().is_some_and(|name| name.starts_with(LOG_FILE_PREFIX)) {
+                moves.push((entry.path(), log_dir.join(entry.file_name())));
+            }
+        }
+    }
+
+    let mut moved = Vec::new();
+    for (from, to) in moves {
+        if !from.is_file() || to.exists() {
+            continue;
+        }
+        if let Some(parent) = to.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        move_file(&from, &to)?;
+        moved.push(to);
+    }
+    Ok(moved)
+}
+
+fn with_suffix(path: &Path, suffix: &str) -> PathBuf {
+    let mut path = path.as_os_str().to_owned();
+    path.push(suffix);
+    path.into()
+}
+
+/// `rename`, falling back to copy and delete across filesystems.
+fn move_file(from: &Path, to: &Path) -> io::Result<()> {
+    if std::fs::rename(from, to).is_ok() {
+        return Ok(());
+    }
+    std::fs::copy(from, to)?;
+    std::fs::remove_file(from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn moves_cache_and_logs_out_of_the_config_dir() {
+        let root = tempfile::tempdir().unwrap();
+        let config_dir = root.path().join("config");
+        let cache_path = root.path().join("data/cache.db");
+        let log_dir = root.path().join("cache");
+        std::fs::create_dir_all(&config_dir).unwrap();
+        for name in ["cache.db", "gcal-imp.log.2025-01-15", "config.toml", "history"] {
+            std::fs::write(config_dir.join(name), name).unwrap();
+        }
+
+        let moved = migrate_legacy_files(&config_dir, &cache_path, &log_dir).unwrap();
+
+        assert_eq!(moved, vec![cache_path.clone(), log_dir.join("gcal-imp.log.2025-01-15")]);
+        assert_eq!(std::fs::read_to_string(&cache_path).unwrap(), "cache.db");
+        assert!(!config_dir.join("cache.db").exists());
+        assert!(config_dir.join("config.toml").exists());
+        assert!(config_dir.join("history").exists());
+    }
+
+    #[test]
+    fn the_cache_side_files_move_with_it() {
+        let root = tempfile::tempdir().unwrap();
+        let config_dir = root.path().join("config");
+        let cache_path = root.path().join("data/cache.db");
+        std::fs::create_dir_all(&config_dir).unwrap();
+        for name in ["cache.db", "cache.db-wal", "cache.db-shm"] {
+            std::fs::write(config_dir.join(name), name).unwrap();
+        }
+
+        migrate_legacy_files(&config_dir, &cache_path, &config_dir).unwrap();
+
+        for name in ["cache.db", "cache.db-wal", "cache.db-shm"] {
+            assert_eq!(std::fs::read_to_string(root.path().join("data").join(name)).unwrap(), name);
+            assert!(!config_dir.join(name).exists());
+        }
+    }
+
+    #[test]
+    fn keeps_files_already_at_the_new_location() {
+        let root = tempfile::tempdir().unwrap();
+        let config_dir = root.path().join("config");
+        let cache_path = root.path().join("data/cache.db");
+        std::fs::create_dir_all(cache_path.parent().unwrap()).unwrap();
+        std::fs::create_dir_all(&config_dir).unwrap();
+        std::fs::write(config_dir.join("cache.db"), "old").unwrap();
+        std::fs::write(config_dir.join("cache.db-wal"), "old").unwrap();
+        std::fs::write(&cache_path, "new").unwrap();
+
+        let moved = migrate_legacy_files(&config_dir, &cache_path, &config_dir).unwrap();
+
+        assert!(moved.is_empty());
+        assert_eq!(std::fs::read_to_string(&cache_path).unwrap(), "new");
+        assert!(config_dir.join("cache.db").exists());
+        assert!(!cache_path.with_file_name("cache.db-wal").exists());
+    }
+
+    #[test]
+    fn a_missing_config_dir_is_nothing_to_migrate() {
+        let root = tempfile::tempdir().unwrap();
+        let missing = root.path().join("missing");
+
+        assert!(migrate_legacy_files(&missing, &root.path().join("cache.db"), root.path()).unwrap().is_empty());
+    }
+}
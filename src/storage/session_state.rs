@@ -0,0 +1,85 @@
+use std::path::{Path, PathBuf};
+
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+use crate::app::{AppState, ViewType};
+use crate::storage::paths;
+
+/// Where the last session left off, restored at the next launch.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SessionState {
+    pub view: String,
+    pub selected_date: NaiveDate,
+    pub calendar: String,
+}
+
+impl SessionState {
+    pub fn from_app(app: &AppState) -> Self {
+        Self {
+            view: app.view.name().to_string(),
+            selected_date: app.selected_date,
+            calendar: app.default_calendar.clone(),
+        }
+    }
+
+    pub fn apply(&self, app: &mut AppState) {
+        app.view = ViewType::from_config(&self.view);
+        app.selected_date = self.selected_date;
+        app.default_calendar = self.calendar.clone();
+    }
+
+    pub fn default_path() -> PathBuf {
+        paths::config_dir().join("state.json")
+    }
+
+    /// `None` on the first run, before anything was saved.
+    pub fn load(path: &Path) -> std::io::Result<Option<Self>> {
+        match std::fs::read_to_string(path) {
+            Ok(content) => Ok(Some(serde_json::from_str(&content)?)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> std::io::Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let content = serde_json::to_string_pretty(self)?;
+        std::fs::write(path, content)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips_through_a_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("state.json");
+        let mut app = AppState::new();
+        app.view = ViewType::Week;
+        app.selected_date = NaiveDate::from_ymd_opt(2025, 3, 14).unwrap();
+        app.default_calendar = "work@example.com".to_string();
+
+        assert_eq!(SessionState::load(&path).unwrap(), None);
+        SessionState::from_app(&app).save(&path).unwrap();
+
+        let mut restored = AppState::new();
+        SessionState::load(&path).unwrap().unwrap().apply(&mut restored);
+        assert_eq!(restored.view, ViewType::Week);
+        assert_eq!(restored.selected_date, app.selected_date);
+        assert_eq!(restored.default_calendar, "work@example.com");
+    }
+
+    #[test]
+    fn corrupt_state_is_an_error() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("state.json");
+        std::fs::write(&path, "{").unwrap();
+
+        assert!(SessionState::load(&path).is_err());
+    }
+}
@@ -0,0 +1,75 @@
+use std::collections::VecDeque;
+
+use chrono::{DateTime, Utc};
+
+use crate::app::MessageKind;
+
+pub const DEFAULT_ACTIVITY_CAPACITY: usize = 200;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ActivityEntry {
+    pub at: DateTime<Utc>,
+    pub kind: MessageKind,
+    pub text: String,
+}
+
+/// Recent syncs, failures and finished operations, oldest dropped first.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ActivityLog {
+    entries: VecDeque<ActivityEntry>,
+    capacity: usize,
+}
+
+impl Default for ActivityLog {
+    fn default() -> Self {
+        Self::with_capacity(DEFAULT_ACTIVITY_CAPACITY)
+    }
+}
+
+impl ActivityLog {
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self { entries: VecDeque::with_capacity(capacity), capacity }
+    }
+
+    pub fn push(&mut self, at: DateTime<Utc>, kind: MessageKind, text: impl Into<String>) {
+        if self.capacity == 0 {
+            return;
+        }
+        if self.entries.len() == self.capacity {
+            self.entries.pop_front();
+        }
+        self.entries.push_back(ActivityEntry { at, kind, text: text.into() });
+    }
+
+    /// Newest first.
+    pub fn entries(&self) -> impl Iterator<Item = &ActivityEntry> {
+        self.entries.iter().rev()
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, TimeZone};
+
+    #[test]
+    fn drops_the_oldest_entry_when_full() {
+        let start = Utc.with_ymd_and_hms(2025, 1, 15, 9, 0, 0).unwrap();
+        let mut log = ActivityLog::with_capacity(2);
+
+        for i in 0..3 {
+            log.push(start + Duration::minutes(i), MessageKind::Info, format!("entry {}", i));
+        }
+
+        let texts: Vec<&str> = log.entries().map(|entry| entry.text.as_str()).collect();
+        assert_eq!(texts, vec!["entry 2", "entry 1"]);
+    }
+}
@@ -1,4 +1,5 @@
 use crate::calendar::Event;
+use crate::storage::cache::LocalEdit;
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum ResolutionStrategy {
@@ -24,17 +25,15 @@ impl Conflict {
     }
 }
 
-pub fn detect_conflict(local: &Event, remote: &Event) -> Option<Conflict> {
-    if local.id != remote.id {
+/// A conflict when the server changed the event after the version `local`
+/// was based on.
+pub fn detect_conflict(local: &LocalEdit, remote: &Event) -> Option<Conflict> {
+    if local.event.id != remote.id {
         return None;
     }
 
-    if local.last_modified == remote.last_modified {
-        return None;
-    }
-
-    if has_local_modifications(local) && remote.last_modified > local.last_modified {
-        return Some(Conflict::new(local.clone(), remote.clone()));
+    if remote.last_modified > local.event.last_modified {
+        return Some(Conflict::new(local.event.clone(), remote.clone()));
     }
 
     None
@@ -47,15 +46,34 @@ pub fn resolve_conflict(
 ) -> Event {
     match strategy {
         ResolutionStrategy::ServerWins => remote.clone(),
-        ResolutionStrategy::LocalWins => local.clone(),
+        // Overwrites the server's version, so it is checked against that one.
+        ResolutionStrategy::LocalWins => Event { etag: remote.etag.clone(), ..local.clone() },
         ResolutionStrategy::Merge => merge_events(local, remote),
     }
 }
 
-fn has_local_modifications(event: &Event) -> bool {
-    event.description.as_ref()
-        .map(|d| d.contains("_local_modified"))
-        .unwrap_or(false)
+/// What to do with unpushed local edits after a fetch.
+#[derive(Debug, Default, PartialEq)]
+pub struct Reconciliation {
+    /// Edits whose event did not change on the server; safe to push.
+    pub to_push: Vec<Event>,
+    pub conflicts: Vec<Conflict>,
+}
+
+/// Checks each cached edit against the fetched `remote` events. Edits whose
+/// event was not fetched are left for a later sync.
+pub fn reconcile(local_edits: &[LocalEdit], remote: &[Event]) -> Reconciliation {
+    let mut result = Reconciliation::default();
+    for local in local_edits {
+        let Some(remote) = remote.iter().find(|r| r.id == local.event.id) else {
+            continue;
+        };
+        match detect_conflict(local, remote) {
+            Some(conflict) => result.conflicts.push(conflict),
+            None => result.to_push.push(local.event.clone()),
+        }
+    }
+    result
 }
 
 fn merge_events(local: &Event, remote: &Event) -> Event {
@@ -80,7 +98,7 @@ fn merge_events(local: &Event, remote: &Event) -> Event {
 mod tests {
     use super::*;
     use chrono::Utc;
-    use crate::calendar::{EventStatus, DEFAULT_CALENDAR_ID};
+    use crate::calendar::{EventStatus, Transparency, Visibility, DEFAULT_CALENDAR_ID};
 
     fn create_event(id: &str, title: &str, last_modified_seconds: i64) -> Event {
         use chrono::TimeZone;
@@ -99,18 +117,30 @@ mod tests {
             status: EventStatus::Confirmed,
             last_modified: Utc.timestamp_opt(last_modified_seconds, 0).unwrap(),
             html_link: None,
+            time_zone: None,
+            conference_url: None,
+            color_id: None,
+            color: None,
+            self_response: None,
+            attendee_responses: Default::default(),
+            visibility: Visibility::Default,
+            transparency: Transparency::Opaque,
+            etag: None,
+            organizer: None,
+            creator: None,
         }
     }
 
-    fn create_event_with_local_modification(id: &str, title: &str, last_modified_seconds: i64) -> Event {
-        let mut event = create_event(id, title, last_modified_seconds);
-        event.description = Some("_local_modified".to_string());
-        event
+    fn create_local_edit(id: &str, title: &str, base_modified_seconds: i64) -> LocalEdit {
+        LocalEdit {
+            event: create_event(id, title, base_modified_seconds),
+            local_modified: Utc::now(),
+        }
     }
 
     #[test]
     fn no_conflict_when_timestamps_match() {
-        let local = create_event("event1", "Meeting", 100);
+        let local = create_local_edit("event1", "Meeting", 100);
         let remote = create_event("event1", "Meeting", 100);
 
         let conflict = detect_conflict(&local, &remote);
@@ -119,9 +149,9 @@ mod tests {
     }
 
     #[test]
-    fn no_conflict_when_no_local_modifications() {
-        let local = create_event("event1", "Meeting", 100);
-        let remote = create_event("event1", "Meeting Updated", 150);
+    fn no_conflict_for_a_different_event() {
+        let local = create_local_edit("event1", "Meeting", 100);
+        let remote = create_event("event2", "Meeting Updated", 150);
 
         let conflict = detect_conflict(&local, &remote);
 
@@ -130,7 +160,7 @@ mod tests {
 
     #[test]
     fn conflict_detected_when_both_modified() {
-        let local = create_event_with_local_modification("event1", "Local Title", 100);
+        let local = create_local_edit("event1", "Local Title", 100);
         let remote = create_event("event1", "Remote Title", 150);
 
         let conflict = detect_conflict(&local, &remote);
@@ -162,6 +192,18 @@ mod tests {
         assert_eq!(resolved.title, "Local");
     }
 
+    #[test]
+    fn local_wins_takes_the_server_etag() {
+        let mut local = create_event("event1", "Local", 100);
+        local.etag = Some("\"1\"".to_string());
+        let mut remote = create_event("event1", "Remote", 150);
+        remote.etag = Some("\"2\"".to_string());
+
+        let resolved = resolve_conflict(&local, &remote, ResolutionStrategy::LocalWins);
+
+        assert_eq!(resolved.etag, remote.etag);
+    }
+
     #[test]
     fn merge_strategy_combines_non_conflicting_fields() {
         let mut local = create_event("event1", "Local Title", 100);
@@ -196,4 +238,21 @@ mod tests {
 
         assert_eq!(resolved.last_modified, remote.last_modified);
     }
+
+    #[test]
+    fn reconcile_pushes_unchanged_and_flags_changed_events() {
+        let mut unchanged = create_local_edit("event1", "Local", 100);
+        unchanged.event.description = Some("Agenda".to_string());
+        let changed = create_local_edit("event2", "Local", 100);
+        let unfetched = create_local_edit("event3", "Local", 100);
+        let remote = vec![create_event("event1", "Server", 100), create_event("event2", "Server", 150)];
+
+        let result = reconcile(&[unchanged, changed, unfetched], &remote);
+
+        assert_eq!(result.to_push.len(), 1);
+        assert_eq!(result.to_push[0].id, "event1");
+        assert_eq!(result.to_push[0].description.as_deref(), Some("Agenda"));
+        assert_eq!(result.conflicts.len(), 1);
+        assert_eq!(result.conflicts[0].event_id, "event2");
+    }
 }
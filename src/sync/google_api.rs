@@ -1,5 +1,7 @@
-use crate::calendar::{Event, EventStatus};
-use chrono::{DateTime, NaiveDate, Utc};
+use crate::calendar::{AccessRole, Calendar, Event, EventStatus, ResponseStatus, Transparency, Visibility};
+use crate::sync::push::{ChannelRequest, ChannelResponse, WatchChannel};
+use std::collections::HashMap;
+use chrono::{DateTime, NaiveDate, NaiveTime, Utc};
 use thiserror::Error;
 use serde::{Deserialize, Serialize};
 use async_trait::async_trait;
@@ -18,13 +20,16 @@ pub enum ApiError {
     AuthenticationFailed,
     #[error("Parse error: {0}")]
     ParseError(String),
+    #[error("Event changed on server: {0}")]
+    PreconditionFailed(String),
 }
 
-async fn check_response_status(response: reqwest::Response, context: &str) -> Result<reqwest::Response, ApiError> {
+pub(crate) async fn check_response_status(response: reqwest::Response, context: &str) -> Result<reqwest::Response, ApiError> {
     let status = response.status();
     match status.as_u16() {
         401 => Err(ApiError::AuthenticationFailed),
         404 => Err(ApiError::NotFound(context.to_string())),
+        412 => Err(ApiError::PreconditionFailed(context.to_string())),
         429 => Err(ApiError::RateLimited),
         _ if !status.is_success() => {
             let body = response.text().await.unwrap_or_default();
@@ -61,6 +66,69 @@ struct GoogleEvent {
     updated: Option<String>,
     #[serde(rename = "htmlLink")]
     html_link: Option<String>,
+    #[serde(rename = "conferenceData", default, skip_serializing)]
+    conference_data: Option<GoogleConferenceData>,
+    #[serde(rename = "colorId", default, skip_serializing_if = "Option::is_none")]
+    color_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    attendees: Vec<GoogleAttendee>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    visibility: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    transparency: Option<String>,
+    #[serde(default, skip_serializing)]
+    etag: Option<String>,
+    #[serde(default, skip_serializing)]
+    organizer: Option<GooglePerson>,
+    #[serde(default, skip_serializing)]
+    creator: Option<GooglePerson>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct GoogleAttendee {
+    email: Option<String>,
+    #[serde(rename = "responseStatus", skip_serializing_if = "Option::is_none")]
+    response_status: Option<String>,
+    #[serde(rename = "self", default, skip_serializing)]
+    is_self: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct GooglePerson {
+    email: Option<String>,
+    #[serde(rename = "displayName")]
+    display_name: Option<String>,
+}
+
+impl GooglePerson {
+    /// "Name <email>", or whichever of the two is set.
+    fn describe(self) -> Option<String> {
+        match (self.display_name, self.email) {
+            (Some(name), Some(email)) => Some(format!("{} <{}>", name, email)),
+            (name, email) => name.or(email),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct GoogleConferenceData {
+    #[serde(rename = "entryPoints", default)]
+    entry_points: Vec<GoogleEntryPoint>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GoogleEntryPoint {
+    #[serde(rename = "entryPointType")]
+    entry_point_type: String,
+    uri: String,
+}
+
+impl GoogleConferenceData {
+    fn video_uri(self) -> Option<String> {
+        self.entry_points.into_iter()
+            .find(|ep| ep.entry_point_type == "video")
+            .map(|ep| ep.uri)
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -68,11 +136,153 @@ struct GoogleDateTime {
     #[serde(rename = "dateTime")]
     date_time: Option<String>,
     date: Option<String>,
+    #[serde(rename = "timeZone", default, skip_serializing_if = "Option::is_none")]
+    time_zone: Option<String>,
+}
+
+impl GoogleDateTime {
+    /// All-day events carry only a `date`; they are stored at UTC midnight
+    /// with an exclusive end date, as Google sends them.
+    fn to_utc(&self, which: &str) -> Result<DateTime<Utc>, ApiError> {
+        if let Some(date_time) = &self.date_time {
+            return DateTime::parse_from_rfc3339(date_time)
+                .map(|dt| dt.with_timezone(&Utc))
+                .map_err(|e| ApiError::ParseError(format!("Invalid {} time: {}", which, e)));
+        }
+        let date = self.date.as_ref()
+            .ok_or_else(|| ApiError::ParseError(format!("Missing {} dateTime", which)))?;
+        NaiveDate::parse_from_str(date, "%Y-%m-%d")
+            .map(|date| date.and_time(NaiveTime::MIN).and_utc())
+            .map_err(|e| ApiError::ParseError(format!("Invalid {} date: {}", which, e)))
+    }
+
+    fn from_utc(value: &DateTime<Utc>, all_day: bool, time_zone: Option<String>) -> Self {
+        if all_day {
+            Self { date_time: None, date: Some(value.date_naive().to_string()), time_zone: None }
+        } else {
+            Self { date_time: Some(value.to_rfc3339()), date: None, time_zone }
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct GoogleColorDefinition {
+    background: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct ColorsResponse {
+    #[serde(default)]
+    event: HashMap<String, GoogleColorDefinition>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CalendarListEntry {
+    #[serde(default)]
+    id: String,
+    #[serde(rename = "backgroundColor")]
+    background_color: Option<String>,
+    #[serde(default)]
+    summary: Option<String>,
+    #[serde(default)]
+    primary: bool,
+    #[serde(rename = "accessRole", default)]
+    access_role: Option<String>,
+}
+
+impl CalendarListEntry {
+    fn into_calendar(self) -> Calendar {
+        Calendar {
+            name: self.summary.unwrap_or_else(|| self.id.clone()),
+            color: self.background_color.unwrap_or_default(),
+            is_primary: self.primary,
+            // Google always sends a role; a missing one is treated as the most restrictive.
+            access_role: self.access_role.as_deref()
+                .and_then(AccessRole::from_google)
+                .unwrap_or(AccessRole::Reader),
+            id: self.id,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct CalendarListResponse {
+    items: Option<Vec<CalendarListEntry>>,
+}
+
+/// Event colorId to `#rrggbb` background, from the `/colors` endpoint.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ColorPalette {
+    pub event: HashMap<String, String>,
+}
+
+impl ColorPalette {
+    /// Fills `event.color` from its colorId, else from `calendar_color`.
+    pub fn apply(&self, event: &mut Event, calendar_color: Option<&str>) {
+        event.color = event.color_id.as_ref()
+            .and_then(|id| self.event.get(id))
+            .map(String::as_str)
+            .or(calendar_color)
+            .map(str::to_string);
+    }
 }
 
 #[derive(Debug, Deserialize)]
 struct EventListResponse {
     items: Option<Vec<GoogleEvent>>,
+    #[serde(rename = "nextPageToken")]
+    next_page_token: Option<String>,
+}
+
+/// An event named by `:open`: a bare id, or a Google Calendar link whose
+/// `eid` carries the event id and, usually, its calendar.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EventReference {
+    pub event_id: String,
+    pub calendar_id: Option<String>,
+}
+
+impl EventReference {
+    pub fn parse(input: &str) -> Option<Self> {
+        let input = input.trim();
+        if input.is_empty() {
+            return None;
+        }
+        if !input.contains("://") {
+            return Some(Self { event_id: input.to_string(), calendar_id: None });
+        }
+
+        let eid = match input.split_once("eid=") {
+            Some((_, rest)) => rest.split(['&', '#']).next().unwrap_or_default(),
+            None => input.split(['?', '#']).next().unwrap_or_default()
+                .trim_end_matches('/')
+                .rsplit('/')
+                .next()
+                .unwrap_or_default(),
+        };
+        let eid = urlencoding::decode(eid).map(|eid| eid.into_owned()).ok()?;
+        Some(Self::from_eid(&eid))
+    }
+
+    /// `eid` is base64 of "<event id> <calendar id>", with gmail.com
+    /// shortened to "m". Anything that does not decode is taken as the id.
+    fn from_eid(eid: &str) -> Self {
+        use base64::Engine;
+        let normalized: String = eid.trim_end_matches('=').replace('+', "-").replace('/', "_");
+        let decoded = base64::engine::general_purpose::URL_SAFE_NO_PAD.decode(normalized)
+            .ok()
+            .and_then(|bytes| String::from_utf8(bytes).ok());
+        match decoded.as_deref().and_then(|text| text.split_once(' ')) {
+            Some((event_id, calendar_id)) => {
+                let calendar_id = match calendar_id.strip_suffix("@m") {
+                    Some(user) => format!("{}@gmail.com", user),
+                    None => calendar_id.to_string(),
+                };
+                Self { event_id: event_id.to_string(), calendar_id: Some(calendar_id) }
+            }
+            None => Self { event_id: eid.to_string(), calendar_id: None },
+        }
+    }
 }
 
 #[async_trait]
@@ -83,24 +293,54 @@ pub trait CalendarApi {
         date_range: DateRange,
     ) -> Result<Vec<Event>, ApiError>;
 
+    /// One event by id, wherever it falls in time.
+    async fn get_event(&self, calendar_id: &str, event_id: &str) -> Result<Event, ApiError>;
+
     async fn create_event(
         &self,
         calendar_id: &str,
         event: &Event,
     ) -> Result<CreatedEventInfo, ApiError>;
 
+    /// Sends `If-Match` when the event has an etag and returns the new one.
     async fn update_event(
         &self,
         calendar_id: &str,
         event_id: &str,
         event: &Event,
-    ) -> Result<(), ApiError>;
+    ) -> Result<Option<String>, ApiError>;
 
     async fn delete_event(
         &self,
         calendar_id: &str,
         event_id: &str,
+        etag: Option<&str>,
     ) -> Result<(), ApiError>;
+
+    async fn fetch_colors(&self) -> Result<ColorPalette, ApiError>;
+
+    async fn fetch_calendar_color(&self, calendar_id: &str) -> Result<Option<String>, ApiError>;
+
+    /// The calendars on the user's list, with their access role.
+    async fn fetch_calendars(&self) -> Result<Vec<Calendar>, ApiError>;
+
+    /// Registers a push channel that posts event changes to `address`.
+    async fn watch_events(
+        &self,
+        calendar_id: &str,
+        channel_id: &str,
+        token: &str,
+        address: &str,
+    ) -> Result<WatchChannel, ApiError>;
+
+    async fn stop_channel(&self, channel: &WatchChannel) -> Result<(), ApiError>;
+}
+
+/// Percent-encodes an id for a URL path. Calendar ids such as
+/// `en.usa#holiday@group.v.calendar.google.com` would otherwise end the path
+/// at the `#`.
+fn segment(id: &str) -> std::borrow::Cow<'_, str> {
+    urlencoding::encode(id)
 }
 
 pub struct GoogleCalendarClient {
@@ -130,18 +370,10 @@ impl GoogleCalendarClient {
     }
 
     fn convert_from_google_event(&self, ge: GoogleEvent, calendar_id: &str) -> Result<Event, ApiError> {
-        let start_str = ge.start.date_time
-            .ok_or_else(|| ApiError::ParseError("Missing start dateTime".to_string()))?;
-        let end_str = ge.end.date_time
-            .ok_or_else(|| ApiError::ParseError("Missing end dateTime".to_string()))?;
-
-        let start = DateTime::parse_from_rfc3339(&start_str)
-            .map_err(|e| ApiError::ParseError(format!("Invalid start time: {}", e)))?
-            .with_timezone(&Utc);
-
-        let end = DateTime::parse_from_rfc3339(&end_str)
-            .map_err(|e| ApiError::ParseError(format!("Invalid end time: {}", e)))?
-            .with_timezone(&Utc);
+        let time_zone = ge.start.time_zone.clone();
+        let all_day = ge.start.date_time.is_none() && ge.start.date.is_some();
+        let start = ge.start.to_utc("start")?;
+        let end = ge.end.to_utc("end")?;
 
         let status = match ge.status.as_deref() {
             Some("confirmed") => EventStatus::Confirmed,
@@ -158,6 +390,15 @@ impl GoogleCalendarClient {
             Utc::now()
         };
 
+        let self_response = ge.attendees.iter()
+            .find(|a| a.is_self)
+            .and_then(|a| a.response_status.as_deref())
+            .and_then(ResponseStatus::from_google);
+        let attendee_responses = ge.attendees.iter()
+            .filter_map(|a| Some((a.email.as_ref()?.to_lowercase(), ResponseStatus::from_google(a.response_status.as_deref()?)?)))
+            .collect();
+        let attendees = ge.attendees.into_iter().filter_map(|a| a.email).collect();
+
         Ok(Event {
             id: ge.id.ok_or_else(|| ApiError::ParseError("Missing event id".to_string()))?,
             calendar_id: calendar_id.to_string(),
@@ -166,12 +407,23 @@ impl GoogleCalendarClient {
             location: ge.location,
             start,
             end,
-            all_day: false,
-            attendees: vec![],
+            all_day,
+            attendees,
             reminders: vec![],
             status,
             last_modified,
             html_link: ge.html_link,
+            time_zone,
+            conference_url: ge.conference_data.and_then(GoogleConferenceData::video_uri),
+            color_id: ge.color_id,
+            color: None,
+            self_response,
+            attendee_responses,
+            visibility: ge.visibility.as_deref().map_or(Visibility::Default, Visibility::from_google),
+            transparency: ge.transparency.as_deref().map_or(Transparency::Opaque, Transparency::from_google),
+            etag: ge.etag,
+            organizer: ge.organizer.and_then(GooglePerson::describe),
+            creator: ge.creator.and_then(GooglePerson::describe),
         })
     }
 
@@ -181,14 +433,8 @@ impl GoogleCalendarClient {
             summary: Some(event.title.clone()),
             description: event.description.clone(),
             location: event.location.clone(),
-            start: GoogleDateTime {
-                date_time: Some(event.start.to_rfc3339()),
-                date: None,
-            },
-            end: GoogleDateTime {
-                date_time: Some(event.end.to_rfc3339()),
-                date: None,
-            },
+            start: GoogleDateTime::from_utc(&event.start, event.all_day, event.time_zone.clone()),
+            end: GoogleDateTime::from_utc(&event.end, event.all_day, event.time_zone.clone()),
             status: Some(match event.status {
                 EventStatus::Confirmed => "confirmed",
                 EventStatus::Tentative => "tentative",
@@ -196,6 +442,20 @@ impl GoogleCalendarClient {
             }.to_string()),
             updated: Some(event.last_modified.to_rfc3339()),
             html_link: None,
+            conference_data: None,
+            color_id: event.color_id.clone(),
+            attendees: event.attendees.iter()
+                .map(|email| GoogleAttendee {
+                    email: Some(email.clone()),
+                    response_status: event.attendee_responses.get(&email.to_lowercase()).map(|r| r.as_google().to_string()),
+                    is_self: false,
+                })
+                .collect(),
+            visibility: Some(event.visibility.as_google().to_string()),
+            transparency: Some(event.transparency.as_google().to_string()),
+            etag: None,
+            organizer: None,
+            creator: None,
         }
     }
 }
@@ -217,33 +477,55 @@ impl CalendarApi for GoogleCalendarClient {
             .and_utc()
             .to_rfc3339();
 
-        let url = format!("{}/calendars/{}/events", self.base_url, calendar_id);
+        let url = format!("{}/calendars/{}/events", self.base_url, segment(calendar_id));
 
         tracing::info!("Fetching events from {} to {}", date_range.start, date_range.end);
 
+        let mut events = Vec::new();
+        let mut page_token: Option<String> = None;
+        loop {
+            let mut request = self.client
+                .get(&url)
+                .bearer_auth(&self.access_token)
+                .query(&[
+                    ("timeMin", time_min.as_str()),
+                    ("timeMax", time_max.as_str()),
+                    ("singleEvents", "true"),
+                    ("orderBy", "startTime"),
+                ]);
+            if let Some(token) = &page_token {
+                request = request.query(&[("pageToken", token.as_str())]);
+            }
+
+            let response = check_response_status(request.send().await?, calendar_id).await?;
+            let event_list: EventListResponse = response.json().await?;
+            events.extend(event_list.items
+                .unwrap_or_default()
+                .into_iter()
+                .filter_map(|ge| self.convert_from_google_event(ge, calendar_id).ok()));
+
+            page_token = event_list.next_page_token;
+            if page_token.is_none() {
+                break;
+            }
+        }
+
+        tracing::info!("Fetched {} events successfully", events.len());
+        Ok(events)
+    }
+
+    async fn get_event(&self, calendar_id: &str, event_id: &str) -> Result<Event, ApiError> {
+        let url = format!("{}/calendars/{}/events/{}", self.base_url, segment(calendar_id), segment(event_id));
+
         let response = self.client
             .get(&url)
             .bearer_auth(&self.access_token)
-            .query(&[
-                ("timeMin", time_min.as_str()),
-                ("timeMax", time_max.as_str()),
-                ("singleEvents", "true"),
-                ("orderBy", "startTime"),
-            ])
             .send()
             .await?;
 
-        let response = check_response_status(response, calendar_id).await?;
-        let event_list: EventListResponse = response.json().await?;
-
-        let events: Vec<Event> = event_list.items
-            .unwrap_or_default()
-            .into_iter()
-            .filter_map(|ge| self.convert_from_google_event(ge, calendar_id).ok())
-            .collect();
-
-        tracing::info!("Fetched {} events successfully", events.len());
-        Ok(events)
+        let response = check_response_status(response, event_id).await?;
+        let google_event: GoogleEvent = response.json().await?;
+        self.convert_from_google_event(google_event, calendar_id)
     }
 
     async fn create_event(
@@ -251,7 +533,7 @@ impl CalendarApi for GoogleCalendarClient {
         calendar_id: &str,
         event: &Event,
     ) -> Result<CreatedEventInfo, ApiError> {
-        let url = format!("{}/calendars/{}/events", self.base_url, calendar_id);
+        let url = format!("{}/calendars/{}/events", self.base_url, segment(calendar_id));
         let google_event = self.convert_to_google_event(event, false);
 
         tracing::info!("Creating event: {} on {}", event.title, event.start);
@@ -280,38 +562,125 @@ impl CalendarApi for GoogleCalendarClient {
         calendar_id: &str,
         event_id: &str,
         event: &Event,
-    ) -> Result<(), ApiError> {
-        let url = format!("{}/calendars/{}/events/{}", self.base_url, calendar_id, event_id);
+    ) -> Result<Option<String>, ApiError> {
+        let url = format!("{}/calendars/{}/events/{}", self.base_url, segment(calendar_id), segment(event_id));
         let google_event = self.convert_to_google_event(event, true);
 
         tracing::info!("Updating event {}: {}", event_id, event.title);
         tracing::debug!("PUT {} with payload: {:?}", url, google_event);
 
-        let response = self.client
+        let mut request = self.client
             .put(&url)
             .bearer_auth(&self.access_token)
-            .json(&google_event)
-            .send()
-            .await?;
+            .json(&google_event);
+        if let Some(etag) = &event.etag {
+            request = request.header(reqwest::header::IF_MATCH, etag);
+        }
+        let response = request.send().await?;
 
-        check_response_status(response, event_id).await?;
-        Ok(())
+        let response = check_response_status(response, event_id).await?;
+        let updated: GoogleEvent = response.json().await?;
+        Ok(updated.etag)
     }
 
     async fn delete_event(
         &self,
         calendar_id: &str,
         event_id: &str,
+        etag: Option<&str>,
     ) -> Result<(), ApiError> {
-        let url = format!("{}/calendars/{}/events/{}", self.base_url, calendar_id, event_id);
+        let url = format!("{}/calendars/{}/events/{}", self.base_url, segment(calendar_id), segment(event_id));
 
-        let response = self.client
+        let mut request = self.client
             .delete(&url)
+            .bearer_auth(&self.access_token);
+        if let Some(etag) = etag {
+            request = request.header(reqwest::header::IF_MATCH, etag);
+        }
+        let response = request.send().await?;
+
+        check_response_status(response, event_id).await?;
+        Ok(())
+    }
+
+    async fn fetch_colors(&self) -> Result<ColorPalette, ApiError> {
+        let url = format!("{}/colors", self.base_url);
+
+        let response = self.client
+            .get(&url)
             .bearer_auth(&self.access_token)
             .send()
             .await?;
 
-        check_response_status(response, event_id).await?;
+        let response = check_response_status(response, "colors").await?;
+        let colors: ColorsResponse = response.json().await?;
+
+        Ok(ColorPalette {
+            event: colors.event.into_iter().map(|(id, color)| (id, color.background)).collect(),
+        })
+    }
+
+    async fn fetch_calendar_color(&self, calendar_id: &str) -> Result<Option<String>, ApiError> {
+        let url = format!("{}/users/me/calendarList/{}", self.base_url, segment(calendar_id));
+
+        let response = self.client
+            .get(&url)
+            .bearer_auth(&self.access_token)
+            .send()
+            .await?;
+
+        let response = check_response_status(response, calendar_id).await?;
+        let entry: CalendarListEntry = response.json().await?;
+        Ok(entry.background_color)
+    }
+
+    async fn fetch_calendars(&self) -> Result<Vec<Calendar>, ApiError> {
+        let url = format!("{}/users/me/calendarList", self.base_url);
+
+        let response = self.client
+            .get(&url)
+            .bearer_auth(&self.access_token)
+            .send()
+            .await?;
+
+        let response = check_response_status(response, "calendarList").await?;
+        let list: CalendarListResponse = response.json().await?;
+        Ok(list.items.unwrap_or_default().into_iter().map(CalendarListEntry::into_calendar).collect())
+    }
+
+    async fn watch_events(
+        &self,
+        calendar_id: &str,
+        channel_id: &str,
+        token: &str,
+        address: &str,
+    ) -> Result<WatchChannel, ApiError> {
+        let url = format!("{}/calendars/{}/events/watch", self.base_url, segment(calendar_id));
+        let request = ChannelRequest { id: channel_id, kind: "web_hook", address, token };
+
+        let response = self.client
+            .post(&url)
+            .bearer_auth(&self.access_token)
+            .json(&request)
+            .send()
+            .await?;
+
+        let response = check_response_status(response, calendar_id).await?;
+        let created: ChannelResponse = response.json().await?;
+        Ok(created.into_channel(calendar_id, token))
+    }
+
+    async fn stop_channel(&self, channel: &WatchChannel) -> Result<(), ApiError> {
+        let url = format!("{}/channels/stop", self.base_url);
+
+        let response = self.client
+            .post(&url)
+            .bearer_auth(&self.access_token)
+            .json(&serde_json::json!({ "id": channel.id, "resourceId": channel.resource_id }))
+            .send()
+            .await?;
+
+        check_response_status(response, &channel.id).await?;
         Ok(())
     }
 }
@@ -324,6 +693,16 @@ mod tests {
         NaiveDate::from_ymd_opt(year, month, day).unwrap()
     }
 
+    #[test]
+    fn event_references_come_from_ids_and_links() {
+        assert_eq!(EventReference::parse(" abc123 "), Some(EventReference { event_id: "abc123".to_string(), calendar_id: None }));
+        assert_eq!(EventReference::parse(""), None);
+
+        let expected = EventReference { event_id: "7pq9e1u2".to_string(), calendar_id: Some("sam@gmail.com".to_string()) };
+        assert_eq!(EventReference::parse("https://www.google.com/calendar/event?eid=N3BxOWUxdTIgc2FtQG0&ctz=Europe/Berlin"), Some(expected.clone()));
+        assert_eq!(EventReference::parse("https://calendar.google.com/calendar/u/0/r/eventedit/N3BxOWUxdTIgc2FtQG0"), Some(expected));
+    }
+
     #[test]
     fn date_range_calculates_days() {
         let range = DateRange::new(
@@ -344,6 +723,203 @@ mod tests {
         assert_eq!(range.days(), 0);
     }
 
+    #[test]
+    fn time_zone_survives_round_trip() {
+        let client = GoogleCalendarClient::new("token".to_string());
+        let json = r#"{
+            "id": "evt1",
+            "summary": "Standup",
+            "start": {"dateTime": "2025-01-15T09:00:00-08:00", "timeZone": "America/Los_Angeles"},
+            "end": {"dateTime": "2025-01-15T09:15:00-08:00", "timeZone": "America/Los_Angeles"}
+        }"#;
+        let ge: GoogleEvent = serde_json::from_str(json).unwrap();
+
+        let event = client.convert_from_google_event(ge, "primary").unwrap();
+        let outgoing = client.convert_to_google_event(&event, true);
+
+        assert_eq!(event.time_zone.as_deref(), Some("America/Los_Angeles"));
+        assert_eq!(outgoing.start.time_zone.as_deref(), Some("America/Los_Angeles"));
+        assert_eq!(outgoing.end.time_zone.as_deref(), Some("America/Los_Angeles"));
+    }
+
+    #[test]
+    fn video_entry_point_becomes_conference_url() {
+        let client = GoogleCalendarClient::new("token".to_string());
+        let json = r#"{
+            "id": "evt1",
+            "summary": "Standup",
+            "start": {"dateTime": "2025-01-15T09:00:00Z"},
+            "end": {"dateTime": "2025-01-15T09:15:00Z"},
+            "conferenceData": {"entryPoints": [
+                {"entryPointType": "phone", "uri": "tel:+1-555-0100"},
+                {"entryPointType": "video", "uri": "https://meet.google.com/abc-defg-hij"}
+            ]}
+        }"#;
+        let ge: GoogleEvent = serde_json::from_str(json).unwrap();
+
+        let event = client.convert_from_google_event(ge, "primary").unwrap();
+
+        assert_eq!(event.conference_url.as_deref(), Some("https://meet.google.com/abc-defg-hij"));
+    }
+
+    #[test]
+    fn color_id_round_trips_and_resolves_from_palette() {
+        let client = GoogleCalendarClient::new("token".to_string());
+        let json = r#"{
+            "id": "evt1",
+            "summary": "Standup",
+            "colorId": "11",
+            "start": {"dateTime": "2025-01-15T09:00:00Z"},
+            "end": {"dateTime": "2025-01-15T09:15:00Z"}
+        }"#;
+        let ge: GoogleEvent = serde_json::from_str(json).unwrap();
+        let mut event = client.convert_from_google_event(ge, "primary").unwrap();
+        let palette = ColorPalette {
+            event: HashMap::from([("11".to_string(), "#dc2127".to_string())]),
+        };
+
+        palette.apply(&mut event, Some("#9fe1e7"));
+        assert_eq!(event.color.as_deref(), Some("#dc2127"));
+        assert_eq!(client.convert_to_google_event(&event, true).color_id.as_deref(), Some("11"));
+
+        event.color_id = None;
+        palette.apply(&mut event, Some("#9fe1e7"));
+        assert_eq!(event.color.as_deref(), Some("#9fe1e7"));
+    }
+
+    #[test]
+    fn attendees_and_own_response_are_parsed() {
+        let client = GoogleCalendarClient::new("token".to_string());
+        let json = r#"{
+            "id": "evt1",
+            "summary": "Planning",
+            "start": {"dateTime": "2025-01-15T09:00:00Z"},
+            "end": {"dateTime": "2025-01-15T10:00:00Z"},
+            "attendees": [
+                {"email": "sam@example.com", "responseStatus": "accepted"},
+                {"email": "me@example.com", "responseStatus": "declined", "self": true}
+            ]
+        }"#;
+        let ge: GoogleEvent = serde_json::from_str(json).unwrap();
+        let event = client.convert_from_google_event(ge, "primary").unwrap();
+
+        assert_eq!(event.attendees, vec!["sam@example.com", "me@example.com"]);
+        assert_eq!(event.self_response, Some(ResponseStatus::Declined));
+        assert!(event.is_declined());
+
+        let payload = serde_json::to_value(client.convert_to_google_event(&event, false)).unwrap();
+        assert_eq!(payload["attendees"], serde_json::json!([
+            {"email": "sam@example.com", "responseStatus": "accepted"},
+            {"email": "me@example.com", "responseStatus": "declined"},
+        ]));
+    }
+
+    #[test]
+    fn visibility_and_transparency_round_trip() {
+        let client = GoogleCalendarClient::new("token".to_string());
+        let json = r#"{
+            "id": "evt1",
+            "summary": "Out of office",
+            "visibility": "private",
+            "transparency": "transparent",
+            "start": {"dateTime": "2025-01-15T09:00:00Z"},
+            "end": {"dateTime": "2025-01-15T17:00:00Z"}
+        }"#;
+        let ge: GoogleEvent = serde_json::from_str(json).unwrap();
+        let event = client.convert_from_google_event(ge, "primary").unwrap();
+
+        assert_eq!(event.visibility, Visibility::Private);
+        assert_eq!(event.transparency, Transparency::Transparent);
+        assert!(!event.is_busy());
+
+        let back = client.convert_to_google_event(&event, false);
+        assert_eq!(back.visibility.as_deref(), Some("private"));
+        assert_eq!(back.transparency.as_deref(), Some("transparent"));
+    }
+
+    #[test]
+    fn etag_is_kept_but_not_sent_in_the_body() {
+        let client = GoogleCalendarClient::new("token".to_string());
+        let json = r#"{
+            "id": "evt1",
+            "etag": "\"3181161784712000\"",
+            "summary": "Standup",
+            "start": {"dateTime": "2025-01-15T09:00:00Z"},
+            "end": {"dateTime": "2025-01-15T09:15:00Z"}
+        }"#;
+        let ge: GoogleEvent = serde_json::from_str(json).unwrap();
+        let event = client.convert_from_google_event(ge, "primary").unwrap();
+
+        assert_eq!(event.etag.as_deref(), Some("\"3181161784712000\""));
+        let payload = serde_json::to_value(client.convert_to_google_event(&event, true)).unwrap();
+        assert!(payload.get("etag").is_none());
+    }
+
+    #[test]
+    fn organizer_and_creator_are_read_but_not_sent() {
+        let client = GoogleCalendarClient::new("token".to_string());
+        let json = r#"{
+            "id": "evt1",
+            "summary": "Planning",
+            "start": {"dateTime": "2025-01-15T09:00:00Z"},
+            "end": {"dateTime": "2025-01-15T10:00:00Z"},
+            "organizer": {"email": "team@group.calendar.google.com", "displayName": "Team"},
+            "creator": {"email": "sam@example.com"}
+        }"#;
+        let ge: GoogleEvent = serde_json::from_str(json).unwrap();
+        let event = client.convert_from_google_event(ge, "primary").unwrap();
+
+        assert_eq!(event.organizer.as_deref(), Some("Team <team@group.calendar.google.com>"));
+        assert_eq!(event.creator.as_deref(), Some("sam@example.com"));
+        let payload = serde_json::to_value(client.convert_to_google_event(&event, true)).unwrap();
+        assert!(payload.get("organizer").is_none());
+    }
+
+    #[test]
+    fn calendar_list_entries_carry_their_access_role() {
+        let json = r#"{"items": [
+            {"id": "me@example.com", "summary": "Me", "primary": true, "accessRole": "owner"},
+            {"id": "holidays", "accessRole": "reader"}
+        ]}"#;
+        let list: CalendarListResponse = serde_json::from_str(json).unwrap();
+        let calendars: Vec<Calendar> = list.items.unwrap().into_iter().map(CalendarListEntry::into_calendar).collect();
+
+        assert!(calendars[0].is_primary);
+        assert_eq!(calendars[0].access_role, AccessRole::Owner);
+        assert_eq!(calendars[1].name, "holidays");
+        assert_eq!(calendars[1].access_role, AccessRole::Reader);
+    }
+
+    #[test]
+    fn all_day_events_use_dates() {
+        let client = GoogleCalendarClient::new("token".to_string());
+        let json = r#"{
+            "id": "holiday1",
+            "summary": "New Year's Day",
+            "start": {"date": "2025-01-01"},
+            "end": {"date": "2025-01-02"}
+        }"#;
+        let ge: GoogleEvent = serde_json::from_str(json).unwrap();
+        let event = client.convert_from_google_event(ge, "primary").unwrap();
+
+        assert!(event.all_day);
+        assert_eq!(event.start.to_rfc3339(), "2025-01-01T00:00:00+00:00");
+        assert_eq!(event.end.to_rfc3339(), "2025-01-02T00:00:00+00:00");
+
+        let back = client.convert_to_google_event(&event, false);
+        assert_eq!(back.start.date.as_deref(), Some("2025-01-01"));
+        assert_eq!(back.start.date_time, None);
+        assert_eq!(back.end.date.as_deref(), Some("2025-01-02"));
+    }
+
+    #[test]
+    fn colors_response_parses_event_palette() {
+        let json = r##"{"kind": "calendar#colors", "event": {"1": {"background": "#a4bdfc", "foreground": "#1d1d1d"}}}"##;
+        let colors: ColorsResponse = serde_json::from_str(json).unwrap();
+
+        assert_eq!(colors.event["1"].background, "#a4bdfc");
+    }
+
     #[test]
     fn google_calendar_client_has_default_base_url() {
         let client = GoogleCalendarClient::new("token".to_string());
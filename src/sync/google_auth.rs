@@ -3,6 +3,7 @@ use std::path::PathBuf;
 use thiserror::Error;
 use chrono::{DateTime, Utc};
 use crate::storage::config::Config;
+use crate::storage::crypto::{is_sealed, CryptoError, KeySource};
 
 #[derive(Debug, Error)]
 pub enum AuthError {
@@ -18,6 +19,8 @@ pub enum AuthError {
     HttpError(#[from] reqwest::Error),
     #[error("OAuth error: {0}")]
     OAuthError(String),
+    #[error("Token encryption failed: {0}")]
+    CryptoError(#[from] CryptoError),
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -31,28 +34,50 @@ pub struct TokenInfo {
 
 pub struct TokenStorage {
     path: PathBuf,
+    keys: KeySource,
 }
 
 impl TokenStorage {
     pub fn new(path: PathBuf) -> Self {
-        Self { path }
+        Self { path, keys: KeySource::Plain }
+    }
+
+    pub fn with_keys(mut self, keys: KeySource) -> Self {
+        self.keys = keys;
+        self
     }
 
     pub fn save_token(&self, token: &TokenInfo) -> Result<(), AuthError> {
         if let Some(parent) = self.path.parent() {
             std::fs::create_dir_all(parent)?;
         }
-        let json = serde_json::to_string_pretty(token)?;
+        let json = self.keys.seal(serde_json::to_string_pretty(token)?)?;
         std::fs::write(&self.path, json)?;
         Ok(())
     }
 
     pub fn load_token(&self) -> Result<TokenInfo, AuthError> {
-        let content = std::fs::read_to_string(&self.path)?;
+        let content = self.keys.open(std::fs::read_to_string(&self.path)?)?;
         let token: TokenInfo = serde_json::from_str(&content)?;
         Ok(token)
     }
 
+    /// Seals a token file written before encryption was turned on. Returns
+    /// whether there was one to seal.
+    pub fn seal_plain_token(&self) -> Result<bool, AuthError> {
+        if !self.keys.encrypts() {
+            return Ok(false);
+        }
+        let content = match std::fs::read_to_string(&self.path) {
+            Ok(content) if !is_sealed(&content) => content,
+            Ok(_) => return Ok(false),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(false),
+            Err(e) => return Err(e.into()),
+        };
+        std::fs::write(&self.path, self.keys.seal(content)?)?;
+        Ok(true)
+    }
+
     pub fn is_expired(&self, token: &TokenInfo) -> bool {
         token.expires_at <= Utc::now()
     }
@@ -101,7 +126,10 @@ struct TokenResponse {
 impl GoogleAuthenticator {
     pub fn new(config: Config) -> Self {
         let token_path = config.google.token_cache.clone();
-        let storage = TokenStorage::new(token_path);
+        let storage = TokenStorage::new(token_path).with_keys(KeySource::from_config(&config.security));
+        if let Err(e) = storage.seal_plain_token() {
+            tracing::warn!("Failed to encrypt the saved token: {}", e);
+        }
         let client = reqwest::Client::new();
 
         Self {
@@ -155,7 +183,11 @@ impl GoogleAuthenticator {
 
     pub fn get_auth_url(&self) -> String {
         let redirect_uri = "http://localhost:8080";
-        let scope = "https://www.googleapis.com/auth/calendar";
+        let scope = if self.config.sync.tasks {
+            "https://www.googleapis.com/auth/calendar https://www.googleapis.com/auth/tasks"
+        } else {
+            "https://www.googleapis.com/auth/calendar"
+        };
 
         format!(
             "https://accounts.google.com/o/oauth2/v2/auth?client_id={}&redirect_uri={}&response_type=code&scope={}&access_type=offline&prompt=consent",
@@ -199,14 +231,14 @@ impl GoogleAuthenticator {
     }
 
     pub fn print_auth_instructions(&self) {
-        println!("\n=== Google Calendar Authentication ===\n");
-        println!("To authenticate with Google Calendar:");
-        println!("1. Visit this URL in your browser:\n");
-        println!("{}\n", self.get_auth_url());
-        println!("2. Sign in and authorize the application");
-        println!("3. After authorizing, you'll be redirected to localhost:8080");
-        println!("4. Copy the 'code' parameter from the URL");
-        println!("5. Paste it when prompted\n");
+        eprintln!("\n=== Google Calendar Authentication ===\n");
+        eprintln!("To authenticate with Google Calendar:");
+        eprintln!("1. Visit this URL in your browser:\n");
+        eprintln!("{}\n", self.get_auth_url());
+        eprintln!("2. Sign in and authorize the application");
+        eprintln!("3. After authorizing, you'll be redirected to localhost:8080");
+        eprintln!("4. Copy the 'code' parameter from the URL");
+        eprintln!("5. Paste it when prompted\n");
     }
 }
 
@@ -275,6 +307,37 @@ mod tests {
         assert_eq!(loaded_token.refresh_token, original_token.refresh_token);
     }
 
+    #[test]
+    fn encrypted_token_is_not_readable_on_disk() {
+        let temp_dir = TempDir::new().unwrap();
+        let token_path = temp_dir.path().join("token.json");
+        let keys = KeySource::Key(crate::storage::crypto::DataKey::generate().unwrap());
+        let storage = TokenStorage::new(token_path.clone()).with_keys(keys);
+        let token = create_test_token().with_refresh_token("refresh".to_string());
+
+        storage.save_token(&token).unwrap();
+
+        assert!(!std::fs::read_to_string(&token_path).unwrap().contains("refresh"));
+        assert_eq!(storage.load_token().unwrap(), token);
+        assert!(TokenStorage::new(token_path).load_token().is_err());
+    }
+
+    #[test]
+    fn turning_encryption_on_seals_the_saved_token() {
+        let temp_dir = TempDir::new().unwrap();
+        let token_path = temp_dir.path().join("token.json");
+        let token = create_test_token().with_refresh_token("refresh".to_string());
+        TokenStorage::new(token_path.clone()).save_token(&token).unwrap();
+        let keys = KeySource::Key(crate::storage::crypto::DataKey::generate().unwrap());
+        let storage = TokenStorage::new(token_path.clone()).with_keys(keys);
+
+        assert!(storage.seal_plain_token().unwrap());
+        assert!(!storage.seal_plain_token().unwrap());
+
+        assert!(!std::fs::read_to_string(&token_path).unwrap().contains("refresh"));
+        assert_eq!(storage.load_token().unwrap(), token);
+    }
+
     #[test]
     fn load_nonexistent_token_returns_error() {
         let temp_dir = TempDir::new().unwrap();
@@ -0,0 +1,188 @@
+use crate::sync::google_api::{check_response_status, ApiError};
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use async_trait::async_trait;
+
+/// An open Google Task, flattened with the id of the list it belongs to.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Task {
+    pub id: String,
+    pub list_id: String,
+    pub title: String,
+    pub due: Option<NaiveDate>,
+}
+
+impl Task {
+    pub fn is_overdue(&self, today: NaiveDate) -> bool {
+        self.due.is_some_and(|due| due < today)
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct TaskListsResponse {
+    items: Option<Vec<GoogleTaskList>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GoogleTaskList {
+    id: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct TasksResponse {
+    items: Option<Vec<GoogleTask>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GoogleTask {
+    id: String,
+    title: Option<String>,
+    due: Option<String>,
+    status: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+struct TaskStatusPatch<'a> {
+    status: &'a str,
+}
+
+impl GoogleTask {
+    fn into_task(self, list_id: &str) -> Option<Task> {
+        if self.status.as_deref() == Some("completed") {
+            return None;
+        }
+        // Google stores due dates as midnight UTC; only the date is meaningful.
+        let due = self.due
+            .and_then(|due| DateTime::parse_from_rfc3339(&due).ok())
+            .map(|due| due.with_timezone(&Utc).date_naive());
+        Some(Task {
+            id: self.id,
+            list_id: list_id.to_string(),
+            title: self.title.unwrap_or_default(),
+            due,
+        })
+    }
+}
+
+#[async_trait]
+pub trait TasksApi {
+    /// Open tasks due on or before `date`, across all task lists.
+    async fn fetch_tasks_due(&self, date: NaiveDate) -> Result<Vec<Task>, ApiError>;
+
+    async fn complete_task(&self, task: &Task) -> Result<(), ApiError>;
+}
+
+pub struct GoogleTasksClient {
+    base_url: String,
+    access_token: String,
+    client: reqwest::Client,
+}
+
+impl GoogleTasksClient {
+    pub fn new(access_token: String) -> Self {
+        Self {
+            base_url: "https://tasks.googleapis.com/tasks/v1".to_string(),
+            access_token,
+            client: reqwest::Client::new(),
+        }
+    }
+
+    pub fn with_base_url(mut self, base_url: String) -> Self {
+        self.base_url = base_url;
+        self
+    }
+
+    async fn fetch_task_list_ids(&self) -> Result<Vec<String>, ApiError> {
+        let url = format!("{}/users/@me/lists", self.base_url);
+
+        let response = self.client
+            .get(&url)
+            .bearer_auth(&self.access_token)
+            .send()
+            .await?;
+
+        let response = check_response_status(response, "task lists").await?;
+        let lists: TaskListsResponse = response.json().await?;
+        Ok(lists.items.unwrap_or_default().into_iter().map(|list| list.id).collect())
+    }
+}
+
+#[async_trait]
+impl TasksApi for GoogleTasksClient {
+    async fn fetch_tasks_due(&self, date: NaiveDate) -> Result<Vec<Task>, ApiError> {
+        let due_max = date.and_hms_opt(23, 59, 59)
+            .ok_or_else(|| ApiError::ParseError("Invalid due date".to_string()))?
+            .and_utc()
+            .to_rfc3339();
+
+        let mut tasks = Vec::new();
+        for list_id in self.fetch_task_list_ids().await? {
+            let url = format!("{}/lists/{}/tasks", self.base_url, list_id);
+
+            let response = self.client
+                .get(&url)
+                .bearer_auth(&self.access_token)
+                .query(&[
+                    ("dueMax", due_max.as_str()),
+                    ("showCompleted", "false"),
+                    ("maxResults", "100"),
+                ])
+                .send()
+                .await?;
+
+            let response = check_response_status(response, &list_id).await?;
+            let list: TasksResponse = response.json().await?;
+            tasks.extend(list.items.unwrap_or_default().into_iter().filter_map(|task| task.into_task(&list_id)));
+        }
+
+        tasks.sort_by(|a, b| a.due.cmp(&b.due).then_with(|| a.title.cmp(&b.title)));
+        Ok(tasks)
+    }
+
+    async fn complete_task(&self, task: &Task) -> Result<(), ApiError> {
+        let url = format!("{}/lists/{}/tasks/{}", self.base_url, task.list_id, task.id);
+
+        let response = self.client
+            .patch(&url)
+            .bearer_auth(&self.access_token)
+            .json(&TaskStatusPatch { status: "completed" })
+            .send()
+            .await?;
+
+        check_response_status(response, &task.title).await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn open_tasks_convert_with_due_date() {
+        let json = r#"{"id": "t1", "title": "File expenses", "due": "2025-01-15T00:00:00.000Z", "status": "needsAction"}"#;
+        let task: GoogleTask = serde_json::from_str(json).unwrap();
+
+        let task = task.into_task("list1").unwrap();
+
+        assert_eq!(task.list_id, "list1");
+        assert_eq!(task.title, "File expenses");
+        assert_eq!(task.due, NaiveDate::from_ymd_opt(2025, 1, 15));
+        assert!(task.is_overdue(NaiveDate::from_ymd_opt(2025, 1, 16).unwrap()));
+    }
+
+    #[test]
+    fn completed_tasks_are_skipped() {
+        let json = r#"{"id": "t1", "title": "Done", "status": "completed"}"#;
+        let task: GoogleTask = serde_json::from_str(json).unwrap();
+
+        assert_eq!(task.into_task("list1"), None);
+    }
+
+    #[test]
+    fn google_tasks_client_has_default_base_url() {
+        let client = GoogleTasksClient::new("token".to_string());
+
+        assert_eq!(client.base_url, "https://tasks.googleapis.com/tasks/v1");
+    }
+}
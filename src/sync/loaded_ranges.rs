@@ -0,0 +1,118 @@
+use chrono::{Days, NaiveDate};
+
+/// Days fetched on each side of the selected date at startup, and the
+/// minimum size of each window fetched later.
+pub const WINDOW_DAYS: u64 = 31;
+/// How far around the selected date events should already be loaded.
+pub const PREFETCH_MARGIN_DAYS: u64 = 14;
+
+/// Inclusive date ranges whose events have been fetched, sorted and merged.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct LoadedRanges {
+    ranges: Vec<(NaiveDate, NaiveDate)>,
+}
+
+impl LoadedRanges {
+    pub fn clear(&mut self) {
+        self.ranges.clear();
+    }
+
+    pub fn insert(&mut self, start: NaiveDate, end: NaiveDate) {
+        self.ranges.push((start.min(end), start.max(end)));
+        self.ranges.sort();
+
+        let mut merged: Vec<(NaiveDate, NaiveDate)> = Vec::new();
+        for (start, end) in self.ranges.drain(..) {
+            match merged.last_mut() {
+                Some(last) if start <= last.1.succ_opt().unwrap_or(last.1) => last.1 = last.1.max(end),
+                _ => merged.push((start, end)),
+            }
+        }
+        self.ranges = merged;
+    }
+
+    pub fn contains(&self, date: NaiveDate) -> bool {
+        self.ranges.iter().any(|&(start, end)| start <= date && date <= end)
+    }
+
+    /// True when every date from `start` through `end` has been fetched.
+    pub fn covers(&self, start: NaiveDate, end: NaiveDate) -> bool {
+        self.ranges.iter().any(|&(from, to)| from <= start && end <= to)
+    }
+
+    pub fn ranges(&self) -> &[(NaiveDate, NaiveDate)] {
+        &self.ranges
+    }
+
+    /// The next window to fetch so that the margin around `date` is loaded:
+    /// the range holding `date` grows by at least a window on the side that
+    /// falls short, or a fresh window is centred on a `date` outside them all.
+    pub fn next_window(&self, date: NaiveDate) -> Option<(NaiveDate, NaiveDate)> {
+        let margin = Days::new(PREFETCH_MARGIN_DAYS);
+        let window = Days::new(WINDOW_DAYS);
+        let wanted_start = date.checked_sub_days(margin)?;
+        let wanted_end = date.checked_add_days(margin)?;
+
+        let Some(&(start, end)) = self.ranges.iter().find(|&&(start, end)| start <= date && date <= end) else {
+            return Some((date.checked_sub_days(window)?, date.checked_add_days(window)?));
+        };
+
+        if wanted_end > end {
+            let from = end.succ_opt()?;
+            Some((from, wanted_end.max(end.checked_add_days(window)?)))
+        } else if wanted_start < start {
+            let to = start.pred_opt()?;
+            Some((wanted_start.min(start.checked_sub_days(window)?), to))
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn day(month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2025, month, day).unwrap()
+    }
+
+    #[test]
+    fn insert_merges_overlapping_and_adjacent_ranges() {
+        let mut loaded = LoadedRanges::default();
+        loaded.insert(day(3, 1), day(3, 31));
+        loaded.insert(day(5, 1), day(5, 31));
+        loaded.insert(day(4, 1), day(4, 10));
+
+        assert_eq!(loaded.ranges(), &[(day(3, 1), day(4, 10)), (day(5, 1), day(5, 31))]);
+        assert!(loaded.contains(day(4, 10)));
+        assert!(!loaded.contains(day(4, 11)));
+        assert!(loaded.covers(day(3, 15), day(4, 10)));
+        assert!(!loaded.covers(day(4, 1), day(5, 1)));
+    }
+
+    #[test]
+    fn nothing_to_fetch_inside_the_margin() {
+        let mut loaded = LoadedRanges::default();
+        loaded.insert(day(1, 1), day(3, 31));
+
+        assert_eq!(loaded.next_window(day(2, 15)), None);
+    }
+
+    #[test]
+    fn extends_the_side_that_runs_out() {
+        let mut loaded = LoadedRanges::default();
+        loaded.insert(day(2, 1), day(3, 31));
+
+        assert_eq!(loaded.next_window(day(3, 25)), Some((day(4, 1), day(5, 1))));
+        assert_eq!(loaded.next_window(day(2, 5)), Some((day(1, 1), day(1, 31))));
+    }
+
+    #[test]
+    fn fresh_window_for_a_far_jump() {
+        let mut loaded = LoadedRanges::default();
+        loaded.insert(day(2, 1), day(3, 31));
+
+        assert_eq!(loaded.next_window(day(9, 15)), Some((day(8, 15), day(10, 16))));
+    }
+}
@@ -0,0 +1,351 @@
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use async_trait::async_trait;
+use chrono::{Duration, Utc};
+
+use crate::calendar::{AccessRole, Calendar, Event, DEFAULT_CALENDAR_ID};
+use crate::sync::google_api::{ApiError, CalendarApi, ColorPalette, CreatedEventInfo, DateRange};
+use crate::sync::push::WatchChannel;
+
+/// Which calendar API the sync engine talks to.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum ApiBackend {
+    #[default]
+    Google,
+    /// The mock API seeded from, and saved to, the cache.
+    Local,
+    Mock,
+}
+
+impl ApiBackend {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.trim().to_lowercase().as_str() {
+            "google" => Some(Self::Google),
+            "local" => Some(Self::Local),
+            "mock" => Some(Self::Mock),
+            _ => None,
+        }
+    }
+
+    pub fn from_config(name: Option<&str>) -> Self {
+        match name {
+            Some(name) => Self::from_name(name).unwrap_or_else(|| {
+                tracing::warn!("Unknown sync backend '{}', using google", name);
+                Self::Google
+            }),
+            None => Self::Google,
+        }
+    }
+
+    /// True for the backends that need no Google account.
+    pub fn is_offline(self) -> bool {
+        self != Self::Google
+    }
+}
+
+#[derive(Debug, Default)]
+struct MockState {
+    events: Vec<Event>,
+    requests: u32,
+    next_id: u32,
+    fail_every: u32,
+    fail_next: u32,
+}
+
+impl MockState {
+    fn next_etag(&mut self) -> String {
+        self.next_id += 1;
+        format!("\"mock-{}\"", self.next_id)
+    }
+
+    /// Counts a request and decides whether it fails.
+    fn check_failure(&mut self) -> Result<(), ApiError> {
+        self.requests += 1;
+        if self.fail_next > 0 {
+            self.fail_next -= 1;
+            return Err(ApiError::RequestError("Injected failure".to_string()));
+        }
+        if self.fail_every > 0 && self.requests.is_multiple_of(self.fail_every) {
+            return Err(ApiError::RequestError(format!("Injected failure on request {}", self.requests)));
+        }
+        Ok(())
+    }
+}
+
+/// An in-memory calendar for development and tests. Clones share the same
+/// events, so a test can keep one to inspect what the engine sent.
+#[derive(Debug, Clone, Default)]
+pub struct MockCalendarApi {
+    state: Arc<Mutex<MockState>>,
+}
+
+impl MockCalendarApi {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_events(self, events: impl IntoIterator<Item = Event>) -> Self {
+        self.add_events(events);
+        self
+    }
+
+    pub fn add_events(&self, events: impl IntoIterator<Item = Event>) {
+        let mut state = self.lock();
+        for mut event in events {
+            event.etag = Some(state.next_etag());
+            state.events.push(event);
+        }
+    }
+
+    /// Fails every `n`th request; 0 never fails.
+    pub fn with_failure_every(self, n: u32) -> Self {
+        self.lock().fail_every = n;
+        self
+    }
+
+    /// Fails the next `count` requests, whatever they are.
+    pub fn fail_next(&self, count: u32) {
+        self.lock().fail_next = count;
+    }
+
+    pub fn events(&self) -> Vec<Event> {
+        self.lock().events.clone()
+    }
+
+    /// Requests made so far, failed ones included.
+    pub fn request_count(&self) -> u32 {
+        self.lock().requests
+    }
+
+    /// Changes an event as if someone else edited it on the server.
+    pub fn edit_remotely(&self, event_id: &str, edit: impl FnOnce(&mut Event)) -> bool {
+        let mut state = self.lock();
+        let etag = state.next_etag();
+        match state.events.iter_mut().find(|e| e.id == event_id) {
+            Some(event) => {
+                edit(event);
+                event.last_modified = Utc::now();
+                event.etag = Some(etag);
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, MockState> {
+        self.state.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+fn check_etag(stored: &Event, etag: Option<&str>) -> Result<(), ApiError> {
+    match etag {
+        Some(etag) if stored.etag.as_deref() != Some(etag) => Err(ApiError::PreconditionFailed(stored.title.clone())),
+        _ => Ok(()),
+    }
+}
+
+#[async_trait]
+impl CalendarApi for MockCalendarApi {
+    async fn fetch_events(
+        &self,
+        calendar_id: &str,
+        date_range: DateRange,
+    ) -> Result<Vec<Event>, ApiError> {
+        let mut state = self.lock();
+        state.check_failure()?;
+        let mut events: Vec<Event> = state.events.iter()
+            .filter(|e| e.calendar_id == calendar_id)
+            .filter(|e| e.start.date_naive() <= date_range.end && e.end.date_naive() >= date_range.start)
+            .cloned()
+            .collect();
+        events.sort_by_key(|e| e.start);
+        Ok(events)
+    }
+
+    async fn get_event(&self, calendar_id: &str, event_id: &str) -> Result<Event, ApiError> {
+        let mut state = self.lock();
+        state.check_failure()?;
+        state.events.iter()
+            .find(|e| e.id == event_id && e.calendar_id == calendar_id)
+            .cloned()
+            .ok_or_else(|| ApiError::NotFound(event_id.to_string()))
+    }
+
+    async fn create_event(
+        &self,
+        calendar_id: &str,
+        event: &Event,
+    ) -> Result<CreatedEventInfo, ApiError> {
+        let mut state = self.lock();
+        state.check_failure()?;
+        let mut etag = state.next_etag();
+        // Events loaded from the local calendar may already use the next id.
+        while state.events.iter().any(|e| e.id == format!("mock_{}", state.next_id)) {
+            etag = state.next_etag();
+        }
+        let id = format!("mock_{}", state.next_id);
+        state.events.push(Event {
+            id: id.clone(),
+            calendar_id: calendar_id.to_string(),
+            last_modified: Utc::now(),
+            etag: Some(etag),
+            ..event.clone()
+        });
+        Ok(CreatedEventInfo { id, html_link: None })
+    }
+
+    async fn update_event(
+        &self,
+        calendar_id: &str,
+        event_id: &str,
+        event: &Event,
+    ) -> Result<Option<String>, ApiError> {
+        let mut state = self.lock();
+        state.check_failure()?;
+        let etag = state.next_etag();
+        let stored = state.events.iter_mut()
+            .find(|e| e.id == event_id && e.calendar_id == calendar_id)
+            .ok_or_else(|| ApiError::NotFound(event_id.to_string()))?;
+        check_etag(stored, event.etag.as_deref())?;
+        *stored = Event { last_modified: Utc::now(), etag: Some(etag.clone()), ..event.clone() };
+        Ok(Some(etag))
+    }
+
+    async fn delete_event(
+        &self,
+        calendar_id: &str,
+        event_id: &str,
+        etag: Option<&str>,
+    ) -> Result<(), ApiError> {
+        let mut state = self.lock();
+        state.check_failure()?;
+        let index = state.events.iter()
+            .position(|e| e.id == event_id && e.calendar_id == calendar_id)
+            .ok_or_else(|| ApiError::NotFound(event_id.to_string()))?;
+        check_etag(&state.events[index], etag)?;
+        state.events.remove(index);
+        Ok(())
+    }
+
+    async fn fetch_colors(&self) -> Result<ColorPalette, ApiError> {
+        self.lock().check_failure()?;
+        Ok(ColorPalette::default())
+    }
+
+    async fn fetch_calendar_color(&self, _calendar_id: &str) -> Result<Option<String>, ApiError> {
+        self.lock().check_failure()?;
+        Ok(None)
+    }
+
+    async fn fetch_calendars(&self) -> Result<Vec<Calendar>, ApiError> {
+        let mut state = self.lock();
+        state.check_failure()?;
+        let mut ids = vec![DEFAULT_CALENDAR_ID.to_string()];
+        for event in &state.events {
+            if !ids.contains(&event.calendar_id) {
+                ids.push(event.calendar_id.clone());
+            }
+        }
+        Ok(ids.into_iter()
+            .map(|id| Calendar {
+                name: id.clone(),
+                color: String::new(),
+                is_primary: id == DEFAULT_CALENDAR_ID,
+                access_role: AccessRole::Owner,
+                id,
+            })
+            .collect())
+    }
+
+    async fn watch_events(
+        &self,
+        calendar_id: &str,
+        channel_id: &str,
+        token: &str,
+        _address: &str,
+    ) -> Result<WatchChannel, ApiError> {
+        self.lock().check_failure()?;
+        Ok(WatchChannel {
+            id: channel_id.to_string(),
+            resource_id: format!("mock-{}", calendar_id),
+            calendar_id: calendar_id.to_string(),
+            token: token.to_string(),
+            expiration: Utc::now() + Duration::hours(1),
+        })
+    }
+
+    async fn stop_channel(&self, _channel: &WatchChannel) -> Result<(), ApiError> {
+        self.lock().check_failure()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::EventBuilder;
+    use chrono::{NaiveDate, TimeZone};
+
+    fn create_event(id: &str, day: u32) -> Event {
+        let start = Utc.with_ymd_and_hms(2025, 1, day, 9, 0, 0).unwrap();
+        EventBuilder::new(id).at(start, Duration::hours(1)).build()
+    }
+
+    fn january(from: u32, to: u32) -> DateRange {
+        DateRange::new(
+            NaiveDate::from_ymd_opt(2025, 1, from).unwrap(),
+            NaiveDate::from_ymd_opt(2025, 1, to).unwrap(),
+        )
+    }
+
+    #[test]
+    fn backend_names_fall_back_to_google() {
+        assert_eq!(ApiBackend::from_config(Some("Mock")), ApiBackend::Mock);
+        assert_eq!(ApiBackend::from_config(Some("carrier-pigeon")), ApiBackend::Google);
+        assert_eq!(ApiBackend::from_config(None), ApiBackend::Google);
+    }
+
+    #[tokio::test]
+    async fn keeps_created_and_updated_events_in_memory() {
+        let api = MockCalendarApi::new().with_events([create_event("a", 10), create_event("b", 20)]);
+
+        assert_eq!(api.fetch_events(DEFAULT_CALENDAR_ID, january(9, 12)).await.unwrap().len(), 1);
+
+        let created = api.create_event(DEFAULT_CALENDAR_ID, &create_event("", 11)).await.unwrap();
+        let mut event = api.events().into_iter().find(|e| e.id == created.id).unwrap();
+        event.title = "Renamed".to_string();
+        let etag = api.update_event(DEFAULT_CALENDAR_ID, &event.id, &event).await.unwrap();
+
+        let fetched = api.fetch_events(DEFAULT_CALENDAR_ID, january(9, 12)).await.unwrap();
+        assert_eq!(fetched.iter().map(|e| e.title.as_str()).collect::<Vec<_>>(), vec!["Event a", "Renamed"]);
+        assert_eq!(fetched[1].etag, etag);
+    }
+
+    #[tokio::test]
+    async fn stale_etags_are_rejected() {
+        let api = MockCalendarApi::new().with_events([create_event("a", 10)]);
+        let event = api.events().remove(0);
+
+        assert!(api.edit_remotely("a", |e| e.title = "Changed elsewhere".to_string()));
+
+        let result = api.update_event(DEFAULT_CALENDAR_ID, "a", &event).await;
+        assert!(matches!(result, Err(ApiError::PreconditionFailed(_))));
+        let result = api.delete_event(DEFAULT_CALENDAR_ID, "a", event.etag.as_deref()).await;
+        assert!(matches!(result, Err(ApiError::PreconditionFailed(_))));
+        assert!(api.delete_event(DEFAULT_CALENDAR_ID, "a", None).await.is_ok());
+        assert!(api.events().is_empty());
+    }
+
+    #[tokio::test]
+    async fn injects_failures() {
+        let api = MockCalendarApi::new().with_failure_every(3);
+
+        assert!(api.fetch_calendars().await.is_ok());
+        assert!(api.fetch_calendars().await.is_ok());
+        assert!(api.fetch_calendars().await.is_err());
+
+        api.fail_next(1);
+        assert!(api.fetch_colors().await.is_err());
+        assert!(api.fetch_colors().await.is_ok());
+        assert_eq!(api.request_count(), 5);
+    }
+}
@@ -1,4 +1,14 @@
+pub mod activity_log;
 pub mod google_auth;
 pub mod google_api;
+pub mod google_tasks;
 pub mod conflict;
+pub mod loaded_ranges;
+pub mod mock_api;
+pub mod push;
+pub mod scheduler;
 pub mod sync_engine;
+pub mod sync_state;
+pub mod subscriptions;
+#[cfg(test)]
+pub(crate) mod test_support;
@@ -0,0 +1,243 @@
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::mpsc::{self, UnboundedReceiver, UnboundedSender};
+
+/// How long before expiry a channel is registered again.
+const RENEW_MARGIN_MINUTES: i64 = 10;
+const MAX_REQUEST_BYTES: usize = 8192;
+/// A client that has not sent its request by then is dropped.
+const READ_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(5);
+
+/// A registered Google Calendar push channel for one calendar.
+#[derive(Debug, Clone, PartialEq)]
+pub struct WatchChannel {
+    pub id: String,
+    pub resource_id: String,
+    pub calendar_id: String,
+    /// Sent back by Google in `X-Goog-Channel-Token` with every notification.
+    pub token: String,
+    pub expiration: DateTime<Utc>,
+}
+
+impl WatchChannel {
+    pub fn needs_renewal(&self, now: DateTime<Utc>) -> bool {
+        now + Duration::minutes(RENEW_MARGIN_MINUTES) >= self.expiration
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub(crate) struct ChannelRequest<'a> {
+    pub id: &'a str,
+    #[serde(rename = "type")]
+    pub kind: &'a str,
+    pub address: &'a str,
+    pub token: &'a str,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct ChannelResponse {
+    pub id: String,
+    #[serde(rename = "resourceId")]
+    pub resource_id: String,
+    /// Milliseconds since the epoch, sent as a string.
+    pub expiration: Option<String>,
+}
+
+impl ChannelResponse {
+    pub fn into_channel(self, calendar_id: &str, token: &str) -> WatchChannel {
+        let expiration = self.expiration
+            .and_then(|ms| ms.parse::<i64>().ok())
+            .and_then(|ms| Utc.timestamp_millis_opt(ms).single())
+            .unwrap_or_else(|| Utc::now() + Duration::hours(1));
+        WatchChannel {
+            id: self.id,
+            resource_id: self.resource_id,
+            calendar_id: calendar_id.to_string(),
+            token: token.to_string(),
+            expiration,
+        }
+    }
+}
+
+/// The headers of a push notification that matter here.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Notification {
+    pub channel_id: String,
+    pub resource_state: String,
+    pub token: Option<String>,
+}
+
+impl Notification {
+    /// "sync" only confirms a new channel; "exists" means events changed.
+    pub fn is_change(&self) -> bool {
+        self.resource_state == "exists"
+    }
+}
+
+/// Reads the channel headers out of a raw HTTP request.
+pub fn parse_notification(request: &str) -> Option<Notification> {
+    let header = |name: &str| {
+        request.lines()
+            .skip(1)
+            .take_while(|line| !line.is_empty())
+            .filter_map(|line| line.split_once(':'))
+            .find(|(key, _)| key.trim().eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.trim().to_string())
+    };
+    Some(Notification {
+        channel_id: header("X-Goog-Channel-ID")?,
+        resource_state: header("X-Goog-Resource-State")?,
+        token: header("X-Goog-Channel-Token"),
+    })
+}
+
+/// Receives notifications forwarded to a local port and tells the UI which
+/// channels reported changes.
+pub struct PushListener {
+    changes: UnboundedReceiver<Notification>,
+    port: u16,
+    pub channel: Option<WatchChannel>,
+}
+
+impl PushListener {
+    /// Binds `127.0.0.1:port` and serves plain HTTP; the public HTTPS
+    /// address given to Google has to forward there.
+    pub async fn start(port: u16) -> std::io::Result<Self> {
+        let listener = TcpListener::bind(("127.0.0.1", port)).await?;
+        let port = listener.local_addr()?.port();
+        let (sender, changes) = mpsc::unbounded_channel();
+        tokio::spawn(serve(listener, sender));
+        Ok(Self { changes, port, channel: None })
+    }
+
+    /// The bound port, which differs from the requested one for port 0.
+    pub fn port(&self) -> u16 {
+        self.port
+    }
+
+    /// Drains pending notifications; true when any came from the current
+    /// channel with its token.
+    pub fn take_change(&mut self) -> bool {
+        let mut changed = false;
+        while let Ok(notification) = self.changes.try_recv() {
+            changed |= self.channel.as_ref().is_some_and(|c| {
+                c.id == notification.channel_id && notification.token.as_deref() == Some(c.token.as_str())
+            });
+        }
+        changed
+    }
+}
+
+/// Answers each connection on its own task, so a client that stalls only
+/// holds up itself.
+async fn serve(listener: TcpListener, sender: UnboundedSender<Notification>) {
+    loop {
+        match listener.accept().await {
+            Ok((stream, _)) => {
+                tokio::spawn(answer(stream, sender.clone()));
+            }
+            Err(e) => {
+                tracing::warn!("Push receiver stopped: {}", e);
+                return;
+            }
+        }
+    }
+}
+
+async fn answer(mut stream: TcpStream, sender: UnboundedSender<Notification>) {
+    let mut buffer = vec![0; MAX_REQUEST_BYTES];
+    let read = match tokio::time::timeout(READ_TIMEOUT, stream.read(&mut buffer)).await {
+        Ok(Ok(read)) => read,
+        _ => return,
+    };
+    let request = String::from_utf8_lossy(&buffer[..read]);
+    if let Some(notification) = parse_notification(&request).filter(Notification::is_change) {
+        let _ = sender.send(notification);
+    }
+    let _ = stream.write_all(b"HTTP/1.1 200 OK\r\nContent-Length: 0\r\n\r\n").await;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_channel_headers_case_insensitively() {
+        let request = "POST /notify HTTP/1.1\r\nHost: example.com\r\nx-goog-channel-id: chan-1\r\nX-Goog-Resource-State: exists\r\n\r\n";
+
+        let notification = parse_notification(request).unwrap();
+
+        assert_eq!(notification.channel_id, "chan-1");
+        assert!(notification.is_change());
+    }
+
+    #[test]
+    fn ignores_requests_without_channel_headers() {
+        assert_eq!(parse_notification("GET / HTTP/1.1\r\nHost: example.com\r\n\r\n"), None);
+        assert_eq!(parse_notification("POST / HTTP/1.1\r\n\r\nX-Goog-Channel-ID: chan-1\r\nX-Goog-Resource-State: exists"), None);
+    }
+
+    #[test]
+    fn renews_shortly_before_expiry() {
+        let expiration = Utc.with_ymd_and_hms(2025, 1, 15, 12, 0, 0).unwrap();
+        let channel = WatchChannel {
+            id: "chan-1".to_string(),
+            resource_id: "res-1".to_string(),
+            calendar_id: "primary".to_string(),
+            token: "token-1".to_string(),
+            expiration,
+        };
+
+        assert!(!channel.needs_renewal(expiration - Duration::minutes(30)));
+        assert!(channel.needs_renewal(expiration - Duration::minutes(5)));
+    }
+
+    #[test]
+    fn expiration_is_read_from_milliseconds() {
+        let response = ChannelResponse {
+            id: "chan-1".to_string(),
+            resource_id: "res-1".to_string(),
+            expiration: Some("1736942400000".to_string()),
+        };
+
+        let channel = response.into_channel("primary", "token-1");
+
+        assert_eq!(channel.expiration, Utc.with_ymd_and_hms(2025, 1, 15, 12, 0, 0).unwrap());
+        assert_eq!(channel.token, "token-1");
+    }
+
+    async fn notify(port: u16, token: &str) {
+        let mut stream = TcpStream::connect(("127.0.0.1", port)).await.unwrap();
+        let request = format!(
+            "POST /notify HTTP/1.1\r\nX-Goog-Channel-ID: chan-1\r\nX-Goog-Channel-Token: {}\r\nX-Goog-Resource-State: exists\r\n\r\n",
+            token
+        );
+        stream.write_all(request.as_bytes()).await.unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).await.unwrap();
+        assert!(response.starts_with("HTTP/1.1 200"));
+    }
+
+    #[tokio::test]
+    async fn takes_changes_with_the_channel_token_past_a_stalled_client() {
+        let mut listener = PushListener::start(0).await.unwrap();
+        listener.channel = Some(WatchChannel {
+            id: "chan-1".to_string(),
+            resource_id: "res-1".to_string(),
+            calendar_id: "primary".to_string(),
+            token: "secret".to_string(),
+            expiration: Utc::now() + Duration::hours(1),
+        });
+        // Connects and never sends anything.
+        let _stalled = TcpStream::connect(("127.0.0.1", listener.port())).await.unwrap();
+
+        notify(listener.port(), "forged").await;
+        assert!(!listener.take_change());
+
+        notify(listener.port(), "secret").await;
+        assert!(listener.take_change());
+        assert!(!listener.take_change());
+    }
+}
@@ -0,0 +1,192 @@
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use crate::calendar::Event;
+
+const WINDOW: Duration = Duration::from_secs(1);
+/// How long queued updates wait for further edits to the same events.
+pub const QUIET_PERIOD: Duration = Duration::from_millis(800);
+
+/// The requests sent in the last second. Clones share it, so fetches on
+/// their own task count against the same budget as the engine.
+#[derive(Debug, Clone)]
+pub struct RequestBudget {
+    max_per_second: u32,
+    sent: Arc<Mutex<VecDeque<Instant>>>,
+}
+
+impl RequestBudget {
+    /// A budget of 0 means unlimited.
+    pub fn new(max_per_second: u32) -> Self {
+        Self { max_per_second, sent: Arc::default() }
+    }
+
+    fn sent(&self) -> std::sync::MutexGuard<'_, VecDeque<Instant>> {
+        self.sent.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// How long to wait before another request fits the budget.
+    pub fn delay(&self, now: Instant) -> Duration {
+        delay(&self.sent(), self.max_per_second, now)
+    }
+
+    pub fn record(&self, now: Instant) {
+        record(&mut self.sent(), now);
+    }
+
+    /// Waits for room in the budget and counts the request about to be sent.
+    pub async fn throttle(&self) {
+        loop {
+            let wait = {
+                let mut sent = self.sent();
+                let now = Instant::now();
+                let wait = delay(&sent, self.max_per_second, now);
+                if wait.is_zero() {
+                    record(&mut sent, now);
+                    return;
+                }
+                wait
+            };
+            tokio::time::sleep(wait).await;
+        }
+    }
+}
+
+fn delay(sent: &VecDeque<Instant>, max_per_second: u32, now: Instant) -> Duration {
+    if max_per_second == 0 || sent.len() < max_per_second as usize {
+        return Duration::ZERO;
+    }
+    let oldest = sent[sent.len() - max_per_second as usize];
+    (oldest + WINDOW).saturating_duration_since(now)
+}
+
+fn record(sent: &mut VecDeque<Instant>, now: Instant) {
+    while sent.front().is_some_and(|&at| now.duration_since(at) >= WINDOW) {
+        sent.pop_front();
+    }
+    sent.push_back(now);
+}
+
+/// Keeps requests under a per-second budget and merges queued updates to
+/// the same event, so bursts of edits do not run into 429s.
+#[derive(Debug)]
+pub struct RequestScheduler {
+    budget: RequestBudget,
+    queued: Vec<Event>,
+    last_queued: Option<Instant>,
+}
+
+impl RequestScheduler {
+    /// A budget of 0 means unlimited.
+    pub fn new(max_per_second: u32) -> Self {
+        Self { budget: RequestBudget::new(max_per_second), queued: Vec::new(), last_queued: None }
+    }
+
+    /// A handle on the budget for requests sent away from the engine.
+    pub fn budget(&self) -> RequestBudget {
+        self.budget.clone()
+    }
+
+    pub fn delay(&self, now: Instant) -> Duration {
+        self.budget.delay(now)
+    }
+
+    pub fn record(&mut self, now: Instant) {
+        self.budget.record(now);
+    }
+
+    pub async fn throttle(&mut self) {
+        self.budget.throttle().await;
+    }
+
+    /// Queues an update, replacing one already queued for the same event.
+    pub fn queue_update(&mut self, event: Event, now: Instant) {
+        match self.queued.iter_mut().find(|queued| queued.id == event.id) {
+            Some(queued) => *queued = event,
+            None => self.queued.push(event),
+        }
+        self.last_queued = Some(now);
+    }
+
+    pub fn has_queued(&self) -> bool {
+        !self.queued.is_empty()
+    }
+
+    /// The queued updates once nothing was queued for `QUIET_PERIOD`.
+    pub fn take_due(&mut self, now: Instant) -> Vec<Event> {
+        match self.last_queued {
+            Some(at) if now.duration_since(at) >= QUIET_PERIOD => self.take_all(),
+            _ => Vec::new(),
+        }
+    }
+
+    pub fn take_all(&mut self) -> Vec<Event> {
+        self.last_queued = None;
+        std::mem::take(&mut self.queued)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::EventBuilder;
+    use chrono::Utc;
+
+    fn create_event(id: &str, title: &str) -> Event {
+        EventBuilder::new(id).title(title).at(Utc::now(), chrono::Duration::hours(1)).build()
+    }
+
+    #[test]
+    fn budget_delays_requests_past_the_limit() {
+        let start = Instant::now();
+        let mut scheduler = RequestScheduler::new(2);
+
+        scheduler.record(start);
+        assert_eq!(scheduler.delay(start), Duration::ZERO);
+        scheduler.record(start + Duration::from_millis(300));
+
+        assert_eq!(scheduler.delay(start + Duration::from_millis(400)), Duration::from_millis(600));
+        assert_eq!(scheduler.delay(start + Duration::from_secs(1)), Duration::ZERO);
+    }
+
+    #[test]
+    fn shared_budgets_count_each_others_requests() {
+        let start = Instant::now();
+        let mut scheduler = RequestScheduler::new(1);
+        let budget = scheduler.budget();
+
+        budget.record(start);
+
+        assert_eq!(scheduler.delay(start), Duration::from_secs(1));
+        scheduler.record(start + Duration::from_secs(1));
+        assert_eq!(budget.delay(start + Duration::from_secs(1)), Duration::from_secs(1));
+    }
+
+    #[test]
+    fn zero_budget_is_unlimited() {
+        let now = Instant::now();
+        let mut scheduler = RequestScheduler::new(0);
+        for _ in 0..10 {
+            scheduler.record(now);
+        }
+
+        assert_eq!(scheduler.delay(now), Duration::ZERO);
+    }
+
+    #[test]
+    fn queued_updates_to_one_event_merge_and_wait_for_quiet() {
+        let start = Instant::now();
+        let mut scheduler = RequestScheduler::new(5);
+
+        scheduler.queue_update(create_event("a", "First"), start);
+        scheduler.queue_update(create_event("b", "Other"), start);
+        scheduler.queue_update(create_event("a", "Second"), start + Duration::from_millis(500));
+
+        assert!(scheduler.take_due(start + Duration::from_millis(900)).is_empty());
+        let due = scheduler.take_due(start + Duration::from_millis(1300));
+        let titles: Vec<&str> = due.iter().map(|e| e.title.as_str()).collect();
+        assert_eq!(titles, vec!["Second", "Other"]);
+        assert!(!scheduler.has_queued());
+    }
+}
@@ -0,0 +1,281 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use chrono::{DateTime, NaiveDate, NaiveTime, Utc};
+use reqwest::header::{ETAG, IF_MODIFIED_SINCE, IF_NONE_MATCH, LAST_MODIFIED};
+use reqwest::StatusCode;
+
+use crate::calendar::{ics, subscription, Event};
+use crate::storage::crypto::KeySource;
+use crate::sync::google_api::{check_response_status, ApiError};
+
+/// How long one feed may take before the fetch moves on without it.
+const FEED_TIMEOUT: Duration = Duration::from_secs(15);
+/// Feeds change rarely, so a download this recent is used without asking again.
+const FRESH_FOR: Duration = Duration::from_secs(5 * 60);
+
+#[derive(Debug, Clone)]
+struct Download {
+    text: String,
+    etag: Option<String>,
+    last_modified: Option<String>,
+    at: Instant,
+}
+
+/// Downloads feeds over one client and keeps them for the session, so
+/// window fetches reuse a recent copy and otherwise ask with `If-None-Match`
+/// and `If-Modified-Since`. Clones share the kept copies.
+#[derive(Debug, Clone)]
+pub struct FeedClient {
+    http: reqwest::Client,
+    downloads: Arc<Mutex<HashMap<String, Download>>>,
+    fresh_for: Duration,
+}
+
+impl Default for FeedClient {
+    fn default() -> Self {
+        let http = reqwest::Client::builder().timeout(FEED_TIMEOUT).build().unwrap_or_else(|e| {
+            tracing::warn!("Failed to set up the feed client, fetching without a timeout: {}", e);
+            reqwest::Client::new()
+        });
+        Self { http, downloads: Arc::default(), fresh_for: FRESH_FOR }
+    }
+}
+
+impl FeedClient {
+    pub fn with_fresh_for(mut self, fresh_for: Duration) -> Self {
+        self.fresh_for = fresh_for;
+        self
+    }
+
+    async fn get(&self, url: &str) -> Result<String, ApiError> {
+        let kept = self.downloads.lock().expect("feed downloads lock").get(url).cloned();
+        if let Some(kept) = &kept
+            && kept.at.elapsed() < self.fresh_for
+        {
+            return Ok(kept.text.clone());
+        }
+
+        let mut request = self.http.get(url);
+        if let Some(kept) = &kept {
+            if let Some(etag) = &kept.etag {
+                request = request.header(IF_NONE_MATCH, etag);
+            }
+            if let Some(last_modified) = &kept.last_modified {
+                request = request.header(IF_MODIFIED_SINCE, last_modified);
+            }
+        }
+        let response = request.send().await?;
+        let download = match kept {
+            Some(kept) if response.status() == StatusCode::NOT_MODIFIED => Download { at: Instant::now(), ..kept },
+            _ => {
+                let response = check_response_status(response, url).await?;
+                let header = |name| response.headers().get(name).and_then(|value| value.to_str().ok()).map(str::to_string);
+                let (etag, last_modified) = (header(ETAG), header(LAST_MODIFIED));
+                Download { text: response.text().await?, etag, last_modified, at: Instant::now() }
+            }
+        };
+        let text = download.text.clone();
+        self.downloads.lock().expect("feed downloads lock").insert(url.to_string(), download);
+        Ok(text)
+    }
+}
+
+/// A `[subscriptions]` feed and where its last copy is kept.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Feed {
+    pub name: String,
+    pub url: String,
+    pub cache_file: PathBuf,
+    pub keys: KeySource,
+}
+
+impl Feed {
+    pub fn new(name: &str, url: &str, dir: &Path) -> Self {
+        // Percent-encoded so that names like "a/b" and "a_b" keep apart.
+        let file_name = urlencoding::encode(name);
+        Self {
+            name: name.to_string(),
+            url: subscription::feed_url(url),
+            cache_file: dir.join(format!("{}.ics", file_name)),
+            keys: KeySource::Plain,
+        }
+    }
+
+    /// Seals the kept copy like the cache; plain copies stay readable.
+    pub fn with_keys(mut self, keys: KeySource) -> Self {
+        self.keys = keys;
+        self
+    }
+
+    pub fn calendar_id(&self) -> String {
+        subscription::calendar_id(&self.name)
+    }
+
+    /// The feed's events overlapping `start_date..=end_date`. Each download
+    /// is kept on disk and used when the feed cannot be reached, so a flaky
+    /// server does not empty the calendar; the error is only returned when
+    /// there is no copy yet.
+    pub async fn fetch_events(
+        &self,
+        client: &FeedClient,
+        start_date: NaiveDate,
+        end_date: NaiveDate,
+    ) -> Result<Vec<Event>, ApiError> {
+        let text = match self.download(client).await {
+            Ok(text) => {
+                self.keep(&text);
+                text
+            }
+            Err(e) => match self.last_copy() {
+                Some(text) => {
+                    tracing::warn!("Failed to fetch subscription {}, using the last copy: {}", self.name, e);
+                    text
+                }
+                None => return Err(e),
+            },
+        };
+        let (window_start, window_end) = window(start_date, end_date);
+        Ok(ics::from_ics(&text, &self.calendar_id(), window_start, window_end))
+    }
+
+    async fn download(&self, client: &FeedClient) -> Result<String, ApiError> {
+        client.get(&self.url).await
+    }
+
+    fn keep(&self, text: &str) {
+        let text = match self.keys.seal(text.to_string()) {
+            Ok(text) => text,
+            Err(e) => {
+                tracing::warn!("Failed to seal the copy of subscription {}: {}", self.name, e);
+                return;
+            }
+        };
+        let result = self.cache_file.parent()
+            .map_or(Ok(()), std::fs::create_dir_all)
+            .and_then(|()| std::fs::write(&self.cache_file, text));
+        if let Err(e) = result {
+            tracing::warn!("Failed to keep a copy of subscription {}: {}", self.name, e);
+        }
+    }
+
+    fn last_copy(&self) -> Option<String> {
+        let text = std::fs::read_to_string(&self.cache_file).ok()?;
+        match self.keys.open(text) {
+            Ok(text) => Some(text),
+            Err(e) => {
+                tracing::warn!("Failed to open the copy of subscription {}: {}", self.name, e);
+                None
+            }
+        }
+    }
+}
+
+/// The instants covering `start_date..=end_date`, as Google's
+/// `timeMin`/`timeMax` do.
+fn window(start_date: NaiveDate, end_date: NaiveDate) -> (DateTime<Utc>, DateTime<Utc>) {
+    let window_start = start_date.and_time(NaiveTime::MIN).and_utc();
+    let window_end = end_date.succ_opt().unwrap_or(end_date).and_time(NaiveTime::MIN).and_utc();
+    (window_start, window_end)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{TimeZone, Utc};
+    use wiremock::{matchers::{header, method, path}, Mock, MockServer, ResponseTemplate};
+
+    const FEED: &str = "BEGIN:VCALENDAR\r\n\
+        BEGIN:VEVENT\r\nUID:old\r\nDTSTART:20241201T150000Z\r\nSUMMARY:Last season\r\nEND:VEVENT\r\n\
+        BEGIN:VEVENT\r\nUID:next\r\nDTSTART;VALUE=DATE:20250119\r\nSUMMARY:Cup final\r\nEND:VEVENT\r\n\
+        END:VCALENDAR\r\n";
+
+    fn january(from: u32, to: u32) -> (NaiveDate, NaiveDate) {
+        (NaiveDate::from_ymd_opt(2025, 1, from).unwrap(), NaiveDate::from_ymd_opt(2025, 1, to).unwrap())
+    }
+
+    #[test]
+    fn feeds_are_named_after_their_subscription() {
+        let feed = Feed::new("Sunday league/U12", "webcal://league.example/u12.ics", Path::new("/cache/subscriptions"));
+
+        assert_eq!(feed.url, "https://league.example/u12.ics");
+        assert_eq!(feed.calendar_id(), "ics:Sunday league/U12");
+        assert_eq!(feed.cache_file, Path::new("/cache/subscriptions/Sunday%20league%2FU12.ics"));
+        assert_ne!(Feed::new("a_b", &feed.url, Path::new("/")).cache_file, Feed::new("a/b", &feed.url, Path::new("/")).cache_file);
+    }
+
+    #[test]
+    fn copies_are_sealed_when_encryption_is_on() {
+        let dir = tempfile::tempdir().unwrap();
+        let keys = KeySource::Key(crate::storage::crypto::DataKey::generate().unwrap());
+        let feed = Feed::new("league", "https://league.example/u12.ics", dir.path()).with_keys(keys);
+
+        feed.keep(FEED);
+
+        assert!(!std::fs::read_to_string(&feed.cache_file).unwrap().contains("Cup final"));
+        assert_eq!(feed.last_copy().as_deref(), Some(FEED));
+    }
+
+    #[tokio::test]
+    async fn recent_downloads_are_reused_and_older_ones_revalidated() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/league.ics"))
+            .and(header("If-None-Match", "\"v1\""))
+            .respond_with(ResponseTemplate::new(304))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/league.ics"))
+            .respond_with(ResponseTemplate::new(200).insert_header("ETag", "\"v1\"").set_body_string(FEED))
+            .expect(2)
+            .mount(&server)
+            .await;
+        let dir = tempfile::tempdir().unwrap();
+        let feed = Feed::new("league", &format!("{}/league.ics", server.uri()), dir.path());
+        let (start, end) = january(13, 19);
+
+        let client = FeedClient::default();
+        feed.fetch_events(&client, start, end).await.unwrap();
+        assert_eq!(feed.fetch_events(&client, start, end).await.unwrap().len(), 1);
+
+        let client = FeedClient::default().with_fresh_for(Duration::ZERO);
+        feed.fetch_events(&client, start, end).await.unwrap();
+        assert_eq!(feed.fetch_events(&client, start, end).await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn an_unreachable_feed_falls_back_to_the_kept_copy() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/league.ics"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(FEED))
+            .up_to_n_times(1)
+            .mount(&server)
+            .await;
+        let dir = tempfile::tempdir().unwrap();
+        let feed = Feed::new("league", &format!("{}/league.ics", server.uri()), dir.path());
+        let (start, end) = january(13, 19);
+
+        let fetched = feed.fetch_events(&FeedClient::default(), start, end).await.unwrap();
+        let kept = feed.fetch_events(&FeedClient::default(), start, end).await.unwrap();
+
+        assert_eq!(kept, fetched);
+    }
+
+    #[test]
+    fn keeps_events_overlapping_the_window() {
+        let (start, end) = january(13, 19);
+
+        let (window_start, window_end) = window(start, end);
+        let events = ics::from_ics(FEED, "ics:league", window_start, window_end);
+
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].start, Utc.with_ymd_and_hms(2025, 1, 19, 0, 0, 0).unwrap());
+        let (window_start, window_end) = window(january(20, 26).0, january(20, 26).1);
+        assert!(ics::from_ics(FEED, "ics:league", window_start, window_end).is_empty());
+    }
+}
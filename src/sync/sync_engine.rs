@@ -1,8 +1,21 @@
-use crate::calendar::Event;
+use crate::calendar::{subscription, AccessRole, Calendar, Event};
+use crate::storage::cache::{Cache, CacheError};
 use crate::storage::config::Config;
-use crate::sync::google_api::{CalendarApi, DateRange, GoogleCalendarClient, CreatedEventInfo};
+use crate::storage::crypto::KeySource;
+use crate::sync::conflict::{reconcile, Reconciliation};
+use crate::sync::google_api::{CalendarApi, ColorPalette, DateRange, GoogleCalendarClient, CreatedEventInfo};
+use crate::sync::mock_api::{ApiBackend, MockCalendarApi};
 use crate::sync::google_auth::GoogleAuthenticator;
+use crate::sync::google_tasks::{GoogleTasksClient, Task, TasksApi};
+use crate::sync::loaded_ranges::{LoadedRanges, WINDOW_DAYS};
+use crate::sync::push::WatchChannel;
+use crate::sync::scheduler::RequestScheduler;
+use crate::sync::subscriptions::{Feed, FeedClient};
 use chrono::NaiveDate;
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Instant;
 use thiserror::Error;
 
 #[derive(Debug, Error)]
@@ -11,17 +24,207 @@ pub enum SyncError {
     AuthError(#[from] crate::sync::google_auth::AuthError),
     #[error("API error: {0}")]
     ApiError(#[from] crate::sync::google_api::ApiError),
+    #[error("Read-only mode: changes are not sent")]
+    ReadOnly,
+    #[error("Calendar {0} is read-only")]
+    CalendarReadOnly(String),
+    #[error("Cache error: {0}")]
+    CacheError(#[from] CacheError),
+}
+
+/// What `sync_to_cache` did.
+#[derive(Debug, Default, PartialEq)]
+pub struct CacheSync {
+    pub start_date: NaiveDate,
+    pub end_date: NaiveDate,
+    pub events: usize,
+    pub calendars: usize,
+    /// Overlays that could not be fetched; the default calendar failing fails the sync.
+    pub failed_calendars: Vec<String>,
+    /// Kept local edits that reached the server.
+    pub pushed: usize,
+    /// Kept local edits that changed on the server too; they need the TUI.
+    pub conflicts: usize,
+}
+
+/// Per-event results of a bulk operation.
+#[derive(Debug, Default)]
+pub struct BatchOutcome {
+    pub succeeded: Vec<String>,
+    pub failed: Vec<(String, SyncError)>,
 }
 
 pub struct SyncEngine {
     config: Config,
     auth: GoogleAuthenticator,
+    colors: Option<(ColorPalette, Option<String>)>,
+    loaded: LoadedRanges,
+    cache: Option<Cache>,
+    scheduler: RequestScheduler,
+    mock: Option<MockCalendarApi>,
+    /// The mock holds the local calendar, saved to the cache after writes.
+    local: bool,
+    api_base_url: Option<String>,
+    /// Roles from the last calendar list; empty until it is fetched.
+    calendar_access: HashMap<String, AccessRole>,
+    feed_client: FeedClient,
 }
 
 impl SyncEngine {
     pub fn new(config: Config) -> Self {
         let auth = GoogleAuthenticator::new(config.clone());
-        Self { config, auth }
+        let scheduler = RequestScheduler::new(config.sync.max_requests_per_second);
+        let backend = ApiBackend::from_config(config.sync.backend.as_deref());
+        let mock = backend.is_offline()
+            .then(|| MockCalendarApi::new().with_failure_every(config.sync.mock_failure_every));
+        let engine = Self { config, auth, colors: None, loaded: LoadedRanges::default(), cache: None, scheduler, mock, local: false, api_base_url: None, calendar_access: HashMap::new(), feed_client: FeedClient::default() };
+        if backend == ApiBackend::Local { engine.open_local_calendar() } else { engine }
+    }
+
+    /// Seeds the mock with the events the local calendar left in the cache.
+    fn open_local_calendar(mut self) -> Self {
+        let cache = match Cache::open(&self.config.cache_path()) {
+            Ok(cache) => cache.with_keys(KeySource::from_config(&self.config.security)),
+            Err(e) => {
+                tracing::warn!("Failed to open the local calendar: {}", e);
+                return self;
+            }
+        };
+        match cache.load_events() {
+            Ok(events) => {
+                if let Some(mock) = &self.mock {
+                    mock.add_events(events);
+                }
+                self.local = true;
+                self.cache = Some(cache);
+            }
+            Err(e) => tracing::warn!("Failed to read the local calendar: {}", e),
+        }
+        self
+    }
+
+    /// Talks to `mock` instead of Google; no sign-in is needed.
+    pub fn with_mock(mut self, mock: MockCalendarApi) -> Self {
+        self.mock = Some(mock);
+        self.local = false;
+        self
+    }
+
+    /// Saves the local calendar after a write; Google and the mock keep
+    /// their own events.
+    fn save_local_calendar(&self) {
+        if !self.local {
+            return;
+        }
+        if let (Some(cache), Some(mock)) = (&self.cache, &self.mock)
+            && let Err(e) = cache.replace_all(&mock.events())
+        {
+            tracing::warn!("Failed to save the local calendar: {}", e);
+        }
+    }
+
+    /// Sends Google Calendar requests to `base_url`, e.g. a stub server.
+    pub fn with_api_base_url(mut self, base_url: String) -> Self {
+        self.api_base_url = Some(base_url);
+        self
+    }
+
+    pub fn mock(&self) -> Option<&MockCalendarApi> {
+        self.mock.as_ref()
+    }
+
+    async fn calendar_client(&mut self) -> Result<Arc<dyn CalendarApi + Send + Sync>, SyncError> {
+        if let Some(mock) = &self.mock {
+            return Ok(Arc::new(mock.clone()));
+        }
+        let token = self.auth.get_valid_token().await?;
+        let client = GoogleCalendarClient::new(token.access_token);
+        Ok(match &self.api_base_url {
+            Some(base_url) => Arc::new(client.with_base_url(base_url.clone())),
+            None => Arc::new(client),
+        })
+    }
+
+    pub fn with_cache(mut self, cache: Cache) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+
+    /// Turns on read-only mode on top of the config setting.
+    pub fn with_read_only(mut self, read_only: bool) -> Self {
+        self.config.sync.read_only |= read_only;
+        self
+    }
+
+    pub fn is_read_only(&self) -> bool {
+        self.config.sync.read_only
+    }
+
+    fn check_writable(&self) -> Result<(), SyncError> {
+        if self.is_read_only() { Err(SyncError::ReadOnly) } else { Ok(()) }
+    }
+
+    /// Refuses subscription feeds and calendars the list marked as not
+    /// editable. Calendars that are not on the list are left to the server.
+    fn check_calendar_writable(&self, calendar_id: &str) -> Result<(), SyncError> {
+        if subscription::is_subscription(calendar_id) {
+            return Err(SyncError::CalendarReadOnly(calendar_id.to_string()));
+        }
+        match self.calendar_access.get(calendar_id) {
+            Some(role) if !role.can_edit() => Err(SyncError::CalendarReadOnly(calendar_id.to_string())),
+            _ => Ok(()),
+        }
+    }
+
+    pub fn cache(&self) -> Option<&Cache> {
+        self.cache.as_ref()
+    }
+
+    /// Keeps an edit that could not be pushed, based on the server's
+    /// `base_modified`, so a later sync can push it or spot a conflict.
+    /// Returns false when there is no cache to keep it in.
+    pub fn keep_local_edit(&self, event: &Event, base_modified: chrono::DateTime<chrono::Utc>) -> Result<bool, CacheError> {
+        let Some(cache) = &self.cache else {
+            return Ok(false);
+        };
+        let mut local = event.clone();
+        local.last_modified = base_modified;
+        cache.store_local_edit(&local, chrono::Utc::now())?;
+        Ok(true)
+    }
+
+    /// The last successful fetch recorded in the cache.
+    pub fn last_sync(&self) -> Option<chrono::DateTime<chrono::Utc>> {
+        let cache = self.cache.as_ref()?;
+        cache.last_sync()
+            .inspect_err(|e| tracing::warn!("Failed to read the last sync time: {}", e))
+            .ok()
+            .flatten()
+    }
+
+    pub fn record_sync(&self, at: chrono::DateTime<chrono::Utc>) {
+        if let Some(cache) = &self.cache
+            && let Err(e) = cache.set_last_sync(at)
+        {
+            tracing::warn!("Failed to save the last sync time: {}", e);
+        }
+    }
+
+    pub fn forget_local_edit(&self, event_id: &str) -> Result<(), CacheError> {
+        match &self.cache {
+            Some(cache) => cache.delete_event(event_id),
+            None => Ok(()),
+        }
+    }
+
+    /// Sorts the kept local edits into ones to push and ones that changed on
+    /// the server too.
+    pub fn reconcile_local_edits(&self, remote: &[Event]) -> Result<Reconciliation, CacheError> {
+        let local_edits = match &self.cache {
+            Some(cache) => cache.local_edits()?,
+            None => Vec::new(),
+        };
+        Ok(reconcile(&local_edits, remote))
     }
 
     pub async fn fetch_events(
@@ -29,57 +232,741 @@ impl SyncEngine {
         start_date: NaiveDate,
         end_date: NaiveDate,
     ) -> Result<Vec<Event>, SyncError> {
-        let token = self.auth.get_valid_token().await?;
+        let batches = self.window_fetch(start_date, end_date).await?.await?;
+        Ok(batches.into_iter().flatten().collect())
+    }
 
-        let client = GoogleCalendarClient::new(token.access_token);
+    /// Authenticates, then returns the fetch of `start_date..=end_date` for the
+    /// default calendar, overlays and subscriptions. The fetch owns everything it needs, so it
+    /// can run on its own task while the UI keeps going. It yields one batch per
+    /// calendar in `sync_calendars` order; a failed overlay or feed is empty.
+    pub async fn window_fetch(
+        &mut self,
+        start_date: NaiveDate,
+        end_date: NaiveDate,
+    ) -> Result<impl Future<Output = Result<Vec<Vec<Event>>, SyncError>> + Send + 'static, SyncError> {
+        let client = self.calendar_client().await?;
+        let calendar_id = self.config.calendars.default.clone();
 
-        let date_range = DateRange::new(start_date, end_date);
+        if self.colors.is_none() {
+            self.colors = Some(Self::fetch_colors(client.as_ref(), &calendar_id).await);
+        }
+        let colors = self.colors.clone();
+        let overlays = self.config.calendars.overlays.clone();
+        let feeds = self.feeds();
+        let feed_client = self.feed_client.clone();
+        let budget = self.scheduler.budget();
 
-        let calendar_id = &self.config.calendars.default;
-        let events = client.fetch_events(calendar_id, date_range).await?;
+        Ok(async move {
+            // Feeds download alongside Google, so a slow one only delays itself.
+            let feed_fetches: Vec<_> = feeds.into_iter()
+                .map(|feed| {
+                    let feed_client = feed_client.clone();
+                    tokio::spawn(async move {
+                        feed.fetch_events(&feed_client, start_date, end_date).await.unwrap_or_else(|e| {
+                            tracing::warn!("Failed to fetch subscription {}: {}", feed.name, e);
+                            Vec::new()
+                        })
+                    })
+                })
+                .collect();
 
-        Ok(events)
+            budget.throttle().await;
+            let mut events = client.fetch_events(&calendar_id, DateRange::new(start_date, end_date)).await?;
+            if let Some((palette, calendar_color)) = &colors {
+                for event in &mut events {
+                    palette.apply(event, calendar_color.as_deref());
+                }
+            }
+
+            let mut batches = vec![events];
+
+            // Overlays are extras; one failing should not hide the user's own events.
+            for overlay in &overlays {
+                budget.throttle().await;
+                batches.push(client.fetch_events(overlay, DateRange::new(start_date, end_date)).await
+                    .unwrap_or_else(|e| {
+                        tracing::warn!("Failed to fetch overlay calendar {}: {}", overlay, e);
+                        Vec::new()
+                    }));
+            }
+            for fetch in feed_fetches {
+                batches.push(fetch.await.unwrap_or_else(|e| {
+                    tracing::warn!("Subscription fetch task failed: {}", e);
+                    Vec::new()
+                }));
+            }
+
+            Ok(batches)
+        })
     }
 
-    pub async fn fetch_events_around_date(
-        &mut self,
-        center_date: NaiveDate,
-    ) -> Result<Vec<Event>, SyncError> {
-        let days_past = self.config.sync.sync_past_days as i64;
-        let days_future = self.config.sync.sync_future_days as i64;
+    /// Starts fetching an overlay calendar from the next sync on.
+    pub fn add_overlay(&mut self, calendar_id: String) {
+        if !self.config.calendars.overlays.contains(&calendar_id) {
+            self.config.calendars.overlays.push(calendar_id);
+        }
+    }
+
+    /// The palette and calendar color rarely change, so they are fetched once
+    /// per session; failures only cost the colors.
+    async fn fetch_colors(client: &(dyn CalendarApi + Send + Sync), calendar_id: &str) -> (ColorPalette, Option<String>) {
+        let palette = client.fetch_colors().await.unwrap_or_else(|e| {
+            tracing::warn!("Failed to fetch color palette: {}", e);
+            ColorPalette::default()
+        });
+        let calendar_color = client.fetch_calendar_color(calendar_id).await.unwrap_or_else(|e| {
+            tracing::warn!("Failed to fetch calendar color: {}", e);
+            None
+        });
+        (palette, calendar_color)
+    }
+
+    /// The window a full sync fetches around `center_date`.
+    pub fn window_around(&self, center_date: NaiveDate) -> (NaiveDate, NaiveDate) {
+        let days_past = (self.config.sync.sync_past_days as u64).min(WINDOW_DAYS);
+        let days_future = (self.config.sync.sync_future_days as u64).min(WINDOW_DAYS);
 
         let start_date = center_date
-            .checked_sub_days(chrono::Days::new(days_past as u64))
+            .checked_sub_days(chrono::Days::new(days_past))
             .unwrap_or(center_date);
 
         let end_date = center_date
-            .checked_add_days(chrono::Days::new(days_future as u64))
+            .checked_add_days(chrono::Days::new(days_future))
             .unwrap_or(center_date);
 
-        self.fetch_events(start_date, end_date).await
+        (start_date, end_date)
     }
 
-    pub async fn create_event(&mut self, event: &Event) -> Result<CreatedEventInfo, SyncError> {
-        let token = self.auth.get_valid_token().await?;
-        let client = GoogleCalendarClient::new(token.access_token);
-        let calendar_id = &self.config.calendars.default;
+    /// Calendars a sync fetches: the default one first, then overlays and
+    /// subscriptions.
+    pub fn sync_calendars(&self) -> Vec<String> {
+        let mut calendars = vec![self.config.calendars.default.clone()];
+        calendars.extend(self.config.calendars.overlays.iter().cloned());
+        calendars.extend(self.feeds().iter().map(Feed::calendar_id));
+        calendars
+    }
+
+    fn feeds(&self) -> Vec<Feed> {
+        let dir = self.config.subscriptions_dir();
+        let keys = KeySource::from_config(&self.config.security);
+        self.config.subscriptions.iter()
+            .map(|(name, url)| Feed::new(name, url, &dir).with_keys(keys.clone()))
+            .collect()
+    }
+
+    /// Fetches one calendar, coloring default-calendar events, so a caller
+    /// can report progress between calendars. Subscriptions need no sign-in.
+    pub async fn fetch_calendar(
+        &mut self,
+        calendar_id: &str,
+        start_date: NaiveDate,
+        end_date: NaiveDate,
+    ) -> Result<Vec<Event>, SyncError> {
+        if let Some(feed) = self.feeds().into_iter().find(|feed| feed.calendar_id() == calendar_id) {
+            return Ok(feed.fetch_events(&self.feed_client, start_date, end_date).await?);
+        }
+        let client = self.calendar_client().await?;
+        let default_calendar = self.config.calendars.default.clone();
+
+        if self.colors.is_none() {
+            self.colors = Some(Self::fetch_colors(client.as_ref(), &default_calendar).await);
+        }
+
+        self.scheduler.throttle().await;
+        let mut events = client.fetch_events(calendar_id, DateRange::new(start_date, end_date)).await?;
+        if calendar_id == default_calendar
+            && let Some((palette, calendar_color)) = &self.colors
+        {
+            for event in &mut events {
+                palette.apply(event, calendar_color.as_deref());
+            }
+        }
+        Ok(events)
+    }
+
+    /// Fetches a single event, even one outside the loaded windows.
+    pub async fn get_event(&mut self, calendar_id: &str, event_id: &str) -> Result<Event, SyncError> {
+        let client = self.calendar_client().await?;
+        self.scheduler.throttle().await;
+        let mut event = client.get_event(calendar_id, event_id).await?;
+        if calendar_id == self.config.calendars.default
+            && let Some((palette, calendar_color)) = &self.colors
+        {
+            palette.apply(&mut event, calendar_color.as_deref());
+        }
+        Ok(event)
+    }
+
+    /// Forgets earlier windows after a full sync of `start_date..=end_date`.
+    pub fn reset_loaded(&mut self, start_date: NaiveDate, end_date: NaiveDate) {
+        self.loaded.clear();
+        self.loaded.insert(start_date, end_date);
+    }
+
+    /// The next date window to load so navigation around `date` has events.
+    pub fn next_window(&self, date: NaiveDate) -> Option<(NaiveDate, NaiveDate)> {
+        self.loaded.next_window(date)
+    }
+
+    /// True when events from `start_date` through `end_date` are loaded.
+    pub fn is_loaded(&self, start_date: NaiveDate, end_date: NaiveDate) -> bool {
+        self.loaded.covers(start_date, end_date)
+    }
+
+    /// Records a window as fetched. Failed windows are recorded too, so a
+    /// network error is not retried on every key; `:w` starts over.
+    pub fn mark_fetched(&mut self, start_date: NaiveDate, end_date: NaiveDate) {
+        self.loaded.insert(start_date, end_date);
+    }
+
+    /// A full sync without the TUI: fetches the window around `center_date`
+    /// from every sync calendar, pushes kept local edits that did not change
+    /// on the server and stores the window in the cache.
+    pub async fn sync_to_cache(&mut self, center_date: NaiveDate, now: chrono::DateTime<chrono::Utc>) -> Result<CacheSync, SyncError> {
+        if let Err(e) = self.fetch_calendars().await {
+            tracing::warn!("Failed to fetch calendar list: {}", e);
+        }
+
+        let (start_date, end_date) = self.window_around(center_date);
+        let calendars = self.sync_calendars();
+        let mut summary = CacheSync { start_date, end_date, calendars: calendars.len(), ..CacheSync::default() };
+        let mut events = Vec::new();
+        let mut fetched_calendars = Vec::new();
+        for (index, calendar_id) in calendars.iter().enumerate() {
+            match self.fetch_calendar(calendar_id, start_date, end_date).await {
+                Ok(fetched) => {
+                    events.extend(fetched);
+                    fetched_calendars.push(calendar_id.clone());
+                }
+                Err(e) if index == 0 => return Err(e),
+                Err(e) => {
+                    tracing::warn!("Failed to fetch overlay calendar {}: {}", calendar_id, e);
+                    summary.failed_calendars.push(calendar_id.clone());
+                }
+            }
+        }
+        summary.events = events.len();
+
+        let reconciliation = self.reconcile_local_edits(&events)?;
+        summary.conflicts = reconciliation.conflicts.len();
+        // Read-only mode leaves kept edits in the cache for later.
+        if !self.is_read_only() {
+            for mut event in reconciliation.to_push {
+                match self.update_event(&mut event).await {
+                    Ok(()) => {
+                        self.forget_local_edit(&event.id)?;
+                        summary.pushed += 1;
+                        if let Some(fetched) = events.iter_mut().find(|fetched| fetched.id == event.id) {
+                            *fetched = event;
+                        }
+                    }
+                    Err(e) => tracing::warn!("Failed to push local edit {}: {}", event.id, e),
+                }
+            }
+        }
+
+        if let Some(cache) = &self.cache {
+            let window_start = start_date.and_time(chrono::NaiveTime::MIN).and_utc();
+            let window_end = end_date.succ_opt().unwrap_or(end_date).and_time(chrono::NaiveTime::MIN).and_utc();
+            cache.replace_window(window_start, window_end, &fetched_calendars, &events)?;
+        }
+        self.reset_loaded(start_date, end_date);
+        self.record_sync(now);
+        Ok(summary)
+    }
+
+    /// Every calendar in the user's calendar list. Their roles are kept,
+    /// and writes to calendars that cannot be edited are refused from then on.
+    pub async fn fetch_calendars(&mut self) -> Result<Vec<Calendar>, SyncError> {
+        let client = self.calendar_client().await?;
+        let calendars = client.fetch_calendars().await?;
+        self.calendar_access = calendars.iter()
+            .map(|calendar| (calendar.id.clone(), calendar.access_role.clone()))
+            .collect();
+        Ok(calendars)
+    }
+
+    pub async fn create_event(&mut self, calendar_id: &str, event: &Event) -> Result<CreatedEventInfo, SyncError> {
+        self.check_writable()?;
+        self.check_calendar_writable(calendar_id)?;
+        let client = self.calendar_client().await?;
+        self.scheduler.throttle().await;
         let created = client.create_event(calendar_id, event).await?;
+        self.save_local_calendar();
         Ok(created)
     }
 
-    pub async fn update_event(&mut self, event: &Event) -> Result<(), SyncError> {
-        let token = self.auth.get_valid_token().await?;
-        let client = GoogleCalendarClient::new(token.access_token);
-        let calendar_id = &self.config.calendars.default;
-        client.update_event(calendar_id, &event.id, event).await?;
+    /// Pushes `event` and stores the server's new etag on it, so the next
+    /// write is checked against this version.
+    pub async fn update_event(&mut self, event: &mut Event) -> Result<(), SyncError> {
+        self.check_writable()?;
+        self.check_calendar_writable(&event.calendar_id)?;
+        let client = self.calendar_client().await?;
+        self.scheduler.throttle().await;
+        event.etag = client.update_event(&event.calendar_id, &event.id, event).await?;
+        self.save_local_calendar();
         Ok(())
     }
 
-    pub async fn delete_event(&mut self, event_id: &str) -> Result<(), SyncError> {
+    /// Deletes `event` from its own calendar, sending its etag as `If-Match`.
+    pub async fn delete_event(&mut self, event: &Event) -> Result<(), SyncError> {
+        self.check_writable()?;
+        self.check_calendar_writable(&event.calendar_id)?;
+        let client = self.calendar_client().await?;
+        self.scheduler.throttle().await;
+        client.delete_event(&event.calendar_id, &event.id, event.etag.as_deref()).await?;
+        self.save_local_calendar();
+        Ok(())
+    }
+
+    /// Deletes several events with a single token and client; one failure,
+    /// such as an event on a read-only calendar, does not stop the rest.
+    pub async fn delete_events(&mut self, events: &[Event]) -> Result<BatchOutcome, SyncError> {
+        self.check_writable()?;
+        let client = self.calendar_client().await?;
+
+        let mut outcome = BatchOutcome::default();
+        for event in events {
+            if let Err(e) = self.check_calendar_writable(&event.calendar_id) {
+                outcome.failed.push((event.id.clone(), e));
+                continue;
+            }
+            self.scheduler.throttle().await;
+            match client.delete_event(&event.calendar_id, &event.id, event.etag.as_deref()).await {
+                Ok(()) => outcome.succeeded.push(event.id.clone()),
+                Err(e) => outcome.failed.push((event.id.clone(), e.into())),
+            }
+        }
+        self.save_local_calendar();
+        Ok(outcome)
+    }
+
+    pub async fn update_events(&mut self, events: &mut [Event]) -> Result<BatchOutcome, SyncError> {
+        self.check_writable()?;
+        let client = self.calendar_client().await?;
+
+        let mut outcome = BatchOutcome::default();
+        for event in events {
+            if let Err(e) = self.check_calendar_writable(&event.calendar_id) {
+                outcome.failed.push((event.id.clone(), e));
+                continue;
+            }
+            self.scheduler.throttle().await;
+            match client.update_event(&event.calendar_id, &event.id, event).await {
+                Ok(etag) => {
+                    event.etag = etag;
+                    outcome.succeeded.push(event.id.clone());
+                }
+                Err(e) => outcome.failed.push((event.id.clone(), e.into())),
+            }
+        }
+        self.save_local_calendar();
+        Ok(outcome)
+    }
+
+    /// Queues an update to send once edits pause; a newer update to the
+    /// same event replaces the queued one.
+    pub fn queue_update(&mut self, event: Event) {
+        self.scheduler.queue_update(event, Instant::now());
+    }
+
+    pub fn has_queued_updates(&self) -> bool {
+        self.scheduler.has_queued()
+    }
+
+    /// Sends the queued updates once edits have paused, or right away with
+    /// `force`. Returns the sent events, with new etags on the ones that
+    /// succeeded.
+    pub async fn flush_updates(&mut self, force: bool) -> Option<(Vec<Event>, Result<BatchOutcome, SyncError>)> {
+        let mut events = if force { self.scheduler.take_all() } else { self.scheduler.take_due(Instant::now()) };
+        if events.is_empty() {
+            return None;
+        }
+        let result = self.update_events(&mut events).await;
+        Some((events, result))
+    }
+
+    /// The public address and local port for push notifications, when configured.
+    pub fn push_target(&self) -> Option<(&str, u16)> {
+        self.config.sync.push_address.as_deref().map(|address| (address, self.config.sync.push_port))
+    }
+
+    /// Registers a fresh push channel for the default calendar.
+    pub async fn watch_default_calendar(&mut self, address: &str) -> Result<WatchChannel, SyncError> {
+        let client = self.calendar_client().await?;
+        let channel_id = uuid::Uuid::new_v4().to_string();
+        let token = uuid::Uuid::new_v4().to_string();
+        Ok(client.watch_events(&self.config.calendars.default, &channel_id, &token, address).await?)
+    }
+
+    pub async fn stop_watch(&mut self, channel: &WatchChannel) -> Result<(), SyncError> {
+        let client = self.calendar_client().await?;
+        Ok(client.stop_channel(channel).await?)
+    }
+
+    /// The mock backend has no tasks.
+    pub fn tasks_enabled(&self) -> bool {
+        self.config.sync.tasks && self.mock.is_none()
+    }
+
+    pub async fn fetch_tasks_due(&mut self, date: NaiveDate) -> Result<Vec<Task>, SyncError> {
         let token = self.auth.get_valid_token().await?;
-        let client = GoogleCalendarClient::new(token.access_token);
-        let calendar_id = &self.config.calendars.default;
-        client.delete_event(calendar_id, event_id).await?;
+        let client = GoogleTasksClient::new(token.access_token);
+        Ok(client.fetch_tasks_due(date).await?)
+    }
+
+    pub async fn complete_task(&mut self, task: &Task) -> Result<(), SyncError> {
+        self.check_writable()?;
+        let token = self.auth.get_valid_token().await?;
+        let client = GoogleTasksClient::new(token.access_token);
+        client.complete_task(task).await?;
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sync::google_api::ApiError;
+    use crate::sync::test_support::{event_json, sample_event, StubGoogleApi, ACCESS_TOKEN};
+    use serde_json::json;
+    use wiremock::matchers::{header, method, path};
+    use wiremock::{Mock, ResponseTemplate};
+
+    fn january(from: u32, to: u32) -> (NaiveDate, NaiveDate) {
+        (NaiveDate::from_ymd_opt(2025, 1, from).unwrap(), NaiveDate::from_ymd_opt(2025, 1, to).unwrap())
+    }
+
+    #[tokio::test]
+    async fn calendar_ids_are_encoded_in_the_path() {
+        let api = StubGoogleApi::start().await;
+        api.events_pages("en.usa%23holiday%40group.v.calendar.google.com", vec![
+            vec![event_json("h", "New Year's Day", "2025-01-01T00:00:00Z", "2025-01-02T00:00:00Z")],
+        ]).await;
+        let mut engine = api.engine();
+
+        let (start, end) = january(1, 7);
+        let events = engine.fetch_calendar("en.usa#holiday@group.v.calendar.google.com", start, end).await.unwrap();
+
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].calendar_id, "en.usa#holiday@group.v.calendar.google.com");
+    }
+
+    #[tokio::test]
+    async fn fetch_follows_page_tokens() {
+        let api = StubGoogleApi::start().await;
+        api.events_pages("primary", vec![
+            vec![
+                event_json("a", "Standup", "2025-01-13T09:00:00Z", "2025-01-13T09:15:00Z"),
+                event_json("b", "Review", "2025-01-13T14:00:00Z", "2025-01-13T15:00:00Z"),
+            ],
+            vec![event_json("c", "Retro", "2025-01-17T16:00:00Z", "2025-01-17T17:00:00Z")],
+        ]).await;
+        let mut engine = api.engine();
+
+        let (start, end) = january(13, 19);
+        let events = engine.fetch_calendar("primary", start, end).await.unwrap();
+
+        let titles: Vec<&str> = events.iter().map(|e| e.title.as_str()).collect();
+        assert_eq!(titles, vec!["Standup", "Review", "Retro"]);
+        assert_eq!(events[2].etag.as_deref(), Some("\"etag-c\""));
+    }
+
+    #[tokio::test]
+    async fn gets_a_single_event_by_id() {
+        let api = StubGoogleApi::start().await;
+        api.respond("GET", "/calendars/work/events/far-away", 200,
+            event_json("far-away", "Conference", "2026-06-01T09:00:00Z", "2026-06-01T17:00:00Z")).await;
+        let mut engine = api.engine();
+
+        let event = engine.get_event("work", "far-away").await.unwrap();
+        assert_eq!((event.title.as_str(), event.calendar_id.as_str()), ("Conference", "work"));
+
+        let missing = engine.get_event("work", "nope").await;
+        assert!(matches!(missing, Err(SyncError::ApiError(ApiError::NotFound(_)))));
+    }
+
+    #[tokio::test]
+    async fn creates_updates_and_deletes_with_etags() {
+        let api = StubGoogleApi::start().await;
+        api.events_pages("primary", vec![
+            vec![event_json("a", "Standup", "2025-01-13T09:00:00Z", "2025-01-13T09:15:00Z")],
+        ]).await;
+        Mock::given(method("POST"))
+            .and(path("/calendars/primary/events"))
+            .and(header("Authorization", format!("Bearer {}", ACCESS_TOKEN).as_str()))
+            .respond_with(ResponseTemplate::new(200).set_body_json(
+                event_json("new", "Lunch", "2025-01-14T12:00:00Z", "2025-01-14T13:00:00Z"),
+            ))
+            .expect(1)
+            .mount(&api.server)
+            .await;
+        Mock::given(method("PUT"))
+            .and(path("/calendars/primary/events/a"))
+            .and(header("If-Match", "\"etag-a\""))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "id": "a",
+                "start": { "dateTime": "2025-01-13T10:00:00Z" },
+                "end": { "dateTime": "2025-01-13T10:15:00Z" },
+                "etag": "\"etag-a2\"",
+            })))
+            .expect(1)
+            .mount(&api.server)
+            .await;
+        Mock::given(method("DELETE"))
+            .and(path("/calendars/primary/events/a"))
+            .and(header("If-Match", "\"etag-a2\""))
+            .respond_with(ResponseTemplate::new(204))
+            .expect(1)
+            .mount(&api.server)
+            .await;
+        let mut engine = api.engine();
+
+        let (start, end) = january(13, 19);
+        let mut event = engine.fetch_calendar("primary", start, end).await.unwrap().remove(0);
+        let created = engine.create_event("primary", &event).await.unwrap();
+        assert_eq!(created.id, "new");
+
+        event.start += chrono::Duration::hours(1);
+        event.end += chrono::Duration::hours(1);
+        engine.update_event(&mut event).await.unwrap();
+        assert_eq!(event.etag.as_deref(), Some("\"etag-a2\""));
+
+        engine.delete_event(&event).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn refuses_writes_to_calendars_listed_as_read_only() {
+        let api = StubGoogleApi::start().await;
+        api.respond("GET", "/users/me/calendarList", 200, json!({ "items": [
+            { "id": "primary", "accessRole": "owner" },
+            { "id": "holidays", "accessRole": "reader" },
+        ]})).await;
+        Mock::given(method("POST"))
+            .and(path("/calendars/holidays/events"))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(0)
+            .mount(&api.server)
+            .await;
+        let mut engine = api.engine();
+        engine.fetch_calendars().await.unwrap();
+
+        let mut event = sample_event("a");
+        event.calendar_id = "holidays".to_string();
+        let result = engine.create_event("holidays", &event).await;
+        assert!(matches!(result, Err(SyncError::CalendarReadOnly(id)) if id == "holidays"));
+
+        let outcome = engine.update_events(std::slice::from_mut(&mut event)).await.unwrap();
+        assert!(outcome.succeeded.is_empty());
+        assert!(matches!(outcome.failed[0].1, SyncError::CalendarReadOnly(_)));
+
+        assert!(matches!(engine.delete_event(&event).await, Err(SyncError::CalendarReadOnly(_))));
+        let outcome = engine.delete_events(std::slice::from_ref(&event)).await.unwrap();
+        assert!(matches!(outcome.failed[0].1, SyncError::CalendarReadOnly(_)));
+    }
+
+    #[tokio::test]
+    async fn read_only_mode_refuses_every_write() {
+        let mock = MockCalendarApi::new().with_events([sample_event("a")]);
+        let mut engine = SyncEngine::new(Config::default()).with_mock(mock).with_read_only(true);
+        let mut event = sample_event("a");
+        event.title = "Renamed".to_string();
+
+        assert!(engine.is_read_only());
+        assert!(matches!(engine.create_event("primary", &sample_event("b")).await, Err(SyncError::ReadOnly)));
+        assert!(matches!(engine.update_event(&mut event).await, Err(SyncError::ReadOnly)));
+        assert!(matches!(engine.update_events(std::slice::from_mut(&mut event)).await, Err(SyncError::ReadOnly)));
+        assert!(matches!(engine.delete_event(&event).await, Err(SyncError::ReadOnly)));
+        assert!(matches!(engine.delete_events(std::slice::from_ref(&event)).await, Err(SyncError::ReadOnly)));
+        let titles: Vec<String> = engine.mock().unwrap().events().into_iter().map(|e| e.title).collect();
+        assert_eq!(titles, vec![sample_event("a").title]);
+        assert!(!SyncEngine::new(Config::default()).with_read_only(false).is_read_only());
+    }
+
+    #[tokio::test]
+    async fn deletes_go_to_the_events_own_calendar() {
+        let api = StubGoogleApi::start().await;
+        Mock::given(method("DELETE"))
+            .and(path("/calendars/team/events/a"))
+            .respond_with(ResponseTemplate::new(204))
+            .expect(2)
+            .mount(&api.server)
+            .await;
+        let mut engine = api.engine();
+
+        let mut event = sample_event("a");
+        event.calendar_id = "team".to_string();
+        engine.delete_event(&event).await.unwrap();
+
+        let outcome = engine.delete_events(&[event]).await.unwrap();
+        assert_eq!(outcome.succeeded, vec!["a"]);
+    }
+
+    #[tokio::test]
+    async fn surfaces_rate_limits_and_server_side_changes() {
+        let api = StubGoogleApi::start().await;
+        api.respond("GET", "/calendars/primary/events", 429, json!({})).await;
+        api.respond("PUT", "/calendars/primary/events/a", 412, json!({})).await;
+        let mut engine = api.engine();
+
+        let (start, end) = january(13, 19);
+        let result = engine.fetch_calendar("primary", start, end).await;
+        assert!(matches!(result, Err(SyncError::ApiError(ApiError::RateLimited))));
+
+        let mut event = sample_event("a");
+        let result = engine.update_event(&mut event).await;
+        assert!(matches!(result, Err(SyncError::ApiError(ApiError::PreconditionFailed(_)))));
+    }
+
+    #[tokio::test]
+    async fn batch_updates_report_each_failure() {
+        let api = StubGoogleApi::start().await;
+        api.respond("PUT", "/calendars/primary/events/a", 200, json!({
+            "start": { "dateTime": "2025-01-13T09:00:00Z" },
+            "end": { "dateTime": "2025-01-13T10:00:00Z" },
+            "etag": "\"etag-a2\"",
+        })).await;
+        api.respond("PUT", "/calendars/primary/events/b", 404, json!({})).await;
+        let mut engine = api.engine();
+
+        let mut events = vec![
+            sample_event("a"),
+            sample_event("b"),
+        ];
+        let outcome = engine.update_events(&mut events).await.unwrap();
+
+        assert_eq!(outcome.succeeded, vec!["a".to_string()]);
+        assert_eq!(outcome.failed.len(), 1);
+        assert_eq!(events[0].etag.as_deref(), Some("\"etag-a2\""));
+    }
+
+    #[tokio::test]
+    async fn sync_to_cache_stores_the_window_and_pushes_kept_edits() {
+        let api = StubGoogleApi::start().await;
+        api.respond("GET", "/users/me/calendarList", 200, json!({ "items": [{ "id": "primary", "accessRole": "owner" }] })).await;
+        api.events_pages("primary", vec![vec![
+            event_json("a", "Standup", "2025-01-13T09:00:00Z", "2025-01-13T09:15:00Z"),
+            event_json("b", "Review", "2025-01-14T14:00:00Z", "2025-01-14T15:00:00Z"),
+        ]]).await;
+        api.respond("PUT", "/calendars/primary/events/a", 200, json!({
+            "start": { "dateTime": "2025-01-13T09:00:00Z" },
+            "end": { "dateTime": "2025-01-13T09:15:00Z" },
+            "etag": "\"etag-a2\"",
+        })).await;
+        let cache = Cache::new(rusqlite::Connection::open_in_memory().unwrap());
+        cache.initialize().unwrap();
+        let mut edit = sample_event("a");
+        edit.title = "Standup (moved room)".to_string();
+        edit.last_modified = "2025-01-10T08:00:00Z".parse().unwrap();
+        cache.store_local_edit(&edit, edit.last_modified).unwrap();
+        cache.store_event(&sample_event("deleted-on-server")).unwrap();
+        let mut engine = api.engine().with_cache(cache);
+
+        let now = "2025-01-15T06:00:00Z".parse().unwrap();
+        let summary = engine.sync_to_cache(january(15, 15).0, now).await.unwrap();
+
+        assert_eq!((summary.events, summary.pushed, summary.conflicts), (2, 1, 0));
+        let cache = engine.cache().unwrap();
+        assert!(cache.local_edits().unwrap().is_empty());
+        assert_eq!(cache.load_event("a").unwrap().unwrap().title, "Standup (moved room)");
+        assert!(cache.load_event("b").unwrap().is_some());
+        assert_eq!(cache.load_event("deleted-on-server").unwrap(), None);
+        assert_eq!(engine.last_sync(), Some(now));
+    }
+
+    #[tokio::test]
+    async fn sync_to_cache_keeps_the_rows_of_a_calendar_that_failed() {
+        let api = StubGoogleApi::start().await;
+        api.respond("GET", "/users/me/calendarList", 200, json!({ "items": [{ "id": "primary", "accessRole": "owner" }] })).await;
+        api.events_pages("primary", vec![vec![]]).await;
+        api.respond("GET", "/calendars/team/events", 500, json!({})).await;
+        let cache = Cache::new(rusqlite::Connection::open_in_memory().unwrap());
+        cache.initialize().unwrap();
+        let mut offsite = sample_event("offsite");
+        offsite.calendar_id = "team".to_string();
+        cache.store_event(&offsite).unwrap();
+        cache.store_event(&sample_event("cancelled")).unwrap();
+        let mut engine = api.engine().with_cache(cache);
+        engine.add_overlay("team".to_string());
+
+        let now = "2025-01-15T06:00:00Z".parse().unwrap();
+        let summary = engine.sync_to_cache(january(15, 15).0, now).await.unwrap();
+
+        assert_eq!(summary.failed_calendars, vec!["team".to_string()]);
+        let cache = engine.cache().unwrap();
+        assert_eq!(cache.load_event("offsite").unwrap(), Some(offsite));
+        assert_eq!(cache.load_event("cancelled").unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn window_fetches_yield_a_batch_per_calendar() {
+        let api = StubGoogleApi::start().await;
+        api.events_pages("primary", vec![vec![
+            event_json("a", "Standup", "2025-01-13T09:00:00Z", "2025-01-13T09:15:00Z"),
+        ]]).await;
+        api.respond("GET", "/calendars/team/events", 500, json!({})).await;
+        let mut engine = api.engine();
+        engine.add_overlay("team".to_string());
+
+        let (start, end) = january(13, 19);
+        let batches = engine.window_fetch(start, end).await.unwrap().await.unwrap();
+
+        let sizes: Vec<usize> = batches.iter().map(Vec::len).collect();
+        assert_eq!(sizes, vec![1, 0]);
+        assert_eq!(batches.len(), engine.sync_calendars().len());
+    }
+
+    #[tokio::test]
+    async fn subscriptions_are_fetched_kept_and_read_only() {
+        let server = wiremock::MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/team.ics"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                "BEGIN:VCALENDAR\r\nBEGIN:VEVENT\r\nUID:game-1\r\nDTSTART:20250118T150000Z\r\n\
+                 DTEND:20250118T170000Z\r\nSUMMARY:Rovers v United\r\nEND:VEVENT\r\nEND:VCALENDAR\r\n",
+            ))
+            .up_to_n_times(1)
+            .mount(&server)
+            .await;
+        let dir = tempfile::tempdir().unwrap();
+        let mut config = Config::default();
+        config.paths.cache = Some(dir.path().join("cache.db"));
+        config.subscriptions.insert("league".to_string(), format!("{}/team.ics", server.uri()));
+        let mut engine = SyncEngine::new(config).with_mock(MockCalendarApi::new());
+
+        assert_eq!(engine.sync_calendars(), vec!["primary", "ics:league"]);
+        let (start, end) = january(13, 19);
+        let mut events = engine.fetch_calendar("ics:league", start, end).await.unwrap();
+        assert_eq!(events.len(), 1);
+        assert_eq!((events[0].id.as_str(), events[0].title.as_str()), ("ics:league:game-1", "Rovers v United"));
+        assert!(dir.path().join("subscriptions/league.ics").is_file());
+
+        // The feed is gone now, so the recent download stands in.
+        let kept = engine.fetch_calendar("ics:league", start, end).await.unwrap();
+        assert_eq!(kept, events);
+
+        let result = engine.update_event(&mut events[0]).await;
+        assert!(matches!(result, Err(SyncError::CalendarReadOnly(id)) if id == "ics:league"));
+    }
+
+    #[tokio::test]
+    async fn the_local_calendar_is_kept_in_the_cache() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut config = Config::default();
+        config.paths.cache = Some(dir.path().join("cache.db"));
+        config.sync.backend = Some("local".to_string());
+
+        let mut engine = SyncEngine::new(config.clone());
+        let created = engine.create_event("primary", &sample_event("a")).await.unwrap();
+        let (start, end) = january(13, 19);
+        let mut events = engine.fetch_events(start, end).await.unwrap();
+        events[0].title = "Renamed".to_string();
+        engine.update_event(&mut events[0]).await.unwrap();
+
+        let mut reopened = SyncEngine::new(config);
+        let events = reopened.fetch_events(start, end).await.unwrap();
+        assert_eq!(events.len(), 1);
+        assert_eq!((events[0].id.as_str(), events[0].title.as_str()), (created.id.as_str(), "Renamed"));
+    }
+}
@@ -0,0 +1,158 @@
+use chrono::{DateTime, Duration, Utc};
+
+pub const DEFAULT_STALE_AFTER_MINUTES: u32 = 60;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum SyncPhase {
+    Idle,
+    Fetching,
+    Saving,
+    Offline,
+    Error(String),
+}
+
+/// Progress of the current sync and totals from the last finished one.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SyncState {
+    pub phase: SyncPhase,
+    pub calendars_done: usize,
+    pub calendars_total: usize,
+    pub events_fetched: usize,
+    /// When events were last fetched successfully.
+    pub last_sync: Option<DateTime<Utc>>,
+    /// Age past which the shown events count as stale.
+    pub stale_after: Duration,
+}
+
+impl Default for SyncState {
+    fn default() -> Self {
+        Self {
+            phase: SyncPhase::Idle,
+            calendars_done: 0,
+            calendars_total: 0,
+            events_fetched: 0,
+            last_sync: None,
+            stale_after: Duration::minutes(DEFAULT_STALE_AFTER_MINUTES as i64),
+        }
+    }
+}
+
+impl SyncPhase {
+    pub fn label(&self) -> String {
+        match self {
+            Self::Idle => "Idle".to_string(),
+            Self::Fetching => "Fetching".to_string(),
+            Self::Saving => "Saving".to_string(),
+            Self::Offline => "Offline".to_string(),
+            Self::Error(message) => format!("Error: {}", message),
+        }
+    }
+}
+
+impl SyncState {
+    pub fn start_fetch(&mut self, calendars_total: usize) {
+        self.phase = SyncPhase::Fetching;
+        self.calendars_done = 0;
+        self.calendars_total = calendars_total;
+        self.events_fetched = 0;
+    }
+
+    pub fn calendar_fetched(&mut self, events: usize) {
+        self.calendars_done = (self.calendars_done + 1).min(self.calendars_total);
+        self.events_fetched += events;
+    }
+
+    pub fn fetch_finished(&mut self, at: DateTime<Utc>) {
+        self.phase = SyncPhase::Idle;
+        self.calendars_done = self.calendars_total;
+        self.last_sync = Some(at);
+    }
+
+    pub fn is_busy(&self) -> bool {
+        matches!(self.phase, SyncPhase::Fetching | SyncPhase::Saving)
+    }
+
+    /// True when nothing was fetched for longer than `stale_after`.
+    pub fn is_stale(&self, now: DateTime<Utc>) -> bool {
+        self.last_sync.is_none_or(|at| now - at > self.stale_after)
+    }
+
+    /// Short form for the status bar.
+    pub fn summary(&self, now: DateTime<Utc>) -> String {
+        match &self.phase {
+            SyncPhase::Idle => match self.last_sync {
+                Some(at) => format!("synced {}", age_label(now - at)),
+                None => "Not synced".to_string(),
+            },
+            SyncPhase::Fetching if self.calendars_done < self.calendars_total => format!(
+                "Fetching {}/{} calendars, {} events",
+                self.calendars_done, self.calendars_total, self.events_fetched
+            ),
+            phase => phase.label(),
+        }
+    }
+}
+
+fn age_label(age: Duration) -> String {
+    if age < Duration::minutes(1) {
+        "just now".to_string()
+    } else if age < Duration::hours(1) {
+        format!("{}m ago", age.num_minutes())
+    } else if age < Duration::days(1) {
+        format!("{}h ago", age.num_hours())
+    } else {
+        format!("{}d ago", age.num_days())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn counts_calendars_and_events_while_fetching() {
+        let mut state = SyncState::default();
+        assert_eq!(state.summary(Utc::now()), "Not synced");
+
+        state.start_fetch(3);
+        state.calendar_fetched(40);
+        state.calendar_fetched(2);
+
+        assert!(state.is_busy());
+        assert_eq!(state.summary(Utc::now()), "Fetching 2/3 calendars, 42 events");
+    }
+
+    #[test]
+    fn finishing_records_the_time_and_keeps_totals() {
+        let at = Utc.with_ymd_and_hms(2025, 1, 15, 9, 30, 0).unwrap();
+        let mut state = SyncState::default();
+        state.start_fetch(2);
+        state.calendar_fetched(5);
+
+        state.fetch_finished(at);
+
+        assert_eq!(state.summary(at), "synced just now");
+        assert_eq!(state.last_sync, Some(at));
+        assert_eq!((state.calendars_done, state.events_fetched), (2, 5));
+
+        state.phase = SyncPhase::Error("offline".to_string());
+        assert_eq!(state.summary(at), "Error: offline");
+        assert!(!state.is_busy());
+    }
+
+    #[test]
+    fn shows_the_age_and_goes_stale_after_the_threshold() {
+        let at = Utc.with_ymd_and_hms(2025, 1, 15, 9, 0, 0).unwrap();
+        let mut state = SyncState::default();
+        assert!(state.is_stale(at));
+
+        state.fetch_finished(at);
+
+        assert_eq!(state.summary(at + Duration::minutes(12)), "synced 12m ago");
+        assert_eq!(state.summary(at + Duration::hours(3)), "synced 3h ago");
+        assert_eq!(state.summary(at + Duration::days(2)), "synced 2d ago");
+        assert!(!state.is_stale(at + Duration::minutes(60)));
+        assert!(state.is_stale(at + Duration::minutes(61)));
+    }
+}
@@ -0,0 +1,93 @@
+use chrono::{Duration, Utc};
+use serde_json::{json, Value};
+use tempfile::TempDir;
+use wiremock::matchers::{method, path, query_param, query_param_is_missing};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+use crate::calendar::Event;
+use crate::storage::config::Config;
+use crate::sync::google_auth::{TokenInfo, TokenStorage};
+use crate::sync::sync_engine::SyncEngine;
+use crate::test_support::EventBuilder;
+
+pub const ACCESS_TOKEN: &str = "test-token";
+
+/// A stubbed Google Calendar API with a signed-in engine pointed at it.
+pub struct StubGoogleApi {
+    pub server: MockServer,
+    token_dir: TempDir,
+}
+
+impl StubGoogleApi {
+    pub async fn start() -> Self {
+        let token_dir = tempfile::tempdir().expect("Failed to create token dir");
+        Self { server: MockServer::start().await, token_dir }
+    }
+
+    /// An engine with a valid token and no request budget.
+    pub fn engine(&self) -> SyncEngine {
+        let mut config = Config::default();
+        config.google.token_cache = self.token_dir.path().join("token.json");
+        config.sync.max_requests_per_second = 0;
+
+        let token = TokenInfo {
+            access_token: ACCESS_TOKEN.to_string(),
+            refresh_token: None,
+            expires_at: Utc::now() + Duration::hours(1),
+            token_type: "Bearer".to_string(),
+        };
+        TokenStorage::new(config.google.token_cache.clone())
+            .save_token(&token)
+            .expect("Failed to save test token");
+
+        SyncEngine::new(config).with_api_base_url(self.server.uri())
+    }
+
+    /// Serves `pages` of events for `calendar_id`, linked by page tokens.
+    pub async fn events_pages(&self, calendar_id: &str, pages: Vec<Vec<Value>>) {
+        let url = format!("/calendars/{}/events", calendar_id);
+        let count = pages.len();
+        for (i, items) in pages.into_iter().enumerate() {
+            let mut body = json!({ "items": items });
+            if i + 1 < count {
+                body["nextPageToken"] = json!(format!("page-{}", i + 1));
+            }
+            let mock = Mock::given(method("GET")).and(path(url.as_str()));
+            let mock = match i {
+                0 => mock.and(query_param_is_missing("pageToken")),
+                _ => mock.and(query_param("pageToken", format!("page-{}", i))),
+            };
+            mock.respond_with(ResponseTemplate::new(200).set_body_json(body))
+                .expect(1)
+                .mount(&self.server)
+                .await;
+        }
+    }
+
+    /// Answers every request matching `method_name` and `url` with `status`.
+    pub async fn respond(&self, method_name: &str, url: &str, status: u16, body: Value) {
+        Mock::given(method(method_name))
+            .and(path(url))
+            .respond_with(ResponseTemplate::new(status).set_body_json(body))
+            .mount(&self.server)
+            .await;
+    }
+}
+
+/// An event as Google sends it.
+pub fn event_json(id: &str, summary: &str, start: &str, end: &str) -> Value {
+    json!({
+        "id": id,
+        "summary": summary,
+        "start": { "dateTime": start },
+        "end": { "dateTime": end },
+        "status": "confirmed",
+        "updated": "2025-01-10T08:00:00Z",
+        "etag": format!("\"etag-{}\"", id),
+    })
+}
+
+/// A timed event on the default calendar, as the engine would hold it.
+pub fn sample_event(id: &str) -> Event {
+    EventBuilder::new(id).build()
+}
@@ -0,0 +1,72 @@
+use chrono::{DateTime, Duration, TimeZone, Utc};
+
+use crate::calendar::{Event, EventStatus, Transparency, Visibility, DEFAULT_CALENDAR_ID};
+
+/// Builds test events so a test only spells out the fields it looks at.
+/// Without changes it is an hour on the default calendar at 09:00 UTC on
+/// 13 January 2025, titled "Event <id>".
+pub struct EventBuilder(Event);
+
+impl EventBuilder {
+    pub fn new(id: &str) -> Self {
+        let start = Utc.with_ymd_and_hms(2025, 1, 13, 9, 0, 0).unwrap();
+        Self(Event {
+            id: id.to_string(),
+            calendar_id: DEFAULT_CALENDAR_ID.to_string(),
+            title: format!("Event {}", id),
+            description: None,
+            location: None,
+            start,
+            end: start + Duration::hours(1),
+            all_day: false,
+            attendees: vec![],
+            reminders: vec![],
+            status: EventStatus::Confirmed,
+            last_modified: start,
+            html_link: None,
+            time_zone: None,
+            conference_url: None,
+            color_id: None,
+            color: None,
+            self_response: None,
+            attendee_responses: Default::default(),
+            visibility: Visibility::Default,
+            transparency: Transparency::Opaque,
+            etag: None,
+            organizer: None,
+            creator: None,
+        })
+    }
+
+    /// Runs for `length` from `start`, which is also when it last changed.
+    pub fn at(mut self, start: DateTime<Utc>, length: Duration) -> Self {
+        self.0.start = start;
+        self.0.end = start + length;
+        self.0.last_modified = start;
+        self
+    }
+
+    pub fn title(mut self, title: impl Into<String>) -> Self {
+        self.0.title = title.into();
+        self
+    }
+
+    pub fn location<'a>(mut self, location: impl Into<Option<&'a str>>) -> Self {
+        self.0.location = location.into().map(String::from);
+        self
+    }
+
+    pub fn calendar(mut self, calendar_id: &str) -> Self {
+        self.0.calendar_id = calendar_id.to_string();
+        self
+    }
+
+    pub fn description(mut self, description: &str) -> Self {
+        self.0.description = Some(description.to_string());
+        self
+    }
+
+    pub fn build(self) -> Event {
+        self.0
+    }
+}
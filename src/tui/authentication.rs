@@ -1,37 +1,50 @@
+use std::io::IsTerminal;
+
 use gcal_imp::storage::config::Config;
 use gcal_imp::sync::google_auth::GoogleAuthenticator;
+use gcal_imp::sync::mock_api::ApiBackend;
+
+use crate::tui::setup_wizard::run_setup_wizard;
 
+/// Signs in if needed, quietly when a token is already valid. Prompts go to
+/// stderr, so subcommands that print to stdout stay clean for pipes and menus.
 pub async fn check_or_setup_auth() -> Result<(), Box<dyn std::error::Error>> {
     let config = Config::load_or_create()?;
+    if ApiBackend::from_config(config.sync.backend.as_deref()).is_offline() {
+        return Ok(());
+    }
 
-    if config.google.client_id.is_empty() || config.google.client_secret.is_empty() {
-        println!("Configuration incomplete. Please edit the config file at:");
-        println!("{}", Config::config_path().display());
-        println!("\nYou need to set:");
-        println!("  - google.client_id: Your Google OAuth2 client ID");
-        println!("  - google.client_secret: Your Google OAuth2 client secret");
-        println!("\nGet these from: https://console.cloud.google.com/apis/credentials");
-        return Err("Missing Google OAuth credentials in config".into());
+    let config = if config.google.client_id.is_empty() || config.google.client_secret.is_empty() {
+        // The wizard needs a terminal; piped output would get its screens.
+        if !(std::io::stdin().is_terminal() && std::io::stdout().is_terminal()) {
+            return Err(format!("Not set up yet. Run gcal-imp in a terminal, or edit {}", Config::config_path().display()).into());
+        }
+        match run_setup_wizard(config).await? {
+            Some(config) => config,
+            None => return Err(format!("Setup cancelled. Run gcal-imp again, or edit {}", Config::config_path().display()).into()),
+        }
+    } else {
+        config
+    };
+    if ApiBackend::from_config(config.sync.backend.as_deref()).is_offline() {
+        return Ok(());
     }
 
     let mut auth = GoogleAuthenticator::new(config);
 
     match auth.get_valid_token().await {
-        Ok(_) => {
-            println!("Authentication successful! Starting calendar...\n");
-            Ok(())
-        }
+        Ok(_) => Ok(()),
         Err(_) => {
-            println!("No valid authentication found. Setting up Google Calendar access...\n");
+            eprintln!("No valid authentication found. Setting up Google Calendar access...\n");
             auth.print_auth_instructions();
 
-            println!("Enter the authorization code: ");
+            eprintln!("Enter the authorization code: ");
             let mut code = String::new();
             std::io::stdin().read_line(&mut code)?;
             let code = code.trim();
 
             auth.exchange_code_for_token(code).await?;
-            println!("\nAuthentication successful! You can now use gcal-imp.\n");
+            eprintln!("\nAuthentication successful! You can now use gcal-imp.\n");
 
             Ok(())
         }
@@ -4,9 +4,10 @@ use ratatui::{
     widgets::{Block, Borders, Paragraph},
     Frame,
 };
+use chrono::{NaiveTime, Timelike};
 use gcal_imp::{
     app::AppState,
-    ui::day_view,
+    ui::{day_view::{self, DayGrid}, stats, theme::event_modifier},
 };
 
 pub fn render(f: &mut Frame, app: &AppState, area: ratatui::layout::Rect) {
@@ -18,44 +19,132 @@ pub fn render(f: &mut Frame, app: &AppState, area: ratatui::layout::Rect) {
         Line::from(vec![
             Span::styled(day_title, Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
         ]),
+        Line::from(Span::styled(stats::day_stats(app, layout.date).summary(), Style::default().fg(app.theme.inactive_day))),
         Line::from(""),
     ];
 
-    for hour_block in &layout.hours {
-        if !hour_block.events.is_empty() {
-            let time_label = format!("{:02}:00", hour_block.hour);
+    for event in &layout.all_day {
+        let style = if event.is_selected {
+            Style::default().bg(app.theme.selected_bg).fg(app.theme.selected_fg).add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().bg(app.theme.event_color(event.color.as_deref(), app.theme.event_indicator)).fg(Color::Black)
+        };
+        let banner = format!(" {:<width$}", event.title, width = area.width.saturating_sub(3) as usize);
+        lines.push(Line::from(vec![
+            Span::styled(banner, style.add_modifier(event_modifier(&event.status, event.declined))),
+        ]));
+    }
+    if !layout.all_day.is_empty() {
+        lines.push(Line::from(""));
+    }
+    let header_lines = lines.len();
+
+    let now_minutes = layout.now.map(|now| now.hour() * 60 + now.minute());
+    let mut now_drawn = now_minutes.is_none();
+    let mut push_now_line = |lines: &mut Vec<Line>, starts_at: Option<u32>| {
+        if let (false, Some(now)) = (now_drawn, now_minutes)
+            && starts_at.is_none_or(|start| start > now)
+        {
+            lines.push(now_line(app, now, area.width.saturating_sub(2) as usize));
+            now_drawn = true;
+        }
+    };
+
+    let mut selected_line = None;
+    for hour_block in layout.visible_hours(app.day_grid) {
+        let first_start = hour_block.events.first().map_or(0, |e| e.start_minute) + hour_block.hour * 60;
+        push_now_line(&mut lines, Some(first_start));
+
+        let time_label = app.time_format.hour_label(hour_block.hour);
+        let label_modifier = if hour_block.events.is_empty() { Modifier::empty() } else { Modifier::BOLD };
+        let label_color = if app.working_hours.overlaps_hour(hour_block.hour) {
+            Color::Yellow
+        } else {
+            app.theme.inactive_day
+        };
+        lines.push(Line::from(vec![
+            Span::styled(time_label, Style::default().fg(label_color).add_modifier(label_modifier)),
+        ]));
+
+        for event in &hour_block.events {
+            push_now_line(&mut lines, Some(hour_block.hour * 60 + event.start_minute));
+            if event.is_selected {
+                selected_line = Some(lines.len());
+            }
+
+            let start = NaiveTime::from_hms_opt(hour_block.hour, event.start_minute, 0).unwrap_or_default();
+            let time_str = format!("{}{:>width$}",
+                if event.is_selected { "> " } else { "  " },
+                app.time_format.format(start),
+                width = app.time_format.width());
+            let (time_style, title_style) = if event.is_selected {
+                let selected = Style::default().bg(app.theme.selected_bg).fg(app.theme.selected_fg).add_modifier(Modifier::BOLD);
+                (selected, selected)
+            } else if is_working_time(app, hour_block.hour, event.start_minute) {
+                (Style::default().fg(Color::Green), Style::default().fg(app.theme.event_color(event.color.as_deref(), Color::White)))
+            } else {
+                (Style::default().fg(app.theme.inactive_day), Style::default().fg(app.theme.inactive_day))
+            };
+            let title_style = if event.overlapping && !event.is_selected {
+                title_style.fg(app.theme.error)
+            } else {
+                title_style
+            }.add_modifier(event_modifier(&event.status, event.declined));
+            let badge = if event.overlapping { " ⚠ " } else { " " };
             lines.push(Line::from(vec![
-                Span::styled(time_label, Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)),
+                Span::styled(time_str, time_style),
+                Span::styled(badge, title_style),
+                Span::styled(&event.title, title_style),
+                Span::styled(format!(" ({}m)", event.duration_minutes), Style::default().fg(Color::DarkGray)),
             ]));
+            if let Some(gap) = event.travel_gap {
+                lines.push(Line::from(Span::styled(
+                    format!("      ⚠ only {}m to get here from the last place", gap),
+                    Style::default().fg(Color::Red),
+                )));
+            }
 
-            for event in &hour_block.events {
-                let time_str = format!("  {:02}:{:02}", hour_block.hour, event.start_minute);
+            if let Some(location) = &event.location {
                 lines.push(Line::from(vec![
-                    Span::styled(time_str, Style::default().fg(Color::Green)),
-                    Span::raw(" "),
-                    Span::styled(&event.title, Style::default().fg(Color::White)),
-                    Span::styled(format!(" ({}m)", event.duration_minutes), Style::default().fg(Color::DarkGray)),
+                    Span::raw("      📍 "),
+                    Span::styled(location, Style::default().fg(Color::DarkGray)),
                 ]));
-
-                if let Some(location) = &event.location {
-                    lines.push(Line::from(vec![
-                        Span::raw("      📍 "),
-                        Span::styled(location, Style::default().fg(Color::DarkGray)),
-                    ]));
-                }
             }
+        }
 
+        if app.day_grid == DayGrid::Compact {
             lines.push(Line::from(""));
         }
     }
 
-    if lines.len() == 2 {
+    if lines.len() == header_lines && layout.all_day.is_empty() {
         lines.push(Line::from(vec![
             Span::styled("No events scheduled", Style::default().fg(Color::DarkGray)),
         ]));
     }
 
+    push_now_line(&mut lines, None);
+
+    // The full grid can be taller than the pane; keep the selection in view.
+    let inner_height = area.height.saturating_sub(2) as usize;
+    let scroll = selected_line.map_or(0, |line| (line + 1).saturating_sub(inner_height));
+
     let content = Paragraph::new(lines)
+        .scroll((scroll as u16, 0))
         .block(Block::default().borders(Borders::ALL));
     f.render_widget(content, area);
 }
+
+fn is_working_time(app: &AppState, hour: u32, minute: u32) -> bool {
+    NaiveTime::from_hms_opt(hour, minute, 0).is_some_and(|time| app.working_hours.contains(time))
+}
+
+fn now_line(app: &AppState, now_minutes: u32, width: usize) -> Line<'static> {
+    let now = NaiveTime::from_hms_opt(now_minutes / 60, now_minutes % 60, 0).unwrap_or_default();
+    let label = format!("── {} now ", app.time_format.format(now));
+    let fill = "─".repeat(width.saturating_sub(label.chars().count()));
+    Line::from(vec![
+        Span::styled(label, Style::default().fg(app.theme.now_line).add_modifier(Modifier::BOLD)),
+        Span::styled(fill, Style::default().fg(app.theme.now_line)),
+    ])
+}
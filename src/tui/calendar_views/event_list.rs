@@ -1,12 +1,40 @@
 use ratatui::{
+    layout::{Margin, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, Paragraph},
+    widgets::{Block, Borders, Paragraph, Scrollbar, ScrollbarOrientation, ScrollbarState},
     Frame,
 };
-use gcal_imp::app::AppState;
+use gcal_imp::{
+    app::AppState,
+    ui::{event_list_view, theme::event_modifier},
+};
+
+const FIRST_EVENT_LINE: u16 = 2;
+const FOOTER_LINES: u16 = 2;
+
+/// Lines left for events once the borders, title and key hints are drawn.
+pub fn visible_event_lines(area: Rect) -> usize {
+    area.height.saturating_sub(2 + FIRST_EVENT_LINE + FOOTER_LINES) as usize
+}
+
+/// Maps a click inside the pane to the index of the event drawn on that line,
+/// mirroring the line layout produced by `render`.
+pub fn event_index_at(app: &AppState, area: Rect, row: u16) -> Option<usize> {
+    let mut line = row.checked_sub(area.y + 1 + FIRST_EVENT_LINE)?;
+
+    let events = app.get_events_for_date(app.selected_date);
+    for (idx, event) in events.iter().enumerate().skip(app.event_list_scroll) {
+        let height = event_list_view::event_height(event) as u16;
+        if line < height - 1 {
+            return Some(idx);
+        }
+        line = line.checked_sub(height)?;
+    }
+    None
+}
 
-pub fn render(f: &mut Frame, app: &AppState, area: ratatui::layout::Rect) {
+pub fn render(f: &mut Frame, app: &AppState, area: Rect) {
     let events = app.get_events_for_date(app.selected_date);
 
     let title = format!("Events on {}", app.selected_date.format("%B %d, %Y"));
@@ -24,16 +52,20 @@ pub fn render(f: &mut Frame, app: &AppState, area: ratatui::layout::Rect) {
         ]));
     } else {
         let selected_base = Style::default().bg(app.theme.selected_bg).add_modifier(Modifier::BOLD);
+        let heights: Vec<usize> = events.iter().map(|e| event_list_view::event_height(e)).collect();
+        let shown = event_list_view::events_per_page(&heights, app.event_list_scroll, visible_event_lines(area));
 
-        for (idx, event) in events.iter().enumerate() {
-            let time_str = event.start.format("%H:%M").to_string();
+        for (idx, event) in events.iter().enumerate().skip(app.event_list_scroll).take(shown) {
+            let start = event.local_start(&app.timezone).time();
+            let time_str = format!("{:>width$}", app.time_format.format(start), width = app.time_format.width());
             let is_selected = idx == app.selected_event_index;
 
             let (time_style, title_style) = if is_selected {
                 (selected_base.fg(Color::Black), selected_base.fg(Color::Black))
             } else {
-                (Style::default().fg(Color::Green), Style::default().fg(Color::White))
+                (Style::default().fg(Color::Green), Style::default().fg(app.theme.event_color(event.color.as_deref(), Color::White)))
             };
+            let title_style = title_style.add_modifier(event_modifier(&event.status, event.is_declined()));
 
             let cursor = if is_selected { ">" } else { " " };
 
@@ -66,6 +98,8 @@ pub fn render(f: &mut Frame, app: &AppState, area: ratatui::layout::Rect) {
             lines.push(Line::from(vec![
                 Span::styled("j/k", Style::default().fg(Color::Cyan)),
                 Span::raw(" = Navigate | "),
+                Span::styled("PgUp/PgDn", Style::default().fg(Color::Cyan)),
+                Span::raw(" = Page | "),
                 Span::styled("E", Style::default().fg(Color::Green)),
                 Span::raw(" = Edit | "),
                 Span::styled("x", Style::default().fg(Color::Red)),
@@ -77,4 +111,16 @@ pub fn render(f: &mut Frame, app: &AppState, area: ratatui::layout::Rect) {
     let content = Paragraph::new(lines)
         .block(Block::default().borders(Borders::ALL));
     f.render_widget(content, area);
+
+    let overflows = app.event_list_scroll > 0
+        || events.iter().skip(app.event_list_scroll).map(|e| event_list_view::event_height(e)).sum::<usize>()
+            > visible_event_lines(area);
+    if overflows {
+        let mut scrollbar_state = ScrollbarState::new(events.len()).position(app.selected_event_index);
+        f.render_stateful_widget(
+            Scrollbar::new(ScrollbarOrientation::VerticalRight).begin_symbol(None).end_symbol(None),
+            area.inner(&Margin { vertical: 1, horizontal: 0 }),
+            &mut scrollbar_state,
+        );
+    }
 }
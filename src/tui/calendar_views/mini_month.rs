@@ -0,0 +1,124 @@
+use ratatui::{
+    layout::{Alignment, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph},
+    Frame,
+};
+use chrono::{Datelike, NaiveDate};
+use gcal_imp::{app::{AppState, ViewType}, ui::week_view::weekdays_from};
+
+const DAY_WIDTH: u16 = 3;
+const GRID_WIDTH: u16 = 7 * DAY_WIDTH - 1;
+const MAX_WEEKS: u16 = 6;
+/// Borders, the weekday header and six weeks.
+pub const HEIGHT: u16 = 2 + 1 + MAX_WEEKS;
+pub const WIDTH: u16 = GRID_WIDTH + 2;
+
+/// The grid is centered, so clicks are mapped back from the padding.
+pub fn date_at(app: &AppState, area: Rect, column: u16, row: u16) -> Option<NaiveDate> {
+    let padding = area.width.saturating_sub(2).saturating_sub(GRID_WIDTH) / 2;
+    let grid_x = column.checked_sub(area.x + 1 + padding)?;
+    let week_idx = row.checked_sub(area.y + 2)? as usize;
+    if grid_x >= GRID_WIDTH || grid_x % DAY_WIDTH == DAY_WIDTH - 1 {
+        return None;
+    }
+
+    let layout = app.layout_cache.month(app);
+    let day = layout.weeks.get(week_idx)?.days.get((grid_x / DAY_WIDTH) as usize)?;
+    day.date.filter(|_| day.is_current_month)
+}
+
+/// Two-letter weekday names from `first_day_of_week` on, e.g. "Mo Tu We".
+fn weekday_headers(app: &AppState) -> String {
+    weekdays_from(app.week_start)
+        .map(|day| format!("{:.2}", day.to_string()))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+pub fn render(f: &mut Frame, app: &AppState, area: Rect) {
+    let layout = app.layout_cache.month(app);
+    let title = NaiveDate::from_ymd_opt(layout.year, layout.month, 1)
+        .map(|d| d.format(" %B %Y ").to_string())
+        .unwrap_or_default();
+
+    let mut lines = vec![Line::from(Span::styled(weekday_headers(app), Style::default().fg(app.theme.weekday_header)))];
+    for week in &layout.weeks {
+        // Week view shades the week it is showing.
+        let in_shown_week = app.view == ViewType::Week && week.days.iter().any(|day| day.is_selected);
+        let mut spans = Vec::new();
+        for (i, day) in week.days.iter().enumerate() {
+            if i > 0 {
+                let gap = if in_shown_week { Style::default().bg(app.theme.selected_bg) } else { Style::default() };
+                spans.push(Span::styled(" ", gap));
+            }
+            let Some(date) = day.date.filter(|_| day.is_current_month) else {
+                spans.push(Span::raw("  "));
+                continue;
+            };
+
+            let mut style = Style::default();
+            if in_shown_week {
+                style = style.bg(app.theme.selected_bg).fg(app.theme.selected_fg);
+            }
+            if day.is_selected {
+                style = style.bg(app.theme.selected_fg).fg(app.theme.selected_bg).add_modifier(Modifier::BOLD);
+            } else if day.is_today {
+                style = style.fg(app.theme.today).add_modifier(Modifier::BOLD);
+            }
+            if day.has_events {
+                style = style.add_modifier(Modifier::UNDERLINED);
+            }
+            spans.push(Span::styled(format!("{:>2}", date.day()), style));
+        }
+        lines.push(Line::from(spans));
+    }
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(Span::styled(title, Style::default().fg(app.theme.title).add_modifier(Modifier::BOLD)));
+    f.render_widget(Paragraph::new(lines).alignment(Alignment::Center).block(block), area);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::tui::{presentation::screen_areas, snapshots::fixture};
+
+    fn january(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2025, 1, day).unwrap()
+    }
+
+    /// The screen column and row of `day_idx` in week `week_idx`.
+    fn cell(area: Rect, week_idx: u16, day_idx: u16) -> (u16, u16) {
+        let padding = (area.width - 2 - GRID_WIDTH) / 2;
+        (area.x + 1 + padding + day_idx * DAY_WIDTH, area.y + 2 + week_idx)
+    }
+
+    #[test]
+    fn clicks_map_to_days_of_the_shown_month() {
+        let app = fixture(ViewType::Week);
+        let area = screen_areas(Rect::new(0, 0, 100, 36), &app.view).mini_calendar.unwrap();
+
+        let (column, row) = cell(area, 2, 4);
+        assert_eq!(date_at(&app, area, column, row), Some(january(17)));
+        assert_eq!(date_at(&app, area, column + 2, row), None);
+        // Monday 30 December leads the first week but is not shown.
+        let (column, row) = cell(area, 0, 0);
+        assert_eq!(date_at(&app, area, column, row), None);
+        assert_eq!(date_at(&app, area, column, row - 1), None);
+    }
+
+    #[test]
+    fn headers_and_clicks_follow_the_first_day_of_the_week() {
+        let mut app = fixture(ViewType::Week);
+        app.set_ui_option("first_day_of_week", "sunday").unwrap();
+        let area = screen_areas(Rect::new(0, 0, 100, 36), &app.view).mini_calendar.unwrap();
+
+        assert_eq!(weekday_headers(&app), "Su Mo Tu We Th Fr Sa");
+        let (column, row) = cell(area, 2, 5);
+        assert_eq!(date_at(&app, area, column, row), Some(january(17)));
+    }
+}
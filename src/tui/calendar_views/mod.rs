@@ -2,4 +2,6 @@ pub mod month;
 pub mod week;
 pub mod day;
 pub mod year;
+pub mod mini_month;
 pub mod event_list;
+pub mod tasks;
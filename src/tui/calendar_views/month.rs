@@ -1,4 +1,5 @@
 use ratatui::{
+    layout::Rect,
     style::{Color, Modifier, Style},
     text::{Line, Span},
     widgets::{Block, Borders, Paragraph},
@@ -7,22 +8,38 @@ use ratatui::{
 use chrono::{Datelike, NaiveDate};
 use gcal_imp::{
     app::{AppState, Mode},
-    ui::month_view,
+    ui::{month_view::MonthCellStyle, week_view::weekdays_from},
 };
 
-const WEEKDAY_HEADERS: [&str; 7] = [" Mon ", " Tue ", " Wed ", " Thu ", " Fri ", " Sat ", " Sun "];
+pub const CELL_WIDTH: u16 = 5;
+pub const WEEK_NUMBER_WIDTH: u16 = 4;
+pub const FIRST_WEEK_LINE: u16 = 3;
 
-pub fn render(f: &mut Frame, app: &AppState, area: ratatui::layout::Rect) {
-    let layout = month_view::calculate_layout(app);
+pub fn date_at(app: &AppState, area: Rect, column: u16, row: u16) -> Option<NaiveDate> {
+    let inner_x = column.checked_sub(area.x + 1)?;
+    let inner_y = row.checked_sub(area.y + 1)?;
+    let offset = if app.show_week_numbers { WEEK_NUMBER_WIDTH } else { 0 };
+    let week_idx = inner_y.checked_sub(FIRST_WEEK_LINE)? as usize;
+    let day_idx = (inner_x.checked_sub(offset)? / CELL_WIDTH) as usize;
+
+    let layout = app.layout_cache.month(app);
+    layout.weeks.get(week_idx)?.days.get(day_idx)?.date
+}
+
+pub fn render(f: &mut Frame, app: &AppState, area: Rect) {
+    let layout = app.layout_cache.month(app);
 
     let month_name = NaiveDate::from_ymd_opt(layout.year, layout.month, 1)
         .map(|d| d.format("%B %Y").to_string())
         .unwrap_or_else(|| format!("{}-{:02}", layout.year, layout.month));
 
     let header_style = Style::default().fg(app.theme.weekday_header);
-    let weekday_spans: Vec<Span> = WEEKDAY_HEADERS.iter()
-        .map(|&day| Span::styled(day, header_style))
-        .collect();
+    let week_number_style = Style::default().fg(app.theme.inactive_day);
+    let mut weekday_spans = Vec::new();
+    if app.show_week_numbers {
+        weekday_spans.push(Span::styled(" Wk ", week_number_style));
+    }
+    weekday_spans.extend(weekdays_from(app.week_start).map(|day| Span::styled(format!(" {} ", day), header_style)));
 
     let mut lines = vec![
         Line::from(vec![
@@ -35,9 +52,13 @@ pub fn render(f: &mut Frame, app: &AppState, area: ratatui::layout::Rect) {
     for week in &layout.weeks {
         let mut day_spans = Vec::new();
 
+        if app.show_week_numbers {
+            day_spans.push(Span::styled(format!(" {:>2} ", week.iso_week), week_number_style));
+        }
+
         for day_cell in &week.days {
             let day_text = if let Some(date) = day_cell.date {
-                format!(" {:>2}  ", date.day())
+                format!(" {:>2}{:<2}", date.day(), day_cell.marker(app.month_cell_style))
             } else {
                 "     ".to_string()
             };
@@ -54,11 +75,17 @@ pub fn render(f: &mut Frame, app: &AppState, area: ratatui::layout::Rect) {
                 style = style.bg(Color::DarkGray).fg(Color::White).add_modifier(Modifier::BOLD);
             } else if day_cell.is_selected {
                 style = style.bg(Color::Blue).fg(Color::White).add_modifier(Modifier::BOLD);
+            } else if let Some(bar) = &day_cell.span_bar {
+                let color = app.theme.event_color(bar.color.as_deref(), app.theme.event_indicator);
+                style = style.bg(color).fg(Color::Black);
+                if day_cell.is_today {
+                    style = style.add_modifier(Modifier::BOLD);
+                }
             } else if day_cell.is_today {
                 style = style.fg(Color::Green).add_modifier(Modifier::BOLD);
             }
 
-            if day_cell.has_events {
+            if day_cell.has_events && app.month_cell_style == MonthCellStyle::Underline {
                 style = style.add_modifier(Modifier::UNDERLINED);
             }
 
@@ -79,6 +106,8 @@ pub fn render(f: &mut Frame, app: &AppState, area: ratatui::layout::Rect) {
                 Span::raw(" | "),
                 Span::styled("Enter", Style::default().fg(Color::Green)),
                 Span::raw(" = Create event | "),
+                Span::styled(":", Style::default().fg(Color::Cyan)),
+                Span::raw(" = :delete/:move | "),
                 Span::styled("Esc", Style::default().fg(Color::Red)),
                 Span::raw(" = Cancel"),
             ]));
@@ -0,0 +1,58 @@
+use ratatui::{
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph},
+    Frame,
+};
+use gcal_imp::app::AppState;
+
+pub fn render(f: &mut Frame, app: &AppState, area: Rect) {
+    let today = app.today();
+
+    let mut lines = vec![
+        Line::from(vec![
+            Span::styled("Tasks due today", Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
+        ]),
+        Line::from(""),
+    ];
+
+    if app.tasks.is_empty() {
+        lines.push(Line::from(vec![
+            Span::styled("No open tasks", Style::default().fg(Color::DarkGray)),
+        ]));
+    } else {
+        for (idx, task) in app.tasks.iter().enumerate() {
+            let is_selected = idx == app.task_index;
+            let style = if is_selected {
+                Style::default().bg(app.theme.selected_bg).fg(Color::Black).add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(Color::White)
+            };
+            let cursor = if is_selected { ">" } else { " " };
+
+            let mut spans = vec![
+                Span::styled(cursor, Style::default().fg(app.theme.selected_bg)),
+                Span::styled(format!("[ ] {}", task.title), style),
+            ];
+            if let Some(due) = task.due.filter(|_| task.is_overdue(today)) {
+                spans.push(Span::styled(format!("  overdue {}", due.format("%b %d")), Style::default().fg(app.theme.error)));
+            }
+            lines.push(Line::from(spans));
+        }
+
+        lines.push(Line::from(""));
+        lines.push(Line::from(vec![
+            Span::styled("j/k", Style::default().fg(Color::Cyan)),
+            Span::raw(" = Navigate | "),
+            Span::styled("x", Style::default().fg(Color::Green)),
+            Span::raw(" = Done | "),
+            Span::styled("T", Style::default().fg(Color::Red)),
+            Span::raw(" = Close"),
+        ]));
+    }
+
+    let content = Paragraph::new(lines)
+        .block(Block::default().borders(Borders::ALL).title(" Tasks "));
+    f.render_widget(content, area);
+}
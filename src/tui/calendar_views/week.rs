@@ -1,17 +1,44 @@
 use ratatui::{
+    layout::Rect,
     style::{Color, Modifier, Style},
     text::{Line, Span},
     widgets::{Block, Borders, Paragraph},
     Frame,
 };
-use chrono::Datelike;
+use chrono::{Datelike, NaiveDate};
 use gcal_imp::{
     app::AppState,
-    ui::week_view,
+    ui::{stats, theme::event_modifier, week_view::{self, DayColumn, WeekLayout, SLOTS_PER_HOUR}},
 };
 
-pub fn render(f: &mut Frame, app: &AppState, area: ratatui::layout::Rect) {
-    let layout = week_view::calculate_layout(app);
+const GUTTER_WIDTH: usize = 6;
+const HEADER_LINES: u16 = 4;
+const MAX_BANNER_ROWS: usize = 2;
+
+fn column_width(area: Rect) -> usize {
+    let inner_width = area.width.saturating_sub(2) as usize;
+    (inner_width.saturating_sub(GUTTER_WIDTH) / 7).max(3)
+}
+
+pub fn date_at(app: &AppState, area: Rect, column: u16, row: u16) -> Option<NaiveDate> {
+    let inner_x = column.checked_sub(area.x + 1)? as usize;
+    row.checked_sub(area.y + 3)?;
+    let day_idx = inner_x.checked_sub(GUTTER_WIDTH)? / column_width(area);
+
+    let week_start = WeekLayout::week_of(app.selected_date, app.week_start);
+    (day_idx < 7)
+        .then(|| week_start.checked_add_days(chrono::Days::new(day_idx as u64)))
+        .flatten()
+}
+
+pub fn render(f: &mut Frame, app: &AppState, area: Rect) {
+    let layout = app.layout_cache.week(app);
+
+    let column_width = column_width(area);
+    let banner_rows = layout.banner_rows(MAX_BANNER_ROWS);
+    let visible_rows = area.height.saturating_sub(2 + HEADER_LINES + banner_rows as u16) as u32;
+    let first_slot = WeekLayout::first_visible_slot(app.week_scroll_hour, visible_rows);
+    let last_slot = (first_slot + visible_rows).min(week_view::SLOTS_PER_DAY);
 
     let week_range = if let Some(last_day) = layout.days.last() {
         format!("{} - {}",
@@ -21,20 +48,27 @@ pub fn render(f: &mut Frame, app: &AppState, area: ratatui::layout::Rect) {
         layout.week_start.format("%b %d, %Y").to_string()
     };
 
+    let scroll_hint = format!("  {:02}:00-{:02}:00",
+        first_slot / SLOTS_PER_HOUR,
+        last_slot.div_ceil(SLOTS_PER_HOUR));
+
     let mut lines = vec![
         Line::from(vec![
             Span::styled(week_range, Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
+            Span::styled(scroll_hint, Style::default().fg(Color::DarkGray)),
         ]),
         Line::from(""),
     ];
 
-    let mut header_spans = vec![Span::styled("      ", Style::default())];
+    let week_label = if app.show_week_numbers {
+        format!(" W{:02}  ", layout.week_start.iso_week().week())
+    } else {
+        "      ".to_string()
+    };
+    let mut header_spans = vec![Span::styled(week_label, Style::default().fg(app.theme.inactive_day))];
 
     for day in &layout.days {
-        let day_str = format!(" {:>3} {:<2} ",
-            day.date.format("%a"),
-            day.date.day()
-        );
+        let day_str = fit(&format!(" {} {}", day.date.format("%a"), day.date.day()), column_width);
 
         let is_in_visual_selection = app.is_date_in_visual_selection(day.date);
 
@@ -52,25 +86,26 @@ pub fn render(f: &mut Frame, app: &AppState, area: ratatui::layout::Rect) {
     }
 
     lines.push(Line::from(header_spans));
-    lines.push(Line::from(""));
+    for row in 0..banner_rows {
+        lines.push(banner_line(app, &layout, row, banner_rows, column_width));
+    }
+    lines.push(stats_line(app, &layout, column_width));
 
-    for hour in 6..22 {
-        let time_str = format!("{:02}:00 ", hour);
-        let mut line_spans = vec![Span::styled(time_str, Style::default().fg(Color::Gray))];
+    for slot in first_slot..last_slot {
+        let is_now_row = layout.days.iter().any(|d| d.now_slot == Some(slot));
+        let is_working = WeekLayout::is_working_slot(app, slot);
+        let label_color = if is_working { Color::Gray } else { app.theme.inactive_day };
+        let (time_str, time_style) = if is_now_row {
+            ("now ──".to_string(), Style::default().fg(app.theme.now_line).add_modifier(Modifier::BOLD))
+        } else if slot % SLOTS_PER_HOUR == 0 {
+            (format!("{} ", app.time_format.hour_label(slot / SLOTS_PER_HOUR)), Style::default().fg(label_color))
+        } else {
+            (" ".repeat(GUTTER_WIDTH), Style::default().fg(label_color))
+        };
+        let mut line_spans = vec![Span::styled(time_str, time_style)];
 
         for day in &layout.days {
-            let hour_events: Vec<_> = day.events.iter()
-                .filter(|slot| slot.hour == hour)
-                .flat_map(|slot| &slot.events)
-                .collect();
-
-            let cell_text = if !hour_events.is_empty() {
-                format!("{:>8}", hour_events.len())
-            } else {
-                "        ".to_string()
-            };
-
-            line_spans.push(Span::raw(cell_text));
+            line_spans.extend(render_day_cell(app, day, slot, first_slot, column_width, is_working));
         }
 
         lines.push(Line::from(line_spans));
@@ -80,3 +115,125 @@ pub fn render(f: &mut Frame, app: &AppState, area: ratatui::layout::Rect) {
         .block(Block::default().borders(Borders::ALL));
     f.render_widget(content, area);
 }
+
+/// Each day's event count and busy time under its header.
+fn stats_line(app: &AppState, layout: &WeekLayout, column_width: usize) -> Line<'static> {
+    let style = Style::default().fg(app.theme.inactive_day);
+    let mut spans = vec![Span::raw(" ".repeat(GUTTER_WIDTH))];
+    spans.extend(layout.days.iter().map(|day| {
+        let short = stats::day_stats(app, day.date).short();
+        Span::styled(fit(&format!(" {}", short), column_width), style)
+    }));
+    Line::from(spans)
+}
+
+/// One row of all-day banners; the last row summarises any that do not fit.
+/// Multi-day banners run over the column separators as one continuous bar.
+fn banner_line(app: &AppState, layout: &WeekLayout, row: usize, rows: usize, column_width: usize) -> Line<'static> {
+    let label = if row == 0 { "allday" } else { "" };
+    let mut spans = vec![Span::styled(fit(label, GUTTER_WIDTH), Style::default().fg(app.theme.inactive_day))];
+    let usable = column_width.saturating_sub(1);
+    let separator_style = Style::default().fg(Color::DarkGray);
+
+    for day in &layout.days {
+        let hidden = day.all_day.iter().filter(|b| b.row + 1 >= rows).count();
+        let overflows = row + 1 == rows && day.all_day.iter().any(|b| b.row >= rows);
+        let entry = day.all_day.iter().find(|b| b.row == row);
+
+        match entry {
+            _ if overflows => {
+                spans.push(Span::styled("│", separator_style));
+                spans.push(Span::styled(fit(&format!("+{} more", hidden), usable), Style::default().fg(app.theme.inactive_day)));
+            }
+            Some(entry) => {
+                let style = if entry.is_selected {
+                    Style::default().bg(app.theme.selected_bg).fg(app.theme.selected_fg).add_modifier(Modifier::BOLD)
+                } else {
+                    Style::default().bg(app.theme.event_color(entry.color.as_deref(), app.theme.event_indicator)).fg(Color::Black)
+                };
+                let text: String = entry.title.chars().skip(entry.span_offset * column_width).collect();
+                if entry.span_offset > 0 {
+                    let joint: String = entry.title.chars().skip(entry.span_offset * column_width - 1).take(1).collect();
+                    spans.push(Span::styled(fit(&joint, 1), style));
+                } else {
+                    spans.push(Span::styled("│", separator_style));
+                }
+                spans.push(Span::styled(fit(&text, usable), style));
+            }
+            None => {
+                spans.push(Span::styled("│", separator_style));
+                spans.push(Span::raw(" ".repeat(usable)));
+            }
+        }
+    }
+    Line::from(spans)
+}
+
+fn render_day_cell<'a>(
+    app: &AppState,
+    day: &DayColumn,
+    slot: u32,
+    first_slot: u32,
+    column_width: usize,
+    is_working: bool,
+) -> Vec<Span<'a>> {
+    let separator_style = Style::default().fg(Color::DarkGray);
+    let usable = column_width.saturating_sub(1);
+    // Outside working hours the empty grid is shaded so the working window stands out.
+    let background = if is_working { Style::default() } else { Style::default().bg(app.theme.inactive_day) };
+    let empty_cell = if day.now_slot == Some(slot) {
+        ('─', background.fg(app.theme.now_line))
+    } else {
+        (' ', background)
+    };
+    let mut cells: Vec<(char, Style)> = vec![empty_cell; usable];
+
+    for block in day.blocks.iter().filter(|b| b.covers_slot(slot)) {
+        let x0 = usable * block.lane / block.lane_count;
+        let x1 = usable * (block.lane + 1) / block.lane_count;
+        if x1 <= x0 {
+            continue;
+        }
+
+        let style = if block.is_selected {
+            Style::default().bg(app.theme.selected_bg).fg(app.theme.selected_fg).add_modifier(Modifier::BOLD)
+        } else if block.overlapping {
+            Style::default().bg(app.theme.error).fg(Color::Black)
+        } else {
+            Style::default().bg(app.theme.event_color(block.color.as_deref(), app.theme.event_indicator)).fg(Color::Black)
+        }.add_modifier(event_modifier(&block.status, block.declined));
+        let show_title = slot == block.start_slot || slot == first_slot;
+        // `!` marks a hop from another location with too little time.
+        let label = match block.travel_gap {
+            Some(_) => format!("!{}", block.title),
+            None => block.title.clone(),
+        };
+        let mut title = if show_title { label.chars() } else { "".chars() };
+
+        for cell in cells.iter_mut().take(x1).skip(x0) {
+            *cell = (title.next().unwrap_or(' '), style);
+        }
+    }
+
+    let mut spans = vec![Span::styled("│", separator_style)];
+    let mut current = String::new();
+    let mut current_style = None;
+    for (ch, style) in cells {
+        if current_style.is_some_and(|s| s != style) {
+            spans.push(Span::styled(std::mem::take(&mut current), current_style.unwrap_or_default()));
+        }
+        current_style = Some(style);
+        current.push(ch);
+    }
+    if !current.is_empty() {
+        spans.push(Span::styled(current, current_style.unwrap_or_default()));
+    }
+    spans
+}
+
+fn fit(text: &str, width: usize) -> String {
+    let mut fitted: String = text.chars().take(width).collect();
+    let len = fitted.chars().count();
+    fitted.push_str(&" ".repeat(width - len));
+    fitted
+}
@@ -1,4 +1,6 @@
+use chrono::NaiveDate;
 use ratatui::{
+    layout::Rect,
     style::{Color, Modifier, Style},
     text::{Line, Span},
     widgets::{Block, Borders, Paragraph},
@@ -6,13 +8,45 @@ use ratatui::{
 };
 use gcal_imp::{
     app::AppState,
-    ui::year_view,
+    ui::{theme::contrasting_text, week_view::weekdays_from, year_view},
 };
 
 const MONTH_NAMES: [&str; 12] = ["Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec"];
-const WEEKDAY_HEADERS: [&str; 7] = ["Mo", "Tu", "We", "Th", "Fr", "Sa", "Su"];
+const MONTH_WIDTH: u16 = 20;
+const MONTH_SEPARATOR_WIDTH: u16 = 3;
+const DAY_WIDTH: u16 = 3;
 
-pub fn render(f: &mut Frame, app: &AppState, area: ratatui::layout::Rect) {
+fn weeks_in_month(month: &year_view::MonthGrid) -> usize {
+    (month.days.len() + month.first_weekday as usize).div_ceil(7)
+}
+
+pub fn date_at(app: &AppState, area: Rect, column: u16, row: u16) -> Option<NaiveDate> {
+    let inner_x = column.checked_sub(area.x + 1)?;
+    let mut line = row.checked_sub(area.y + 1)?.checked_sub(2)? as usize;
+    let col = (inner_x / (MONTH_WIDTH + MONTH_SEPARATOR_WIDTH)) as usize;
+    let x_in_month = inner_x % (MONTH_WIDTH + MONTH_SEPARATOR_WIDTH);
+    if col >= 3 || x_in_month >= MONTH_WIDTH {
+        return None;
+    }
+    let weekday = (x_in_month / DAY_WIDTH) as usize;
+
+    let layout = year_view::calculate_layout(app);
+    for row_months in layout.months.chunks(3) {
+        let max_weeks = row_months.iter().map(weeks_in_month).max().unwrap_or(0);
+        let block_height = 2 + max_weeks + 1;
+        if line < block_height {
+            let week = line.checked_sub(2)?;
+            let month = row_months.get(col)?;
+            let day_idx = (week * 7 + weekday).checked_sub(month.first_weekday as usize)?;
+            let day = month.days.get(day_idx)?;
+            return NaiveDate::from_ymd_opt(layout.year, month.month, day.day);
+        }
+        line -= block_height;
+    }
+    None
+}
+
+pub fn render(f: &mut Frame, app: &AppState, area: Rect) {
     let layout = year_view::calculate_layout(app);
 
     let mut lines = vec![
@@ -49,11 +83,11 @@ pub fn render(f: &mut Frame, app: &AppState, area: ratatui::layout::Rect) {
         for col in 0..3 {
             let month_idx = row * 3 + col;
             if month_idx < layout.months.len() {
-                for (i, dow) in WEEKDAY_HEADERS.iter().enumerate() {
+                for (i, dow) in weekdays_from(app.week_start).enumerate() {
                     if i > 0 {
                         dow_headers.push(Span::styled(" ", header_style));
                     }
-                    dow_headers.push(Span::styled(format!("{:2}", dow), header_style));
+                    dow_headers.push(Span::styled(format!("{:.2}", dow.to_string()), header_style));
                 }
                 if col < 2 {
                     dow_headers.push(Span::styled(" │ ", header_style));
@@ -67,8 +101,7 @@ pub fn render(f: &mut Frame, app: &AppState, area: ratatui::layout::Rect) {
             let month_idx = row * 3 + col;
             if month_idx < layout.months.len() {
                 let month = &layout.months[month_idx];
-                let weeks = (month.days.len() + month.first_weekday as usize).div_ceil(7);
-                max_weeks = max_weeks.max(weeks);
+                max_weeks = max_weeks.max(weeks_in_month(month));
             }
         }
 
@@ -95,6 +128,10 @@ pub fn render(f: &mut Frame, app: &AppState, area: ratatui::layout::Rect) {
 
                             let style = if day.is_selected {
                                 Style::default().bg(Color::Blue).fg(Color::White).add_modifier(Modifier::BOLD)
+                            } else if app.year_heatmap && day.heat > 0 {
+                                let background = app.theme.heatmap[day.heat as usize - 1];
+                                let style = Style::default().bg(background).fg(contrasting_text(background));
+                                if day.is_today { style.add_modifier(Modifier::BOLD | Modifier::UNDERLINED) } else { style }
                             } else if day.is_today {
                                 Style::default().fg(Color::Green).add_modifier(Modifier::BOLD)
                             } else if day.has_events {
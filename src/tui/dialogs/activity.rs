@@ -0,0 +1,52 @@
+use ratatui::{
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+use gcal_imp::app::{AppState, MessageKind};
+
+pub fn render(f: &mut Frame, app: &AppState) {
+    let area = f.size();
+    let dialog_width = 80.min(area.width);
+    let dialog_height = 20.min(area.height);
+    let x = (area.width.saturating_sub(dialog_width)) / 2;
+    let y = (area.height.saturating_sub(dialog_height)) / 2;
+
+    let dialog_area = ratatui::layout::Rect {
+        x,
+        y,
+        width: dialog_width,
+        height: dialog_height,
+    };
+
+    f.render_widget(Clear, dialog_area);
+
+    let mut lines: Vec<Line> = app.activity.entries()
+        .skip(app.activity_scroll)
+        .map(|entry| {
+            let color = match entry.kind {
+                MessageKind::Error => app.theme.error,
+                MessageKind::Success => app.theme.success,
+                MessageKind::Info => app.theme.status_bar,
+            };
+            let time = app.timezone.to_local(&entry.at).format("%H:%M:%S").to_string();
+            Line::from(vec![
+                Span::styled(format!(" {} ", time), Style::default().fg(Color::DarkGray)),
+                Span::styled(entry.text.clone(), Style::default().fg(color)),
+            ])
+        })
+        .collect();
+    if lines.is_empty() {
+        lines.push(Line::from(Span::styled(" Nothing yet", Style::default().fg(Color::DarkGray))));
+    }
+
+    let paragraph = Paragraph::new(lines)
+        .wrap(Wrap { trim: false })
+        .block(Block::default()
+            .borders(Borders::ALL)
+            .title(" Messages (newest first, j/k scroll, Esc to close) ")
+            .style(Style::default().bg(Color::Black)));
+
+    f.render_widget(paragraph, dialog_area);
+}
@@ -0,0 +1,51 @@
+use ratatui::{
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+use gcal_imp::{app::AppState, calendar::overlay};
+
+pub fn render(f: &mut Frame, app: &AppState) {
+    let calendars = app.known_calendars();
+
+    let area = f.size();
+    let dialog_width = 50.min(area.width);
+    let dialog_height = (calendars.len().max(1) as u16 + 4).min(area.height);
+    let x = (area.width.saturating_sub(dialog_width)) / 2;
+    let y = (area.height.saturating_sub(dialog_height)) / 2;
+
+    let dialog_area = ratatui::layout::Rect {
+        x,
+        y,
+        width: dialog_width,
+        height: dialog_height,
+    };
+
+    f.render_widget(Clear, dialog_area);
+
+    let visible = dialog_height.saturating_sub(4) as usize;
+    let scroll = (app.calendar_list_index + 1).saturating_sub(visible);
+
+    let mut lines = vec![Line::from("")];
+    if calendars.is_empty() {
+        lines.push(Line::from("  No calendars"));
+    }
+    for (index, calendar) in calendars.iter().enumerate().skip(scroll).take(visible) {
+        let checkbox = if app.is_calendar_visible(calendar) { "[x]" } else { "[ ]" };
+        let style = if index == app.calendar_list_index {
+            Style::default().fg(app.theme.selected_fg).bg(app.theme.selected_bg).add_modifier(Modifier::BOLD)
+        } else {
+            Style::default()
+        };
+        lines.push(Line::from(Span::styled(format!("  {} {}", checkbox, overlay::label(calendar)), style)));
+    }
+
+    let paragraph = Paragraph::new(lines)
+        .block(Block::default()
+            .borders(Borders::ALL)
+            .title(" Calendars (j/k, Space to toggle, Esc) ")
+            .style(Style::default().bg(Color::Black)));
+
+    f.render_widget(paragraph, dialog_area);
+}
@@ -0,0 +1,74 @@
+use ratatui::{
+    layout::Alignment,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+use gcal_imp::app::AppState;
+
+const MAX_LISTED_CONFLICTS: usize = 6;
+
+pub fn render(f: &mut Frame, app: &AppState) {
+    if app.conflict_event_ids.is_empty() {
+        return;
+    }
+
+    let conflicts: Vec<_> = app.conflict_event_ids
+        .iter()
+        .filter_map(|id| app.events.get(id))
+        .collect();
+    let listed = conflicts.len().min(MAX_LISTED_CONFLICTS);
+    let hidden = conflicts.len() - listed;
+
+    let area = f.size();
+    let dialog_width = 60;
+    let dialog_height = (listed as u16 + if hidden > 0 { 7 } else { 6 }).min(area.height);
+    let x = (area.width.saturating_sub(dialog_width)) / 2;
+    let y = (area.height.saturating_sub(dialog_height)) / 2;
+
+    let dialog_area = ratatui::layout::Rect {
+        x,
+        y,
+        width: dialog_width,
+        height: dialog_height,
+    };
+
+    f.render_widget(Clear, dialog_area);
+
+    let mut dialog_text = vec![Line::from("")];
+    for event in conflicts.iter().take(listed) {
+        dialog_text.push(Line::from(vec![
+            Span::raw("Overlaps with "),
+            Span::styled(event.title.clone(), Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)),
+            Span::raw(format!(
+                " {}\u{2013}{}",
+                app.time_format.format(event.local_start(&app.timezone).time()),
+                app.time_format.format(event.local_end(&app.timezone).time())
+            )),
+        ]));
+    }
+    if hidden > 0 {
+        dialog_text.push(Line::from(format!("...and {} more", hidden)));
+    }
+
+    dialog_text.extend([
+        Line::from(""),
+        Line::from("Save anyway?"),
+        Line::from(vec![
+            Span::styled("Y", Style::default().fg(Color::Green)),
+            Span::raw(" = Yes, save | "),
+            Span::styled("N", Style::default().fg(Color::Red)),
+            Span::raw(" = No, keep editing"),
+        ]),
+    ]);
+
+    let dialog_paragraph = Paragraph::new(dialog_text)
+        .block(Block::default()
+            .borders(Borders::ALL)
+            .title(" Scheduling Conflict ")
+            .style(Style::default().bg(Color::Black)))
+        .alignment(Alignment::Center);
+
+    f.render_widget(dialog_paragraph, dialog_area);
+}
@@ -58,3 +58,71 @@ pub fn render(f: &mut Frame, app: &AppState) {
 
     f.render_widget(dialog_paragraph, dialog_area);
 }
+
+const MAX_LISTED_EVENTS: usize = 8;
+
+pub fn render_bulk(f: &mut Frame, app: &AppState) {
+    if app.bulk_delete_event_ids.is_empty() {
+        return;
+    }
+
+    let listed = app.bulk_delete_event_ids.len().min(MAX_LISTED_EVENTS);
+    let hidden = app.bulk_delete_event_ids.len() - listed;
+
+    let area = f.size();
+    let dialog_width = 60;
+    let dialog_height = (listed as u16 + if hidden > 0 { 9 } else { 8 }).min(area.height);
+    let x = (area.width.saturating_sub(dialog_width)) / 2;
+    let y = (area.height.saturating_sub(dialog_height)) / 2;
+
+    let dialog_area = ratatui::layout::Rect {
+        x,
+        y,
+        width: dialog_width,
+        height: dialog_height,
+    };
+
+    f.render_widget(Clear, dialog_area);
+
+    let mut dialog_text = vec![
+        Line::from(vec![Span::styled(
+            format!("Delete {} Events?", app.bulk_delete_event_ids.len()),
+            Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+        )]),
+        Line::from(""),
+    ];
+
+    for event_id in app.bulk_delete_event_ids.iter().take(listed) {
+        let Some(event) = app.events.get(event_id) else {
+            continue;
+        };
+        dialog_text.push(Line::from(vec![
+            Span::raw(event.local_start(&app.timezone).format("%a %b %d  ").to_string()),
+            Span::styled(event.title.clone(), Style::default().fg(Color::Yellow)),
+        ]));
+    }
+    if hidden > 0 {
+        dialog_text.push(Line::from(format!("...and {} more", hidden)));
+    }
+
+    dialog_text.extend([
+        Line::from(""),
+        Line::from("This action cannot be undone."),
+        Line::from(""),
+        Line::from(vec![
+            Span::styled("Y", Style::default().fg(Color::Green)),
+            Span::raw(" = Yes, delete all | "),
+            Span::styled("N", Style::default().fg(Color::Red)),
+            Span::raw(" = No, cancel"),
+        ]),
+    ]);
+
+    let dialog_paragraph = Paragraph::new(dialog_text)
+        .block(Block::default()
+            .borders(Borders::ALL)
+            .title(" Confirm Delete ")
+            .style(Style::default().bg(Color::Black)))
+        .alignment(Alignment::Center);
+
+    f.render_widget(dialog_paragraph, dialog_area);
+}
@@ -0,0 +1,49 @@
+use ratatui::{
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+use gcal_imp::app::AppState;
+
+pub fn render(f: &mut Frame, app: &AppState) {
+    let Some(report) = &app.error_report else {
+        return;
+    };
+    let area = f.size();
+    let dialog_width = 64.min(area.width);
+    let dialog_height = 12.min(area.height);
+    let x = (area.width.saturating_sub(dialog_width)) / 2;
+    let y = (area.height.saturating_sub(dialog_height)) / 2;
+
+    let dialog_area = ratatui::layout::Rect {
+        x,
+        y,
+        width: dialog_width,
+        height: dialog_height,
+    };
+
+    f.render_widget(Clear, dialog_area);
+
+    let mut lines = vec![
+        Line::from(""),
+        Line::from(Span::styled(report.message.clone(), Style::default().fg(app.theme.error).add_modifier(Modifier::BOLD))),
+        Line::from(""),
+    ];
+    if let Some(hint) = &report.hint {
+        lines.push(Line::from(hint.clone()));
+        lines.push(Line::from(""));
+    }
+    lines.push(Line::from(Span::styled(report.detail.clone(), Style::default().fg(Color::DarkGray))));
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled("Esc to close", Style::default().fg(Color::DarkGray))));
+
+    let paragraph = Paragraph::new(lines)
+        .wrap(Wrap { trim: true })
+        .block(Block::default()
+            .borders(Borders::ALL)
+            .title(format!(" {} ", report.action))
+            .style(Style::default().bg(Color::Black)));
+
+    f.render_widget(paragraph, dialog_area);
+}
@@ -5,7 +5,13 @@ use ratatui::{
     widgets::{Block, Borders, Clear, Paragraph},
     Frame,
 };
-use gcal_imp::app::{AppState, FormField};
+use gcal_imp::{
+    app::{AppState, EventForm, FormField},
+    input::text_area,
+    ui::theme::status_modifier,
+};
+
+const MAX_DESCRIPTION_LINES: usize = 5;
 
 pub fn render(f: &mut Frame, app: &AppState) {
     let Some(form) = &app.event_form else {
@@ -14,7 +20,10 @@ pub fn render(f: &mut Frame, app: &AppState) {
 
     let area = f.size();
     let form_width = 70;
-    let form_height = if form.all_day { 14 } else { 18 };
+    let active_color = app.theme.selected_bg;
+    let inactive_color = Color::DarkGray;
+    let description = description_lines(form, if form.active_field == FormField::Description { active_color } else { inactive_color });
+    let form_height = if form.all_day { 27 } else { 31 } + description.len() as u16;
     let x = (area.width.saturating_sub(form_width)) / 2;
     let y = (area.height.saturating_sub(form_height)) / 2;
 
@@ -27,72 +36,123 @@ pub fn render(f: &mut Frame, app: &AppState) {
 
     f.render_widget(Clear, form_area);
 
-    let active_color = app.theme.selected_bg;
-    let inactive_color = Color::DarkGray;
-
     let form_title = if form.is_editing() { "Edit Event" } else { "Create New Event" };
 
     let mut form_text = vec![
         Line::from(vec![Span::styled(form_title, Style::default().fg(app.theme.title).add_modifier(Modifier::BOLD))]),
         Line::from(""),
-        Line::from(vec![
-            Span::styled("Title: ", Style::default().fg(if form.active_field == FormField::Title { active_color } else { inactive_color })),
-            Span::raw(&form.title),
-        ]),
+        Line::from([
+            vec![Span::styled("Title: ", Style::default().fg(if form.active_field == FormField::Title { active_color } else { inactive_color }))],
+            field_spans(form, FormField::Title),
+            vec![error_span(form, FormField::Title)],
+        ].concat()),
+        Line::from(""),
+        Line::from([
+            vec![Span::styled("Date: ", Style::default().fg(if form.active_field == FormField::Date { active_color } else { inactive_color }))],
+            field_spans(form, FormField::Date),
+            vec![
+                Span::styled(if form.active_field == FormField::Date {
+                    if form.date_buffer_touched {
+                        " (2025-01-15, fri, +2d...)"
+                    } else {
+                        " [type to replace, \u{2191}\u{2193} to change day]"
+                    }
+                } else { "" }, Style::default().fg(Color::DarkGray)),
+                error_span(form, FormField::Date),
+            ],
+        ].concat()),
         Line::from(""),
         Line::from(vec![
-            Span::styled("Date: ", Style::default().fg(inactive_color)),
-            Span::raw(form.date.format("%Y-%m-%d").to_string()),
+            Span::styled("All day: ", Style::default().fg(if form.active_field == FormField::AllDay { active_color } else { inactive_color })),
+            Span::raw(if form.all_day { "[x]" } else { "[ ]" }),
+            Span::styled(if form.active_field == FormField::AllDay { " [Space to change]" } else { "" }, Style::default().fg(Color::DarkGray)),
         ]),
         Line::from(""),
     ];
 
     if !form.all_day {
         form_text.extend(vec![
-            Line::from(vec![
-                Span::styled("Start Time: ", Style::default().fg(if form.active_field == FormField::StartTime { active_color } else { inactive_color })),
-                Span::raw(&form.time_input_buffer),
-                Span::styled(if form.active_field == FormField::StartTime {
-                    if form.time_buffer_touched {
-                        " (HH:MM or HHMM)"
-                    } else {
-                        " [type to replace]"
-                    }
-                } else { "" }, Style::default().fg(Color::DarkGray)),
-            ]),
+            Line::from([
+                vec![Span::styled("Start Time: ", Style::default().fg(if form.active_field == FormField::StartTime { active_color } else { inactive_color }))],
+                field_spans(form, FormField::StartTime),
+                vec![
+                    Span::styled(if form.active_field == FormField::StartTime {
+                        if form.time_buffer_touched {
+                            " (HH:MM or HHMM)"
+                        } else {
+                            " [type to replace]"
+                        }
+                    } else { "" }, Style::default().fg(Color::DarkGray)),
+                    error_span(form, FormField::StartTime),
+                ],
+            ].concat()),
             Line::from(""),
         ]);
     }
 
     let duration_label = if form.all_day { "Duration (days): " } else { "Duration (min): " };
     form_text.extend(vec![
+        Line::from([
+            vec![Span::styled(duration_label, Style::default().fg(if form.active_field == FormField::Duration { active_color } else { inactive_color }))],
+            field_spans(form, FormField::Duration),
+            vec![
+                Span::styled(duration_hint(form), Style::default().fg(Color::DarkGray)),
+                error_span(form, FormField::Duration),
+            ],
+        ].concat()),
+        Line::from(""),
+        Line::from([
+            vec![Span::styled("Location: ", Style::default().fg(if form.active_field == FormField::Location { active_color } else { inactive_color }))],
+            field_spans(form, FormField::Location),
+        ].concat()),
+        Line::from(""),
+        Line::from([
+            vec![Span::styled("Link: ", Style::default().fg(if form.active_field == FormField::Link { active_color } else { inactive_color }))],
+            field_spans(form, FormField::Link),
+            vec![error_span(form, FormField::Link)],
+        ].concat()),
+        Line::from(""),
+        Line::from([
+            vec![Span::styled("Attendees: ", Style::default().fg(if form.active_field == FormField::Attendees { active_color } else { inactive_color }))],
+            attendee_spans(form, app.contacts.emails()),
+            vec![error_span(form, FormField::Attendees)],
+        ].concat()),
+        Line::from(""),
+    ]);
+    form_text.extend(description);
+    form_text.extend(vec![
+        Line::from(""),
         Line::from(vec![
-            Span::styled(duration_label, Style::default().fg(if form.active_field == FormField::Duration { active_color } else { inactive_color })),
-            Span::raw(&form.duration_input_buffer),
-            Span::styled(if form.active_field == FormField::Duration {
-                if form.duration_buffer_touched {
-                    ""
-                } else {
-                    " [type to replace]"
-                }
+            Span::styled("Calendar: ", Style::default().fg(if form.active_field == FormField::Calendar { active_color } else { inactive_color })),
+            Span::raw(form.calendar_or(&app.default_calendar)),
+            Span::styled(if form.active_field == FormField::Calendar && !form.is_editing() {
+                " [Space/\u{2190}\u{2192} to change]"
             } else { "" }, Style::default().fg(Color::DarkGray)),
         ]),
         Line::from(""),
         Line::from(vec![
-            Span::styled("Location: ", Style::default().fg(if form.active_field == FormField::Location { active_color } else { inactive_color })),
-            Span::raw(&form.location),
+            Span::styled("Status: ", Style::default().fg(if form.active_field == FormField::Status { active_color } else { inactive_color })),
+            Span::styled(form.status.label(), Style::default().add_modifier(status_modifier(&form.status))),
+            Span::styled(if form.active_field == FormField::Status { " [Space to change]" } else { "" }, Style::default().fg(Color::DarkGray)),
+        ]),
+        Line::from(""),
+        Line::from(vec![
+            Span::styled("Show as: ", Style::default().fg(if form.active_field == FormField::ShowAs { active_color } else { inactive_color })),
+            Span::raw(form.transparency.label()),
+            Span::styled(if form.active_field == FormField::ShowAs { " [Space to change]" } else { "" }, Style::default().fg(Color::DarkGray)),
         ]),
         Line::from(""),
         Line::from(vec![
-            Span::styled("Description: ", Style::default().fg(if form.active_field == FormField::Description { active_color } else { inactive_color })),
-            Span::raw(&form.description),
+            Span::styled("Visibility: ", Style::default().fg(if form.active_field == FormField::Visibility { active_color } else { inactive_color })),
+            Span::raw(form.visibility.as_google()),
+            Span::styled(if form.active_field == FormField::Visibility { " [Space to change]" } else { "" }, Style::default().fg(Color::DarkGray)),
         ]),
         Line::from(""),
         Line::from(vec![
             Span::styled("Tab", Style::default().fg(Color::Cyan)),
             Span::raw(" = Next field | "),
             Span::styled("Enter", Style::default().fg(Color::Green)),
-            Span::raw(" = Save | "),
+            Span::raw(if form.active_field == FormField::Description { " = New line | " } else { " = Save | " }),
             Span::styled("Esc", Style::default().fg(Color::Red)),
             Span::raw(" = Cancel"),
         ]),
@@ -109,3 +169,103 @@ pub fn render(f: &mut Frame, app: &AppState) {
 
     f.render_widget(form_paragraph, form_area);
 }
+
+/// The label followed by up to `MAX_DESCRIPTION_LINES` lines of text,
+/// scrolled to keep the cursor in view while the field is active.
+fn description_lines(form: &EventForm, label_color: Color) -> Vec<Line<'static>> {
+    let active = form.active_field == FormField::Description;
+    let text_lines: Vec<&str> = form.description.split('\n').collect();
+    let (cursor_line, cursor_col) = text_area::line_and_column(&form.description, form.description_cursor);
+    let first = if active { (cursor_line + 1).saturating_sub(MAX_DESCRIPTION_LINES) } else { 0 };
+
+    let mut lines = vec![Line::from(Span::styled("Description:", Style::default().fg(label_color)))];
+    for (idx, text) in text_lines.iter().enumerate().skip(first).take(MAX_DESCRIPTION_LINES) {
+        let mut spans = vec![Span::raw("  ")];
+        if active && idx == cursor_line {
+            spans.extend(cursor_spans(text, cursor_col));
+        } else {
+            spans.push(Span::raw(text.to_string()));
+        }
+        lines.push(Line::from(spans));
+    }
+
+    let hidden = text_lines.len().saturating_sub(first + MAX_DESCRIPTION_LINES) + first;
+    if hidden > 0 {
+        lines.push(Line::from(Span::styled(format!("  ({} more lines)", hidden), Style::default().fg(Color::DarkGray))));
+    }
+    lines
+}
+
+/// Timed events show where the duration ends, and the end-time syntax while typing.
+fn duration_hint(form: &EventForm) -> String {
+    let editing = form.active_field == FormField::Duration;
+    if form.duration_buffer_touched {
+        return if editing && !form.all_day { " (minutes, -17:30 or until 1730)".to_string() } else { String::new() };
+    }
+
+    let mut hint = String::new();
+    if !form.all_day {
+        let (hour, minute) = form.end_time();
+        hint.push_str(&format!(" (until {:02}:{:02})", hour, minute));
+    }
+    if editing {
+        hint.push_str(" [type to replace]");
+    }
+    hint
+}
+
+/// The field's text, with the cursor drawn while it is active.
+fn field_spans(form: &EventForm, field: FormField) -> Vec<Span<'static>> {
+    let text = match field {
+        FormField::Title => &form.title,
+        FormField::Date => &form.date_input_buffer,
+        FormField::StartTime => &form.time_input_buffer,
+        FormField::Duration => &form.duration_input_buffer,
+        FormField::Link => &form.link,
+        FormField::Attendees => &form.attendees,
+        _ => &form.location,
+    };
+    if form.active_field == field {
+        cursor_spans(text, form.field_cursor)
+    } else {
+        vec![Span::raw(text.clone())]
+    }
+}
+
+/// While typing at the end of the field, the rest of a suggested address
+/// is shown dimmed under the cursor; Right or End accepts it.
+fn attendee_spans(form: &EventForm, contacts: &[String]) -> Vec<Span<'static>> {
+    let at_end = form.field_cursor >= form.attendees.chars().count();
+    let suggestion = form.attendee_suggestion(contacts).filter(|_| at_end && form.active_field == FormField::Attendees);
+    let Some(suggestion) = suggestion else {
+        return field_spans(form, FormField::Attendees);
+    };
+
+    let typed = form.attendees.rsplit(',').next().map_or(0, |last| last.trim_start().chars().count());
+    let rest: String = suggestion.chars().skip(typed).collect();
+    let mut ghost = rest.chars();
+    let first = ghost.next().map(String::from).unwrap_or_default();
+    vec![
+        Span::raw(form.attendees.clone()),
+        Span::styled(first, Style::default().fg(Color::DarkGray).add_modifier(Modifier::REVERSED)),
+        Span::styled(ghost.collect::<String>(), Style::default().fg(Color::DarkGray)),
+    ]
+}
+
+fn cursor_spans(text: &str, cursor: usize) -> Vec<Span<'static>> {
+    let before: String = text.chars().take(cursor).collect();
+    let at: String = text.chars().nth(cursor).map_or(" ".to_string(), String::from);
+    let after: String = text.chars().skip(cursor + 1).collect();
+    vec![
+        Span::raw(before),
+        Span::styled(at, Style::default().add_modifier(Modifier::REVERSED)),
+        Span::raw(after),
+    ]
+}
+
+fn error_span(form: &EventForm, field: FormField) -> Span<'static> {
+    match form.error_for(&field) {
+        Some(message) => Span::styled(format!("  {}", message), Style::default().fg(Color::Red)),
+        None => Span::raw(""),
+    }
+}
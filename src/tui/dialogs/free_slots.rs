@@ -0,0 +1,60 @@
+use ratatui::{
+    layout::Alignment,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+use gcal_imp::app::AppState;
+
+pub fn render(f: &mut Frame, app: &AppState) {
+    if app.free_slots.is_empty() {
+        return;
+    }
+
+    let area = f.size();
+    let dialog_width = 50;
+    let dialog_height = (app.free_slots.len() as u16 + 4).min(area.height);
+    let x = (area.width.saturating_sub(dialog_width)) / 2;
+    let y = (area.height.saturating_sub(dialog_height)) / 2;
+
+    let dialog_area = ratatui::layout::Rect {
+        x,
+        y,
+        width: dialog_width,
+        height: dialog_height,
+    };
+
+    f.render_widget(Clear, dialog_area);
+
+    let visible = dialog_height.saturating_sub(4) as usize;
+    let scroll = (app.free_slot_index + 1).saturating_sub(visible);
+
+    let mut lines = vec![Line::from("")];
+    for (index, slot) in app.free_slots.iter().enumerate().skip(scroll).take(visible) {
+        let free_minutes = (slot.end - slot.start).num_minutes();
+        let text = format!(
+            "{} {}\u{2013}{}  ({}h{:02} free)",
+            slot.start.format("%a %b %d"),
+            app.time_format.format(slot.start.time()),
+            app.time_format.format(slot.end.time()),
+            free_minutes / 60,
+            free_minutes % 60
+        );
+        let style = if index == app.free_slot_index {
+            Style::default().fg(app.theme.selected_fg).bg(app.theme.selected_bg).add_modifier(Modifier::BOLD)
+        } else {
+            Style::default()
+        };
+        lines.push(Line::from(Span::styled(text, style)));
+    }
+
+    let paragraph = Paragraph::new(lines)
+        .block(Block::default()
+            .borders(Borders::ALL)
+            .title(format!(" Free {}m slots (j/k, Enter to book, Esc) ", app.free_slot_minutes))
+            .style(Style::default().bg(Color::Black)))
+        .alignment(Alignment::Center);
+
+    f.render_widget(paragraph, dialog_area);
+}
@@ -29,40 +29,89 @@ pub fn render(f: &mut Frame, app: &AppState) {
         Line::from(vec![Span::styled("Navigation:", Style::default().fg(app.theme.help_section))]),
         Line::from("  h/l      - Previous/next day"),
         Line::from("  j/k      - Navigate events (or week if no events)"),
+        Line::from("           Scroll the hour grid in week view"),
+        Line::from("  Tab/S-Tab - Cycle through the day's events (i opens)"),
+        Line::from("  PgUp/PgDn - Page through the day's events"),
         Line::from("  t        - Jump to today"),
         Line::from("  g/G      - First/last day of month"),
         Line::from("  { / }    - Previous/next month"),
+        Line::from("  ( / )    - Previous/next quarter"),
+        Line::from("  1-12     - Year view: jump to month, Enter opens the day"),
+        Line::from("  Mouse    - Click a day to select, an event to open it"),
+        Line::from("           Wheel moves months (weeks/days in week/day view)"),
         Line::from(""),
         Line::from(vec![Span::styled("Views:", Style::default().fg(app.theme.help_section))]),
         Line::from("  m/w/d/y  - Month/Week/Day/Year view"),
+        Line::from("  s        - Month and day side by side"),
+        Line::from("  c        - Show/hide calendars"),
+        Line::from("  z        - Day view: full 24h grid / only busy hours"),
         Line::from(""),
         Line::from(vec![Span::styled("Event Management:", Style::default().fg(app.theme.help_section))]),
         Line::from("  a        - Add new event (insert mode)"),
         Line::from("  :new     - Create event (:new [Meeting title])"),
+        Line::from("           e.g. :new Standup tomorrow 9am 15m"),
         Line::from("  Enter    - Day view (Month) / Edit (Day)"),
+        Line::from("           Open selected event details (Week)"),
         Line::from("  i        - View event details (scrollable)"),
         Line::from("  E        - Edit selected event"),
+        Line::from("  J        - Join meeting (selected or next event)"),
+        Line::from("  T        - Tasks due today (x to mark done)"),
         Line::from("  x        - Delete selected event"),
+        Line::from("  D        - Duplicate event (:duplicate [date])"),
+        Line::from("  .        - Repeat last move, duplicate or new event"),
         Line::from("  v        - Visual mode (select date range)"),
+        Line::from("           then :delete or :move +7d for all events in it"),
+        Line::from("  Q<a-z>   - Record keys until q, @<a-z> replays, @@ again"),
+        Line::from("  \"ay      - Yank selected event into register a"),
+        Line::from(""),
+        Line::from(vec![Span::styled("Event Form:", Style::default().fg(app.theme.help_section))]),
+        Line::from("  Tab/S-Tab - Next/previous field"),
+        Line::from("  Home/End  - Start/end of field, ^Left/^Right by word"),
+        Line::from("  ^w        - Delete word before cursor"),
+        Line::from("  ^v        - Paste from the clipboard"),
+        Line::from("  ^r a      - Paste register a (an event pastes its field)"),
         Line::from(""),
         Line::from(vec![Span::styled("Detail View:", Style::default().fg(app.theme.help_section))]),
         Line::from("  hjkl     - Navigate cursor"),
         Line::from("  wbe      - Word motions"),
         Line::from("  0^$      - Line start/first-non-ws/end"),
-        Line::from("  gG       - Top/bottom"),
+        Line::from("  gg/G     - Top/bottom"),
+        Line::from("  ^d/^u    - Half page down/up"),
         Line::from("  a        - Add new event"),
         Line::from("  o        - Open URL at cursor"),
-        Line::from("  y        - Yank line to clipboard"),
+        Line::from("  f        - Label every URL; type a label to open it"),
+        Line::from("  J        - Join video meeting"),
+        Line::from("  y        - Yank line to clipboard (\"ay into register a)"),
+        Line::from("  d        - Cut selection or line from the description"),
+        Line::from("           into a draft: E saves it, u restores"),
+        Line::from("  I/A      - Edit the description in $EDITOR and save it"),
         Line::from("  B        - Open event in browser"),
+        Line::from("  M        - Open the location in OpenStreetMap or Google Maps"),
+        Line::from("           (:set map_provider=google)"),
         Line::from("  E        - Edit event"),
         Line::from("  q/Esc    - Close detail view"),
         Line::from(""),
         Line::from(vec![Span::styled("Commands:", Style::default().fg(app.theme.help_section))]),
         Line::from("  :q       - Quit"),
         Line::from("  :w       - Sync with Google Calendar"),
-        Line::from("  :goto    - Jump to date (:goto 2025-12-25)"),
+        Line::from("  :sync-status - Show sync progress and last sync"),
+        Line::from("  :goto    - Jump to date (:goto 2025-12-25, +2w, dec 25)"),
         Line::from("  :theme   - Change theme (:theme gruvbox)"),
+        Line::from("  :cal     - Calendar for new events (:cal work)"),
+        Line::from("  :set     - Override a ui option (:set day_grid=compact)"),
+        Line::from("  :reload-config - Re-read config.toml"),
+        Line::from("  :free    - Find free time (:free 60m this week 9-17)"),
+        Line::from("  :report  - Hours per calendar and title (:report last week)"),
+        Line::from("  :conflicts - Tight hops between locations (travel_buffer_minutes)"),
+        Line::from("  :open    - Show an event by id or Google Calendar link"),
+        Line::from("  :registers - Show yanked text and events"),
+        Line::from("  :export-view - Save the view for printing (:export-view html|text [file])"),
+        Line::from("  :toggle  - Toggle a setting (:toggle declined)"),
+        Line::from("  :subscribe - Add holidays [region] or birthdays overlay"),
+        Line::from("  :messages - Show recent syncs and errors"),
+        Line::from("  :log     - Open the log file in the pager"),
         Line::from("  :help    - Show this help"),
+        Line::from("  Up/Down  - Command history, Tab - complete"),
         Line::from(""),
     ];
 
@@ -1,3 +1,14 @@
 pub mod help;
 pub mod event_form;
 pub mod delete_confirmation;
+pub mod conflict_warning;
+pub mod update_diff;
+pub mod sync_conflict;
+pub mod free_slots;
+pub mod calendar_list;
+pub mod sync_status;
+pub mod activity;
+pub mod error;
+pub mod report;
+pub mod travel_report;
+pub mod registers;
@@ -0,0 +1,51 @@
+use ratatui::{
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+use gcal_imp::app::AppState;
+
+pub fn render(f: &mut Frame, app: &AppState) {
+    let area = f.size();
+    let dialog_width = 70.min(area.width);
+    let preview_width = dialog_width.saturating_sub(10) as usize;
+
+    let mut lines = vec![Line::from("")];
+    for (name, register) in app.registers.iter() {
+        let preview: String = register.preview(&app.timezone).chars().take(preview_width).collect();
+        lines.push(Line::from(vec![
+            Span::styled(format!("  \"{}  ", name), Style::default().fg(Color::Cyan)),
+            Span::raw(preview),
+        ]));
+    }
+    if app.registers.is_empty() {
+        lines.push(Line::from("  Nothing yanked yet"));
+    }
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled(
+        "  \"ay yanks into a, Ctrl-R a pastes in the form. Esc to close",
+        Style::default().fg(Color::DarkGray),
+    )));
+
+    let dialog_height = (lines.len() as u16 + 2).min(area.height);
+    let x = (area.width.saturating_sub(dialog_width)) / 2;
+    let y = (area.height.saturating_sub(dialog_height)) / 2;
+
+    let dialog_area = ratatui::layout::Rect {
+        x,
+        y,
+        width: dialog_width,
+        height: dialog_height,
+    };
+
+    f.render_widget(Clear, dialog_area);
+
+    let paragraph = Paragraph::new(lines)
+        .block(Block::default()
+            .borders(Borders::ALL)
+            .title(" Registers ")
+            .style(Style::default().bg(Color::Black)));
+
+    f.render_widget(paragraph, dialog_area);
+}
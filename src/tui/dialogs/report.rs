@@ -0,0 +1,51 @@
+use ratatui::{
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+use gcal_imp::app::AppState;
+
+pub fn render(f: &mut Frame, app: &AppState) {
+    let Some(report) = &app.report else {
+        return;
+    };
+
+    let rows = report.text_lines();
+    let area = f.size();
+    let content_width = rows.iter().map(|row| row.chars().count()).max().unwrap_or(0) as u16;
+    let dialog_width = (content_width + 6).max(40).min(area.width);
+    let heading = Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD);
+    let mut lines = vec![Line::from("")];
+    for (index, row) in rows.into_iter().enumerate() {
+        // The summary and section titles are the unindented lines.
+        let style = if index == 0 || !row.starts_with(' ') { heading } else { Style::default() };
+        lines.push(Line::from(Span::styled(format!("  {}", row), style)));
+    }
+    if report.events == 0 {
+        lines.push(Line::from("  No timed events in this range"));
+    }
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled("  Esc to close", Style::default().fg(Color::DarkGray))));
+
+    let dialog_height = (lines.len() as u16 + 2).min(area.height);
+    let x = (area.width.saturating_sub(dialog_width)) / 2;
+    let y = (area.height.saturating_sub(dialog_height)) / 2;
+
+    let dialog_area = ratatui::layout::Rect {
+        x,
+        y,
+        width: dialog_width,
+        height: dialog_height,
+    };
+
+    f.render_widget(Clear, dialog_area);
+
+    let paragraph = Paragraph::new(lines)
+        .block(Block::default()
+            .borders(Borders::ALL)
+            .title(" Report ")
+            .style(Style::default().bg(Color::Black)));
+
+    f.render_widget(paragraph, dialog_area);
+}
@@ -0,0 +1,83 @@
+use ratatui::{
+    layout::Alignment,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+use gcal_imp::{app::AppState, calendar::event_diff};
+
+use super::update_diff::shown;
+
+const DIALOG_WIDTH: u16 = 70;
+
+pub fn render(f: &mut Frame, app: &AppState) {
+    let Some(conflict) = app.sync_conflicts.first() else {
+        return;
+    };
+
+    let changes = event_diff::diff(&conflict.remote, &conflict.local, &app.timezone);
+    let area = f.size();
+    let value_width = DIALOG_WIDTH.saturating_sub(13) as usize;
+    let dialog_height = (changes.len() as u16 * 3 + 7).min(area.height);
+    let x = (area.width.saturating_sub(DIALOG_WIDTH)) / 2;
+    let y = (area.height.saturating_sub(dialog_height)) / 2;
+
+    let dialog_area = ratatui::layout::Rect {
+        x,
+        y,
+        width: DIALOG_WIDTH,
+        height: dialog_height,
+    };
+
+    f.render_widget(Clear, dialog_area);
+
+    let remaining = if app.sync_conflicts.len() > 1 {
+        format!(" (1 of {})", app.sync_conflicts.len())
+    } else {
+        String::new()
+    };
+    let mut dialog_text = vec![
+        Line::from(vec![
+            Span::raw("Changed here and on the server"),
+            Span::styled(remaining, Style::default().fg(Color::DarkGray)),
+        ]),
+        Line::from(""),
+    ];
+    for change in &changes {
+        dialog_text.extend([
+            Line::from(Span::styled(change.field, Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD))),
+            Line::from(vec![
+                Span::styled("  server: ", Style::default().fg(Color::Cyan)),
+                Span::raw(shown(&change.old, value_width)),
+            ]),
+            Line::from(vec![
+                Span::styled("  local:  ", Style::default().fg(Color::Magenta)),
+                Span::raw(shown(&change.new, value_width)),
+            ]),
+        ]);
+    }
+
+    dialog_text.extend([
+        Line::from(""),
+        Line::from(vec![
+            Span::styled("S", Style::default().fg(Color::Cyan)),
+            Span::raw(" = Server | "),
+            Span::styled("L", Style::default().fg(Color::Magenta)),
+            Span::raw(" = Local | "),
+            Span::styled("M", Style::default().fg(Color::Green)),
+            Span::raw(" = Merge | "),
+            Span::styled("Esc", Style::default().fg(Color::Red)),
+            Span::raw(" = Later"),
+        ]),
+    ]);
+
+    let dialog_paragraph = Paragraph::new(dialog_text)
+        .block(Block::default()
+            .borders(Borders::ALL)
+            .title(" Sync Conflict ")
+            .style(Style::default().bg(Color::Black)))
+        .alignment(Alignment::Left);
+
+    f.render_widget(dialog_paragraph, dialog_area);
+}
@@ -0,0 +1,54 @@
+use ratatui::{
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+use gcal_imp::{app::AppState, SyncPhase};
+
+pub fn render(f: &mut Frame, app: &AppState) {
+    let area = f.size();
+    let dialog_width = 50.min(area.width);
+    let dialog_height = 9.min(area.height);
+    let x = (area.width.saturating_sub(dialog_width)) / 2;
+    let y = (area.height.saturating_sub(dialog_height)) / 2;
+
+    let dialog_area = ratatui::layout::Rect {
+        x,
+        y,
+        width: dialog_width,
+        height: dialog_height,
+    };
+
+    f.render_widget(Clear, dialog_area);
+
+    let sync = &app.sync;
+    let phase = sync.phase.label();
+    let phase_color = match sync.phase {
+        SyncPhase::Error(_) => app.theme.error,
+        SyncPhase::Idle => app.theme.success,
+        _ => Color::Yellow,
+    };
+    let last_sync = sync.last_sync.map_or("never".to_string(), |at| {
+        app.timezone.to_local(&at).format("%Y-%m-%d %H:%M:%S").to_string()
+    });
+
+    let label = |text: &'static str| Span::styled(text, Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD));
+    let lines = vec![
+        Line::from(""),
+        Line::from(vec![label("  Phase:      "), Span::styled(phase, Style::default().fg(phase_color))]),
+        Line::from(vec![label("  Calendars:  "), Span::raw(format!("{}/{}", sync.calendars_done, sync.calendars_total))]),
+        Line::from(vec![label("  Events:     "), Span::raw(sync.events_fetched.to_string())]),
+        Line::from(vec![label("  Last sync:  "), Span::raw(last_sync)]),
+        Line::from(""),
+        Line::from(Span::styled("  Esc to close", Style::default().fg(Color::DarkGray))),
+    ];
+
+    let paragraph = Paragraph::new(lines)
+        .block(Block::default()
+            .borders(Borders::ALL)
+            .title(" Sync Status ")
+            .style(Style::default().bg(Color::Black)));
+
+    f.render_widget(paragraph, dialog_area);
+}
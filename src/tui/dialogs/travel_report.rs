@@ -0,0 +1,53 @@
+use ratatui::{
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+use gcal_imp::app::AppState;
+
+pub fn render(f: &mut Frame, app: &AppState) {
+    let Some(report) = &app.travel_report else {
+        return;
+    };
+
+    let rows = report.text_lines();
+    let area = f.size();
+    let content_width = rows.iter().map(|row| row.chars().count()).max().unwrap_or(0) as u16;
+    let dialog_width = (content_width + 6).max(40).min(area.width);
+    let mut lines = vec![Line::from("")];
+    for (index, row) in rows.into_iter().enumerate() {
+        let style = if index == 0 {
+            Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(Color::Red)
+        };
+        lines.push(Line::from(Span::styled(format!("  {}", row), style)));
+    }
+    if report.warnings.is_empty() {
+        lines.push(Line::from("  Enough time between every change of place"));
+    }
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled("  Esc to close", Style::default().fg(Color::DarkGray))));
+
+    let dialog_height = (lines.len() as u16 + 2).min(area.height);
+    let x = (area.width.saturating_sub(dialog_width)) / 2;
+    let y = (area.height.saturating_sub(dialog_height)) / 2;
+
+    let dialog_area = ratatui::layout::Rect {
+        x,
+        y,
+        width: dialog_width,
+        height: dialog_height,
+    };
+
+    f.render_widget(Clear, dialog_area);
+
+    let paragraph = Paragraph::new(lines)
+        .block(Block::default()
+            .borders(Borders::ALL)
+            .title(" Conflicts ")
+            .style(Style::default().bg(Color::Black)));
+
+    f.render_widget(paragraph, dialog_area);
+}
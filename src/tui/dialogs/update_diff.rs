@@ -0,0 +1,77 @@
+use ratatui::{
+    layout::Alignment,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+use gcal_imp::app::AppState;
+
+const DIALOG_WIDTH: u16 = 70;
+
+pub fn render(f: &mut Frame, app: &AppState) {
+    if app.pending_changes.is_empty() {
+        return;
+    }
+
+    let area = f.size();
+    let value_width = DIALOG_WIDTH.saturating_sub(6) as usize;
+    let dialog_height = (app.pending_changes.len() as u16 * 3 + 5).min(area.height);
+    let x = (area.width.saturating_sub(DIALOG_WIDTH)) / 2;
+    let y = (area.height.saturating_sub(dialog_height)) / 2;
+
+    let dialog_area = ratatui::layout::Rect {
+        x,
+        y,
+        width: DIALOG_WIDTH,
+        height: dialog_height,
+    };
+
+    f.render_widget(Clear, dialog_area);
+
+    let mut dialog_text = vec![Line::from("")];
+    for change in &app.pending_changes {
+        dialog_text.extend([
+            Line::from(Span::styled(change.field, Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD))),
+            Line::from(vec![
+                Span::styled("  - ", Style::default().fg(Color::Red)),
+                Span::styled(shown(&change.old, value_width), Style::default().fg(Color::Red)),
+            ]),
+            Line::from(vec![
+                Span::styled("  + ", Style::default().fg(Color::Green)),
+                Span::styled(shown(&change.new, value_width), Style::default().fg(Color::Green)),
+            ]),
+        ]);
+    }
+
+    dialog_text.extend([
+        Line::from(""),
+        Line::from(vec![
+            Span::styled("Y", Style::default().fg(Color::Green)),
+            Span::raw(" = Yes, update | "),
+            Span::styled("N", Style::default().fg(Color::Red)),
+            Span::raw(" = No, keep editing"),
+        ]),
+    ]);
+
+    let dialog_paragraph = Paragraph::new(dialog_text)
+        .block(Block::default()
+            .borders(Borders::ALL)
+            .title(" Confirm Changes ")
+            .style(Style::default().bg(Color::Black)))
+        .alignment(Alignment::Left);
+
+    f.render_widget(dialog_paragraph, dialog_area);
+}
+
+pub fn shown(value: &str, width: usize) -> String {
+    if value.is_empty() {
+        return "(none)".to_string();
+    }
+    if value.chars().count() <= width {
+        return value.to_string();
+    }
+    let mut cut: String = value.chars().take(width.saturating_sub(1)).collect();
+    cut.push('\u{2026}');
+    cut
+}
@@ -1,10 +1,221 @@
 use std::sync::OnceLock;
-use html2text;
+use html2text::render::text_renderer::RichAnnotation;
+use ratatui::{
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+};
 use regex::Regex;
 
 pub fn strip_html(html: &str) -> String {
-    let normalized = expand_anchor_tags(html);
-    html2text::from_read(normalized.as_bytes(), 1000)
+    html_to_lines(html).iter().map(line_text).collect::<Vec<_>>().join("\n")
+}
+
+/// Description text as it would be edited: HTML is stripped, anything else
+/// is returned as written.
+pub fn plain_text(description: &str) -> String {
+    if looks_like_html(description) { strip_html(description) } else { description.to_string() }
+}
+
+/// Styled lines for a description: HTML through html2text, anything else
+/// as Markdown. Plain text without Markdown comes through unchanged.
+pub fn description_to_lines(description: &str) -> Vec<Line<'static>> {
+    if looks_like_html(description) { html_to_lines(description) } else { markdown_to_lines(description) }
+}
+
+pub fn looks_like_html(text: &str) -> bool {
+    static HTML_TAG_RE: OnceLock<Regex> = OnceLock::new();
+    HTML_TAG_RE
+        .get_or_init(|| {
+            Regex::new(r"(?i)</?(a|b|br|code|div|em|h[1-6]|i|li|ol|p|pre|span|strong|u|ul)(\s[^>]*)?/?>")
+                .expect("invalid html tag regex")
+        })
+        .is_match(text)
+}
+
+pub fn line_text(line: &Line<'_>) -> String {
+    line.spans.iter().map(|span| span.content.as_ref()).collect()
+}
+
+/// Description HTML as styled lines: bold, italics and code keep their
+/// look, headings are underlined and list items get bullets. Links stay
+/// inline as "text (url)" so `o` can open them.
+pub fn html_to_lines(html: &str) -> Vec<Line<'static>> {
+    let normalized = expand_anchor_tags(&bold_as_strong(html));
+    html2text::from_read_rich(normalized.as_bytes(), 1000)
+        .into_iter()
+        .map(|line| {
+            let runs: Vec<(String, Vec<RichAnnotation>)> = line.tagged_strings()
+                .map(|tagged| (tagged.s.clone(), tagged.tag.clone()))
+                .collect();
+            styled_line(runs)
+        })
+        // html2text leaves an indent-only line before a nested list.
+        .filter(|line| {
+            let text = line_text(line);
+            text.is_empty() || !text.trim().is_empty()
+        })
+        .collect()
+}
+
+/// Headings, `-`/`*`/`+` lists, fenced code, code spans, bold, italics and
+/// `[text](url)` links. Links read "text (url)" like the HTML ones do.
+pub fn markdown_to_lines(text: &str) -> Vec<Line<'static>> {
+    let mut in_fence = false;
+    text.lines()
+        .filter_map(|line| {
+            if line.trim_start().starts_with("```") {
+                in_fence = !in_fence;
+                return None;
+            }
+            if in_fence {
+                return Some(Line::from(Span::styled(line.to_string(), code_style())));
+            }
+
+            let rest = line.trim_start();
+            let indent = &line[..line.len() - rest.len()];
+            let runs = match rest.get(..2) {
+                Some("- " | "* " | "+ ") => {
+                    let mut runs = vec![(format!("{}* ", indent), Vec::new())];
+                    runs.extend(markdown_inline(&rest[2..], &[]));
+                    runs
+                }
+                _ => markdown_inline(line, &[]),
+            };
+            Some(styled_line(runs))
+        })
+        .collect()
+}
+
+fn markdown_inline(text: &str, tags: &[RichAnnotation]) -> Vec<(String, Vec<RichAnnotation>)> {
+    static INLINE_RE: OnceLock<Regex> = OnceLock::new();
+    let regex = INLINE_RE.get_or_init(|| {
+        Regex::new(concat!(
+            r"`([^`]+)`",
+            r"|\*\*(.+?)\*\*|__(.+?)__",
+            r"|\[([^\]]+)\]\((https?://[^\s)]+)\)",
+            r"|\*([^*\s](?:[^*]*[^*\s])?)\*|_([^_\s](?:[^_]*[^_\s])?)_",
+        ))
+        .expect("invalid markdown inline regex")
+    });
+    let with = |tag: RichAnnotation| {
+        let mut tags = tags.to_vec();
+        tags.push(tag);
+        tags
+    };
+
+    let mut runs = Vec::new();
+    let mut plain_from = 0;
+    let mut search_from = 0;
+    while let Some(caps) = regex.captures_at(text, search_from) {
+        let whole = caps.get(0).expect("match");
+        // `snake_case_names` are not emphasis.
+        if caps.get(7).is_some() && !is_word_edge(text, whole.start(), whole.end()) {
+            search_from = whole.start() + 1;
+            continue;
+        }
+        if whole.start() > plain_from {
+            runs.push((text[plain_from..whole.start()].to_string(), tags.to_vec()));
+        }
+        if let Some(code) = caps.get(1) {
+            runs.push((code.as_str().to_string(), with(RichAnnotation::Code)));
+        } else if let Some(strong) = caps.get(2).or(caps.get(3)) {
+            runs.extend(markdown_inline(strong.as_str(), &with(RichAnnotation::Strong)));
+        } else if let (Some(label), Some(url)) = (caps.get(4), caps.get(5)) {
+            runs.extend(markdown_inline(label.as_str(), &with(RichAnnotation::Link(url.as_str().to_string()))));
+            runs.push((format!(" ({})", url.as_str()), tags.to_vec()));
+        } else if let Some(emphasis) = caps.get(6).or(caps.get(7)) {
+            runs.extend(markdown_inline(emphasis.as_str(), &with(RichAnnotation::Emphasis)));
+        }
+        plain_from = whole.end();
+        search_from = whole.end();
+    }
+    if plain_from < text.len() {
+        runs.push((text[plain_from..].to_string(), tags.to_vec()));
+    }
+    runs
+}
+
+fn is_word_edge(text: &str, start: usize, end: usize) -> bool {
+    let before = text[..start].chars().next_back();
+    let after = text[end..].chars().next();
+    !before.is_some_and(char::is_alphanumeric) && !after.is_some_and(char::is_alphanumeric)
+}
+
+/// Google Calendar writes `<b>`, which html2text does not treat as strong.
+fn bold_as_strong(html: &str) -> String {
+    static BOLD_RE: OnceLock<Regex> = OnceLock::new();
+    let regex = BOLD_RE.get_or_init(|| Regex::new(r"(?i)<(/?)b(\s[^>]*)?>").expect("invalid bold tag regex"));
+    regex.replace_all(html, "<${1}strong>").into_owned()
+}
+
+fn has(tags: &[RichAnnotation], wanted: &RichAnnotation) -> bool {
+    tags.contains(wanted)
+}
+
+fn styled_line(mut runs: Vec<(String, Vec<RichAnnotation>)>) -> Line<'static> {
+    // html2text marks strong text with `*` and code with backticks; the
+    // style says it instead.
+    for (marker, tag) in [('*', RichAnnotation::Strong), ('`', RichAnnotation::Code)] {
+        for i in 0..runs.len() {
+            if !has(&runs[i].1, &tag) {
+                continue;
+            }
+            if (i == 0 || !has(&runs[i - 1].1, &tag)) && runs[i].0.starts_with(marker) {
+                runs[i].0.remove(0);
+            }
+            if (i + 1 == runs.len() || !has(&runs[i + 1].1, &tag)) && runs[i].0.ends_with(marker) {
+                runs[i].0.pop();
+            }
+        }
+    }
+
+    let mut heading = false;
+    if let Some((first, tags)) = runs.first_mut()
+        && tags.is_empty()
+    {
+        let hashes = first.chars().take_while(|&c| c == '#').count();
+        if (1..=6).contains(&hashes) && first[hashes..].starts_with(' ') {
+            heading = true;
+            first.replace_range(..=hashes, "");
+        }
+    }
+    // Nested items come as indentation, then the `* ` prefix.
+    for (text, tags) in runs.iter_mut() {
+        if !tags.is_empty() {
+            break;
+        }
+        if text.trim().is_empty() {
+            continue;
+        }
+        if text.trim_start() == "* " {
+            *text = text.replacen('*', "•", 1);
+        }
+        break;
+    }
+
+    let base = if heading { heading_style() } else { Style::default() };
+    Line::from(runs.into_iter()
+        .filter(|(text, _)| !text.is_empty())
+        .map(|(text, tags)| Span::styled(text, tags.iter().fold(base, |style, tag| style.patch(annotation_style(tag)))))
+        .collect::<Vec<_>>())
+}
+
+pub fn heading_style() -> Style {
+    Style::default().add_modifier(Modifier::BOLD | Modifier::UNDERLINED)
+}
+
+fn annotation_style(tag: &RichAnnotation) -> Style {
+    match tag {
+        RichAnnotation::Strong => Style::default().add_modifier(Modifier::BOLD),
+        RichAnnotation::Emphasis => Style::default().add_modifier(Modifier::ITALIC),
+        RichAnnotation::Strikeout => Style::default().add_modifier(Modifier::CROSSED_OUT),
+        RichAnnotation::Code | RichAnnotation::Preformat(_) => code_style(),
+        _ => Style::default(),
+    }
+}
+
+pub fn code_style() -> Style {
+    Style::default().fg(Color::Yellow)
 }
 
 fn expand_anchor_tags(html: &str) -> String {
@@ -39,6 +250,45 @@ mod tests {
         assert!(text.contains("Example (https://example.com)"));
     }
 
+    #[test]
+    fn keeps_bold_italics_headings_and_bullets() {
+        let html = "<h2>Agenda</h2><p>Bring <b>slides</b> and <i>notes</i><br>then <code>make demo</code></p><ul><li>intros<ul><li>names</li></ul></li></ul>";
+
+        let lines = html_to_lines(html);
+        let text: Vec<String> = lines.iter().map(line_text).collect();
+
+        assert_eq!(text, vec!["Agenda", "", "Bring slides and notes", "then make demo", "", "• intros", "  • names"]);
+        assert_eq!(lines[0].spans[0].style, heading_style());
+        let bold = lines[2].spans.iter().find(|span| span.content == "slides").unwrap();
+        assert!(bold.style.add_modifier.contains(Modifier::BOLD));
+        let italic = lines[2].spans.iter().find(|span| span.content == "notes").unwrap();
+        assert!(italic.style.add_modifier.contains(Modifier::ITALIC));
+        assert_eq!(lines[3].spans[1].style, code_style());
+    }
+
+    #[test]
+    fn renders_markdown_with_the_same_styles() {
+        let text = "## Agenda\n- **Demo** by `ops`\n  - see [notes](https://example.com/n)\nuse snake_case_names, *not* this\n```\nmake demo\n```";
+
+        let lines = description_to_lines(text);
+        let text: Vec<String> = lines.iter().map(line_text).collect();
+
+        assert_eq!(text, vec![
+            "Agenda",
+            "• Demo by ops",
+            "  • see notes (https://example.com/n)",
+            "use snake_case_names, not this",
+            "make demo",
+        ]);
+        assert_eq!(lines[0].spans[0].style, heading_style());
+        assert!(lines[1].spans[1].style.add_modifier.contains(Modifier::BOLD));
+        assert_eq!(lines[1].spans[3].style, code_style());
+        let emphasis = lines[3].spans.iter().find(|span| span.content == "not").unwrap();
+        assert!(emphasis.style.add_modifier.contains(Modifier::ITALIC));
+        assert_eq!(lines[4].spans[0].style, code_style());
+        assert_eq!(plain_text("**as typed**"), "**as typed**");
+    }
+
     #[test]
     fn anchor_without_text_falls_back_to_url() {
         let html = r#"<a href="https://example.com"></a>"#;
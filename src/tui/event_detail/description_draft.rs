@@ -0,0 +1,103 @@
+use super::line_wrapping::logical_position;
+
+/// The draft `(line, col)` shown at display `(row, col)` of `lines` wrapped
+/// to `width`, where the draft's `line_count` lines start at logical line
+/// `first`. None outside the draft.
+pub fn draft_position(
+    lines: &[String],
+    width: usize,
+    first: usize,
+    line_count: usize,
+    (row, col): (usize, usize),
+) -> Option<(usize, usize)> {
+    logical_position(lines, width, row, col)
+        .filter(|(index, _)| (first..first + line_count).contains(index))
+        .map(|(index, offset)| (index - first, offset))
+}
+
+/// Char offset in `text` of column `col` on line `line`, clamped to the line.
+fn offset(text: &str, line: usize, col: usize) -> usize {
+    let mut start = 0;
+    for (idx, content) in text.split('\n').enumerate() {
+        let len = content.chars().count();
+        if idx == line {
+            return start + col.min(len);
+        }
+        start += len + 1;
+    }
+    text.chars().count()
+}
+
+fn remove_chars(text: &mut String, start: usize, end: usize) -> String {
+    let chars: Vec<char> = text.chars().collect();
+    let end = end.min(chars.len());
+    let start = start.min(end);
+    let removed = chars[start..end].iter().collect();
+    *text = chars[..start].iter().chain(&chars[end..]).collect();
+    removed
+}
+
+/// Removes `start` through `end` (inclusive, as `(line, col)`) from `text`
+/// and returns what was removed.
+pub fn cut(text: &mut String, start: (usize, usize), end: (usize, usize)) -> String {
+    let from = offset(text, start.0, start.1);
+    let to = offset(text, end.0, end.1.saturating_add(1));
+    remove_chars(text, from, to)
+}
+
+/// Removes line `line` with its line break and returns it.
+pub fn cut_line(text: &mut String, line: usize) -> String {
+    let from = offset(text, line, 0);
+    let to = offset(text, line + 1, 0);
+    let mut removed = remove_chars(text, from, to);
+    if removed.ends_with('\n') {
+        removed.pop();
+    } else if from > 0 && from == text.chars().count() {
+        // The last line goes with the break before it.
+        remove_chars(text, from - 1, from);
+    }
+    removed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cuts_a_selection_across_lines() {
+        let mut text = "Agenda\n- intros\n- roadmap".to_string();
+
+        assert_eq!(cut(&mut text, (1, 2), (2, 1)), "intros\n- ");
+        assert_eq!(text, "Agenda\n- roadmap");
+        assert_eq!(cut(&mut text, (1, 0), (1, 99)), "- roadmap");
+        assert_eq!(text, "Agenda\n");
+    }
+
+    #[test]
+    fn display_positions_map_to_the_draft_past_wrapped_rows() {
+        let lines: Vec<String> = ["Standup", "Notes:", "Agenda", "- intros and roadmap", "Link: x"]
+            .iter()
+            .map(|line| line.to_string())
+            .collect();
+
+        // "- intros and roadmap" shows as "- intros " and "and " and "roadmap".
+        assert_eq!(draft_position(&lines, 10, 2, 2, (2, 3)), Some((0, 3)));
+        assert_eq!(draft_position(&lines, 10, 2, 2, (3, 2)), Some((1, 2)));
+        assert_eq!(draft_position(&lines, 10, 2, 2, (4, 1)), Some((1, 10)));
+        assert_eq!(draft_position(&lines, 10, 2, 2, (5, 0)), Some((1, 13)));
+        assert_eq!(draft_position(&lines, 10, 2, 2, (1, 0)), None);
+        assert_eq!(draft_position(&lines, 10, 2, 2, (6, 0)), None);
+    }
+
+    #[test]
+    fn cuts_whole_lines() {
+        let mut text = "one\ntwo\nthree".to_string();
+
+        assert_eq!(cut_line(&mut text, 1), "two");
+        assert_eq!(text, "one\nthree");
+        assert_eq!(cut_line(&mut text, 1), "three");
+        assert_eq!(text, "one");
+        assert_eq!(cut_line(&mut text, 0), "one");
+        assert_eq!(text, "");
+    }
+}
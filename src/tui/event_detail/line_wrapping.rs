@@ -0,0 +1,138 @@
+use unicode_width::UnicodeWidthChar;
+
+/// Char indices at which each display row of `text` starts when wrapped to
+/// `width` columns. Rows break after whitespace where possible.
+pub fn wrap_offsets(text: &str, width: usize) -> Vec<usize> {
+    let chars: Vec<char> = text.chars().collect();
+    let mut offsets = vec![0];
+    if width == 0 {
+        return offsets;
+    }
+
+    let mut row_start = 0;
+    let mut row_width = 0;
+    let mut last_break = None;
+    let mut i = 0;
+
+    while i < chars.len() {
+        let char_width = chars[i].width().unwrap_or(0);
+        if row_width + char_width > width && i > row_start {
+            let break_at = match last_break {
+                Some(b) if b > row_start => b,
+                _ => i,
+            };
+            offsets.push(break_at);
+            row_start = break_at;
+            row_width = chars[row_start..i].iter().map(|c| c.width().unwrap_or(0)).sum();
+            last_break = None;
+            continue;
+        }
+
+        row_width += char_width;
+        if chars[i].is_whitespace() {
+            last_break = Some(i + 1);
+        }
+        i += 1;
+    }
+
+    offsets
+}
+
+pub fn wrap_line(text: &str, width: usize) -> Vec<String> {
+    let chars: Vec<char> = text.chars().collect();
+    let offsets = wrap_offsets(text, width);
+
+    offsets
+        .iter()
+        .enumerate()
+        .map(|(row, &start)| {
+            let end = offsets.get(row + 1).copied().unwrap_or(chars.len());
+            chars[start..end].iter().collect()
+        })
+        .collect()
+}
+
+pub fn wrap_lines(lines: &[String], width: usize) -> Vec<String> {
+    lines.iter().flat_map(|line| wrap_line(line, width)).collect()
+}
+
+pub fn logical_line_index(lines: &[String], width: usize, display_line: usize) -> Option<usize> {
+    let mut rows = 0;
+    for (idx, line) in lines.iter().enumerate() {
+        rows += wrap_offsets(line, width).len();
+        if display_line < rows {
+            return Some(idx);
+        }
+    }
+    None
+}
+
+/// The logical line and char offset shown at `col` of display row
+/// `display_line`. The offset may run past the end of a short row.
+pub fn logical_position(lines: &[String], width: usize, display_line: usize, col: usize) -> Option<(usize, usize)> {
+    let mut rows = 0;
+    for (idx, line) in lines.iter().enumerate() {
+        let offsets = wrap_offsets(line, width);
+        if display_line < rows + offsets.len() {
+            return Some((idx, offsets[display_line - rows] + col));
+        }
+        rows += offsets.len();
+    }
+    None
+}
+
+/// The display row and column where char `offset` of logical line `line`
+/// is shown; the reverse of `logical_position`.
+pub fn display_position(lines: &[String], width: usize, line: usize, offset: usize) -> (usize, usize) {
+    let rows: usize = lines.iter().take(line).map(|text| wrap_offsets(text, width).len()).sum();
+    let offsets = lines.get(line).map(|text| wrap_offsets(text, width)).unwrap_or_else(|| vec![0]);
+    let row = offsets.iter().rposition(|&start| start <= offset).unwrap_or(0);
+    (rows + row, offset - offsets[row])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn short_lines_are_not_wrapped() {
+        assert_eq!(wrap_line("hello world", 20), vec!["hello world"]);
+        assert_eq!(wrap_line("", 20), vec![""]);
+    }
+
+    #[test]
+    fn wraps_at_word_boundaries() {
+        assert_eq!(wrap_line("the quick brown fox", 10), vec!["the quick ", "brown fox"]);
+    }
+
+    #[test]
+    fn breaks_long_words_mid_word() {
+        assert_eq!(wrap_line("abcdefghij", 4), vec!["abcd", "efgh", "ij"]);
+    }
+
+    #[test]
+    fn wide_characters_count_double() {
+        assert_eq!(wrap_line("📅📅📅", 4), vec!["📅📅", "📅"]);
+    }
+
+    #[test]
+    fn wrapped_rows_keep_every_character() {
+        let text = "Join at https://meet.example.com/abc-defg-hij for the sync";
+        assert_eq!(wrap_line(text, 12).concat(), text);
+    }
+
+    #[test]
+    fn maps_display_rows_to_logical_lines() {
+        let lines = vec!["title".to_string(), "aaaa bbbb cccc".to_string(), "end".to_string()];
+
+        assert_eq!(wrap_lines(&lines, 5).len(), 5);
+        assert_eq!(logical_line_index(&lines, 5, 0), Some(0));
+        assert_eq!(logical_line_index(&lines, 5, 3), Some(1));
+        assert_eq!(logical_line_index(&lines, 5, 4), Some(2));
+        assert_eq!(logical_line_index(&lines, 5, 5), None);
+        assert_eq!(logical_position(&lines, 5, 3, 2), Some((1, 12)));
+        assert_eq!(logical_position(&lines, 5, 4, 0), Some((2, 0)));
+        assert_eq!(display_position(&lines, 5, 1, 12), (3, 2));
+        assert_eq!(display_position(&lines, 5, 2, 0), (4, 0));
+    }
+}
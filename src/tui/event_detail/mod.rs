@@ -1,4 +1,6 @@
 pub mod content_formatting;
+pub mod description_draft;
+pub mod line_wrapping;
 pub mod navigation;
 pub mod presentation;
 pub mod text_selection;
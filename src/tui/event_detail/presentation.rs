@@ -1,14 +1,18 @@
+use std::collections::HashMap;
 use std::sync::OnceLock;
 use ratatui::{
-    layout::Alignment,
+    layout::{Alignment, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span},
     widgets::{Block, Borders, Clear, Paragraph},
     Frame,
 };
 use regex::Regex;
-use gcal_imp::{app::AppState, calendar::Event as CalendarEvent};
-use super::content_formatting::strip_html;
+use gcal_imp::{app::AppState, calendar::{event::split_link, map_link::MapProvider, AccessRole, DisplayTimezone, Event as CalendarEvent}, ui::time_format::TimeFormat};
+use super::{content_formatting::{description_to_lines, line_text, looks_like_html, plain_text}, line_wrapping::{display_position, wrap_lines, wrap_offsets}};
+
+/// Starts the line above the description, draft or not.
+pub const DESCRIPTION_HEADER: &str = "📝 Description";
 
 static MARKDOWN_LINK_RE: OnceLock<Regex> = OnceLock::new();
 static PLAIN_URL_RE: OnceLock<Regex> = OnceLock::new();
@@ -27,6 +31,30 @@ fn plain_url_pattern() -> &'static Regex {
     })
 }
 
+/// Every link in the detail lines as (display row, column, url), for `f`.
+/// A markdown link is labelled at its `[`.
+pub fn detail_urls(lines: &[String], width: usize) -> Vec<(usize, usize, String)> {
+    let mut urls = Vec::new();
+    for (line_idx, text) in lines.iter().enumerate() {
+        let mut found: Vec<(usize, usize, &str)> = markdown_link_pattern().captures_iter(text)
+            .filter_map(|caps| Some((caps.get(0)?.start(), caps.get(0)?.end(), caps.get(2)?.as_str())))
+            .collect();
+        for url in plain_url_pattern().find_iter(text) {
+            if !found.iter().any(|&(start, end, _)| url.start() >= start && url.start() < end) {
+                found.push((url.start(), url.end(), url.as_str()));
+            }
+        }
+        found.sort_by_key(|&(start, _, _)| start);
+
+        for (start, _, url) in found {
+            let offset = text[..start].chars().count();
+            let (row, col) = display_position(lines, width, line_idx, offset);
+            urls.push((row, col, url.to_string()));
+        }
+    }
+    urls
+}
+
 fn is_char_in_visual_selection(
     line_idx: usize,
     char_idx: usize,
@@ -49,22 +77,144 @@ fn is_char_in_visual_selection(
 pub fn refresh_detail_view_lines(app: &mut AppState) {
     if let Some(event_id) = &app.detail_view_event_id {
         if let Some(event) = app.events.get(event_id) {
-            app.detail_view_line_text = build_event_detail_lines(event);
+            let access = app.calendar_access_role(&event.calendar_id);
+            let lines = build_event_detail_lines(event, &app.timezone, access.as_ref(), app.detail_view_draft.as_deref(), app.map_provider, app.time_format);
+            app.detail_view_line_text = wrap_lines(&lines, app.detail_view_wrap_width);
         } else {
             app.detail_view_line_text.clear();
         }
+        sync_detail_scroll(app);
     }
 }
 
-pub fn build_event_detail_lines(event: &CalendarEvent) -> Vec<String> {
-    let mut lines = vec![event.title.clone(), String::new()];
+fn sync_detail_scroll(app: &mut AppState) {
+    let total_lines = app.detail_view_line_text.len();
+    let visible = app.detail_view_visible_lines;
+    let cursor_line = app.detail_view_cursor_line.min(total_lines.saturating_sub(1));
+    app.detail_view_cursor_line = cursor_line;
 
-    let date_line = if event.all_day {
-        format!("📅 {}", event.start.format("%A, %B %d, %Y"))
+    app.detail_view_scroll = if visible == 0 {
+        0
+    } else if cursor_line >= app.detail_view_scroll + visible {
+        cursor_line + 1 - visible
+    } else if cursor_line < app.detail_view_scroll {
+        cursor_line
     } else {
-        format!("📅 {} at {}", event.start.format("%A, %B %d, %Y"), event.start.format("%H:%M"))
+        app.detail_view_scroll
+    }.min(total_lines.saturating_sub(visible));
+}
+
+fn format_date_line(event: &CalendarEvent, tz: &DisplayTimezone, time_format: TimeFormat) -> String {
+    let start = event.local_start(tz);
+    if event.all_day {
+        return format!("📅 {}", start.format("%A, %B %d, %Y"));
+    }
+
+    let mut line = format!("📅 {} at {}", start.format("%A, %B %d, %Y"), time_format.format(start.time()));
+    if let Some(event_tz) = &event.time_zone
+        && tz.name().as_ref() != Some(event_tz)
+    {
+        line.push_str(&format!(" (event zone: {})", event_tz));
+    }
+    line
+}
+
+/// The description without its attached link, and the link itself when the
+/// Join line does not already show it.
+fn description_and_link(event: &CalendarEvent) -> (Option<String>, Option<String>) {
+    let Some(description) = &event.description else {
+        return (None, None);
     };
-    lines.push(date_line);
+    let (body, link) = split_link(description);
+    let link = link.filter(|link| event.meeting_url() != Some(link.as_str()));
+    ((!body.is_empty()).then_some(body), link)
+}
+
+/// The description as plain text, to start a draft from.
+pub fn description_draft(event: &CalendarEvent) -> String {
+    description_and_link(event).0
+        .map(|description| plain_text(&description).trim_end().to_string())
+        .unwrap_or_default()
+}
+
+/// True when a draft would lose the description's HTML formatting on save.
+pub fn description_is_html(event: &CalendarEvent) -> bool {
+    description_and_link(event).0.is_some_and(|description| looks_like_html(&description))
+}
+
+/// The draft is shown as typed; the event's own description may be HTML.
+fn description_lines(event: &CalendarEvent, draft: Option<&str>) -> Option<Vec<Line<'static>>> {
+    match draft {
+        Some(draft) => Some(draft.lines().map(|line| Line::raw(line.to_string())).collect()),
+        None => description_and_link(event).0.map(|description| description_to_lines(&description)),
+    }
+}
+
+/// Underlines markdown and plain links on top of the line's own styles.
+fn highlight_links(line: Line<'static>) -> Line<'static> {
+    let text = line_text(&line);
+    let link_style = Style::default().fg(Color::Blue).add_modifier(Modifier::UNDERLINED);
+    let ranges: Vec<(usize, usize)> = markdown_link_pattern().find_iter(&text)
+        .chain(plain_url_pattern().find_iter(&text))
+        .map(|m| (m.start(), m.end()))
+        .collect();
+    if ranges.is_empty() {
+        return line;
+    }
+
+    let mut spans: Vec<Span<'static>> = Vec::new();
+    let mut offset = 0;
+    for span in line.spans {
+        for ch in span.content.chars() {
+            let in_link = ranges.iter().any(|&(start, end)| offset >= start && offset < end);
+            let style = if in_link { span.style.patch(link_style) } else { span.style };
+            match spans.last_mut() {
+                Some(last) if last.style == style => last.content.to_mut().push(ch),
+                _ => spans.push(Span::styled(ch.to_string(), style)),
+            }
+            offset += ch.len_utf8();
+        }
+    }
+    Line::from(spans)
+}
+
+fn description_header(draft: bool) -> String {
+    if draft {
+        format!("{} (draft: E saves, u restores):", DESCRIPTION_HEADER)
+    } else {
+        format!("{}:", DESCRIPTION_HEADER)
+    }
+}
+
+/// Organizer, creator and whether the calendar can be edited, for the
+/// Sharing section. Empty when none of them are known.
+fn sharing_lines(event: &CalendarEvent, access: Option<&AccessRole>) -> Vec<String> {
+    let mut lines = Vec::new();
+    if let Some(organizer) = &event.organizer {
+        lines.push(format!("   Organizer: {}", organizer));
+    }
+    if let Some(creator) = &event.creator {
+        lines.push(format!("   Created by: {}", creator));
+    }
+    match access {
+        Some(role) if role.can_edit() => lines.push(format!("   You can edit ({})", role.label())),
+        Some(role) => lines.push(format!("   Read-only ({})", role.label())),
+        None => {}
+    }
+    lines
+}
+
+pub fn build_event_detail_lines(
+    event: &CalendarEvent,
+    tz: &DisplayTimezone,
+    access: Option<&AccessRole>,
+    draft: Option<&str>,
+    map_provider: MapProvider,
+    time_format: TimeFormat,
+) -> Vec<String> {
+    let mut lines = vec![event.title.clone(), String::new()];
+
+    lines.push(format_date_line(event, tz, time_format));
 
     if event.all_day {
         let duration_days = (event.end - event.start).num_days();
@@ -81,13 +231,24 @@ pub fn build_event_detail_lines(event: &CalendarEvent) -> Vec<String> {
         lines.push(duration_str);
     }
 
+    let (_, link) = description_and_link(event);
+    if let Some(url) = event.meeting_url() {
+        lines.extend([String::new(), format!("📹 Join: {}", url)]);
+    }
+    if let Some(link) = link {
+        lines.extend([String::new(), format!("🔗 Link: {}", link)]);
+    }
+
     if let Some(location) = &event.location {
         lines.extend([String::new(), "📍 Location:".to_string(), format!("   {}", location)]);
+        if let Some(url) = event.map_url(map_provider) {
+            lines.push(format!("   Map: {}", url));
+        }
     }
 
-    if let Some(description) = &event.description {
-        lines.extend([String::new(), "📝 Description:".to_string(), String::new()]);
-        lines.extend(strip_html(description).lines().map(String::from));
+    if let Some(description) = description_lines(event, draft) {
+        lines.extend([String::new(), description_header(draft.is_some()), String::new()]);
+        lines.extend(description.iter().map(line_text));
     }
 
     if !event.attendees.is_empty() {
@@ -95,15 +256,58 @@ pub fn build_event_detail_lines(event: &CalendarEvent) -> Vec<String> {
         lines.extend(event.attendees.iter().map(|a| format!("   • {}", a)));
     }
 
+    let sharing = sharing_lines(event, access);
+    if !sharing.is_empty() {
+        lines.extend([String::new(), "🔐 Sharing:".to_string()]);
+        lines.extend(sharing);
+    }
+
     lines.extend([
         String::new(),
-        "hjkl = Move | wbe = Word | 0^$ = Line | gG = Top/Bottom".to_string(),
-        "o = Open URL | y = Yank line | B = Browser | E = Edit | q/Esc = Close".to_string(),
+        "hjkl = Move | wbe = Word | 0^$ = Line | gg/G = Top/Bottom | ^d/^u = Half page".to_string(),
+        "o = Open URL | J = Join | y = Yank line | B = Browser | E = Edit | q/Esc = Close".to_string(),
     ]);
 
     lines
 }
 
+pub fn panel_area(area: Rect) -> Rect {
+    let panel_width = (area.width as f32 * 0.7) as u16;
+    let panel_height = (area.height as f32 * 0.8) as u16;
+
+    Rect {
+        x: (area.width.saturating_sub(panel_width)) / 2,
+        y: (area.height.saturating_sub(panel_height)) / 2,
+        width: panel_width,
+        height: panel_height,
+    }
+}
+
+pub fn wrap_width(area: Rect) -> usize {
+    panel_area(area).width.saturating_sub(2) as usize
+}
+
+pub fn visible_lines(area: Rect) -> usize {
+    panel_area(area).height.saturating_sub(2) as usize
+}
+
+fn wrap_styled_line(line: Line<'_>, width: usize) -> Vec<Line<'static>> {
+    let cells: Vec<(char, Style)> = line.spans.iter()
+        .flat_map(|span| span.content.chars().map(move |ch| (ch, span.style)))
+        .collect();
+    let text: String = cells.iter().map(|(ch, _)| ch).collect();
+    let offsets = wrap_offsets(&text, width);
+
+    offsets.iter().enumerate()
+        .map(|(row, &start)| {
+            let end = offsets.get(row + 1).copied().unwrap_or(cells.len());
+            Line::from(cells[start..end].iter()
+                .map(|(ch, style)| Span::styled(ch.to_string(), *style))
+                .collect::<Vec<_>>())
+        })
+        .collect()
+}
+
 pub fn render(f: &mut Frame, app: &AppState) {
     let Some(event_id) = &app.detail_view_event_id else {
         return;
@@ -113,18 +317,8 @@ pub fn render(f: &mut Frame, app: &AppState) {
         return;
     };
 
-    let area = f.size();
-    let panel_width = (area.width as f32 * 0.7) as u16;
-    let panel_height = (area.height as f32 * 0.8) as u16;
-    let x = (area.width.saturating_sub(panel_width)) / 2;
-    let y = (area.height.saturating_sub(panel_height)) / 2;
-
-    let panel_area = ratatui::layout::Rect {
-        x,
-        y,
-        width: panel_width,
-        height: panel_height,
-    };
+    let panel_area = panel_area(f.size());
+    let width = wrap_width(f.size());
 
     f.render_widget(Clear, panel_area);
 
@@ -133,11 +327,7 @@ pub fn render(f: &mut Frame, app: &AppState) {
         Line::from(""),
     ];
 
-    let start_str = if event.all_day {
-        format!("📅 {}", event.start.format("%A, %B %d, %Y"))
-    } else {
-        format!("📅 {} at {}", event.start.format("%A, %B %d, %Y"), event.start.format("%H:%M"))
-    };
+    let start_str = format_date_line(event, &app.timezone, app.time_format);
     lines.push(Line::from(vec![Span::styled(start_str, Style::default().fg(Color::Green))]));
 
     if event.all_day {
@@ -157,68 +347,45 @@ pub fn render(f: &mut Frame, app: &AppState) {
         lines.push(Line::from(vec![Span::styled(duration_str, Style::default().fg(Color::Yellow))]));
     }
 
+    let (_, link) = description_and_link(event);
+    if let Some(url) = event.meeting_url() {
+        lines.push(Line::from(""));
+        lines.push(Line::from(vec![
+            Span::styled("📹 Join: ", Style::default().fg(Color::Magenta).add_modifier(Modifier::BOLD)),
+            Span::styled(url.to_string(), Style::default().fg(Color::Blue).add_modifier(Modifier::UNDERLINED)),
+        ]));
+    }
+
+    if let Some(link) = link {
+        lines.push(Line::from(""));
+        lines.push(Line::from(vec![
+            Span::styled("🔗 Link: ", Style::default().fg(Color::Magenta).add_modifier(Modifier::BOLD)),
+            Span::styled(link, Style::default().fg(Color::Blue).add_modifier(Modifier::UNDERLINED)),
+        ]));
+    }
+
     if let Some(location) = &event.location {
         lines.push(Line::from(""));
         lines.push(Line::from(vec![
             Span::styled("📍 Location:", Style::default().fg(Color::Magenta).add_modifier(Modifier::BOLD))
         ]));
         lines.push(Line::from(vec![Span::raw(format!("   {}", location))]));
+        if let Some(url) = event.map_url(app.map_provider) {
+            lines.push(Line::from(vec![
+                Span::raw("   Map: "),
+                Span::styled(url, Style::default().fg(Color::Blue).add_modifier(Modifier::UNDERLINED)),
+            ]));
+        }
     }
 
-    if let Some(description) = &event.description {
+    if let Some(description) = description_lines(event, app.detail_view_draft.as_deref()) {
         lines.push(Line::from(""));
         lines.push(Line::from(vec![
-            Span::styled("📝 Description:", Style::default().fg(Color::Magenta).add_modifier(Modifier::BOLD))
+            Span::styled(description_header(app.detail_view_draft.is_some()), Style::default().fg(Color::Magenta).add_modifier(Modifier::BOLD))
         ]));
         lines.push(Line::from(""));
-
-        let clean_description = strip_html(description);
-
-        for line in clean_description.lines() {
-            let line_owned = line.to_string();
-            if line_owned.trim().is_empty() {
-                lines.push(Line::from(""));
-            } else {
-                let md_pattern = markdown_link_pattern();
-                let url_pattern = plain_url_pattern();
-                let mut spans = Vec::new();
-                let mut last_end = 0;
-
-                let markdown_captures: Vec<_> = md_pattern.captures_iter(&line_owned).collect();
-                for cap in markdown_captures {
-                    let Some(m) = cap.get(0) else { continue };
-                    if m.start() > last_end && m.start() <= line_owned.len() {
-                        spans.push(Span::raw(line_owned[last_end..m.start()].to_string()));
-                    }
-                    spans.push(Span::styled(m.as_str().to_string(), Style::default().fg(Color::Blue).add_modifier(Modifier::UNDERLINED)));
-                    last_end = m.end();
-                }
-
-                if last_end > line_owned.len() {
-                    last_end = line_owned.len();
-                }
-                let plain_captures: Vec<_> = url_pattern.captures_iter(&line_owned[last_end..]).collect();
-                for cap in plain_captures.iter() {
-                    let Some(m) = cap.get(0) else { continue };
-                    let abs_start = last_end + m.start();
-                    let abs_end = last_end + m.end();
-                    if abs_start > last_end && abs_start <= line_owned.len() {
-                        spans.push(Span::raw(line_owned[last_end..abs_start].to_string()));
-                    }
-                    spans.push(Span::styled(m.as_str().to_string(), Style::default().fg(Color::Blue).add_modifier(Modifier::UNDERLINED)));
-                    last_end = abs_end;
-                }
-
-                if last_end < line_owned.len() {
-                    spans.push(Span::raw(line_owned[last_end..].to_string()));
-                }
-
-                if spans.is_empty() {
-                    spans.push(Span::raw(line_owned));
-                }
-
-                lines.push(Line::from(spans));
-            }
+        for line in description {
+            lines.push(highlight_links(line));
         }
     }
 
@@ -232,6 +399,15 @@ pub fn render(f: &mut Frame, app: &AppState) {
         }
     }
 
+    let sharing = sharing_lines(event, app.calendar_access_role(&event.calendar_id).as_ref());
+    if !sharing.is_empty() {
+        lines.push(Line::from(""));
+        lines.push(Line::from(vec![
+            Span::styled("🔐 Sharing:", Style::default().fg(Color::Magenta).add_modifier(Modifier::BOLD))
+        ]));
+        lines.extend(sharing.into_iter().map(Line::from));
+    }
+
     lines.push(Line::from(""));
     lines.push(Line::from(vec![
         Span::styled("hjkl", Style::default().fg(Color::Cyan)),
@@ -240,12 +416,20 @@ pub fn render(f: &mut Frame, app: &AppState) {
         Span::raw(" = Word | "),
         Span::styled("0^$", Style::default().fg(Color::Cyan)),
         Span::raw(" = Line | "),
-        Span::styled("gG", Style::default().fg(Color::Cyan)),
-        Span::raw(" = Top/Bottom"),
+        Span::styled("gg/G", Style::default().fg(Color::Cyan)),
+        Span::raw(" = Top/Bottom | "),
+        Span::styled("^d/^u", Style::default().fg(Color::Cyan)),
+        Span::raw(" = Half page"),
     ]));
     lines.push(Line::from(vec![
         Span::styled("o", Style::default().fg(Color::Magenta)),
         Span::raw(" = Open URL | "),
+        Span::styled("f", Style::default().fg(Color::Magenta)),
+        Span::raw(" = Hints | "),
+        Span::styled("J", Style::default().fg(Color::Magenta)),
+        Span::raw(" = Join | "),
+        Span::styled("M", Style::default().fg(Color::Magenta)),
+        Span::raw(" = Map | "),
         Span::styled("y", Style::default().fg(Color::Yellow)),
         Span::raw(" = Yank line | "),
         Span::styled("B", Style::default().fg(Color::Blue)),
@@ -258,7 +442,11 @@ pub fn render(f: &mut Frame, app: &AppState) {
         Span::raw(" = Close"),
     ]));
 
-    let visible_lines_count = panel_height.saturating_sub(2) as usize;
+    let lines: Vec<Line> = lines.into_iter()
+        .flat_map(|line| wrap_styled_line(line, width))
+        .collect();
+
+    let visible_lines_count = visible_lines(f.size());
     let total_lines = lines.len();
 
     let cursor_line = app.detail_view_cursor_line.min(total_lines.saturating_sub(1));
@@ -286,6 +474,12 @@ pub fn render(f: &mut Frame, app: &AppState) {
         (None, None)
     };
 
+    let hint_style = Style::default().bg(Color::Yellow).fg(Color::Black).add_modifier(Modifier::BOLD);
+    let hint_cells: HashMap<(usize, usize), char> = app.detail_view_hints.iter()
+        .flat_map(|hints| hints.visible())
+        .flat_map(|hint| hint.label.chars().enumerate().map(move |(i, c)| ((hint.line, hint.col + i), c)))
+        .collect();
+
     let lines_with_cursor: Vec<Line> = lines.into_iter()
         .enumerate()
         .skip(scroll_start)
@@ -303,6 +497,11 @@ pub fn render(f: &mut Frame, app: &AppState) {
                     let is_cursor = line_idx == cursor_line && global_char_idx == cursor_col;
                     let is_in_visual = is_char_in_visual_selection(line_idx, global_char_idx, visual_start, visual_end);
 
+                    if let Some(&label) = hint_cells.get(&(line_idx, global_char_idx)) {
+                        new_spans.push(Span::styled(label.to_string(), hint_style));
+                        continue;
+                    }
+
                     let style = if is_cursor {
                         span.style.bg(Color::White).fg(Color::Black)
                     } else if is_in_visual {
@@ -330,8 +529,7 @@ pub fn render(f: &mut Frame, app: &AppState) {
             .borders(Borders::ALL)
             .title(" Event Details ")
             .style(Style::default().bg(Color::Black)))
-        .alignment(Alignment::Left)
-        .wrap(ratatui::widgets::Wrap { trim: false });
+        .alignment(Alignment::Left);
 
     f.render_widget(detail_paragraph, panel_area);
 }
@@ -1,4 +1,81 @@
+use std::sync::OnceLock;
+use base64::{engine::general_purpose::STANDARD, Engine};
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum ClipboardBackend {
+    #[default]
+    Auto,
+    Command,
+    Osc52,
+    Arboard,
+}
+
+impl ClipboardBackend {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.trim().to_lowercase().as_str() {
+            "" | "auto" => Some(Self::Auto),
+            "command" | "system" => Some(Self::Command),
+            "osc52" => Some(Self::Osc52),
+            "arboard" => Some(Self::Arboard),
+            _ => None,
+        }
+    }
+
+    pub fn from_config(name: Option<&str>) -> Self {
+        match name {
+            Some(name) => Self::from_name(name).unwrap_or_else(|| {
+                tracing::warn!("Unknown clipboard backend '{}', using auto", name);
+                Self::Auto
+            }),
+            None => Self::Auto,
+        }
+    }
+}
+
+static BACKEND: OnceLock<ClipboardBackend> = OnceLock::new();
+
+pub fn set_backend(backend: ClipboardBackend) {
+    if BACKEND.set(backend).is_err() {
+        tracing::warn!("Clipboard backend already configured");
+    }
+}
+
+fn backend() -> ClipboardBackend {
+    BACKEND.get().copied().unwrap_or_default()
+}
+
+fn is_remote_session() -> bool {
+    std::env::var_os("SSH_TTY").is_some() || std::env::var_os("SSH_CONNECTION").is_some()
+}
+
 pub fn paste_from_clipboard() -> Result<String, String> {
+    match backend() {
+        ClipboardBackend::Command => paste_with_command(),
+        ClipboardBackend::Arboard => paste_with_arboard(),
+        ClipboardBackend::Osc52 => Err("Pasting is not supported by the OSC 52 clipboard".to_string()),
+        ClipboardBackend::Auto => paste_with_command().or_else(|_| paste_with_arboard()),
+    }
+}
+
+pub fn copy_to_clipboard(text: &str) -> Result<(), String> {
+    let result = match backend() {
+        ClipboardBackend::Command => copy_with_command(text),
+        ClipboardBackend::Arboard => copy_with_arboard(text),
+        ClipboardBackend::Osc52 => copy_with_osc52(text),
+        ClipboardBackend::Auto if is_remote_session() => copy_with_osc52(text),
+        ClipboardBackend::Auto => copy_with_command(text)
+            .or_else(|_| copy_with_arboard(text))
+            .or_else(|_| copy_with_osc52(text)),
+    };
+
+    match &result {
+        Ok(()) => tracing::info!("Copied {} bytes to clipboard", text.len()),
+        Err(e) => tracing::error!("{}", e),
+    }
+    result
+}
+
+fn paste_with_command() -> Result<String, String> {
     use std::process::{Command, Stdio};
 
     let output = Command::new("wl-paste")
@@ -23,7 +100,7 @@ pub fn paste_from_clipboard() -> Result<String, String> {
     }
 }
 
-pub fn copy_to_clipboard(text: &str) -> Result<(), String> {
+fn copy_with_command(text: &str) -> Result<(), String> {
     use std::process::{Command, Stdio};
     use std::io::Write;
 
@@ -45,14 +122,78 @@ pub fn copy_to_clipboard(text: &str) -> Result<(), String> {
                 stdin.write_all(text.as_bytes())
                     .map_err(|e| format!("Failed to write to clipboard: {}", e))?;
             }
-            child.wait().map_err(|e| format!("Clipboard command failed: {}", e))?;
-            tracing::info!("Copied {} bytes to clipboard", text.len());
-            Ok(())
-        }
-        Err(e) => {
-            let err = format!("No clipboard tool found (wl-copy/xclip/xsel): {}", e);
-            tracing::error!("{}", err);
-            Err(err)
+            let status = child.wait().map_err(|e| format!("Clipboard command failed: {}", e))?;
+            if status.success() {
+                Ok(())
+            } else {
+                Err(format!("Clipboard command failed: {:?}", status))
+            }
         }
+        Err(e) => Err(format!("No clipboard tool found (wl-copy/xclip/xsel): {}", e)),
+    }
+}
+
+#[cfg(feature = "arboard")]
+fn copy_with_arboard(text: &str) -> Result<(), String> {
+    arboard::Clipboard::new()
+        .and_then(|mut clipboard| clipboard.set_text(text))
+        .map_err(|e| format!("arboard clipboard failed: {}", e))
+}
+
+#[cfg(not(feature = "arboard"))]
+fn copy_with_arboard(_text: &str) -> Result<(), String> {
+    Err("gcal-imp was built without the arboard feature".to_string())
+}
+
+#[cfg(feature = "arboard")]
+fn paste_with_arboard() -> Result<String, String> {
+    arboard::Clipboard::new()
+        .and_then(|mut clipboard| clipboard.get_text())
+        .map_err(|e| format!("arboard clipboard failed: {}", e))
+}
+
+#[cfg(not(feature = "arboard"))]
+fn paste_with_arboard() -> Result<String, String> {
+    Err("gcal-imp was built without the arboard feature".to_string())
+}
+
+fn copy_with_osc52(text: &str) -> Result<(), String> {
+    use std::io::Write;
+
+    let in_tmux = std::env::var_os("TMUX").is_some();
+    let mut stdout = std::io::stdout();
+    stdout.write_all(osc52_sequence(text, in_tmux).as_bytes())
+        .and_then(|_| stdout.flush())
+        .map_err(|e| format!("Failed to write OSC 52 sequence: {}", e))
+}
+
+fn osc52_sequence(text: &str, in_tmux: bool) -> String {
+    let sequence = format!("\x1b]52;c;{}\x07", STANDARD.encode(text));
+    if in_tmux {
+        format!("\x1bPtmux;{}\x1b\\", sequence.replace('\x1b', "\x1b\x1b"))
+    } else {
+        sequence
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_backend_names() {
+        assert_eq!(ClipboardBackend::from_name("OSC52"), Some(ClipboardBackend::Osc52));
+        assert_eq!(ClipboardBackend::from_name("system"), Some(ClipboardBackend::Command));
+        assert_eq!(ClipboardBackend::from_config(Some("carrier-pigeon")), ClipboardBackend::Auto);
+    }
+
+    #[test]
+    fn osc52_sequence_encodes_text() {
+        assert_eq!(osc52_sequence("hi", false), "\x1b]52;c;aGk=\x07");
+    }
+
+    #[test]
+    fn osc52_sequence_is_wrapped_for_tmux() {
+        assert_eq!(osc52_sequence("hi", true), "\x1bPtmux;\x1b\x1b]52;c;aGk=\x07\x1b\\");
     }
 }
@@ -2,9 +2,15 @@ mod authentication;
 mod session;
 mod presentation;
 mod sample_events;
+mod setup_wizard;
 mod calendar_views;
 mod dialogs;
 mod event_detail;
+mod mouse;
+mod terminal_guard;
+pub mod view_export;
+#[cfg(test)]
+mod snapshots;
 
 pub use authentication::check_or_setup_auth;
 pub use session::run_tui;
@@ -0,0 +1,151 @@
+use crossterm::event::{MouseButton, MouseEvent, MouseEventKind};
+use gcal_imp::{
+    app::{AppState, Mode, ViewType},
+    input::normal_mode,
+};
+use ratatui::layout::Rect;
+
+use super::{calendar_views, presentation::screen_areas};
+
+pub fn handle_mouse(event: MouseEvent, app: &mut AppState, screen: Rect) {
+    if app.mode != Mode::Normal || app.show_help {
+        return;
+    }
+
+    if app.detail_view_event_id.is_some() {
+        scroll_detail_view(event.kind, app);
+        return;
+    }
+
+    match event.kind {
+        MouseEventKind::Down(MouseButton::Left) => handle_click(app, screen, event.column, event.row),
+        MouseEventKind::ScrollDown => scroll_calendar(app, 1),
+        MouseEventKind::ScrollUp => scroll_calendar(app, -1),
+        _ => {}
+    }
+}
+
+fn handle_click(app: &mut AppState, screen: Rect, column: u16, row: u16) {
+    let areas = screen_areas(screen, &app.view);
+    let mini_calendar = areas.mini_calendar.filter(|&area| contains(area, column, row));
+
+    if contains(areas.calendar, column, row) || mini_calendar.is_some() {
+        let date = if let Some(area) = mini_calendar {
+            calendar_views::mini_month::date_at(app, area, column, row)
+        } else {
+            match app.view {
+                ViewType::Month | ViewType::Split => calendar_views::month::date_at(app, areas.calendar, column, row),
+                ViewType::Week => calendar_views::week::date_at(app, areas.calendar, column, row),
+                ViewType::Year => calendar_views::year::date_at(app, areas.calendar, column, row),
+                ViewType::Day => None,
+            }
+        };
+
+        if let Some(date) = date
+            && date != app.selected_date
+        {
+            app.selected_date = date;
+            app.reset_event_selection();
+        }
+    } else if !app.show_tasks
+        && app.view != ViewType::Split
+        && contains(areas.event_list, column, row)
+        && let Some(idx) = calendar_views::event_list::event_index_at(app, areas.event_list, row)
+    {
+        app.selected_event_index = idx;
+        normal_mode::open_event_detail_view(app);
+    }
+}
+
+fn scroll_calendar(app: &mut AppState, direction: i32) {
+    match app.view {
+        ViewType::Month | ViewType::Year | ViewType::Split => normal_mode::change_month(app, direction),
+        ViewType::Week if direction > 0 => normal_mode::move_down_week(app),
+        ViewType::Week => normal_mode::move_up_week(app),
+        ViewType::Day if direction > 0 => normal_mode::move_next_day(app),
+        ViewType::Day => normal_mode::move_previous_day(app),
+    }
+}
+
+fn scroll_detail_view(kind: MouseEventKind, app: &mut AppState) {
+    let last_line = app.detail_view_line_text.len().saturating_sub(1);
+    match kind {
+        MouseEventKind::ScrollDown => {
+            app.detail_view_cursor_line = (app.detail_view_cursor_line + 1).min(last_line);
+        }
+        MouseEventKind::ScrollUp => {
+            app.detail_view_cursor_line = app.detail_view_cursor_line.saturating_sub(1);
+        }
+        _ => return,
+    }
+    let line_len = app.detail_view_line_text
+        .get(app.detail_view_cursor_line)
+        .map_or(0, |l| l.chars().count());
+    app.detail_view_cursor_col = app.detail_view_cursor_col.min(line_len.saturating_sub(1));
+}
+
+fn contains(area: Rect, column: u16, row: u16) -> bool {
+    column >= area.x && column < area.x + area.width && row >= area.y && row < area.y + area.height
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+    use crossterm::event::KeyModifiers;
+
+    use crate::tui::snapshots::fixture;
+
+    const SCREEN: Rect = Rect { x: 0, y: 0, width: 100, height: 36 };
+
+    fn click(app: &mut AppState, column: u16, row: u16) {
+        let event = MouseEvent { kind: MouseEventKind::Down(MouseButton::Left), column, row, modifiers: KeyModifiers::NONE };
+        handle_mouse(event, app, SCREEN);
+    }
+
+    fn january(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2025, 1, day).unwrap()
+    }
+
+    #[test]
+    fn month_clicks_pick_the_day_under_the_pointer() {
+        let mut app = fixture(ViewType::Month);
+        let calendar = screen_areas(SCREEN, &app.view).calendar;
+
+        // The third week row holds 13-19; its fifth cell is Friday the 17th.
+        click(&mut app, calendar.x + 1 + 4 * 5 + 2, calendar.y + 1 + 3 + 2);
+        assert_eq!(app.selected_date, january(17));
+
+        // The weekday headers are not a date.
+        click(&mut app, calendar.x + 2, calendar.y + 3);
+        assert_eq!(app.selected_date, january(17));
+    }
+
+    #[test]
+    fn week_clicks_pick_the_column_under_the_pointer() {
+        let mut app = fixture(ViewType::Week);
+        let calendar = screen_areas(SCREEN, &app.view).calendar;
+
+        // Past the six-column gutter, each day is seven columns wide.
+        click(&mut app, calendar.x + 1 + 6 + 4 * 7 + 3, calendar.y + 8);
+        assert_eq!(app.selected_date, january(17));
+
+        click(&mut app, calendar.x + 3, calendar.y + 8);
+        assert_eq!(app.selected_date, january(17));
+    }
+
+    #[test]
+    fn day_clicks_open_the_event_in_the_list() {
+        let mut app = fixture(ViewType::Day);
+        let areas = screen_areas(SCREEN, &app.view);
+
+        click(&mut app, areas.calendar.x + 5, areas.calendar.y + 5);
+        assert_eq!(app.selected_date, january(15));
+        assert_eq!(app.detail_view_event_id, None);
+
+        // The standup takes three lines with its location, so the workshop
+        // starts on the fourth.
+        click(&mut app, areas.event_list.x + 3, areas.event_list.y + 1 + 2 + 3);
+        assert_eq!(app.detail_view_event_id.as_deref(), Some("workshop"));
+    }
+}
@@ -1,13 +1,25 @@
 use ratatui::{
-    layout::{Alignment, Constraint, Direction, Layout},
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
     style::{Modifier, Style},
     widgets::{Block, Borders, Paragraph},
     Frame,
 };
-use gcal_imp::app::{AppState, ViewType, Mode};
+use gcal_imp::{app::{AppState, MessageKind, ViewType, Mode}, ui::statusline};
 use crate::tui::{calendar_views, dialogs, event_detail};
 
-pub fn ui(f: &mut Frame, app: &AppState) {
+pub struct ScreenAreas {
+    pub title: Rect,
+    pub calendar: Rect,
+    pub event_list: Rect,
+    /// Above the event list in day and week view, when it fits.
+    pub mini_calendar: Option<Rect>,
+    pub status: Rect,
+}
+
+/// Event lines the list keeps below the mini calendar.
+const MIN_EVENT_LIST_HEIGHT: u16 = 8;
+
+pub fn screen_areas(area: Rect, view: &ViewType) -> ScreenAreas {
     let main_chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
@@ -15,7 +27,7 @@ pub fn ui(f: &mut Frame, app: &AppState) {
             Constraint::Min(0),
             Constraint::Length(3),
         ])
-        .split(f.size());
+        .split(area);
 
     let content_chunks = Layout::default()
         .direction(Direction::Horizontal)
@@ -25,51 +37,116 @@ pub fn ui(f: &mut Frame, app: &AppState) {
         ])
         .split(main_chunks[1]);
 
-    let chunks = [main_chunks[0], content_chunks[0], content_chunks[1], main_chunks[2]];
+    let side = content_chunks[1];
+    let (mini_calendar, event_list) = if matches!(view, ViewType::Day | ViewType::Week)
+        && side.width >= calendar_views::mini_month::WIDTH
+        && side.height >= calendar_views::mini_month::HEIGHT + MIN_EVENT_LIST_HEIGHT
+    {
+        let side_chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(calendar_views::mini_month::HEIGHT), Constraint::Min(0)])
+            .split(side);
+        (Some(side_chunks[0]), side_chunks[1])
+    } else {
+        (None, side)
+    };
+
+    ScreenAreas {
+        title: main_chunks[0],
+        calendar: content_chunks[0],
+        event_list,
+        mini_calendar,
+        status: main_chunks[2],
+    }
+}
+
+/// The month, week, day or year grid, without the panes around it.
+pub fn render_calendar(f: &mut Frame, app: &AppState, area: Rect) {
+    match app.view {
+        ViewType::Month | ViewType::Split => calendar_views::month::render(f, app, area),
+        ViewType::Week => calendar_views::week::render(f, app, area),
+        ViewType::Day => calendar_views::day::render(f, app, area),
+        ViewType::Year => calendar_views::year::render(f, app, area),
+    }
+}
+
+pub fn ui(f: &mut Frame, app: &AppState) {
+    let areas = screen_areas(f.size(), &app.view);
 
-    let title_text = format!("gcal-imp - {} View - {:?} Mode",
-        match app.view {
-            ViewType::Month => "Month",
-            ViewType::Week => "Week",
-            ViewType::Day => "Day",
-            ViewType::Year => "Year",
-        },
-        app.mode
-    );
+    let title_text = format!("gcal-imp - {} View - {:?} Mode", app.view.name(), app.mode);
 
     let title = Paragraph::new(title_text)
         .style(Style::default().fg(app.theme.title).add_modifier(Modifier::BOLD))
         .alignment(Alignment::Center)
         .block(Block::default().borders(Borders::ALL));
-    f.render_widget(title, chunks[0]);
+    f.render_widget(title, areas.title);
 
-    match app.view {
-        ViewType::Month => calendar_views::month::render(f, app, chunks[1]),
-        ViewType::Week => calendar_views::week::render(f, app, chunks[1]),
-        ViewType::Day => calendar_views::day::render(f, app, chunks[1]),
-        ViewType::Year => calendar_views::year::render(f, app, chunks[1]),
+    render_calendar(f, app, areas.calendar);
+
+    if let Some(area) = areas.mini_calendar {
+        calendar_views::mini_month::render(f, app, area);
     }
 
-    calendar_views::event_list::render(f, app, chunks[2]);
+    if app.show_tasks {
+        calendar_views::tasks::render(f, app, areas.event_list);
+    } else if app.view == ViewType::Split {
+        calendar_views::day::render(f, app, areas.event_list);
+    } else {
+        calendar_views::event_list::render(f, app, areas.event_list);
+    }
 
     let status_text = if matches!(app.mode, Mode::Command) {
         app.command_buffer.to_string()
+    } else if let Some(message) = &app.status_message {
+        message.text.clone()
     } else {
-        format!("Events: {} | Sync: {:?} | Press 'q' to quit, '?' for help",
-            app.events.len(), app.sync_status)
+        statusline::render(app.ui_config.statusline.as_deref().unwrap_or(statusline::DEFAULT_FORMAT), app)
     };
 
     let status_color = if matches!(app.mode, Mode::Command) {
         app.theme.command_mode
     } else {
-        app.theme.status_bar
+        match app.status_message.as_ref().map(|message| message.kind) {
+            Some(MessageKind::Error) => app.theme.error,
+            Some(MessageKind::Success) => app.theme.success,
+            None if !app.sync.is_busy() && app.sync.is_stale(app.now()) => app.theme.error,
+            Some(MessageKind::Info) | None => app.theme.status_bar,
+        }
     };
 
     let status = Paragraph::new(status_text)
         .style(Style::default().fg(status_color))
         .alignment(if matches!(app.mode, Mode::Command) { Alignment::Left } else { Alignment::Center })
         .block(Block::default().borders(Borders::ALL));
-    f.render_widget(status, chunks[3]);
+    f.render_widget(status, areas.status);
+
+    if !app.free_slots.is_empty() {
+        dialogs::free_slots::render(f, app);
+    }
+
+    if app.show_calendar_list {
+        dialogs::calendar_list::render(f, app);
+    }
+
+    if app.show_sync_status {
+        dialogs::sync_status::render(f, app);
+    }
+
+    if app.report.is_some() {
+        dialogs::report::render(f, app);
+    }
+
+    if app.travel_report.is_some() {
+        dialogs::travel_report::render(f, app);
+    }
+
+    if app.show_registers {
+        dialogs::registers::render(f, app);
+    }
+
+    if app.show_activity {
+        dialogs::activity::render(f, app);
+    }
 
     if app.show_help {
         dialogs::help::render(f, app);
@@ -79,6 +156,22 @@ pub fn ui(f: &mut Frame, app: &AppState) {
         dialogs::event_form::render(f, app);
     }
 
+    if !app.pending_changes.is_empty() {
+        dialogs::update_diff::render(f, app);
+    }
+
+    if !app.sync_conflicts.is_empty() {
+        dialogs::sync_conflict::render(f, app);
+    }
+
+    if !app.conflict_event_ids.is_empty() {
+        dialogs::conflict_warning::render(f, app);
+    }
+
+    if !app.bulk_delete_event_ids.is_empty() {
+        dialogs::delete_confirmation::render_bulk(f, app);
+    }
+
     if app.delete_confirmation_event_id.is_some() {
         dialogs::delete_confirmation::render(f, app);
     }
@@ -86,4 +179,8 @@ pub fn ui(f: &mut Frame, app: &AppState) {
     if app.detail_view_event_id.is_some() {
         event_detail::presentation::render(f, app);
     }
+
+    if app.error_report.is_some() {
+        dialogs::error::render(f, app);
+    }
 }
@@ -1,14 +1,20 @@
-use chrono::{Local, TimeZone, Utc};
+use chrono::{DateTime, Utc};
 use gcal_imp::{
     app::AppState,
-    calendar::{Event as CalendarEvent, EventStatus, DEFAULT_CALENDAR_ID},
+    calendar::{DisplayTimezone, Event as CalendarEvent, EventStatus, Transparency, Visibility, DEFAULT_CALENDAR_ID},
 };
 
 pub fn add_sample_events(app: &mut AppState) {
-    let today = Local::now().date_naive();
+    for event in sample_events(&app.timezone, app.now()) {
+        app.add_event(event);
+    }
+}
 
-    let Some(tomorrow) = today.succ_opt() else { return };
-    let Some(yesterday) = today.pred_opt() else { return };
+/// A few events around `now`, also used to seed the mock backend.
+pub fn sample_events(timezone: &DisplayTimezone, now: DateTime<Utc>) -> Vec<CalendarEvent> {
+    let today = timezone.date_of(&now);
+    let Some(tomorrow) = today.succ_opt() else { return Vec::new() };
+    let Some(yesterday) = today.pred_opt() else { return Vec::new() };
 
     let events = vec![
         ("Morning Standup", today, 9, 0, 9, 30, None),
@@ -19,18 +25,13 @@ pub fn add_sample_events(app: &mut AppState) {
         ("Lunch with Team", yesterday, 12, 30, 13, 30, Some("Downtown Cafe")),
     ];
 
+    let mut sample = Vec::new();
     for (i, (title, date, start_h, start_m, end_h, end_m, location)) in events.into_iter().enumerate() {
         let Some(start_time) = date.and_hms_opt(start_h, start_m, 0) else { continue };
         let Some(end_time) = date.and_hms_opt(end_h, end_m, 0) else { continue };
 
-        let start = match Utc.from_local_datetime(&start_time) {
-            chrono::LocalResult::Single(dt) => dt,
-            _ => continue,
-        };
-        let end = match Utc.from_local_datetime(&end_time) {
-            chrono::LocalResult::Single(dt) => dt,
-            _ => continue,
-        };
+        let Some(start) = timezone.from_local(&start_time) else { continue };
+        let Some(end) = timezone.from_local(&end_time) else { continue };
 
         let event = CalendarEvent {
             id: format!("sample_{}", i),
@@ -44,10 +45,22 @@ pub fn add_sample_events(app: &mut AppState) {
             attendees: vec![],
             reminders: vec![],
             status: EventStatus::Confirmed,
-            last_modified: Utc::now(),
+            last_modified: now,
             html_link: None,
+            time_zone: None,
+            conference_url: None,
+            color_id: None,
+            color: None,
+            self_response: None,
+            attendee_responses: Default::default(),
+            visibility: Visibility::Default,
+            transparency: Transparency::Opaque,
+            etag: None,
+            organizer: None,
+            creator: None,
         };
 
-        app.add_event(event);
+        sample.push(event);
     }
+    sample
 }
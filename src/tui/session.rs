@@ -1,103 +1,210 @@
-use std::io;
+use std::collections::VecDeque;
+use std::io::{self, Write};
 use std::sync::OnceLock;
+use std::time::{Duration, Instant};
+use chrono::NaiveDate;
 use crossterm::{
-    event::{self, DisableMouseCapture, EnableMouseCapture, Event as TermEvent, KeyCode, KeyEventKind},
-    execute,
-    terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
+    event::{self, Event as TermEvent, KeyCode, KeyEvent, KeyEventKind, KeyModifiers, MouseEventKind},
 };
 use ratatui::{
     backend::CrosstermBackend,
     Terminal,
 };
 use regex::Regex;
+use tokio::task::JoinHandle;
 use uuid::Uuid;
 use gcal_imp::{
-    app::{AppState, Mode, SyncStatus, EventForm},
-    storage::config::Config,
-    sync::sync_engine::SyncEngine,
-    ui::theme::Theme,
-    input::{normal_mode, command_mode, insert_mode, visual_mode},
-    calendar::{Event as CalendarEvent, EventStatus, DEFAULT_CALENDAR_ID},
+    error::{AppError, ErrorReport},
+    report::TitlePattern,
+    app::{AppState, DeclinedEvents, EventForm, FormField, MessageKind, Mode, RepeatableEdit, ViewType},
+    storage::{cache::Cache, config::{Config, ConfigError}, contacts::Contacts, crypto::KeySource, paths, session_state::SessionState},
+    sync::{conflict::{resolve_conflict, ResolutionStrategy}, mock_api::{ApiBackend, MockCalendarApi}, push::PushListener, sync_engine::{BatchOutcome, SyncEngine, SyncError}, sync_state::SyncPhase},
+    ui::{theme::Theme, url_hints::{HintMatch, UrlHints}},
+    util::{clock::FixedClock, logging, opener::open_url},
+    input::{normal_mode, calendar_list, command_mode, command_history::CommandHistory, insert_mode, key_script::KeyScript, macros::MacroAction, registers::Register, slot_picker, task_pane, visual_mode},
+    calendar::{event::{join_link, split_link}, event_diff, free_slots::find_free_slots, quick_add::parse_quick_add, Event as CalendarEvent},
 };
 use crate::tui::{
-    presentation::ui,
-    sample_events::add_sample_events,
+    calendar_views::event_list,
+    mouse::handle_mouse,
+    terminal_guard::{with_terminal_suspended, TerminalGuard},
+    view_export::write_view,
+    presentation::{screen_areas, ui},
+    sample_events::{add_sample_events, sample_events},
     event_detail::{
-        presentation::refresh_detail_view_lines,
+        presentation::{build_event_detail_lines, description_draft, description_is_html, detail_urls, refresh_detail_view_lines, visible_lines as detail_visible_lines, wrap_width as detail_wrap_width, DESCRIPTION_HEADER},
+        description_draft::{cut, cut_line, draft_position},
+        line_wrapping::logical_line_index,
         navigation::{next_word_position, prev_word_position, word_end_position, last_char_index, find_first_non_whitespace},
-        text_selection::{copy_to_clipboard, paste_from_clipboard},
-        content_formatting::strip_html,
+        text_selection::{self, ClipboardBackend, copy_to_clipboard, paste_from_clipboard},
     },
 };
 
+/// How long to wait for input before redrawing, so time-dependent widgets such
+/// as the "now" line stay current while the app is idle.
+const TICK_RATE: Duration = Duration::from_secs(30);
+/// Input poll interval while a background window fetch is running.
+const WINDOW_POLL_RATE: Duration = Duration::from_millis(100);
+/// Input poll interval while push notifications may arrive.
+const PUSH_POLL_RATE: Duration = Duration::from_secs(1);
+/// Time of day the clock is pinned to when a script sets `@date`.
+const REPLAY_TIME: chrono::NaiveTime = chrono::NaiveTime::from_hms_opt(9, 0, 0).unwrap();
+/// Keys one `@` may replay, nested macros included, before it is taken to
+/// be calling itself.
+const MAX_MACRO_KEYS: usize = 10_000;
+
+/// Events for a date window being fetched on a background task.
+struct PendingWindow {
+    start: NaiveDate,
+    end: NaiveDate,
+    handle: JoinHandle<Result<Vec<Vec<CalendarEvent>>, SyncError>>,
+}
+
 fn build_event_from_form(
     id: String,
+    calendar_id: String,
     form: &EventForm,
     start: chrono::DateTime<chrono::Utc>,
     end: chrono::DateTime<chrono::Utc>,
-    all_day: bool,
     html_link: Option<String>,
+    now: chrono::DateTime<chrono::Utc>,
 ) -> CalendarEvent {
     CalendarEvent {
         id,
-        calendar_id: DEFAULT_CALENDAR_ID.to_string(),
+        calendar_id,
         title: form.title.clone(),
-        description: (!form.description.is_empty()).then(|| form.description.clone()),
+        description: form.description_with_link(),
         location: (!form.location.is_empty()).then(|| form.location.clone()),
         start,
         end,
-        all_day,
-        attendees: vec![],
+        all_day: form.all_day,
+        attendees: form.attendee_list(),
         reminders: vec![],
-        status: EventStatus::Confirmed,
-        last_modified: chrono::Utc::now(),
+        status: form.status.clone(),
+        last_modified: now,
         html_link,
+        time_zone: None,
+        conference_url: None,
+        color_id: None,
+        color: None,
+        self_response: None,
+        attendee_responses: Default::default(),
+        visibility: form.visibility,
+        transparency: form.transparency,
+        etag: None,
+        organizer: None,
+        creator: None,
     }
 }
 
-pub async fn run_tui(sample: bool) -> Result<(), io::Error> {
-    let config = Config::load_or_create()
-        .map_err(|e| io::Error::other(e.to_string()))?;
+pub async fn run_tui(sample: bool, read_only: bool, mock: bool, script: Option<KeyScript>) -> Result<(), AppError> {
+    let config = Config::load_or_create()?;
+    let mock = mock || ApiBackend::from_config(config.sync.backend.as_deref()) == ApiBackend::Mock;
 
-    enable_raw_mode()?;
-    let mut stdout = io::stdout();
-    execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
-    let backend = CrosstermBackend::new(stdout);
+    let guard = TerminalGuard::enter()?;
+    let backend = CrosstermBackend::new(io::stdout());
     let mut terminal = Terminal::new(backend)?;
 
-    let theme = Theme::get_by_name(&config.ui.theme);
-    let mut app = AppState::new().with_theme(theme);
+    text_selection::set_backend(ClipboardBackend::from_config(config.ui.clipboard.as_deref()));
+
+    // Mock events must not mix with the real calendar's cache.
+    let keys = KeySource::from_config(&config.security);
+    let cache = if mock {
+        None
+    } else {
+        Cache::open(&config.cache_path())
+            .map(|cache| cache.with_keys(keys.clone()))
+            .inspect(|cache| if let Err(e) = cache.seal_plain_rows() {
+                tracing::warn!("Failed to encrypt cached events: {}", e);
+            })
+            .inspect_err(|e| tracing::warn!("Failed to open the local cache: {}", e))
+            .ok()
+    };
+    // Replays start from the config alone and leave the user's files alone.
+    let replaying = script.is_some();
+    let mut app = AppState::new()
+        .with_replaying(replaying)
+        .with_ui_config(&config.ui)
+        .with_stale_after(config.sync.stale_after_minutes)
+        .with_command_history(if replaying { CommandHistory::default() } else { load_command_history() })
+        .with_contacts(if mock || replaying { Contacts::default() } else { load_contacts(&keys) })
+        .with_calendar_names(configured_calendars(&config))
+        .with_report_patterns(TitlePattern::from_config(&config.report.patterns))
+        .with_default_calendar(config.calendars.default.clone())
+        .with_visible_calendars(&config.calendars.visible);
+
+    // Replays run at a fixed time on the script's date, so they look the same on any day.
+    if let Some(at) = script.as_ref()
+        .and_then(|script| script.date)
+        .and_then(|date| app.timezone.from_local(&date.and_time(REPLAY_TIME)))
+    {
+        app = app.with_clock(FixedClock(at));
+    }
+
+    // Everything but a replay resumes the last session.
+    app.view = ViewType::from_config(&config.ui.default_view);
+    if !replaying {
+        restore_session(&mut app);
+    }
 
-    let mut sync_engine = SyncEngine::new(config);
+    let mock_failure_every = config.sync.mock_failure_every;
+    let mut sync_engine = SyncEngine::new(config).with_read_only(read_only);
+    if let Some(cache) = cache {
+        sync_engine = sync_engine.with_cache(cache);
+    }
+    if mock {
+        let mock_api = MockCalendarApi::new().with_failure_every(mock_failure_every);
+        mock_api.add_events(sample_events(&app.timezone, app.now()));
+        sync_engine = sync_engine.with_mock(mock_api);
+    }
+    app.read_only = sync_engine.is_read_only();
+    app.sync.last_sync = sync_engine.last_sync();
 
-    app.sync_status = SyncStatus::Syncing;
+    app.sync.phase = SyncPhase::Fetching;
     terminal.draw(|f| ui(f, &app)).ok();
 
     if sample {
         add_sample_events(&mut app);
     }
 
-    match sync_engine.fetch_events_around_date(app.selected_date).await {
+    match sync_engine.fetch_calendars().await {
+        Ok(calendars) => app.add_calendars(calendars),
+        Err(e) => tracing::warn!("Failed to fetch calendar list: {}", e),
+    }
+
+    match fetch_with_progress(&mut app, &mut terminal, &mut sync_engine).await {
         Ok(events) => {
-            for event in events {
+            for event in events.iter().cloned() {
                 app.add_event(event);
             }
-            app.sync_status = SyncStatus::Synced;
-        }
-        Err(e) => {
-            app.sync_status = SyncStatus::Error(format!("Sync failed: {}", e));
+            reconcile_local_edits(&mut app, &mut sync_engine, &events).await;
+            finish_fetch(&mut app, &sync_engine);
         }
+        Err(e) => report_error(&mut app, "Sync failed", e),
     }
 
-    let res = run_app(&mut terminal, &mut app, sync_engine).await;
+    let mut push = if sample || replaying { None } else { start_push_sync(&mut sync_engine).await };
+    let mut script = script.map(|script| script.keys.into_iter());
+    let res = run_app(&mut terminal, &mut app, &mut sync_engine, &mut push, &mut script).await;
+    if !replaying
+        && let Err(e) = SessionState::from_app(&app).save(&SessionState::default_path())
+    {
+        tracing::warn!("Failed to save session state: {}", e);
+    }
+    flush_queued_updates(&mut app, &mut sync_engine, true).await;
+    // Attendees of the loaded events were recorded as they were added.
+    if !(mock || sample || replaying)
+        && let Err(e) = app.contacts.save(&Contacts::default_path(), &keys)
+    {
+        tracing::warn!("Failed to save contacts: {}", e);
+    }
+    if let Some(channel) = push.and_then(|listener| listener.channel)
+        && let Err(e) = sync_engine.stop_watch(&channel).await
+    {
+        tracing::warn!("Failed to stop push channel {}: {}", channel.id, e);
+    }
 
-    disable_raw_mode()?;
-    execute!(
-        terminal.backend_mut(),
-        LeaveAlternateScreen,
-        DisableMouseCapture
-    )?;
-    terminal.show_cursor()?;
+    drop(guard);
 
     if let Err(err) = res {
         println!("Error: {:?}", err);
@@ -106,56 +213,589 @@ pub async fn run_tui(sample: bool) -> Result<(), io::Error> {
     Ok(())
 }
 
+fn restore_session(app: &mut AppState) {
+    match SessionState::load(&SessionState::default_path()) {
+        Ok(Some(state)) => state.apply(app),
+        Ok(None) => {}
+        Err(e) => tracing::warn!("Failed to load session state: {}", e),
+    }
+}
+
+fn load_command_history() -> CommandHistory {
+    CommandHistory::load(&CommandHistory::default_path()).unwrap_or_else(|e| {
+        tracing::warn!("Failed to load command history: {}", e);
+        CommandHistory::default()
+    })
+}
+
+fn load_contacts(keys: &KeySource) -> Contacts {
+    Contacts::load(&Contacts::default_path(), keys).unwrap_or_else(|e| {
+        tracing::warn!("Failed to load contacts: {}", e);
+        Contacts::default()
+    })
+}
+
+fn configured_calendars(config: &Config) -> Vec<String> {
+    let mut names = config.calendars.visible.clone();
+    names.push(config.calendars.default.clone());
+    names.extend(config.calendars.overlays.iter().cloned());
+    names
+}
+
+async fn open_tasks_pane<B: ratatui::backend::Backend>(
+    app: &mut AppState,
+    terminal: &mut Terminal<B>,
+    sync_engine: &mut SyncEngine,
+) -> io::Result<()> {
+    if !sync_engine.tasks_enabled() {
+        app.set_message(MessageKind::Error, "Tasks are off; set sync.tasks = true and sign in again");
+        return Ok(());
+    }
+
+    app.sync.phase = SyncPhase::Fetching;
+    terminal.draw(|f| ui(f, app))?;
+
+    match sync_engine.fetch_tasks_due(app.today()).await {
+        Ok(tasks) => {
+            app.tasks = tasks;
+            app.task_index = 0;
+            app.show_tasks = true;
+            app.sync.phase = SyncPhase::Idle;
+        }
+        Err(e) => {
+            report_error(app, "Failed to fetch tasks", e);
+        }
+    }
+    Ok(())
+}
+
+async fn complete_selected_task<B: ratatui::backend::Backend>(
+    app: &mut AppState,
+    terminal: &mut Terminal<B>,
+    sync_engine: &mut SyncEngine,
+) -> io::Result<()> {
+    let Some(task) = app.selected_task().cloned() else {
+        return Ok(());
+    };
+    if skipped_in_read_only(app, "task completion") {
+        return Ok(());
+    }
+
+    app.sync.phase = SyncPhase::Saving;
+    terminal.draw(|f| ui(f, app))?;
+
+    match sync_engine.complete_task(&task).await {
+        Ok(()) => {
+            app.remove_task(&task.id);
+            app.sync.phase = SyncPhase::Idle;
+            app.set_message(MessageKind::Success, format!("Completed \"{}\"", task.title));
+        }
+        Err(e) => {
+            report_error(app, "Failed to complete task", e);
+        }
+    }
+    Ok(())
+}
+
+async fn start_window_fetch(
+    app: &mut AppState,
+    sync_engine: &mut SyncEngine,
+    start: NaiveDate,
+    end: NaiveDate,
+) -> Option<PendingWindow> {
+    match sync_engine.window_fetch(start, end).await {
+        Ok(fetch) => {
+            app.sync.start_fetch(sync_engine.sync_calendars().len());
+            app.needs_redraw = true;
+            Some(PendingWindow { start, end, handle: tokio::spawn(fetch) })
+        }
+        Err(e) => {
+            sync_engine.mark_fetched(start, end);
+            set_background_sync_error(app, e);
+            app.needs_redraw = true;
+            None
+        }
+    }
+}
+
+async fn finish_window_fetch(app: &mut AppState, sync_engine: &mut SyncEngine, pending: PendingWindow) {
+    sync_engine.mark_fetched(pending.start, pending.end);
+    app.needs_redraw = true;
+    match pending.handle.await {
+        Ok(Ok(batches)) => {
+            for events in batches {
+                app.sync.calendar_fetched(events.len());
+                for event in events {
+                    app.add_event(event);
+                }
+            }
+            finish_fetch(app, sync_engine);
+        }
+        Ok(Err(e)) => set_background_sync_error(app, e),
+        Err(e) => {
+            tracing::warn!("Window fetch task failed: {}", e);
+            set_background_sync_error(app, e);
+        }
+    }
+}
+
+/// Fetches the user did not ask for fail quietly: a status bar note and an
+/// activity entry instead of the error dialog.
+fn set_background_sync_error(app: &mut AppState, error: impl std::fmt::Display) {
+    let text = format!("Sync failed: {}", error);
+    app.log_activity(MessageKind::Error, text.clone());
+    app.sync.phase = SyncPhase::Error(text);
+}
+
+/// Starts the local receiver and registers a channel when push sync is configured.
+async fn start_push_sync(sync_engine: &mut SyncEngine) -> Option<PushListener> {
+    let (address, port) = sync_engine.push_target()?;
+    let address = address.to_string();
+    let mut listener = PushListener::start(port).await
+        .inspect_err(|e| tracing::warn!("Failed to start the push receiver on port {}: {}", port, e))
+        .ok()?;
+    listener.channel = sync_engine.watch_default_calendar(&address).await
+        .inspect_err(|e| tracing::warn!("Failed to register a push channel: {}", e))
+        .ok();
+    Some(listener)
+}
+
+/// Refetches only the calendar a push notification was for, over the window
+/// around the selected date, instead of a full sync.
+async fn refresh_pushed_calendar(app: &mut AppState, sync_engine: &mut SyncEngine, calendar_id: &str) {
+    flush_queued_updates(app, sync_engine, true).await;
+    let (start, end) = sync_engine.window_around(app.selected_date);
+    match sync_engine.fetch_calendar(calendar_id, start, end).await {
+        Ok(events) => {
+            app.log_activity(MessageKind::Info, format!("Pushed change: fetched {} events from {}", events.len(), calendar_id));
+            app.replace_calendar_events(calendar_id, start, end, events);
+            sync_engine.record_sync(app.now());
+        }
+        Err(e) => set_background_sync_error(app, e),
+    }
+}
+
+/// Registers a replacement before the current channel expires; push sync
+/// stops if that fails.
+async fn renew_push_channel(listener: &mut PushListener, sync_engine: &mut SyncEngine) {
+    let Some(address) = sync_engine.push_target().map(|(address, _)| address.to_string()) else {
+        return;
+    };
+    let renewed = sync_engine.watch_default_calendar(&address).await
+        .inspect_err(|e| tracing::warn!("Failed to renew the push channel: {}", e))
+        .ok();
+    if let Some(old) = std::mem::replace(&mut listener.channel, renewed)
+        && let Err(e) = sync_engine.stop_watch(&old).await
+    {
+        tracing::warn!("Failed to stop push channel {}: {}", old.id, e);
+    }
+}
+
+fn finish_fetch(app: &mut AppState, sync_engine: &SyncEngine) {
+    let now = app.now();
+    app.sync.fetch_finished(now);
+    sync_engine.record_sync(now);
+    let text = format!("Fetched {} events from {} calendars", app.sync.events_fetched, app.sync.calendars_total);
+    app.log_activity(MessageKind::Info, text);
+}
+
+/// Fetches the window around the selected date one calendar at a time,
+/// drawing the progress in between. Only the default calendar failing fails
+/// the sync; overlays are extras.
+async fn fetch_with_progress<B: ratatui::backend::Backend>(
+    app: &mut AppState,
+    terminal: &mut Terminal<B>,
+    sync_engine: &mut SyncEngine,
+) -> Result<Vec<CalendarEvent>, SyncError> {
+    let (start, end) = sync_engine.window_around(app.selected_date);
+    let calendars = sync_engine.sync_calendars();
+    app.sync.start_fetch(calendars.len());
+    terminal.draw(|f| ui(f, app)).ok();
+
+    let mut events = Vec::new();
+    for (index, calendar_id) in calendars.iter().enumerate() {
+        match sync_engine.fetch_calendar(calendar_id, start, end).await {
+            Ok(fetched) => {
+                app.sync.calendar_fetched(fetched.len());
+                events.extend(fetched);
+            }
+            Err(e) if index == 0 => return Err(e),
+            Err(e) => {
+                tracing::warn!("Failed to fetch overlay calendar {}: {}", calendar_id, e);
+                app.log_activity(MessageKind::Error, format!("Failed to fetch overlay {}: {}", calendar_id, e));
+                app.sync.calendar_fetched(0);
+            }
+        }
+        terminal.draw(|f| ui(f, app)).ok();
+    }
+
+    sync_engine.reset_loaded(start, end);
+    Ok(events)
+}
+
+async fn sync_events<B: ratatui::backend::Backend>(
+    app: &mut AppState,
+    terminal: &mut Terminal<B>,
+    sync_engine: &mut SyncEngine,
+) -> io::Result<()> {
+    // Queued edits go out first, or the fetch would overwrite them.
+    flush_queued_updates(app, sync_engine, true).await;
+    match fetch_with_progress(app, terminal, sync_engine).await {
+        Ok(events) => {
+            app.clear_events();
+            let count = events.len();
+            for event in events.iter().cloned() {
+                app.add_event(event);
+            }
+            reconcile_local_edits(app, sync_engine, &events).await;
+            finish_fetch(app, sync_engine);
+            app.set_message(MessageKind::Success, format!("Synced {} events", count));
+        }
+        Err(e) => {
+            report_error(app, "Sync failed", e);
+        }
+    }
+    Ok(())
+}
+
+/// Pushes kept local edits whose events did not change on the server and
+/// queues the rest for the conflict dialog.
+async fn reconcile_local_edits(app: &mut AppState, sync_engine: &mut SyncEngine, remote: &[CalendarEvent]) {
+    let reconciliation = match sync_engine.reconcile_local_edits(remote) {
+        Ok(reconciliation) => reconciliation,
+        Err(e) => {
+            tracing::warn!("Failed to read local edits: {}", e);
+            return;
+        }
+    };
+
+    // Read-only mode leaves kept edits in the cache for later.
+    let to_push = if sync_engine.is_read_only() { Vec::new() } else { reconciliation.to_push };
+    for mut event in to_push {
+        match sync_engine.update_event(&mut event).await {
+            Ok(()) => {
+                if let Err(e) = sync_engine.forget_local_edit(&event.id) {
+                    tracing::warn!("Failed to clear local edit {}: {}", event.id, e);
+                }
+            }
+            Err(e) => tracing::warn!("Failed to push local edit {}: {}", event.id, e),
+        }
+        app.add_event(event);
+    }
+    app.sync_conflicts = reconciliation.conflicts;
+}
+
+/// Applies the chosen side of the first sync conflict: the server version
+/// replaces the local edit, anything else is pushed over it.
+async fn resolve_sync_conflict<B: ratatui::backend::Backend>(
+    app: &mut AppState,
+    terminal: &mut Terminal<B>,
+    sync_engine: &mut SyncEngine,
+    strategy: ResolutionStrategy,
+) -> io::Result<()> {
+    if app.sync_conflicts.is_empty()
+        || (strategy != ResolutionStrategy::ServerWins && skipped_in_read_only(app, "resolution"))
+    {
+        return Ok(());
+    }
+    let conflict = app.sync_conflicts.remove(0);
+
+    if strategy == ResolutionStrategy::ServerWins {
+        if let Err(e) = sync_engine.forget_local_edit(&conflict.event_id) {
+            tracing::warn!("Failed to clear local edit {}: {}", conflict.event_id, e);
+        }
+        app.add_event(conflict.remote);
+        app.set_message(MessageKind::Success, "Kept the server version");
+        return Ok(());
+    }
+
+    let mut resolved = resolve_conflict(&conflict.local, &conflict.remote, strategy);
+    app.sync.phase = SyncPhase::Saving;
+    terminal.draw(|f| ui(f, app))?;
+
+    match sync_engine.update_event(&mut resolved).await {
+        Ok(()) => {
+            if let Err(e) = sync_engine.forget_local_edit(&resolved.id) {
+                tracing::warn!("Failed to clear local edit {}: {}", resolved.id, e);
+            }
+            app.sync.phase = SyncPhase::Idle;
+            app.set_message(MessageKind::Success, "Conflict resolved");
+        }
+        Err(e) => {
+            // Rebase the kept edit on the version it was resolved against.
+            if let Err(e) = sync_engine.keep_local_edit(&resolved, conflict.remote.last_modified) {
+                tracing::warn!("Failed to keep local edit {}: {}", resolved.id, e);
+            }
+            report_error(app, "Failed to update", e);
+        }
+    }
+    app.add_event(resolved);
+    Ok(())
+}
+
+async fn handle_sync_conflict_keys<B: ratatui::backend::Backend>(
+    code: KeyCode,
+    app: &mut AppState,
+    terminal: &mut Terminal<B>,
+    sync_engine: &mut SyncEngine,
+) -> io::Result<()> {
+    let strategy = match code {
+        KeyCode::Char('s') => ResolutionStrategy::ServerWins,
+        KeyCode::Char('l') => ResolutionStrategy::LocalWins,
+        KeyCode::Char('m') => ResolutionStrategy::Merge,
+        KeyCode::Esc => {
+            // Undecided edits stay cached and come back on the next sync.
+            app.sync_conflicts.clear();
+            return Ok(());
+        }
+        _ => return Ok(()),
+    };
+    resolve_sync_conflict(app, terminal, sync_engine, strategy).await
+}
+
+/// Adds the overlay to config, checked in the calendar list so it stays
+/// visible after a restart.
+fn save_overlay(calendar_id: &str) -> Result<(), ConfigError> {
+    let mut config = Config::load_or_create()?;
+    if !config.calendars.overlays.iter().any(|id| id == calendar_id) {
+        config.calendars.overlays.push(calendar_id.to_string());
+        config.calendars.visible.push(calendar_id.to_string());
+        config.save()?;
+    }
+    Ok(())
+}
+
+/// Writes the calendar list's checkboxes back to `calendars.visible`.
+fn save_visible_calendars(app: &mut AppState) {
+    if app.replaying {
+        return;
+    }
+    let result = Config::load_or_create().and_then(|mut config| {
+        config.calendars.visible = app.visible_calendars();
+        config.save()
+    });
+    if let Err(e) = result {
+        app.set_message(MessageKind::Error, format!("Failed to save calendars: {}", e));
+    }
+}
+
 async fn run_app<B: ratatui::backend::Backend>(
     terminal: &mut Terminal<B>,
     app: &mut AppState,
-    mut sync_engine: SyncEngine,
+    sync_engine: &mut SyncEngine,
+    push: &mut Option<PushListener>,
+    script: &mut Option<std::vec::IntoIter<KeyEvent>>,
 ) -> io::Result<()> {
+    let mut pending_window: Option<PendingWindow> = None;
+    let replaying = script.is_some();
+    let mut macro_keys: VecDeque<KeyEvent> = VecDeque::new();
+    let mut macro_keys_played = 0;
+    let mut last_draw = Instant::now();
+
     loop {
-        if app.detail_view_event_id.is_some() {
-            refresh_detail_view_lines(app);
-        } else if !app.detail_view_line_text.is_empty() {
-            app.detail_view_line_text.clear();
+        if sync_engine.has_queued_updates() {
+            flush_queued_updates(app, sync_engine, replaying).await;
+        }
+        if let Some(listener) = push.as_mut() {
+            if listener.channel.as_ref().is_some_and(|c| c.needs_renewal(app.now())) {
+                renew_push_channel(listener, sync_engine).await;
+            }
+            if app.mode == Mode::Normal && app.event_form.is_none() && listener.take_change()
+                && let Some(calendar_id) = listener.channel.as_ref().map(|c| c.calendar_id.clone())
+            {
+                refresh_pushed_calendar(app, sync_engine, &calendar_id).await;
+                app.needs_redraw = true;
+            }
         }
 
-        terminal.draw(|f| ui(f, app))?;
-
-        if let TermEvent::Key(key) = event::read()?
-            && key.kind == KeyEventKind::Press
+        if let Some(pending) = pending_window.take_if(|p| p.handle.is_finished()) {
+            finish_window_fetch(app, sync_engine, pending).await;
+        }
+        if pending_window.is_none()
+            && let Some((start, end)) = sync_engine.next_window(app.selected_date)
         {
-            match app.mode {
-                Mode::Normal => {
-                    if app.show_help {
-                        handle_help_keys(key.code, app);
-                    } else if app.detail_view_event_id.is_some() {
-                        if handle_detail_view_keys(key.code, app)? {
-                            return Ok(());
-                        }
-                    } else {
-                        match key.code {
-                            KeyCode::Char('q') => return Ok(()),
-                            _ => normal_mode::handle_key(key.code, app),
-                        }
+            pending_window = start_window_fetch(app, sync_engine, start, end).await;
+        }
+        // A replay waits for each fetch so every run sees the same events.
+        if replaying
+            && let Some(pending) = pending_window.take()
+        {
+            finish_window_fetch(app, sync_engine, pending).await;
+        }
+
+        if app.needs_redraw {
+            if app.detail_view_event_id.is_some() {
+                let size = terminal.size()?;
+                app.detail_view_wrap_width = detail_wrap_width(size);
+                app.detail_view_visible_lines = detail_visible_lines(size);
+                refresh_detail_view_lines(app);
+            } else if !app.detail_view_line_text.is_empty() {
+                app.detail_view_line_text.clear();
+            }
+            app.event_list_visible_lines = event_list::visible_event_lines(screen_areas(terminal.size()?, &app.view).event_list);
+            app.sync_event_list_scroll();
+
+            terminal.draw(|f| ui(f, app))?;
+            app.needs_redraw = false;
+            last_draw = Instant::now();
+        }
+
+        let until_tick = TICK_RATE.saturating_sub(last_draw.elapsed());
+        let timeout = until_tick.min(if pending_window.is_some() || sync_engine.has_queued_updates() {
+            WINDOW_POLL_RATE
+        } else if push.as_ref().is_some_and(|listener| listener.channel.is_some()) {
+            PUSH_POLL_RATE
+        } else {
+            TICK_RATE
+        });
+        let from_macro = macro_keys.pop_front();
+        if from_macro.is_none() {
+            macro_keys_played = 0;
+        }
+        let key = if let Some(key) = from_macro {
+            key
+        } else if let Some(keys) = script.as_mut() {
+            match keys.next() {
+                Some(key) => key,
+                None => return Ok(()),
+            }
+        } else {
+            // Fetches and pushes mark what they change; an idle poll only
+            // redraws once a tick has passed.
+            if !event::poll(timeout)? {
+                if last_draw.elapsed() >= TICK_RATE {
+                    app.needs_redraw = true;
+                }
+                continue;
+            }
+
+            match event::read()? {
+                TermEvent::Key(key) if key.kind == KeyEventKind::Press => key,
+                TermEvent::Mouse(mouse) => {
+                    if matches!(mouse.kind, MouseEventKind::Down(_) | MouseEventKind::ScrollUp | MouseEventKind::ScrollDown) {
+                        let size = terminal.size()?;
+                        handle_mouse(mouse, app, size);
+                        app.needs_redraw = true;
                     }
+                    continue;
                 }
-                Mode::Command => {
-                    if handle_command_mode(key.code, app, terminal, &mut sync_engine).await? {
-                        return Ok(());
+                TermEvent::Resize(..) => {
+                    app.needs_redraw = true;
+                    continue;
+                }
+                // A terminal paste arrives whole, so a newline in it cannot submit the form
+                // or run the command.
+                TermEvent::Paste(text) => {
+                    if app.mode == Mode::Insert && app.pending_changes.is_empty() && app.conflict_event_ids.is_empty() {
+                        insert_mode::paste(app, &text);
+                        app.needs_redraw = true;
+                    } else if app.mode == Mode::Command {
+                        command_mode::paste(app, &text);
+                        app.needs_redraw = true;
                     }
+                    continue;
                 }
-                Mode::Insert => {
-                    if handle_insert_mode(key.code, app, terminal, &mut sync_engine).await? {
+                _ => continue,
+            }
+        };
+
+        app.needs_redraw = true;
+        app.clear_message();
+        if from_macro.is_none() {
+            app.macros.record(key);
+        }
+
+        if app.error_report.is_some() {
+            if matches!(key.code, KeyCode::Esc | KeyCode::Enter | KeyCode::Char('q')) {
+                app.error_report = None;
+            }
+            continue;
+        }
+
+        match app.mode {
+            Mode::Normal => {
+                if !app.sync_conflicts.is_empty() {
+                    handle_sync_conflict_keys(key.code, app, terminal, sync_engine).await?;
+                } else if app.show_activity {
+                    handle_activity_keys(key.code, app);
+                } else if app.show_sync_status {
+                    if matches!(key.code, KeyCode::Esc | KeyCode::Char('q') | KeyCode::Enter) {
+                        app.show_sync_status = false;
+                    }
+                } else if app.report.is_some() {
+                    if matches!(key.code, KeyCode::Esc | KeyCode::Char('q') | KeyCode::Enter) {
+                        app.report = None;
+                    }
+                } else if app.travel_report.is_some() {
+                    if matches!(key.code, KeyCode::Esc | KeyCode::Char('q') | KeyCode::Enter) {
+                        app.travel_report = None;
+                    }
+                } else if app.show_registers {
+                    if matches!(key.code, KeyCode::Esc | KeyCode::Char('q') | KeyCode::Enter) {
+                        app.show_registers = false;
+                    }
+                } else if app.show_help {
+                    handle_help_keys(key.code, app);
+                } else if !app.free_slots.is_empty() {
+                    slot_picker::handle_key(key.code, app);
+                } else if app.show_tasks {
+                    if task_pane::handle_key(key.code, app) {
+                        complete_selected_task(app, terminal, sync_engine).await?;
+                    }
+                } else if app.show_calendar_list {
+                    if calendar_list::handle_key(key.code, app) {
+                        save_visible_calendars(app);
+                    }
+                } else if app.detail_view_event_id.is_some() {
+                    if !app.awaiting_register && matches!(key.code, KeyCode::Char('I') | KeyCode::Char('A')) {
+                        edit_description(app, terminal, sync_engine, key.code == KeyCode::Char('A')).await?;
+                    } else if handle_detail_view_keys(key, app)? {
                         return Ok(());
                     }
-                }
-                Mode::Visual => {
-                    if app.delete_confirmation_event_id.is_some() {
-                        handle_delete_confirmation(key.code, app, terminal, &mut sync_engine).await?;
-                    } else {
-                        visual_mode::handle_key(key.code, app);
+                } else if !app.awaiting_register && app.macros.wants(&key) {
+                    match app.macros.handle_key(key) {
+                        MacroAction::Consumed => {}
+                        MacroAction::Message(text) => app.set_message(MessageKind::Info, text),
+                        MacroAction::Replay(keys) if macro_keys_played + keys.len() > MAX_MACRO_KEYS => {
+                            macro_keys.clear();
+                            app.set_message(MessageKind::Error, "Macro stopped: it keeps replaying itself");
+                        }
+                        MacroAction::Replay(keys) => {
+                            macro_keys_played += keys.len();
+                            for key in keys.into_iter().rev() {
+                                macro_keys.push_front(key);
+                            }
+                        }
+                    }
+                } else {
+                    match key.code {
+                        KeyCode::Char('q') => return Ok(()),
+                        KeyCode::Char('J') => handle_join_meeting(app.meeting_event(app.now())),
+                        KeyCode::Char('T') => open_tasks_pane(app, terminal, sync_engine).await?,
+                        KeyCode::Char('.') => repeat_last_edit(app, terminal, sync_engine).await?,
+                        _ => normal_mode::handle_key(key.code, app),
                     }
                 }
             }
+            Mode::Command => {
+                if handle_command_mode(key.code, app, terminal, sync_engine).await? {
+                    return Ok(());
+                }
+            }
+            Mode::Insert => {
+                if handle_insert_mode(key, app, terminal, sync_engine).await? {
+                    return Ok(());
+                }
+            }
+            Mode::Visual => {
+                if !app.bulk_delete_event_ids.is_empty() {
+                    handle_bulk_delete_confirmation(key.code, app, terminal, sync_engine).await?;
+                } else if app.delete_confirmation_event_id.is_some() {
+                    handle_delete_confirmation(key.code, app, terminal, sync_engine).await?;
+                } else {
+                    visual_mode::handle_key(key.code, app);
+                }
+            }
         }
     }
 }
@@ -176,9 +816,60 @@ fn handle_help_keys(code: KeyCode, app: &mut AppState) {
     }
 }
 
-fn handle_detail_view_keys(code: KeyCode, app: &mut AppState) -> io::Result<bool> {
+fn handle_activity_keys(code: KeyCode, app: &mut AppState) {
     match code {
+        KeyCode::Char('j') | KeyCode::Down => {
+            app.activity_scroll = (app.activity_scroll + 1).min(app.activity.len().saturating_sub(1));
+        }
+        KeyCode::Char('k') | KeyCode::Up => {
+            app.activity_scroll = app.activity_scroll.saturating_sub(1);
+        }
+        KeyCode::Char('q') | KeyCode::Esc => {
+            app.show_activity = false;
+            app.activity_scroll = 0;
+        }
+        _ => {}
+    }
+}
+
+fn scroll_detail_half_page(app: &mut AppState, down: bool) {
+    let half = (app.detail_view_visible_lines / 2).max(1);
+    let last_line = app.detail_view_line_text.len().saturating_sub(1);
+
+    if down {
+        app.detail_view_cursor_line = (app.detail_view_cursor_line + half).min(last_line);
+        app.detail_view_scroll += half;
+    } else {
+        app.detail_view_cursor_line = app.detail_view_cursor_line.saturating_sub(half);
+        app.detail_view_scroll = app.detail_view_scroll.saturating_sub(half);
+    }
+}
+
+fn handle_detail_view_keys(key: KeyEvent, app: &mut AppState) -> io::Result<bool> {
+    let pending_g = std::mem::take(&mut app.detail_view_pending_g);
+    if app.detail_view_hints.is_some() {
+        handle_hint_key(key, app);
+        return Ok(false);
+    }
+    if normal_mode::take_register_name(key.code, app) {
+        return Ok(false);
+    }
+    let register = app.pending_register.take();
+
+    if key.modifiers.contains(KeyModifiers::CONTROL) {
+        match key.code {
+            KeyCode::Char('d') => scroll_detail_half_page(app, true),
+            KeyCode::Char('u') => scroll_detail_half_page(app, false),
+            _ => {}
+        }
+        return Ok(false);
+    }
+
+    match key.code {
         KeyCode::Esc => {
+            if app.detail_view_draft.take().is_some() {
+                app.set_message(MessageKind::Info, "Description draft discarded");
+            }
             app.detail_view_event_id = None;
             app.detail_view_scroll = 0;
             app.detail_view_cursor_line = 0;
@@ -255,20 +946,38 @@ fn handle_detail_view_keys(code: KeyCode, app: &mut AppState) -> io::Result<bool
             }
             Ok(false)
         }
-        KeyCode::Char('g') => {
+        KeyCode::Char('g') if pending_g => {
             app.detail_view_cursor_line = 0;
+            app.detail_view_cursor_col = app.detail_view_line_text.first()
+                .map_or(0, |line| find_first_non_whitespace(line));
             app.detail_view_scroll = 0;
             Ok(false)
         }
+        KeyCode::Char('g') => {
+            app.detail_view_pending_g = true;
+            Ok(false)
+        }
         KeyCode::Char('G') => {
-            app.detail_view_cursor_line = 999;
+            app.detail_view_cursor_line = app.detail_view_line_text.len().saturating_sub(1);
+            app.detail_view_cursor_col = app.detail_view_line_text.last()
+                .map_or(0, |line| find_first_non_whitespace(line));
             Ok(false)
         }
         KeyCode::Char('E') => {
             if let Some(event_id) = &app.detail_view_event_id
                 && let Some(event) = app.events.get(event_id).cloned()
+                && !app.refuse_read_only_calendar(&event.calendar_id)
             {
-                app.event_form = Some(EventForm::for_event(&event));
+                let mut form = EventForm::for_event(&event, &app.timezone);
+                if let Some(draft) = app.detail_view_draft.take() {
+                    form.description_cursor = draft.chars().count();
+                    form.description = draft;
+                    form.active_field = FormField::Description;
+                    if description_is_html(&event) {
+                        app.set_message(MessageKind::Error, "Saving replaces the description's HTML formatting with this plain text");
+                    }
+                }
+                app.event_form = Some(form);
                 app.mode = Mode::Insert;
                 app.detail_view_event_id = None;
                 app.detail_view_scroll = 0;
@@ -278,9 +987,10 @@ fn handle_detail_view_keys(code: KeyCode, app: &mut AppState) -> io::Result<bool
             Ok(false)
         }
         KeyCode::Char('a') => {
-            app.event_form = Some(EventForm::new(app.selected_date, String::new()));
+            app.event_form = Some(EventForm::new(app.selected_date, String::new(), app.local_now()));
             app.mode = Mode::Insert;
             app.detail_view_event_id = None;
+            app.detail_view_draft = None;
             app.detail_view_scroll = 0;
             app.detail_view_cursor_line = 0;
             app.detail_view_cursor_col = 0;
@@ -291,8 +1001,26 @@ fn handle_detail_view_keys(code: KeyCode, app: &mut AppState) -> io::Result<bool
             handle_open_url(app);
             Ok(false)
         }
+        KeyCode::Char('f') => {
+            show_url_hints(app);
+            Ok(false)
+        }
+        KeyCode::Char('"') => {
+            app.awaiting_register = true;
+            Ok(false)
+        }
         KeyCode::Char('y') => {
-            handle_yank(app);
+            handle_yank(app, register);
+            Ok(false)
+        }
+        KeyCode::Char('d') => {
+            handle_cut(app, register);
+            Ok(false)
+        }
+        KeyCode::Char('u') => {
+            if app.detail_view_draft.take().is_some() {
+                app.set_message(MessageKind::Info, "Description restored");
+            }
             Ok(false)
         }
         KeyCode::Char('p') => {
@@ -308,10 +1036,18 @@ fn handle_detail_view_keys(code: KeyCode, app: &mut AppState) -> io::Result<bool
             }
             Ok(false)
         }
+        KeyCode::Char('J') => {
+            handle_join_meeting(app.detail_view_event_id.as_ref().and_then(|id| app.events.get(id)));
+            Ok(false)
+        }
         KeyCode::Char('B') => {
             handle_open_browser(app);
             Ok(false)
         }
+        KeyCode::Char('M') => {
+            handle_open_map(app);
+            Ok(false)
+        }
         _ => Ok(false)
     }
 }
@@ -321,47 +1057,11 @@ fn handle_open_url(app: &AppState) {
     if let Some(event_id) = &app.detail_view_event_id
         && let Some(event) = app.events.get(event_id)
     {
-        let mut all_lines = Vec::new();
+        let access = app.calendar_access_role(&event.calendar_id);
+        let all_lines = build_event_detail_lines(event, &app.timezone, access.as_ref(), app.detail_view_draft.as_deref(), app.map_provider, app.time_format);
 
-        all_lines.push(event.title.clone());
-        all_lines.push(String::new());
-
-        if event.all_day {
-            all_lines.push(format!("📅 {}", event.start.format("%A, %B %d, %Y")));
-        } else {
-            all_lines.push(format!("📅 {} at {}", event.start.format("%A, %B %d, %Y"), event.start.format("%H:%M")));
-        }
-
-        if event.all_day {
-            let duration_days = (event.end - event.start).num_days();
-            if duration_days > 1 {
-                all_lines.push(format!("⏱  {} days", duration_days));
-            }
-        } else {
-            let duration = event.duration_minutes();
-            if duration >= 60 {
-                all_lines.push(format!("⏱  {} hour{} {} min", duration / 60, if duration / 60 > 1 { "s" } else { "" }, duration % 60));
-            } else {
-                all_lines.push(format!("⏱  {} minutes", duration));
-            }
-        }
-
-        if let Some(location) = &event.location {
-            all_lines.extend([String::new(), "📍 Location:".to_string(), format!("   {}", location)]);
-        }
-
-        if let Some(desc) = &event.description {
-            all_lines.extend([String::new(), "📝 Description:".to_string(), String::new()]);
-            all_lines.extend(strip_html(desc).lines().map(String::from));
-        }
-
-        if !event.attendees.is_empty() {
-            all_lines.extend([String::new(), "👥 Attendees:".to_string()]);
-            all_lines.extend(event.attendees.iter().map(|a| format!("   • {}", a)));
-        }
-
-        if app.detail_view_cursor_line < all_lines.len() {
-            let line_text = &all_lines[app.detail_view_cursor_line];
+        if let Some(line_idx) = logical_line_index(&all_lines, app.detail_view_wrap_width, app.detail_view_cursor_line) {
+            let line_text = &all_lines[line_idx];
 
             static MARKDOWN_LINK_RE: OnceLock<Regex> = OnceLock::new();
             static PLAIN_URL_RE: OnceLock<Regex> = OnceLock::new();
@@ -382,8 +1082,8 @@ fn handle_open_url(app: &AppState) {
 
             if let Some(url) = url_to_open {
                 tracing::info!("Opening URL: {}", url);
-                match std::process::Command::new("xdg-open").arg(url).spawn() {
-                    Ok(_) => tracing::info!("Successfully launched xdg-open"),
+                match open_url(url) {
+                    Ok(()) => tracing::info!("Successfully launched URL opener"),
                     Err(e) => tracing::error!("Failed to open URL: {}", e),
                 }
             } else {
@@ -393,7 +1093,50 @@ fn handle_open_url(app: &AppState) {
     }
 }
 
-fn handle_yank(app: &mut AppState) {
+fn show_url_hints(app: &mut AppState) {
+    let Some(event) = app.detail_view_event_id.as_ref().and_then(|id| app.events.get(id)) else {
+        return;
+    };
+    let access = app.calendar_access_role(&event.calendar_id);
+    let all_lines = build_event_detail_lines(event, &app.timezone, access.as_ref(), app.detail_view_draft.as_deref(), app.map_provider, app.time_format);
+    let urls = detail_urls(&all_lines, app.detail_view_wrap_width);
+
+    if urls.is_empty() {
+        app.set_message(MessageKind::Info, "No URLs in this event");
+    } else {
+        app.detail_view_hints = Some(UrlHints::new(urls));
+    }
+}
+
+/// Typing a whole label opens its URL; any other key leaves hint mode.
+fn handle_hint_key(key: KeyEvent, app: &mut AppState) {
+    let Some(hints) = app.detail_view_hints.as_mut() else {
+        return;
+    };
+    match key.code {
+        KeyCode::Backspace => {
+            hints.typed.pop();
+        }
+        KeyCode::Char(c) if key.modifiers.difference(KeyModifiers::SHIFT).is_empty() => match hints.type_char(c) {
+            HintMatch::Partial => {}
+            HintMatch::Open(url) => {
+                app.detail_view_hints = None;
+                tracing::info!("Opening URL: {}", url);
+                if let Err(e) = open_url(&url) {
+                    app.set_message(MessageKind::Error, format!("Failed to open {}: {}", url, e));
+                }
+            }
+            HintMatch::NoMatch => {
+                app.detail_view_hints = None;
+                app.set_message(MessageKind::Info, "No such hint");
+            }
+        },
+        _ => app.detail_view_hints = None,
+    }
+}
+
+/// Yanks into `register` and `"`; a plain `y` also goes to the clipboard.
+fn handle_yank(app: &mut AppState, register: Option<char>) {
     if !app.detail_view_line_text.is_empty() {
         let lines = &app.detail_view_line_text;
 
@@ -444,11 +1187,73 @@ fn handle_yank(app: &mut AppState) {
 
         if !text_to_yank.is_empty() {
             tracing::info!("Yanking {} bytes: '{}'", text_to_yank.len(), text_to_yank);
-            if let Err(e) = copy_to_clipboard(&text_to_yank) {
-                tracing::error!("Clipboard yank failed: {}", e);
+            let characters = text_to_yank.chars().count();
+            match store_yank(app, register, text_to_yank) {
+                Ok(()) => app.set_message(MessageKind::Info, match register {
+                    Some(name) => format!("Yanked {} characters into \"{}", characters, name),
+                    None => format!("Yanked {} characters", characters),
+                }),
+                Err(e) => {
+                    tracing::error!("Clipboard yank failed: {}", e);
+                    app.set_message(MessageKind::Error, format!("Clipboard yank failed: {}", e));
+                }
+            }
+        }
+    }
+}
+
+/// Puts `text` in `register` and `"`; without a register it goes to the
+/// clipboard as well.
+fn store_yank(app: &mut AppState, register: Option<char>, text: String) -> Result<(), String> {
+    app.registers.yank(register, Register::Text(text.clone()));
+    match register {
+        Some(_) => Ok(()),
+        None => copy_to_clipboard(&text),
+    }
+}
+
+/// `d`: cuts the visual selection, or the line under the cursor, out of a
+/// draft of the description and yanks it like `y`. Only the description
+/// can be cut, since the rest of the view is not free text.
+fn handle_cut(app: &mut AppState, register: Option<char>) {
+    let visual_start = app.detail_view_visual_start.take();
+    let Some(event) = app.detail_view_event_id.as_ref().and_then(|id| app.events.get(id)) else {
+        return;
+    };
+    let mut draft = app.detail_view_draft.clone().unwrap_or_else(|| description_draft(event));
+    let html = description_is_html(event);
+    let access = app.calendar_access_role(&event.calendar_id);
+    let lines = build_event_detail_lines(event, &app.timezone, access.as_ref(), Some(&draft), app.map_provider, app.time_format);
+    let Some(first) = lines.iter().position(|line| line.starts_with(DESCRIPTION_HEADER)).map(|header| header + 2) else {
+        app.set_message(MessageKind::Info, "No description to cut from");
+        return;
+    };
+    let line_count = draft.lines().count();
+    let to_draft = |position| draft_position(&lines, app.detail_view_wrap_width, first, line_count, position);
+
+    let cursor = (app.detail_view_cursor_line, app.detail_view_cursor_col);
+    let removed = match visual_start {
+        Some(start) => {
+            let (start, end) = if start <= cursor { (start, cursor) } else { (cursor, start) };
+            match (to_draft(start), to_draft(end)) {
+                (Some(start), Some(end)) => cut(&mut draft, start, end),
+                _ => String::new(),
             }
         }
+        None => to_draft(cursor).map(|(line, _)| cut_line(&mut draft, line)).unwrap_or_default(),
+    };
+    if removed.is_empty() {
+        app.set_message(MessageKind::Info, "Only the description can be cut");
+        return;
+    }
+
+    let characters = removed.chars().count();
+    if let Err(e) = store_yank(app, register, removed) {
+        tracing::warn!("Clipboard yank failed: {}", e);
     }
+    app.detail_view_draft = Some(draft);
+    let saves = if html { "E saves the description as plain text" } else { "E saves the description" };
+    app.set_message(MessageKind::Info, format!("Cut {} characters; {}, u restores it", characters, saves));
 }
 
 fn handle_paste(_app: &AppState) {
@@ -462,6 +1267,28 @@ fn handle_paste(_app: &AppState) {
     }
 }
 
+fn paste_clipboard_into_form(app: &mut AppState) {
+    match paste_from_clipboard() {
+        Ok(text) => insert_mode::paste(app, &text),
+        Err(e) => {
+            tracing::error!("Clipboard paste failed: {}", e);
+            app.set_message(MessageKind::Error, format!("Clipboard paste failed: {}", e));
+        }
+    }
+}
+
+fn handle_join_meeting(event: Option<&CalendarEvent>) {
+    let Some((title, url)) = event.and_then(|e| e.meeting_url().map(|url| (&e.title, url))) else {
+        tracing::info!("No meeting link found");
+        return;
+    };
+
+    tracing::info!("Joining meeting for {}", title);
+    if let Err(e) = open_url(url) {
+        tracing::error!("Failed to open meeting link: {}", e);
+    }
+}
+
 fn handle_open_browser(app: &AppState) {
     tracing::info!("Opening event in browser");
     if let Some(event_id) = &app.detail_view_event_id
@@ -470,13 +1297,123 @@ fn handle_open_browser(app: &AppState) {
         let url = event.html_link.clone()
             .unwrap_or_else(|| format!("https://calendar.google.com/calendar/u/0/r/eventedit/{}", event.id));
         tracing::info!("Opening Google Calendar URL: {}", url);
-        match std::process::Command::new("xdg-open").arg(&url).spawn() {
-            Ok(_) => tracing::info!("Successfully launched browser"),
+        match open_url(&url) {
+            Ok(()) => tracing::info!("Successfully launched browser"),
             Err(e) => tracing::error!("Failed to open browser: {}", e),
         }
     }
 }
 
+fn handle_open_map(app: &mut AppState) {
+    let Some(event) = app.detail_view_event_id.as_ref().and_then(|id| app.events.get(id)) else {
+        return;
+    };
+    let Some(url) = event.map_url(app.map_provider) else {
+        app.set_message(MessageKind::Info, "No location to look up");
+        return;
+    };
+
+    tracing::info!("Opening map: {}", url);
+    if let Err(e) = open_url(&url) {
+        app.set_message(MessageKind::Error, format!("Failed to open map: {}", e));
+    }
+}
+
+/// `I`/`A` in the detail view: edits the description, or the draft cut
+/// with `d`, in `$VISUAL`/`$EDITOR` and queues it like any other update.
+/// `A` starts at the end in vi-like editors.
+async fn edit_description<B: ratatui::backend::Backend>(
+    app: &mut AppState,
+    terminal: &mut Terminal<B>,
+    sync_engine: &mut SyncEngine,
+    at_end: bool,
+) -> io::Result<()> {
+    let Some(mut event) = app.detail_view_event_id.as_ref().and_then(|id| app.events.get(id)).cloned() else {
+        return Ok(());
+    };
+    if app.refuse_read_only_calendar(&event.calendar_id) || skipped_in_read_only(app, "description edit") {
+        return Ok(());
+    }
+    let original = description_draft(&event);
+    let draft = app.detail_view_draft.clone().unwrap_or_else(|| original.clone());
+
+    // The editor needs plain text, so the draft is not sealed. tempfile
+    // gives it a unique name and 0600 permissions, and removes it on close.
+    let dir = paths::cache_dir();
+    std::fs::create_dir_all(&dir)?;
+    let mut file = tempfile::Builder::new()
+        .prefix("description-")
+        .suffix(".txt")
+        .tempfile_in(&dir)?;
+    writeln!(file, "{}", draft)?;
+    file.flush()?;
+
+    let editor = std::env::var("VISUAL").or_else(|_| std::env::var("EDITOR")).unwrap_or_else(|_| "vi".to_string());
+    let parts = shlex::split(&editor).filter(|parts| !parts.is_empty()).unwrap_or_else(|| vec![editor.clone()]);
+    let program = parts[0].clone();
+    let mut command = std::process::Command::new(&program);
+    command.args(&parts[1..]);
+    if at_end && ["vi", "vim", "nvim"].iter().any(|vi| program.ends_with(vi)) {
+        command.arg("+");
+    }
+    let status = with_terminal_suspended(|| command.arg(file.path()).status())?;
+    terminal.clear()?;
+    let edited = std::fs::read_to_string(file.path());
+    if let Err(e) = file.close() {
+        tracing::warn!("Failed to remove the description draft: {}", e);
+    }
+
+    match status {
+        Err(e) => {
+            app.set_message(MessageKind::Error, format!("Failed to run {}: {}", program, e));
+            return Ok(());
+        }
+        Ok(status) if !status.success() => {
+            app.set_message(MessageKind::Info, format!("{} exited with {}; description unchanged", program, status));
+            return Ok(());
+        }
+        Ok(_) => {}
+    }
+    let edited = edited?.trim_end().to_string();
+    if edited == original {
+        app.detail_view_draft = None;
+        app.set_message(MessageKind::Info, "Description unchanged");
+        return Ok(());
+    }
+
+    let (_, link) = split_link(event.description.as_deref().unwrap_or_default());
+    let description = join_link(&edited, link.as_deref().unwrap_or_default());
+    event.description = (!description.is_empty()).then_some(description);
+
+    // A failed push is kept as a local edit when the queue flushes.
+    app.sync.phase = SyncPhase::Saving;
+    app.detail_view_draft = None;
+    app.add_event(event.clone());
+    sync_engine.queue_update(event);
+    app.set_message(MessageKind::Info, "Saving description");
+    Ok(())
+}
+
+/// Shows the newest log file in `$PAGER`, or `less`.
+fn open_log<B: ratatui::backend::Backend>(app: &mut AppState, terminal: &mut Terminal<B>) -> io::Result<()> {
+    let log_dir = Config::load_or_create().map(|config| config.log_dir()).unwrap_or_else(|_| paths::cache_dir());
+    let Some(path) = logging::latest_log_file(&log_dir) else {
+        app.set_message(MessageKind::Error, format!("No log file in {}", log_dir.display()));
+        return Ok(());
+    };
+
+    let pager = std::env::var("PAGER").unwrap_or_else(|_| "less".to_string());
+    let mut parts = pager.split_whitespace();
+    let program = parts.next().unwrap_or("less");
+    let status = with_terminal_suspended(|| std::process::Command::new(program).args(parts).arg(&path).status())?;
+    terminal.clear()?;
+
+    if let Err(e) = status {
+        app.set_message(MessageKind::Error, format!("Failed to run {}: {}", program, e));
+    }
+    Ok(())
+}
+
 async fn handle_command_mode<B: ratatui::backend::Backend>(
     code: KeyCode,
     app: &mut AppState,
@@ -486,52 +1423,225 @@ async fn handle_command_mode<B: ratatui::backend::Backend>(
     match code {
         KeyCode::Enter => {
             let command_text = app.command_buffer.clone();
-            let cmd = command_mode::parse_command(&command_text);
+            app.command_history.push(&command_text);
+            if !app.replaying
+                && let Err(e) = app.command_history.save(&CommandHistory::default_path())
+            {
+                tracing::warn!("Failed to save command history: {}", e);
+            }
+            let cmd = command_mode::parse_command_at(&command_text, app.today());
+            let selection = app.get_visual_selection_range();
+            app.visual_selection_start = None;
 
             match cmd {
                 command_mode::Command::Quit => return Ok(true),
-                command_mode::Command::Sync => {
+                command_mode::Command::Delete => {
                     app.command_buffer.clear();
                     app.mode = Mode::Normal;
-                    app.sync_status = SyncStatus::Syncing;
-                    terminal.draw(|f| ui(f, app))?;
-
-                    match sync_engine.fetch_events_around_date(app.selected_date).await {
-                        Ok(events) => {
-                            app.events.clear();
-                            for event in events {
-                                app.add_event(event);
+                    match selection {
+                        Some((start, end)) => {
+                            let ids: Vec<String> = app.events_in_range(start, end)
+                                .iter()
+                                .filter(|event| app.can_edit_calendar(&event.calendar_id))
+                                .map(|event| event.id.clone())
+                                .collect();
+                            if ids.is_empty() {
+                                app.set_message(MessageKind::Info, "No editable events in selection");
+                            } else {
+                                app.bulk_delete_event_ids = ids;
+                                app.mode = Mode::Visual;
                             }
-                            app.sync_status = SyncStatus::Synced;
                         }
-                        Err(e) => {
-                            app.sync_status = SyncStatus::Error(format!("Sync failed: {}", e));
+                        None => app.set_message(MessageKind::Error, "delete needs a visual selection (v)"),
+                    }
+                }
+                command_mode::Command::Free(query) => {
+                    app.command_buffer.clear();
+                    app.mode = Mode::Normal;
+                    let slots = find_free_slots(&query, app.events.values(), &app.working_hours, &app.timezone, app.local_now());
+                    if slots.is_empty() {
+                        app.set_message(MessageKind::Info, format!("No free {}m slots in that range", query.duration_minutes));
+                    } else {
+                        app.free_slots = slots;
+                        app.free_slot_index = 0;
+                        app.free_slot_minutes = query.duration_minutes;
+                    }
+                }
+                command_mode::Command::Report(start, end) => {
+                    app.command_buffer.clear();
+                    app.mode = Mode::Normal;
+                    // Loaded windows may not reach back to e.g. last month.
+                    if !sync_engine.is_loaded(start, end) {
+                        match sync_engine.fetch_events(start, end).await {
+                            Ok(events) => {
+                                for event in events {
+                                    app.add_event(event);
+                                }
+                                sync_engine.mark_fetched(start, end);
+                            }
+                            Err(e) => app.set_message(
+                                MessageKind::Error,
+                                format!("Report covers loaded events only; failed to fetch the range: {}", e),
+                            ),
                         }
                     }
+                    app.open_report(start, end);
+                }
+                command_mode::Command::ExportView(format, path) => {
+                    app.command_buffer.clear();
+                    app.mode = Mode::Normal;
+                    match write_view(app, format, path) {
+                        Ok(path) => app.set_message(MessageKind::Success, format!("Wrote {}", path.display())),
+                        Err(e) => app.set_message(MessageKind::Error, format!("Failed to export the view: {}", e)),
+                    }
+                }
+                command_mode::Command::Conflicts(start, end) => {
+                    app.command_buffer.clear();
+                    app.mode = Mode::Normal;
+                    if let Err(message) = app.open_travel_report(start, end) {
+                        app.set_message(MessageKind::Info, message);
+                    }
+                }
+                command_mode::Command::Duplicate(date) => {
+                    app.command_buffer.clear();
+                    app.mode = Mode::Normal;
+                    duplicate_selected_event(app, terminal, sync_engine, date).await?;
+                }
+                command_mode::Command::Move(days) => {
+                    app.command_buffer.clear();
+                    app.mode = Mode::Normal;
+                    match selection {
+                        Some((start, end)) => move_events_in_range(app, sync_engine, start, end, days),
+                        None => app.set_message(MessageKind::Error, "move needs a visual selection (v)"),
+                    }
+                }
+                command_mode::Command::Sync => {
+                    app.command_buffer.clear();
+                    app.mode = Mode::Normal;
+                    sync_events(app, terminal, sync_engine).await?;
+                }
+                command_mode::Command::Subscribe(calendar_id) => {
+                    app.command_buffer.clear();
+                    app.mode = Mode::Normal;
+                    let saved = if app.replaying { Ok(()) } else { save_overlay(&calendar_id) };
+                    if let Err(e) = saved {
+                        app.set_message(MessageKind::Error, format!("Failed to save subscription: {}", e));
+                    } else {
+                        sync_engine.add_overlay(calendar_id.clone());
+                        app.calendar_names.push(calendar_id);
+                        sync_events(app, terminal, sync_engine).await?;
+                    }
+                }
+                command_mode::Command::Open(reference) => {
+                    app.command_buffer.clear();
+                    app.mode = Mode::Normal;
+                    let calendar_id = reference.calendar_id.unwrap_or_else(|| app.default_calendar.clone());
+                    match sync_engine.get_event(&calendar_id, &reference.event_id).await {
+                        Ok(event) => app.show_event(event),
+                        Err(e) => report_error(app, "Failed to open event", e),
+                    }
+                }
+                command_mode::Command::SwitchCalendar(name) => {
+                    if app.known_calendars().contains(&name) {
+                        app.set_message(MessageKind::Info, format!("New events go to {}", name));
+                        app.default_calendar = name;
+                    } else {
+                        app.set_message(MessageKind::Error, format!("Unknown calendar: {}", name));
+                    }
+                    app.command_buffer.clear();
+                    app.mode = Mode::Normal;
                 }
                 command_mode::Command::Goto(date) => {
                     app.selected_date = date;
                     app.command_buffer.clear();
                     app.mode = Mode::Normal;
                 }
+                command_mode::Command::Messages => {
+                    app.show_activity = !app.show_activity;
+                    app.activity_scroll = 0;
+                    app.command_buffer.clear();
+                    app.mode = Mode::Normal;
+                }
+                command_mode::Command::SyncStatus => {
+                    app.show_sync_status = true;
+                    app.command_buffer.clear();
+                    app.mode = Mode::Normal;
+                }
+                command_mode::Command::Registers => {
+                    app.show_registers = true;
+                    app.command_buffer.clear();
+                    app.mode = Mode::Normal;
+                }
+                command_mode::Command::Set(key, value) => {
+                    match app.set_ui_option(&key, &value) {
+                        Ok(()) => app.set_message(MessageKind::Info, format!("{}={}", key, value)),
+                        Err(e) => app.set_message(MessageKind::Error, e),
+                    }
+                    app.command_buffer.clear();
+                    app.mode = Mode::Normal;
+                }
+                command_mode::Command::ReloadConfig => {
+                    app.command_buffer.clear();
+                    app.mode = Mode::Normal;
+                    match Config::load_or_create() {
+                        Ok(config) => {
+                            text_selection::set_backend(ClipboardBackend::from_config(config.ui.clipboard.as_deref()));
+                            app.apply_ui_config(&config.ui);
+                            app.report_patterns = TitlePattern::from_config(&config.report.patterns);
+                            app.set_message(MessageKind::Success, "Reloaded config");
+                        }
+                        Err(e) => report_error(app, "Failed to reload config", e),
+                    }
+                }
+                command_mode::Command::Log => {
+                    app.command_buffer.clear();
+                    app.mode = Mode::Normal;
+                    open_log(app, terminal)?;
+                }
                 command_mode::Command::Help => {
                     app.show_help = !app.show_help;
                     app.command_buffer.clear();
                     app.mode = Mode::Normal;
                 }
+                command_mode::Command::Toggle(command_mode::ToggleOption::Declined) => {
+                    app.declined_events = app.declined_events.toggled();
+                    app.reset_event_selection();
+                    let state = match app.declined_events {
+                        DeclinedEvents::Dim => "dimmed",
+                        DeclinedEvents::Hide => "hidden",
+                    };
+                    app.set_message(MessageKind::Info, format!("Declined events {}", state));
+                    app.command_buffer.clear();
+                    app.mode = Mode::Normal;
+                }
                 command_mode::Command::Theme(theme_name) => {
-                    app.theme = Theme::get_by_name(&theme_name);
+                    match Theme::find_by_name(&theme_name) {
+                        Some(theme) => app.theme = theme,
+                        None => app.set_message(
+                            MessageKind::Error,
+                            format!("Unknown theme: {} (available: {})", theme_name, Theme::available_themes().join(", ")),
+                        ),
+                    }
                     app.command_buffer.clear();
                     app.mode = Mode::Normal;
                 }
-                command_mode::Command::NewEvent(title) => {
-                    let title = title.unwrap_or_default();
-                    let form = EventForm::new(app.selected_date, title);
+                command_mode::Command::NewEvent(text) => {
+                    let form = match text {
+                        Some(text) => match parse_quick_add(&text, app.today()) {
+                            Ok(quick_add) => EventForm::from_quick_add(&quick_add, app.selected_date, app.local_now()),
+                            Err(e) => {
+                                tracing::warn!("Could not parse :new text: {}", e);
+                                EventForm::new(app.selected_date, text, app.local_now())
+                            }
+                        },
+                        None => EventForm::new(app.selected_date, String::new(), app.local_now()),
+                    };
                     app.event_form = Some(form);
                     app.command_buffer.clear();
                     app.mode = Mode::Insert;
                 }
-                _ => {
+                command_mode::Command::Error(message) => {
+                    app.set_message(MessageKind::Error, message);
                     app.command_buffer.clear();
                     app.mode = Mode::Normal;
                 }
@@ -540,108 +1650,250 @@ async fn handle_command_mode<B: ratatui::backend::Backend>(
         }
         KeyCode::Esc => {
             app.command_buffer.clear();
+            app.command_history.reset();
+            app.visual_selection_start = None;
             app.mode = Mode::Normal;
             Ok(false)
         }
-        KeyCode::Backspace => {
-            app.command_buffer.pop();
-            Ok(false)
-        }
-        KeyCode::Char(c) => {
-            app.command_buffer.push(c);
+        _ => {
+            command_mode::handle_key(code, app);
             Ok(false)
         }
-        _ => Ok(false)
     }
 }
 
 async fn handle_insert_mode<B: ratatui::backend::Backend>(
-    code: KeyCode,
+    key: KeyEvent,
     app: &mut AppState,
     terminal: &mut Terminal<B>,
     sync_engine: &mut SyncEngine,
 ) -> io::Result<bool> {
+    let code = key.code;
+    if !app.pending_changes.is_empty() {
+        match code {
+            KeyCode::Char('y') | KeyCode::Char('Y') => {
+                app.pending_changes.clear();
+                submit_event_form(app, terminal, sync_engine, true).await?;
+            }
+            KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => {
+                app.pending_changes.clear();
+            }
+            _ => {}
+        }
+        return Ok(false);
+    }
+
+    if !app.conflict_event_ids.is_empty() {
+        match code {
+            KeyCode::Char('y') | KeyCode::Char('Y') => {
+                app.conflict_event_ids.clear();
+                if let Some(form) = app.event_form.take() {
+                    save_event_form(app, terminal, sync_engine, form).await?;
+                }
+            }
+            KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => {
+                app.conflict_event_ids.clear();
+            }
+            _ => {}
+        }
+        return Ok(false);
+    }
+
+    if std::mem::take(&mut app.awaiting_register) {
+        insert_mode::paste_register(code, app);
+        return Ok(false);
+    }
+
+    if key.modifiers.contains(KeyModifiers::CONTROL) {
+        if code == KeyCode::Char('v') {
+            paste_clipboard_into_form(app);
+        } else {
+            insert_mode::handle_ctrl_key(code, app);
+        }
+        return Ok(false);
+    }
+
     match code {
         KeyCode::Esc => {
             app.event_form = None;
             app.mode = Mode::Normal;
             Ok(false)
         }
+        KeyCode::Enter if app.event_form.as_ref().is_some_and(|f| f.active_field == FormField::Description) => {
+            insert_mode::handle_key(code, app);
+            Ok(false)
+        }
         KeyCode::Enter => {
-            if let Some(mut form) = app.event_form.take() {
-                form.parse_time_input();
-                form.parse_duration_input();
-
-                let (start_datetime, end_datetime, all_day) = if form.all_day {
-                    let days = form.duration_minutes / (24 * 60);
-                    let start = form.date.and_hms_opt(0, 0, 0)
-                        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "Invalid date"))?
-                        .and_utc();
-                    let end = start + chrono::Duration::days(days as i64);
-                    (start, end, true)
-                } else {
-                    let start = form.date
-                        .and_hms_opt(form.start_hour, form.start_minute, 0)
-                        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "Invalid time"))?
-                        .and_utc();
-                    let end = start + chrono::Duration::minutes(form.duration_minutes as i64);
-                    (start, end, false)
-                };
-
-                let is_update = form.event_id.is_some();
-                let (event_id, html_link) = if let Some(id) = form.event_id.clone() {
-                    let link = app.events.get(&id).and_then(|e| e.html_link.clone());
-                    (id, link)
-                } else {
-                    (Uuid::new_v4().to_string(), None)
-                };
-
-                let event = build_event_from_form(
-                    event_id,
-                    &form,
-                    start_datetime,
-                    end_datetime,
-                    all_day,
-                    html_link,
-                );
+            submit_event_form(app, terminal, sync_engine, false).await?;
+            Ok(false)
+        }
+        _ => {
+            insert_mode::handle_key(code, app);
+            Ok(false)
+        }
+    }
+}
 
-                app.sync_status = SyncStatus::Syncing;
-                terminal.draw(|f| ui(f, app))?;
+/// Validates the form, asks to confirm what an edit changes unless that has
+/// been done, warns about overlaps and then saves.
+async fn submit_event_form<B: ratatui::backend::Backend>(
+    app: &mut AppState,
+    terminal: &mut Terminal<B>,
+    sync_engine: &mut SyncEngine,
+    changes_confirmed: bool,
+) -> io::Result<()> {
+    let Some(mut form) = app.event_form.take() else {
+        return Ok(());
+    };
+    form.errors = form.validate(&app.timezone, app.today());
+    if !form.errors.is_empty() {
+        app.set_message(MessageKind::Error, "Fix the highlighted fields before saving");
+        app.event_form = Some(form);
+        return Ok(());
+    }
+    let event = match event_from_form(app, &form) {
+        Ok(event) => event,
+        Err(e) => {
+            app.set_message(MessageKind::Error, e.to_string());
+            app.event_form = Some(form);
+            return Ok(());
+        }
+    };
 
-                if is_update {
-                    match sync_engine.update_event(&event).await {
-                        Ok(()) => {
-                            app.add_event(event);
-                            app.sync_status = SyncStatus::Synced;
-                        }
-                        Err(e) => {
-                            app.sync_status = SyncStatus::Error(format!("Failed to update: {}", e));
-                        }
-                    }
-                } else {
-                    match sync_engine.create_event(&event).await {
-                        Ok(created_info) => {
-                            let mut created_event = event;
-                            created_event.id = created_info.id;
-                            created_event.html_link = created_info.html_link;
-                            app.add_event(created_event);
-                            app.sync_status = SyncStatus::Synced;
-                        }
-                        Err(e) => {
-                            app.sync_status = SyncStatus::Error(format!("Failed to create: {}", e));
-                        }
+    let existing = form.event_id.as_ref().and_then(|id| app.events.get(id));
+    let changes = existing.map(|old| event_diff::diff(old, &event, &app.timezone)).unwrap_or_default();
+    if !changes_confirmed && !changes.is_empty() {
+        app.pending_changes = changes;
+        app.event_form = Some(form);
+        return Ok(());
+    }
+
+    let conflicts: Vec<String> = app.conflicts_with(&event)
+        .iter()
+        .map(|conflict| conflict.id.clone())
+        .collect();
+
+    if conflicts.is_empty() {
+        save_event_form(app, terminal, sync_engine, form).await?;
+    } else {
+        app.conflict_event_ids = conflicts;
+        app.event_form = Some(form);
+    }
+    Ok(())
+}
+
+fn event_from_form(app: &AppState, form: &EventForm) -> io::Result<CalendarEvent> {
+    let mut form = form.clone();
+    form.parse_date_input(app.today());
+    form.parse_time_input();
+    form.parse_duration_input();
+
+    let (start_datetime, end_datetime) = form.time_range(&app.timezone)
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "Invalid date or time"))?;
+
+    let existing = form.event_id.as_ref().and_then(|id| app.events.get(id));
+    let (event_id, html_link, time_zone) = match (&form.event_id, existing) {
+        (Some(id), existing) => (
+            id.clone(),
+            existing.and_then(|e| e.html_link.clone()),
+            existing.and_then(|e| e.time_zone.clone()),
+        ),
+        (None, _) => (Uuid::new_v4().to_string(), None, app.timezone.name()),
+    };
+
+    let mut event = build_event_from_form(
+        event_id,
+        form.calendar_or(&app.default_calendar).to_string(),
+        &form,
+        start_datetime,
+        end_datetime,
+        html_link,
+        app.now(),
+    );
+    event.time_zone = time_zone;
+    if let Some(existing) = existing {
+        event.color_id = existing.color_id.clone();
+        event.color = existing.color.clone();
+        event.self_response = existing.self_response.clone();
+        event.attendee_responses = existing.attendee_responses.clone();
+        event.etag = existing.etag.clone();
+    }
+    Ok(event)
+}
+
+/// Marks the sync as failed and opens the error dialog with what to do next.
+fn report_error(app: &mut AppState, action: &str, error: impl Into<AppError>) {
+    let report = ErrorReport::new(action, &error.into());
+    app.log_activity(MessageKind::Error, format!("{} ({})", report.summary(), report.detail));
+    app.sync.phase = SyncPhase::Error(report.summary());
+    app.error_report = Some(report);
+}
+
+/// True, with a note in the status bar, when read-only mode skips `action`.
+fn skipped_in_read_only(app: &mut AppState, action: &str) -> bool {
+    if app.read_only {
+        app.set_message(MessageKind::Info, format!("Read-only mode: {} not sent", action));
+    }
+    app.read_only
+}
+
+async fn save_event_form<B: ratatui::backend::Backend>(
+    app: &mut AppState,
+    terminal: &mut Terminal<B>,
+    sync_engine: &mut SyncEngine,
+    form: EventForm,
+) -> io::Result<()> {
+    let mut event = event_from_form(app, &form)?;
+    let is_update = form.event_id.is_some();
+    // The form stays open so the user can copy it out or cancel it.
+    if skipped_in_read_only(app, if is_update { "update" } else { "new event" }) {
+        app.event_form = Some(form);
+        return Ok(());
+    }
+
+    app.sync.phase = SyncPhase::Saving;
+    terminal.draw(|f| ui(f, app))?;
+
+    if is_update {
+        let base_modified = app.events.get(&event.id).map_or(event.last_modified, |e| e.last_modified);
+        match sync_engine.update_event(&mut event).await {
+            Ok(()) => {
+                app.add_event(event);
+                app.sync.phase = SyncPhase::Idle;
+                app.set_message(MessageKind::Success, "Event updated");
+            }
+            Err(e @ SyncError::CalendarReadOnly(_)) => report_error(app, "Failed to update", e),
+            Err(e) => {
+                app.sync.phase = SyncPhase::Error(format!("Failed to update: {}", e));
+                match sync_engine.keep_local_edit(&event, base_modified) {
+                    Ok(true) => {
+                        app.add_event(event);
+                        app.set_message(MessageKind::Error, format!("Failed to update: {}; kept locally, :w retries", e));
                     }
+                    Ok(false) => app.set_message(MessageKind::Error, format!("Failed to update: {}", e)),
+                    Err(cache_error) => app.set_message(MessageKind::Error, format!("Failed to update: {}; {}", e, cache_error)),
                 }
-
-                app.mode = Mode::Normal;
             }
-            Ok(false)
         }
-        _ => {
-            insert_mode::handle_key(code, app);
-            Ok(false)
+    } else {
+        match sync_engine.create_event(&event.calendar_id, &event).await {
+            Ok(created_info) => {
+                let mut created_event = event;
+                created_event.id = created_info.id;
+                created_event.html_link = created_info.html_link;
+                app.last_edit = Some(RepeatableEdit::Create(Box::new(created_event.clone())));
+                app.add_event(created_event);
+                app.sync.phase = SyncPhase::Idle;
+                app.set_message(MessageKind::Success, "Event created");
+            }
+            Err(e) => {
+                report_error(app, "Failed to create", e);
+            }
         }
     }
+
+    app.mode = Mode::Normal;
+    Ok(())
 }
 
 async fn handle_delete_confirmation<B: ratatui::backend::Backend>(
@@ -651,24 +1903,31 @@ async fn handle_delete_confirmation<B: ratatui::backend::Backend>(
     sync_engine: &mut SyncEngine,
 ) -> io::Result<()> {
     match code {
+        KeyCode::Char('y') | KeyCode::Char('Y') if skipped_in_read_only(app, "delete") => {
+            app.delete_confirmation_event_id = None;
+            app.mode = Mode::Normal;
+        }
         KeyCode::Char('y') | KeyCode::Char('Y') => {
-            if let Some(event_id) = app.delete_confirmation_event_id.take() {
+            let event = app.delete_confirmation_event_id.take().and_then(|id| app.events.get(&id).cloned());
+            if let Some(event) = event {
+                let event_id = event.id.clone();
                 tracing::info!("Deleting event: {}", event_id);
-                app.sync_status = SyncStatus::Syncing;
+                app.sync.phase = SyncPhase::Saving;
                 terminal.draw(|f| ui(f, app))?;
 
-                match sync_engine.delete_event(&event_id).await {
+                match sync_engine.delete_event(&event).await {
                     Ok(()) => {
                         tracing::info!("Event deleted successfully");
                         app.remove_event(&event_id);
-                        app.sync_status = SyncStatus::Synced;
+                        app.sync.phase = SyncPhase::Idle;
+                        app.set_message(MessageKind::Success, "Event deleted");
                         if app.selected_event_index > 0 {
                             app.selected_event_index -= 1;
                         }
                     }
                     Err(e) => {
                         tracing::error!("Failed to delete event: {}", e);
-                        app.sync_status = SyncStatus::Error(format!("Failed to delete event: {}", e));
+                        report_error(app, "Failed to delete event", e);
                     }
                 }
             }
@@ -682,3 +1941,223 @@ async fn handle_delete_confirmation<B: ratatui::backend::Backend>(
     }
     Ok(())
 }
+
+async fn handle_bulk_delete_confirmation<B: ratatui::backend::Backend>(
+    code: KeyCode,
+    app: &mut AppState,
+    terminal: &mut Terminal<B>,
+    sync_engine: &mut SyncEngine,
+) -> io::Result<()> {
+    match code {
+        KeyCode::Char('y') | KeyCode::Char('Y') if skipped_in_read_only(app, "delete") => {
+            app.bulk_delete_event_ids.clear();
+            app.mode = Mode::Normal;
+        }
+        KeyCode::Char('y') | KeyCode::Char('Y') => {
+            let event_ids = std::mem::take(&mut app.bulk_delete_event_ids);
+            tracing::info!("Deleting {} events", event_ids.len());
+            app.sync.phase = SyncPhase::Saving;
+            terminal.draw(|f| ui(f, app))?;
+
+            let targets: Vec<CalendarEvent> = event_ids.iter()
+                .filter_map(|id| app.events.get(id).cloned())
+                .collect();
+            match sync_engine.delete_events(&targets).await {
+                Ok(outcome) => {
+                    for event_id in &outcome.succeeded {
+                        app.remove_event(event_id);
+                    }
+                    app.reset_event_selection();
+                    report_batch(app, "Deleted", outcome);
+                }
+                Err(e) => {
+                    tracing::error!("Failed to delete events: {}", e);
+                    report_error(app, "Failed to delete events", e);
+                }
+            }
+            app.mode = Mode::Normal;
+        }
+        KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => {
+            app.bulk_delete_event_ids.clear();
+            app.mode = Mode::Normal;
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+/// Moves the events locally and queues the updates, so repeated moves of
+/// the same events reach the server as one request each.
+fn move_events_in_range(
+    app: &mut AppState,
+    sync_engine: &mut SyncEngine,
+    start: NaiveDate,
+    end: NaiveDate,
+    days: i64,
+) {
+    let moved: Vec<CalendarEvent> = app.events_in_range(start, end)
+        .into_iter()
+        .filter_map(|event| event.shifted_by_days(days, &app.timezone))
+        .collect();
+    move_events(app, sync_engine, moved, days);
+}
+
+/// Events on calendars the user cannot edit are left where they are.
+fn move_events(app: &mut AppState, sync_engine: &mut SyncEngine, moved: Vec<CalendarEvent>, days: i64) {
+    if skipped_in_read_only(app, "move") {
+        return;
+    }
+    let moved: Vec<CalendarEvent> = moved.into_iter()
+        .filter(|event| app.can_edit_calendar(&event.calendar_id))
+        .collect();
+    if moved.is_empty() {
+        app.set_message(MessageKind::Info, "No editable events in selection");
+        return;
+    }
+
+    tracing::info!("Moving {} events by {} days", moved.len(), days);
+    app.sync.phase = SyncPhase::Saving;
+    app.set_message(MessageKind::Info, format!("Moving {} events", moved.len()));
+    app.last_edit = Some(RepeatableEdit::Move(days));
+    for event in moved {
+        app.add_event(event.clone());
+        sync_engine.queue_update(event);
+    }
+}
+
+/// Pushes queued updates once edits pause (or now, with `force`). Failed
+/// updates are kept as local edits so the next sync retries them, except
+/// on read-only calendars, where a retry can never succeed; those events
+/// go back to their cached copy.
+async fn flush_queued_updates(app: &mut AppState, sync_engine: &mut SyncEngine, force: bool) {
+    let Some((events, result)) = sync_engine.flush_updates(force).await else {
+        return;
+    };
+    app.needs_redraw = true;
+    let succeeded = result.as_ref().map_or(&[][..], |outcome| &outcome.succeeded[..]);
+    let failed = result.as_ref().map_or(&[][..], |outcome| &outcome.failed[..]);
+    for event in events {
+        let refused = failed.iter()
+            .any(|(id, e)| id == &event.id && matches!(e, SyncError::CalendarReadOnly(_)));
+        if succeeded.contains(&event.id) {
+            app.add_event(event);
+        } else if refused {
+            if let Some(cached) = sync_engine.cache().and_then(|cache| cache.load_event(&event.id).ok().flatten()) {
+                app.add_event(cached);
+            }
+        } else if let Err(e) = sync_engine.keep_local_edit(&event, event.last_modified) {
+            tracing::warn!("Failed to keep local edit {}: {}", event.id, e);
+        }
+    }
+    match result {
+        Ok(outcome) => report_batch(app, "Saved", outcome),
+        Err(e) => {
+            tracing::error!("Failed to save events: {}", e);
+            report_error(app, "Failed to save events", e);
+        }
+    }
+}
+
+async fn duplicate_selected_event<B: ratatui::backend::Backend>(
+    app: &mut AppState,
+    terminal: &mut Terminal<B>,
+    sync_engine: &mut SyncEngine,
+    date: Option<NaiveDate>,
+) -> io::Result<()> {
+    if skipped_in_read_only(app, "duplicate") {
+        return Ok(());
+    }
+    let Some(event) = app.get_selected_event() else {
+        app.set_message(MessageKind::Error, "No event selected to duplicate");
+        return Ok(());
+    };
+    let date = date.unwrap_or_else(|| event.local_start(&app.timezone).date());
+    let Some(copy) = event.duplicate_to(Uuid::new_v4().to_string(), date, &app.timezone, app.now()) else {
+        app.set_message(MessageKind::Error, format!("{} does not exist on {}", event.title, date));
+        return Ok(());
+    };
+
+    tracing::info!("Duplicating event {} to {}", event.id, date);
+    let days = (date - event.local_start(&app.timezone).date()).num_days();
+    let message = format!("Duplicated \"{}\" to {}", copy.title, date.format("%a %b %d"));
+    if create_copy(app, terminal, sync_engine, copy, message, "Failed to duplicate").await? {
+        app.last_edit = Some(RepeatableEdit::Duplicate(days));
+    }
+    Ok(())
+}
+
+/// `.`: applies the last move, duplicate or new event again to the selected
+/// event or date.
+async fn repeat_last_edit<B: ratatui::backend::Backend>(
+    app: &mut AppState,
+    terminal: &mut Terminal<B>,
+    sync_engine: &mut SyncEngine,
+) -> io::Result<()> {
+    let Some(edit) = app.last_edit.clone() else {
+        app.set_message(MessageKind::Info, "Nothing to repeat");
+        return Ok(());
+    };
+    let Some(event) = edit.target(app.get_selected_event(), app.selected_date, &app.timezone, app.now()) else {
+        app.set_message(MessageKind::Error, format!("Nothing selected to {}", edit.describe()));
+        return Ok(());
+    };
+
+    tracing::info!("Repeating {}", edit.describe());
+    match edit {
+        RepeatableEdit::Move(days) => move_events(app, sync_engine, vec![event], days),
+        RepeatableEdit::Duplicate(_) | RepeatableEdit::Create(_) => {
+            if skipped_in_read_only(app, "repeat") {
+                return Ok(());
+            }
+            let message = format!("Repeated {}", edit.describe());
+            create_copy(app, terminal, sync_engine, event, message, "Failed to repeat").await?;
+        }
+    }
+    Ok(())
+}
+
+/// Creates `copy` and adds it with the id Google gave it; true on success.
+async fn create_copy<B: ratatui::backend::Backend>(
+    app: &mut AppState,
+    terminal: &mut Terminal<B>,
+    sync_engine: &mut SyncEngine,
+    copy: CalendarEvent,
+    message: String,
+    action: &str,
+) -> io::Result<bool> {
+    app.sync.phase = SyncPhase::Saving;
+    terminal.draw(|f| ui(f, app))?;
+
+    match sync_engine.create_event(&copy.calendar_id, &copy).await {
+        Ok(created_info) => {
+            let mut created_event = copy;
+            created_event.id = created_info.id;
+            created_event.html_link = created_info.html_link;
+            app.set_message(MessageKind::Success, message);
+            app.add_event(created_event);
+            app.sync.phase = SyncPhase::Idle;
+            Ok(true)
+        }
+        Err(e) => {
+            report_error(app, action, e);
+            Ok(false)
+        }
+    }
+}
+
+fn report_batch(app: &mut AppState, verb: &str, outcome: BatchOutcome) {
+    match outcome.failed.first() {
+        None => {
+            app.sync.phase = SyncPhase::Idle;
+            app.set_message(MessageKind::Success, format!("{} {} events", verb, outcome.succeeded.len()));
+        }
+        Some((event_id, e)) => {
+            tracing::error!("{} {} events, {} failed (first: {}: {})", verb, outcome.succeeded.len(), outcome.failed.len(), event_id, e);
+            app.sync.phase = SyncPhase::Error(e.to_string());
+            app.set_message(
+                MessageKind::Error,
+                format!("{} {} events, {} failed: {}", verb, outcome.succeeded.len(), outcome.failed.len(), e),
+            );
+        }
+    }
+}
@@ -0,0 +1,180 @@
+use std::io;
+
+use crossterm::event::{self, Event as TermEvent, KeyEventKind};
+use ratatui::{
+    backend::{Backend, CrosstermBackend},
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+    Frame, Terminal,
+};
+use gcal_imp::{
+    error::AppError,
+    input::setup_wizard::{SetupWizard, WizardAction, WizardStep},
+    storage::config::Config,
+    sync::{google_auth::GoogleAuthenticator, mock_api::ApiBackend, sync_engine::SyncEngine},
+};
+
+use crate::tui::terminal_guard::TerminalGuard;
+
+/// Walks through first-run setup and saves the result. Returns the saved
+/// config, or `None` when the user quit the wizard.
+pub async fn run_setup_wizard(config: Config) -> Result<Option<Config>, AppError> {
+    let _guard = TerminalGuard::enter()?;
+    let mut terminal = Terminal::new(CrosstermBackend::new(io::stdout()))?;
+    terminal.clear()?;
+
+    let mut wizard = SetupWizard::from_config(&config);
+    let mut config = config;
+    loop {
+        terminal.draw(|f| render(f, &wizard, &config))?;
+
+        let key = match event::read()? {
+            TermEvent::Key(key) if key.kind == KeyEventKind::Press => key,
+            _ => continue,
+        };
+        match wizard.handle_key(key.code) {
+            WizardAction::None => {}
+            WizardAction::Cancel => return Ok(None),
+            WizardAction::ExchangeCode => {
+                wizard.apply(&mut config);
+                draw_busy(&mut terminal, "Signing in...")?;
+                match sign_in(&config, &wizard.code()).await {
+                    Ok(calendars) => wizard.set_calendars(calendars),
+                    Err(e) => wizard.error = Some(format!("{}: {}", e.message(), e)),
+                }
+            }
+            WizardAction::Finish => {
+                wizard.apply(&mut config);
+                config.save()?;
+                return Ok(Some(config));
+            }
+        }
+    }
+}
+
+/// Exchanges the code for a token, then lists the account's calendars.
+async fn sign_in(config: &Config, code: &str) -> Result<Vec<String>, AppError> {
+    GoogleAuthenticator::new(config.clone()).exchange_code_for_token(code).await?;
+    let mut calendars: Vec<String> = SyncEngine::new(config.clone())
+        .fetch_calendars()
+        .await?
+        .into_iter()
+        .map(|calendar| calendar.id)
+        .collect();
+    if calendars.is_empty() {
+        calendars.push(config.calendars.default.clone());
+    }
+    Ok(calendars)
+}
+
+fn draw_busy<B: Backend>(terminal: &mut Terminal<B>, text: &str) -> io::Result<()> {
+    terminal.draw(|f| {
+        let area = dialog_area(f.size());
+        f.render_widget(Clear, area);
+        f.render_widget(Paragraph::new(format!("\n  {}", text)).block(block()), area);
+    })?;
+    Ok(())
+}
+
+fn dialog_area(area: Rect) -> Rect {
+    let width = 72.min(area.width);
+    let height = 20.min(area.height);
+    Rect {
+        x: area.width.saturating_sub(width) / 2,
+        y: area.height.saturating_sub(height) / 2,
+        width,
+        height,
+    }
+}
+
+fn block() -> Block<'static> {
+    Block::default()
+        .borders(Borders::ALL)
+        .title(" gcal-imp setup ")
+        .style(Style::default().bg(Color::Black))
+}
+
+fn render(f: &mut Frame, wizard: &SetupWizard, config: &Config) {
+    let area = dialog_area(f.size());
+    f.render_widget(Clear, area);
+
+    let hint = |text: &str| Line::from(Span::styled(format!("  {}", text), Style::default().fg(Color::DarkGray)));
+    let selected = Style::default().fg(Color::Black).bg(Color::Cyan).add_modifier(Modifier::BOLD);
+    let input = |label: &str, value: String| {
+        Line::from(vec![
+            Span::styled(format!("  {}: ", label), Style::default().fg(Color::Cyan)),
+            Span::raw(value),
+            Span::styled("_", Style::default().add_modifier(Modifier::SLOW_BLINK)),
+        ])
+    };
+
+    let mut lines = vec![Line::from("")];
+    match wizard.step {
+        WizardStep::Provider => {
+            lines.push(Line::from("  Where should your calendar come from?"));
+            lines.push(Line::from(""));
+            for provider in SetupWizard::PROVIDERS {
+                let label = match provider {
+                    ApiBackend::Google => "Google Calendar (needs an OAuth client)",
+                    ApiBackend::Local => "Local only, kept on this computer (no account)",
+                    ApiBackend::Mock => "Sample events in memory (no account)",
+                };
+                let style = if *provider == wizard.provider { selected } else { Style::default() };
+                lines.push(Line::from(Span::styled(format!("  {}", label), style)));
+            }
+            lines.push(Line::from(""));
+            lines.push(hint("j/k to choose, Enter to continue, Esc to quit"));
+        }
+        WizardStep::ClientId | WizardStep::ClientSecret => {
+            lines.push(Line::from("  Create an OAuth client of type \"Desktop app\" at"));
+            lines.push(Line::from("  https://console.cloud.google.com/apis/credentials"));
+            lines.push(Line::from("  and enable the Google Calendar API for its project."));
+            lines.push(Line::from(""));
+            if wizard.step == WizardStep::ClientId {
+                lines.push(input("Client ID", wizard.client_id.clone()));
+            } else {
+                lines.push(input("Client secret", "*".repeat(wizard.client_secret.chars().count())));
+            }
+            lines.push(Line::from(""));
+            lines.push(hint("Enter to continue, Esc to go back"));
+        }
+        WizardStep::AuthCode => {
+            lines.push(Line::from("  Open this URL, sign in and allow access:"));
+            lines.push(Line::from(""));
+            lines.push(Line::from(Span::styled(
+                format!("  {}", GoogleAuthenticator::new(config.clone()).get_auth_url()),
+                Style::default().fg(Color::Yellow),
+            )));
+            lines.push(Line::from(""));
+            lines.push(Line::from("  The browser then fails to load localhost:8080. Paste that"));
+            lines.push(Line::from("  address, or just its code= value, here:"));
+            lines.push(input("Code", wizard.auth_code.clone()));
+            lines.push(Line::from(""));
+            lines.push(hint("Enter to sign in, Esc to go back"));
+        }
+        WizardStep::Calendars => {
+            lines.push(Line::from("  Choose the calendars to show. New events go to the"));
+            lines.push(Line::from("  highlighted one when you press Enter."));
+            lines.push(Line::from(""));
+            let visible = area.height.saturating_sub(9) as usize;
+            let scroll = (wizard.calendar_index + 1).saturating_sub(visible);
+            for (index, calendar) in wizard.calendars.iter().enumerate().skip(scroll).take(visible) {
+                let checkbox = if wizard.visible[index] { "[x]" } else { "[ ]" };
+                let style = if index == wizard.calendar_index { selected } else { Style::default() };
+                lines.push(Line::from(Span::styled(format!("  {} {}", checkbox, calendar), style)));
+            }
+            lines.push(Line::from(""));
+            lines.push(hint("j/k to move, Space to show/hide, Enter to finish"));
+        }
+    }
+
+    if let Some(error) = &wizard.error {
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(format!("  {}", error), Style::default().fg(Color::Red))));
+    }
+
+    let paragraph = Paragraph::new(lines).wrap(Wrap { trim: false }).block(block());
+    f.render_widget(paragraph, area);
+}
@@ -0,0 +1,129 @@
+use std::path::PathBuf;
+
+use chrono::{Duration, TimeZone, Utc};
+use gcal_imp::{
+    app::{AppState, ViewType},
+    calendar::{DisplayTimezone, Event as CalendarEvent, EventStatus, Transparency, Visibility, DEFAULT_CALENDAR_ID},
+    util::clock::FixedClock,
+};
+use pretty_assertions::assert_eq;
+use ratatui::{backend::TestBackend, Terminal};
+
+use crate::tui::presentation::ui;
+
+const WIDTH: u16 = 100;
+const HEIGHT: u16 = 36;
+
+/// Draws the whole screen into a test buffer and returns its text, one line
+/// per row with trailing spaces trimmed.
+fn render(app: &AppState) -> String {
+    let mut terminal = Terminal::new(TestBackend::new(WIDTH, HEIGHT)).unwrap();
+    terminal.draw(|f| ui(f, app)).unwrap();
+
+    let buffer = terminal.backend().buffer();
+    let width = buffer.area.width as usize;
+    buffer.content
+        .chunks(width)
+        .map(|row| row.iter().map(|cell| cell.symbol()).collect::<String>().trim_end().to_string())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Compares against `snapshots/<name>.txt`. A missing snapshot is written
+/// instead; set `UPDATE_SNAPSHOTS=1` to rewrite them all after an intended
+/// layout change.
+fn assert_snapshot(name: &str, actual: &str) {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("src/tui/snapshots")
+        .join(format!("{}.txt", name));
+
+    if std::env::var_os("UPDATE_SNAPSHOTS").is_some() || !path.exists() {
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(&path, format!("{}\n", actual)).unwrap();
+        return;
+    }
+
+    let expected = std::fs::read_to_string(&path).unwrap();
+    assert_eq!(expected.trim_end_matches('\n'), actual, "snapshot {} changed", name);
+}
+
+fn event(id: &str, title: &str, day: u32, hour: u32, hours: i64) -> CalendarEvent {
+    let start = Utc.with_ymd_and_hms(2025, 1, day, hour, 0, 0).unwrap();
+    CalendarEvent {
+        id: id.to_string(),
+        calendar_id: DEFAULT_CALENDAR_ID.to_string(),
+        title: title.to_string(),
+        description: None,
+        location: None,
+        start,
+        end: start + Duration::hours(hours),
+        all_day: false,
+        attendees: vec![],
+        reminders: vec![],
+        status: EventStatus::Confirmed,
+        last_modified: start,
+        html_link: None,
+        time_zone: None,
+        conference_url: None,
+        color_id: None,
+        color: None,
+        self_response: None,
+        attendee_responses: Default::default(),
+        visibility: Visibility::Default,
+        transparency: Transparency::Opaque,
+        etag: None,
+        organizer: None,
+        creator: None,
+    }
+}
+
+/// 08:30 UTC on 15 January 2025, with a morning standup, a long workshop,
+/// an all-day holiday and a meeting later in the week.
+pub(super) fn fixture(view: ViewType) -> AppState {
+    let mut app = AppState::new()
+        .with_timezone(DisplayTimezone::Named(chrono_tz::UTC))
+        .with_clock(FixedClock(Utc.with_ymd_and_hms(2025, 1, 15, 8, 30, 0).unwrap()));
+    app.view = view;
+
+    let mut standup = event("standup", "Standup", 15, 9, 1);
+    standup.location = Some("Room 4".to_string());
+    standup.description = Some("Yesterday, today, blockers.".to_string());
+    standup.attendees = vec!["sam@example.com".to_string(), "kim@example.com".to_string()];
+    app.add_event(standup);
+
+    app.add_event(event("workshop", "Design workshop", 15, 13, 3));
+    app.add_event(event("review", "Sprint review", 17, 10, 1));
+
+    let mut holiday = event("holiday", "Company holiday", 20, 0, 24);
+    holiday.all_day = true;
+    app.add_event(holiday);
+    app
+}
+
+#[test]
+fn month_view() {
+    assert_snapshot("month_view", &render(&fixture(ViewType::Month)));
+}
+
+#[test]
+fn week_view() {
+    assert_snapshot("week_view", &render(&fixture(ViewType::Week)));
+}
+
+#[test]
+fn day_view() {
+    assert_snapshot("day_view", &render(&fixture(ViewType::Day)));
+}
+
+#[test]
+fn split_view() {
+    assert_snapshot("split_view", &render(&fixture(ViewType::Split)));
+}
+
+#[test]
+fn event_detail() {
+    let mut app = fixture(ViewType::Day);
+    app.detail_view_event_id = Some("standup".to_string());
+
+    assert_snapshot("event_detail", &render(&app));
+}
@@ -0,0 +1,75 @@
+use std::io;
+use std::panic;
+use std::sync::Once;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use crossterm::{
+    cursor::Show,
+    event::{DisableBracketedPaste, DisableMouseCapture, EnableBracketedPaste, EnableMouseCapture},
+    execute,
+    terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
+};
+
+static PANIC_HOOK: Once = Once::new();
+static ACTIVE: AtomicBool = AtomicBool::new(false);
+
+/// Puts the terminal into raw mode on the alternate screen and puts it back
+/// when dropped, including while unwinding from a panic.
+pub struct TerminalGuard;
+
+impl TerminalGuard {
+    pub fn enter() -> io::Result<Self> {
+        install_panic_hook();
+        // From here on a failure still has to leave raw mode, which the guard does.
+        let guard = Self;
+        activate()?;
+        Ok(guard)
+    }
+}
+
+/// Hands the normal screen to `f`, e.g. to run a pager, then takes it back.
+/// The caller should clear the ratatui terminal afterwards.
+pub fn with_terminal_suspended<T>(f: impl FnOnce() -> T) -> io::Result<T> {
+    restore();
+    let result = f();
+    activate()?;
+    Ok(result)
+}
+
+fn activate() -> io::Result<()> {
+    enable_raw_mode()?;
+    ACTIVE.store(true, Ordering::SeqCst);
+    execute!(io::stdout(), EnterAlternateScreen, EnableMouseCapture, EnableBracketedPaste)
+}
+
+impl Drop for TerminalGuard {
+    fn drop(&mut self) {
+        restore();
+    }
+}
+
+/// Leaves raw mode and the alternate screen, once per `enter`.
+fn restore() {
+    if !ACTIVE.swap(false, Ordering::SeqCst) {
+        return;
+    }
+    if let Err(e) = disable_raw_mode() {
+        tracing::warn!("Failed to disable raw mode: {}", e);
+    }
+    if let Err(e) = execute!(io::stdout(), LeaveAlternateScreen, DisableMouseCapture, DisableBracketedPaste, Show) {
+        tracing::warn!("Failed to restore the terminal: {}", e);
+    }
+}
+
+/// The default hook prints the panic message straight away, before any
+/// guard is dropped, so the message would land on the alternate screen and
+/// vanish. Restoring first keeps it readable.
+fn install_panic_hook() {
+    PANIC_HOOK.call_once(|| {
+        let previous = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            restore();
+            previous(info);
+        }));
+    });
+}
@@ -0,0 +1,29 @@
+use std::io;
+use std::path::PathBuf;
+
+use gcal_imp::{app::AppState, ui::view_export::ViewExportFormat};
+use ratatui::{backend::TestBackend, Terminal};
+
+use crate::tui::presentation::render_calendar;
+
+/// Wide enough for titles in the week grid and about a landscape page.
+const EXPORT_WIDTH: u16 = 140;
+const EXPORT_HEIGHT: u16 = 50;
+
+/// Draws the current view off-screen and returns it as `format`.
+pub fn export_view(app: &AppState, format: ViewExportFormat) -> io::Result<String> {
+    let mut terminal = Terminal::new(TestBackend::new(EXPORT_WIDTH, EXPORT_HEIGHT))?;
+    terminal.draw(|f| render_calendar(f, app, f.size()))?;
+    let title = format!("{} view, {}", app.view.name(), app.selected_date);
+    Ok(format.render(terminal.backend().buffer(), &title))
+}
+
+/// Writes the view to `path`, or to `gcal-<view>-<date>.<ext>` in the
+/// working directory, and returns where it went.
+pub fn write_view(app: &AppState, format: ViewExportFormat, path: Option<PathBuf>) -> io::Result<PathBuf> {
+    let path = path.unwrap_or_else(|| {
+        PathBuf::from(format!("gcal-{}-{}.{}", app.view.name().to_lowercase(), app.selected_date, format.extension()))
+    });
+    std::fs::write(&path, export_view(app, format)?)?;
+    Ok(path)
+}
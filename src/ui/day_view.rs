@@ -1,11 +1,51 @@
-use chrono::{NaiveDate, Timelike};
+use chrono::{NaiveDate, NaiveTime, Timelike};
 use crate::app::AppState;
-use crate::calendar::Event;
+use crate::calendar::{DisplayTimezone, Event, EventStatus};
+
+/// Which hours the day view draws (`ui.day_grid`, toggled with `z`).
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum DayGrid {
+    /// Only hours that have events.
+    #[default]
+    Compact,
+    /// All 24 hours, so gaps between events show.
+    Full,
+}
+
+impl DayGrid {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.trim().to_lowercase().as_str() {
+            "compact" => Some(Self::Compact),
+            "full" | "24h" => Some(Self::Full),
+            _ => None,
+        }
+    }
+
+    pub fn from_config(name: Option<&str>) -> Self {
+        match name {
+            Some(name) => Self::from_name(name).unwrap_or_else(|| {
+                tracing::warn!("Unknown day grid '{}', using compact", name);
+                Self::Compact
+            }),
+            None => Self::Compact,
+        }
+    }
+
+    pub fn toggled(self) -> Self {
+        match self {
+            Self::Compact => Self::Full,
+            Self::Full => Self::Compact,
+        }
+    }
+}
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct DayLayout {
     pub date: NaiveDate,
     pub is_today: bool,
+    pub now: Option<NaiveTime>,
+    /// All-day and multi-day events, drawn as a banner above the hours.
+    pub all_day: Vec<EventEntry>,
     pub hours: Vec<HourBlock>,
 }
 
@@ -23,37 +63,84 @@ pub struct EventEntry {
     pub duration_minutes: i64,
     pub location: Option<String>,
     pub description: Option<String>,
+    pub color: Option<String>,
+    pub status: EventStatus,
+    pub declined: bool,
+    /// Minutes to get here from the previous event's location, when that
+    /// is less than the travel buffer.
+    pub travel_gap: Option<i64>,
+    /// Double-booked with another event that day.
+    pub overlapping: bool,
+    pub is_selected: bool,
 }
 
 pub fn calculate_layout(state: &AppState) -> DayLayout {
     let date = state.selected_date;
-    let today = chrono::Local::now().date_naive();
+    let now = state.local_now();
+    let today = now.date();
     let events = state.get_events_for_date(date);
 
-    let hours = build_hour_blocks(&events);
+    let selected_event_id = state.get_selected_event().map(|e| e.id.as_str());
+    let (banners, timed): (Vec<&Event>, Vec<&Event>) = events
+        .into_iter()
+        .partition(|e| e.all_day || e.spans_multiple_days(&state.timezone));
+    let all_day = banners.iter()
+        .map(|e| event_entry(e, &state.timezone, selected_event_id))
+        .collect();
+    let mut hours = build_hour_blocks(&timed, &state.timezone, selected_event_id);
+    let travel_gaps = state.travel_gaps(date, date);
+    let overlapping = state.overlapping_on(date);
+    for entry in hours.iter_mut().flat_map(|block| block.events.iter_mut()) {
+        entry.travel_gap = travel_gaps.get(&entry.event_id).copied();
+        entry.overlapping = overlapping.contains(&entry.event_id);
+    }
 
     DayLayout {
         date,
         is_today: date == today,
+        now: (date == today).then(|| now.time()),
+        all_day,
         hours,
     }
 }
 
-fn build_hour_blocks(events: &[&Event]) -> Vec<HourBlock> {
+impl DayLayout {
+    /// The hour blocks to draw: every hour in the full grid, otherwise only
+    /// those with events.
+    pub fn visible_hours(&self, grid: DayGrid) -> impl Iterator<Item = &HourBlock> {
+        self.hours.iter().filter(move |h| grid == DayGrid::Full || !h.events.is_empty())
+    }
+}
+
+fn event_entry(e: &Event, tz: &DisplayTimezone, selected_event_id: Option<&str>) -> EventEntry {
+    EventEntry {
+        event_id: e.id.clone(),
+        title: e.title.clone(),
+        start_minute: e.local_start(tz).minute(),
+        duration_minutes: e.duration_minutes(),
+        location: e.location.clone(),
+        description: e.description.clone(),
+        color: e.color.clone(),
+        status: e.status.clone(),
+        declined: e.is_declined(),
+        travel_gap: None,
+        overlapping: false,
+        is_selected: selected_event_id == Some(e.id.as_str()),
+    }
+}
+
+fn build_hour_blocks(
+    events: &[&Event],
+    tz: &DisplayTimezone,
+    selected_event_id: Option<&str>,
+) -> Vec<HourBlock> {
     let mut blocks = Vec::new();
 
     for hour in 0..24 {
         let hour_events: Vec<EventEntry> = events
             .iter()
-            .filter(|e| e.start.hour() == hour)
-            .map(|e| EventEntry {
-                event_id: e.id.clone(),
-                title: e.title.clone(),
-                start_minute: e.start.minute(),
-                duration_minutes: e.duration_minutes(),
-                location: e.location.clone(),
-                description: e.description.clone(),
-            })
+            .filter(|e| e.local_start(tz).hour() == hour)
+            .map(|e| event_entry(e, tz, selected_event_id))
             .collect();
 
         blocks.push(HourBlock {
@@ -68,8 +155,9 @@ fn build_hour_blocks(events: &[&Event]) -> Vec<HourBlock> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use chrono::Utc;
-    use crate::calendar::{Event, EventStatus, DEFAULT_CALENDAR_ID};
+    use chrono::{TimeZone, Utc};
+    use crate::calendar::{DisplayTimezone, Event, Transparency, Visibility, DEFAULT_CALENDAR_ID};
+    use crate::util::clock::FixedClock;
 
     fn date(year: i32, month: u32, day: u32) -> NaiveDate {
         NaiveDate::from_ymd_opt(year, month, day).unwrap()
@@ -101,6 +189,17 @@ mod tests {
             status: EventStatus::Confirmed,
             last_modified: Utc::now(),
             html_link: None,
+            time_zone: None,
+            conference_url: None,
+            color_id: None,
+            color: None,
+            self_response: None,
+            attendee_responses: Default::default(),
+            visibility: Visibility::Default,
+            transparency: Transparency::Opaque,
+            etag: None,
+            organizer: None,
+            creator: None,
         }
     }
 
@@ -196,6 +295,20 @@ mod tests {
         assert_eq!(hour_10.events.len(), 2);
     }
 
+    #[test]
+    fn selected_event_entry_is_marked() {
+        let mut state = AppState::new();
+        state.selected_date = date(2025, 1, 15);
+        state.add_event(create_event("e1", "Early", date(2025, 1, 15), 8, 0, 30));
+        state.add_event(create_event("e2", "Late", date(2025, 1, 15), 16, 0, 30));
+        state.selected_event_index = 1;
+
+        let layout = calculate_layout(&state);
+
+        assert!(!layout.hours[8].events[0].is_selected);
+        assert!(layout.hours[16].events[0].is_selected);
+    }
+
     #[test]
     fn empty_hours_have_no_events() {
         let mut state = AppState::new();
@@ -209,14 +322,27 @@ mod tests {
 
     #[test]
     fn is_today_flag_set_correctly() {
-        let mut state = AppState::new();
-        state.selected_date = chrono::Local::now().date_naive();
+        let state = AppState::new()
+            .with_timezone(DisplayTimezone::Named(chrono_tz::UTC))
+            .with_clock(FixedClock(Utc.with_ymd_and_hms(2025, 1, 15, 9, 30, 0).unwrap()));
+        assert_eq!(state.selected_date, date(2025, 1, 15));
 
         let layout = calculate_layout(&state);
 
         assert!(layout.is_today);
     }
 
+    #[test]
+    fn now_is_only_set_for_today() {
+        let mut state = AppState::new()
+            .with_timezone(DisplayTimezone::Named(chrono_tz::UTC))
+            .with_clock(FixedClock(Utc.with_ymd_and_hms(2025, 1, 15, 9, 30, 0).unwrap()));
+        assert_eq!(calculate_layout(&state).now, Some(NaiveTime::from_hms_opt(9, 30, 0).unwrap()));
+
+        state.selected_date = date(2025, 1, 1);
+        assert!(calculate_layout(&state).now.is_none());
+    }
+
     #[test]
     fn is_today_flag_false_for_other_days() {
         let mut state = AppState::new();
@@ -226,4 +352,90 @@ mod tests {
 
         assert!(!layout.is_today);
     }
+
+    #[test]
+    fn all_day_events_go_to_the_banner() {
+        let mut state = AppState::new();
+        state.selected_date = date(2025, 1, 15);
+        let mut holiday = create_event("h1", "Holiday", date(2025, 1, 15), 0, 0, 24 * 60);
+        holiday.all_day = true;
+        state.add_event(holiday);
+        state.add_event(create_event("e1", "Standup", date(2025, 1, 15), 9, 0, 15));
+
+        let layout = calculate_layout(&state);
+
+        assert_eq!(layout.all_day.len(), 1);
+        assert_eq!(layout.all_day[0].title, "Holiday");
+        assert!(layout.hours[0].events.is_empty());
+        assert_eq!(layout.hours[9].events.len(), 1);
+    }
+
+    #[test]
+    fn events_spanning_midnight_go_to_the_banner() {
+        let mut state = AppState::new()
+            .with_timezone(DisplayTimezone::Named(chrono_tz::UTC));
+        state.selected_date = date(2025, 1, 16);
+        state.add_event(create_event("e1", "Overnight deploy", date(2025, 1, 15), 22, 0, 240));
+
+        let layout = calculate_layout(&state);
+
+        assert_eq!(layout.all_day.len(), 1);
+        assert!(layout.hours.iter().all(|h| h.events.is_empty()));
+    }
+
+    #[test]
+    fn full_grid_shows_empty_hours() {
+        let mut state = AppState::new();
+        state.selected_date = date(2025, 1, 15);
+        state.add_event(create_event("e1", "Standup", date(2025, 1, 15), 9, 0, 15));
+        state.add_event(create_event("e2", "Review", date(2025, 1, 15), 14, 0, 60));
+
+        let layout = calculate_layout(&state);
+        let compact: Vec<u32> = layout.visible_hours(DayGrid::Compact).map(|h| h.hour).collect();
+
+        assert_eq!(compact, vec![9, 14]);
+        assert_eq!(layout.visible_hours(DayGrid::Full).count(), 24);
+    }
+
+    #[test]
+    fn tight_hops_between_places_are_flagged_when_a_buffer_is_set() {
+        let mut state = AppState::new();
+        state.selected_date = date(2025, 1, 15);
+        let mut office = create_event("e1", "Standup", date(2025, 1, 15), 9, 0, 60);
+        office.location = Some("Office".to_string());
+        let mut cafe = create_event("e2", "Lunch", date(2025, 1, 15), 10, 10, 60);
+        cafe.location = Some("Café".to_string());
+        state.add_event(office);
+        state.add_event(cafe);
+
+        assert_eq!(calculate_layout(&state).hours[10].events[0].travel_gap, None);
+
+        state.travel_buffer_minutes = Some(15);
+        let layout = calculate_layout(&state);
+        assert_eq!(layout.hours[9].events[0].travel_gap, None);
+        assert_eq!(layout.hours[10].events[0].travel_gap, Some(10));
+    }
+
+    #[test]
+    fn double_booked_events_are_marked() {
+        let mut state = AppState::new();
+        state.selected_date = date(2025, 1, 15);
+        state.add_event(create_event("e1", "Standup", date(2025, 1, 15), 9, 0, 30));
+        state.add_event(create_event("e2", "Interview", date(2025, 1, 15), 9, 15, 60));
+        state.add_event(create_event("e3", "Lunch", date(2025, 1, 15), 12, 0, 60));
+
+        let layout = calculate_layout(&state);
+
+        assert!(layout.hours[9].events.iter().all(|e| e.overlapping));
+        assert!(!layout.hours[12].events[0].overlapping);
+        assert_eq!(state.overlapping_on(date(2025, 1, 15)).len(), 2);
+    }
+
+    #[test]
+    fn day_grid_from_config_and_toggle() {
+        assert_eq!(DayGrid::from_config(Some("full")), DayGrid::Full);
+        assert_eq!(DayGrid::from_config(Some("bogus")), DayGrid::Compact);
+        assert_eq!(DayGrid::from_config(None), DayGrid::Compact);
+        assert_eq!(DayGrid::Compact.toggled(), DayGrid::Full);
+    }
 }
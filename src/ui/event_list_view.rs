@@ -0,0 +1,75 @@
+use crate::calendar::Event;
+
+/// Lines an event takes in the list pane: the title line, the location
+/// when there is one, and a spacer.
+pub fn event_height(event: &Event) -> usize {
+    if event.location.is_some() { 3 } else { 2 }
+}
+
+/// First event to draw so that `selected` fits in `visible_lines`, moving the
+/// current `scroll` as little as possible.
+pub fn scroll_for_selection(heights: &[usize], selected: usize, scroll: usize, visible_lines: usize) -> usize {
+    if heights.is_empty() || visible_lines == 0 {
+        return 0;
+    }
+    let selected = selected.min(heights.len() - 1);
+    if selected < scroll {
+        return selected;
+    }
+
+    let mut first = scroll;
+    while first < selected && heights[first..=selected].iter().sum::<usize>() > visible_lines {
+        first += 1;
+    }
+    first
+}
+
+/// How many events from `first` on fit in `visible_lines`; always at least one
+/// so paging still moves on a tiny pane.
+pub fn events_per_page(heights: &[usize], first: usize, visible_lines: usize) -> usize {
+    let mut used = 0;
+    let count = heights.iter()
+        .skip(first)
+        .take_while(|&&height| {
+            used += height;
+            used <= visible_lines
+        })
+        .count();
+    count.max(1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scroll_follows_selection_down_and_up() {
+        let heights = [2, 3, 2, 2, 3, 2];
+
+        assert_eq!(scroll_for_selection(&heights, 2, 0, 7), 0);
+        assert_eq!(scroll_for_selection(&heights, 3, 0, 7), 1);
+        assert_eq!(scroll_for_selection(&heights, 5, 1, 7), 3);
+        assert_eq!(scroll_for_selection(&heights, 4, 3, 7), 3);
+        assert_eq!(scroll_for_selection(&heights, 1, 3, 7), 1);
+    }
+
+    #[test]
+    fn scroll_resets_without_room_or_events() {
+        assert_eq!(scroll_for_selection(&[], 3, 2, 10), 0);
+        assert_eq!(scroll_for_selection(&[2, 2], 1, 1, 0), 0);
+    }
+
+    #[test]
+    fn selection_taller_than_pane_is_drawn_first() {
+        assert_eq!(scroll_for_selection(&[2, 3, 2], 1, 0, 2), 1);
+    }
+
+    #[test]
+    fn page_counts_events_that_fit() {
+        let heights = [2, 3, 2, 2, 3];
+
+        assert_eq!(events_per_page(&heights, 0, 7), 3);
+        assert_eq!(events_per_page(&heights, 3, 100), 2);
+        assert_eq!(events_per_page(&heights, 1, 1), 1);
+    }
+}
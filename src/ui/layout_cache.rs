@@ -0,0 +1,120 @@
+use std::cell::RefCell;
+use std::collections::HashSet;
+
+use chrono::{NaiveDate, NaiveDateTime, Timelike};
+
+use crate::app::{AppState, DeclinedEvents, ViewType};
+use crate::calendar::DisplayTimezone;
+use crate::ui::month_view::{self, MonthLayout};
+use crate::ui::week_view::{self, WeekLayout, SLOT_MINUTES};
+
+/// Month and week layouts from the last frame, reused until something they
+/// are built from changes.
+#[derive(Debug, Default)]
+pub struct LayoutCache {
+    month: RefCell<Option<(LayoutKey, MonthLayout)>>,
+    week: RefCell<Option<(LayoutKey, WeekLayout)>>,
+}
+
+/// Everything a month or week layout depends on.
+#[derive(Debug, Clone, PartialEq)]
+struct LayoutKey {
+    view: ViewType,
+    selected_date: NaiveDate,
+    selected_event_index: usize,
+    events_version: u64,
+    timezone: DisplayTimezone,
+    hidden_calendars: HashSet<String>,
+    declined_events: DeclinedEvents,
+    /// The current time rounded down to a week slot, for today and the now line.
+    now_slot: NaiveDateTime,
+}
+
+impl LayoutKey {
+    fn of(state: &AppState) -> Self {
+        let now = state.local_now();
+        let now_slot = now
+            .with_minute(now.minute() - now.minute() % SLOT_MINUTES)
+            .and_then(|t| t.with_second(0))
+            .and_then(|t| t.with_nanosecond(0))
+            .unwrap_or(now);
+        Self {
+            view: state.view.clone(),
+            selected_date: state.selected_date,
+            selected_event_index: state.selected_event_index,
+            events_version: state.events_version(),
+            timezone: state.timezone,
+            hidden_calendars: state.hidden_calendars.clone(),
+            declined_events: state.declined_events,
+            now_slot,
+        }
+    }
+}
+
+impl LayoutCache {
+    pub fn month(&self, state: &AppState) -> MonthLayout {
+        cached(&self.month, LayoutKey::of(state), || month_view::calculate_layout(state))
+    }
+
+    pub fn week(&self, state: &AppState) -> WeekLayout {
+        cached(&self.week, LayoutKey::of(state), || week_view::calculate_layout(state))
+    }
+
+    /// Drops both layouts, for settings the key does not cover such as working hours.
+    pub fn clear(&self) {
+        self.month.borrow_mut().take();
+        self.week.borrow_mut().take();
+    }
+}
+
+fn cached<T: Clone>(slot: &RefCell<Option<(LayoutKey, T)>>, key: LayoutKey, build: impl FnOnce() -> T) -> T {
+    let mut slot = slot.borrow_mut();
+    match slot.as_ref() {
+        Some((cached_key, layout)) if *cached_key == key => layout.clone(),
+        _ => {
+            let layout = build();
+            *slot = Some((key, layout.clone()));
+            layout
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::EventBuilder;
+    use crate::calendar::Event;
+
+    fn event_on(id: &str, date: NaiveDate) -> Event {
+        let start = date.and_hms_opt(10, 0, 0).unwrap().and_utc();
+        EventBuilder::new(id).title(id).at(start, chrono::Duration::hours(1)).build()
+    }
+
+    #[test]
+    fn month_layout_is_rebuilt_when_events_change() {
+        let date = NaiveDate::from_ymd_opt(2025, 1, 15).unwrap();
+        let mut state = AppState::new();
+        state.selected_date = date;
+        let event_count = |state: &AppState| -> usize {
+            state.layout_cache.month(state).weeks.iter().flat_map(|w| &w.days).map(|d| d.event_count).sum()
+        };
+
+        assert_eq!(event_count(&state), 0);
+        state.add_event(event_on("e1", date));
+        assert_eq!(event_count(&state), 1);
+        state.remove_event("e1");
+        assert_eq!(event_count(&state), 0);
+    }
+
+    #[test]
+    fn week_layout_is_reused_until_the_selection_moves() {
+        let mut state = AppState::new();
+        state.selected_date = NaiveDate::from_ymd_opt(2025, 1, 15).unwrap();
+
+        let first = state.layout_cache.week(&state);
+        assert_eq!(state.layout_cache.week(&state), first);
+
+        state.selected_date = NaiveDate::from_ymd_opt(2025, 1, 22).unwrap();
+        assert_ne!(state.layout_cache.week(&state).week_start, first.week_start);
+    }
+}
@@ -2,4 +2,11 @@ pub mod month_view;
 pub mod week_view;
 pub mod day_view;
 pub mod year_view;
+pub mod event_list_view;
+pub mod layout_cache;
 pub mod theme;
+pub mod time_format;
+pub mod statusline;
+pub mod stats;
+pub mod url_hints;
+pub mod view_export;
@@ -1,5 +1,38 @@
-use chrono::{Datelike, NaiveDate, Weekday};
+use chrono::{Datelike, NaiveDate};
 use crate::app::AppState;
+use crate::ui::week_view::weekday_column;
+
+/// How a month cell shows that a day has events (`ui.month_cell_style`).
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum MonthCellStyle {
+    #[default]
+    Underline,
+    Dots,
+    Count,
+}
+
+const DENSITY_DOTS: [&str; 4] = ["\u{b7}", ":", "\u{205d}", "\u{205e}"];
+
+impl MonthCellStyle {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.trim().to_lowercase().as_str() {
+            "underline" => Some(Self::Underline),
+            "dots" | "density" => Some(Self::Dots),
+            "count" => Some(Self::Count),
+            _ => None,
+        }
+    }
+
+    pub fn from_config(name: Option<&str>) -> Self {
+        match name {
+            Some(name) => Self::from_name(name).unwrap_or_else(|| {
+                tracing::warn!("Unknown month cell style '{}', using underline", name);
+                Self::Underline
+            }),
+            None => Self::Underline,
+        }
+    }
+}
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct MonthLayout {
@@ -10,16 +43,40 @@ pub struct MonthLayout {
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct Week {
+    pub iso_week: u32,
     pub days: Vec<DayCell>,
 }
 
+impl Week {
+    fn new() -> Self {
+        Self { iso_week: 0, days: Vec::new() }
+    }
+
+    fn finish(mut self) -> Self {
+        if let Some(date) = self.days.iter().find_map(|c| c.date) {
+            self.iso_week = date.iso_week().week();
+        }
+        self
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct DayCell {
     pub date: Option<NaiveDate>,
     pub is_selected: bool,
     pub is_today: bool,
     pub has_events: bool,
+    pub event_count: usize,
+    pub busy_minutes: i64,
     pub is_current_month: bool,
+    /// The multi-day event drawn as a bar through this cell.
+    pub span_bar: Option<SpanBar>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpanBar {
+    pub event_id: String,
+    pub color: Option<String>,
 }
 
 impl DayCell {
@@ -29,7 +86,10 @@ impl DayCell {
             is_selected: false,
             is_today: false,
             has_events: false,
+            event_count: 0,
+            busy_minutes: 0,
             is_current_month: true,
+            span_bar: None,
         }
     }
 
@@ -48,16 +108,54 @@ impl DayCell {
         self
     }
 
+    pub fn with_busy(mut self, event_count: usize, busy_minutes: i64) -> Self {
+        self.has_events = event_count > 0;
+        self.event_count = event_count;
+        self.busy_minutes = busy_minutes;
+        self
+    }
+
+    /// 1-4, from whichever is higher: the event count or busy hours
+    /// (up to 1h, 3h, 6h, more).
+    pub fn density_level(&self) -> usize {
+        if self.event_count == 0 {
+            return 0;
+        }
+        let by_hours = match self.busy_minutes {
+            ..=60 => 1,
+            61..=180 => 2,
+            181..=360 => 3,
+            _ => 4,
+        };
+        self.event_count.min(4).max(by_hours)
+    }
+
+    /// Up to two characters drawn after the day number.
+    pub fn marker(&self, style: MonthCellStyle) -> String {
+        match style {
+            _ if self.event_count == 0 => String::new(),
+            MonthCellStyle::Underline => String::new(),
+            MonthCellStyle::Dots => DENSITY_DOTS[self.density_level() - 1].to_string(),
+            MonthCellStyle::Count if self.event_count > 9 => "9+".to_string(),
+            MonthCellStyle::Count => self.event_count.to_string(),
+        }
+    }
+
     pub fn with_current_month(mut self, current_month: bool) -> Self {
         self.is_current_month = current_month;
         self
     }
+
+    pub fn with_span_bar(mut self, span_bar: Option<SpanBar>) -> Self {
+        self.span_bar = span_bar;
+        self
+    }
 }
 
 pub fn calculate_layout(state: &AppState) -> MonthLayout {
     let year = state.selected_date.year();
     let month = state.selected_date.month();
-    let today = chrono::Local::now().date_naive();
+    let today = state.today();
 
     let Some(first_day) = NaiveDate::from_ymd_opt(year, month, 1) else {
         return MonthLayout { year, month, weeks: Vec::new() };
@@ -74,10 +172,10 @@ pub fn calculate_layout(state: &AppState) -> MonthLayout {
     };
 
     let mut weeks = Vec::new();
-    let mut current_week = Week { days: Vec::new() };
+    let mut current_week = Week::new();
 
     let start_weekday = first_day.weekday();
-    let days_before = start_weekday.num_days_from_monday() as i64;
+    let days_before = weekday_column(start_weekday, state.week_start) as i64;
 
     for i in 0..days_before {
         let prev_date = first_day.pred_opt()
@@ -91,19 +189,30 @@ pub fn calculate_layout(state: &AppState) -> MonthLayout {
 
     let mut current_date = first_day;
     while current_date <= last_day {
-        let has_events = !state.get_events_for_date(current_date).is_empty();
+        let events = state.get_events_for_date(current_date);
+        let busy_minutes = events.iter()
+            .filter(|event| !event.all_day && event.is_busy())
+            .map(|event| event.duration_minutes())
+            .sum();
+        // The earliest, longest multi-day event keeps its bar unbroken
+        // across the days it shares with shorter ones.
+        let span_bar = events.iter()
+            .filter(|event| event.spans_multiple_days(&state.timezone))
+            .min_by_key(|event| (event.start, std::cmp::Reverse(event.end)))
+            .map(|event| SpanBar { event_id: event.id.clone(), color: event.color.clone() });
 
         let cell = DayCell::new(Some(current_date))
             .with_selected(current_date == state.selected_date)
             .with_today(current_date == today)
-            .with_events(has_events)
-            .with_current_month(true);
+            .with_busy(events.len(), busy_minutes)
+            .with_current_month(true)
+            .with_span_bar(span_bar);
 
         current_week.days.push(cell);
 
-        if current_date.weekday() == Weekday::Sun {
-            weeks.push(current_week);
-            current_week = Week { days: Vec::new() };
+        if current_week.days.len() == 7 {
+            weeks.push(current_week.finish());
+            current_week = Week::new();
         }
 
         let Some(next) = current_date.succ_opt() else { break };
@@ -120,7 +229,7 @@ pub fn calculate_layout(state: &AppState) -> MonthLayout {
             let Some(next) = current_date.succ_opt() else { break };
             current_date = next;
         }
-        weeks.push(current_week);
+        weeks.push(current_week.finish());
     }
 
     MonthLayout { year, month, weeks }
@@ -130,7 +239,7 @@ pub fn calculate_layout(state: &AppState) -> MonthLayout {
 mod tests {
     use super::*;
     use chrono::Utc;
-    use crate::calendar::{Event, EventStatus, DEFAULT_CALENDAR_ID};
+    use crate::calendar::{Event, EventStatus, Transparency, Visibility, DEFAULT_CALENDAR_ID};
 
     fn date(year: i32, month: u32, day: u32) -> NaiveDate {
         NaiveDate::from_ymd_opt(year, month, day).unwrap()
@@ -152,6 +261,17 @@ mod tests {
             status: EventStatus::Confirmed,
             last_modified: Utc::now(),
             html_link: None,
+            time_zone: None,
+            conference_url: None,
+            color_id: None,
+            color: None,
+            self_response: None,
+            attendee_responses: Default::default(),
+            visibility: Visibility::Default,
+            transparency: Transparency::Opaque,
+            etag: None,
+            organizer: None,
+            creator: None,
         }
     }
 
@@ -209,6 +329,63 @@ mod tests {
         assert_eq!(event_cells.len(), 1);
     }
 
+    #[test]
+    fn multi_day_events_draw_a_bar_through_each_day() {
+        let mut state = AppState::new()
+            .with_timezone(crate::calendar::DisplayTimezone::Named(chrono_tz::UTC));
+        state.selected_date = date(2025, 1, 15);
+        let mut trip = create_event_on_date("trip", date(2025, 1, 10));
+        trip.end = trip.start + chrono::Duration::days(2);
+        state.add_event(trip);
+        state.add_event(create_event_on_date("standup", date(2025, 1, 11)));
+
+        let layout = calculate_layout(&state);
+        let bar_days: Vec<NaiveDate> = layout.weeks.iter()
+            .flat_map(|w| &w.days)
+            .filter(|c| c.span_bar.as_ref().is_some_and(|b| b.event_id == "trip"))
+            .filter_map(|c| c.date)
+            .collect();
+
+        assert_eq!(bar_days, vec![date(2025, 1, 10), date(2025, 1, 11), date(2025, 1, 12)]);
+    }
+
+    #[test]
+    fn cells_count_events_and_busy_minutes() {
+        let mut state = AppState::new();
+        state.selected_date = date(2025, 1, 15);
+        state.add_event(create_event_on_date("event1", date(2025, 1, 10)));
+        state.add_event(create_event_on_date("event2", date(2025, 1, 10)));
+
+        let layout = calculate_layout(&state);
+
+        let cell = layout.weeks.iter()
+            .flat_map(|w| &w.days)
+            .find(|c| c.date == Some(date(2025, 1, 10)))
+            .unwrap();
+        assert_eq!(cell.event_count, 2);
+        assert_eq!(cell.busy_minutes, 120);
+    }
+
+    #[test]
+    fn density_marker_uses_busier_of_count_and_hours() {
+        let light = DayCell::new(None).with_busy(1, 30);
+        let long_meeting = DayCell::new(None).with_busy(1, 300);
+        let many = DayCell::new(None).with_busy(12, 600);
+
+        assert_eq!(light.marker(MonthCellStyle::Dots), "\u{b7}");
+        assert_eq!(long_meeting.marker(MonthCellStyle::Dots), "\u{205d}");
+        assert_eq!(many.marker(MonthCellStyle::Count), "9+");
+        assert_eq!(many.marker(MonthCellStyle::Underline), "");
+        assert_eq!(DayCell::new(None).marker(MonthCellStyle::Dots), "");
+    }
+
+    #[test]
+    fn month_cell_style_from_config() {
+        assert_eq!(MonthCellStyle::from_config(Some("Count")), MonthCellStyle::Count);
+        assert_eq!(MonthCellStyle::from_config(Some("sparkles")), MonthCellStyle::Underline);
+        assert_eq!(MonthCellStyle::from_config(None), MonthCellStyle::Underline);
+    }
+
     #[test]
     fn each_week_has_seven_days() {
         let mut state = AppState::new();
@@ -221,6 +398,40 @@ mod tests {
         }
     }
 
+    #[test]
+    fn weeks_may_start_on_sunday() {
+        let mut state = AppState::new();
+        state.selected_date = date(2025, 1, 15);
+        state.week_start = chrono::Weekday::Sun;
+
+        let layout = calculate_layout(&state);
+
+        let firsts: Vec<Option<NaiveDate>> = layout.weeks.iter().map(|week| week.days[0].date).collect();
+        assert_eq!(firsts, vec![Some(date(2024, 12, 29)), Some(date(2025, 1, 5)), Some(date(2025, 1, 12)), Some(date(2025, 1, 19)), Some(date(2025, 1, 26))]);
+        assert!(layout.weeks.iter().all(|week| week.days.len() == 7));
+    }
+
+    #[test]
+    fn weeks_carry_iso_week_numbers() {
+        let mut state = AppState::new();
+        state.selected_date = date(2025, 1, 15);
+
+        let layout = calculate_layout(&state);
+
+        let numbers: Vec<u32> = layout.weeks.iter().map(|w| w.iso_week).collect();
+        assert_eq!(numbers, vec![1, 2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn iso_week_wraps_at_year_boundary() {
+        let mut state = AppState::new();
+        state.selected_date = date(2021, 1, 15);
+
+        let layout = calculate_layout(&state);
+
+        assert_eq!(layout.weeks[0].iso_week, 53);
+    }
+
     #[test]
     fn previous_month_days_marked_as_not_current() {
         let mut state = AppState::new();
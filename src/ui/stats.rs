@@ -0,0 +1,126 @@
+use chrono::{NaiveDate, NaiveDateTime};
+
+use crate::app::AppState;
+
+/// How full a day is, for the day view header and week view columns.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct DayStats {
+    pub events: usize,
+    /// Time covered by busy timed events, counting overlaps once.
+    pub busy_minutes: i64,
+    /// Working hours not covered by busy events.
+    pub free_working_minutes: i64,
+}
+
+impl DayStats {
+    /// "5 events · 4h15m busy · 3h45m free in working hours"
+    pub fn summary(&self) -> String {
+        format!(
+            "{} event{} · {} busy · {} free in working hours",
+            self.events,
+            if self.events == 1 { "" } else { "s" },
+            format_minutes(self.busy_minutes),
+            format_minutes(self.free_working_minutes),
+        )
+    }
+
+    /// "5 4h15" style, for narrow week view columns.
+    pub fn short(&self) -> String {
+        if self.events == 0 {
+            return String::new();
+        }
+        format!("{} {}", self.events, format_minutes(self.busy_minutes).trim_end_matches('m'))
+    }
+}
+
+/// "4h15m", "4h" or "45m".
+pub fn format_minutes(minutes: i64) -> String {
+    match (minutes / 60, minutes % 60) {
+        (0, m) => format!("{}m", m),
+        (h, 0) => format!("{}h", h),
+        (h, m) => format!("{}h{:02}m", h, m),
+    }
+}
+
+pub fn day_stats(state: &AppState, date: NaiveDate) -> DayStats {
+    let events = state.get_events_for_date(date);
+    let (Some(day_start), Some(day_end)) = (date.and_hms_opt(0, 0, 0), date.succ_opt().and_then(|d| d.and_hms_opt(0, 0, 0))) else {
+        return DayStats::default();
+    };
+
+    let mut busy: Vec<(NaiveDateTime, NaiveDateTime)> = events.iter()
+        .filter(|event| !event.all_day && event.is_busy() && !event.is_declined())
+        .map(|event| (event.local_start(&state.timezone).max(day_start), event.local_end(&state.timezone).min(day_end)))
+        .filter(|(start, end)| start < end)
+        .collect();
+    busy.sort();
+    let busy = merge(busy);
+
+    let work_start = date.and_time(state.working_hours.start);
+    let work_end = date.and_time(state.working_hours.end);
+    let busy_in_work: i64 = busy.iter()
+        .map(|&(start, end)| (end.min(work_end) - start.max(work_start)).num_minutes().max(0))
+        .sum();
+
+    DayStats {
+        events: events.len(),
+        busy_minutes: busy.iter().map(|(start, end)| (*end - *start).num_minutes()).sum(),
+        free_working_minutes: (work_end - work_start).num_minutes() - busy_in_work,
+    }
+}
+
+/// Joins overlapping or touching intervals of a sorted list.
+fn merge(sorted: Vec<(NaiveDateTime, NaiveDateTime)>) -> Vec<(NaiveDateTime, NaiveDateTime)> {
+    let mut merged: Vec<(NaiveDateTime, NaiveDateTime)> = Vec::with_capacity(sorted.len());
+    for (start, end) in sorted {
+        match merged.last_mut() {
+            Some(last) if start <= last.1 => last.1 = last.1.max(end),
+            _ => merged.push((start, end)),
+        }
+    }
+    merged
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::EventBuilder;
+    use chrono::{Duration, TimeZone, Utc};
+    use crate::calendar::{DisplayTimezone, Event, Transparency};
+
+    fn event(id: &str, hour: u32, minute: u32, minutes: i64) -> Event {
+        let start = Utc.with_ymd_and_hms(2025, 1, 15, hour, minute, 0).unwrap();
+        EventBuilder::new(id).title(id).at(start, Duration::minutes(minutes)).build()
+    }
+
+    fn date() -> NaiveDate {
+        NaiveDate::from_ymd_opt(2025, 1, 15).unwrap()
+    }
+
+    #[test]
+    fn overlapping_events_count_once_and_free_time_stays_in_working_hours() {
+        let mut state = AppState::new().with_timezone(DisplayTimezone::Named(chrono_tz::UTC));
+        state.add_event(event("standup", 9, 0, 60));
+        state.add_event(event("overlap", 9, 30, 60));
+        state.add_event(event("evening", 18, 0, 45));
+        let mut free = event("focus", 13, 0, 120);
+        free.transparency = Transparency::Transparent;
+        state.add_event(free);
+
+        let stats = day_stats(&state, date());
+
+        assert_eq!(stats.events, 4);
+        assert_eq!(stats.busy_minutes, 90 + 45);
+        assert_eq!(stats.free_working_minutes, 8 * 60 - 90);
+        assert_eq!(stats.summary(), "4 events · 2h15m busy · 6h30m free in working hours");
+        assert_eq!(stats.short(), "4 2h15");
+    }
+
+    #[test]
+    fn formats_minutes() {
+        assert_eq!(format_minutes(45), "45m");
+        assert_eq!(format_minutes(240), "4h");
+        assert_eq!(format_minutes(255), "4h15m");
+        assert_eq!(DayStats::default().short(), "");
+    }
+}
@@ -0,0 +1,116 @@
+use std::fmt::Write;
+
+use chrono::NaiveDate;
+
+use crate::app::AppState;
+
+/// What the status bar showed before `ui.statusline` existed.
+pub const DEFAULT_FORMAT: &str = "Events: %events%overlaps | Sync: %sync%readonly%recording | Press 'q' to quit, '?' for help";
+
+/// Expands a vim-style statusline, e.g. `"%date %view %sync %events"`.
+/// `%%` is a literal percent sign and unknown items are kept as typed.
+pub fn render(format: &str, state: &AppState) -> String {
+    let mut out = String::with_capacity(format.len());
+    let mut rest = format;
+    while let Some(start) = rest.find('%') {
+        out.push_str(&rest[..start]);
+        let after = &rest[start + 1..];
+        if let Some(tail) = after.strip_prefix('%') {
+            out.push('%');
+            rest = tail;
+            continue;
+        }
+
+        let len = after.find(|c: char| !(c.is_ascii_alphanumeric() || c == '_')).unwrap_or(after.len());
+        let (name, tail) = after.split_at(len);
+        match item(name, state) {
+            Some(value) => out.push_str(&value),
+            None => {
+                out.push('%');
+                out.push_str(name);
+            }
+        }
+        rest = tail;
+    }
+    out.push_str(rest);
+    out
+}
+
+fn item(name: &str, state: &AppState) -> Option<String> {
+    let value = match name {
+        "date" => format_date(state.selected_date, &state.ui_config.date_format),
+        "today" => format_date(state.today(), &state.ui_config.date_format),
+        "time" => state.time_format.format(state.local_now().time()),
+        "view" => state.view.name().to_string(),
+        "mode" => format!("{:?}", state.mode),
+        "sync" => state.sync.summary(state.now()),
+        "events" => state.events.len().to_string(),
+        "day_events" => state.get_events_for_date(state.selected_date).len().to_string(),
+        "calendar" => state.default_calendar.clone(),
+        // Carries its own separator so the default line reads as before.
+        "readonly" if state.read_only => " | read-only".to_string(),
+        "readonly" => String::new(),
+        "overlaps" => match state.overlapping_on(state.selected_date).len() {
+            0 => String::new(),
+            count => format!(" ({} overlapping)", count),
+        },
+        "recording" => state.macros.recording()
+            .map(|register| format!(" | recording @{}", register))
+            .unwrap_or_default(),
+        _ => return None,
+    };
+    Some(value)
+}
+
+/// A bad `ui.date_format` falls back to ISO instead of panicking in `to_string`.
+fn format_date(date: NaiveDate, format: &str) -> String {
+    let mut out = String::new();
+    match write!(out, "{}", date.format(format)) {
+        Ok(()) => out,
+        Err(_) => date.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{TimeZone, Utc};
+    use crate::app::ViewType;
+    use crate::util::clock::FixedClock;
+
+    fn state() -> AppState {
+        let mut state = AppState::new().with_clock(FixedClock(Utc.with_ymd_and_hms(2025, 1, 15, 8, 30, 0).unwrap()));
+        state.selected_date = NaiveDate::from_ymd_opt(2025, 1, 17).unwrap();
+        state.view = ViewType::Week;
+        state
+    }
+
+    #[test]
+    fn expands_items() {
+        let state = state();
+
+        assert_eq!(render("%date %view %mode %events", &state), "2025-01-17 Week Normal 0");
+        assert_eq!(render("[%calendar] %today", &state), "[primary] 2025-01-15");
+    }
+
+    #[test]
+    fn default_format_matches_the_old_status_bar() {
+        let mut state = state();
+        assert_eq!(render(DEFAULT_FORMAT, &state), "Events: 0 | Sync: Not synced | Press 'q' to quit, '?' for help");
+
+        state.read_only = true;
+        assert!(render(DEFAULT_FORMAT, &state).contains("Not synced | read-only |"));
+    }
+
+    #[test]
+    fn percent_signs_and_unknown_items_pass_through() {
+        let state = state();
+
+        assert_eq!(render("100%% %bogus %view", &state), "100% %bogus Week");
+        assert_eq!(render("trailing %", &state), "trailing %");
+
+        let mut state = state;
+        state.ui_config.date_format = "%Q".to_string();
+        assert_eq!(render("%date", &state), "2025-01-17");
+    }
+}
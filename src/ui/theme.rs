@@ -1,4 +1,6 @@
-use ratatui::style::Color;
+use ratatui::style::{Color, Modifier};
+
+use crate::calendar::EventStatus;
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct Theme {
@@ -16,6 +18,9 @@ pub struct Theme {
     pub command_mode: Color,
     pub error: Color,
     pub success: Color,
+    pub now_line: Color,
+    /// Year view heatmap, from the quietest day to the busiest.
+    pub heatmap: [Color; 4],
 }
 
 impl Theme {
@@ -35,6 +40,8 @@ impl Theme {
             command_mode: Color::White,
             error: Color::Red,
             success: Color::Green,
+            now_line: Color::LightRed,
+            heatmap: [Color::Rgb(14, 68, 41), Color::Rgb(0, 109, 50), Color::Rgb(38, 166, 65), Color::Rgb(57, 211, 83)],
         }
     }
 
@@ -54,6 +61,8 @@ impl Theme {
             command_mode: Color::Rgb(235, 219, 178),
             error: Color::Rgb(251, 73, 52),
             success: Color::Rgb(184, 187, 38),
+            now_line: Color::Rgb(251, 73, 52),
+            heatmap: [Color::Rgb(121, 116, 14), Color::Rgb(152, 151, 26), Color::Rgb(184, 187, 38), Color::Rgb(250, 189, 47)],
         }
     }
 
@@ -73,6 +82,8 @@ impl Theme {
             command_mode: Color::Rgb(216, 222, 233),
             error: Color::Rgb(191, 97, 106),
             success: Color::Rgb(163, 190, 140),
+            now_line: Color::Rgb(208, 135, 112),
+            heatmap: [Color::Rgb(76, 86, 106), Color::Rgb(94, 129, 172), Color::Rgb(129, 161, 193), Color::Rgb(136, 192, 208)],
         }
     }
 
@@ -92,6 +103,8 @@ impl Theme {
             command_mode: Color::Rgb(248, 248, 242),
             error: Color::Rgb(255, 85, 85),
             success: Color::Rgb(80, 250, 123),
+            now_line: Color::Rgb(255, 184, 108),
+            heatmap: [Color::Rgb(98, 114, 164), Color::Rgb(189, 147, 249), Color::Rgb(255, 121, 198), Color::Rgb(255, 85, 85)],
         }
     }
 
@@ -111,6 +124,8 @@ impl Theme {
             command_mode: Color::Rgb(147, 161, 161),
             error: Color::Rgb(220, 50, 47),
             success: Color::Rgb(133, 153, 0),
+            now_line: Color::Rgb(203, 75, 22),
+            heatmap: [Color::Rgb(88, 110, 117), Color::Rgb(38, 139, 210), Color::Rgb(42, 161, 152), Color::Rgb(133, 153, 0)],
         }
     }
 
@@ -130,17 +145,24 @@ impl Theme {
             command_mode: Color::Rgb(248, 248, 240),
             error: Color::Rgb(249, 38, 114),
             success: Color::Rgb(166, 226, 46),
+            now_line: Color::Rgb(253, 151, 31),
+            heatmap: [Color::Rgb(117, 113, 94), Color::Rgb(230, 219, 116), Color::Rgb(253, 151, 31), Color::Rgb(249, 38, 114)],
         }
     }
 
     pub fn get_by_name(name: &str) -> Self {
+        Self::find_by_name(name).unwrap_or_default()
+    }
+
+    pub fn find_by_name(name: &str) -> Option<Self> {
         match name.to_lowercase().as_str() {
-            "gruvbox" => Self::gruvbox(),
-            "nord" => Self::nord(),
-            "dracula" => Self::dracula(),
-            "solarized-dark" | "solarized" => Self::solarized_dark(),
-            "monokai" => Self::monokai(),
-            _ => Self::default_theme(),
+            "default" => Some(Self::default_theme()),
+            "gruvbox" => Some(Self::gruvbox()),
+            "nord" => Some(Self::nord()),
+            "dracula" => Some(Self::dracula()),
+            "solarized-dark" | "solarized" => Some(Self::solarized_dark()),
+            "monokai" => Some(Self::monokai()),
+            _ => None,
         }
     }
 
@@ -149,8 +171,83 @@ impl Theme {
     }
 }
 
+/// Parses a Google `#rrggbb` color.
+pub fn parse_hex_color(hex: &str) -> Option<Color> {
+    let hex = hex.strip_prefix('#')?;
+    if hex.len() != 6 || !hex.is_ascii() {
+        return None;
+    }
+    let channel = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).ok();
+    Some(Color::Rgb(channel(0)?, channel(2)?, channel(4)?))
+}
+
+/// Black or white, whichever reads better on `background`.
+pub fn contrasting_text(background: Color) -> Color {
+    match background {
+        Color::Rgb(r, g, b) if 299 * r as u32 + 587 * g as u32 + 114 * b as u32 > 140_000 => Color::Black,
+        _ => Color::White,
+    }
+}
+
+/// Tentative events are italic; cancelled ones are dimmed and struck out.
+pub fn status_modifier(status: &EventStatus) -> Modifier {
+    match status {
+        EventStatus::Confirmed => Modifier::empty(),
+        EventStatus::Tentative => Modifier::ITALIC,
+        EventStatus::Cancelled => Modifier::DIM | Modifier::CROSSED_OUT,
+    }
+}
+
+/// `status_modifier`, dimmed further when the user declined the event.
+pub fn event_modifier(status: &EventStatus, declined: bool) -> Modifier {
+    let modifier = status_modifier(status);
+    if declined { modifier | Modifier::DIM } else { modifier }
+}
+
+impl Theme {
+    /// Foreground for an event title: its own color when it has one.
+    pub fn event_color(&self, color: Option<&str>, fallback: Color) -> Color {
+        color.and_then(parse_hex_color).unwrap_or(fallback)
+    }
+}
+
 impl Default for Theme {
     fn default() -> Self {
         Self::default_theme()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_hex_colors() {
+        assert_eq!(parse_hex_color("#dc2127"), Some(Color::Rgb(0xdc, 0x21, 0x27)));
+        assert_eq!(parse_hex_color("dc2127"), None);
+        assert_eq!(parse_hex_color("#zzzzzz"), None);
+        assert_eq!(parse_hex_color("#fff"), None);
+    }
+
+    #[test]
+    fn status_modifier_marks_tentative_and_cancelled() {
+        assert_eq!(status_modifier(&EventStatus::Confirmed), Modifier::empty());
+        assert!(status_modifier(&EventStatus::Tentative).contains(Modifier::ITALIC));
+        assert!(status_modifier(&EventStatus::Cancelled).contains(Modifier::CROSSED_OUT));
+    }
+
+    #[test]
+    fn text_contrasts_with_the_background() {
+        assert_eq!(contrasting_text(Color::Rgb(230, 219, 116)), Color::Black);
+        assert_eq!(contrasting_text(Color::Rgb(14, 68, 41)), Color::White);
+        assert_eq!(contrasting_text(Color::Blue), Color::White);
+    }
+
+    #[test]
+    fn event_color_falls_back() {
+        let theme = Theme::default();
+
+        assert_eq!(theme.event_color(Some("#000000"), Color::White), Color::Rgb(0, 0, 0));
+        assert_eq!(theme.event_color(None, Color::White), Color::White);
+    }
+}
@@ -0,0 +1,68 @@
+use chrono::NaiveTime;
+
+/// How the TUI shows times of day (`ui.time_format`).
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum TimeFormat {
+    #[default]
+    TwentyFourHour,
+    TwelveHour,
+}
+
+impl TimeFormat {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.trim().to_lowercase().as_str() {
+            "24h" => Some(Self::TwentyFourHour),
+            "12h" => Some(Self::TwelveHour),
+            _ => None,
+        }
+    }
+
+    pub fn from_config(name: &str) -> Self {
+        Self::from_name(name).unwrap_or_else(|| {
+            tracing::warn!("Unknown time format '{}', using 24h", name);
+            Self::TwentyFourHour
+        })
+    }
+
+    /// "14:30" or "2:30pm".
+    pub fn format(self, time: NaiveTime) -> String {
+        match self {
+            Self::TwentyFourHour => time.format("%H:%M").to_string(),
+            Self::TwelveHour => time.format("%-I:%M%P").to_string(),
+        }
+    }
+
+    /// The widest `format` result, for lining times up.
+    pub fn width(self) -> usize {
+        match self {
+            Self::TwentyFourHour => 5,
+            Self::TwelveHour => 7,
+        }
+    }
+
+    /// A five-column hour label for time gutters: "09:00" or "  9am".
+    pub fn hour_label(self, hour: u32) -> String {
+        match self {
+            Self::TwentyFourHour => format!("{:02}:00", hour),
+            Self::TwelveHour => format!("{:>3}{}", (hour + 11) % 12 + 1, if hour < 12 { "am" } else { "pm" }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn twelve_hour_times_drop_the_leading_zero() {
+        let time = NaiveTime::from_hms_opt(14, 5, 0).unwrap();
+
+        assert_eq!(TimeFormat::TwentyFourHour.format(time), "14:05");
+        assert_eq!(TimeFormat::TwelveHour.format(time), "2:05pm");
+        assert_eq!(TimeFormat::TwelveHour.hour_label(0), " 12am");
+        assert_eq!(TimeFormat::TwelveHour.hour_label(12), " 12pm");
+        assert_eq!(TimeFormat::TwelveHour.hour_label(9), "  9am");
+        assert_eq!(TimeFormat::from_name("12H"), Some(TimeFormat::TwelveHour));
+        assert_eq!(TimeFormat::from_name("am/pm"), None);
+    }
+}
@@ -0,0 +1,97 @@
+/// Home-row letters the labels are made of.
+const HINT_CHARS: &[char] = &['a', 's', 'd', 'f', 'g', 'h', 'j', 'k', 'l'];
+
+/// A URL in the detail view and the label that opens it, drawn over the
+/// start of the URL at display row `line`, column `col`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct UrlHint {
+    pub label: String,
+    pub url: String,
+    pub line: usize,
+    pub col: usize,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum HintMatch {
+    /// The typed letters are a whole label.
+    Open(String),
+    /// More letters are needed.
+    Partial,
+    /// No label starts with the typed letters.
+    NoMatch,
+}
+
+/// Vimium-style `f` hints: every URL gets a label and typing one opens it.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct UrlHints {
+    pub hints: Vec<UrlHint>,
+    pub typed: String,
+}
+
+impl UrlHints {
+    /// Labels URLs given as (display row, column, url), in that order.
+    pub fn new(urls: Vec<(usize, usize, String)>) -> Self {
+        let labels = hint_labels(urls.len());
+        let hints = urls.into_iter()
+            .zip(labels)
+            .map(|((line, col, url), label)| UrlHint { label, url, line, col })
+            .collect();
+        Self { hints, typed: String::new() }
+    }
+
+    /// Hints still reachable with what has been typed so far.
+    pub fn visible(&self) -> impl Iterator<Item = &UrlHint> {
+        self.hints.iter().filter(|hint| hint.label.starts_with(&self.typed))
+    }
+
+    pub fn type_char(&mut self, c: char) -> HintMatch {
+        self.typed.push(c.to_ascii_lowercase());
+        if let Some(hint) = self.hints.iter().find(|hint| hint.label == self.typed) {
+            return HintMatch::Open(hint.url.clone());
+        }
+        if self.visible().next().is_some() { HintMatch::Partial } else { HintMatch::NoMatch }
+    }
+}
+
+/// One letter each while they go round, otherwise two letters each so no
+/// label is the start of another. Beyond 81 URLs the rest get no label.
+pub fn hint_labels(count: usize) -> Vec<String> {
+    if count <= HINT_CHARS.len() {
+        return HINT_CHARS.iter().take(count).map(|c| c.to_string()).collect();
+    }
+    HINT_CHARS.iter()
+        .flat_map(|first| HINT_CHARS.iter().map(move |second| format!("{}{}", first, second)))
+        .take(count)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn urls(count: usize) -> Vec<(usize, usize, String)> {
+        (0..count).map(|i| (i, 0, format!("https://example.com/{}", i))).collect()
+    }
+
+    #[test]
+    fn labels_are_single_letters_until_they_run_out() {
+        assert_eq!(hint_labels(3), vec!["a", "s", "d"]);
+
+        let labels = hint_labels(12);
+        assert_eq!(labels[..3], ["aa", "as", "ad"]);
+        assert_eq!(labels[11], "sd");
+        assert_eq!(hint_labels(100).len(), 81);
+    }
+
+    #[test]
+    fn typing_a_label_opens_its_url() {
+        let mut hints = UrlHints::new(urls(12));
+
+        assert_eq!(hints.type_char('S'), HintMatch::Partial);
+        assert_eq!(hints.visible().count(), 3);
+        assert_eq!(hints.type_char('a'), HintMatch::Open("https://example.com/9".to_string()));
+
+        let mut hints = UrlHints::new(urls(2));
+        assert_eq!(hints.type_char('x'), HintMatch::NoMatch);
+    }
+}
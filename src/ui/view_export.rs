@@ -0,0 +1,228 @@
+use ratatui::{
+    buffer::{Buffer, Cell},
+    style::{Color, Modifier},
+};
+use unicode_width::UnicodeWidthStr;
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum ViewExportFormat {
+    /// A standalone page with the view's colours, for printing.
+    #[default]
+    Html,
+    Text,
+}
+
+impl ViewExportFormat {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.trim().to_lowercase().as_str() {
+            "html" | "htm" => Some(Self::Html),
+            "text" | "txt" => Some(Self::Text),
+            _ => None,
+        }
+    }
+
+    pub fn extension(self) -> &'static str {
+        match self {
+            Self::Html => "html",
+            Self::Text => "txt",
+        }
+    }
+
+    /// Turns a view drawn off-screen into a file's contents.
+    pub fn render(self, buffer: &Buffer, title: &str) -> String {
+        match self {
+            Self::Html => buffer_to_html(buffer, title),
+            Self::Text => buffer_to_text(buffer),
+        }
+    }
+}
+
+/// The cells of each row, leaving out the blank cells that follow wide
+/// characters such as emoji.
+fn rows(buffer: &Buffer) -> Vec<Vec<&Cell>> {
+    let width = buffer.area.width as usize;
+    if width == 0 {
+        return Vec::new();
+    }
+    buffer.content
+        .chunks(width)
+        .map(|row| {
+            let mut cells = Vec::new();
+            let mut skip = 0;
+            for cell in row {
+                if skip > 0 {
+                    skip -= 1;
+                    continue;
+                }
+                skip = cell.symbol().width().saturating_sub(1);
+                cells.push(cell);
+            }
+            cells
+        })
+        .collect()
+}
+
+/// One line per row with trailing spaces trimmed.
+pub fn buffer_to_text(buffer: &Buffer) -> String {
+    let mut text: String = rows(buffer).iter()
+        .map(|row| row.iter().map(|cell| cell.symbol()).collect::<String>().trim_end().to_string())
+        .collect::<Vec<_>>()
+        .join("\n");
+    text.push('\n');
+    text
+}
+
+/// A page holding the rows in a `<pre>`, each run of equally styled cells
+/// in one `<span>`.
+pub fn buffer_to_html(buffer: &Buffer, title: &str) -> String {
+    let mut body = String::new();
+    for row in rows(buffer) {
+        let mut runs: Vec<(String, String)> = Vec::new();
+        for cell in row {
+            let style = css(cell);
+            match runs.last_mut() {
+                Some((text, last)) if *last == style => text.push_str(cell.symbol()),
+                _ => runs.push((cell.symbol().to_string(), style)),
+            }
+        }
+        if let Some((text, style)) = runs.last_mut()
+            && style.is_empty()
+        {
+            text.truncate(text.trim_end().len());
+        }
+        for (text, style) in runs {
+            if style.is_empty() {
+                body.push_str(&escape(&text));
+            } else {
+                body.push_str(&format!("<span style=\"{}\">{}</span>", style, escape(&text)));
+            }
+        }
+        body.push('\n');
+    }
+
+    format!(
+        concat!(
+            "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{title}</title>\n<style>\n",
+            "body {{ background: #000; color: #e5e5e5; }}\n",
+            "pre {{ font-family: monospace; line-height: 1.2; }}\n",
+            "* {{ print-color-adjust: exact; -webkit-print-color-adjust: exact; }}\n",
+            "</style>\n</head>\n<body>\n<pre>\n{body}</pre>\n</body>\n</html>\n",
+        ),
+        title = escape(title),
+        body = body,
+    )
+}
+
+fn css(cell: &Cell) -> String {
+    let mut rules = Vec::new();
+    if let Some(fg) = css_color(cell.fg) {
+        rules.push(format!("color: {}", fg));
+    }
+    if let Some(bg) = css_color(cell.bg) {
+        rules.push(format!("background: {}", bg));
+    }
+    if cell.modifier.contains(Modifier::BOLD) {
+        rules.push("font-weight: bold".to_string());
+    }
+    if cell.modifier.contains(Modifier::ITALIC) {
+        rules.push("font-style: italic".to_string());
+    }
+    match (cell.modifier.contains(Modifier::UNDERLINED), cell.modifier.contains(Modifier::CROSSED_OUT)) {
+        (true, true) => rules.push("text-decoration: underline line-through".to_string()),
+        (true, false) => rules.push("text-decoration: underline".to_string()),
+        (false, true) => rules.push("text-decoration: line-through".to_string()),
+        (false, false) => {}
+    }
+    if cell.modifier.contains(Modifier::DIM) {
+        rules.push("opacity: 0.6".to_string());
+    }
+    rules.join("; ")
+}
+
+/// xterm's palette for the named colours; the terminal default stays unset.
+fn css_color(color: Color) -> Option<String> {
+    let hex = match color {
+        Color::Reset => return None,
+        Color::Rgb(r, g, b) => return Some(format!("#{:02x}{:02x}{:02x}", r, g, b)),
+        Color::Indexed(index) => return Some(indexed_color(index)),
+        Color::Black => "#000000",
+        Color::Red => "#cd0000",
+        Color::Green => "#00cd00",
+        Color::Yellow => "#cdcd00",
+        Color::Blue => "#0000ee",
+        Color::Magenta => "#cd00cd",
+        Color::Cyan => "#00cdcd",
+        Color::Gray => "#e5e5e5",
+        Color::DarkGray => "#7f7f7f",
+        Color::LightRed => "#ff0000",
+        Color::LightGreen => "#00ff00",
+        Color::LightYellow => "#ffff00",
+        Color::LightBlue => "#5c5cff",
+        Color::LightMagenta => "#ff00ff",
+        Color::LightCyan => "#00ffff",
+        Color::White => "#ffffff",
+    };
+    Some(hex.to_string())
+}
+
+/// xterm's 256-colour palette: the 16 named colours, a 6x6x6 cube and a
+/// grey ramp.
+fn indexed_color(index: u8) -> String {
+    const NAMED: [Color; 16] = [
+        Color::Black, Color::Red, Color::Green, Color::Yellow, Color::Blue, Color::Magenta, Color::Cyan, Color::Gray,
+        Color::DarkGray, Color::LightRed, Color::LightGreen, Color::LightYellow, Color::LightBlue, Color::LightMagenta, Color::LightCyan, Color::White,
+    ];
+    let (r, g, b) = match index {
+        0..=15 => return css_color(NAMED[index as usize]).unwrap_or_default(),
+        16..=231 => {
+            let level = |n: u8| if n == 0 { 0 } else { 55 + n * 40 };
+            let n = index - 16;
+            (level(n / 36), level(n / 6 % 6), level(n % 6))
+        }
+        _ => {
+            let grey = 8 + (index - 232) * 10;
+            (grey, grey, grey)
+        }
+    };
+    format!("#{:02x}{:02x}{:02x}", r, g, b)
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::{layout::Rect, style::Style};
+
+    fn buffer() -> Buffer {
+        let mut buffer = Buffer::empty(Rect::new(0, 0, 12, 2));
+        buffer.set_string(0, 0, "📅 Standup", Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD));
+        buffer.set_string(0, 1, "<1:1> & co", Style::default());
+        buffer
+    }
+
+    #[test]
+    fn text_skips_the_cells_behind_wide_characters() {
+        assert_eq!(buffer_to_text(&buffer()), "📅 Standup\n<1:1> & co\n");
+    }
+
+    #[test]
+    fn html_keeps_styles_and_escapes_text() {
+        let html = buffer_to_html(&buffer(), "Week of <Jan 13>");
+
+        assert!(html.contains("<title>Week of &lt;Jan 13&gt;</title>"));
+        assert!(html.contains("<span style=\"color: #00cdcd; font-weight: bold\">📅 Standup</span>"));
+        assert!(html.contains("\n&lt;1:1&gt; &amp; co\n</pre>"));
+        assert_eq!(ViewExportFormat::from_name("TXT"), Some(ViewExportFormat::Text));
+    }
+
+    #[test]
+    fn indexed_colours_use_the_xterm_palette() {
+        assert_eq!(css_color(Color::Indexed(6)).as_deref(), Some("#00cdcd"));
+        assert_eq!(css_color(Color::Indexed(16)).as_deref(), Some("#000000"));
+        assert_eq!(css_color(Color::Indexed(208)).as_deref(), Some("#ff8700"));
+        assert_eq!(css_color(Color::Indexed(244)).as_deref(), Some("#808080"));
+    }
+}
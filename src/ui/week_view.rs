@@ -1,6 +1,12 @@
-use chrono::{Datelike, NaiveDate, Timelike};
+use chrono::{Datelike, NaiveDate, NaiveTime, Timelike, Weekday};
 use crate::app::AppState;
-use crate::calendar::Event;
+use crate::calendar::{DisplayTimezone, Event, EventStatus};
+
+pub const SLOTS_PER_HOUR: u32 = 2;
+pub const SLOT_MINUTES: u32 = 60 / SLOTS_PER_HOUR;
+pub const SLOTS_PER_DAY: u32 = 24 * SLOTS_PER_HOUR;
+pub const DEFAULT_SCROLL_HOUR: u32 = 7;
+pub const MAX_SCROLL_HOUR: u32 = 16;
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct WeekLayout {
@@ -13,13 +19,25 @@ pub struct DayColumn {
     pub date: NaiveDate,
     pub is_selected: bool,
     pub is_today: bool,
-    pub events: Vec<TimeSlot>,
+    pub now_slot: Option<u32>,
+    /// All-day and multi-day events, drawn as banner rows above the hour
+    /// grid, in row order.
+    pub all_day: Vec<BannerEntry>,
+    pub blocks: Vec<EventBlock>,
 }
 
 #[derive(Debug, Clone, PartialEq)]
-pub struct TimeSlot {
-    pub hour: u32,
-    pub events: Vec<EventBlock>,
+pub struct BannerEntry {
+    pub event_id: String,
+    pub title: String,
+    pub color: Option<String>,
+    /// Banner row; an event keeps the same row on every day it covers.
+    pub row: usize,
+    /// Days since the banner's first visible day this week.
+    pub span_offset: usize,
+    pub continues_before: bool,
+    pub continues_after: bool,
+    pub is_selected: bool,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -29,74 +47,265 @@ pub struct EventBlock {
     pub start_hour: u32,
     pub start_minute: u32,
     pub duration_minutes: i64,
+    pub start_slot: u32,
+    pub end_slot: u32,
+    pub lane: usize,
+    pub lane_count: usize,
+    pub color: Option<String>,
+    pub status: EventStatus,
+    pub declined: bool,
+    /// Minutes to get here from the previous event's location, when that
+    /// is less than the travel buffer.
+    pub travel_gap: Option<i64>,
+    /// Double-booked with another event that day.
+    pub overlapping: bool,
+    pub is_selected: bool,
+}
+
+impl EventBlock {
+    pub fn covers_slot(&self, slot: u32) -> bool {
+        slot >= self.start_slot && slot < self.end_slot
+    }
+}
+
+/// `ui.first_day_of_week`, e.g. "Sunday" or "mon".
+pub fn parse_first_day(name: &str) -> Option<Weekday> {
+    name.trim().parse().ok()
+}
+
+pub fn first_day_from_config(name: &str) -> Weekday {
+    parse_first_day(name).unwrap_or_else(|| {
+        tracing::warn!("Unknown first day of week '{}', using Monday", name);
+        Weekday::Mon
+    })
+}
+
+/// The column `weekday` falls in when weeks start on `first_day`.
+pub fn weekday_column(weekday: Weekday, first_day: Weekday) -> u32 {
+    (7 + weekday.num_days_from_monday() - first_day.num_days_from_monday()) % 7
+}
+
+/// The seven weekdays in column order.
+pub fn weekdays_from(first_day: Weekday) -> impl Iterator<Item = Weekday> {
+    std::iter::successors(Some(first_day), |day| Some(day.succ())).take(7)
 }
 
 impl WeekLayout {
     pub fn week_of_date(date: NaiveDate) -> NaiveDate {
-        let weekday = date.weekday();
-        let days_from_monday = weekday.num_days_from_monday() as u64;
-        date.checked_sub_days(chrono::Days::new(days_from_monday))
+        Self::week_of(date, Weekday::Mon)
+    }
+
+    /// The first day of the week `date` is in.
+    pub fn week_of(date: NaiveDate, first_day: Weekday) -> NaiveDate {
+        let days = weekday_column(date.weekday(), first_day) as u64;
+        date.checked_sub_days(chrono::Days::new(days))
             .unwrap_or(date)
     }
-}
 
-pub fn calculate_layout(state: &AppState) -> WeekLayout {
-    let week_start = WeekLayout::week_of_date(state.selected_date);
-    let today = chrono::Local::now().date_naive();
+    /// Whether the slot row starts inside the configured working hours.
+    pub fn is_working_slot(state: &AppState, slot: u32) -> bool {
+        NaiveTime::from_hms_opt(slot / SLOTS_PER_HOUR, (slot % SLOTS_PER_HOUR) * SLOT_MINUTES, 0)
+            .is_some_and(|time| state.working_hours.contains(time))
+    }
 
-    let mut days = Vec::new();
+    /// First slot row to draw so that `visible_rows` rows starting at
+    /// `scroll_hour` never run past the end of the day.
+    pub fn first_visible_slot(scroll_hour: u32, visible_rows: u32) -> u32 {
+        let max_start = SLOTS_PER_DAY.saturating_sub(visible_rows);
+        (scroll_hour * SLOTS_PER_HOUR).min(max_start)
+    }
+}
 
-    for day_offset in 0..7u64 {
-        let Some(date) = week_start.checked_add_days(chrono::Days::new(day_offset)) else {
-            continue;
-        };
+pub fn calculate_layout(state: &AppState) -> WeekLayout {
+    let week_start = WeekLayout::week_of(state.selected_date, state.week_start);
+    let now = state.local_now();
+    let today = now.date();
+    let now_slot = now.hour() * SLOTS_PER_HOUR + now.minute() / SLOT_MINUTES;
+    let selected_event_id = state.get_selected_event().map(|e| e.id.as_str());
+
+    let dates: Vec<NaiveDate> = (0..7u64)
+        .filter_map(|offset| week_start.checked_add_days(chrono::Days::new(offset)))
+        .collect();
+    let banners = place_banners(state, &dates);
+    let travel_gaps = state.travel_gaps(week_start, dates.last().copied().unwrap_or(week_start));
+
+    let days = dates.iter().map(|&date| {
         let events = state.get_events_for_date(date);
+        let timed: Vec<&Event> = events.into_iter()
+            .filter(|e| !is_banner(e, &state.timezone))
+            .collect();
+        let overlapping = state.overlapping_on(date);
+
+        let mut all_day: Vec<BannerEntry> = banners.iter()
+            .filter(|(_, first, last, _)| *first <= date && date <= *last)
+            .map(|&(event, first, last, row)| {
+                let visible_first = first.max(week_start);
+                BannerEntry {
+                    event_id: event.id.clone(),
+                    title: event.title.clone(),
+                    color: event.color.clone(),
+                    row,
+                    span_offset: (date - visible_first).num_days() as usize,
+                    continues_before: first < date,
+                    continues_after: date < last,
+                    is_selected: selected_event_id == Some(event.id.as_str()),
+                }
+            })
+            .collect();
+        all_day.sort_by_key(|b| b.row);
 
-        let time_slots = build_time_slots(&events);
-
-        days.push(DayColumn {
+        DayColumn {
             date,
             is_selected: date == state.selected_date,
             is_today: date == today,
-            events: time_slots,
-        });
-    }
+            now_slot: (date == today).then_some(now_slot),
+            all_day,
+            blocks: build_event_blocks(&timed, &state.timezone, selected_event_id)
+                .into_iter()
+                .map(|block| EventBlock {
+                    travel_gap: travel_gaps.get(&block.event_id).copied(),
+                    overlapping: overlapping.contains(&block.event_id),
+                    ..block
+                })
+                .collect(),
+        }
+    }).collect();
 
     WeekLayout { week_start, days }
 }
 
-fn build_time_slots(events: &[&Event]) -> Vec<TimeSlot> {
-    let mut slots = Vec::new();
+impl WeekLayout {
+    /// Banner rows needed for the busiest day, capped at `max_rows`.
+    pub fn banner_rows(&self, max_rows: usize) -> usize {
+        self.days.iter()
+            .flat_map(|d| d.all_day.iter().map(|b| b.row + 1))
+            .max()
+            .unwrap_or(0)
+            .min(max_rows)
+    }
+}
+
+/// All-day and multi-day events sit in the banner instead of the hour grid.
+pub fn is_banner(event: &Event, tz: &DisplayTimezone) -> bool {
+    event.all_day || event.spans_multiple_days(tz)
+}
+
+/// Banner events touching the week with their first and last day and the
+/// lowest row that is free on all of those days. Earlier and longer events
+/// get the top rows.
+fn place_banners<'a>(state: &'a AppState, dates: &[NaiveDate]) -> Vec<(&'a Event, NaiveDate, NaiveDate, usize)> {
+    let (Some(&week_first), Some(&week_last)) = (dates.first(), dates.last()) else {
+        return Vec::new();
+    };
+    let mut spans: Vec<(&Event, NaiveDate, NaiveDate)> = Vec::new();
+    for &date in dates {
+        for event in state.get_events_for_date(date) {
+            if is_banner(event, &state.timezone) && !spans.iter().any(|(e, _, _)| e.id == event.id) {
+                let (first, last) = event.local_dates(&state.timezone);
+                spans.push((event, first, last));
+            }
+        }
+    }
+    spans.sort_by_key(|&(e, first, last)| (first.max(week_first), std::cmp::Reverse(last), e.start));
+
+    let mut placed: Vec<(&Event, NaiveDate, NaiveDate, usize)> = Vec::new();
+    for (event, first, last) in spans {
+        let (from, to) = (first.max(week_first), last.min(week_last));
+        let row = (0..)
+            .find(|row| !placed.iter().any(|&(_, f, l, r)| {
+                r == *row && f.max(week_first) <= to && from <= l.min(week_last)
+            }))
+            .unwrap_or(0);
+        placed.push((event, first, last, row));
+    }
+    placed
+}
 
-    for hour in 0..24 {
-        let hour_events: Vec<EventBlock> = events
-            .iter()
-            .filter(|e| e.start.hour() == hour)
-            .map(|e| EventBlock {
+fn build_event_blocks(
+    events: &[&Event],
+    tz: &DisplayTimezone,
+    selected_event_id: Option<&str>,
+) -> Vec<EventBlock> {
+    let mut blocks: Vec<EventBlock> = events
+        .iter()
+        .map(|e| {
+            let start = e.local_start(tz);
+            let start_slot = start.hour() * SLOTS_PER_HOUR + start.minute() / SLOT_MINUTES;
+            let offset_minutes = (start.minute() % SLOT_MINUTES) as i64;
+            let span = (e.duration_minutes().max(1) + offset_minutes) as u32;
+            let end_slot = (start_slot + span.div_ceil(SLOT_MINUTES)).min(SLOTS_PER_DAY);
+            EventBlock {
                 event_id: e.id.clone(),
                 title: e.title.clone(),
-                start_hour: e.start.hour(),
-                start_minute: e.start.minute(),
+                start_hour: start.hour(),
+                start_minute: start.minute(),
                 duration_minutes: e.duration_minutes(),
-            })
-            .collect();
+                start_slot,
+                end_slot: end_slot.max(start_slot + 1),
+                lane: 0,
+                lane_count: 1,
+                color: e.color.clone(),
+                status: e.status.clone(),
+                declined: e.is_declined(),
+                travel_gap: None,
+                overlapping: false,
+                is_selected: selected_event_id == Some(e.id.as_str()),
+            }
+        })
+        .collect();
+
+    blocks.sort_by_key(|b| (b.start_slot, std::cmp::Reverse(b.end_slot)));
+    assign_lanes(&mut blocks);
+    blocks
+}
+
+/// Places overlapping blocks side by side. Blocks that transitively overlap form
+/// a cluster; every block in a cluster shares the cluster's lane count.
+fn assign_lanes(blocks: &mut [EventBlock]) {
+    let mut cluster_start = 0;
+    let mut cluster_end = 0;
+    let mut lane_ends: Vec<u32> = Vec::new();
+
+    for idx in 0..blocks.len() {
+        if blocks[idx].start_slot >= cluster_end && idx > cluster_start {
+            finish_cluster(&mut blocks[cluster_start..idx], lane_ends.len());
+            cluster_start = idx;
+            lane_ends.clear();
+        }
 
-        if !hour_events.is_empty() {
-            slots.push(TimeSlot {
-                hour,
-                events: hour_events,
-            });
+        let block = &mut blocks[idx];
+        match lane_ends.iter().position(|&end| end <= block.start_slot) {
+            Some(lane) => {
+                block.lane = lane;
+                lane_ends[lane] = block.end_slot;
+            }
+            None => {
+                block.lane = lane_ends.len();
+                lane_ends.push(block.end_slot);
+            }
         }
+        cluster_end = if idx == cluster_start {
+            block.end_slot
+        } else {
+            cluster_end.max(block.end_slot)
+        };
     }
 
-    slots
+    let lane_count = lane_ends.len();
+    finish_cluster(&mut blocks[cluster_start..], lane_count);
+}
+
+fn finish_cluster(cluster: &mut [EventBlock], lane_count: usize) {
+    for block in cluster {
+        block.lane_count = lane_count.max(1);
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use chrono::{Utc, Weekday};
-    use crate::calendar::{Event, EventStatus, DEFAULT_CALENDAR_ID};
+    use chrono::{TimeZone, Utc, Weekday};
+    use crate::calendar::{Event, Transparency, Visibility, DEFAULT_CALENDAR_ID};
 
     fn date(year: i32, month: u32, day: u32) -> NaiveDate {
         NaiveDate::from_ymd_opt(year, month, day).unwrap()
@@ -119,6 +328,17 @@ mod tests {
             status: EventStatus::Confirmed,
             last_modified: Utc::now(),
             html_link: None,
+            time_zone: None,
+            conference_url: None,
+            color_id: None,
+            color: None,
+            self_response: None,
+            attendee_responses: Default::default(),
+            visibility: Visibility::Default,
+            transparency: Transparency::Opaque,
+            etag: None,
+            organizer: None,
+            creator: None,
         }
     }
 
@@ -130,6 +350,28 @@ mod tests {
         assert_eq!(monday.weekday(), Weekday::Mon);
     }
 
+    #[test]
+    fn weeks_may_start_on_sunday() {
+        let wednesday = date(2025, 1, 15);
+        assert_eq!(WeekLayout::week_of(wednesday, Weekday::Sun), date(2025, 1, 12));
+        assert_eq!(WeekLayout::week_of(date(2025, 1, 12), Weekday::Sun), date(2025, 1, 12));
+        assert_eq!(weekday_column(Weekday::Sat, Weekday::Sun), 6);
+        assert_eq!(weekdays_from(Weekday::Sun).last(), Some(Weekday::Sat));
+        assert_eq!(parse_first_day("Sunday"), Some(Weekday::Sun));
+        assert_eq!(parse_first_day("someday"), None);
+    }
+
+    #[test]
+    fn working_slots_follow_working_hours() {
+        let mut state = AppState::new();
+        state.working_hours = crate::calendar::working_hours::WorkingHours::parse("8:30-17").unwrap();
+
+        assert!(!WeekLayout::is_working_slot(&state, 16));
+        assert!(WeekLayout::is_working_slot(&state, 17));
+        assert!(WeekLayout::is_working_slot(&state, 33));
+        assert!(!WeekLayout::is_working_slot(&state, 34));
+    }
+
     #[test]
     fn week_of_date_for_monday_returns_same_date() {
         let monday = date(2025, 1, 13);
@@ -190,28 +432,28 @@ mod tests {
     }
 
     #[test]
-    fn events_are_organized_by_hour() {
+    fn events_are_ordered_by_start() {
         let mut state = AppState::new();
         state.selected_date = date(2025, 1, 15);
 
         let event1 = create_event("e1", "Morning", date(2025, 1, 15), 9, 1);
         let event2 = create_event("e2", "Afternoon", date(2025, 1, 15), 14, 2);
 
-        state.add_event(event1);
         state.add_event(event2);
+        state.add_event(event1);
 
         let layout = calculate_layout(&state);
 
         let wednesday = &layout.days[2];
-        let time_slots = &wednesday.events;
+        let blocks = &wednesday.blocks;
 
-        assert_eq!(time_slots.len(), 2);
-        assert_eq!(time_slots[0].hour, 9);
-        assert_eq!(time_slots[1].hour, 14);
+        assert_eq!(blocks.len(), 2);
+        assert_eq!(blocks[0].start_hour, 9);
+        assert_eq!(blocks[1].start_hour, 14);
     }
 
     #[test]
-    fn event_block_includes_duration() {
+    fn event_block_spans_its_duration() {
         let mut state = AppState::new();
         state.selected_date = date(2025, 1, 15);
 
@@ -220,15 +462,17 @@ mod tests {
 
         let layout = calculate_layout(&state);
 
-        let wednesday = &layout.days[2];
-        let time_slot = &wednesday.events[0];
-        let event_block = &time_slot.events[0];
+        let block = &layout.days[2].blocks[0];
 
-        assert_eq!(event_block.duration_minutes, 120);
+        assert_eq!(block.duration_minutes, 120);
+        assert_eq!(block.start_slot, 10 * SLOTS_PER_HOUR);
+        assert_eq!(block.end_slot, 12 * SLOTS_PER_HOUR);
+        assert!(block.covers_slot(11 * SLOTS_PER_HOUR));
+        assert!(!block.covers_slot(12 * SLOTS_PER_HOUR));
     }
 
     #[test]
-    fn multiple_events_in_same_hour() {
+    fn overlapping_events_are_placed_side_by_side() {
         let mut state = AppState::new();
         state.selected_date = date(2025, 1, 15);
 
@@ -240,9 +484,140 @@ mod tests {
 
         let layout = calculate_layout(&state);
 
+        let blocks = &layout.days[2].blocks;
+        let mut lanes: Vec<usize> = blocks.iter().map(|b| b.lane).collect();
+        lanes.sort();
+
+        assert_eq!(lanes, vec![0, 1]);
+        assert!(blocks.iter().all(|b| b.lane_count == 2));
+    }
+
+    #[test]
+    fn lanes_are_reused_after_overlap_ends() {
+        let mut state = AppState::new();
+        state.selected_date = date(2025, 1, 15);
+
+        state.add_event(create_event("e1", "Long", date(2025, 1, 15), 9, 3));
+        state.add_event(create_event("e2", "Short", date(2025, 1, 15), 9, 1));
+        state.add_event(create_event("e3", "Later", date(2025, 1, 15), 10, 1));
+        state.add_event(create_event("e4", "Alone", date(2025, 1, 15), 15, 1));
+
+        let layout = calculate_layout(&state);
+        let blocks = &layout.days[2].blocks;
+        let lane_of = |id: &str| blocks.iter().find(|b| b.event_id == id).unwrap();
+
+        assert_eq!(lane_of("e1").lane, 0);
+        assert_eq!(lane_of("e2").lane, 1);
+        assert_eq!(lane_of("e3").lane, 1);
+        assert_eq!(lane_of("e3").lane_count, 2);
+        assert_eq!(lane_of("e4").lane_count, 1);
+    }
+
+    #[test]
+    fn selected_event_block_is_marked() {
+        let mut state = AppState::new();
+        state.selected_date = date(2025, 1, 15);
+        state.add_event(create_event("e1", "First", date(2025, 1, 15), 9, 1));
+        state.add_event(create_event("e2", "Second", date(2025, 1, 15), 11, 1));
+        state.add_event(create_event("e3", "Other day", date(2025, 1, 16), 11, 1));
+        state.selected_event_index = 1;
+
+        let layout = calculate_layout(&state);
+
+        let selected: Vec<_> = layout.days.iter()
+            .flat_map(|d| &d.blocks)
+            .filter(|b| b.is_selected)
+            .map(|b| b.event_id.as_str())
+            .collect();
+        assert_eq!(selected, vec!["e2"]);
+    }
+
+    #[test]
+    fn only_today_has_a_now_slot() {
+        let now = Utc.with_ymd_and_hms(2025, 1, 15, 12, 0, 0).unwrap();
+        let state = AppState::new().with_clock(crate::util::clock::FixedClock(now));
+
+        let layout = calculate_layout(&state);
+
+        let with_now: Vec<_> = layout.days.iter().filter(|d| d.now_slot.is_some()).collect();
+        assert_eq!(with_now.len(), 1);
+        assert!(with_now[0].is_today);
+    }
+
+    #[test]
+    fn first_visible_slot_clamps_to_end_of_day() {
+        assert_eq!(WeekLayout::first_visible_slot(7, 20), 7 * SLOTS_PER_HOUR);
+        assert_eq!(WeekLayout::first_visible_slot(20, 20), SLOTS_PER_DAY - 20);
+    }
+
+    #[test]
+    fn all_day_events_become_banners() {
+        let mut state = AppState::new();
+        state.selected_date = date(2025, 1, 15);
+        let mut holiday = create_event("h1", "Holiday", date(2025, 1, 15), 0, 24);
+        holiday.all_day = true;
+        state.add_event(holiday);
+        state.add_event(create_event("e1", "Meeting", date(2025, 1, 15), 9, 1));
+
+        let layout = calculate_layout(&state);
         let wednesday = &layout.days[2];
-        let time_slot = &wednesday.events[0];
 
-        assert_eq!(time_slot.events.len(), 2);
+        assert_eq!(wednesday.all_day.len(), 1);
+        assert_eq!(wednesday.blocks.len(), 1);
+        assert_eq!(layout.banner_rows(2), 1);
+    }
+
+    #[test]
+    fn multi_day_events_keep_one_banner_row_across_the_week() {
+        let mut state = AppState::new()
+            .with_timezone(DisplayTimezone::Named(chrono_tz::UTC));
+        state.selected_date = date(2025, 1, 15);
+        let mut conference = create_event("c1", "Conference", date(2025, 1, 14), 0, 72);
+        conference.all_day = true;
+        let mut holiday = create_event("h1", "Holiday", date(2025, 1, 13), 0, 24);
+        holiday.all_day = true;
+        state.add_event(conference);
+        state.add_event(holiday);
+        state.add_event(create_event("e1", "Overnight deploy", date(2025, 1, 17), 22, 4));
+
+        let layout = calculate_layout(&state);
+        let rows = |day: usize| -> Vec<(&str, usize)> {
+            layout.days[day].all_day.iter().map(|b| (b.event_id.as_str(), b.row)).collect()
+        };
+
+        assert_eq!(rows(0), vec![("h1", 0)]);
+        assert_eq!(rows(1), vec![("c1", 0)]);
+        assert_eq!(rows(3), vec![("c1", 0)]);
+        assert!(layout.days[1].blocks.is_empty());
+
+        let thursday = &layout.days[3].all_day[0];
+        assert_eq!(thursday.span_offset, 2);
+        assert!(thursday.continues_before);
+        assert!(!thursday.continues_after);
+
+        assert_eq!(rows(4), vec![("e1", 0)]);
+        assert_eq!(rows(5), vec![("e1", 0)]);
+        assert!(layout.days[4].blocks.is_empty());
+    }
+
+    #[test]
+    fn overlapping_banners_stack_into_rows() {
+        let mut state = AppState::new()
+            .with_timezone(DisplayTimezone::Named(chrono_tz::UTC));
+        state.selected_date = date(2025, 1, 15);
+        let mut trip = create_event("t1", "Trip", date(2025, 1, 13), 0, 24 * 5);
+        trip.all_day = true;
+        let mut holiday = create_event("h1", "Holiday", date(2025, 1, 15), 0, 24);
+        holiday.all_day = true;
+        state.add_event(trip);
+        state.add_event(holiday);
+
+        let layout = calculate_layout(&state);
+        let wednesday: Vec<(&str, usize)> = layout.days[2].all_day.iter()
+            .map(|b| (b.event_id.as_str(), b.row))
+            .collect();
+
+        assert_eq!(wednesday, vec![("t1", 0), ("h1", 1)]);
+        assert_eq!(layout.banner_rows(2), 2);
     }
 }
@@ -1,5 +1,6 @@
 use chrono::{Datelike, NaiveDate};
 use crate::app::AppState;
+use crate::ui::week_view::weekday_column;
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct YearLayout {
@@ -21,11 +22,25 @@ pub struct DayCell {
     pub is_today: bool,
     pub is_selected: bool,
     pub has_events: bool,
+    pub event_count: usize,
+    /// 0 for a free day, then 1-4 relative to the busiest day of the year.
+    pub heat: u8,
+}
+
+pub const HEAT_LEVELS: u8 = 4;
+
+/// GitHub-style buckets: the busiest day gets the top level and the others
+/// are scaled against it, rounding up so any event shows.
+pub fn heat_level(event_count: usize, busiest: usize) -> u8 {
+    if event_count == 0 || busiest == 0 {
+        return 0;
+    }
+    (event_count * HEAT_LEVELS as usize).div_ceil(busiest).min(HEAT_LEVELS as usize) as u8
 }
 
 pub fn calculate_layout(state: &AppState) -> YearLayout {
     let year = state.selected_date.year();
-    let today = chrono::Local::now().date_naive();
+    let today = state.today();
     let current_month = today.month();
     let current_year = today.year();
 
@@ -51,17 +66,19 @@ pub fn calculate_layout(state: &AppState) -> YearLayout {
             let Some(date) = NaiveDate::from_ymd_opt(year, month, day) else {
                 continue;
             };
-            let has_events = !state.get_events_for_date(date).is_empty();
+            let event_count = state.get_events_for_date(date).len();
 
             days.push(DayCell {
                 day,
                 is_today: date == today,
                 is_selected: date == state.selected_date,
-                has_events,
+                has_events: event_count > 0,
+                event_count,
+                heat: 0,
             });
         }
 
-        let first_weekday = first_day.weekday().num_days_from_monday();
+        let first_weekday = weekday_column(first_day.weekday(), state.week_start);
 
         months.push(MonthGrid {
             month,
@@ -71,12 +88,22 @@ pub fn calculate_layout(state: &AppState) -> YearLayout {
         });
     }
 
+    let busiest = months.iter()
+        .flat_map(|month| &month.days)
+        .map(|day| day.event_count)
+        .max()
+        .unwrap_or(0);
+    for day in months.iter_mut().flat_map(|month| &mut month.days) {
+        day.heat = heat_level(day.event_count, busiest);
+    }
+
     YearLayout { year, months }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_support::EventBuilder;
 
     fn date(year: i32, month: u32, day: u32) -> NaiveDate {
         NaiveDate::from_ymd_opt(year, month, day).unwrap()
@@ -128,4 +155,34 @@ mod tests {
         assert_eq!(selected_days.len(), 1);
         assert_eq!(selected_days[0].day, 15);
     }
+
+    #[test]
+    fn heat_is_relative_to_the_busiest_day() {
+        assert_eq!(heat_level(0, 8), 0);
+        assert_eq!(heat_level(1, 8), 1);
+        assert_eq!(heat_level(3, 8), 2);
+        assert_eq!(heat_level(8, 8), 4);
+        assert_eq!(heat_level(1, 1), 4);
+        assert_eq!(heat_level(0, 0), 0);
+    }
+
+    #[test]
+    fn days_are_shaded_by_event_count() {
+        let mut state = AppState::new();
+        state.selected_date = date(2025, 3, 1);
+        let event = |id: &str, day: u32| {
+            let start = date(2025, 3, day).and_hms_opt(9, 0, 0).unwrap().and_utc();
+            EventBuilder::new(id).title(id).at(start, chrono::Duration::hours(1)).build()
+        };
+        for (i, day) in [3, 3, 3, 3, 4].into_iter().enumerate() {
+            state.add_event(event(&format!("e{}", i), day));
+        }
+
+        let layout = calculate_layout(&state);
+
+        let march = &layout.months[2];
+        assert_eq!((march.days[2].event_count, march.days[2].heat), (4, 4));
+        assert_eq!((march.days[3].event_count, march.days[3].heat), (1, 1));
+        assert_eq!(march.days[4].heat, 0);
+    }
 }
@@ -0,0 +1,87 @@
+use std::io::{self, Write};
+use std::process::{Command, Output, Stdio};
+
+const SERVICE: &str = "gcal-imp";
+/// `errSecItemNotFound`, which `security` uses as its exit status.
+const MACOS_ITEM_NOT_FOUND: i32 = 44;
+
+/// Reads a secret from the OS keyring: the login keychain on macOS and the
+/// Secret Service (`secret-tool`) elsewhere. `None` only when nothing is
+/// stored; a locked keyring or a dismissed unlock prompt is an error, so
+/// callers never mistake it for a missing key.
+pub fn lookup(account: &str) -> io::Result<Option<String>> {
+    let mut command = match std::env::consts::OS {
+        "macos" => {
+            let mut command = Command::new("security");
+            command.args(["find-generic-password", "-s", SERVICE, "-a", account, "-w"]);
+            command
+        }
+        _ => {
+            let mut command = Command::new("secret-tool");
+            command.args(["lookup", "service", SERVICE, "account", account]);
+            command
+        }
+    };
+
+    let output = command.stdin(Stdio::null()).output()?;
+    let secret = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    if output.status.success() && !secret.is_empty() {
+        Ok(Some(secret))
+    } else if is_not_found(&output) {
+        Ok(None)
+    } else {
+        Err(command_error(&output))
+    }
+}
+
+/// `security` has a dedicated status for a missing item; `secret-tool`
+/// exits 1 without a word, and explains every other failure on stderr.
+fn is_not_found(output: &Output) -> bool {
+    match std::env::consts::OS {
+        "macos" => output.status.code() == Some(MACOS_ITEM_NOT_FOUND),
+        _ => output.status.code() == Some(1) && output.stdout.is_empty() && output.stderr.is_empty(),
+    }
+}
+
+fn command_error(output: &Output) -> io::Error {
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    match stderr.trim() {
+        "" => io::Error::other(format!("keyring command failed: {}", output.status)),
+        message => io::Error::other(format!("keyring command failed: {}: {}", output.status, message)),
+    }
+}
+
+/// Adds a secret that is not stored yet. On macOS an existing entry makes
+/// this fail rather than being replaced. The secret goes through stdin so it
+/// never shows up in `ps`.
+pub fn store(account: &str, secret: &str) -> io::Result<()> {
+    let (mut command, input) = match std::env::consts::OS {
+        "macos" => {
+            // A trailing `-w` prompts for the password and then asks again.
+            let mut command = Command::new("security");
+            command.args(["add-generic-password", "-s", SERVICE, "-a", account, "-w"]);
+            (command, format!("{secret}\n{secret}\n"))
+        }
+        _ => {
+            let mut command = Command::new("secret-tool");
+            command.args(["store", "--label", "gcal-imp data key", "service", SERVICE, "account", account]);
+            (command, secret.to_string())
+        }
+    };
+
+    let mut child = command
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()?;
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin.write_all(input.as_bytes())?;
+    }
+    let output = child.wait_with_output()?;
+
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(command_error(&output))
+    }
+}
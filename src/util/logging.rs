@@ -0,0 +1,86 @@
+use std::path::{Path, PathBuf};
+
+/// Daily files are named `gcal-imp.log.YYYY-MM-DD`.
+pub const LOG_FILE_PREFIX: &str = "gcal-imp.log";
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum LogDestination {
+    #[default]
+    File,
+    Stderr,
+}
+
+impl LogDestination {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "file" => Some(Self::File),
+            "stderr" => Some(Self::Stderr),
+            _ => None,
+        }
+    }
+
+    /// Logging is not set up yet when this runs, so a bad value goes to stderr.
+    pub fn from_config(value: Option<&str>) -> Self {
+        match value {
+            None => Self::default(),
+            Some(name) => Self::from_name(name).unwrap_or_else(|| {
+                eprintln!("Unknown logging.destination '{}', using file", name);
+                Self::default()
+            }),
+        }
+    }
+
+    /// The TUI draws on stderr's terminal, so while it runs log lines would
+    /// land in the calendar; it logs to the file instead.
+    pub fn for_mode(self, runs_tui: bool) -> Self {
+        if runs_tui && self == Self::Stderr {
+            eprintln!("logging.destination 'stderr' would draw over the calendar, logging to the file instead");
+            return Self::File;
+        }
+        self
+    }
+}
+
+/// The newest daily log file in `dir`, if there is one.
+pub fn latest_log_file(dir: &Path) -> Option<PathBuf> {
+    std::fs::read_dir(dir)
+        .ok()?
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(|name| name.starts_with(LOG_FILE_PREFIX))
+        })
+        .max()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn destination_falls_back_to_file() {
+        assert_eq!(LogDestination::from_config(Some("STDERR")), LogDestination::Stderr);
+        assert_eq!(LogDestination::from_config(Some("syslog")), LogDestination::File);
+        assert_eq!(LogDestination::from_config(None), LogDestination::File);
+    }
+
+    #[test]
+    fn the_tui_logs_to_the_file_instead_of_stderr() {
+        assert_eq!(LogDestination::Stderr.for_mode(true), LogDestination::File);
+        assert_eq!(LogDestination::Stderr.for_mode(false), LogDestination::Stderr);
+        assert_eq!(LogDestination::File.for_mode(true), LogDestination::File);
+    }
+
+    #[test]
+    fn latest_log_file_picks_the_newest_day() {
+        let dir = tempfile::tempdir().unwrap();
+        for name in ["gcal-imp.log.2025-01-14", "gcal-imp.log.2025-01-15", "config.toml"] {
+            std::fs::write(dir.path().join(name), "").unwrap();
+        }
+
+        assert_eq!(latest_log_file(dir.path()), Some(dir.path().join("gcal-imp.log.2025-01-15")));
+        assert_eq!(latest_log_file(&dir.path().join("missing")), None);
+    }
+}
@@ -0,0 +1,5 @@
+pub mod clock;
+pub mod keyring;
+pub mod logging;
+pub mod notify;
+pub mod opener;
@@ -0,0 +1,38 @@
+use std::io;
+use std::process::{Command, Stdio};
+
+pub fn send_notification(summary: &str, body: &str) -> io::Result<()> {
+    let mut command = match std::env::consts::OS {
+        "macos" => {
+            let script = format!(
+                "display notification {} with title {}",
+                applescript_string(body),
+                applescript_string(summary)
+            );
+            let mut command = Command::new("osascript");
+            command.args(["-e", &script]);
+            command
+        }
+        _ => {
+            let mut command = Command::new("notify-send");
+            command.args(["--app-name", "gcal-imp", summary, body]);
+            command
+        }
+    };
+
+    let status = command
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()?;
+
+    if status.success() {
+        Ok(())
+    } else {
+        Err(io::Error::other(format!("notification command failed: {}", status)))
+    }
+}
+
+fn applescript_string(text: &str) -> String {
+    format!("\"{}\"", text.replace('\\', "\\\\").replace('"', "\\\""))
+}
@@ -0,0 +1,69 @@
+use std::io;
+use std::process::{Command, Stdio};
+
+pub fn open_url(url: &str) -> io::Result<()> {
+    let browser = std::env::var("BROWSER").ok();
+    let args = opener_args(url, browser.as_deref(), std::env::consts::OS);
+    let (program, rest) = args.split_first()
+        .ok_or_else(|| io::Error::other("no URL opener available"))?;
+
+    tracing::info!("Opening {} with {}", url, program);
+    Command::new(program)
+        .args(rest)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .map(|_| ())
+}
+
+fn opener_args(url: &str, browser: Option<&str>, os: &str) -> Vec<String> {
+    if let Some(command) = browser
+        .and_then(|b| b.split(':').map(str::trim).find(|c| !c.is_empty()))
+    {
+        let mut args: Vec<String> = command.split_whitespace().map(String::from).collect();
+        if command.contains("%s") {
+            for arg in &mut args {
+                *arg = arg.replace("%s", url);
+            }
+        } else {
+            args.push(url.to_string());
+        }
+        return args;
+    }
+
+    match os {
+        "macos" => vec!["open".to_string(), url.to_string()],
+        "windows" => vec!["cmd".to_string(), "/C".to_string(), "start".to_string(), String::new(), url.to_string()],
+        _ => vec!["xdg-open".to_string(), url.to_string()],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const URL: &str = "https://example.com";
+
+    #[test]
+    fn uses_platform_opener() {
+        assert_eq!(opener_args(URL, None, "linux"), vec!["xdg-open", URL]);
+        assert_eq!(opener_args(URL, None, "macos"), vec!["open", URL]);
+        assert_eq!(opener_args(URL, None, "windows"), vec!["cmd", "/C", "start", "", URL]);
+    }
+
+    #[test]
+    fn browser_variable_takes_precedence() {
+        assert_eq!(opener_args(URL, Some("firefox --new-tab"), "linux"), vec!["firefox", "--new-tab", URL]);
+    }
+
+    #[test]
+    fn browser_variable_substitutes_placeholder() {
+        assert_eq!(opener_args(URL, Some("w3m %s:lynx"), "linux"), vec!["w3m", URL]);
+    }
+
+    #[test]
+    fn empty_browser_variable_is_ignored() {
+        assert_eq!(opener_args(URL, Some(""), "macos"), vec!["open", URL]);
+    }
+}